@@ -0,0 +1,70 @@
+//! Times the CPU frustum culling path: [`extract_frustum_planes`] once per
+//! frame, then [`AABB::is_visible`] for every sub-chunk within
+//! [`RENDER_DISTANCE`].
+//!
+//! ```text
+//! cargo bench --bench culling
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use glam::{Mat4, Vec3};
+use minerust::{
+    AABB, CHUNK_SIZE, NEAR_PLANE, NUM_SUBCHUNKS, RENDER_DISTANCE, SUBCHUNK_HEIGHT,
+    extract_frustum_planes, far_plane,
+};
+
+/// View-projection of a camera at spawn height looking slightly down along
+/// +X, as in the game.
+fn view_proj() -> Mat4 {
+    let eye = Vec3::new(0.0, 80.0, 0.0);
+    let view = Mat4::look_at_rh(eye, eye + Vec3::new(1.0, -0.2, 0.3), Vec3::Y);
+    let proj = Mat4::perspective_rh(
+        70f32.to_radians(),
+        16.0 / 9.0,
+        NEAR_PLANE,
+        far_plane(RENDER_DISTANCE),
+    );
+    proj * view
+}
+
+/// Bounds of every sub-chunk within [`RENDER_DISTANCE`] of the origin.
+fn subchunk_bounds() -> Vec<AABB> {
+    let mut boxes = Vec::new();
+    for cx in -RENDER_DISTANCE..=RENDER_DISTANCE {
+        for cz in -RENDER_DISTANCE..=RENDER_DISTANCE {
+            for sy in 0..NUM_SUBCHUNKS {
+                let min = Vec3::new(
+                    (cx * CHUNK_SIZE) as f32,
+                    (sy * SUBCHUNK_HEIGHT) as f32,
+                    (cz * CHUNK_SIZE) as f32,
+                );
+                let size = Vec3::new(CHUNK_SIZE as f32, SUBCHUNK_HEIGHT as f32, CHUNK_SIZE as f32);
+                boxes.push(AABB::new(min, min + size));
+            }
+        }
+    }
+    boxes
+}
+
+fn frustum_culling(c: &mut Criterion) {
+    let view_proj = view_proj();
+    c.bench_function("extract_frustum_planes", |b| {
+        b.iter(|| extract_frustum_planes(black_box(&view_proj)))
+    });
+
+    let planes = extract_frustum_planes(&view_proj);
+    let boxes = subchunk_bounds();
+    c.bench_function("is_visible_render_distance", |b| {
+        b.iter(|| {
+            black_box(&boxes)
+                .iter()
+                .filter(|aabb| aabb.is_visible(&planes))
+                .count()
+        })
+    });
+}
+
+criterion_group!(benches, frustum_culling);
+criterion_main!(benches);
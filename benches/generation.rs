@@ -0,0 +1,35 @@
+//! Times [`ChunkGenerator::generate_chunk`], the terrain, biome and
+//! structure pass every new chunk column goes through.
+//!
+//! ```text
+//! cargo bench --bench generation
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use minerust::ChunkGenerator;
+
+const SEED: u32 = 12345;
+
+fn generate_chunk(c: &mut Criterion) {
+    let generator = ChunkGenerator::new(SEED);
+
+    // Columns are cached nowhere, so regenerating the same one measures the
+    // full cost.  Spawn is typical land with trees nearby.
+    c.bench_function("generate_chunk", |b| {
+        b.iter(|| generator.generate_chunk(black_box(0), black_box(0)))
+    });
+
+    // A spread of columns covers other biomes and the ocean.
+    c.bench_function("generate_chunk_spread", |b| {
+        let mut next = 0;
+        b.iter(|| {
+            next = (next + 1) % 64;
+            generator.generate_chunk(black_box(next * 7 - 224), black_box(next * 13 - 416))
+        })
+    });
+}
+
+criterion_group!(benches, generate_chunk);
+criterion_main!(benches);
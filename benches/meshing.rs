@@ -0,0 +1,48 @@
+//! Times [`World::build_subchunk_mesh`] on the sub-chunk holding the
+//! surface at spawn, with every neighbor loaded, and the coarse mesh of
+//! [`World::build_coarse_subchunk_mesh`] for the same sub-chunk.
+//!
+//! ```text
+//! cargo bench --bench meshing
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use minerust::{ChunkGenerator, SUBCHUNK_HEIGHT, World};
+
+const SEED: u32 = 12345;
+
+/// The 3×3 chunk columns around the origin, so the center column is meshed
+/// against real neighbors rather than unloaded borders.
+fn spawn_world() -> World {
+    let generator = ChunkGenerator::new(SEED);
+    let mut world = World::new_empty_with_seed(SEED);
+    for cx in -1..=1 {
+        for cz in -1..=1 {
+            world
+                .chunks
+                .insert((cx, cz), generator.generate_chunk(cx, cz));
+        }
+    }
+    world
+}
+
+fn build_subchunk_mesh(c: &mut Criterion) {
+    let world = spawn_world();
+    // The surface sub-chunk has the most visible faces: grass, the upper
+    // dirt and stone layers, and any trees.
+    let surface_sy = world.get_terrain_height(8, 8) / SUBCHUNK_HEIGHT;
+
+    c.bench_function("build_subchunk_mesh", |b| {
+        b.iter(|| world.build_subchunk_mesh(black_box(0), black_box(0), black_box(surface_sy)))
+    });
+    c.bench_function("build_coarse_subchunk_mesh", |b| {
+        b.iter(|| {
+            world.build_coarse_subchunk_mesh(black_box(0), black_box(0), black_box(surface_sy))
+        })
+    });
+}
+
+criterion_group!(benches, build_subchunk_mesh);
+criterion_main!(benches);
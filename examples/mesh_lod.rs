@@ -0,0 +1,100 @@
+//! Counts the triangles of a generated world meshed at full detail and with
+//! the coarse mesh LOD beyond `MESH_LOD_DISTANCE`, showing what the LOD saves.
+//!
+//! ```text
+//! cargo run --release --example mesh_lod
+//! ```
+//!
+//! Every column within [`RENDER_DISTANCE`] of the origin is generated and
+//! meshed; columns next to the edge are meshed against generated neighbors
+//! so no border faces are counted.
+
+use std::thread;
+
+use minerust::{ChunkGenerator, MESH_LOD_DISTANCE, NUM_SUBCHUNKS, RENDER_DISTANCE, World};
+
+const SEED: u32 = 12345;
+
+/// Generates every column within `radius` of the origin, spread over the
+/// available cores.
+fn generate(radius: i32) -> World {
+    let columns: Vec<_> = (-radius..=radius)
+        .flat_map(|cx| (-radius..=radius).map(move |cz| (cx, cz)))
+        .collect();
+    let cores = thread::available_parallelism().map_or(1, |n| n.get());
+    let generator = ChunkGenerator::new(SEED);
+
+    let mut world = World::new_empty_with_seed(SEED);
+    thread::scope(|scope| {
+        let workers: Vec<_> = columns
+            .chunks(columns.len().div_ceil(cores))
+            .map(|batch| {
+                let generator = &generator;
+                scope.spawn(move || {
+                    batch
+                        .iter()
+                        .map(|&(cx, cz)| ((cx, cz), generator.generate_chunk(cx, cz)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        for worker in workers {
+            world.chunks.extend(worker.join().unwrap());
+        }
+    });
+    world
+}
+
+/// Triangles of every subchunk in the columns within [`RENDER_DISTANCE`],
+/// split into `(near, far)` by [`MESH_LOD_DISTANCE`].
+fn count_triangles(world: &World, lod: bool) -> (usize, usize) {
+    let mut near = 0;
+    let mut far = 0;
+    for cx in -RENDER_DISTANCE..=RENDER_DISTANCE {
+        for cz in -RENDER_DISTANCE..=RENDER_DISTANCE {
+            let mut triangles = 0;
+            for sy in 0..NUM_SUBCHUNKS {
+                let (terrain, translucent, water) = if lod {
+                    world.build_subchunk_mesh_at_lod(cx, cz, sy)
+                } else {
+                    world.build_subchunk_mesh(cx, cz, sy)
+                };
+                triangles += (terrain.1.len() + translucent.1.len() + water.1.len()) / 3;
+            }
+            if cx.abs().max(cz.abs()) >= MESH_LOD_DISTANCE {
+                far += triangles;
+            } else {
+                near += triangles;
+            }
+        }
+    }
+    (near, far)
+}
+
+fn main() {
+    let mut world = generate(RENDER_DISTANCE + 1);
+    println!(
+        "{} chunk columns, coarse from {} chunks out",
+        (2 * RENDER_DISTANCE + 1).pow(2),
+        MESH_LOD_DISTANCE
+    );
+
+    let (full_near, full_far) = count_triangles(&world, false);
+    world.update_mesh_lods(0, 0);
+    let (lod_near, lod_far) = count_triangles(&world, true);
+
+    let reduction = |full: usize, lod: usize| 100.0 * (1.0 - lod as f64 / full as f64);
+    println!("           full detail   with LOD   reduction");
+    println!(
+        "far        {:>11}   {:>8}   {:>8.1}%",
+        full_far,
+        lod_far,
+        reduction(full_far, lod_far)
+    );
+    println!(
+        "total      {:>11}   {:>8}   {:>8.1}%",
+        full_near + full_far,
+        lod_near + lod_far,
+        reduction(full_near + full_far, lod_near + lod_far)
+    );
+}
@@ -0,0 +1,104 @@
+//! Times chunk generation and mesh building with a single worker thread and
+//! with one worker per core, showing what the game's `--workers` flag buys.
+//!
+//! ```text
+//! cargo run --release --example worker_scaling
+//! ```
+//!
+//! Both loaders are handed the same square of chunk columns around the
+//! origin; the time until the last result comes back is reported.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use minerust::{ChunkLoader, MeshLoader, NUM_SUBCHUNKS, SEA_LEVEL, World, WorldGenConfig};
+use parking_lot::RwLock;
+
+const SEED: u32 = 12345;
+/// 15 × 15 = 225 columns, below the loaders' 256-request channel capacity.
+const RADIUS: i32 = 7;
+
+/// Generates every column within [`RADIUS`] and returns the elapsed time
+/// together with the filled world.
+fn generate(workers: usize) -> (Duration, World) {
+    let requests: Vec<_> = (-RADIUS..=RADIUS)
+        .flat_map(|cx| (-RADIUS..=RADIUS).map(move |cz| (cx, cz, cx * cx + cz * cz)))
+        .collect();
+    let mut loader =
+        ChunkLoader::with_worker_count(workers, SEED, SEA_LEVEL, WorldGenConfig::default());
+    let mut world = World::new_empty_with_seed(SEED);
+
+    let start = Instant::now();
+    loader.request_chunks(&requests);
+    while world.chunks.len() < requests.len() {
+        for result in loader.poll_all_results() {
+            world.chunks.insert((result.cx, result.cz), result.chunk);
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    let elapsed = start.elapsed();
+
+    loader.shutdown();
+    (elapsed, world)
+}
+
+/// Builds the mesh of every subchunk in `world`.
+fn mesh(world: &Arc<RwLock<World>>, workers: usize) -> Duration {
+    let mut queue: Vec<_> = world
+        .read()
+        .chunks
+        .keys()
+        .flat_map(|&(cx, cz)| (0..NUM_SUBCHUNKS).map(move |sy| (cx, cz, sy)))
+        .collect();
+    let total = queue.len();
+    let mut loader = MeshLoader::new(Arc::clone(world), workers);
+
+    let start = Instant::now();
+    let mut done = 0;
+    while done < total {
+        // Refill the request channel; `request_mesh` drops requests while
+        // it is full, which leaves the subchunk not pending.
+        while let Some(&(cx, cz, sy)) = queue.last() {
+            loader.request_mesh(cx, cz, sy);
+            if !loader.is_pending(cx, cz, sy) {
+                break;
+            }
+            queue.pop();
+        }
+        while loader.poll_result().is_some() {
+            done += 1;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    let elapsed = start.elapsed();
+
+    loader.shutdown();
+    elapsed
+}
+
+fn main() {
+    let cores = thread::available_parallelism().map_or(1, |n| n.get());
+    println!(
+        "{} chunk columns, {} cores available",
+        (2 * RADIUS + 1).pow(2),
+        cores
+    );
+    println!("workers   generation   meshing");
+
+    let mut counts = vec![1];
+    if cores > 1 {
+        counts.push(cores);
+    }
+    for workers in counts {
+        let (generation, world) = generate(workers);
+        let world = Arc::new(RwLock::new(world));
+        let meshing = mesh(&world, workers);
+        println!(
+            "{:>7}   {:>8.0} ms   {:>6.0} ms",
+            workers,
+            generation.as_secs_f64() * 1000.0,
+            meshing.as_secs_f64() * 1000.0
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use minerust::Biome;
+
+/// Time constant of the blend towards a new biome's atmosphere, in seconds.
+/// After this long about two thirds of the change has happened, so crossing
+/// a border fades in over a few seconds instead of snapping.
+const BLEND_SECONDS: f32 = 1.5;
+
+/// Biome-dependent look of the air around the player: a tint on the
+/// ambient light and the color and strength of the distance haze.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Atmosphere {
+    /// Multiplier on the ambient light color.
+    pub ambient_tint: [f32; 3],
+    /// Daylight color of the haze, darkened with the ambient light at night.
+    pub fog_color: [f32; 3],
+    /// Haze covering terrain at the edge of the render distance.
+    pub fog_strength: f32,
+}
+
+impl Atmosphere {
+    /// The atmosphere of `biome`, as reached after standing in it a while.
+    pub fn of(biome: Biome) -> Self {
+        Self {
+            ambient_tint: biome.ambient_tint(),
+            fog_color: biome.fog_color(),
+            fog_strength: biome.fog_strength(),
+        }
+    }
+
+    /// Moves this atmosphere part of the way towards `target` for a frame
+    /// of `dt` seconds.  The step does not depend on the frame rate.
+    pub fn blend_towards(&mut self, target: Atmosphere, dt: f32) {
+        let t = 1.0 - (-dt / BLEND_SECONDS).exp();
+        for i in 0..3 {
+            self.ambient_tint[i] += (target.ambient_tint[i] - self.ambient_tint[i]) * t;
+            self.fog_color[i] += (target.fog_color[i] - self.fog_color[i]) * t;
+        }
+        self.fog_strength += (target.fog_strength - self.fog_strength) * t;
+    }
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Self::of(Biome::Plains)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crossing_a_border_fades_instead_of_snapping() {
+        let mut atmosphere = Atmosphere::of(Biome::Forest);
+        let desert = Atmosphere::of(Biome::Desert);
+
+        atmosphere.blend_towards(desert, 1.0 / 60.0);
+        let step = atmosphere.fog_strength - Biome::Forest.fog_strength();
+        assert!(step > 0.0 && step < 0.01, "{}", step);
+
+        for _ in 0..60 * 10 {
+            atmosphere.blend_towards(desert, 1.0 / 60.0);
+        }
+        assert!((atmosphere.fog_strength - desert.fog_strength).abs() < 1e-3);
+        assert!((atmosphere.ambient_tint[2] - desert.ambient_tint[2]).abs() < 1e-3);
+    }
+
+    #[test]
+    fn blend_does_not_depend_on_frame_rate() {
+        let mut slow = Atmosphere::of(Biome::Plains);
+        let mut fast = slow;
+        let swamp = Atmosphere::of(Biome::Swamp);
+        for _ in 0..30 {
+            slow.blend_towards(swamp, 1.0 / 30.0);
+        }
+        for _ in 0..120 {
+            fast.blend_towards(swamp, 1.0 / 120.0);
+        }
+        assert!((slow.fog_color[0] - fast.fog_color[0]).abs() < 1e-4);
+    }
+}
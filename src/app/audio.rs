@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Cursor;
+
+use glam::Vec2;
+use minerust::BlockType;
+use rodio::source::Buffered;
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Source};
+
+use crate::logger::{LogLevel, log};
+
+use super::settings::DEFAULT_VOLUME;
+use super::state::State;
+
+/// Directory the block sounds are loaded from, relative to the working
+/// directory like the texture atlas.
+const SOUNDS_DIR: &str = "assets/sounds";
+
+/// Blocks walked between two footsteps.
+const FOOTSTEP_DISTANCE: f32 = 1.7;
+
+/// Horizontal speed, in blocks per second, below which the player counts as
+/// standing still and no footsteps play.
+const FOOTSTEP_MIN_SPEED: f32 = 0.5;
+
+/// Each sound is played between these speeds (and so pitches), so repeated
+/// footsteps do not all sound the same.
+const PITCH_RANGE: std::ops::Range<f32> = 0.9..1.1;
+
+/// Material family that decides which samples a block plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SoundGroup {
+    Stone,
+    Grass,
+    Wood,
+}
+
+impl SoundGroup {
+    const ALL: [SoundGroup; 3] = [SoundGroup::Stone, SoundGroup::Grass, SoundGroup::Wood];
+
+    /// The group `block` sounds like, or `None` for blocks without sounds
+    /// (air and water).
+    fn of(block: BlockType) -> Option<Self> {
+        match block {
+            BlockType::Air | BlockType::Water => None,
+            BlockType::Stone
+            | BlockType::Bedrock
+            | BlockType::Ice
+            | BlockType::StoneSlab
+            | BlockType::StoneStairs
+            | BlockType::Cobblestone
+            | BlockType::Glass => Some(SoundGroup::Stone),
+            BlockType::Wood | BlockType::WoodStairs | BlockType::Bed | BlockType::Torch => {
+                Some(SoundGroup::Wood)
+            }
+            BlockType::Grass
+            | BlockType::Dirt
+            | BlockType::Sand
+            | BlockType::Leaves
+            | BlockType::Snow
+            | BlockType::Gravel
+            | BlockType::Clay
+            | BlockType::Cactus
+            | BlockType::DeadBush
+            | BlockType::SnowLayer => Some(SoundGroup::Grass),
+        }
+    }
+
+    /// Prefix of the group's files in [`SOUNDS_DIR`].
+    fn name(self) -> &'static str {
+        match self {
+            SoundGroup::Stone => "stone",
+            SoundGroup::Grass => "grass",
+            SoundGroup::Wood => "wood",
+        }
+    }
+}
+
+/// What a block sound is played for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockSound {
+    /// The block was broken.
+    Break,
+    /// The block was placed.
+    Place,
+    /// The player stepped on the block.
+    Step,
+}
+
+impl BlockSound {
+    const ALL: [BlockSound; 3] = [BlockSound::Break, BlockSound::Place, BlockSound::Step];
+
+    /// Suffix of the sample file; breaking and placing share the dig sample.
+    fn file_suffix(self) -> &'static str {
+        match self {
+            BlockSound::Break | BlockSound::Place => "dig",
+            BlockSound::Step => "step",
+        }
+    }
+
+    /// Volume relative to the master volume, keeping footsteps in the
+    /// background.
+    fn gain(self) -> f32 {
+        match self {
+            BlockSound::Break => 1.0,
+            BlockSound::Place => 0.8,
+            BlockSound::Step => 0.35,
+        }
+    }
+}
+
+/// A decoded sample; cloning it shares the decoded data.
+type Sample = Buffered<Decoder<Cursor<Vec<u8>>>>;
+
+/// The audio output device and the block sounds loaded at startup.
+pub struct AudioOutput {
+    /// Keeps the device open; every sound stops when it is dropped.
+    _stream: OutputStream,
+    handle: OutputStreamHandle,
+    samples: HashMap<(SoundGroup, BlockSound), Sample>,
+    /// Master volume in `0.0..=1.0`; 0 silences every sound.
+    pub volume: f32,
+    /// Blocks walked on the ground since the last footstep.
+    step_distance: f32,
+}
+
+impl AudioOutput {
+    /// Opens the default output device and loads every sample in
+    /// [`SOUNDS_DIR`].
+    ///
+    /// # Returns
+    /// `None` (after logging the reason) if there is no usable output
+    /// device, in which case the game runs silently.  A missing or broken
+    /// sample is logged and only silences that sound.
+    pub fn new() -> Option<Self> {
+        let (stream, handle) = match OutputStream::try_default() {
+            Ok(output) => output,
+            Err(e) => {
+                log(LogLevel::Warning, &format!("Audio unavailable: {}", e));
+                return None;
+            }
+        };
+
+        let mut samples = HashMap::new();
+        for group in SoundGroup::ALL {
+            for sound in BlockSound::ALL {
+                let path = format!(
+                    "{}/{}_{}.wav",
+                    SOUNDS_DIR,
+                    group.name(),
+                    sound.file_suffix()
+                );
+                match load_sample(&path) {
+                    Ok(sample) => {
+                        samples.insert((group, sound), sample);
+                    }
+                    Err(e) => log(
+                        LogLevel::Warning,
+                        &format!("Failed to load {}: {}", path, e),
+                    ),
+                }
+            }
+        }
+
+        Some(Self {
+            _stream: stream,
+            handle,
+            samples,
+            volume: DEFAULT_VOLUME,
+            step_distance: 0.0,
+        })
+    }
+
+    /// Starts playing the `sound` of `block`; returns immediately.
+    fn play(&self, block: BlockType, sound: BlockSound) {
+        if self.volume <= 0.0 {
+            return;
+        }
+        let Some(sample) =
+            SoundGroup::of(block).and_then(|group| self.samples.get(&(group, sound)))
+        else {
+            return;
+        };
+        let source = sample
+            .clone()
+            .speed(rand::random_range(PITCH_RANGE))
+            .amplify(self.volume * sound.gain())
+            .convert_samples::<f32>();
+        if let Err(e) = self.handle.play_raw(source) {
+            log(LogLevel::Debug, &format!("Failed to play a sound: {}", e));
+        }
+    }
+}
+
+/// Reads and decodes one sample file.
+fn load_sample(path: &str) -> Result<Sample, String> {
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    let decoder = Decoder::new(Cursor::new(bytes)).map_err(|e| e.to_string())?;
+    Ok(decoder.buffered())
+}
+
+/// Parses the `--volume` value, accepting `0.0..=1.0`.
+pub fn parse_volume(value: &str) -> Result<f32, String> {
+    let volume: f32 = value
+        .parse()
+        .map_err(|_| format!("`{}` is not a number", value))?;
+    if (0.0..=1.0).contains(&volume) {
+        Ok(volume)
+    } else {
+        Err(format!("{} is not between 0 and 1", volume))
+    }
+}
+
+impl State {
+    /// Plays the `sound` of `block`, if audio is available.
+    pub fn play_block_sound(&self, block: BlockType, sound: BlockSound) {
+        if let Some(audio) = &self.audio {
+            audio.play(block, sound);
+        }
+    }
+
+    /// Plays a footstep every [`FOOTSTEP_DISTANCE`] blocks walked on the
+    /// ground, sounding like the block underfoot.  Called once per frame
+    /// from `update`, after the camera has moved.
+    pub fn update_footsteps(&mut self, dt: f32) {
+        let Some(audio) = &mut self.audio else {
+            return;
+        };
+        let camera = &self.camera;
+        let speed = Vec2::new(camera.velocity.x, camera.velocity.z).length();
+        if !camera.on_ground || camera.in_water || speed < FOOTSTEP_MIN_SPEED {
+            // The first step after standing still comes a full stride later.
+            audio.step_distance = 0.0;
+            return;
+        }
+
+        audio.step_distance += speed * dt;
+        if audio.step_distance < FOOTSTEP_DISTANCE {
+            return;
+        }
+        audio.step_distance -= FOOTSTEP_DISTANCE;
+
+        // The block just below the feet; `position` is the bottom of the
+        // player's box.
+        let feet = camera.position;
+        let ground = self.world.read().get_block(
+            feet.x.floor() as i32,
+            (feet.y - 0.05).floor() as i32,
+            feet.z.floor() as i32,
+        );
+        audio.play(ground, BlockSound::Step);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_sound_group_has_its_samples() {
+        for group in SoundGroup::ALL {
+            for sound in BlockSound::ALL {
+                let path = format!(
+                    "{}/{}_{}.wav",
+                    SOUNDS_DIR,
+                    group.name(),
+                    sound.file_suffix()
+                );
+                assert!(load_sample(&path).is_ok(), "{} should decode", path);
+            }
+        }
+    }
+
+    #[test]
+    fn volume_flag_rejects_values_outside_zero_to_one() {
+        assert_eq!(parse_volume("0.5"), Ok(0.5));
+        assert!(parse_volume("1.5").is_err());
+        assert!(parse_volume("-0.1").is_err());
+        assert!(parse_volume("loud").is_err());
+    }
+}
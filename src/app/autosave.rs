@@ -0,0 +1,133 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use minerust::{DEFAULT_WORLD_FILE, SavedWorld, save_world};
+
+use crate::logger::{LogLevel, log};
+use crate::ui::menu::GameState;
+
+use super::state::State;
+
+/// Time between automatic saves of a singleplayer world.
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(180);
+
+/// Minimum time the "Saving..." indicator stays up, so a save that finishes
+/// within a frame is still noticeable.
+const SAVING_INDICATOR_TIME: Duration = Duration::from_millis(1500);
+
+impl State {
+    /// Captures the modified chunks and the player state for writing.
+    ///
+    /// Only the copy happens here, under a brief read lock;
+    /// `SavedWorld::from_world` skips every chunk without player edits, so
+    /// the terrain itself is regenerated from the seed on load.
+    fn saved_world(&self) -> SavedWorld {
+        let world = self.world.read();
+        // A spectating player is saved where their body is.
+        let body = self.camera.body_position();
+        SavedWorld::from_world(
+            &world,
+            (body.x, body.y, body.z),
+            (self.camera.yaw, self.camera.pitch),
+            self.camera.creative,
+            &self.inventory,
+            self.time_of_day,
+            self.time_scale,
+        )
+    }
+
+    /// Whether the autosave and the save on quit apply: a singleplayer world
+    /// has been entered, whether it is being played or paused in the menu.
+    /// The world behind the menu at launch is not saved before the player
+    /// enters it, so quitting from the title menu does not overwrite the
+    /// save file.
+    pub fn autosave_enabled(&self) -> bool {
+        self.world_entered && self.network_tx.is_none()
+    }
+
+    /// Writes the world to [`DEFAULT_WORLD_FILE`] on a background thread.
+    ///
+    /// The snapshot is taken immediately; serializing and writing it happen
+    /// off the main thread so large worlds do not hitch the frame.  Ignored
+    /// while a previous save is still being written.
+    pub fn save_world_in_background(&mut self) {
+        if self.world_save.is_some() {
+            return;
+        }
+        let saved = self.saved_world();
+        let spawned = thread::Builder::new()
+            .name("world-save".to_string())
+            .spawn(move || save_world(DEFAULT_WORLD_FILE, &saved));
+        match spawned {
+            Ok(handle) => {
+                self.world_save = Some(handle);
+                self.last_world_save = Instant::now();
+                self.saving_indicator_until = Instant::now() + SAVING_INDICATOR_TIME;
+            }
+            Err(e) => log(
+                LogLevel::Error,
+                &format!("Failed to start saving the world: {}", e),
+            ),
+        }
+    }
+
+    /// Writes the world to [`DEFAULT_WORLD_FILE`] and waits for it, after
+    /// letting any background save finish first.  Used when the window
+    /// closes, where nothing is left to hitch.
+    pub fn save_world_blocking(&mut self) {
+        self.wait_for_world_save();
+        report_world_save(Ok(save_world(DEFAULT_WORLD_FILE, &self.saved_world())));
+        self.last_world_save = Instant::now();
+    }
+
+    /// Blocks until a running background save has been written, so the
+    /// save file can be read or rewritten safely.
+    pub fn wait_for_world_save(&mut self) {
+        if let Some(handle) = self.world_save.take() {
+            report_world_save(handle.join());
+        }
+    }
+
+    /// Reports a finished background save and starts the next autosave once
+    /// [`AUTOSAVE_INTERVAL`] has passed.  Called once per frame from
+    /// `update`.
+    pub fn update_autosave(&mut self) {
+        if self.game_state == GameState::Playing && self.network_tx.is_none() {
+            self.world_entered = true;
+        }
+        if self
+            .world_save
+            .as_ref()
+            .is_some_and(thread::JoinHandle::is_finished)
+            && let Some(handle) = self.world_save.take()
+        {
+            report_world_save(handle.join());
+        }
+
+        if !self.autosave_enabled() || self.game_state != GameState::Playing {
+            // Playing time only counts towards the next autosave.
+            self.last_world_save = Instant::now();
+        } else if self.last_world_save.elapsed() >= AUTOSAVE_INTERVAL {
+            log(LogLevel::Debug, "Autosaving world");
+            self.save_world_in_background();
+        }
+    }
+
+    /// Whether the "Saving..." indicator is shown this frame.
+    pub fn saving_indicator_visible(&self) -> bool {
+        self.world_save.is_some() || Instant::now() < self.saving_indicator_until
+    }
+}
+
+/// Logs the outcome of a save, whether it returned an error or its thread
+/// panicked.
+fn report_world_save(result: thread::Result<Result<(), String>>) {
+    match result {
+        Ok(Ok(())) => log(
+            LogLevel::Info,
+            &format!("World saved to {}", DEFAULT_WORLD_FILE),
+        ),
+        Ok(Err(e)) => log(LogLevel::Error, &format!("Failed to save world: {}", e)),
+        Err(_) => log(LogLevel::Error, "World save thread panicked"),
+    }
+}
@@ -0,0 +1,300 @@
+use minerust::{BLOOM_INTENSITY, BLOOM_KNEE, BLOOM_THRESHOLD, BloomConfig};
+
+use crate::logger::{LogLevel, log};
+
+use super::gpu_timer::GpuPhase;
+use super::state::State;
+
+/// Format of every color target the scene is drawn into before the
+/// composite pass (MSAA target, SSR color, scene color, bloom chain).
+///
+/// Floating point so the sun and emissive surfaces can exceed 1.0 and be
+/// picked up by the bloom bright-pass; the composite pass clamps the result
+/// when it writes to the swap-chain.
+pub const HDR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// Returns the bloom settings buffer contents for the given toggle state.
+pub fn bloom_config(enabled: bool) -> BloomConfig {
+    BloomConfig {
+        threshold: BLOOM_THRESHOLD,
+        knee: BLOOM_KNEE,
+        intensity: if enabled { BLOOM_INTENSITY } else { 0.0 },
+        _pad: 0.0,
+    }
+}
+
+/// Resolution-independent bloom resources: the four full-screen pipelines
+/// from `bloom.wgsl` and the layout they share.
+pub struct BloomPipelines {
+    /// Layout of every bloom pass bind group: source texture, sampler and
+    /// the bloom settings buffer.
+    pub bind_group_layout: wgpu::BindGroupLayout,
+    /// Bilinear clamp sampler; the passes rely on filtering for their taps.
+    pub sampler: wgpu::Sampler,
+    /// Scene color → half resolution, keeping only pixels above the threshold.
+    pub prefilter: wgpu::RenderPipeline,
+    /// Half → quarter resolution box downsample.
+    pub downsample: wgpu::RenderPipeline,
+    /// Horizontal Gaussian blur at quarter resolution.
+    pub blur_h: wgpu::RenderPipeline,
+    /// Vertical Gaussian blur at quarter resolution.
+    pub blur_v: wgpu::RenderPipeline,
+}
+
+impl BloomPipelines {
+    /// Compiles `bloom.wgsl` and builds the four pass pipelines.
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Bloom Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/bloom.wgsl").into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Bloom Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Bloom Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            immediate_size: 0,
+        });
+
+        let create_pipeline = |label: &str, entry_point: &str| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                cache: None,
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    compilation_options: Default::default(),
+                    buffers: &[], // full-screen triangle from vertex index
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    compilation_options: Default::default(),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: HDR_FORMAT,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+            })
+        };
+
+        Self {
+            prefilter: create_pipeline("Bloom Prefilter Pipeline", "fs_prefilter"),
+            downsample: create_pipeline("Bloom Downsample Pipeline", "fs_downsample"),
+            blur_h: create_pipeline("Bloom Horizontal Blur Pipeline", "fs_blur_h"),
+            blur_v: create_pipeline("Bloom Vertical Blur Pipeline", "fs_blur_v"),
+            sampler: device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some("Bloom Sampler"),
+                address_mode_u: wgpu::AddressMode::ClampToEdge,
+                address_mode_v: wgpu::AddressMode::ClampToEdge,
+                mag_filter: wgpu::FilterMode::Linear,
+                min_filter: wgpu::FilterMode::Linear,
+                ..Default::default()
+            }),
+            bind_group_layout,
+        }
+    }
+}
+
+/// Size-dependent bloom textures and the bind groups that read them.
+///
+/// Rebuilt on every resize because the first pass reads `scene_color_view`,
+/// which is recreated along with the window.
+pub struct BloomTargets {
+    half_view: wgpu::TextureView,
+    quarter_view: wgpu::TextureView,
+    scratch_view: wgpu::TextureView,
+    prefilter_bind_group: wgpu::BindGroup,
+    downsample_bind_group: wgpu::BindGroup,
+    blur_h_bind_group: wgpu::BindGroup,
+    blur_v_bind_group: wgpu::BindGroup,
+}
+
+impl BloomTargets {
+    /// Allocates the half- and quarter-resolution targets for a
+    /// `width`×`height` scene.
+    pub fn new(
+        device: &wgpu::Device,
+        pipelines: &BloomPipelines,
+        config_buffer: &wgpu::Buffer,
+        scene_color_view: &wgpu::TextureView,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let create_view = |label: &str, divisor: u32| {
+            device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some(label),
+                    size: wgpu::Extent3d {
+                        width: (width / divisor).max(1),
+                        height: (height / divisor).max(1),
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: HDR_FORMAT,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                        | wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        };
+        let half_view = create_view("Bloom Half Texture", 2);
+        let quarter_view = create_view("Bloom Quarter Texture", 4);
+        let scratch_view = create_view("Bloom Blur Scratch Texture", 4);
+
+        let create_bind_group = |label: &str, source: &wgpu::TextureView| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &pipelines.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(source),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&pipelines.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: config_buffer.as_entire_binding(),
+                    },
+                ],
+            })
+        };
+
+        Self {
+            prefilter_bind_group: create_bind_group("Bloom Prefilter Bind Group", scene_color_view),
+            downsample_bind_group: create_bind_group("Bloom Downsample Bind Group", &half_view),
+            blur_h_bind_group: create_bind_group("Bloom Horizontal Blur Bind Group", &quarter_view),
+            blur_v_bind_group: create_bind_group("Bloom Vertical Blur Bind Group", &scratch_view),
+            half_view,
+            quarter_view,
+            scratch_view,
+        }
+    }
+
+    /// The final blurred glow, sampled by the composite pass.
+    pub fn output_view(&self) -> &wgpu::TextureView {
+        &self.quarter_view
+    }
+}
+
+impl State {
+    /// Turns the bloom post-process on or off (B key).
+    ///
+    /// The composite shader skips the glow when the intensity in the bloom
+    /// settings buffer is zero, and `render` skips the bloom passes.
+    pub fn set_bloom_enabled(&mut self, enabled: bool) {
+        self.bloom_enabled = enabled;
+        self.queue.write_buffer(
+            &self.bloom_config_buffer,
+            0,
+            bytemuck::cast_slice(&[bloom_config(enabled)]),
+        );
+        log(
+            LogLevel::Info,
+            &format!("Bloom: {}", if enabled { "On" } else { "Off" }),
+        );
+    }
+
+    /// Records the bloom passes: bright-pass to half resolution, downsample
+    /// to quarter resolution, then a horizontal and a vertical blur.
+    ///
+    /// Must run after everything that resolves into `scene_color_view` and
+    /// before the composite pass that samples the result.
+    pub fn encode_bloom(&self, encoder: &mut wgpu::CommandEncoder) {
+        let targets = &self.bloom_targets;
+        let pipelines = &self.bloom_pipelines;
+        let passes = [
+            (
+                "Bloom Prefilter Pass",
+                &pipelines.prefilter,
+                &targets.prefilter_bind_group,
+                &targets.half_view,
+            ),
+            (
+                "Bloom Downsample Pass",
+                &pipelines.downsample,
+                &targets.downsample_bind_group,
+                &targets.quarter_view,
+            ),
+            (
+                "Bloom Horizontal Blur Pass",
+                &pipelines.blur_h,
+                &targets.blur_h_bind_group,
+                &targets.scratch_view,
+            ),
+            (
+                "Bloom Vertical Blur Pass",
+                &pipelines.blur_v,
+                &targets.blur_v_bind_group,
+                &targets.quarter_view,
+            ),
+        ];
+
+        let last = passes.len() - 1;
+        for (i, (label, pipeline, bind_group, target)) in passes.into_iter().enumerate() {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(label),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    depth_slice: None,
+                    ops: wgpu::Operations {
+                        // Every texel is overwritten by the full-screen triangle.
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: self.render_timestamps(GpuPhase::Bloom, i == 0, i == last),
+                ..Default::default()
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+    }
+}
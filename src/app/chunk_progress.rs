@@ -0,0 +1,107 @@
+use glyphon::{Attrs, Family, Shaping};
+
+use super::state::State;
+
+/// Fraction of the chunks around the player below which the loading
+/// readout appears.
+const SHOW_BELOW: f32 = 0.75;
+
+/// Fraction of the chunks around the player above which the loading
+/// readout goes away again.  Higher than [`SHOW_BELOW`] so the readout
+/// does not flicker while chunks stream in and out at the edge.
+const HIDE_ABOVE: f32 = 0.95;
+
+/// How many of the chunks within `GENERATION_DISTANCE` of the player are
+/// loaded, and whether the "Loading chunks" readout is up.
+///
+/// The readout shows while a significant part of the surroundings is
+/// missing, as on startup or after a teleport, and hides once they are
+/// mostly in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChunkProgress {
+    /// Chunks in the generation square that are in the world.
+    pub loaded: usize,
+    /// Chunks in the generation square.
+    pub total: usize,
+    /// Whether the readout is shown.
+    pub visible: bool,
+}
+
+impl ChunkProgress {
+    /// Records that `loaded` of `total` chunks are in and shows or hides
+    /// the readout accordingly.
+    pub fn update(&mut self, loaded: usize, total: usize) {
+        self.loaded = loaded;
+        self.total = total;
+        let fraction = if total == 0 {
+            1.0
+        } else {
+            loaded as f32 / total as f32
+        };
+        if fraction < SHOW_BELOW {
+            self.visible = true;
+        } else if fraction > HIDE_ABOVE {
+            self.visible = false;
+        }
+    }
+
+    /// Text of the readout.
+    pub fn text(&self) -> String {
+        format!("Loading chunks: {}/{}", self.loaded, self.total)
+    }
+}
+
+impl State {
+    /// Updates [`ChunkProgress`] with the count from this frame's world
+    /// snapshot and re-shapes the readout when its text changed while it
+    /// is shown.
+    pub fn update_chunk_progress(&mut self, loaded: usize, total: usize) {
+        let previous = self.chunk_progress;
+        self.chunk_progress.update(loaded, total);
+        if !self.chunk_progress.visible
+            || (previous.visible && previous.loaded == loaded && previous.total == total)
+        {
+            return;
+        }
+
+        self.chunk_progress_buffer.set_text(
+            &mut self.font_system,
+            &self.chunk_progress.text(),
+            &Attrs::new().family(Family::SansSerif),
+            Shaping::Advanced,
+            None,
+        );
+        self.chunk_progress_buffer
+            .set_size(&mut self.font_system, None, None);
+        self.chunk_progress_width = self
+            .chunk_progress_buffer
+            .layout_runs()
+            .map(|run| run.line_w)
+            .fold(0.0, f32::max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readout_shows_while_many_chunks_are_missing() {
+        let mut progress = ChunkProgress::default();
+        progress.update(0, 100);
+        assert!(progress.visible);
+        assert_eq!(progress.text(), "Loading chunks: 0/100");
+
+        // Stays up until the surroundings are mostly in...
+        progress.update(90, 100);
+        assert!(progress.visible);
+        progress.update(96, 100);
+        assert!(!progress.visible);
+
+        // ...and does not come back for a few chunks at the edge.
+        progress.update(85, 100);
+        assert!(!progress.visible);
+        progress.update(40, 100);
+        assert!(progress.visible);
+    }
+}
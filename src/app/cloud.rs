@@ -0,0 +1,52 @@
+use std::ops::Range;
+
+/// Cloud coverage used unless `--clouds` says otherwise.
+pub const DEFAULT_CLOUD_COVERAGE: f32 = 0.5;
+
+/// Rain intensities over which the cloud layer fades out: light rain keeps
+/// the clouds, heavy rain means an overcast sky where they are hidden.
+const OVERCAST_RAIN: Range<f32> = 0.5..0.9;
+
+/// Parses the `--clouds` value, accepting `0.0..=1.0`.
+pub fn parse_cloud_coverage(value: &str) -> Result<f32, String> {
+    let coverage: f32 = value
+        .parse()
+        .map_err(|_| format!("`{}` is not a number", value))?;
+    if (0.0..=1.0).contains(&coverage) {
+        Ok(coverage)
+    } else {
+        Err(format!("{} is not between 0 and 1", coverage))
+    }
+}
+
+/// Coverage uploaded to the shaders for this frame: the `--clouds`
+/// setting, faded out across [`OVERCAST_RAIN`] so heavy rain shows no
+/// clouds and casts no cloud shadows.
+pub fn visible_cloud_coverage(coverage: f32, rain_factor: f32) -> f32 {
+    let overcast = ((rain_factor - OVERCAST_RAIN.start)
+        / (OVERCAST_RAIN.end - OVERCAST_RAIN.start))
+        .clamp(0.0, 1.0);
+    coverage * (1.0 - overcast)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clouds_flag_rejects_values_outside_zero_to_one() {
+        assert_eq!(parse_cloud_coverage("0"), Ok(0.0));
+        assert_eq!(parse_cloud_coverage("0.75"), Ok(0.75));
+        assert!(parse_cloud_coverage("1.2").is_err());
+        assert!(parse_cloud_coverage("cloudy").is_err());
+    }
+
+    #[test]
+    fn heavy_rain_hides_the_clouds() {
+        assert_eq!(visible_cloud_coverage(0.6, 0.0), 0.6);
+        assert_eq!(visible_cloud_coverage(0.6, OVERCAST_RAIN.start), 0.6);
+        let light = visible_cloud_coverage(0.6, 0.7);
+        assert!(light > 0.0 && light < 0.6);
+        assert_eq!(visible_cloud_coverage(0.6, 1.0), 0.0);
+    }
+}
@@ -0,0 +1,357 @@
+use glam::Vec3;
+use minerust::{
+    BOX_EDIT_BLOCKS_PER_FRAME, BlockDiff, BoxEdit, BoxEditKind, CHUNK_SIZE, ChatCommand,
+    DiggingState, EDIT_TOOL_REACH, MAX_FLOOD_FILL_VOLUME, parse_command,
+};
+
+use crate::logger::{LogLevel, log};
+use crate::multiplayer::protocol::Packet;
+
+use super::state::State;
+
+impl State {
+    /// Handles a line submitted from the chat box.
+    ///
+    /// Lines beginning with `/` are parsed and executed as commands; the
+    /// outcome (or parse error) is echoed into the chat log.  Plain text is
+    /// simply echoed locally for now.
+    ///
+    /// # Parameters
+    /// - `line` – The trimmed, non-empty line the player submitted.
+    pub fn submit_chat_line(&mut self, line: &str) {
+        if !line.starts_with('/') {
+            self.chat
+                .push_message(format!("<{}> {}", self.menu_state.username, line));
+            return;
+        }
+
+        let feedback = match parse_command(line) {
+            // Connected, these run once the server sends them back; see
+            // `run_server_command`.
+            Ok(command) if command.runs_on_server() && self.network_tx.is_some() => {
+                if let Some(tx) = &self.network_tx {
+                    let _ = tx.send(Packet::Command {
+                        player_id: self.my_player_id,
+                        command: line[1..].to_string(),
+                    });
+                }
+                log(
+                    LogLevel::Info,
+                    &format!("Command '{}' sent to the server", line),
+                );
+                return;
+            }
+            Ok(command) => self.execute_command(command),
+            Err(e) => e,
+        };
+        log(LogLevel::Info, &format!("Command '{}': {}", line, feedback));
+        self.chat.push_message(feedback);
+    }
+
+    /// Runs a command the server sent (see `Packet::Command`) and echoes the
+    /// outcome into the chat log, naming the player who issued it when that
+    /// was someone else.
+    pub fn run_server_command(&mut self, player_id: u32, command: &str) {
+        let feedback = match parse_command(command) {
+            Ok(command) => self.execute_command(command),
+            Err(e) => e,
+        };
+        let feedback = match self.remote_players.get(&player_id) {
+            Some(player) if player_id != self.my_player_id => {
+                format!("{}: {}", player.username, feedback)
+            }
+            _ => feedback,
+        };
+        log(
+            LogLevel::Info,
+            &format!("Server command '{}': {}", command, feedback),
+        );
+        self.chat.push_message(feedback);
+    }
+
+    /// Executes a parsed [`ChatCommand`] and returns a one-line summary for
+    /// the chat log.
+    fn execute_command(&mut self, command: ChatCommand) -> String {
+        match command {
+            ChatCommand::WaterFill { level } => {
+                // The fill starts in the air block in front of the face the
+                // player is aiming at, i.e. inside the basin.
+                let Some(hit) = self.camera.raycast(&self.world.read(), EDIT_TOOL_REACH) else {
+                    return "No block targeted".to_string();
+                };
+                let (x, y, z) = hit.place_position();
+                let level = level.unwrap_or(y);
+                let result =
+                    self.world
+                        .write()
+                        .flood_fill_water((x, y, z), level, MAX_FLOOD_FILL_VOLUME);
+                match result {
+                    Ok(changed) => {
+                        let count = changed.len();
+                        self.apply_block_edits(&changed);
+                        self.edit_history.push(changed);
+                        format!("Filled {} blocks with water up to y {}", count, level)
+                    }
+                    Err(e) => format!("Fill aborted: {}", e),
+                }
+            }
+            ChatCommand::WaterDrain => {
+                // Water is not solid, so the ray passes through it and the
+                // placement position is the water cell above the basin floor.
+                let Some(hit) = self.camera.raycast(&self.world.read(), EDIT_TOOL_REACH) else {
+                    return "No block targeted".to_string();
+                };
+                let (x, y, z) = hit.place_position();
+                let result = self
+                    .world
+                    .write()
+                    .drain_water((x, y, z), MAX_FLOOD_FILL_VOLUME);
+                match result {
+                    Ok(changed) => {
+                        let count = changed.len();
+                        self.apply_block_edits(&changed);
+                        self.edit_history.push(changed);
+                        format!("Drained {} water blocks", count)
+                    }
+                    Err(e) => format!("Drain aborted: {}", e),
+                }
+            }
+            ChatCommand::Fill { from, to, block } => self
+                .queue_box_edit(from, to, BoxEditKind::Fill(block))
+                .map_or_else(
+                    |e| e,
+                    |volume| {
+                        self.last_fill_region = Some([from, to]);
+                        format!("Filling {} blocks with {}", volume, block.display_name())
+                    },
+                ),
+            ChatCommand::Replace { from, to } => {
+                let Some([a, b]) = self.last_fill_region else {
+                    return "No region selected; run /fill first".to_string();
+                };
+                self.queue_box_edit(a, b, BoxEditKind::Replace { from, to })
+                    .map_or_else(
+                        |e| e,
+                        |volume| {
+                            format!(
+                                "Replacing {} with {} in {} blocks",
+                                from.display_name(),
+                                to.display_name(),
+                                volume
+                            )
+                        },
+                    )
+            }
+            ChatCommand::ChunkBorders { radius } => match radius {
+                Some(radius) => {
+                    self.chunk_border_radius = radius;
+                    self.show_chunk_borders = true;
+                    format!("Showing chunk borders within {} chunks", radius)
+                }
+                None => {
+                    self.show_chunk_borders = !self.show_chunk_borders;
+                    if self.show_chunk_borders {
+                        "Chunk borders shown".to_string()
+                    } else {
+                        "Chunk borders hidden".to_string()
+                    }
+                }
+            },
+            ChatCommand::Acceleration { enabled } => {
+                let movement = &mut self.camera.movement;
+                movement.acceleration = enabled.unwrap_or(!movement.acceleration);
+                if movement.acceleration {
+                    "Movement acceleration enabled".to_string()
+                } else {
+                    "Movement acceleration disabled".to_string()
+                }
+            }
+            ChatCommand::Teleport { x, y, z } => {
+                // The body moves, so a spectating camera returns to it first.
+                self.camera.set_spectating(false);
+                self.camera.position = Vec3::new(x, y, z);
+                self.camera.velocity = Vec3::ZERO;
+                self.digging = DiggingState::default();
+                format!("Teleported to {:.1} {:.1} {:.1}", x, y, z)
+            }
+            ChatCommand::Seed => format!("World seed: {}", self.world.read().seed),
+            ChatCommand::SetTime { time } => {
+                self.time_of_day = time;
+                format!("Time set to {}", time)
+            }
+            // A server runs its clock at the normal speed and keeps clients
+            // in step with it, see `State::sync_time`.
+            ChatCommand::SetTimeScale { .. } if self.network_tx.is_some() => {
+                "The time scale cannot be changed on a server".to_string()
+            }
+            ChatCommand::SetTimeScale { scale } => {
+                self.time_scale = scale;
+                format!("Time scale set to {}", scale)
+            }
+            ChatCommand::Give { block, count } => {
+                self.inventory.add(block, count);
+                format!("Gave {} {}", count, block.display_name())
+            }
+            ChatCommand::FpsCap { fps } => {
+                self.fps_cap = fps;
+                self.frame_limiter.reset();
+                match fps {
+                    Some(fps) => format!("Frame rate capped at {} FPS", fps),
+                    None => "Frame rate cap removed".to_string(),
+                }
+            }
+            ChatCommand::RegenerateChunks { radius, force } => {
+                self.regenerate_chunks(radius, force)
+            }
+            ChatCommand::GameMode { creative } => {
+                // Not `set_creative`, which reports the change in chat itself.
+                self.camera.set_creative(creative);
+                self.digging = DiggingState::default();
+                if creative {
+                    "Creative mode".to_string()
+                } else {
+                    "Survival mode".to_string()
+                }
+            }
+        }
+    }
+
+    /// Queues the box edit spanned by corners `a` and `b` to be streamed in
+    /// by [`State::step_box_edits`].  Boxes reaching outside the world
+    /// height or larger than `MAX_BOX_EDIT_VOLUME` are rejected; see
+    /// [`BoxEdit::new`].
+    ///
+    /// # Returns
+    /// The box volume on success, or a chat-ready error message.
+    fn queue_box_edit(
+        &mut self,
+        a: (i32, i32, i32),
+        b: (i32, i32, i32),
+        kind: BoxEditKind,
+    ) -> Result<usize, String> {
+        let edit = BoxEdit::new(a, b, kind)?;
+        let volume = edit.volume();
+        self.pending_box_edits.push_back(edit);
+        Ok(volume)
+    }
+
+    /// Runs `/regen`: generates the loaded chunks within `radius` chunks of
+    /// the player's again (see [`minerust::World::regenerate_chunk`]) and
+    /// frees their old meshes, which the rebuilt ones replace over the next
+    /// frames.
+    ///
+    /// # Returns
+    /// A chat-ready summary of how many chunks were regenerated and skipped.
+    fn regenerate_chunks(&mut self, radius: i32, force: bool) -> String {
+        let player_cx = (self.camera.position.x.floor() as i32).div_euclid(CHUNK_SIZE);
+        let player_cz = (self.camera.position.z.floor() as i32).div_euclid(CHUNK_SIZE);
+        let mut regenerated = Vec::new();
+        let mut skipped = 0;
+        {
+            let mut world = self.world.write();
+            for cx in player_cx - radius..=player_cx + radius {
+                for cz in player_cz - radius..=player_cz + radius {
+                    if world.regenerate_chunk(cx, cz, force) {
+                        regenerated.push((cx, cz));
+                    } else if world.chunks.contains_key(&(cx, cz)) {
+                        skipped += 1;
+                    }
+                }
+            }
+        }
+        self.remove_chunk_gpu_data(&regenerated);
+        if skipped > 0 {
+            format!(
+                "Regenerated {} chunks, kept {} with player edits (add force to include them)",
+                regenerated.len(),
+                skipped
+            )
+        } else {
+            format!("Regenerated {} chunks", regenerated.len())
+        }
+    }
+
+    /// Applies the next slice of the oldest pending `/fill` or `/replace`.
+    ///
+    /// Called once per frame from `update`.  At most
+    /// [`BOX_EDIT_BLOCKS_PER_FRAME`] blocks are visited per call so large
+    /// regions are spread over several frames instead of causing a hitch.
+    /// A finished edit is recorded in the history as a single operation.
+    pub fn step_box_edits(&mut self) {
+        let Some(edit) = self.pending_box_edits.front_mut() else {
+            return;
+        };
+        let changed = edit.step(&mut self.world.write(), BOX_EDIT_BLOCKS_PER_FRAME);
+        let finished = edit.is_done();
+
+        self.apply_block_edits(&changed);
+
+        if finished && let Some(edit) = self.pending_box_edits.pop_front() {
+            self.chat.push_message(format!(
+                "Edit complete: {} blocks changed",
+                edit.applied.len()
+            ));
+            self.edit_history.push(edit.applied);
+        }
+    }
+
+    /// Reverts the most recent edit in [`State::edit_history`] (Ctrl+Z).
+    ///
+    /// Refused while a `/fill` or `/replace` is still streaming in, since
+    /// its diff is not complete yet.
+    pub fn undo_edit(&mut self) {
+        if !self.pending_box_edits.is_empty() {
+            self.chat
+                .push_message("Wait for the current edit to finish");
+            return;
+        }
+        match self.edit_history.undo() {
+            Some(diffs) => {
+                self.world.write().apply_diffs(&diffs);
+                self.apply_block_edits(&diffs);
+                self.chat
+                    .push_message(format!("Undid {} block changes", diffs.len()));
+            }
+            None => self.chat.push_message("Nothing to undo"),
+        }
+    }
+
+    /// Re-applies the most recently undone edit (Ctrl+Y).
+    pub fn redo_edit(&mut self) {
+        if !self.pending_box_edits.is_empty() {
+            self.chat
+                .push_message("Wait for the current edit to finish");
+            return;
+        }
+        match self.edit_history.redo() {
+            Some(diffs) => {
+                self.world.write().apply_diffs(&diffs);
+                self.apply_block_edits(&diffs);
+                self.chat
+                    .push_message(format!("Redid {} block changes", diffs.len()));
+            }
+            None => self.chat.push_message("Nothing to redo"),
+        }
+    }
+
+    /// Remeshes and replicates a batch of blocks that were already written
+    /// to the world.
+    ///
+    /// Every position is marked dirty (together with any neighbouring
+    /// sub-chunk it borders) and, when connected, a `BlockChange` packet with
+    /// the diff's `after` state is sent so other players see the same edit.
+    pub fn apply_block_edits(&mut self, changed: &[BlockDiff]) {
+        for diff in changed {
+            self.mark_chunk_dirty(diff.x, diff.y, diff.z);
+            if let Some(tx) = &self.network_tx {
+                let _ = tx.send(Packet::BlockChange {
+                    x: diff.x,
+                    y: diff.y,
+                    z: diff.z,
+                    block_type: diff.after as u8,
+                    facing: diff.after_facing as u8,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+/// Night ambient brightness used until the menu slider changes it.
+pub const DEFAULT_MIN_AMBIENT: f32 = 0.06;
+
+/// Range of the menu's minimum ambient slider.
+pub const MAX_MIN_AMBIENT: f32 = 0.3;
+
+/// Ambient light of a clear day.
+const DAY_AMBIENT: f32 = 0.38;
+
+/// Hue of the night ambient, scaled by the minimum ambient setting.  Blue
+/// so moonlit surfaces and caves read as night rather than as dim daylight.
+const NIGHT_TINT: [f32; 3] = [0.55, 0.65, 1.0];
+
+/// Warm ambient added around sunrise and sunset.
+const TWILIGHT_AMBIENT: [f32; 3] = [0.16, 0.09, 0.04];
+
+/// Color the lit terrain is multiplied towards at the peak of dawn/dusk.
+const DAWN_GLOW: [f32; 3] = [1.0, 0.78, 0.6];
+
+/// Terrain lighting for one frame, uploaded in the per-frame uniforms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DayLight {
+    /// How far into the day the sun is, `0.0` at night to `1.0` with the
+    /// sun high, eased so the light ramps up softly after sunrise.
+    pub day_factor: f32,
+    /// Ambient light color added to every face before face shading.
+    pub ambient: [f32; 3],
+    /// Tint multiplied onto the lit terrain; white except at dawn/dusk.
+    pub dawn_tint: [f32; 3],
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn mix(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Computes the terrain lighting from the height of the sun.
+///
+/// `sun_y` is the Y component of the normalized direction towards the sun,
+/// and `min_ambient` the night brightness picked on the menu slider; the
+/// ambient never drops below it, so caves and night-time surfaces keep a
+/// faint blue light instead of going pitch black.
+pub fn day_light(sun_y: f32, min_ambient: f32) -> DayLight {
+    let day_factor = smoothstep(-0.1, 0.7, sun_y);
+    let twilight = smoothstep(-0.15, 0.1, sun_y) * smoothstep(0.45, 0.05, sun_y);
+
+    let mut ambient = [0.0; 3];
+    let mut dawn_tint = [0.0; 3];
+    for i in 0..3 {
+        let night = NIGHT_TINT[i] * min_ambient;
+        ambient[i] =
+            mix(night, DAY_AMBIENT.max(night), day_factor) + TWILIGHT_AMBIENT[i] * twilight;
+        dawn_tint[i] = mix(1.0, DAWN_GLOW[i], twilight * 0.6);
+    }
+
+    DayLight {
+        day_factor,
+        ambient,
+        dawn_tint,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn light_ramps_smoothly_and_keeps_a_blue_night() {
+        let night = day_light(-0.8, DEFAULT_MIN_AMBIENT);
+        assert_eq!(night.day_factor, 0.0);
+        assert!(night.ambient[2] > night.ambient[0]);
+        assert!(night.ambient[2] >= DEFAULT_MIN_AMBIENT);
+        assert_eq!(night.dawn_tint, [1.0; 3]);
+
+        let noon = day_light(1.0, DEFAULT_MIN_AMBIENT);
+        assert_eq!(noon.day_factor, 1.0);
+        assert_eq!(noon.ambient, [DAY_AMBIENT; 3]);
+
+        // No jumps between neighboring sun heights.
+        let mut previous = day_light(-1.0, DEFAULT_MIN_AMBIENT);
+        for step in 1..=200 {
+            let light = day_light(-1.0 + step as f32 * 0.01, DEFAULT_MIN_AMBIENT);
+            assert!(light.day_factor >= previous.day_factor);
+            assert!((light.ambient[0] - previous.ambient[0]).abs() < 0.02);
+            previous = light;
+        }
+
+        let dawn = day_light(0.05, DEFAULT_MIN_AMBIENT);
+        assert!(dawn.dawn_tint[2] < dawn.dawn_tint[0]);
+    }
+
+    #[test]
+    fn brighter_minimum_ambient_lifts_the_night() {
+        let dark = day_light(-0.5, 0.0);
+        let bright = day_light(-0.5, MAX_MIN_AMBIENT);
+        assert_eq!(dark.ambient, [0.0; 3]);
+        assert!(bright.ambient.iter().all(|&c| c > 0.1));
+    }
+}
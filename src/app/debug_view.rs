@@ -0,0 +1,198 @@
+use crate::logger::{LogLevel, log};
+
+use super::state::State;
+
+/// Render target shown full-screen in place of the finished frame, for
+/// debugging the passes that feed the water reflections.
+///
+/// Picked with the debug view key while the F3 overlay is open; closing the
+/// overlay goes back to [`DebugView::Final`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DebugView {
+    /// The composited frame, as in normal play.
+    #[default]
+    Final,
+    /// The opaque scene the water pass reflects (`ssr_color_view`).
+    SsrColor,
+    /// The resolved scene depth the reflection rays march against
+    /// (`ssr_depth_view`), shaded by distance.
+    SsrDepth,
+}
+
+impl DebugView {
+    /// Every view, in the order the key cycles through them.
+    pub const ALL: [DebugView; 3] = [DebugView::Final, DebugView::SsrColor, DebugView::SsrDepth];
+
+    /// The view after this one, wrapping around to the first.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&v| v == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Name shown in the debug overlay.
+    pub fn label(self) -> &'static str {
+        match self {
+            DebugView::Final => "Final",
+            DebugView::SsrColor => "SSR color",
+            DebugView::SsrDepth => "SSR depth",
+        }
+    }
+}
+
+/// The blit pipelines from `debug_view.wgsl` and the layout they share.
+pub struct DebugViewPipelines {
+    /// One unfilterable texture, read with `textureLoad`.
+    bind_group_layout: wgpu::BindGroupLayout,
+    /// Copies an HDR color target to the swap-chain.
+    color: wgpu::RenderPipeline,
+    /// Shades a depth target by view distance.
+    depth: wgpu::RenderPipeline,
+}
+
+impl DebugViewPipelines {
+    /// Compiles `debug_view.wgsl` and builds both pipelines for a swap-chain
+    /// in `surface_format`.
+    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Debug View Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/debug_view.wgsl").into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Debug View Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            }],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Debug View Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            immediate_size: 0,
+        });
+
+        let create_pipeline = |label: &str, entry_point: &str| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                cache: None,
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    compilation_options: Default::default(),
+                    buffers: &[], // full-screen triangle from vertex index
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    compilation_options: Default::default(),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: surface_format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+            })
+        };
+
+        Self {
+            color: create_pipeline("Debug View Color Pipeline", "fs_color"),
+            depth: create_pipeline("Debug View Depth Pipeline", "fs_depth"),
+            bind_group_layout,
+        }
+    }
+}
+
+impl State {
+    /// Switches to the next debug view and reports it in the log.
+    ///
+    /// Does nothing unless the F3 overlay is open, and closing the overlay
+    /// goes back to the final frame, so the key cannot leave a debug view
+    /// on screen by accident.
+    pub fn cycle_debug_view(&mut self) {
+        if !self.show_debug_overlay {
+            return;
+        }
+        self.debug_view = self.debug_view.next();
+        log(
+            LogLevel::Info,
+            &format!("Debug view: {}", self.debug_view.label()),
+        );
+    }
+
+    /// Records a pass drawing the picked debug view over the whole of
+    /// `target`, the swap-chain image.  Records nothing for
+    /// [`DebugView::Final`].
+    ///
+    /// Runs after the composite pass and before the UI pass, so the
+    /// overlay text stays readable on top.  The bind group is built per
+    /// frame: the views it reads are recreated on resize, and this is a
+    /// debugging aid only.
+    pub fn encode_debug_view(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+    ) {
+        let (pipeline, source) = match self.debug_view {
+            DebugView::Final => return,
+            DebugView::SsrColor => (&self.debug_view_pipelines.color, &self.ssr_color_view),
+            DebugView::SsrDepth => (&self.debug_view_pipelines.depth, &self.ssr_depth_view),
+        };
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Debug View Bind Group"),
+            layout: &self.debug_view_pipelines.bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(source),
+            }],
+        });
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Debug View Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                depth_slice: None,
+                ops: wgpu::Operations {
+                    // Every pixel is overwritten by the full-screen triangle.
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            ..Default::default()
+        });
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_views_cycle_back_to_the_final_frame() {
+        let mut view = DebugView::default();
+        let mut seen = Vec::new();
+        for _ in 0..DebugView::ALL.len() {
+            seen.push(view);
+            view = view.next();
+        }
+        assert_eq!(seen, DebugView::ALL);
+        assert_eq!(view, DebugView::Final);
+    }
+}
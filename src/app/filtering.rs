@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+
+use crate::logger::{LogLevel, log};
+
+use super::state::State;
+
+/// How the block texture atlas is sampled by the terrain and water shaders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextureFiltering {
+    /// Nearest-neighbour texels and mip levels: the crisp, blocky look.
+    #[default]
+    Nearest,
+    /// Trilinear filtering with as much anisotropy as the device supports.
+    /// Smoother in the distance and on surfaces seen at a grazing angle.
+    Anisotropic,
+}
+
+impl TextureFiltering {
+    /// Returns the other mode.
+    pub fn toggled(self) -> Self {
+        match self {
+            TextureFiltering::Nearest => TextureFiltering::Anisotropic,
+            TextureFiltering::Anisotropic => TextureFiltering::Nearest,
+        }
+    }
+
+    /// Short name shown on the menu toggle button.
+    pub fn label(self) -> &'static str {
+        match self {
+            TextureFiltering::Nearest => "CRISP",
+            TextureFiltering::Anisotropic => "SMOOTH",
+        }
+    }
+}
+
+/// Returns the highest `anisotropy_clamp` the adapter accepts.
+///
+/// wgpu does not report a numeric limit; devices either support anisotropic
+/// filtering up to 16× or not at all, in which case the clamp must stay at 1.
+pub fn max_sampler_anisotropy(adapter: &wgpu::Adapter) -> u16 {
+    if adapter
+        .get_downlevel_capabilities()
+        .flags
+        .contains(wgpu::DownlevelFlags::ANISOTROPIC_FILTERING)
+    {
+        16
+    } else {
+        1
+    }
+}
+
+/// Creates the texture atlas sampler for the given filtering mode.
+///
+/// # Parameters
+/// - `device`         – Device to create the sampler on.
+/// - `filtering`      – Requested filtering mode.
+/// - `max_anisotropy` – Value from [`max_sampler_anisotropy`].  With `1`,
+///   [`TextureFiltering::Anisotropic`] falls back to plain trilinear.
+pub fn create_atlas_sampler(
+    device: &wgpu::Device,
+    filtering: TextureFiltering,
+    max_anisotropy: u16,
+) -> wgpu::Sampler {
+    let (filter, mipmap_filter, anisotropy_clamp) = match filtering {
+        TextureFiltering::Nearest => (
+            wgpu::FilterMode::Nearest,
+            wgpu::MipmapFilterMode::Nearest,
+            1,
+        ),
+        // wgpu requires every filter to be linear when anisotropy is enabled.
+        TextureFiltering::Anisotropic => (
+            wgpu::FilterMode::Linear,
+            wgpu::MipmapFilterMode::Linear,
+            max_anisotropy,
+        ),
+    };
+    device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("Texture Sampler"),
+        address_mode_u: wgpu::AddressMode::ClampToEdge,
+        address_mode_v: wgpu::AddressMode::ClampToEdge,
+        address_mode_w: wgpu::AddressMode::ClampToEdge,
+        mag_filter: filter,
+        min_filter: filter,
+        mipmap_filter,
+        anisotropy_clamp,
+        ..Default::default()
+    })
+}
+
+impl State {
+    /// Switches the texture atlas sampler to `filtering`.
+    ///
+    /// The sampler is baked into both `uniform_bind_group` and
+    /// `water_bind_group`, so both are rebuilt around the new sampler.  The
+    /// switch takes effect on the next frame and is saved in the settings
+    /// file.
+    pub fn set_texture_filtering(&mut self, filtering: TextureFiltering) {
+        self.texture_filtering = filtering;
+        self.texture_sampler = create_atlas_sampler(&self.device, filtering, self.max_anisotropy);
+        self.uniform_bind_group = self.create_uniform_bind_group();
+        self.water_bind_group = self.create_water_bind_group();
+        self.save_settings();
+        log(
+            LogLevel::Info,
+            &format!(
+                "Texture filtering set to {:?} (max anisotropy {}x)",
+                filtering, self.max_anisotropy
+            ),
+        );
+    }
+
+    /// Builds the group(0) bind group shared by the terrain, outline and UI
+    /// pipelines from the resources currently held by `State`.
+    pub fn create_uniform_bind_group(&self) -> wgpu::BindGroup {
+        self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.uniform_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&self.texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&self.texture_sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&self.shadow_texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::Sampler(&self.shadow_sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: self.shadow_config_buffer.as_entire_binding(),
+                },
+            ],
+            label: Some("uniform_bind_group"),
+        })
+    }
+
+    /// Builds the water pass bind group from the resources currently held by
+    /// `State`, including the SSR views that are recreated on resize.
+    pub fn create_water_bind_group(&self) -> wgpu::BindGroup {
+        self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.water_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&self.texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&self.texture_sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&self.shadow_texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::Sampler(&self.shadow_sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: wgpu::BindingResource::TextureView(&self.ssr_color_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&self.ssr_depth_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 7,
+                    resource: wgpu::BindingResource::Sampler(&self.ssr_sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 8,
+                    resource: wgpu::BindingResource::TextureView(&self.flow_map_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 9,
+                    resource: wgpu::BindingResource::Sampler(&self.flow_sampler),
+                },
+            ],
+            label: Some("water_bind_group"),
+        })
+    }
+}
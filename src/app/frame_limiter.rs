@@ -0,0 +1,92 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::state::State;
+
+/// How long before a frame deadline the limiter stops sleeping and spins
+/// instead.  OS sleeps commonly overshoot by a millisecond or more, which
+/// at high caps would cost whole frames.
+const SPIN_MARGIN: Duration = Duration::from_micros(1500);
+
+/// Holds the frame rate at a cap by waiting at the end of each frame.
+///
+/// Deadlines are spaced exactly one frame time apart rather than measured
+/// from the end of the wait, so sleep overshoot does not pile up and the
+/// rate converges on the cap.  A frame that ends past its deadline starts
+/// the schedule over instead of letting the following frames catch up in
+/// a burst.
+#[derive(Debug, Default)]
+pub struct FrameLimiter {
+    /// When the previous frame was released.
+    last_deadline: Option<Instant>,
+}
+
+impl FrameLimiter {
+    /// Returns when the frame finishing at `now` may end to hold `fps`
+    /// frames per second, and remembers it as the start of the next frame.
+    pub fn deadline(&mut self, fps: u32, now: Instant) -> Instant {
+        let frame = Duration::from_nanos(1_000_000_000 / u64::from(fps));
+        let deadline = match self.last_deadline {
+            Some(last) if last + frame > now => last + frame,
+            _ => now,
+        };
+        self.last_deadline = Some(deadline);
+        deadline
+    }
+
+    /// Forgets the schedule, e.g. after the cap was changed.
+    pub fn reset(&mut self) {
+        self.last_deadline = None;
+    }
+}
+
+/// Blocks the thread until `deadline`: sleeps for most of the wait, then
+/// spins through the last [`SPIN_MARGIN`] for an accurate wake-up.
+fn wait_until(deadline: Instant) {
+    let now = Instant::now();
+    if deadline > now + SPIN_MARGIN {
+        thread::sleep(deadline - now - SPIN_MARGIN);
+    }
+    while Instant::now() < deadline {
+        std::hint::spin_loop();
+    }
+}
+
+impl State {
+    /// Waits out the rest of the frame when an FPS cap is set.
+    ///
+    /// Called after `render`, so the wait covers both the update and the
+    /// render time of the frame.  Independent of the present mode: with
+    /// VSync on, a cap above the refresh rate has no effect.
+    pub fn limit_frame_rate(&mut self) {
+        let Some(fps) = self.fps_cap else {
+            return;
+        };
+        wait_until(self.frame_limiter.deadline(fps, Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_rate_converges_on_the_cap() {
+        let mut limiter = FrameLimiter::default();
+        let start = Instant::now();
+        let mut now = start;
+        // Frames taking 4 ms of work, capped at 100 fps.
+        for _ in 0..100 {
+            now = limiter.deadline(100, now + Duration::from_millis(4));
+        }
+        let fps = 100.0 / (now - start).as_secs_f64();
+        assert!((fps - 100.0).abs() < 1.0, "{} fps", fps);
+
+        // Frames slower than the cap are not held back, and a long hitch is
+        // not followed by a burst of frames making up for it.
+        let hitch = now + Duration::from_millis(50);
+        assert_eq!(limiter.deadline(100, hitch), hitch);
+        let after = limiter.deadline(100, hitch + Duration::from_millis(1));
+        assert_eq!(after, hitch + Duration::from_millis(10));
+    }
+}
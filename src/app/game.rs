@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::time::Instant;
 
 use clap::Parser;
@@ -9,14 +10,22 @@ use winit::{
     window::{CursorGrabMode, Fullscreen, WindowBuilder},
 };
 
-use minerust::{
-    CHUNK_SIZE, DEFAULT_WORLD_FILE, SUBCHUNK_HEIGHT, SavedWorld, World, load_world, save_world,
-};
+use minerust::{CHUNK_SIZE, ChunkLoader, DEFAULT_WORLD_FILE, SUBCHUNK_HEIGHT, World, load_world};
 
 use crate::logger::{LogLevel, log};
-use crate::ui::menu::GameState;
-
+use crate::multiplayer::server::ServerConfig;
+use crate::ui::menu::{GameState, MenuScreen};
+
+#[cfg(feature = "audio")]
+use super::audio::parse_volume;
+use super::cloud::{DEFAULT_CLOUD_COVERAGE, parse_cloud_coverage};
+use super::debug_view::DebugView;
+use super::keybindings::Action;
+use super::msaa::parse_msaa_samples;
+use super::particles::DEFAULT_BREAK_PARTICLES;
+use super::profiler;
 use super::server::run_dedicated_server;
+use super::sky::{SkyPreset, parse_sky_preset};
 use super::state::State;
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -26,7 +35,9 @@ use super::state::State;
 /// Command-line arguments parsed by [`clap`] at startup.
 ///
 /// Running with `--server` skips the windowed game entirely and starts a
-/// headless TCP server instead.  The port can be overridden with `--port`.
+/// headless TCP server instead.  The port can be overridden with `--port`
+/// and the world tick rate with `--tick-rate`.  `--world` makes the server
+/// keep its block edits in a file across restarts.
 ///
 /// # Examples
 /// ```text
@@ -36,6 +47,27 @@ use super::state::State;
 /// # Start a headless server on a custom port
 /// minerust --server --port 12345
 ///
+/// # Start a headless server ticking 40 times per second
+/// minerust --server --tick-rate 40
+///
+/// # Start the game with 2 chunk and 2 mesh worker threads
+/// minerust --workers 2
+///
+/// # Start the game without MSAA
+/// minerust --msaa 1
+///
+/// # Start the game under a mostly clouded sky
+/// minerust --clouds 0.8
+///
+/// # Start the game under a pink and purple sky
+/// minerust --sky vaporwave
+///
+/// # Start the game without debris from broken blocks
+/// minerust --break-particles 0
+///
+/// # Start a headless server that saves its edits to server.minerust
+/// minerust --server --world server.minerust
+///
 /// # Start the windowed game (default when no flags are given)
 /// minerust
 /// ```
@@ -49,6 +81,54 @@ struct Args {
     /// TCP port the dedicated server listens on.
     #[arg(long, default_value_t = 25565)]
     port: u16,
+
+    /// Ticks per second of the dedicated server's world simulation.
+    #[arg(long, default_value_t = 20)]
+    tick_rate: u32,
+
+    /// Threads used for chunk generation and for mesh building each.
+    /// Defaults to a count derived from the CPU's cores.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..=64))]
+    workers: Option<u16>,
+
+    /// MSAA samples per pixel: 1 (off), 2, 4 or 8.  Lowered to what the GPU
+    /// supports.  Saved in the settings; defaults to the saved count, or 4.
+    #[arg(long, value_name = "N", value_parser = parse_msaa_samples)]
+    msaa: Option<u32>,
+
+    /// Fraction of the sky covered by clouds, from 0 (clear, no cloud
+    /// shadows) to 1 (overcast).
+    #[arg(long, value_name = "COVERAGE", default_value_t = DEFAULT_CLOUD_COVERAGE, value_parser = parse_cloud_coverage)]
+    clouds: f32,
+
+    /// Colors of the sky: vanilla, vaporwave or overcast.
+    #[arg(long, value_name = "PRESET", default_value = "vanilla", value_parser = parse_sky_preset)]
+    sky: SkyPreset,
+
+    /// Debris cubes thrown out by each broken block, from 0 (off) to 64.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_BREAK_PARTICLES, value_parser = clap::value_parser!(u32).range(0..=64))]
+    break_particles: u32,
+
+    /// World file the dedicated server loads on startup and saves block
+    /// edits to.  Without it, edits are lost when the server stops.
+    #[arg(long, value_name = "PATH")]
+    world: Option<PathBuf>,
+
+    /// Username allowed to run server commands (`/tp`, `/time set`) on the
+    /// dedicated server.  Repeat for several operators.
+    #[arg(long = "op", value_name = "NAME")]
+    operators: Vec<String>,
+
+    /// Right-stick look speed in radians per second at full deflection.
+    #[cfg(feature = "gamepad")]
+    #[arg(long, default_value_t = minerust::GAMEPAD_LOOK_SENSITIVITY)]
+    gamepad_sensitivity: f32,
+
+    /// Volume of the block sounds, from 0 (muted) to 1.  Saved in the
+    /// settings; defaults to the saved volume, or 1.
+    #[cfg(feature = "audio")]
+    #[arg(long, value_parser = parse_volume)]
+    volume: Option<f32>,
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -70,29 +150,61 @@ struct Args {
 /// | Event | Action |
 /// |---|---|
 /// | `Resized` | Rebuilds all resolution-dependent GPU resources. |
-/// | `RedrawRequested` | Measures frame time, runs `update`, calls `render`. |
+/// | `RedrawRequested` | Measures frame time, polls the gamepad, runs `update`, calls `render`, waits out the FPS cap. |
 /// | `KeyboardInput` | Dispatches to menu or in-game key handlers (see below). |
-/// | `MouseWheel` | Scrolls the hotbar slot selection. |
+/// | `MouseWheel` | Scrolls the hotbar slot selection, or changes the spectator speed. |
 /// | `MouseInput` | Captures cursor on first in-game click; dispatches block actions. |
-/// | `CursorMoved` | Tracks cursor position for menu hover/hit-testing. |
+/// | `ModifiersChanged` | Tracks held modifier keys for shortcuts. |
+/// | `CursorMoved` | Tracks cursor position for menu hover/hit-testing; drags the brightness slider. |
 /// | `DeviceEvent::MouseMotion` | Rotates the camera when the cursor is captured. |
 /// | `AboutToWait` | Switches to 30 fps throttle after 30 s of inactivity. |
-/// | `CloseRequested` | Exits the event loop cleanly. |
+/// | `CloseRequested` | Saves a singleplayer world, then exits the event loop. |
+/// | `LoopExiting` | Joins the chunk and mesh worker threads. |
 ///
 /// # Key bindings (in-game)
 ///
+/// Every key below except Escape, 1–9 and Ctrl+Z / Ctrl+Y is a default that
+/// can be changed on the controls page of the menu (see `KeyBindings`).
+///
 /// | Key | Action |
 /// |---|---|
 /// | W / A / S / D | Move forward / left / backward / right. |
-/// | Space | Jump. |
-/// | Left Shift | Sprint. |
+/// | Space | Jump; fly up in creative mode. |
+/// | Space (double tap) | Toggle creative flight. |
+/// | Left Shift | Sprint; fly down in creative mode. |
+/// | Left Ctrl | Sprint in creative mode. |
 /// | 1–9 | Select hotbar slot. |
 /// | Escape (mouse captured) | Release cursor without leaving the game. |
 /// | Escape (mouse free) | Open the main menu. |
-/// | F5 | Save world to disk. |
+/// | F3 | Toggle the frame timing breakdown in the debug overlay. |
+/// | F7 (F3 overlay open) | Cycle full-screen views of the SSR color and depth targets. |
+/// | F4 | Toggle the coordinate, facing and biome readout. |
+/// | F5 | Save world to disk (written in the background). |
 /// | F9 | Load world from disk. |
 /// | F11 | Toggle borderless fullscreen. |
 /// | R | Cycle water reflection mode (Off → SSR). |
+/// | B | Toggle bloom. |
+/// | H | Toggle the targeted-face highlight. |
+/// | G | Toggle the chunk-border overlay. |
+/// | O | Toggle the sun and moon path overlay. |
+/// | M | Toggle the minimap. |
+/// | T | Open the chat box. |
+/// | / | Open the chat box pre-filled with `/` for a command. |
+/// | Tab (held) | Show the list of players online (multiplayer). |
+/// | Ctrl+Z | Undo the last block edit (placing, breaking, or a command). |
+/// | Ctrl+Y | Redo the last undone block edit. |
+///
+/// With the `gamepad` feature a controller can be used at the same time;
+/// see `GamepadInput` for its bindings.  With the `audio` feature breaking,
+/// placing and walking on blocks play sounds (see `AudioOutput`).
+///
+/// # Key bindings (chat box open)
+///
+/// | Key | Action |
+/// |---|---|
+/// | Enter | Send the line (lines starting with `/` run a command). |
+/// | Escape | Close the chat box without sending. |
+/// | Backspace | Delete the last character. |
 ///
 /// # Key bindings (menu)
 ///
@@ -104,6 +216,7 @@ struct Args {
 /// | Backspace | Delete the last character in the active field. |
 /// | F11 | Toggle borderless fullscreen. |
 /// | Any printable character | Appended to the active text field. |
+/// | Any key (controls page, row clicked) | Bound to the clicked action; Escape cancels. |
 ///
 /// # Panics
 /// Panics if the winit event loop or window cannot be created, or if the
@@ -113,7 +226,14 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
 
     // ── Dedicated server mode ─────────────────────────────────────────────── //
     if args.server {
-        let addr = format!("0.0.0.0:{}", args.port);
+        let config = ServerConfig {
+            port: args.port,
+            tick_rate: args.tick_rate,
+            world_path: args.world,
+            operators: args.operators,
+            ..Default::default()
+        };
+        let addr = config.bind_address();
         log(
             LogLevel::Info,
             &format!("Starting headless server on {}...", addr),
@@ -135,7 +255,7 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
         // Block the main thread on the async server; `run_dedicated_server`
         // runs an infinite accept loop so this never returns normally.
         let rt = tokio::runtime::Runtime::new()?;
-        rt.block_on(run_dedicated_server(&addr));
+        rt.block_on(run_dedicated_server(config));
         return Ok(());
     }
 
@@ -177,7 +297,36 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
     // `State::new` is async (wgpu adapter/device requests are futures), but
     // the rest of the game is synchronous; `pollster::block_on` bridges them
     // without pulling in a full async runtime for the client path.
-    let mut state = pollster::block_on(State::new(window));
+    let mut state =
+        match pollster::block_on(State::new(window, args.workers.map(usize::from), args.msaa)) {
+            Ok(state) => state,
+            Err(e) => {
+                log(LogLevel::Error, &e);
+                return Err(e.into());
+            }
+        };
+    state.cloud_coverage = args.clouds;
+    state.sky_palette = args.sky.palette();
+    state.break_particle_count = args.break_particles;
+    #[cfg(feature = "gamepad")]
+    if let Some(pad) = &mut state.gamepad {
+        pad.look_sensitivity = args.gamepad_sensitivity;
+    }
+    #[cfg(feature = "audio")]
+    if let Some(volume) = args.volume {
+        state.volume = volume;
+        if let Some(audio) = &mut state.audio {
+            audio.volume = volume;
+        }
+    }
+    // Flags change the saved options, like picking them in the menu would.
+    #[cfg(feature = "audio")]
+    let flags_changed_settings = args.msaa.is_some() || args.volume.is_some();
+    #[cfg(not(feature = "audio"))]
+    let flags_changed_settings = args.msaa.is_some();
+    if flags_changed_settings {
+        state.save_settings();
+    }
 
     event_loop
         .run(move |event, elwt| {
@@ -217,24 +366,36 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
                         state.last_fps_update = now;
                     }
 
+                    // Publish last frame's per-system CPU timings to the
+                    // overlay and start collecting this frame's.
+                    profiler::end_frame();
+
                     // Run game logic (camera, physics, chunk uploads, networking).
+                    // The gamepad is sampled first so its input lands this frame.
                     let update_start = Instant::now();
+                    #[cfg(feature = "gamepad")]
+                    state.poll_gamepad();
                     state.update();
                     state.cpu_update_ms = update_start.elapsed().as_secs_f32() * 1000.0;
 
                     match state.render() {
                         Ok(_) => {}
                         // Surface lost (e.g., window un-minimized on some
-                        // platforms): trigger a resize to reconfigure the
-                        // swap-chain with the current window dimensions.
-                        Err(wgpu::SurfaceError::Lost) => state.resize(state.window.inner_size()),
+                        // platforms) or outdated (it no longer matches the
+                        // window, e.g. mid-resize): trigger a resize to
+                        // reconfigure the swap-chain with the current window
+                        // dimensions.
+                        Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                            state.resize(state.window.inner_size())
+                        }
                         // GPU out of memory: nothing reasonable to do here,
                         // so exit cleanly rather than panic.
                         Err(wgpu::SurfaceError::OutOfMemory) => elwt.exit(),
                         Err(e) => log(LogLevel::Error, &format!("Render error: {:?}", e)),
                     }
 
-                    // Request the next frame immediately (uncapped frame rate).
+                    // Hold the FPS cap, if any, then request the next frame.
+                    state.limit_frame_rate();
                     state.window.request_redraw();
                 }
 
@@ -247,6 +408,7 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
                                     physical_key: PhysicalKey::Code(key),
                                     state: key_state,
                                     text,
+                                    repeat,
                                     ..
                                 },
                             ..
@@ -271,14 +433,31 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
 
                     if state.game_state == GameState::Menu {
                         // ---- Menu navigation hotkeys -------------------------
-                        if pressed {
+                        if pressed && state.pending_rebind.is_some() {
+                            // A row on the controls page is waiting for its
+                            // new key, which must not trigger a hotkey too.
+                            state.handle_rebind_key(key);
+                        } else if pressed {
                             match key {
                                 KeyCode::Tab => {
-                                    // Cycle focus: ServerAddress → Username → ServerAddress.
+                                    // Cycle focus through the fields of the
+                                    // current page.
                                     state.menu_state.next_field();
                                 }
-                                KeyCode::Enter => {
-                                    state.connect_to_server();
+                                KeyCode::Enter => match state.menu_state.screen {
+                                    MenuScreen::Main => state.connect_to_server(),
+                                    MenuScreen::Singleplayer => state.create_singleplayer_world(),
+                                    MenuScreen::Controls => {}
+                                },
+                                KeyCode::Escape
+                                    if state.menu_state.screen == MenuScreen::Singleplayer =>
+                                {
+                                    state.menu_state.close_singleplayer();
+                                }
+                                KeyCode::Escape
+                                    if state.menu_state.screen == MenuScreen::Controls =>
+                                {
+                                    state.close_controls();
                                 }
                                 KeyCode::Escape => {
                                     // Dismiss the menu and return to the game
@@ -312,17 +491,34 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
                                 _ => {}
                             }
                         }
+                    } else if state.chat.open {
+                        // ---- Chat input line ----------------------------------
+                        // While the chat box is open it swallows every key so
+                        // typing a command never moves the player.
+                        if pressed {
+                            match key {
+                                KeyCode::Enter => {
+                                    if let Some(line) = state.chat.submit() {
+                                        state.submit_chat_line(&line);
+                                    }
+                                }
+                                KeyCode::Escape => state.chat.close(),
+                                KeyCode::Backspace => state.chat.handle_backspace(),
+                                _ => {
+                                    if let Some(ref txt) = text {
+                                        for ch in txt.chars() {
+                                            state.chat.handle_char(ch);
+                                        }
+                                    }
+                                }
+                            }
+                        }
                     } else {
                         // ---- In-game key bindings ----------------------------
+                        // Escape, undo / redo and the hotbar digits are fixed;
+                        // every other key goes through the remappable
+                        // bindings (see `KeyBindings`).
                         match key {
-                            // Movement – held state polled each frame by `update`.
-                            KeyCode::KeyW => state.input.forward = pressed,
-                            KeyCode::KeyS => state.input.backward = pressed,
-                            KeyCode::KeyA => state.input.left = pressed,
-                            KeyCode::KeyD => state.input.right = pressed,
-                            KeyCode::Space => state.input.jump = pressed,
-                            KeyCode::ShiftLeft => state.input.sprint = pressed,
-
                             KeyCode::Escape if pressed => {
                                 // Escape always returns to the menu from gameplay.
                                 // Release the cursor at the same time so the UI is
@@ -330,152 +526,20 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
                                 state.game_state = GameState::Menu;
                                 state.mouse_captured = false;
                                 state.input = Default::default();
+                                state.show_player_list = false;
                                 state.digging = Default::default();
                                 let _ = state.window.set_cursor_grab(CursorGrabMode::None);
                                 state.window.set_cursor_visible(true);
                             }
 
-                            KeyCode::F11 if pressed => {
-                                if state.window.fullscreen().is_some() {
-                                    state.window.set_fullscreen(None);
-                                } else {
-                                    state.window.set_fullscreen(Some(
-                                        winit::window::Fullscreen::Borderless(None),
-                                    ));
-                                }
+                            // ---- Undo / redo of block edits -----------------
+                            KeyCode::KeyZ if pressed && state.modifiers.control_key() => {
+                                state.undo_edit();
                             }
-
-                            KeyCode::KeyR if pressed => {
-                                // Cycle: 0 = Off, 1 = SSR.  Wraps with modulo
-                                // so adding more modes in the future only
-                                // requires extending the match arm below.
-                                state.reflection_mode = (state.reflection_mode + 1) % 2;
-                                let mode_name = match state.reflection_mode {
-                                    0 => "Off",
-                                    1 => "SSR",
-                                    _ => "Unknown",
-                                };
-                                log(LogLevel::Info, &format!("Reflection mode: {}", mode_name));
-                            }
-
-                            // ---- F5: Save world to disk ---------------------
-                            KeyCode::F5 if pressed => {
-                                let world = state.world.read();
-                                // `SavedWorld::from_world` serializes only the
-                                // chunks that contain player-modified blocks, so
-                                // procedurally-generated terrain can be
-                                // regenerated from the seed on the next load.
-                                let saved = SavedWorld::from_world(
-                                    &world.chunks,
-                                    world.seed,
-                                    (
-                                        state.camera.position.x,
-                                        state.camera.position.y,
-                                        state.camera.position.z,
-                                    ),
-                                    (state.camera.yaw, state.camera.pitch),
-                                );
-                                if let Err(e) = save_world(DEFAULT_WORLD_FILE, &saved) {
-                                    log(LogLevel::Error, &format!("Failed to save world: {}", e));
-                                } else {
-                                    log(
-                                        LogLevel::Info,
-                                        &format!("World saved to {}", DEFAULT_WORLD_FILE),
-                                    );
-                                }
+                            KeyCode::KeyY if pressed && state.modifiers.control_key() => {
+                                state.redo_edit();
                             }
 
-                            // ---- F9: Load world from disk -------------------
-                            KeyCode::F9 if pressed => match load_world(DEFAULT_WORLD_FILE) {
-                                Ok(saved) => {
-                                    log(
-                                        LogLevel::Info,
-                                        &format!("Regenerating world with seed {}...", saved.seed),
-                                    );
-
-                                    // Reinitialize the world from the saved seed
-                                    // so procedurally-generated terrain is
-                                    // recreated, then overwrite individual blocks
-                                    // with the serialized player edits below.
-                                    {
-                                        let mut world = state.world.write();
-                                        *world = World::new_with_seed(saved.seed);
-                                    }
-
-                                    // Clear the indirect draw managers so they
-                                    // don't hold stale GPU buffer references from
-                                    // the previous world.
-                                    state.indirect_manager.clear();
-                                    state.water_indirect_manager.clear();
-
-                                    // Restore camera transform.
-                                    state.camera.position.x = saved.player_x;
-                                    state.camera.position.y = saved.player_y;
-                                    state.camera.position.z = saved.player_z;
-                                    state.camera.yaw = saved.player_yaw;
-                                    state.camera.pitch = saved.player_pitch;
-
-                                    // Overwrite sub-chunk block data with the
-                                    // serialized player edits.  Block data is
-                                    // stored flat (x-major, then y, then z) in
-                                    // the save file and must be unpacked in the
-                                    // same order here.
-                                    {
-                                        let mut world = state.world.write();
-                                        for chunk_data in &saved.chunks {
-                                            let cx = chunk_data.cx;
-                                            let cz = chunk_data.cz;
-                                            for (&sy, block_data) in &chunk_data.subchunks {
-                                                if let Some(chunk) = world.chunks.get_mut(&(cx, cz))
-                                                {
-                                                    if (sy as usize) < chunk.subchunks.len() {
-                                                        let subchunk =
-                                                            &mut chunk.subchunks[sy as usize];
-                                                        // Fill blocks in x→y→z order to match
-                                                        // the serialization order in save_world.
-                                                        let mut n = 0;
-                                                        for lx in 0..CHUNK_SIZE as usize {
-                                                            for ly in 0..SUBCHUNK_HEIGHT as usize {
-                                                                for lz in 0..CHUNK_SIZE as usize {
-                                                                    if n < block_data.len() {
-                                                                        subchunk.blocks[lx][ly]
-                                                                            [lz] = block_data[n];
-                                                                        n += 1;
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                        subchunk.is_empty = false;
-                                                        subchunk.mesh_dirty = true;
-                                                    }
-                                                    chunk.player_modified = true;
-                                                }
-                                            }
-                                        }
-                                    }
-
-                                    // Mark every sub-chunk dirty so the mesh
-                                    // loader rebuilds all GPU geometry on the
-                                    // next few frames (not just the edited ones).
-                                    {
-                                        let mut world = state.world.write();
-                                        for chunk in world.chunks.values_mut() {
-                                            for subchunk in &mut chunk.subchunks {
-                                                subchunk.mesh_dirty = true;
-                                            }
-                                        }
-                                    }
-                                    log(
-                                        LogLevel::Info,
-                                        &format!(
-                                            "World loaded from {} (seed: {})",
-                                            DEFAULT_WORLD_FILE, saved.seed
-                                        ),
-                                    );
-                                }
-                                Err(e) => log(LogLevel::Error, &format!("Error loading: {}", e)),
-                            },
-
                             // ---- Hotbar slot selection (1–9) ----------------
                             // Setting `hotbar_dirty` triggers a mesh rebuild of
                             // the hotbar UI on the next frame.
@@ -515,7 +579,264 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
                                 state.hotbar_slot = 8;
                                 state.hotbar_dirty = true;
                             }
-                            _ => {}
+
+                            _ => match state.key_bindings.action_for(key) {
+                                // Movement – held state polled each frame by `update`.
+                                Some(Action::Forward) => state.input.forward = pressed,
+                                Some(Action::Back) => state.input.backward = pressed,
+                                Some(Action::Left) => state.input.left = pressed,
+                                Some(Action::Right) => state.input.right = pressed,
+                                Some(Action::Jump) => {
+                                    state.input.jump = pressed;
+                                    // Held keys auto-repeat; only real taps count
+                                    // towards the double tap.
+                                    if pressed && !repeat {
+                                        state.register_jump_press();
+                                    }
+                                }
+                                Some(Action::Sprint) => state.input.sprint = pressed,
+                                Some(Action::FlySprint) => state.input.fly_sprint = pressed,
+                                Some(Action::PlayerList) => state.show_player_list = pressed,
+
+                                // ---- Chat / command line --------------------
+                                // Held movement keys are released so the player
+                                // does not keep walking while typing.
+                                Some(Action::Chat) if pressed => {
+                                    state.input = Default::default();
+                                    state.show_player_list = false;
+                                    state.chat.open_with("");
+                                }
+                                Some(Action::Command) if pressed => {
+                                    state.input = Default::default();
+                                    state.show_player_list = false;
+                                    state.chat.open_with("/");
+                                }
+
+                                Some(Action::Fullscreen) if pressed => {
+                                    if state.window.fullscreen().is_some() {
+                                        state.window.set_fullscreen(None);
+                                    } else {
+                                        state.window.set_fullscreen(Some(
+                                            winit::window::Fullscreen::Borderless(None),
+                                        ));
+                                    }
+                                }
+
+                                Some(Action::ToggleDebugOverlay) if pressed => {
+                                    state.show_debug_overlay = !state.show_debug_overlay;
+                                    if !state.show_debug_overlay {
+                                        state.debug_view = DebugView::Final;
+                                    }
+                                }
+
+                                Some(Action::CycleDebugView) if pressed => {
+                                    state.cycle_debug_view();
+                                }
+
+                                Some(Action::ToggleFaceHighlight) if pressed => {
+                                    state.show_face_highlight = !state.show_face_highlight;
+                                }
+
+                                Some(Action::ToggleChunkBorders) if pressed => {
+                                    state.show_chunk_borders = !state.show_chunk_borders;
+                                }
+
+                                Some(Action::TogglePlayerColors) if pressed => {
+                                    state.player_colors = !state.player_colors;
+                                }
+
+                                Some(Action::ToggleCelestialPath) if pressed => {
+                                    state.show_celestial_path = !state.show_celestial_path;
+                                }
+
+                                Some(Action::ToggleDigCracks) if pressed => {
+                                    state.show_dig_cracks = !state.show_dig_cracks;
+                                }
+
+                                Some(Action::ToggleBloom) if pressed => {
+                                    state.set_bloom_enabled(!state.bloom_enabled);
+                                }
+
+                                Some(Action::ToggleMinimap) if pressed => {
+                                    state.minimap.visible = !state.minimap.visible;
+                                }
+
+                                Some(Action::ToggleCoords) if pressed => {
+                                    state.show_coords = !state.show_coords;
+                                }
+
+                                Some(Action::ToggleSpectator) if pressed => {
+                                    state.toggle_spectator();
+                                }
+
+                                Some(Action::CycleReflections) if pressed => {
+                                    // Cycle: 0 = Off, 1 = SSR.  Wraps with modulo
+                                    // so adding more modes in the future only
+                                    // requires extending the match arm below.
+                                    state.reflection_mode = (state.reflection_mode + 1) % 2;
+                                    let mode_name = match state.reflection_mode {
+                                        0 => "Off",
+                                        1 => "SSR",
+                                        _ => "Unknown",
+                                    };
+                                    log(LogLevel::Info, &format!("Reflection mode: {}", mode_name));
+                                }
+
+                                // ---- Save world to disk ---------------------
+                                Some(Action::SaveWorld) if pressed => {
+                                    // Serializing and writing happen on a
+                                    // background thread (see `autosave.rs`).
+                                    state.save_world_in_background();
+                                }
+
+                                // ---- Load world from disk -------------------
+                                Some(Action::LoadWorld) if pressed => {
+                                    state.wait_for_world_save();
+                                    match load_world(DEFAULT_WORLD_FILE) {
+                                        Ok(saved) => {
+                                            log(
+                                                LogLevel::Info,
+                                                &format!(
+                                                    "Regenerating world with seed {}...",
+                                                    saved.seed
+                                                ),
+                                            );
+
+                                            // Reinitialize the world from the saved seed
+                                            // so procedurally-generated terrain is
+                                            // recreated, then overwrite individual blocks
+                                            // with the serialized player edits below.
+                                            {
+                                                let mut world = state.world.write();
+                                                *world = World::new_with_config(
+                                                    saved.seed,
+                                                    saved.sea_level,
+                                                    saved.gen_config,
+                                                );
+                                            }
+                                            // Stream the rest of the world from the
+                                            // saved seed, sea level and generation
+                                            // settings, not the ones the current
+                                            // session started with.
+                                            state.chunk_loader = ChunkLoader::with_worker_count(
+                                                state.chunk_workers,
+                                                saved.seed,
+                                                saved.sea_level,
+                                                saved.gen_config,
+                                            );
+                                            state.edit_history.clear();
+                                            state.pending_box_edits.clear();
+                                            state.block_mutations.clear();
+
+                                            // Clear the indirect draw managers so they
+                                            // don't hold stale GPU buffer references from
+                                            // the previous world, and drop its meshes
+                                            // still waiting for upload.
+                                            state.indirect_manager.clear();
+                                            state.water_indirect_manager.clear();
+                                            state.mesh_uploads.clear();
+
+                                            // Restore camera transform.
+                                            state.camera.set_spectating(false);
+                                            state.camera.position.x = saved.player_x;
+                                            state.camera.position.y = saved.player_y;
+                                            state.camera.position.z = saved.player_z;
+                                            state.camera.yaw = saved.player_yaw;
+                                            state.camera.pitch = saved.player_pitch;
+                                            state.camera.set_creative(saved.creative);
+                                            state.inventory = saved.inventory.clone();
+
+                                            // Resume the day cycle where it was saved.
+                                            state.time_of_day = saved.time_of_day;
+                                            state.time_scale = saved.time_scale;
+
+                                            // Overwrite sub-chunk block data with the
+                                            // serialized player edits.  Block data is
+                                            // stored flat (x-major, then y, then z) in
+                                            // the save file and must be unpacked in the
+                                            // same order here.
+                                            {
+                                                let mut world = state.world.write();
+                                                for chunk_data in &saved.chunks {
+                                                    let cx = chunk_data.cx;
+                                                    let cz = chunk_data.cz;
+                                                    for (&sy, block_data) in &chunk_data.subchunks {
+                                                        if let Some(chunk) =
+                                                            world.chunks.get_mut(&(cx, cz))
+                                                        {
+                                                            if (sy as usize) < chunk.subchunks.len()
+                                                            {
+                                                                let subchunk = &mut chunk.subchunks
+                                                                    [sy as usize];
+                                                                // Fill blocks in x→y→z order to match
+                                                                // the serialization order in save_world.
+                                                                let mut n = 0;
+                                                                for lx in 0..CHUNK_SIZE as usize {
+                                                                    for ly in
+                                                                        0..SUBCHUNK_HEIGHT as usize
+                                                                    {
+                                                                        for lz in
+                                                                            0..CHUNK_SIZE as usize
+                                                                        {
+                                                                            if n < block_data.len()
+                                                                            {
+                                                                                subchunk.blocks
+                                                                                    [lx][ly][lz] =
+                                                                                    block_data[n];
+                                                                                n += 1;
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                                subchunk.facings = chunk_data
+                                                                    .facings
+                                                                    .get(&sy)
+                                                                    .map(|f| {
+                                                                        f.iter().copied().collect()
+                                                                    })
+                                                                    .unwrap_or_default();
+                                                                subchunk.water_levels = chunk_data
+                                                                    .water_levels
+                                                                    .get(&sy)
+                                                                    .map(|l| {
+                                                                        l.iter().copied().collect()
+                                                                    })
+                                                                    .unwrap_or_default();
+                                                                subchunk.is_empty = false;
+                                                                subchunk.mark_mesh_dirty();
+                                                            }
+                                                            chunk.player_modified = true;
+                                                        }
+                                                    }
+                                                }
+                                            }
+
+                                            // Mark every sub-chunk dirty so the mesh
+                                            // loader rebuilds all GPU geometry on the
+                                            // next few frames (not just the edited ones).
+                                            {
+                                                let mut world = state.world.write();
+                                                for chunk in world.chunks.values_mut() {
+                                                    for subchunk in &mut chunk.subchunks {
+                                                        subchunk.mark_mesh_dirty();
+                                                    }
+                                                }
+                                            }
+                                            log(
+                                                LogLevel::Info,
+                                                &format!(
+                                                    "World loaded from {} (seed: {})",
+                                                    DEFAULT_WORLD_FILE, saved.seed
+                                                ),
+                                            );
+                                        }
+                                        Err(e) => {
+                                            log(LogLevel::Error, &format!("Error loading: {}", e))
+                                        }
+                                    }
+                                }
+                                _ => {}
+                            },
                         }
                     }
                 }
@@ -533,14 +854,20 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
                             MouseScrollDelta::LineDelta(_, y) => y,
                             MouseScrollDelta::PixelDelta(pos) => pos.y as f32 / 20.0,
                         };
-                        let slots = crate::ui::ui::HOTBAR_SLOTS.len() as i32;
-                        // `rem_euclid` wraps correctly for negative values
-                        // (scrolling backward past slot 0 lands on the last slot).
-                        let new_slot = (state.hotbar_slot as i32 - scroll.signum() as i32)
-                            .rem_euclid(slots) as usize;
-                        if new_slot != state.hotbar_slot {
-                            state.hotbar_slot = new_slot;
-                            state.hotbar_dirty = true;
+                        if state.camera.spectating() {
+                            // Scrolling up speeds the spectator camera up.
+                            state.camera.adjust_spectator_speed(scroll);
+                        } else {
+                            let slots = crate::ui::ui::HOTBAR_SLOTS.len() as i32;
+                            // `rem_euclid` wraps correctly for negative values
+                            // (scrolling backward past slot 0 lands on the last slot).
+                            let new_slot = (state.hotbar_slot as i32 - scroll.signum() as i32)
+                                .rem_euclid(slots)
+                                as usize;
+                            if new_slot != state.hotbar_slot {
+                                state.hotbar_slot = new_slot;
+                                state.hotbar_dirty = true;
+                            }
                         }
                     }
                 }
@@ -566,6 +893,8 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
                             if let Some((x, y)) = state.cursor_position {
                                 state.handle_menu_click(x, y);
                             }
+                        } else if button == winit::event::MouseButton::Left {
+                            state.finish_menu_drag();
                         }
                     } else if pressed && !state.mouse_captured {
                         // First click in-game captures the cursor so subsequent
@@ -587,11 +916,22 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
                         ));
                     } else {
                         // Cursor already captured: forward to the block
-                        // interaction handler (left = dig, right = place).
+                        // interaction handler (left = dig, right = place,
+                        // middle = pick block).
                         state.handle_mouse_input(button, pressed);
                     }
                 }
 
+                // ── Modifier keys ─────────────────────────────────────────── //
+                // Tracked separately because `KeyboardInput` only reports the
+                // key itself; needed for the Ctrl+Z / Ctrl+Y shortcuts.
+                Event::WindowEvent {
+                    event: WindowEvent::ModifiersChanged(modifiers),
+                    ..
+                } => {
+                    state.modifiers = modifiers.state();
+                }
+
                 // ── Cursor position tracking ──────────────────────────────── //
                 // Updated unconditionally so menu hover effects and hit-testing
                 // always have an up-to-date position even while the menu is open.
@@ -600,6 +940,9 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
                     ..
                 } => {
                     state.cursor_position = Some((position.x as f32, position.y as f32));
+                    if state.game_state == GameState::Menu {
+                        state.handle_menu_drag(position.x as f32);
+                    }
                 }
 
                 // ── Raw mouse motion → camera rotation ────────────────────── //
@@ -650,7 +993,22 @@ pub fn run_game() -> Result<(), Box<dyn std::error::Error>> {
                 Event::WindowEvent {
                     event: WindowEvent::CloseRequested,
                     ..
-                } => elwt.exit(),
+                } => {
+                    // Save a singleplayer world before exiting, also from the
+                    // pause menu, so closing the window never loses progress
+                    // since the last autosave.
+                    if state.autosave_enabled() {
+                        state.save_world_blocking();
+                    }
+                    elwt.exit();
+                }
+
+                // ── Shutdown ──────────────────────────────────────────────── //
+                // Join the loader threads so none is cut off mid-chunk.
+                Event::LoopExiting => {
+                    state.chunk_loader.shutdown();
+                    state.mesh_loader.shutdown();
+                }
 
                 _ => {}
             }
@@ -0,0 +1,186 @@
+use std::time::Instant;
+
+use gilrs::{Axis, Button, EventType, GamepadId, Gilrs};
+use minerust::{GAMEPAD_LOOK_SENSITIVITY, GAMEPAD_STICK_DEADZONE, InputState};
+use winit::window::CursorGrabMode;
+
+use crate::logger::{LogLevel, log};
+use crate::ui::menu::GameState;
+use crate::ui::ui::HOTBAR_SLOTS;
+
+use super::state::State;
+
+/// Controller state polled once per frame.
+///
+/// # Bindings
+///
+/// | Control | Action |
+/// |---|---|
+/// | Left stick | Move. |
+/// | Right stick | Look around. |
+/// | Right trigger / bumper | Break the targeted block (hold). |
+/// | Left trigger / bumper | Place the selected block. |
+/// | South (A / Cross) | Jump; fly up in creative mode. |
+/// | East (B / Circle) or left stick click | Sprint.  In creative mode East flies down and the stick click sprints. |
+/// | D-pad left / right | Previous / next hotbar slot. |
+/// | Start | Leave the menu and capture the cursor. |
+pub struct GamepadInput {
+    gilrs: Gilrs,
+    /// The pad that most recently sent an event; only it drives the player.
+    active: Option<GamepadId>,
+    /// Place button state on the previous poll, so holding it places once.
+    place_held: bool,
+    /// Camera rotation in radians per second at full right-stick deflection.
+    pub look_sensitivity: f32,
+}
+
+impl GamepadInput {
+    /// Starts the gilrs backend.
+    ///
+    /// # Returns
+    /// `None` (after logging the reason) if the platform has no gamepad
+    /// support, in which case the game runs with keyboard and mouse only.
+    pub fn new() -> Option<Self> {
+        match Gilrs::new() {
+            Ok(gilrs) => {
+                let active = gilrs.gamepads().next().map(|(id, pad)| {
+                    log(LogLevel::Info, &format!("Gamepad found: {}", pad.name()));
+                    id
+                });
+                Some(Self {
+                    gilrs,
+                    active,
+                    place_held: false,
+                    look_sensitivity: GAMEPAD_LOOK_SENSITIVITY,
+                })
+            }
+            Err(e) => {
+                log(
+                    LogLevel::Warning,
+                    &format!("Gamepad support unavailable: {}", e),
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Rescales a stick axis so the dead zone maps to 0 and full deflection
+/// still reaches ±1.
+fn apply_deadzone(value: f32) -> f32 {
+    if value.abs() <= GAMEPAD_STICK_DEADZONE {
+        0.0
+    } else {
+        value.signum() * (value.abs() - GAMEPAD_STICK_DEADZONE) / (1.0 - GAMEPAD_STICK_DEADZONE)
+    }
+}
+
+impl State {
+    /// Reads the active gamepad and applies it to the player.
+    ///
+    /// Called every frame from the event loop before [`State::update`].
+    /// Held actions are written to `pad_input` rather than `input`, so the
+    /// keyboard and mouse keep working alongside the controller.  Like mouse
+    /// look, the controller only drives the player while the cursor is
+    /// captured and the chat box is closed.
+    pub fn poll_gamepad(&mut self) {
+        let Some(pad) = &mut self.gamepad else {
+            return;
+        };
+
+        // Events must be drained for gilrs to update its cached state.
+        let mut start_pressed = false;
+        let mut hotbar_step = 0i32;
+        while let Some(event) = pad.gilrs.next_event() {
+            self.last_input_time = Instant::now();
+            match event.event {
+                EventType::Connected => {
+                    log(
+                        LogLevel::Info,
+                        &format!("Gamepad connected: {}", pad.gilrs.gamepad(event.id).name()),
+                    );
+                }
+                EventType::Disconnected => {
+                    log(LogLevel::Info, "Gamepad disconnected");
+                    if pad.active == Some(event.id) {
+                        pad.active = None;
+                    }
+                    continue;
+                }
+                EventType::ButtonPressed(Button::Start, _) => start_pressed = true,
+                EventType::ButtonPressed(Button::DPadLeft, _) => hotbar_step -= 1,
+                EventType::ButtonPressed(Button::DPadRight, _) => hotbar_step += 1,
+                _ => {}
+            }
+            pad.active = Some(event.id);
+        }
+
+        let controls_player =
+            self.game_state == GameState::Playing && self.mouse_captured && !self.chat.open;
+        let Some(id) = pad.active.filter(|_| controls_player) else {
+            pad.place_held = false;
+            self.pad_input = InputState::default();
+            if start_pressed && !self.chat.open {
+                self.resume_with_gamepad();
+            }
+            return;
+        };
+        let gamepad = pad.gilrs.gamepad(id);
+
+        let move_x = apply_deadzone(gamepad.value(Axis::LeftStickX));
+        let move_y = apply_deadzone(gamepad.value(Axis::LeftStickY));
+        let look_x = apply_deadzone(gamepad.value(Axis::RightStickX));
+        let look_y = apply_deadzone(gamepad.value(Axis::RightStickY));
+        let place =
+            gamepad.is_pressed(Button::LeftTrigger2) || gamepad.is_pressed(Button::LeftTrigger);
+
+        self.pad_input = InputState {
+            forward: move_y > 0.0,
+            backward: move_y < 0.0,
+            left: move_x < 0.0,
+            right: move_x > 0.0,
+            jump: gamepad.is_pressed(Button::South),
+            // East descends while flying, so the stick click alone sprints there.
+            sprint: gamepad.is_pressed(Button::East)
+                || (gamepad.is_pressed(Button::LeftThumb) && !self.camera.creative),
+            fly_sprint: gamepad.is_pressed(Button::LeftThumb),
+            left_mouse: gamepad.is_pressed(Button::RightTrigger2)
+                || gamepad.is_pressed(Button::RightTrigger),
+            right_mouse: place,
+        };
+
+        // Same frame-time measure `update` uses, capped the same way.
+        let dt = self.last_frame.elapsed().as_secs_f32().min(0.1);
+        let turn = pad.look_sensitivity * dt;
+        self.camera.yaw += look_x * turn;
+        // Stick up is positive Y and should pitch the camera up.
+        self.camera.pitch = (self.camera.pitch + look_y * turn).clamp(
+            -std::f32::consts::FRAC_PI_2 + 0.1,
+            std::f32::consts::FRAC_PI_2 - 0.1,
+        );
+
+        let place_pressed = place && !pad.place_held;
+        pad.place_held = place;
+
+        if hotbar_step != 0 {
+            let slots = HOTBAR_SLOTS.len() as i32;
+            self.hotbar_slot = (self.hotbar_slot as i32 + hotbar_step).rem_euclid(slots) as usize;
+            self.hotbar_dirty = true;
+        }
+        if place_pressed {
+            self.place_block();
+        }
+    }
+
+    /// Leaves the menu and captures the cursor, like Escape in the menu or
+    /// the first click in game, so the controller can take over.
+    fn resume_with_gamepad(&mut self) {
+        self.game_state = GameState::Playing;
+        self.mouse_captured = true;
+        let _ = self
+            .window
+            .set_cursor_grab(CursorGrabMode::Confined)
+            .or_else(|_| self.window.set_cursor_grab(CursorGrabMode::Locked));
+        self.window.set_cursor_visible(false);
+    }
+}
@@ -0,0 +1,265 @@
+use std::cell::Cell;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+/// A group of consecutive passes timed as one entry in the debug overlay.
+///
+/// Variants are listed in the order the passes are recorded.
+#[derive(Clone, Copy)]
+pub enum GpuPhase {
+    Shadows,
+    DepthPrepass,
+    ShadowMask,
+    HiZ,
+    Opaque,
+    /// The water pass, which also does the screen-space reflections.
+    Water,
+    Bloom,
+    Composite,
+    /// From the in-game UI pass to the end of the text pass, so it also
+    /// covers the progress bar.  Not timed outside active play.
+    Ui,
+}
+
+impl GpuPhase {
+    const ALL: [GpuPhase; 9] = [
+        GpuPhase::Shadows,
+        GpuPhase::DepthPrepass,
+        GpuPhase::ShadowMask,
+        GpuPhase::HiZ,
+        GpuPhase::Opaque,
+        GpuPhase::Water,
+        GpuPhase::Bloom,
+        GpuPhase::Composite,
+        GpuPhase::Ui,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            GpuPhase::Shadows => "shadows",
+            GpuPhase::DepthPrepass => "depth prepass",
+            GpuPhase::ShadowMask => "shadow mask",
+            GpuPhase::HiZ => "hi-z",
+            GpuPhase::Opaque => "opaque",
+            GpuPhase::Water => "water + SSR",
+            GpuPhase::Bloom => "bloom",
+            GpuPhase::Composite => "composite",
+            GpuPhase::Ui => "UI & text",
+        }
+    }
+
+    /// Query index of the phase's begin timestamp; the end follows it.
+    fn begin_query(self) -> u32 {
+        self as u32 * 2
+    }
+}
+
+/// Two timestamps (begin and end) per phase.
+const QUERY_COUNT: u32 = GpuPhase::ALL.len() as u32 * 2;
+
+/// Each query resolves to its own aligned slot, so written queries can be
+/// resolved one by one without touching unwritten ones.
+const SLOT_SIZE: u64 = wgpu::QUERY_RESOLVE_BUFFER_ALIGNMENT;
+
+/// Where the readback buffer is in its copy → map → read cycle.
+enum Readback {
+    /// Free to receive the next frame's timestamps.
+    Idle,
+    /// A copy was recorded; it is mapped once the frame is submitted.
+    Copied,
+    /// Mapping was requested; the callback stores its outcome here.
+    Mapping(Arc<Mutex<Option<bool>>>),
+}
+
+/// Times the render phases of a frame with GPU timestamp queries.
+///
+/// Passes ask for their timestamp writes with [`render_writes`] or
+/// [`compute_writes`]; the frame's queries are resolved just before submit
+/// and read back a frame or two later, without ever stalling on the GPU.
+/// While a readback is still in flight, new frames are simply not measured.
+///
+/// Only created when the device has `TIMESTAMP_QUERY`.
+///
+/// [`render_writes`]: GpuTimer::render_writes
+/// [`compute_writes`]: GpuTimer::compute_writes
+pub struct GpuTimer {
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    /// Nanoseconds per timestamp tick.
+    period: f32,
+    /// Bit `i` is set once query `i` has been written this frame.
+    written: Cell<u32>,
+    /// `written` of the frame whose timestamps are in `readback_buffer`.
+    readback_written: u32,
+    readback: Readback,
+    /// GPU time per phase of the last frame read back, in milliseconds.
+    last: Vec<(&'static str, f32)>,
+}
+
+impl GpuTimer {
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let size = QUERY_COUNT as u64 * SLOT_SIZE;
+        Self {
+            query_set: device.create_query_set(&wgpu::QuerySetDescriptor {
+                label: Some("GPU Timer Queries"),
+                ty: wgpu::QueryType::Timestamp,
+                count: QUERY_COUNT,
+            }),
+            resolve_buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("GPU Timer Resolve Buffer"),
+                size,
+                usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            }),
+            readback_buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("GPU Timer Readback Buffer"),
+                size,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            period: queue.get_timestamp_period(),
+            written: Cell::new(0),
+            readback_written: 0,
+            readback: Readback::Idle,
+            last: Vec::new(),
+        }
+    }
+
+    /// Starts a new frame: picks up the timings of an earlier frame if its
+    /// readback has finished and forgets which queries were written.
+    pub fn begin_frame(&mut self, device: &wgpu::Device) {
+        self.written.set(0);
+        let Readback::Mapping(result) = &self.readback else {
+            return;
+        };
+        // Never waits; only runs the callbacks of mappings that are done.
+        let _ = device.poll(wgpu::PollType::Poll);
+        let Some(mapped) = result.lock().take() else {
+            return;
+        };
+        if mapped {
+            let data = self.readback_buffer.slice(..).get_mapped_range();
+            let timestamp = |query: u32| {
+                let offset = (query as u64 * SLOT_SIZE) as usize;
+                u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+            };
+            self.last.clear();
+            for phase in GpuPhase::ALL {
+                let begin = phase.begin_query();
+                let both = 0b11 << begin;
+                if self.readback_written & both == both {
+                    let ticks = timestamp(begin + 1).wrapping_sub(timestamp(begin));
+                    let ms = ticks as f64 * self.period as f64 / 1_000_000.0;
+                    self.last.push((phase.name(), ms as f32));
+                }
+            }
+            drop(data);
+            self.readback_buffer.unmap();
+        }
+        self.readback = Readback::Idle;
+    }
+
+    /// Claims the begin and/or end query of `phase` for this frame, or
+    /// returns `None` if neither is wanted or the readback is still busy.
+    fn claim(&self, phase: GpuPhase, begin: bool, end: bool) -> Option<(Option<u32>, Option<u32>)> {
+        if !matches!(self.readback, Readback::Idle) || !(begin || end) {
+            return None;
+        }
+        let first = phase.begin_query();
+        let begin = begin.then_some(first);
+        let end = end.then_some(first + 1);
+        let mut written = self.written.get();
+        for query in begin.into_iter().chain(end) {
+            written |= 1 << query;
+        }
+        self.written.set(written);
+        Some((begin, end))
+    }
+
+    /// Timestamp writes for a render pass that begins and/or ends `phase`.
+    pub fn render_writes(
+        &self,
+        phase: GpuPhase,
+        begin: bool,
+        end: bool,
+    ) -> Option<wgpu::RenderPassTimestampWrites<'_>> {
+        let (begin, end) = self.claim(phase, begin, end)?;
+        Some(wgpu::RenderPassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: begin,
+            end_of_pass_write_index: end,
+        })
+    }
+
+    /// Timestamp writes for a compute pass that begins and/or ends `phase`.
+    pub fn compute_writes(
+        &self,
+        phase: GpuPhase,
+        begin: bool,
+        end: bool,
+    ) -> Option<wgpu::ComputePassTimestampWrites<'_>> {
+        let (begin, end) = self.claim(phase, begin, end)?;
+        Some(wgpu::ComputePassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: begin,
+            end_of_pass_write_index: end,
+        })
+    }
+
+    /// Records the resolve of this frame's queries and their copy into the
+    /// readback buffer.  Call after the last timed pass, before submit.
+    pub fn resolve(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        let written = self.written.get();
+        if !matches!(self.readback, Readback::Idle) || written == 0 {
+            return;
+        }
+        // Resolve only the written queries; unwritten ones have no defined
+        // value on every backend.
+        for query in (0..QUERY_COUNT).filter(|query| written & (1 << query) != 0) {
+            encoder.resolve_query_set(
+                &self.query_set,
+                query..query + 1,
+                &self.resolve_buffer,
+                query as u64 * SLOT_SIZE,
+            );
+        }
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &self.readback_buffer,
+            0,
+            QUERY_COUNT as u64 * SLOT_SIZE,
+        );
+        self.readback_written = written;
+        self.readback = Readback::Copied;
+    }
+
+    /// Starts mapping the readback buffer once the frame that filled it has
+    /// been submitted.
+    pub fn after_submit(&mut self) {
+        if !matches!(self.readback, Readback::Copied) {
+            return;
+        }
+        let result = Arc::new(Mutex::new(None));
+        let callback_result = Arc::clone(&result);
+        self.readback_buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |mapped| {
+                *callback_result.lock() = Some(mapped.is_ok());
+            });
+        self.readback = Readback::Mapping(result);
+    }
+
+    /// Formats the last frame read back as one `"name: 1.23 ms"` line per
+    /// phase that was timed, in the order they ran.
+    pub fn last_frame_summary(&self) -> String {
+        let mut summary = String::new();
+        for (name, ms) in &self.last {
+            let _ = writeln!(summary, "{}: {:.2} ms", name, ms);
+        }
+        summary
+    }
+}
@@ -0,0 +1,162 @@
+use minerust::{RENDER_DISTANCE, ShadowConfig, get_active_cascade_count};
+use serde::{Deserialize, Serialize};
+
+use crate::logger::{LogLevel, log};
+
+use super::state::State;
+
+/// Shadow quality preset chosen in the menu.
+///
+/// Each step trades shadow sharpness and reach for VRAM and shadow-pass
+/// time: the preset picks the per-cascade shadow map resolution and the
+/// maximum number of cascades.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphicsQuality {
+    /// 1024² shadow maps and two cascades, for low-end GPUs.
+    Low,
+    /// 2048² shadow maps and up to four cascades.
+    #[default]
+    Medium,
+    /// 4096² shadow maps and up to four cascades.
+    High,
+}
+
+impl GraphicsQuality {
+    /// Returns the next preset, wrapping from `High` back to `Low`.
+    pub fn next(self) -> Self {
+        match self {
+            GraphicsQuality::Low => GraphicsQuality::Medium,
+            GraphicsQuality::Medium => GraphicsQuality::High,
+            GraphicsQuality::High => GraphicsQuality::Low,
+        }
+    }
+
+    /// Short name shown on the menu toggle button.
+    pub fn label(self) -> &'static str {
+        match self {
+            GraphicsQuality::Low => "LOW",
+            GraphicsQuality::Medium => "MEDIUM",
+            GraphicsQuality::High => "HIGH",
+        }
+    }
+
+    /// Width and height of one shadow cascade layer in texels.
+    pub fn shadow_map_size(self) -> u32 {
+        match self {
+            GraphicsQuality::Low => 1024,
+            GraphicsQuality::Medium => 2048,
+            GraphicsQuality::High => 4096,
+        }
+    }
+
+    /// Number of cascades to render.
+    ///
+    /// Capped by [`get_active_cascade_count`], since short render distances
+    /// do not reach the far splits anyway.
+    pub fn cascade_count(self) -> usize {
+        let max_cascades = match self {
+            GraphicsQuality::Low => 2,
+            GraphicsQuality::Medium | GraphicsQuality::High => 4,
+        };
+        max_cascades.min(get_active_cascade_count(RENDER_DISTANCE))
+    }
+
+    /// Shadow settings buffer contents for this preset.
+    pub fn shadow_config(self) -> ShadowConfig {
+        ShadowConfig {
+            shadow_map_size: self.shadow_map_size() as f32,
+            pcf_samples: 16,
+            cascade_count: self.cascade_count() as u32,
+            _pad: 0,
+        }
+    }
+}
+
+/// Allocates the cascaded shadow map for `quality`.
+///
+/// The Depth32Float texture array has one layer per rendered cascade, so
+/// lower presets also save the memory of the unused layers.
+///
+/// # Returns
+/// The `D2Array` view sampled by the terrain and water shaders, and one `D2`
+/// view per cascade used as the shadow pass render target (wgpu render
+/// attachments cannot target array layers through an array view).
+pub fn create_shadow_map(
+    device: &wgpu::Device,
+    quality: GraphicsQuality,
+) -> (wgpu::TextureView, Vec<wgpu::TextureView>) {
+    let size = quality.shadow_map_size();
+    let layers = quality.cascade_count() as u32;
+    let shadow_texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Shadow Map"),
+        size: wgpu::Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: layers,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Depth32Float,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+
+    // The dimension must be explicit: a single-layer texture would otherwise
+    // get a plain `D2` view, which does not match the shaders' binding.
+    let array_view = shadow_texture.create_view(&wgpu::TextureViewDescriptor {
+        label: Some("Shadow Map Array View"),
+        dimension: Some(wgpu::TextureViewDimension::D2Array),
+        ..Default::default()
+    });
+
+    let cascade_views = (0..layers)
+        .map(|i| {
+            shadow_texture.create_view(&wgpu::TextureViewDescriptor {
+                label: Some(&format!("Shadow Map Cascade View {}", i)),
+                dimension: Some(wgpu::TextureViewDimension::D2),
+                base_array_layer: i,
+                array_layer_count: Some(1),
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    (array_view, cascade_views)
+}
+
+impl State {
+    /// Switches the shadow quality preset.
+    ///
+    /// Reallocates the shadow map at the new resolution, reconfigures the
+    /// cascade manager and rewrites the shadow settings buffer.  The array
+    /// view is baked into `uniform_bind_group` and `water_bind_group`, so
+    /// both are rebuilt; the change takes effect on the next frame and is
+    /// saved in the settings file.
+    pub fn set_graphics_quality(&mut self, quality: GraphicsQuality) {
+        self.graphics_quality = quality;
+        let (shadow_texture_view, shadow_cascade_views) = create_shadow_map(&self.device, quality);
+        self.shadow_texture_view = shadow_texture_view;
+        self.shadow_cascade_views = shadow_cascade_views;
+        self.csm
+            .configure(quality.cascade_count(), quality.shadow_map_size());
+        self.queue.write_buffer(
+            &self.shadow_config_buffer,
+            0,
+            bytemuck::cast_slice(&[quality.shadow_config()]),
+        );
+        self.uniform_bind_group = self.create_uniform_bind_group();
+        self.water_bind_group = self.create_water_bind_group();
+        self.save_settings();
+        log(
+            LogLevel::Info,
+            &format!(
+                "Graphics quality set to {:?} ({}px shadow map, {} cascades)",
+                quality,
+                quality.shadow_map_size(),
+                quality.cascade_count()
+            ),
+        );
+    }
+}
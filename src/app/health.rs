@@ -0,0 +1,135 @@
+use std::f32::consts::TAU;
+
+use crate::logger::{LogLevel, log};
+use crate::multiplayer::protocol::Packet;
+
+use super::state::State;
+
+/// Health of an unhurt player.
+pub const MAX_HEALTH: f32 = 20.0;
+
+/// Blocks a player can fall without getting hurt; every further block
+/// costs one point of health.
+const SAFE_FALL_DISTANCE: f32 = 3.0;
+
+/// Health regained per second while below [`MAX_HEALTH`].
+const HEALTH_REGEN: f32 = 0.25;
+
+/// Seconds the red flash takes to fade out after a hit.
+pub const HURT_FLASH_DURATION: f32 = 0.5;
+
+/// Damage of a hit that flashes the screen at full strength; smaller hits
+/// flash weaker.
+const FULL_FLASH_DAMAGE: f32 = 10.0;
+
+/// Weakest flash, so even a one-point hit is noticed.
+const MIN_FLASH_STRENGTH: f32 = 0.25;
+
+/// Health at or below which the screen edges pulse red.
+const LOW_HEALTH: f32 = 6.0;
+
+/// Pulses per second of the low-health warning.
+const LOW_HEALTH_PULSE_RATE: f32 = 1.2;
+
+/// Returns the opacity of the red damage vignette as `(edges, centre)`.
+///
+/// A hit flashes the whole screen, strongest at the edges, fading out over
+/// [`HURT_FLASH_DURATION`] as `hurt_timer` runs down; `hurt_strength`
+/// scales it with the damage.  At [`LOW_HEALTH`] or below the edges also
+/// pulse with the clock `time`, deeper the less health is left.
+pub fn damage_vignette(hurt_timer: f32, hurt_strength: f32, health: f32, time: f32) -> (f32, f32) {
+    let flash = hurt_strength * (hurt_timer / HURT_FLASH_DURATION).clamp(0.0, 1.0);
+    let mut edges = 0.6 * flash;
+    let centre = 0.25 * flash;
+    if health <= LOW_HEALTH {
+        let danger = 1.0 - health.max(0.0) / (LOW_HEALTH + 1.0);
+        let beat = 0.5 + 0.5 * (time * LOW_HEALTH_PULSE_RATE * TAU).sin();
+        edges = edges.max(danger * (0.15 + 0.25 * beat));
+    }
+    (edges.min(0.8), centre)
+}
+
+impl State {
+    /// Takes `amount` health from the player and flashes the screen red.
+    ///
+    /// Creative and spectating players cannot be hurt.  At zero health the
+    /// player respawns.
+    pub fn hurt(&mut self, amount: f32) {
+        if amount <= 0.0 || self.camera.creative || self.camera.spectating() {
+            return;
+        }
+        self.health -= amount;
+        self.hurt_timer = HURT_FLASH_DURATION;
+        self.hurt_strength = (amount / FULL_FLASH_DAMAGE).clamp(MIN_FLASH_STRENGTH, 1.0);
+        if self.health <= 0.0 {
+            self.respawn();
+        }
+    }
+
+    /// Puts the player back at the spawn point with full health.
+    ///
+    /// In multiplayer the server is told with a `Respawn` packet, so the
+    /// jump back to spawn is not rejected by its speed cap.
+    fn respawn(&mut self) {
+        log(LogLevel::Info, "Player died, respawning");
+        self.chat.push_message("You died".to_string());
+        let spawn = self.world.write().find_safe_spawn_point();
+        self.camera.position = glam::Vec3::new(spawn.0, spawn.1, spawn.2);
+        self.camera.velocity = glam::Vec3::ZERO;
+        self.camera.on_ground = false;
+        self.health = MAX_HEALTH;
+        if let Some(tx) = &self.network_tx {
+            let _ = tx.send(Packet::Respawn {
+                player_id: self.my_player_id,
+                x: spawn.0,
+                y: spawn.1,
+                z: spawn.2,
+            });
+        }
+    }
+
+    /// Applies fall damage for a landing in the last physics step, fades
+    /// the hurt flash and regenerates health.
+    pub fn update_health(&mut self, dt: f32) {
+        if let Some(fall) = self.camera.take_fall() {
+            self.hurt((fall - SAFE_FALL_DISTANCE).floor());
+        }
+        self.hurt_timer = (self.hurt_timer - dt).max(0.0);
+        self.health = (self.health + HEALTH_REGEN * dt).min(MAX_HEALTH);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hurt_flash_fades_and_low_health_pulses() {
+        // A full-health player shows nothing until hit.
+        assert_eq!(damage_vignette(0.0, 1.0, MAX_HEALTH, 0.0), (0.0, 0.0));
+
+        // Harder hits flash stronger, and the flash fades out.
+        let weak = damage_vignette(HURT_FLASH_DURATION, 0.3, MAX_HEALTH, 0.0);
+        let strong = damage_vignette(HURT_FLASH_DURATION, 1.0, MAX_HEALTH, 0.0);
+        assert!(strong.0 > weak.0 && strong.1 > weak.1);
+        let fading = damage_vignette(HURT_FLASH_DURATION / 2.0, 1.0, MAX_HEALTH, 0.0);
+        assert!(fading.0 < strong.0 && fading.0 > 0.0);
+
+        // At low health only the edges pulse.
+        let pulse: Vec<_> = (0..10)
+            .map(|i| damage_vignette(0.0, 0.0, 2.0, i as f32 * 0.1))
+            .collect();
+        assert!(
+            pulse
+                .iter()
+                .all(|&(edges, centre)| edges > 0.0 && centre == 0.0)
+        );
+        let (low, high) = pulse
+            .iter()
+            .fold((1.0f32, 0.0f32), |(low, high), &(edges, _)| {
+                (low.min(edges), high.max(edges))
+            });
+        assert!(high - low > 0.1);
+        assert!(damage_vignette(0.0, 0.0, 1.0, 0.25).0 > damage_vignette(0.0, 0.0, 5.0, 0.25).0);
+    }
+}
@@ -1,25 +1,52 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::Instant;
 
 use bytemuck;
 use glam::{Mat4, Vec4};
 use glyphon::{
-    Cache, FontSystem, Metrics, Resolution, SwashCache, TextAtlas, TextRenderer, Viewport,
+    Attrs, Cache, Family, FontSystem, Metrics, Resolution, Shaping, SwashCache, TextAtlas,
+    TextRenderer, Viewport, fontdb,
 };
 use wgpu::util::DeviceExt;
+use winit::keyboard::ModifiersState;
 use winit::window::Window;
 
-use crate::app::texture_cache;
+use crate::app::texture_cache::{AtlasLoader, create_placeholder_atlas};
 use crate::logger::{LogLevel, log};
-use crate::ui::menu::{GameState, MenuState};
+use crate::ui::chat::ChatState;
+use crate::ui::menu::{BINDING_ROW_HEIGHT, GameState, MenuState};
+use crate::ui::minimap::Minimap;
+use crate::ui::ui::HOTBAR_SLOTS;
 use minerust::chunk_loader::ChunkLoader;
 use minerust::{
-    CSM_SHADOW_MAP_SIZE, Camera, DiggingState, IndirectManager, InputState, OutlineVertex,
-    RENDER_DISTANCE, SEA_LEVEL, ShadowConfig, Uniforms, Vertex, World, build_crosshair,
+    BlockMutationQueue, CHUNK_BORDER_RADIUS, Camera, DiggingState, EDIT_HISTORY_MAX_BLOCKS,
+    EDIT_HISTORY_MAX_OPERATIONS, EditHistory, IndirectManager, InputState, Inventory,
+    OutlineVertex, ParticleSystem, RENDER_DISTANCE, SEA_LEVEL, Uniforms, Vertex, World,
+    build_crosshair,
 };
 
+use super::atmosphere::Atmosphere;
+#[cfg(feature = "audio")]
+use super::audio::AudioOutput;
+use super::bloom::{BloomPipelines, BloomTargets, HDR_FORMAT, bloom_config};
+use super::chunk_progress::ChunkProgress;
+use super::cloud::DEFAULT_CLOUD_COVERAGE;
+use super::debug_view::{DebugView, DebugViewPipelines};
+use super::filtering::{create_atlas_sampler, max_sampler_anisotropy};
+use super::frame_limiter::FrameLimiter;
+#[cfg(feature = "gamepad")]
+use super::gamepad::GamepadInput;
+use super::gpu_timer::GpuTimer;
+use super::graphics_quality::create_shadow_map;
+use super::health::MAX_HEALTH;
+use super::keybindings::KeyBindings;
+use super::msaa::{depth_resolve_shader_source, select_msaa_samples};
+use super::particles::DEFAULT_BREAK_PARTICLES;
+use super::settings::Settings;
+use super::sky::SkyPalette;
 use super::state::State;
+use super::time_sync::ClockSync;
 
 /// Converts an OpenGL-style clip-space matrix to wgpu's NDC convention.
 ///
@@ -57,6 +84,61 @@ pub fn frustum_planes_to_array(planes: &[Vec4; 6]) -> [[f32; 4]; 6] {
     unsafe { std::mem::transmute(*planes) }
 }
 
+/// Appended to GPU setup errors: almost every failure there comes down to a
+/// missing or outdated graphics driver.
+const GPU_DRIVER_HINT: &str = "Make sure a graphics driver with Vulkan, DirectX 12 or Metal \
+     support is installed and up to date (on Linux, `vulkaninfo` should list the GPU).";
+
+/// Installed fonts tried, in order, when the bundled font cannot be used.
+const FALLBACK_UI_FONTS: [&str; 5] = [
+    "Noto Sans",
+    "DejaVu Sans",
+    "Liberation Sans",
+    "Arial",
+    "Helvetica",
+];
+
+/// Loads `bundled` into `db` and makes it the sans-serif family every UI
+/// text element is drawn with.
+///
+/// If the bundled data cannot be parsed, the first installed font from
+/// [`FALLBACK_UI_FONTS`] is used instead.  Never panics: with no usable font
+/// at all, text is simply not drawn.
+///
+/// # Returns
+/// The family name now used for sans-serif text, or `None` if none was found.
+fn select_ui_font(db: &mut fontdb::Database, bundled: Vec<u8>) -> Option<String> {
+    let bundled_family = db
+        .load_font_source(fontdb::Source::Binary(Arc::new(bundled)))
+        .first()
+        .and_then(|&id| db.face(id))
+        .and_then(|face| face.families.first())
+        .map(|(name, _)| name.clone());
+
+    let family = bundled_family.or_else(|| {
+        log(
+            LogLevel::Warning,
+            "Bundled UI font could not be loaded; trying system fonts",
+        );
+        FALLBACK_UI_FONTS
+            .iter()
+            .find(|name| {
+                db.faces()
+                    .any(|face| face.families.iter().any(|(family, _)| family == *name))
+            })
+            .map(|name| name.to_string())
+    });
+
+    match &family {
+        Some(name) => db.set_sans_serif_family(name.as_str()),
+        None => log(
+            LogLevel::Error,
+            "No usable UI font found; text will not be drawn",
+        ),
+    }
+    family
+}
+
 impl State {
     /// Initializes the complete rendering state for the application.
     ///
@@ -69,12 +151,16 @@ impl State {
     ///    `MULTI_DRAW_INDIRECT_COUNT` when the adapter supports it so the
     ///    indirect draw manager can cull invisible chunks on the GPU.
     /// 3. **Swap-chain configuration** – prefers an sRGB surface format and
-    ///    `PresentMode::Immediate` (uncapped frame rate) with 4× MSAA.
+    ///    the present mode of the saved [`VSyncMode`] (VSync by default),
+    ///    with the MSAA sample count the adapter allows closest to the
+    ///    requested one.
     /// 4. **Shader compilation** – compiles all WGSL shaders (terrain, water,
     ///    shadow, sky, sun, UI, Hi-Z, depth-resolve, composite).
     /// 5. **Buffers & textures** – allocates the uniform buffer, shadow map
     ///    cascade array, SSR color/depth targets, MSAA resolve targets, and
-    ///    the hierarchical-Z (Hi-Z) mip chain.
+    ///    the hierarchical-Z (Hi-Z) mip chain.  The texture atlas itself is
+    ///    loaded on a background thread started first thing; a placeholder
+    ///    stands in for it until `State::poll_texture_atlas` swaps it in.
     /// 6. **Bind group layouts & bind groups** – wires textures, samplers, and
     ///    buffers to the correct shader bindings for each pipeline.
     /// 7. **Render pipelines** – builds one `RenderPipeline` per pass:
@@ -91,13 +177,28 @@ impl State {
     ///     opaque terrain and water, and wires them to the Hi-Z texture so GPU
     ///     occlusion culling works correctly.
     ///
-    /// # Panics
-    /// Panics if:
-    /// - No compatible GPU adapter is found.
-    /// - The logical device cannot be created.
+    /// # Errors
+    /// Returns a description of the failure, with a hint about graphics
+    /// drivers where one applies, if:
     /// - The window surface cannot be created.
+    /// - No GPU adapter is found, not even wgpu's software fallback.
+    /// - The logical device cannot be created.
     /// - The Tokio runtime for networking cannot be created.
-    pub async fn new(window: Window) -> Self {
+    ///
+    /// `workers` sets the thread count of both the chunk and mesh loaders;
+    /// `None` picks each from the CPU count (see `get_chunk_worker_count` and
+    /// `get_mesh_worker_count`).  `msaa_samples` is the requested MSAA
+    /// sample count, `None` for the saved one; it is lowered when the
+    /// adapter cannot render it (see `select_msaa_samples`).
+    pub async fn new(
+        window: Window,
+        workers: Option<usize>,
+        msaa_samples: Option<u32>,
+    ) -> Result<Self, String> {
+        // Start decoding the texture atlas right away so it overlaps the
+        // rest of the GPU setup; the loading screen covers what is left.
+        let atlas_loader = AtlasLoader::spawn();
+
         let window = Arc::new(window);
         let size = window.inner_size();
 
@@ -116,9 +217,12 @@ impl State {
 
         // The surface must be created before adapter selection so that wgpu
         // can guarantee the chosen adapter can present to this window.
-        let surface = instance
-            .create_surface(window.clone())
-            .expect("Failed to create surface");
+        let surface = instance.create_surface(window.clone()).map_err(|e| {
+            format!(
+                "Failed to create window surface: {}. {}",
+                e, GPU_DRIVER_HINT
+            )
+        })?;
 
         // ------------------------------------------------------------------ //
         // Adapter selection
@@ -126,14 +230,41 @@ impl State {
 
         // Request the highest-performance (discrete) GPU.  If two adapters are
         // equally capable, wgpu falls back to its own scoring heuristic.
-        let adapter = instance
+        // Without a usable hardware adapter (headless machines, broken
+        // drivers) retry with the fallback adapter, a software renderer such
+        // as WARP or llvmpipe: slow, but the game still starts.
+        let adapter = match instance
             .request_adapter(&wgpu::RequestAdapterOptions {
                 power_preference: wgpu::PowerPreference::HighPerformance,
                 compatible_surface: Some(&surface),
                 force_fallback_adapter: false,
             })
             .await
-            .expect("Failed to find a suitable GPU adapter");
+        {
+            Ok(adapter) => adapter,
+            Err(e) => {
+                log(
+                    LogLevel::Warning,
+                    &format!(
+                        "No hardware GPU adapter found ({}), trying the software fallback",
+                        e
+                    ),
+                );
+                instance
+                    .request_adapter(&wgpu::RequestAdapterOptions {
+                        power_preference: wgpu::PowerPreference::HighPerformance,
+                        compatible_surface: Some(&surface),
+                        force_fallback_adapter: true,
+                    })
+                    .await
+                    .map_err(|e| {
+                        format!(
+                            "Failed to find a usable GPU adapter: {}. {}",
+                            e, GPU_DRIVER_HINT
+                        )
+                    })?
+            }
+        };
 
         let info = adapter.get_info();
         log(
@@ -168,6 +299,23 @@ impl State {
             log(LogLevel::Info, "Adapter supports SHADER_F16");
         }
 
+        // `TIMESTAMP_QUERY` lets the F3 overlay time each render phase on the
+        // GPU.  Without it the overlay only shows CPU timings.
+        let supports_timestamps = adapter_features.contains(wgpu::Features::TIMESTAMP_QUERY);
+        if supports_timestamps {
+            requested_features |= wgpu::Features::TIMESTAMP_QUERY;
+            log(LogLevel::Info, "Adapter supports TIMESTAMP_QUERY");
+        }
+
+        // Sample counts other than 1 and 4 need the adapter's own format
+        // capabilities, which are only honoured with
+        // `TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`.
+        let settings = Settings::load();
+        let msaa_samples = msaa_samples.unwrap_or(settings.msaa);
+        let (msaa_samples, msaa_features) = select_msaa_samples(&adapter, msaa_samples);
+        requested_features |= msaa_features;
+        log(LogLevel::Info, &format!("Using {}x MSAA", msaa_samples));
+
         // ------------------------------------------------------------------ //
         // Logical device & queue
         // ------------------------------------------------------------------ //
@@ -184,7 +332,26 @@ impl State {
                 trace: wgpu::Trace::Off,
             })
             .await
-            .expect("Failed to create GPU device");
+            .map_err(|e| {
+                format!(
+                    "Failed to create a GPU device on {}: {}. {}",
+                    info.name, e, GPU_DRIVER_HINT
+                )
+            })?;
+        // Losing the device (driver crash or reset, GPU removed) cannot be
+        // recovered from; at least leave the reason in the log.  `Destroyed`
+        // is the normal teardown on exit.
+        device.set_device_lost_callback(|reason, message| {
+            let level = match reason {
+                wgpu::DeviceLostReason::Destroyed => LogLevel::Debug,
+                _ => LogLevel::Error,
+            };
+            log(
+                level,
+                &format!("GPU device lost ({:?}): {}", reason, message),
+            );
+        });
+        let gpu_timer = supports_timestamps.then(|| GpuTimer::new(&device, &queue));
 
         // ------------------------------------------------------------------ //
         // Swap-chain (surface) configuration
@@ -200,14 +367,21 @@ impl State {
             .find(|f| f.is_srgb())
             .unwrap_or(surface_caps.formats[0]);
 
+        // The saved VSync choice, or `Fifo` where the surface lacks it.
+        let vsync_mode = settings.vsync;
+        let present_modes = surface_caps.present_modes.clone();
+        let present_mode = vsync_mode.present_mode(&present_modes);
+        log(
+            LogLevel::Info,
+            &format!("VSync {:?}: presenting with {:?}", vsync_mode, present_mode),
+        );
+
         let config = wgpu::SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
             format: surface_format,
             width: size.width,
             height: size.height,
-            // `Immediate` disables vsync so the frame rate is uncapped.
-            // Switch to `Fifo` (vsync) to reduce GPU power consumption.
-            present_mode: wgpu::PresentMode::Immediate,
+            present_mode,
             alpha_mode: surface_caps
                 .alpha_modes
                 .iter()
@@ -237,19 +411,20 @@ impl State {
         // MSAA & depth textures
         // ------------------------------------------------------------------ //
 
-        // 4× MSAA reduces aliasing on geometry edges with a reasonable
-        // memory/bandwidth cost.  All color render passes write to the MSAA
-        // texture; it is resolved to the swap-chain image at the end of each
-        // frame.
-        let msaa_sample_count: u32 = 4;
-
-        // A multisampled Depth32Float texture is used for all geometry passes
-        // (terrain, water, sun, sky).  A separate single-sampled depth texture
-        // is used for SSR so that the water shader can sample the opaque scene
-        // depth at full precision.
-        let depth_texture = Self::create_depth_texture(&device, &config, msaa_sample_count);
+        // MSAA (4× by default) reduces aliasing on geometry edges with a
+        // reasonable memory/bandwidth cost.  All color render passes write to
+        // the MSAA texture in `HDR_FORMAT`; it is resolved into the scene
+        // color texture, which the composite pass writes to the swap-chain.
+        // With a single sample there is no MSAA texture and the passes render
+        // into the resolve targets directly.
+
+        // A Depth32Float texture with the same sample count is used for all
+        // geometry passes (terrain, water, sun, sky).  A separate
+        // single-sampled depth texture is used for SSR so that the water
+        // shader can sample the opaque scene depth at full precision.
+        let depth_texture = Self::create_depth_texture(&device, &config, msaa_samples);
         let msaa_texture_view =
-            Self::create_msaa_texture(&device, &config, surface_format, msaa_sample_count);
+            Self::create_msaa_texture(&device, &config, HDR_FORMAT, msaa_samples);
 
         // ------------------------------------------------------------------ //
         // Shader compilation
@@ -337,6 +512,18 @@ impl State {
                 wind_speed: 1.0,
                 _pad: 0.0,
                 rain_factor: 0.0,
+                cloud_coverage: DEFAULT_CLOUD_COVERAGE,
+                _pad_clouds: 0.0,
+                ambient_color: [0.38; 3],
+                day_factor: 1.0,
+                dawn_tint: [1.0; 3],
+                _pad_light: 0.0,
+                fog_color: [0.68, 0.78, 0.88],
+                fog_strength: 0.0,
+                fog_start: 0.0,
+                fog_end: 1.0,
+                _pad_fog: [0.0; 2],
+                sky_palette: SkyPalette::default().to_uniform(),
             }]),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
@@ -347,69 +534,28 @@ impl State {
 
         // The texture atlas packs all block textures into a single 2D array
         // texture.  It is either loaded from a disk cache or generated from the
-        // raw asset images on first run.
-        let (texture_atlas, texture_view, _atlas_width, _atlas_height) =
-            texture_cache::load_or_generate_atlas(&device, &queue);
-
-        // Anisotropic filtering (16×) significantly reduces blurring on
-        // steeply-angled surfaces like cliff faces.
-        let texture_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Texture Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::MipmapFilterMode::Linear,
-            anisotropy_clamp: 16,
-            ..Default::default()
-        });
+        // raw asset images on first run, on `atlas_loader`'s thread; until it
+        // arrives the bind groups hold a placeholder and only the loading
+        // screen is drawn (see `State::poll_texture_atlas`).
+        let (texture_atlas, texture_view) = create_placeholder_atlas(&device, &queue);
+
+        // The atlas is sampled as saved in the settings: nearest filtering
+        // for the crisp blocky look unless trilinear + anisotropic filtering
+        // was switched on from the menu (see `State::set_texture_filtering`).
+        let max_anisotropy = max_sampler_anisotropy(&adapter);
+        let texture_filtering = settings.texture_filtering;
+        let texture_sampler = create_atlas_sampler(&device, texture_filtering, max_anisotropy);
 
         // ------------------------------------------------------------------ //
         // Shadow map (Cascaded Shadow Maps – CSM)
         // ------------------------------------------------------------------ //
 
-        // A 2 K × 2 K Depth32Float texture array with 4 layers, one per
-        // cascade.  Increasing `shadow_map_size` improves shadow sharpness at
-        // the cost of VRAM and shadow-pass render time.
-        let shadow_map_size = 2048;
-        let shadow_texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Shadow Map"),
-            size: wgpu::Extent3d {
-                width: shadow_map_size,
-                height: shadow_map_size,
-                depth_or_array_layers: 4, // one layer per CSM cascade
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Depth32Float,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-
-        // `D2Array` view used by the terrain fragment shader to sample all
-        // four cascades in a single `textureSampleCompareLevel` call.
-        let shadow_texture_view = shadow_texture.create_view(&wgpu::TextureViewDescriptor {
-            label: Some("Shadow Map Array View"),
-            dimension: Some(wgpu::TextureViewDimension::D2Array),
-            ..Default::default()
-        });
-
-        // Individual `D2` views, one per cascade, used as render targets in
-        // the shadow pass (wgpu render attachments cannot target array layers
-        // through an array view).
-        let shadow_cascade_views = (0..4)
-            .map(|i| {
-                shadow_texture.create_view(&wgpu::TextureViewDescriptor {
-                    label: Some(&format!("Shadow Map Cascade View {}", i)),
-                    dimension: Some(wgpu::TextureViewDimension::D2),
-                    base_array_layer: i,
-                    array_layer_count: Some(1),
-                    ..Default::default()
-                })
-            })
-            .collect::<Vec<_>>();
+        // Depth32Float texture array with one layer per cascade.  Its size
+        // and layer count come from the saved graphics quality preset, which
+        // can be changed from the menu (see `State::set_graphics_quality`).
+        let graphics_quality = settings.graphics_quality;
+        let (shadow_texture_view, shadow_cascade_views) =
+            create_shadow_map(&device, graphics_quality);
 
         // Dynamic-offset uniform buffer that stores the per-cascade light-space
         // view-projection matrix.  Using a dynamic offset means we can switch
@@ -501,11 +647,7 @@ impl State {
 
         let shadow_config_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Shadow Config Buffer"),
-            contents: bytemuck::cast_slice(&[ShadowConfig {
-                shadow_map_size: CSM_SHADOW_MAP_SIZE as f32,
-                pcf_samples: 16,
-                _pad: [0; 2],
-            }]),
+            contents: bytemuck::cast_slice(&[graphics_quality.shadow_config()]),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
@@ -685,8 +827,12 @@ impl State {
             mip_level_count: 1,
             sample_count: 1, // SSR targets are single-sampled (no MSAA)
             dimension: wgpu::TextureDimension::D2,
-            format: surface_format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            format: HDR_FORMAT,
+            // `COPY_SRC`: without MSAA the opaque pass renders here directly
+            // and is copied into the scene color texture.
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
             view_formats: &[],
         });
         let ssr_color_view = ssr_color_texture.create_view(&wgpu::TextureViewDescriptor::default());
@@ -1050,7 +1196,7 @@ impl State {
         // ------------------------------------------------------------------ //
 
         // --- Terrain (opaque geometry) ---
-        // Back-face culled, depth write enabled, 4× MSAA.
+        // Back-face culled, depth write enabled, multisampled.
         let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
             label: Some("Render Pipeline"),
             layout: Some(&pipeline_layout),
@@ -1066,7 +1212,7 @@ impl State {
                 entry_point: Some("fs_main"),
                 compilation_options: Default::default(),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
+                    format: HDR_FORMAT,
                     blend: Some(wgpu::BlendState::REPLACE),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -1085,7 +1231,52 @@ impl State {
                 bias: wgpu::DepthBiasState::default(),
             }),
             multisample: wgpu::MultisampleState {
-                count: msaa_sample_count,
+                count: msaa_samples,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview_mask: None,
+        });
+
+        // --- Translucent terrain (glass, ice) ---
+        // Alpha-blended over the finished scene after the water.  Depth is
+        // tested but not written, so translucent faces never hide one
+        // another; the renderer draws them back to front instead.
+        let translucent_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Translucent Terrain Pipeline"),
+            layout: Some(&pipeline_layout),
+            cache: None,
+            vertex: wgpu::VertexState {
+                module: &terrain_shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+                buffers: &[Vertex::desc()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &terrain_shader,
+                entry_point: Some("fs_translucent"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: HDR_FORMAT,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: msaa_samples,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
@@ -1121,7 +1312,7 @@ impl State {
                     bias: wgpu::DepthBiasState::default(),
                 }),
                 multisample: wgpu::MultisampleState {
-                    count: msaa_sample_count,
+                    count: msaa_samples,
                     mask: !0,
                     alpha_to_coverage_enabled: false,
                 },
@@ -1147,7 +1338,7 @@ impl State {
                 entry_point: Some("fs_water"),
                 compilation_options: Default::default(),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
+                    format: HDR_FORMAT,
                     blend: Some(wgpu::BlendState::ALPHA_BLENDING),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -1166,7 +1357,7 @@ impl State {
                 bias: wgpu::DepthBiasState::default(),
             }),
             multisample: wgpu::MultisampleState {
-                count: msaa_sample_count,
+                count: msaa_samples,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
@@ -1191,7 +1382,7 @@ impl State {
                 entry_point: Some("fs_outline"),
                 compilation_options: Default::default(),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
+                    format: HDR_FORMAT,
                     blend: Some(wgpu::BlendState::ALPHA_BLENDING),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -1210,7 +1401,7 @@ impl State {
                 bias: wgpu::DepthBiasState::default(),
             }),
             multisample: wgpu::MultisampleState {
-                count: msaa_sample_count,
+                count: msaa_samples,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
@@ -1256,6 +1447,54 @@ impl State {
             multiview_mask: None,
         });
 
+        // --- Minimap ---
+        // Same shader, vertex format and blending as the UI pipeline; the
+        // fragment stage samples the minimap texture bound at group 1.
+        let minimap = Minimap::new(&device);
+        let minimap_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Minimap Pipeline Layout"),
+                bind_group_layouts: &[
+                    &uniform_bind_group_layout, // group: 0
+                    &minimap.bind_group_layout, // group: 1
+                ],
+                immediate_size: 0,
+            });
+        let minimap_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Minimap Pipeline"),
+            layout: Some(&minimap_pipeline_layout),
+            cache: None,
+            vertex: wgpu::VertexState {
+                module: &ui_shader,
+                entry_point: Some("vs_ui"),
+                compilation_options: Default::default(),
+                buffers: &[Vertex::desc()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &ui_shader,
+                entry_point: Some("fs_minimap"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview_mask: None,
+        });
+
         // --- Shadow depth pass ---
         // Fragment shader is intentionally omitted; we only need the depth
         // values written by the vertex stage.  A depth bias is applied to
@@ -1329,7 +1568,7 @@ impl State {
                 entry_point: Some("fs_sun"),
                 compilation_options: Default::default(),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
+                    format: HDR_FORMAT,
                     blend: Some(wgpu::BlendState::ALPHA_BLENDING),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -1348,7 +1587,7 @@ impl State {
                 bias: wgpu::DepthBiasState::default(),
             }),
             multisample: wgpu::MultisampleState {
-                count: msaa_sample_count,
+                count: msaa_samples,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
@@ -1374,7 +1613,7 @@ impl State {
                 entry_point: Some("fs_sky"),
                 compilation_options: Default::default(),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
+                    format: HDR_FORMAT,
                     blend: Some(wgpu::BlendState::REPLACE),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -1395,7 +1634,7 @@ impl State {
                 bias: wgpu::DepthBiasState::default(),
             }),
             multisample: wgpu::MultisampleState {
-                count: msaa_sample_count,
+                count: msaa_samples,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
@@ -1447,44 +1686,61 @@ impl State {
         log(LogLevel::Info, "Generating world in background...");
         let world = Arc::new(parking_lot::RwLock::new(World::new()));
 
-        // `find_spawn_point` searches downward from a candidate column until
-        // it finds a non-air block, ensuring the player spawns on solid ground.
-        let spawn = world.read().find_spawn_point();
-        let camera = Camera::new(spawn);
-
-        {
+        // `find_safe_spawn_point` picks a dry column near the origin and
+        // builds a small platform there if nothing solid is under it, so the
+        // chunks around the origin are generated first.
+        let spawn = {
             let mut world = world.write();
             world.generate_chunks_in_radius(0, 0, 2);
-        }
+            world.find_safe_spawn_point()
+        };
+        let camera = Camera::new(spawn);
         World::spawn_chunks_in_ring_async(Arc::clone(&world), 0, 0, 2, RENDER_DISTANCE);
 
         log(LogLevel::Info, &format!("Spawn selected: {:?}", spawn));
 
         let seed = world.read().seed;
+        // `--workers` overrides both pool sizes; otherwise each is derived
+        // from the core count, leaving cores free for the render thread.
+        let chunk_workers = workers.unwrap_or_else(minerust::get_chunk_worker_count);
+        let mesh_workers = workers.unwrap_or_else(minerust::get_mesh_worker_count);
+        log(
+            LogLevel::Info,
+            &format!(
+                "Worker threads: {} chunk, {} mesh",
+                chunk_workers, mesh_workers
+            ),
+        );
+
         // `ChunkLoader` generates chunk data (terrain noise, biomes, structures)
         // on background threads.  It is seeded from the world so that chunk
         // generation is deterministic and seamlessly continuous across sessions.
-        let chunk_loader = ChunkLoader::new(seed);
+        let chunk_loader = ChunkLoader::with_worker_count(
+            chunk_workers,
+            seed,
+            world.read().sea_level(),
+            world.read().gen_config(),
+        );
 
         // `MeshLoader` converts raw chunk block data into GPU vertex/index
-        // buffers.  It runs on a pool of worker threads whose count is chosen
-        // by `get_mesh_worker_count` (typically `num_cpus - 1`).
-        let mesh_loader =
-            minerust::MeshLoader::new(Arc::clone(&world), minerust::get_mesh_worker_count());
+        // buffers on its own pool of worker threads.
+        let mesh_loader = minerust::MeshLoader::new(Arc::clone(&world), mesh_workers);
 
         // ------------------------------------------------------------------ //
         // Crosshair geometry
         // ------------------------------------------------------------------ //
 
         // `build_crosshair` returns a pre-built vertex/index list for a small
-        // plus-sign rendered at the screen center in NDC coordinates.
-        let (crosshair_vertices, crosshair_indices) = build_crosshair();
+        // plus-sign rendered at the screen center in NDC coordinates.  The
+        // vertices are rewritten on resize to follow the aspect ratio.
+        let (crosshair_vertices, crosshair_indices) =
+            build_crosshair(config.width as f32 / config.height as f32);
         let num_crosshair_indices = crosshair_indices.len() as u32;
         let crosshair_vertex_buffer =
             device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("Crosshair Vertex Buffer"),
                 contents: bytemuck::cast_slice(&crosshair_vertices),
-                usage: wgpu::BufferUsages::VERTEX,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
             });
         let crosshair_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Crosshair Index Buffer"),
@@ -1502,11 +1758,14 @@ impl State {
         // string changes.
 
         let mut font_system = FontSystem::new();
-        // Load the bundled Google Sans font so that text looks consistent
-        // across all platforms regardless of system fonts installed.
-        font_system.db_mut().load_font_data(
+        // Draw all text with the bundled Google Sans font so it looks
+        // consistent across platforms regardless of system fonts installed.
+        if let Some(family) = select_ui_font(
+            font_system.db_mut(),
             include_bytes!("../../assets/fonts/GoogleSans_17pt-Regular.ttf").to_vec(),
-        );
+        ) {
+            log(LogLevel::Debug, &format!("UI font: {}", family));
+        }
 
         let swash_cache = SwashCache::new();
         let cache = Cache::new(&device);
@@ -1532,6 +1791,7 @@ impl State {
 
         /// FPS counter displayed in the top-left corner.
         let fps_buffer = glyphon::Buffer::new(&mut font_system, Metrics::new(40.0, 48.0));
+        let loading_buffer = glyphon::Buffer::new(&mut font_system, Metrics::new(32.0, 40.0));
 
         // --- Main-menu text buffers ---
         let menu_title_buffer = glyphon::Buffer::new(&mut font_system, Metrics::new(44.0, 52.0));
@@ -1550,23 +1810,78 @@ impl State {
             glyphon::Buffer::new(&mut font_system, Metrics::new(20.0, 28.0));
         let menu_singleplayer_button_buffer =
             glyphon::Buffer::new(&mut font_system, Metrics::new(20.0, 28.0));
+        let menu_filter_button_buffer =
+            glyphon::Buffer::new(&mut font_system, Metrics::new(16.0, 22.0));
+        let menu_quality_button_buffer =
+            glyphon::Buffer::new(&mut font_system, Metrics::new(16.0, 22.0));
+        let menu_vsync_button_buffer =
+            glyphon::Buffer::new(&mut font_system, Metrics::new(16.0, 22.0));
+        let menu_brightness_buffer =
+            glyphon::Buffer::new(&mut font_system, Metrics::new(16.0, 22.0));
         /// Connection status / error message shown below the buttons.
         let menu_status_buffer = glyphon::Buffer::new(&mut font_system, Metrics::new(18.0, 24.0));
+        let menu_controls_button_buffer =
+            glyphon::Buffer::new(&mut font_system, Metrics::new(16.0, 22.0));
+        let menu_preset_button_buffer =
+            glyphon::Buffer::new(&mut font_system, Metrics::new(16.0, 22.0));
+        // Controls page columns.  The line height matches the row height so
+        // each line sits in its row.
+        let binding_metrics = Metrics::new(15.0, BINDING_ROW_HEIGHT);
+        let menu_binding_label_buffers =
+            [(); 2].map(|_| glyphon::Buffer::new(&mut font_system, binding_metrics));
+        let menu_binding_key_buffers =
+            [(); 2].map(|_| glyphon::Buffer::new(&mut font_system, binding_metrics));
 
         // Hotbar slot name (e.g., "Stone Sword") displayed above the hotbar.
         let hotbar_label_buffer = glyphon::Buffer::new(&mut font_system, Metrics::new(22.0, 28.0));
 
+        // Survival block counts, one per hotbar slot; filled in by
+        // `update_hotbar_counts` whenever a count changes.
+        let hotbar_count_buffers = HOTBAR_SLOTS
+            .iter()
+            .map(|_| glyphon::Buffer::new(&mut font_system, Metrics::new(16.0, 20.0)))
+            .collect();
+
+        // Coordinate readout in the top-right corner.
+        let coords_buffer = glyphon::Buffer::new(&mut font_system, Metrics::new(18.0, 24.0));
+
+        // Player list, centred at the top while its key is held.
+        let player_list_buffer = glyphon::Buffer::new(&mut font_system, Metrics::new(20.0, 26.0));
+
+        // "Saving..." indicator in the bottom-right corner.  The text never
+        // changes, so it is shaped and measured once here.
+        let mut saving_buffer = glyphon::Buffer::new(&mut font_system, Metrics::new(18.0, 24.0));
+        saving_buffer.set_text(
+            &mut font_system,
+            "Saving...",
+            &Attrs::new().family(Family::SansSerif),
+            Shaping::Advanced,
+            None,
+        );
+        saving_buffer.set_size(&mut font_system, None, None);
+        let saving_width = saving_buffer
+            .layout_runs()
+            .map(|run| run.line_w)
+            .fold(0.0, f32::max);
+
+        // Chunk loading readout, shaped when the count changes.
+        let chunk_progress_buffer =
+            glyphon::Buffer::new(&mut font_system, Metrics::new(20.0, 26.0));
+
+        // Chat log and input line shown in the bottom-left corner.
+        let chat_buffer = glyphon::Buffer::new(&mut font_system, Metrics::new(20.0, 26.0));
+
         // ------------------------------------------------------------------ //
         // Depth-resolve compute pipeline
         // ------------------------------------------------------------------ //
 
-        // After the MSAA opaque pass we resolve the multisampled depth buffer
-        // into two single-sampled outputs:
+        // After the depth prepass we resolve the (possibly multisampled)
+        // depth buffer into two single-sampled outputs:
         //   • `hiz_mips[0]`    – conservative max-depth seed for Hi-Z
         //   • `ssr_depth_view` – closest-depth copy for water refraction
         let depth_resolve_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Depth Resolve Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/depth_resolve.wgsl").into()),
+            source: wgpu::ShaderSource::Wgsl(depth_resolve_shader_source(msaa_samples).into()),
         });
         let depth_resolve_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -1578,7 +1893,7 @@ impl State {
                         ty: wgpu::BindingType::Texture {
                             sample_type: wgpu::TextureSampleType::Depth,
                             view_dimension: wgpu::TextureViewDimension::D2,
-                            multisampled: true, // must match the MSAA depth texture
+                            multisampled: msaa_samples > 1, // must match the depth texture
                         },
                         count: None,
                     },
@@ -1644,13 +1959,32 @@ impl State {
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: surface_format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            format: HDR_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST,
             view_formats: &[],
         });
         let scene_color_view =
             scene_color_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
+        // Bloom: bright-pass and blur chain over `scene_color_view`, added
+        // back by the composite pass.  Enabled by default (toggled with B).
+        let bloom_config_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Bloom Config Buffer"),
+            contents: bytemuck::cast_slice(&[bloom_config(true)]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let bloom_pipelines = BloomPipelines::new(&device);
+        let bloom_targets = BloomTargets::new(
+            &device,
+            &bloom_pipelines,
+            &bloom_config_buffer,
+            &scene_color_view,
+            config.width,
+            config.height,
+        );
+
         let composite_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("Composite Bind Group Layout"),
@@ -1683,6 +2017,40 @@ impl State {
                         ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                         count: None,
                     },
+                    // Blurred bloom glow added on top of the scene.
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    // Bloom settings (intensity 0 = off).
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 4,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    // Resolved scene depth, so underwater caustics can be
+                    // placed on the world rather than the screen.
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 5,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
                 ],
             });
         let composite_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
@@ -1709,6 +2077,18 @@ impl State {
                     binding: 2,
                     resource: wgpu::BindingResource::Sampler(&composite_sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(bloom_targets.output_view()),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: bloom_config_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: wgpu::BindingResource::TextureView(&ssr_depth_view),
+                },
             ],
         });
         let composite_pipeline_layout =
@@ -1746,6 +2126,10 @@ impl State {
             multiview_mask: None,
         });
 
+        // Blits of intermediate targets drawn over the composite when a
+        // debug view is picked in the F3 overlay.
+        let debug_view_pipelines = DebugViewPipelines::new(&device, surface_format);
+
         // ------------------------------------------------------------------ //
         // Indirect draw managers
         // ------------------------------------------------------------------ //
@@ -1755,9 +2139,9 @@ impl State {
         // cull step.  One manager for opaque terrain, one for water.
         let mut indirect_manager = IndirectManager::new(&device);
         let mut water_indirect_manager = IndirectManager::new(&device);
-        // Initialize the per-cascade shadow draw argument buffers.
+        // Initialize the per-cascade shadow draw argument buffers.  Water
+        // casts no shadow, so only the terrain manager needs them.
         indirect_manager.init_shadow_resources(&device);
-        water_indirect_manager.init_shadow_resources(&device);
 
         // ------------------------------------------------------------------ //
         // Hierarchical-Z (Hi-Z) occlusion buffer
@@ -1906,18 +2290,20 @@ impl State {
         // Assemble and return State
         // ------------------------------------------------------------------ //
 
-        Self {
+        Ok(Self {
             surface,
             device,
             queue,
             config,
             render_pipeline,
+            translucent_pipeline,
             water_pipeline,
             outline_pipeline,
             sun_pipeline,
             sky_pipeline,
             shadow_pipeline,
             crosshair_pipeline,
+            minimap_pipeline,
             sun_vertex_buffer,
             sun_index_buffer,
             crosshair_vertex_buffer,
@@ -1926,12 +2312,14 @@ impl State {
             uniform_buffer,
             shadow_config_buffer,
             uniform_bind_group,
+            uniform_bind_group_layout,
             terrain_gbuffer_bind_group: terrain_gbuffer_bind_group.clone(),
             terrain_shadow_output_bind_group: terrain_shadow_output_bind_group.clone(),
             shadow_bind_group,
             terrain_depth_pipeline,
             shadow_mask_pipeline,
             depth_texture,
+            msaa_samples,
             msaa_texture_view,
             shadow_texture_view,
             shadow_mask_texture,
@@ -1944,10 +2332,34 @@ impl State {
             shadow_mask_output_bind_group,
             world,
             mesh_loader,
+            mesh_uploads: minerust::MeshUploadQueue::new(minerust::MAX_PENDING_MESH_UPLOADS),
             camera,
             highlighted_block: None,
+            placement_preview: None,
+            show_face_highlight: true,
+            show_chunk_borders: false,
+            player_colors: true,
+            show_dig_cracks: true,
+            show_celestial_path: false,
+            show_debug_overlay: false,
+            debug_view: DebugView::Final,
+            debug_view_pipelines,
+            chunk_border_radius: CHUNK_BORDER_RADIUS,
             input: InputState::default(),
+            pad_input: InputState::default(),
+            #[cfg(feature = "gamepad")]
+            gamepad: GamepadInput::new(),
+            #[cfg(feature = "audio")]
+            audio: AudioOutput::new().map(|mut audio| {
+                audio.volume = settings.volume;
+                audio
+            }),
+            volume: settings.volume,
+            modifiers: ModifiersState::default(),
             digging: DiggingState::default(),
+            creative_break_cooldown: 0.0,
+            inventory: Inventory::new(),
+            last_jump_press: None,
             window,
             frame_count: 0,
             last_fps_update: Instant::now(),
@@ -1960,24 +2372,74 @@ impl State {
             chunks_rendered: 0,
             subchunks_rendered: 0,
             game_start_time: Instant::now(), // - std::time::Duration::from_secs_f32(3.14 / 0.005),
-            coords_vertex_buffer: None,
-            coords_index_buffer: None,
-            coords_num_indices: 0,
-            last_coords_position: (i32::MIN, i32::MIN, i32::MIN),
+            time_of_day: 0.0,
+            time_scale: 1.0,
+            clock_sync: ClockSync::default(),
+            night_skip: None,
+            bed: None,
+            health: MAX_HEALTH,
+            hurt_timer: 0.0,
+            hurt_strength: 0.0,
+            particles: ParticleSystem::default(),
+            break_particle_count: DEFAULT_BREAK_PARTICLES,
+            water_tick_timer: 0.0,
+            random_tick_timer: 0.0,
+            coords_buffer,
+            coords_width: 0.0,
+            coords_text: String::new(),
+            show_coords: true,
+            player_list_buffer,
+            player_list_width: 0.0,
+            player_list_entries: Vec::new(),
+            show_player_list: false,
+            world_entered: false,
+            world_save: None,
+            last_world_save: Instant::now(),
+            saving_indicator_until: Instant::now(),
+            saving_buffer,
+            chunk_progress: ChunkProgress::default(),
+            chunk_progress_buffer,
+            chunk_progress_width: 0.0,
+            saving_width,
             progress_bar_vertex_buffer: None,
             progress_bar_index_buffer: None,
+            damage_vignette_vertex_buffer: None,
+            damage_vignette_index_buffer: None,
             texture_atlas,
             texture_view,
+            atlas_loader: Some(atlas_loader),
             texture_sampler,
+            texture_filtering,
+            vsync_mode,
+            present_modes,
+            fps_cap: None,
+            frame_limiter: FrameLimiter::default(),
+            max_anisotropy,
             game_state: GameState::Menu,
             menu_state: MenuState::default(),
+            key_bindings: KeyBindings::load(),
+            pending_rebind: None,
+            chat: ChatState::default(),
+            pending_box_edits: VecDeque::new(),
+            last_fill_region: None,
+            edit_history: EditHistory::new(EDIT_HISTORY_MAX_OPERATIONS, EDIT_HISTORY_MAX_BLOCKS),
+            block_mutations: BlockMutationQueue::new(),
+            deferred_block_changes: HashMap::new(),
             reflection_mode: 1,
+            bloom_enabled: true,
+            cloud_coverage: DEFAULT_CLOUD_COVERAGE,
+            sky_palette: SkyPalette::default(),
+            min_ambient: settings.brightness,
+            atmosphere: Atmosphere::default(),
             is_underwater: 0.0,
             remote_players: HashMap::new(),
             my_player_id: 0,
             last_position_send: Instant::now(),
+            last_dig_sent: None,
+            last_dig_send: Instant::now(),
             network_runtime: Some(
-                tokio::runtime::Runtime::new().expect("Failed to create tokio runtime"),
+                tokio::runtime::Runtime::new()
+                    .map_err(|e| format!("Failed to create the network runtime: {}", e))?,
             ),
             network_rx: None,
             network_tx: None,
@@ -1987,7 +2449,12 @@ impl State {
             player_model_num_indices: 0,
             player_model_vertex_capacity: 0,
             player_model_index_capacity: 0,
+            player_model_index_counts: (0, 0, 0),
+            player_model_vertices: Vec::new(),
+            player_model_indices: Vec::new(),
             chunk_loader,
+            chunk_workers,
+            mesh_workers,
             last_gen_player_cx: i32::MIN,
             last_gen_player_cz: i32::MIN,
             ssr_color_texture,
@@ -2000,13 +2467,13 @@ impl State {
             flow_sampler,
             water_bind_group,
             water_bind_group_layout,
-            surface_format,
             font_system,
             swash_cache,
             text_atlas,
             text_renderer,
             viewport,
             fps_buffer,
+            loading_buffer,
             menu_title_buffer,
             menu_subtitle_buffer,
             menu_server_label_buffer,
@@ -2016,15 +2483,31 @@ impl State {
             menu_tips_buffer,
             menu_connect_button_buffer,
             menu_singleplayer_button_buffer,
+            menu_filter_button_buffer,
+            menu_quality_button_buffer,
+            menu_vsync_button_buffer,
+            menu_brightness_buffer,
             menu_status_buffer,
+            menu_controls_button_buffer,
+            menu_preset_button_buffer,
+            menu_binding_label_buffers,
+            menu_binding_key_buffers,
             hotbar_label_buffer,
             hotbar_label_width: 0.0,
+            hotbar_count_buffers,
             last_hotbar_slot: usize::MAX,
+            hotbar_counts: None,
+            empty_slot_flash_until: Instant::now(),
+            hotbar_flash: false,
             player_label_buffers: Vec::new(),
+            chat_buffer,
             composite_pipeline,
             composite_bind_group,
             scene_color_texture,
             scene_color_view,
+            bloom_pipelines,
+            bloom_targets,
+            bloom_config_buffer,
             indirect_manager,
             water_indirect_manager,
             hiz_texture,
@@ -2037,14 +2520,25 @@ impl State {
             depth_resolve_pipeline,
             depth_resolve_bind_group,
             supports_indirect_count,
-            csm: minerust::render_core::csm::CsmManager::new(),
+            gpu_timer,
+            csm: {
+                let mut csm = minerust::render_core::csm::CsmManager::new();
+                csm.configure(
+                    graphics_quality.cascade_count(),
+                    graphics_quality.shadow_map_size(),
+                );
+                csm
+            },
+            graphics_quality,
             hotbar_slot: 0,
+            hotbar: HOTBAR_SLOTS,
             hotbar_vertex_buffer: None,
             hotbar_index_buffer: None,
             hotbar_num_indices: 0,
             hotbar_dirty: true,
+            minimap,
             cursor_position: None,
-        }
+        })
     }
 
     /// Creates a (possibly multisampled) depth texture and returns a view into it.
@@ -2058,9 +2552,9 @@ impl State {
     /// - `config`       – Current surface configuration; width/height are read
     ///                    from here so the depth texture always matches the
     ///                    swap-chain resolution.
-    /// - `sample_count` – Number of MSAA samples.  Pass `1` for a
-    ///                    single-sampled texture (e.g., SSR targets) or `4`
-    ///                    for the main multisampled depth buffer.
+    /// - `sample_count` – Number of MSAA samples; `State::msaa_samples` for
+    ///                    the main depth buffer, which is single-sampled when
+    ///                    MSAA is off.
     ///
     /// # Returns
     /// A `TextureView` wrapping the newly created depth texture.
@@ -2081,7 +2575,7 @@ impl State {
             dimension: wgpu::TextureDimension::D2,
             format: wgpu::TextureFormat::Depth32Float,
             // `TEXTURE_BINDING` is needed so the depth-resolve shader can read
-            // the multisampled depth as a texture.
+            // the depth (multisampled or not) as a texture.
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
             view_formats: &[],
         });
@@ -2091,24 +2585,29 @@ impl State {
     /// Creates a multisampled color texture used as the MSAA render target.
     ///
     /// All geometry passes render into this texture.  At the end of each frame
-    /// it is resolved to the single-sampled `scene_color_texture` (and
-    /// ultimately to the swap-chain surface) by the wgpu resolve attachment
-    /// mechanism.
+    /// it is resolved to the single-sampled `scene_color_texture` by the wgpu
+    /// resolve attachment mechanism.
     ///
     /// # Parameters
     /// - `device`       – Active wgpu logical device.
     /// - `config`       – Current surface configuration.
-    /// - `format`       – Surface pixel format (sRGB if available).
-    /// - `sample_count` – Number of MSAA samples (typically 4).
+    /// - `format`       – Color format; must match the resolve targets
+    ///   ([`HDR_FORMAT`]).
+    /// - `sample_count` – Number of MSAA samples (`State::msaa_samples`).
     ///
     /// # Returns
-    /// A `TextureView` wrapping the newly created MSAA color texture.
+    /// A `TextureView` wrapping the newly created MSAA color texture, or
+    /// `None` when `sample_count` is 1: the passes then render straight into
+    /// their resolve targets.
     pub fn create_msaa_texture(
         device: &wgpu::Device,
         config: &wgpu::SurfaceConfiguration,
         format: wgpu::TextureFormat,
         sample_count: u32,
-    ) -> wgpu::TextureView {
+    ) -> Option<wgpu::TextureView> {
+        if sample_count == 1 {
+            return None;
+        }
         let msaa_texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("MSAA Texture"),
             size: wgpu::Extent3d {
@@ -2125,6 +2624,25 @@ impl State {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
             view_formats: &[],
         });
-        msaa_texture.create_view(&wgpu::TextureViewDescriptor::default())
+        Some(msaa_texture.create_view(&wgpu::TextureViewDescriptor::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_font_is_used_without_system_fonts() {
+        let mut db = fontdb::Database::new();
+        let bundled = include_bytes!("../../assets/fonts/GoogleSans_17pt-Regular.ttf").to_vec();
+        let family = select_ui_font(&mut db, bundled).expect("bundled font should load");
+        assert_eq!(db.family_name(&fontdb::Family::SansSerif), family);
+    }
+
+    #[test]
+    fn missing_fonts_do_not_panic() {
+        let mut db = fontdb::Database::new();
+        assert_eq!(select_ui_font(&mut db, b"not a font".to_vec()), None);
     }
 }
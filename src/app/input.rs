@@ -1,9 +1,18 @@
-use minerust::camera::check_intersection;
+use std::time::Instant;
+
+use minerust::{BlockType, CREATIVE_DOUBLE_TAP_WINDOW, ChunkLoader, MeshLoader, World};
 use winit::event::MouseButton;
+use winit::window::CursorGrabMode;
 
-use crate::ui::menu::{MenuField, MenuHit, MenuLayout};
-use crate::ui::ui::HOTBAR_SLOTS;
+use crate::logger::{LogLevel, log};
+use crate::ui::menu::{MenuField, MenuHit, MenuLayout, MenuScreen};
+use crate::ui::ui::pick_block_slot;
 
+#[cfg(feature = "audio")]
+use super::audio::BlockSound;
+use super::daylight::MAX_MIN_AMBIENT;
+use super::health::MAX_HEALTH;
+use super::keybindings::Action;
 use super::state::State;
 
 impl State {
@@ -21,8 +30,19 @@ impl State {
     /// | `ServerAddress`         | Moves keyboard focus to the server-address text field.  |
     /// | `Username`              | Moves keyboard focus to the username text field.        |
     /// | `Connect`               | Initiates a multiplayer connection attempt.             |
-    /// | `Singleplayer`          | Transitions directly to `GameState::Playing`.           |
-    /// | `None` (missed all UI)  | Clears the active field so keyboard input is ignored.   |
+    /// | `Singleplayer`          | Opens the singleplayer page with the seed field.        |
+    /// | `Seed`                  | Moves keyboard focus to the seed text field.            |
+    /// | `SeaLevel`              | Moves keyboard focus to the sea level text field.       |
+    /// | `WorldPreset`           | Cycles the Default / Flat / Amplified / Superflat preset.|
+    /// | `CreateWorld`           | Starts a new world from the singleplayer page settings. |
+    /// | `Back`                  | Returns to the main page.                               |
+    /// | `TextureFiltering`      | Toggles crisp / smooth atlas filtering.                 |
+    /// | `GraphicsQuality`       | Cycles the low / medium / high shadow quality preset.   |
+    /// | `Brightness`            | Sets the minimum ambient light and starts a slider drag.|
+    /// | `Controls`              | Opens the key binding page.                             |
+    /// | `Binding`               | Waits for the next key press to rebind that action.     |
+    /// | `ResetBindings`         | Restores and saves the default key bindings.            |
+    /// | `None` (missed all UI)  | Clears the active field and cancels a pending rebind.   |
     ///
     /// # Parameters
     /// - `x` – Horizontal cursor position in physical pixels (origin = top-left).
@@ -30,14 +50,64 @@ impl State {
     pub fn handle_menu_click(&mut self, x: f32, y: f32) {
         let layout = MenuLayout::new(self.config.width, self.config.height);
 
-        match layout.hit_test(x, y) {
+        match layout.hit_test(self.menu_state.screen, x, y) {
             Some(MenuHit::ServerAddress) => self.menu_state.select_field(MenuField::ServerAddress),
             Some(MenuHit::Username) => self.menu_state.select_field(MenuField::Username),
             Some(MenuHit::Connect) => self.connect_to_server(),
-            Some(MenuHit::Singleplayer) => self.game_state = crate::ui::menu::GameState::Playing,
+            Some(MenuHit::Singleplayer) => self.menu_state.open_singleplayer(),
+            Some(MenuHit::Seed) => self.menu_state.select_field(MenuField::Seed),
+            Some(MenuHit::SeaLevel) => self.menu_state.select_field(MenuField::SeaLevel),
+            Some(MenuHit::WorldPreset) => {
+                self.menu_state.world_preset = self.menu_state.world_preset.next()
+            }
+            Some(MenuHit::CreateWorld) => self.create_singleplayer_world(),
+            Some(MenuHit::Back) if self.menu_state.screen == MenuScreen::Controls => {
+                self.close_controls()
+            }
+            Some(MenuHit::Back) => self.menu_state.close_singleplayer(),
+            Some(MenuHit::TextureFiltering) => {
+                self.set_texture_filtering(self.texture_filtering.toggled())
+            }
+            Some(MenuHit::GraphicsQuality) => {
+                self.set_graphics_quality(self.graphics_quality.next())
+            }
+            Some(MenuHit::VSync) => self.set_vsync_mode(self.vsync_mode.next()),
+            Some(MenuHit::Brightness) => {
+                self.menu_state.dragging_brightness = true;
+                self.min_ambient = layout.brightness_slider_value(x) * MAX_MIN_AMBIENT;
+            }
+            Some(MenuHit::Controls) => self.menu_state.open_controls(),
+            Some(MenuHit::Binding(index)) => {
+                if let Some(&action) = Action::ALL.get(index) {
+                    self.start_rebind(action);
+                }
+            }
+            Some(MenuHit::ResetBindings) => self.reset_key_bindings(),
             // Clicking outside any widget deselects everything so subsequent
             // key events are not accidentally routed to a text field.
-            None => self.menu_state.select_field(MenuField::None),
+            None => {
+                self.pending_rebind = None;
+                self.menu_state.select_field(MenuField::None);
+            }
+        }
+    }
+
+    /// Moves the brightness slider to cursor X coordinate `x` while the
+    /// left button that pressed it is held; does nothing otherwise.
+    pub fn handle_menu_drag(&mut self, x: f32) {
+        if !self.menu_state.dragging_brightness {
+            return;
+        }
+        let layout = MenuLayout::new(self.config.width, self.config.height);
+        self.min_ambient = layout.brightness_slider_value(x) * MAX_MIN_AMBIENT;
+    }
+
+    /// Ends a brightness slider drag (the left button was released) and
+    /// saves the brightness it was left at.
+    pub fn finish_menu_drag(&mut self) {
+        if self.menu_state.dragging_brightness {
+            self.menu_state.dragging_brightness = false;
+            self.save_settings();
         }
     }
 
@@ -51,15 +121,20 @@ impl State {
     ///
     /// 2. **Only when the mouse is captured** (i.e., the player is in-game
     ///    with the cursor locked): handle the right-click block-placement
-    ///    action, including all placement guards.
+    ///    action, including all placement guards, and middle-click block
+    ///    picking (see [`Self::pick_block`]).
     ///
     /// # Block placement guards (right-click)
     /// Placement is skipped when any of the following is true:
-    /// - The raycast does not hit a surface within reach (5 blocks).
+    /// - The raycast does not hit a surface within reach (see
+    ///   `Camera::current_reach`).
     /// - The target placement position overlaps the player's own AABB —
     ///   prevents the player from trapping themselves inside a block.
     /// - The target position overlaps a remote player's AABB — prevents
     ///   griefing by walling another player in.
+    /// - The target position overlaps a mob.
+    ///
+    /// All three body checks are [`Self::can_place_block`].
     ///
     /// When all guards pass, the block currently selected in the hotbar is
     /// written to the world and the affected chunk is mark`ed dirty so its
@@ -84,49 +159,146 @@ impl State {
         }
 
         if button == MouseButton::Right && pressed {
-            // Cast a ray from the camera up to 5 blocks to find the block face
-            // the player is looking at.  The tuple contains
-            // (hit_x, hit_y, hit_z, place_x, place_y, place_z) where the
-            // first triple is the block that was hit and the second is the
-            // adjacent air block where the new block should be placed.
-            let target = self.camera.raycast(&*self.world.read(), 5.0);
-            if let Some((_, _, _, px, py, pz)) = target {
-                // Guard 1: don't place a block inside the local player's AABB.
-                if self.camera.intersects_block(px, py, pz) {
-                    return;
-                }
+            self.place_block();
+        }
+        if button == MouseButton::Middle && pressed {
+            self.pick_block();
+        }
+    }
 
-                // Guard 2: don't place a block inside any remote player's AABB.
-                // This iterates all known remote players and checks their
-                // server-authoritative positions.
-                for player in self.remote_players.values() {
-                    let player_pos = glam::Vec3::new(player.x, player.y, player.z);
-                    if check_intersection(player_pos, px, py, pz) {
-                        return;
-                    }
-                }
+    /// Puts the block the player is looking at into the hotbar and selects
+    /// it, without touching the world.
+    ///
+    /// A block already in the hotbar selects its slot; any other block
+    /// replaces the block of the selected slot.  Air and unbreakable blocks
+    /// (bedrock) are ignored.
+    pub fn pick_block(&mut self) {
+        let block = {
+            let world = self.world.read();
+            let Some(hit) = self.camera.target(&world) else {
+                return;
+            };
+            let (bx, by, bz) = hit.block;
+            world.get_block(bx, by, bz)
+        };
+        if block == BlockType::Air || block.break_time().is_infinite() {
+            return;
+        }
 
-                // All guards passed — place the block selected in the hotbar.
-                let block_to_place = HOTBAR_SLOTS[self.hotbar_slot];
-                self.world
-                    .write()
-                    .set_block_player(px, py, pz, block_to_place);
-
-                // Send the block change to the server so other players see it.
-                if let Some(tx) = &self.network_tx {
-                    let _ = tx.send(crate::multiplayer::protocol::Packet::BlockChange {
-                        x: px,
-                        y: py,
-                        z: pz,
-                        block_type: block_to_place as u8,
-                    });
-                }
+        self.hotbar_slot = pick_block_slot(&mut self.hotbar, self.hotbar_slot, block);
+        // Rebuild the slot swatches and the block name label even if the
+        // selected slot stayed the same.
+        self.hotbar_dirty = true;
+        self.last_hotbar_slot = usize::MAX;
+    }
 
-                // Invalidate the mesh of every sub-chunk that touches this
-                // block position so the geometry is rebuilt before next render.
-                self.mark_chunk_dirty(px, py, pz);
-            }
+    /// Places the block selected in the hotbar against the face the player
+    /// is looking at, if any.
+    ///
+    /// Shared by the right mouse button and the gamepad place trigger.
+    pub fn place_block(&mut self) {
+        // Cast a ray from the camera up to the player's reach to find the
+        // block face they are looking at.  The new block goes into the
+        // empty cell in front of that face (hit block + face normal).
+        let Some(hit) = self.camera.target(&self.world.read()) else {
+            return;
+        };
+        // Right-clicking a bed sleeps in it rather than building onto it.
+        let (bx, by, bz) = hit.block;
+        if self.world.read().get_block(bx, by, bz) == BlockType::Bed {
+            self.use_bed(hit.block);
+            return;
+        }
+        let (px, py, pz) = hit.place_position();
+        if !self.can_place_block(px, py, pz) {
+            return;
+        }
+
+        // In survival mode the block comes out of the inventory; with none
+        // left the hotbar flashes and nothing is placed.
+        let block = self.hotbar[self.hotbar_slot];
+        if !self.take_block_for_placement(block) {
+            return;
+        }
+
+        // All guards passed — queue the block selected in the hotbar.
+        // Stairs are turned to ascend away from the player, logs run along
+        // the axis of the face they were placed on.  `update` applies it
+        // together with any remote changes, records it for undo, sends it
+        // to the server and remeshes the touched sub-chunks.
+        let facing = block.placement_facing(self.camera.yaw, hit.normal);
+        if self.world.read().get_block(px, py, pz) == BlockType::Water {
+            self.splash_block(px, py, pz);
         }
+        self.block_mutations.push_local(px, py, pz, block, facing);
+        #[cfg(feature = "audio")]
+        self.play_block_sound(block, BlockSound::Place);
+    }
+
+    /// Returns `true` if a block may be placed at `(px, py, pz)` without
+    /// ending up inside the local player, a remote player (at their
+    /// server-authoritative position) or a mob; see
+    /// `World::can_place_block`.
+    ///
+    /// Shared by [`Self::place_block`] and the placement preview computed in
+    /// `update`.
+    pub fn can_place_block(&self, px: i32, py: i32, pz: i32) -> bool {
+        let players: Vec<glam::Vec3> = std::iter::once(self.camera.position)
+            .chain(
+                self.remote_players
+                    .values()
+                    .map(|player| glam::Vec3::new(player.x, player.y, player.z)),
+            )
+            .collect();
+        self.world.read().can_place_block(px, py, pz, &players)
+    }
+
+    /// Records a Space press and toggles creative flight when it follows the
+    /// previous one within [`CREATIVE_DOUBLE_TAP_WINDOW`] seconds.
+    ///
+    /// Key auto-repeat must be filtered out by the caller.
+    pub fn register_jump_press(&mut self) {
+        if self.camera.spectating() {
+            return;
+        }
+        let now = Instant::now();
+        let double_tap = self.last_jump_press.is_some_and(|last| {
+            now.duration_since(last).as_secs_f32() <= CREATIVE_DOUBLE_TAP_WINDOW
+        });
+        if double_tap {
+            // A third tap starts a new pair instead of toggling back.
+            self.last_jump_press = None;
+            self.set_creative(!self.camera.creative);
+        } else {
+            self.last_jump_press = Some(now);
+        }
+    }
+
+    /// Enters or leaves creative mode and reports the change in chat.
+    pub fn set_creative(&mut self, creative: bool) {
+        self.camera.set_creative(creative);
+        self.digging = minerust::DiggingState::default();
+        let mode = if creative { "Creative" } else { "Survival" };
+        log(LogLevel::Info, &format!("Game mode: {}", mode));
+        self.chat.push_message(format!("{} mode", mode));
+    }
+
+    /// Enters or leaves the debug spectator camera and reports the change
+    /// in chat.  See `Camera::set_spectating`.
+    pub fn toggle_spectator(&mut self) {
+        let spectating = !self.camera.spectating();
+        self.camera.set_spectating(spectating);
+        self.digging = minerust::DiggingState::default();
+        let message = if spectating {
+            format!(
+                "Spectating at {:.0} blocks/s, scroll to change speed",
+                self.camera.spectator_speed
+            )
+        } else {
+            "Spectator mode off".to_string()
+        };
+        log(LogLevel::Info, &message);
+        self.chat.push_message(message);
     }
 
     /// Initiates an asynchronous connection to the multiplayer server.
@@ -155,4 +327,75 @@ impl State {
             &mut self.network_tx,
         );
     }
+
+    /// Replaces the world with a new one generated from the seed and sea
+    /// level fields and the world preset on the singleplayer page (a random
+    /// seed when the field is empty), moves the player to its spawn point
+    /// and starts playing.
+    ///
+    /// Everything tied to the old world is dropped: undo history, queued
+    /// edits, chunk and mesh workers and the GPU chunk buffers.  Refused
+    /// while connected to a server, whose world would immediately disagree,
+    /// or when the sea level field does not hold a valid height.
+    pub fn create_singleplayer_world(&mut self) {
+        if self.network_tx.is_some() {
+            self.menu_state
+                .set_error("Singleplayer worlds cannot be created while connected");
+            return;
+        }
+        let sea_level = match self.menu_state.sea_level() {
+            Ok(sea_level) => sea_level,
+            Err(e) => {
+                self.menu_state.set_error(&e);
+                return;
+            }
+        };
+
+        let seed = self.menu_state.seed().unwrap_or_else(rand::random);
+        let preset = self.menu_state.world_preset;
+        let spawn = {
+            let mut world = self.world.write();
+            *world = World::new_with_config(seed, sea_level, preset.config());
+            world.find_safe_spawn_point()
+        };
+        self.camera.set_spectating(false);
+        self.camera.position = glam::Vec3::new(spawn.0, spawn.1, spawn.2);
+        self.camera.velocity = glam::Vec3::ZERO;
+        self.camera.on_ground = false;
+
+        self.edit_history.clear();
+        self.pending_box_edits.clear();
+        self.last_fill_region = None;
+        self.inventory.clear();
+        self.health = MAX_HEALTH;
+        self.hurt_timer = 0.0;
+        self.block_mutations.clear();
+        self.deferred_block_changes.clear();
+        self.digging = minerust::DiggingState::default();
+        self.chunk_loader =
+            ChunkLoader::with_worker_count(self.chunk_workers, seed, sea_level, preset.config());
+        self.mesh_loader = MeshLoader::new(self.world.clone(), self.mesh_workers);
+        self.mesh_uploads.clear();
+        self.indirect_manager.clear_gpu_data(&self.queue);
+        self.water_indirect_manager.clear_gpu_data(&self.queue);
+        log(
+            LogLevel::Info,
+            &format!(
+                "New world created (seed: {}, sea level: {}, type: {}), spawn {:?}",
+                seed,
+                sea_level,
+                preset.label(),
+                spawn
+            ),
+        );
+
+        self.menu_state.close_singleplayer();
+        self.game_state = crate::ui::menu::GameState::Playing;
+        self.mouse_captured = true;
+        let _ = self
+            .window
+            .set_cursor_grab(CursorGrabMode::Confined)
+            .or_else(|_| self.window.set_cursor_grab(CursorGrabMode::Locked));
+        self.window.set_cursor_visible(false);
+    }
 }
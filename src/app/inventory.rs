@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+use glyphon::{Attrs, Color, Family, Shaping};
+use minerust::BlockType;
+
+use super::state::State;
+
+/// How long the selected hotbar slot flashes after trying to place a block
+/// the inventory has run out of.
+const EMPTY_SLOT_FLASH_TIME: Duration = Duration::from_millis(300);
+
+impl State {
+    /// Adds the drop of a block broken in survival mode to the inventory.
+    /// Creative mode collects nothing.
+    pub fn collect_block(&mut self, broken: BlockType) {
+        if self.camera.creative {
+            return;
+        }
+        if let Some(drop) = broken.dropped_block() {
+            self.inventory.add(drop, 1);
+        }
+    }
+
+    /// Takes one `block` out of the inventory for placing it, returning
+    /// `false` if there is none.  An empty slot flashes the hotbar instead.
+    /// Creative mode places without limit.
+    pub fn take_block_for_placement(&mut self, block: BlockType) -> bool {
+        if self.camera.creative || self.inventory.take(block) {
+            return true;
+        }
+        self.empty_slot_flash_until = Instant::now() + EMPTY_SLOT_FLASH_TIME;
+        false
+    }
+
+    /// Marks the hotbar for a rebuild when its block counts or the
+    /// empty-slot flash changed since it was last built, and reshapes the
+    /// count labels.  Called by `render` before drawing the hotbar.
+    pub fn refresh_hotbar_counts(&mut self) {
+        let counts =
+            (!self.camera.creative).then(|| self.hotbar.map(|block| self.inventory.count(block)));
+        let flash = Instant::now() < self.empty_slot_flash_until;
+        if flash != self.hotbar_flash {
+            self.hotbar_flash = flash;
+            self.hotbar_dirty = true;
+        }
+        if counts == self.hotbar_counts {
+            return;
+        }
+
+        for (i, buffer) in self.hotbar_count_buffers.iter_mut().enumerate() {
+            // Empty slots and creative mode show no number.
+            let text = match counts {
+                Some(counts) if counts[i] > 0 => counts[i].to_string(),
+                _ => String::new(),
+            };
+            buffer.set_text(
+                &mut self.font_system,
+                &text,
+                &Attrs::new()
+                    .family(Family::SansSerif)
+                    .color(Color::rgb(255, 255, 255)),
+                Shaping::Advanced,
+                None,
+            );
+            buffer.set_size(&mut self.font_system, None, None);
+        }
+        self.hotbar_counts = counts;
+        self.hotbar_dirty = true;
+    }
+}
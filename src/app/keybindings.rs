@@ -0,0 +1,586 @@
+use std::fs;
+use std::path::PathBuf;
+
+use winit::keyboard::KeyCode;
+
+use crate::logger::{LogLevel, log};
+use crate::minerust_data::data;
+
+use super::state::State;
+
+/// Name of the bindings file inside the per-user config directory.
+const BINDINGS_FILE: &str = "keybindings.cfg";
+
+/// An in-game action whose key can be changed on the controls page.
+///
+/// Escape (menu), 1–9 (hotbar) and Ctrl+Z / Ctrl+Y (undo / redo) are not
+/// actions: they stay fixed so the player can always get back to the menu
+/// and the hotbar keeps matching its slot numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Forward,
+    Back,
+    Left,
+    Right,
+    /// Jump; fly up in creative mode.  A double tap toggles flight.
+    Jump,
+    /// Sprint; fly down in creative mode.
+    Sprint,
+    /// Sprint in creative mode.
+    FlySprint,
+    /// Open the chat box.
+    Chat,
+    /// Open the chat box pre-filled with `/`.
+    Command,
+    /// Show the player list while held.
+    PlayerList,
+    ToggleDebugOverlay,
+    /// Cycle the render target shown while the debug overlay
+    /// ([`Action::ToggleDebugOverlay`]) is open; ignored otherwise.
+    CycleDebugView,
+    ToggleFaceHighlight,
+    ToggleChunkBorders,
+    TogglePlayerColors,
+    ToggleCelestialPath,
+    ToggleDigCracks,
+    ToggleBloom,
+    CycleReflections,
+    ToggleMinimap,
+    ToggleCoords,
+    /// Detach the camera and fly through everything, for debugging.
+    ToggleSpectator,
+    SaveWorld,
+    LoadWorld,
+    Fullscreen,
+}
+
+impl Action {
+    /// Every action, in the order shown on the controls page.
+    pub const ALL: [Action; 25] = [
+        Action::Forward,
+        Action::Back,
+        Action::Left,
+        Action::Right,
+        Action::Jump,
+        Action::Sprint,
+        Action::FlySprint,
+        Action::Chat,
+        Action::Command,
+        Action::PlayerList,
+        Action::ToggleDebugOverlay,
+        Action::CycleDebugView,
+        Action::ToggleFaceHighlight,
+        Action::ToggleChunkBorders,
+        Action::TogglePlayerColors,
+        Action::ToggleCelestialPath,
+        Action::ToggleDigCracks,
+        Action::ToggleBloom,
+        Action::CycleReflections,
+        Action::ToggleMinimap,
+        Action::ToggleCoords,
+        Action::ToggleSpectator,
+        Action::SaveWorld,
+        Action::LoadWorld,
+        Action::Fullscreen,
+    ];
+
+    /// Key used in the bindings file.
+    fn config_name(self) -> &'static str {
+        match self {
+            Action::Forward => "forward",
+            Action::Back => "back",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::Jump => "jump",
+            Action::Sprint => "sprint",
+            Action::FlySprint => "fly_sprint",
+            Action::Chat => "chat",
+            Action::Command => "command",
+            Action::PlayerList => "player_list",
+            Action::ToggleDebugOverlay => "debug_overlay",
+            Action::CycleDebugView => "debug_view",
+            Action::ToggleFaceHighlight => "face_highlight",
+            Action::ToggleChunkBorders => "chunk_borders",
+            Action::TogglePlayerColors => "player_colors",
+            Action::ToggleCelestialPath => "celestial_path",
+            Action::ToggleDigCracks => "dig_cracks",
+            Action::ToggleBloom => "bloom",
+            Action::CycleReflections => "reflections",
+            Action::ToggleMinimap => "minimap",
+            Action::ToggleCoords => "coords",
+            Action::ToggleSpectator => "spectator",
+            Action::SaveWorld => "save_world",
+            Action::LoadWorld => "load_world",
+            Action::Fullscreen => "fullscreen",
+        }
+    }
+
+    /// Name shown on the controls page.
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Forward => "Forward",
+            Action::Back => "Back",
+            Action::Left => "Left",
+            Action::Right => "Right",
+            Action::Jump => "Jump / fly up",
+            Action::Sprint => "Sprint / fly down",
+            Action::FlySprint => "Fly faster",
+            Action::Chat => "Chat",
+            Action::Command => "Command",
+            Action::PlayerList => "Player list",
+            Action::ToggleDebugOverlay => "Frame timings",
+            Action::CycleDebugView => "Debug view",
+            Action::ToggleFaceHighlight => "Face highlight",
+            Action::ToggleChunkBorders => "Chunk borders",
+            Action::TogglePlayerColors => "Player colors",
+            Action::ToggleCelestialPath => "Sun & moon path",
+            Action::ToggleDigCracks => "Dig cracks",
+            Action::ToggleBloom => "Bloom",
+            Action::CycleReflections => "Reflections",
+            Action::ToggleMinimap => "Minimap",
+            Action::ToggleCoords => "Coordinates",
+            Action::ToggleSpectator => "Spectator (debug)",
+            Action::SaveWorld => "Save world",
+            Action::LoadWorld => "Load world",
+            Action::Fullscreen => "Fullscreen",
+        }
+    }
+
+    /// Key bound to the action out of the box.
+    fn default_key(self) -> KeyCode {
+        match self {
+            Action::Forward => KeyCode::KeyW,
+            Action::Back => KeyCode::KeyS,
+            Action::Left => KeyCode::KeyA,
+            Action::Right => KeyCode::KeyD,
+            Action::Jump => KeyCode::Space,
+            Action::Sprint => KeyCode::ShiftLeft,
+            Action::FlySprint => KeyCode::ControlLeft,
+            Action::Chat => KeyCode::KeyT,
+            Action::Command => KeyCode::Slash,
+            Action::PlayerList => KeyCode::Tab,
+            Action::ToggleDebugOverlay => KeyCode::F3,
+            Action::CycleDebugView => KeyCode::F7,
+            Action::ToggleFaceHighlight => KeyCode::KeyH,
+            Action::ToggleChunkBorders => KeyCode::KeyG,
+            Action::TogglePlayerColors => KeyCode::KeyP,
+            Action::ToggleCelestialPath => KeyCode::KeyO,
+            Action::ToggleDigCracks => KeyCode::KeyC,
+            Action::ToggleBloom => KeyCode::KeyB,
+            Action::CycleReflections => KeyCode::KeyR,
+            Action::ToggleMinimap => KeyCode::KeyM,
+            Action::ToggleCoords => KeyCode::F4,
+            Action::ToggleSpectator => KeyCode::F6,
+            Action::SaveWorld => KeyCode::F5,
+            Action::LoadWorld => KeyCode::F9,
+            Action::Fullscreen => KeyCode::F11,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Keys that can be bound to an action.
+///
+/// The bindings file stores keys by their `KeyCode` name, and this list is
+/// what those names are parsed against.  Escape and 1–9 are left out on
+/// purpose (see [`Action`]).
+const BINDABLE_KEYS: [KeyCode; 94] = [
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::Space,
+    KeyCode::Tab,
+    KeyCode::CapsLock,
+    KeyCode::Enter,
+    KeyCode::Backspace,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::Backquote,
+    KeyCode::Minus,
+    KeyCode::Equal,
+    KeyCode::BracketLeft,
+    KeyCode::BracketRight,
+    KeyCode::Backslash,
+    KeyCode::IntlBackslash,
+    KeyCode::Semicolon,
+    KeyCode::Quote,
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::Slash,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::Insert,
+    KeyCode::Delete,
+    KeyCode::Home,
+    KeyCode::End,
+    KeyCode::PageUp,
+    KeyCode::PageDown,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+    KeyCode::NumpadAdd,
+    KeyCode::NumpadSubtract,
+    KeyCode::NumpadMultiply,
+    KeyCode::NumpadDivide,
+    KeyCode::NumpadDecimal,
+    KeyCode::NumpadEnter,
+    KeyCode::PrintScreen,
+    KeyCode::ScrollLock,
+    KeyCode::Pause,
+    KeyCode::ContextMenu,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+];
+
+/// Returns `true` if `key` may be bound to an action.
+pub fn is_bindable(key: KeyCode) -> bool {
+    BINDABLE_KEYS.contains(&key)
+}
+
+/// Parses a `KeyCode` name as written by [`KeyBindings::save`].
+fn parse_key(name: &str) -> Option<KeyCode> {
+    BINDABLE_KEYS
+        .iter()
+        .copied()
+        .find(|key| format!("{:?}", key) == name)
+}
+
+/// Short key name for the controls page: `"W"` rather than `"KeyW"`.
+pub fn key_label(key: KeyCode) -> String {
+    let name = format!("{:?}", key);
+    match name
+        .strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+    {
+        Some(short) => short.to_string(),
+        None => name,
+    }
+}
+
+/// The key bound to each [`Action`].
+///
+/// A key belongs to at most one action: binding it again moves it, leaving
+/// the previous action unbound.  Loaded once at startup from the config
+/// directory and written back after every change on the controls page.
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    keys: [Option<KeyCode>; Action::ALL.len()],
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            keys: Action::ALL.map(|action| Some(action.default_key())),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Key bound to `action`, or `None` if it lost its key to another action.
+    pub fn key(&self, action: Action) -> Option<KeyCode> {
+        self.keys[action.index()]
+    }
+
+    /// Action bound to `key`, if any.
+    pub fn action_for(&self, key: KeyCode) -> Option<Action> {
+        Action::ALL
+            .into_iter()
+            .find(|action| self.keys[action.index()] == Some(key))
+    }
+
+    /// Binds `key` to `action`.
+    ///
+    /// If another action already had the key, the last binding wins: that
+    /// action is left unbound, a warning is logged, and it is returned so
+    /// the caller can tell the player.
+    pub fn bind(&mut self, action: Action, key: KeyCode) -> Option<Action> {
+        let previous = self.action_for(key).filter(|&other| other != action);
+        if let Some(other) = previous {
+            log(
+                LogLevel::Warning,
+                &format!(
+                    "{:?} was bound to both {} and {}; keeping it for {}",
+                    key,
+                    other.config_name(),
+                    action.config_name(),
+                    action.config_name()
+                ),
+            );
+            self.keys[other.index()] = None;
+        }
+        self.keys[action.index()] = Some(key);
+        previous
+    }
+
+    /// Path of the bindings file, or `None` if the config directory is
+    /// unknown on this platform.
+    fn path() -> Option<PathBuf> {
+        data::get_project_dirs()
+            .ok()
+            .map(|dirs| dirs.config_dir().join(BINDINGS_FILE))
+    }
+
+    /// Loads the bindings file, falling back to the defaults; see
+    /// [`KeyBindings::parse`].
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text),
+            // No file yet: nothing has been rebound.
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                log(
+                    LogLevel::Warning,
+                    &format!("Could not read {}: {}", path.display(), e),
+                );
+                Self::default()
+            }
+        }
+    }
+
+    /// Reads bindings from the text of a bindings file.
+    ///
+    /// Actions missing from the text keep their default key, so bindings
+    /// files from older versions pick up new actions automatically, and an
+    /// action with an empty key is left unbound.  Unknown actions or key
+    /// names are skipped with a warning, and lines are applied in order, so
+    /// a key listed twice ends up on the later action.
+    fn parse(text: &str) -> Self {
+        let mut bindings = Self::default();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, value)) = line.split_once('=') else {
+                log(
+                    LogLevel::Warning,
+                    &format!("{}:{}: expected `action = Key`", BINDINGS_FILE, number + 1),
+                );
+                continue;
+            };
+            let (name, value) = (name.trim(), value.trim());
+            let Some(action) = Action::ALL
+                .into_iter()
+                .find(|action| action.config_name() == name)
+            else {
+                log(
+                    LogLevel::Warning,
+                    &format!(
+                        "{}:{}: unknown action `{}`",
+                        BINDINGS_FILE,
+                        number + 1,
+                        name
+                    ),
+                );
+                continue;
+            };
+            if value.is_empty() {
+                bindings.keys[action.index()] = None;
+                continue;
+            }
+            match parse_key(value) {
+                Some(key) => {
+                    bindings.bind(action, key);
+                }
+                None => log(
+                    LogLevel::Warning,
+                    &format!("{}:{}: unknown key `{}`", BINDINGS_FILE, number + 1, value),
+                ),
+            }
+        }
+        bindings
+    }
+
+    /// Writes every binding to the bindings file, one `action = Key` line
+    /// each; an unbound action is written with an empty key.
+    ///
+    /// # Errors
+    /// Returns a message if the config directory is unknown or the file
+    /// cannot be written.
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path().ok_or("Could not find the config directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let mut text = String::from("# Minerust key bindings: action = KeyCode\n");
+        for action in Action::ALL {
+            let key = self
+                .key(action)
+                .map(|key| format!("{:?}", key))
+                .unwrap_or_default();
+            text.push_str(&format!("{} = {}\n", action.config_name(), key));
+        }
+        fs::write(&path, text).map_err(|e| e.to_string())
+    }
+}
+
+impl State {
+    /// Starts waiting for the new key of `action` (its row on the controls
+    /// page was clicked).
+    pub fn start_rebind(&mut self, action: Action) {
+        self.pending_rebind = Some(action);
+        self.menu_state.clear_error();
+    }
+
+    /// Handles a key press on the controls page while an action is waiting
+    /// for its new key.
+    ///
+    /// Escape cancels.  Keys that cannot be bound are reported in the status
+    /// pill and keep waiting.  Otherwise the key is bound, moving it off any
+    /// action that had it, and the bindings file is rewritten.
+    pub fn handle_rebind_key(&mut self, key: KeyCode) {
+        let Some(action) = self.pending_rebind else {
+            return;
+        };
+        if key == KeyCode::Escape {
+            self.pending_rebind = None;
+            return;
+        }
+        if !is_bindable(key) {
+            self.menu_state
+                .set_error(&format!("{} cannot be rebound", key_label(key)));
+            return;
+        }
+        self.menu_state.clear_error();
+        self.pending_rebind = None;
+        let status = match self.key_bindings.bind(action, key) {
+            Some(other) => format!(
+                "{} moved from {} to {}; {} is unbound",
+                key_label(key),
+                other.label(),
+                action.label(),
+                other.label()
+            ),
+            None => format!("{} bound to {}", action.label(), key_label(key)),
+        };
+        self.menu_state.set_status(&status);
+        self.save_key_bindings();
+    }
+
+    /// Restores every default binding and saves them.
+    pub fn reset_key_bindings(&mut self) {
+        self.pending_rebind = None;
+        self.key_bindings = KeyBindings::default();
+        self.menu_state.set_status("Default key bindings restored");
+        self.save_key_bindings();
+    }
+
+    /// Leaves the controls page, cancelling a pending rebind.
+    pub fn close_controls(&mut self) {
+        self.pending_rebind = None;
+        self.menu_state.close_controls();
+    }
+
+    fn save_key_bindings(&mut self) {
+        if let Err(e) = self.key_bindings.save() {
+            log(
+                LogLevel::Error,
+                &format!("Failed to save key bindings: {}", e),
+            );
+            self.menu_state
+                .set_error(&format!("Could not save key bindings: {}", e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_key_listed_twice_stays_with_the_later_action() {
+        let bindings = KeyBindings::parse("forward = KeyQ\nback = KeyQ\n");
+        assert_eq!(bindings.key(Action::Back), Some(KeyCode::KeyQ));
+        assert_eq!(bindings.key(Action::Forward), None);
+        assert_eq!(bindings.action_for(KeyCode::KeyQ), Some(Action::Back));
+    }
+
+    #[test]
+    fn unknown_lines_are_skipped() {
+        let bindings = KeyBindings::parse(
+            "# comment\nteleport = KeyQ\njump = Escape\nleft = KeyQ\nnot a binding\n",
+        );
+        // Only `left` applied; `jump` kept its default, as Escape cannot be
+        // bound.
+        assert_eq!(bindings.key(Action::Left), Some(KeyCode::KeyQ));
+        assert_eq!(bindings.key(Action::Jump), Some(KeyCode::Space));
+        assert_eq!(bindings.key(Action::Forward), Some(KeyCode::KeyW));
+    }
+
+    #[test]
+    fn an_empty_key_leaves_the_action_unbound() {
+        let bindings = KeyBindings::parse("chat =\n");
+        assert_eq!(bindings.key(Action::Chat), None);
+        assert_eq!(bindings.action_for(KeyCode::KeyT), None);
+    }
+
+    #[test]
+    fn key_names_round_trip_through_their_config_spelling() {
+        assert_eq!(parse_key("KeyW"), Some(KeyCode::KeyW));
+        assert_eq!(parse_key("F3"), Some(KeyCode::F3));
+        assert_eq!(parse_key("W"), None);
+        assert_eq!(parse_key("Escape"), None);
+        for key in BINDABLE_KEYS {
+            assert_eq!(parse_key(&format!("{:?}", key)), Some(key));
+        }
+
+        assert_eq!(key_label(KeyCode::KeyW), "W");
+        assert_eq!(key_label(KeyCode::Digit0), "0");
+        assert_eq!(key_label(KeyCode::ShiftLeft), "ShiftLeft");
+    }
+}
@@ -1,11 +1,38 @@
+mod atmosphere;
+#[cfg(feature = "audio")]
+mod audio;
+mod autosave;
+mod bloom;
+mod chunk_progress;
+mod cloud;
+mod commands;
+mod daylight;
+mod debug_view;
+mod filtering;
+mod frame_limiter;
 mod game;
+#[cfg(feature = "gamepad")]
+mod gamepad;
+mod gpu_timer;
+mod graphics_quality;
+mod health;
 mod init;
 mod input;
+mod inventory;
+mod keybindings;
+mod msaa;
+mod particles;
+mod present_mode;
+mod profiler;
 mod render;
 mod resize;
 mod server;
+mod settings;
+mod sky;
+mod sleep;
 mod state;
 mod texture_cache;
+mod time_sync;
 mod update;
 
 pub use game::run_game;
@@ -0,0 +1,154 @@
+use wgpu::{Adapter, Features, TextureFormat, TextureFormatFeatureFlags};
+
+use crate::logger::{LogLevel, log};
+
+use super::bloom::HDR_FORMAT;
+
+/// Sample counts accepted by `--msaa`.  1 turns MSAA off.
+pub const MSAA_SAMPLE_COUNTS: [u32; 4] = [1, 2, 4, 8];
+
+/// Sample count used unless `--msaa` says otherwise.
+pub const DEFAULT_MSAA_SAMPLES: u32 = 4;
+
+/// Formats of the scene targets that share the sample count: the HDR color
+/// target and the depth buffer.
+const SCENE_FORMATS: [TextureFormat; 2] = [HDR_FORMAT, TextureFormat::Depth32Float];
+
+/// `scene_depth` declaration for a multisampled depth buffer: every sample
+/// is read and the resolve keeps their min and max.
+const MULTISAMPLED_DEPTH_SOURCE: &str = "\
+@group(0) @binding(0)
+var scene_depth: texture_depth_multisampled_2d;
+
+fn depth_sample_count() -> u32 {
+    return textureNumSamples(scene_depth);
+}
+
+fn load_depth(coords: vec2<i32>, index: i32) -> f32 {
+    return textureLoad(scene_depth, coords, index);
+}
+";
+
+/// `scene_depth` declaration for a single-sampled depth buffer, where the
+/// resolve is a plain copy.
+const SINGLE_SAMPLED_DEPTH_SOURCE: &str = "\
+@group(0) @binding(0)
+var scene_depth: texture_depth_2d;
+
+fn depth_sample_count() -> u32 {
+    return 1u;
+}
+
+fn load_depth(coords: vec2<i32>, index: i32) -> f32 {
+    return textureLoad(scene_depth, coords, 0);
+}
+";
+
+/// Parses the `--msaa` value, accepting only [`MSAA_SAMPLE_COUNTS`].
+pub fn parse_msaa_samples(value: &str) -> Result<u32, String> {
+    let samples: u32 = value
+        .parse()
+        .map_err(|_| format!("`{}` is not a sample count", value))?;
+    if MSAA_SAMPLE_COUNTS.contains(&samples) {
+        Ok(samples)
+    } else {
+        Err(format!("{} samples is not one of 1, 2, 4 or 8", samples))
+    }
+}
+
+/// Picks the sample count for the scene targets: `requested`, or the largest
+/// smaller count the adapter can render and resolve in every
+/// [`SCENE_FORMATS`] format.
+///
+/// WebGPU only guarantees 1 and 4 samples.  Other counts are used only when
+/// the adapter supports them natively; the returned features then contain
+/// `TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`, which the device must be
+/// created with for them to pass validation.
+pub fn select_msaa_samples(adapter: &Adapter, requested: u32) -> (u32, Features) {
+    let adapter_specific = adapter
+        .features()
+        .contains(Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES);
+
+    let (samples, features) = MSAA_SAMPLE_COUNTS
+        .iter()
+        .rev()
+        .copied()
+        .filter(|&samples| samples <= requested)
+        .find_map(|samples| {
+            if supports_samples(adapter, samples, false) {
+                Some((samples, Features::empty()))
+            } else if adapter_specific && supports_samples(adapter, samples, true) {
+                Some((samples, Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES))
+            } else {
+                None
+            }
+        })
+        .unwrap_or((1, Features::empty()));
+
+    if samples != requested {
+        log(
+            LogLevel::Warning,
+            &format!(
+                "{}x MSAA is not supported by this adapter, using {}x",
+                requested, samples
+            ),
+        );
+    }
+    (samples, features)
+}
+
+/// Whether every scene format can be rendered with `samples` samples and,
+/// for the color target, resolved.  Without `adapter_specific` only the
+/// WebGPU-guaranteed capabilities count.
+fn supports_samples(adapter: &Adapter, samples: u32, adapter_specific: bool) -> bool {
+    samples == 1
+        || SCENE_FORMATS.iter().all(|&format| {
+            let mut flags = adapter.get_texture_format_features(format).flags;
+            if !adapter_specific {
+                flags &= format.guaranteed_format_features(adapter.features()).flags;
+            }
+            flags.sample_count_supported(samples)
+                && (format.is_depth_stencil_format()
+                    || flags.contains(TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE))
+        })
+}
+
+/// WGSL source of the depth-resolve compute shader for a depth buffer with
+/// `samples` samples.
+///
+/// A multisampled depth texture needs a different binding type, so the
+/// declaration of `scene_depth` is chosen here and prepended to
+/// `depth_resolve.wgsl`; its bind group layout must use
+/// `multisampled: samples > 1` to match.
+pub fn depth_resolve_shader_source(samples: u32) -> String {
+    let depth_source = if samples > 1 {
+        MULTISAMPLED_DEPTH_SOURCE
+    } else {
+        SINGLE_SAMPLED_DEPTH_SOURCE
+    };
+    format!(
+        "{}\n{}",
+        depth_source,
+        include_str!("../shaders/depth_resolve.wgsl")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn msaa_flag_accepts_only_supported_sample_counts() {
+        assert_eq!(parse_msaa_samples("1"), Ok(1));
+        assert_eq!(parse_msaa_samples("8"), Ok(8));
+        assert!(parse_msaa_samples("3").is_err());
+        assert!(parse_msaa_samples("16").is_err());
+        assert!(parse_msaa_samples("four").is_err());
+    }
+
+    #[test]
+    fn depth_resolve_binding_matches_sample_count() {
+        assert!(depth_resolve_shader_source(4).contains("texture_depth_multisampled_2d"));
+        assert!(depth_resolve_shader_source(1).contains("texture_depth_2d;"));
+    }
+}
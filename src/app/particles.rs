@@ -0,0 +1,157 @@
+use std::f32::consts::TAU;
+
+use glam::Vec3;
+use minerust::{BlockType, Particle, ParticleLook, ParticleSystem};
+
+use super::state::State;
+
+/// Particles in the splash of a player diving in at full speed.
+const SPLASH_PARTICLES: f32 = 40.0;
+
+/// Entry speed, in blocks/s, that makes a full-strength splash.
+const FULL_SPLASH_SPEED: f32 = 20.0;
+
+/// Entry speed below which wading into water makes no splash.
+const MIN_SPLASH_SPEED: f32 = 2.0;
+
+/// Strength of the splash of a block placed into water.
+const BLOCK_SPLASH_STRENGTH: f32 = 0.3;
+
+/// Color of splash droplets.
+const SPLASH_COLOR: [f32; 4] = [0.85, 0.92, 1.0, 0.8];
+
+/// Debris cubes thrown out by a broken block unless `--break-particles`
+/// says otherwise.
+pub const DEFAULT_BREAK_PARTICLES: u32 = 12;
+
+/// Throws up a burst of water droplets from `at`, on the water surface.
+///
+/// `strength` in `0.0..=1.0` scales the number of droplets and how high
+/// they fly.
+fn splash(particles: &mut ParticleSystem, at: Vec3, strength: f32) {
+    let strength = strength.clamp(0.0, 1.0);
+    let count = (SPLASH_PARTICLES * strength).ceil() as usize;
+    for _ in 0..count {
+        let angle = rand::random::<f32>() * TAU;
+        let spread = 0.5 + 2.0 * rand::random::<f32>();
+        let rise = (2.0 + 4.0 * rand::random::<f32>()) * (0.5 + 0.5 * strength);
+        let droplet = Particle::new(
+            at + Vec3::new(angle.cos(), 0.0, angle.sin()) * 0.3,
+            Vec3::new(angle.cos() * spread, rise, angle.sin() * spread),
+            0.4 + 0.4 * rand::random::<f32>(),
+            0.03 + 0.03 * rand::random::<f32>(),
+            ParticleLook::Flat(SPLASH_COLOR),
+        );
+        if !particles.spawn(droplet) {
+            break;
+        }
+    }
+}
+
+/// Scatters `count` small cubes of `block` from inside the block at
+/// `(x, y, z)`, flying outwards and a little up.
+fn break_debris(
+    particles: &mut ParticleSystem,
+    (x, y, z): (i32, i32, i32),
+    block: BlockType,
+    count: u32,
+) {
+    let centre = Vec3::new(x as f32 + 0.5, y as f32 + 0.5, z as f32 + 0.5);
+    for _ in 0..count {
+        let offset = Vec3::new(
+            rand::random::<f32>() - 0.5,
+            rand::random::<f32>() - 0.5,
+            rand::random::<f32>() - 0.5,
+        ) * 0.7;
+        let velocity = offset * 4.0 + Vec3::Y * (1.5 + 2.0 * rand::random::<f32>());
+        let cube = Particle::new(
+            centre + offset,
+            velocity,
+            0.5 + 0.5 * rand::random::<f32>(),
+            0.04 + 0.04 * rand::random::<f32>(),
+            ParticleLook::Block(block),
+        );
+        if !particles.spawn(cube) {
+            break;
+        }
+    }
+}
+
+impl State {
+    /// Splashes where the player entered water since the last frame, then
+    /// moves the particles on by `dt` seconds, landing them on solid blocks.
+    pub fn update_particles(&mut self, dt: f32) {
+        let world = self.world.read();
+        if let Some(speed) = self.camera.take_splash()
+            && speed >= MIN_SPLASH_SPEED
+        {
+            let feet = self.camera.position;
+            let (x, z) = (feet.x.floor() as i32, feet.z.floor() as i32);
+            // The surface is the top of the water the feet are in.
+            let mut y = feet.y.floor() as i32;
+            while world.get_block(x, y + 1, z) == BlockType::Water && y < feet.y as i32 + 4 {
+                y += 1;
+            }
+            splash(
+                &mut self.particles,
+                Vec3::new(feet.x, y as f32 + 1.0, feet.z),
+                speed / FULL_SPLASH_SPEED,
+            );
+        }
+        self.particles.update(dt, |p| {
+            world.is_solid(p.x.floor() as i32, p.y.floor() as i32, p.z.floor() as i32)
+        });
+    }
+
+    /// Makes a small splash on top of the water block at `(x, y, z)`, which
+    /// a block is being placed into.
+    pub fn splash_block(&mut self, x: i32, y: i32, z: i32) {
+        let at = Vec3::new(x as f32 + 0.5, y as f32 + 1.0, z as f32 + 0.5);
+        splash(&mut self.particles, at, BLOCK_SPLASH_STRENGTH);
+    }
+
+    /// Scatters debris of `block`, just broken at `(x, y, z)`; as many cubes
+    /// as `--break-particles` asks for.
+    pub fn break_particles(&mut self, x: i32, y: i32, z: i32, block: BlockType) {
+        break_debris(
+            &mut self.particles,
+            (x, y, z),
+            block,
+            self.break_particle_count,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splashes_scale_with_strength_and_debris_with_the_setting() {
+        let mut particles = ParticleSystem::default();
+        splash(&mut particles, Vec3::ZERO, 1.0);
+        assert_eq!(particles.len(), SPLASH_PARTICLES as usize);
+
+        // Weaker splashes throw up fewer droplets.
+        let mut small = ParticleSystem::default();
+        splash(&mut small, Vec3::ZERO, BLOCK_SPLASH_STRENGTH);
+        assert!(!small.is_empty() && small.len() < particles.len());
+
+        let mut debris = ParticleSystem::default();
+        break_debris(&mut debris, (0, 0, 0), BlockType::Stone, 0);
+        assert!(debris.is_empty());
+        break_debris(
+            &mut debris,
+            (0, 0, 0),
+            BlockType::Stone,
+            DEFAULT_BREAK_PARTICLES,
+        );
+        assert_eq!(debris.len(), DEFAULT_BREAK_PARTICLES as usize);
+
+        // Bursts stop at the particle cap.
+        for _ in 0..100 {
+            splash(&mut particles, Vec3::ZERO, 1.0);
+        }
+        assert_eq!(particles.len(), minerust::MAX_PARTICLES);
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+use crate::logger::{LogLevel, log};
+
+use super::state::State;
+
+/// How presented frames are synchronised with the display, picked on the
+/// menu and saved in the settings file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VSyncMode {
+    /// Waits for the vertical blank (`Fifo`): no tearing, and the frame
+    /// rate never exceeds the refresh rate.
+    #[default]
+    On,
+    /// Like [`VSyncMode::On`], but a late frame is shown at once instead
+    /// of waiting for the next refresh (`FifoRelaxed`).
+    Adaptive,
+    /// Presents as fast as frames are rendered (`Immediate`, or `Mailbox`
+    /// where immediate presentation is missing).  May tear.
+    Off,
+}
+
+impl VSyncMode {
+    /// Every mode, in the order the menu cycles through them.
+    pub const ALL: [VSyncMode; 3] = [VSyncMode::On, VSyncMode::Adaptive, VSyncMode::Off];
+
+    /// The mode after this one, wrapping around to the first.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&m| m == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Name shown on the menu toggle button.
+    pub fn label(self) -> &'static str {
+        match self {
+            VSyncMode::On => "ON",
+            VSyncMode::Adaptive => "ADAPTIVE",
+            VSyncMode::Off => "OFF",
+        }
+    }
+
+    /// Present modes that implement this mode, best first.
+    fn present_modes(self) -> &'static [wgpu::PresentMode] {
+        match self {
+            VSyncMode::On => &[wgpu::PresentMode::Fifo],
+            VSyncMode::Adaptive => &[wgpu::PresentMode::FifoRelaxed],
+            VSyncMode::Off => &[wgpu::PresentMode::Immediate, wgpu::PresentMode::Mailbox],
+        }
+    }
+
+    /// Returns the present mode to configure the surface with, given the
+    /// modes the surface `supported`.
+    ///
+    /// Falls back to `Fifo`, which every surface supports, when none of
+    /// this mode's present modes are available.
+    pub fn present_mode(self, supported: &[wgpu::PresentMode]) -> wgpu::PresentMode {
+        self.present_modes()
+            .iter()
+            .copied()
+            .find(|mode| supported.contains(mode))
+            .unwrap_or(wgpu::PresentMode::Fifo)
+    }
+}
+
+impl State {
+    /// Switches to `mode`, reconfigures the surface with the matching
+    /// present mode and saves the choice.
+    ///
+    /// A mode the surface cannot provide falls back to `Fifo`, and the
+    /// fallback is reported in the menu status pill.
+    pub fn set_vsync_mode(&mut self, mode: VSyncMode) {
+        self.vsync_mode = mode;
+        let present_mode = mode.present_mode(&self.present_modes);
+        self.config.present_mode = present_mode;
+        self.surface.configure(&self.device, &self.config);
+        log(
+            LogLevel::Info,
+            &format!("VSync {:?}: presenting with {:?}", mode, present_mode),
+        );
+        if !mode.present_modes().contains(&present_mode) {
+            self.menu_state.set_status(&format!(
+                "{:?} is not supported here, using VSync",
+                mode.present_modes()[0]
+            ));
+        }
+        self.save_settings();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wgpu::PresentMode;
+
+    #[test]
+    fn unsupported_modes_fall_back_to_fifo() {
+        let all = [
+            PresentMode::Fifo,
+            PresentMode::FifoRelaxed,
+            PresentMode::Immediate,
+            PresentMode::Mailbox,
+        ];
+        assert_eq!(VSyncMode::On.present_mode(&all), PresentMode::Fifo);
+        assert_eq!(
+            VSyncMode::Adaptive.present_mode(&all),
+            PresentMode::FifoRelaxed
+        );
+        assert_eq!(VSyncMode::Off.present_mode(&all), PresentMode::Immediate);
+
+        // Wayland, for one, offers Mailbox but not Immediate.
+        let no_immediate = [PresentMode::Fifo, PresentMode::Mailbox];
+        assert_eq!(
+            VSyncMode::Off.present_mode(&no_immediate),
+            PresentMode::Mailbox
+        );
+        assert_eq!(
+            VSyncMode::Adaptive.present_mode(&no_immediate),
+            PresentMode::Fifo
+        );
+        assert_eq!(
+            VSyncMode::Off.present_mode(&[PresentMode::Fifo]),
+            PresentMode::Fifo
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// CPU time per named system, in the order the systems first ran.
+///
+/// A handful of entries per frame, so a linear scan beats hashing.
+type Timings = Vec<(&'static str, Duration)>;
+
+#[derive(Default)]
+struct FrameTimings {
+    /// Timings being collected for the frame in progress.
+    current: Timings,
+    /// Timings of the last completed frame, shown in the debug overlay.
+    last: Timings,
+}
+
+thread_local! {
+    // Update and render both run on the main thread; scopes opened on other
+    // threads land in that thread's own (never displayed) map.
+    static FRAME: RefCell<FrameTimings> = RefCell::new(FrameTimings::default());
+}
+
+/// Measures the CPU time from its creation until it is dropped and adds it
+/// to the current frame's entry for `name`.
+///
+/// Entries with the same name are summed, so a scope inside a loop reports
+/// the whole loop.  End a scope early with `drop(scope)`.
+///
+/// ```ignore
+/// let scope = ProfileScope::new("network");
+/// self.update_network_state();
+/// drop(scope);
+/// ```
+pub struct ProfileScope {
+    name: &'static str,
+    start: Instant,
+}
+
+impl ProfileScope {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        FRAME.with_borrow_mut(|frame| {
+            let entry = frame
+                .current
+                .iter_mut()
+                .find(|(name, _)| *name == self.name);
+            match entry {
+                Some((_, total)) => *total += elapsed,
+                None => frame.current.push((self.name, elapsed)),
+            }
+        });
+    }
+}
+
+/// Closes the current frame: its timings become the ones reported by
+/// [`last_frame_summary`] and collection restarts from zero.
+///
+/// Called once per redraw, before `update`.
+pub fn end_frame() {
+    FRAME.with_borrow_mut(|frame| {
+        frame.last = std::mem::take(&mut frame.current);
+    });
+}
+
+/// Formats the last completed frame as one `"name: 1.23 ms"` line per
+/// system, in the order they ran.
+pub fn last_frame_summary() -> String {
+    FRAME.with_borrow(|frame| {
+        let mut summary = String::new();
+        for (name, time) in &frame.last {
+            let _ = writeln!(summary, "{}: {:.2} ms", name, time.as_secs_f32() * 1000.0);
+        }
+        summary
+    })
+}
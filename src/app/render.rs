@@ -1,18 +1,30 @@
-use glam::{Mat4, Vec3};
 use glyphon::{Attrs, Color, Family, Metrics, Shaping, TextArea, TextBounds};
 use wgpu::util::DeviceExt;
 
 use minerust::{
-    BlockType, CHUNK_SIZE, DEFAULT_FOV, RENDER_DISTANCE, SEA_LEVEL, Uniforms, Vertex, World,
-    build_block_outline, build_player_model, extract_frustum_planes,
+    BlockType, CELESTIAL_MARKER_COLOR, CELESTIAL_PATH_COLOR, CELESTIAL_PATH_RADIUS,
+    CHUNK_BORDER_COLOR, CHUNK_SIZE, DAY_LENGTH, FACE_HIGHLIGHT_COLOR, NEAR_PLANE, OutlineVertex,
+    PLACEMENT_PREVIEW_COLOR, PLAYER_SHIRT_COLOR, RENDER_DISTANCE, Uniforms, Vertex, World,
+    append_mob_model, append_player_model, build_block_cracks, build_block_outline,
+    build_celestial_path, build_chunk_borders, build_face_highlight, build_placement_preview,
+    extract_frustum_planes, far_plane, sun_direction,
 };
 
 use crate::logger::{LogLevel, log};
-use crate::multiplayer::player::queue_remote_players_labels;
-use crate::ui::menu::{GameState, MenuField, MenuLayout, Rect};
+use crate::multiplayer::player::{player_color, queue_remote_players_labels};
+use crate::ui::menu::{
+    BINDING_ROWS_PER_COLUMN, GameState, MenuField, MenuHit, MenuLayout, MenuScreen, Rect,
+};
+use crate::ui::minimap;
 
+use super::cloud::visible_cloud_coverage;
+use super::daylight::{MAX_MIN_AMBIENT, day_light};
+use super::gpu_timer::GpuPhase;
+use super::health::damage_vignette;
 use super::init::OPENGL_TO_WGPU_MATRIX;
 use super::init::frustum_planes_to_array;
+use super::keybindings::{Action, key_label};
+use super::profiler::{self, ProfileScope};
 use super::state::State;
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -105,6 +117,45 @@ fn visible_outline_faces(world: &World, bx: i32, by: i32, bz: i32) -> [bool; 6]
     ]
 }
 
+/// Draws one GPU-culled indirect command list with up to `max_count` draws.
+///
+/// With `MULTI_DRAW_INDIRECT_COUNT` the culling pass's `count` buffer bounds
+/// the draws; otherwise all `max_count` commands are issued and the unused
+/// ones, cleared to zero before culling, draw nothing.
+fn draw_culled(
+    pass: &mut wgpu::RenderPass,
+    commands: &wgpu::Buffer,
+    count: &wgpu::Buffer,
+    max_count: u32,
+    supports_indirect_count: bool,
+) {
+    if supports_indirect_count {
+        pass.multi_draw_indexed_indirect_count(commands, 0, count, 0, max_count);
+    } else {
+        pass.multi_draw_indexed_indirect(commands, 0, max_count);
+    }
+}
+
+/// Color attachment of a scene pass that ends up in `target`.
+///
+/// With MSAA the pass draws into the multisampled `msaa_view` and resolves
+/// into `target`; without it the pass draws into `target` directly.
+fn scene_color_attachment<'a>(
+    msaa_view: Option<&'a wgpu::TextureView>,
+    target: &'a wgpu::TextureView,
+    load: wgpu::LoadOp<wgpu::Color>,
+) -> wgpu::RenderPassColorAttachment<'a> {
+    wgpu::RenderPassColorAttachment {
+        view: msaa_view.unwrap_or(target),
+        resolve_target: msaa_view.map(|_| target),
+        depth_slice: None,
+        ops: wgpu::Operations {
+            load,
+            store: wgpu::StoreOp::Store,
+        },
+    }
+}
+
 impl State {
     /// Produces one complete frame and presents it to the OS window.
     ///
@@ -113,7 +164,7 @@ impl State {
     /// The frame is built from the following render / compute passes in order:
     ///
     /// 1. **Player model update** – re-builds the combined vertex/index buffers
-    ///    for all visible remote players if any exist.
+    ///    for all visible remote players, mobs and debris cubes if any exist.
     /// 2. **Uniform upload** – computes the camera matrices, advances the day
     ///    cycle, updates CSM cascades, and uploads the `Uniforms` struct.
     /// 3. **Shadow cull + shadow passes** (×`active_cascades`) – each cascade
@@ -123,24 +174,30 @@ impl State {
     ///    meshes for background rebuild, and tallies rendered counts.
     /// 5. **Main cull dispatch** – GPU frustum + Hi-Z occlusion cull for both
     ///    the opaque terrain and water indirect managers.
-    /// 6. **Opaque pass** – sky dome → terrain → remote player models → sun/moon.
-    ///    Resolves MSAA into `ssr_color_view` for later water reflections.
-    /// 7. **Depth resolve compute** – resolves the multisampled depth buffer
-    ///    into `ssr_depth_view` (for water refraction) and the first Hi-Z mip
-    ///    level (for next-frame occlusion culling).
+    /// 6. **Opaque pass** – sky dome → terrain → player, mob and debris models → sun/moon.
+    ///    Resolves MSAA into `ssr_color_view` for later water reflections;
+    ///    without MSAA it renders there and is copied to `scene_color_view`.
+    /// 7. **Depth resolve compute** – resolves the (multisampled) depth buffer
+    ///    into `ssr_depth_view` (for water reflections and the depth fade at
+    ///    shorelines) and the first Hi-Z mip level (for next-frame occlusion
+    ///    culling).
     /// 8. **Hi-Z generation** (compute) – downsamples the depth mip chain.
-    /// 9. **Transparent pass** – water surfaces, alpha-blended on top of the
-    ///    opaque result.  Resolves MSAA into `scene_color_view`.
-    /// 10. **Composite pass** – post-processing blit from `scene_color_view`
-    ///     to the swap-chain surface (underwater fog, vignette, etc.).
-    /// 11. **UI pass** – crosshair, coordinate debug overlay, hotbar.
-    /// 12. **Progress bar pass** – block-breaking progress indicator (only
+    /// 9. **Transparent pass** – water surfaces, then glass and ice sorted
+    ///    back to front, alpha-blended on top of the opaque result.
+    ///    Resolves MSAA into `scene_color_view`.
+    /// 10. **Bloom passes** (when enabled) – bright-pass, downsample and
+    ///     blur of `scene_color_view` at half / quarter resolution.
+    /// 11. **Composite pass** – post-processing blit from `scene_color_view`
+    ///     to the swap-chain surface (bloom, underwater grade, vignette).
+    /// 12. **UI pass** – crosshair, coordinate debug overlay, hotbar (only
+    ///     while `game_state` is `Playing`).
+    /// 13. **Progress bar pass** – block-breaking progress indicator (only
     ///     when the player is actively mining).
-    /// 13. **Menu / HUD** – either the main-menu overlay or remote-player
+    /// 14. **Menu / HUD** – either the main-menu overlay or remote-player
     ///     name labels, depending on `game_state`.
-    /// 14. **Text pass** – all `glyphon` text areas (FPS counter, menu
+    /// 15. **Text pass** – all `glyphon` text areas (FPS counter, menu
     ///     labels, hotbar slot name, player name tags).
-    /// 15. **Submit** – the completed command buffer is submitted and the
+    /// 16. **Submit** – the completed command buffer is submitted and the
     ///     swap-chain texture is presented.
     ///
     /// # Errors
@@ -149,83 +206,127 @@ impl State {
     /// The caller should handle `Lost` / `Outdated` by calling `resize`.
     pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
         // ── Acquire swap-chain texture ────────────────────────────────────── //
+        // Blocks while the presentation queue is full, so with vsync this is
+        // where the CPU waits for the display.
+        let scope = ProfileScope::new("surface acquire");
         let output = self.surface.get_current_texture()?;
+        drop(scope);
+        if let Some(timer) = &mut self.gpu_timer {
+            timer.begin_frame(&self.device);
+        }
+        self.indirect_manager.poll_visible_count(&self.device);
         let view = output
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
 
-        // ── Remote player model buffers ───────────────────────────────────── //
-        // All remote player meshes are concatenated into a single vertex/index
-        // buffer pair that grows on demand (doubling strategy).  This avoids
-        // per-player draw calls and keeps buffer management simple.
-        if !self.remote_players.is_empty() && self.game_state != GameState::Menu {
-            let mut all_vertices = Vec::with_capacity(self.remote_players.len() * 16);
-            let mut all_indices = Vec::with_capacity(self.remote_players.len() * 24);
-
-            for (_id, player) in &self.remote_players {
-                let (vertices, indices) =
-                    build_player_model(player.x, player.y, player.z, player.yaw);
-                let base_idx = all_vertices.len() as u32;
-                all_vertices.extend(vertices);
-                // Remap local indices to the combined buffer's address space.
-                all_indices.extend(indices.iter().map(|i| i + base_idx));
-            }
+        // Nothing can be drawn with the placeholder atlas, so only the
+        // loading screen is shown until the real one is in.
+        if self.atlas_loader.is_some() {
+            return self.render_loading_screen(output, &view);
+        }
 
-            self.player_model_num_indices = all_indices.len() as u32;
+        // ── Remote player, mob and debris model buffers ───────────────────── //
+        // All remote player and mob meshes, and the cubes of broken-block
+        // debris, are concatenated into a single persistent vertex/index
+        // buffer pair that grows on demand (doubling strategy).  Vertices
+        // follow the models' poses and are rewritten every frame; the index
+        // pattern only depends on how many of each there are, so it is
+        // uploaded again only when that changes.
+        let world = self.world.read();
+        let model_counts = if self.game_state != GameState::Menu {
+            (
+                self.remote_players.len(),
+                world.mobs.len(),
+                self.particles.block_count(),
+            )
+        } else {
+            (0, 0, 0)
+        };
+        if model_counts != (0, 0, 0) {
+            let vertices = &mut self.player_model_vertices;
+            let indices = &mut self.player_model_indices;
+            vertices.clear();
+            indices.clear();
+
+            // Models are drawn at an interpolated pose between the last two
+            // network updates rather than snapping to each packet.
+            let now = std::time::Instant::now();
+            for (&id, player) in &self.remote_players {
+                let (x, y, z, yaw) = player.interpolated_pose(now);
+                let shirt = if self.player_colors {
+                    player_color(id)
+                } else {
+                    PLAYER_SHIRT_COLOR
+                };
+                append_player_model(vertices, indices, x, y, z, yaw, shirt);
+            }
+            // Mobs go after every player so the index pattern stays a
+            // function of the two counts.
+            for mob in &world.mobs {
+                append_mob_model(vertices, indices, mob);
+            }
+            self.particles.append_cubes(vertices, indices);
 
-            if !all_vertices.is_empty() {
-                let needed_verts = all_vertices.len() as u32;
-                let needed_idxs = all_indices.len() as u32;
+            let needed_verts = vertices.len() as u32;
+            let needed_idxs = indices.len() as u32;
+            let mut indices_stale = model_counts != self.player_model_index_counts;
 
-                // Grow the vertex buffer if it no longer fits all players.
-                // New capacity = 2× required, minimum 256 vertices.
-                if needed_verts > self.player_model_vertex_capacity
-                    || self.player_model_vertex_buffer.is_none()
-                {
-                    let new_cap = (needed_verts * 2).max(256);
-                    self.player_model_vertex_buffer =
-                        Some(self.device.create_buffer(&wgpu::BufferDescriptor {
-                            label: Some("Player Model Vertex Buffer"),
-                            size: (new_cap as usize * size_of::<Vertex>()) as u64,
-                            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                            mapped_at_creation: false,
-                        }));
-                    self.player_model_vertex_capacity = new_cap;
-                }
-                // Same doubling strategy for the index buffer.
-                if needed_idxs > self.player_model_index_capacity
-                    || self.player_model_index_buffer.is_none()
-                {
-                    let new_cap = (needed_idxs * 2).max(512);
-                    self.player_model_index_buffer =
-                        Some(self.device.create_buffer(&wgpu::BufferDescriptor {
-                            label: Some("Player Model Index Buffer"),
-                            size: (new_cap as usize * size_of::<u32>()) as u64,
-                            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
-                            mapped_at_creation: false,
-                        }));
-                    self.player_model_index_capacity = new_cap;
-                }
+            // Grow the vertex buffer if it no longer fits all players.
+            // New capacity = 2× required, minimum 256 vertices.
+            if needed_verts > self.player_model_vertex_capacity
+                || self.player_model_vertex_buffer.is_none()
+            {
+                let new_cap = (needed_verts * 2).max(256);
+                self.player_model_vertex_buffer =
+                    Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+                        label: Some("Player Model Vertex Buffer"),
+                        size: (new_cap as usize * size_of::<Vertex>()) as u64,
+                        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                        mapped_at_creation: false,
+                    }));
+                self.player_model_vertex_capacity = new_cap;
+            }
+            // Same doubling strategy for the index buffer.  A new buffer
+            // starts out empty, so the indices must be written again.
+            if needed_idxs > self.player_model_index_capacity
+                || self.player_model_index_buffer.is_none()
+            {
+                let new_cap = (needed_idxs * 2).max(512);
+                self.player_model_index_buffer =
+                    Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+                        label: Some("Player Model Index Buffer"),
+                        size: (new_cap as usize * size_of::<u32>()) as u64,
+                        usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+                        mapped_at_creation: false,
+                    }));
+                self.player_model_index_capacity = new_cap;
+                indices_stale = true;
+            }
 
-                self.queue.write_buffer(
-                    self.player_model_vertex_buffer
-                        .as_ref()
-                        .expect("Player model vertex buffer should be initialized"),
-                    0,
-                    bytemuck::cast_slice(&all_vertices),
-                );
+            self.queue.write_buffer(
+                self.player_model_vertex_buffer
+                    .as_ref()
+                    .expect("Player model vertex buffer should be initialized"),
+                0,
+                bytemuck::cast_slice(vertices),
+            );
+            if indices_stale {
                 self.queue.write_buffer(
                     self.player_model_index_buffer
                         .as_ref()
                         .expect("Player model index buffer should be initialized"),
                     0,
-                    bytemuck::cast_slice(&all_indices),
+                    bytemuck::cast_slice(indices),
                 );
+                self.player_model_index_counts = model_counts;
             }
+            self.player_model_num_indices = needed_idxs;
         } else {
-            // No remote players or we're in the menu – skip the draw later.
+            // No remote players, mobs or debris, or we're in the menu – skip
+            // the draw later.
             self.player_model_num_indices = 0;
         }
+        drop(world);
 
         // ── Command encoder ───────────────────────────────────────────────── //
         let mut encoder = self
@@ -236,10 +337,10 @@ impl State {
 
         // ── Camera & projection matrices ──────────────────────────────────── //
         let aspect = self.config.width as f32 / self.config.height as f32;
-        // Extend the far plane beyond RENDER_DISTANCE so chunks at the horizon
-        // are not clipped by the projection; 400 blocks is a sensible floor.
-        let far_plane = (RENDER_DISTANCE as f32 * CHUNK_SIZE as f32 * 1.5).max(400.0);
-        let proj = Mat4::perspective_rh(DEFAULT_FOV, aspect, 0.1, far_plane);
+        // The FOV widens while sprinting (see `Camera::fov`), so the
+        // projection is rebuilt every frame; the CSM fit below uses the same
+        // FOV and clip planes so cascades keep covering the visible frustum.
+        let proj = self.camera.projection_matrix(aspect);
         let view_mat = self.camera.view_matrix();
         // Combine projection, view, and the OpenGL→wgpu NDC correction into
         // one matrix uploaded to the GPU once per frame.
@@ -247,29 +348,31 @@ impl State {
         let view_proj_array: [[f32; 4]; 4] = view_proj.to_cols_array_2d();
 
         // ── Day/night cycle ───────────────────────────────────────────────── //
+        // `time` animates the shaders; the sun follows the day-cycle clock,
+        // which can be saved and run at a different speed.
         let time = self.game_start_time.elapsed().as_secs_f32();
-
-        // `day_cycle_speed` controls how fast the sun orbits.  At 0.005 rad/s
-        // a full day takes ~1257 seconds (≈21 minutes).
-        let day_cycle_speed = 0.005;
-        // Offset by π/2 so the sun starts at noon (Y = +1) rather than
-        // the horizon.
-        let sun_angle = time * day_cycle_speed + std::f32::consts::FRAC_PI_2;
-        let sun_x = 0.0;
-        let sun_y = sun_angle.sin(); // +1 = overhead noon, −1 = midnight
-        let sun_z = sun_angle.cos();
-        let sun_dir = Vec3::new(sun_x, sun_y, sun_z).normalize();
+        let sun_dir = sun_direction(self.time_of_day);
         let moon_intensity = (-sun_dir.y).clamp(0.0, 1.0);
 
         // The moon is always opposite the sun direction.
         let moon_position = [-sun_dir.x, -sun_dir.y, -sun_dir.z];
 
         // ── CSM (Cascaded Shadow Maps) update ─────────────────────────────── //
-        // `CsmManager::update` computes the four tight orthographic light-space
-        // matrices that cover successive depth ranges of the camera frustum.
+        // `CsmManager::update` computes the tight orthographic light-space
+        // matrices that cover successive depth ranges of the camera frustum,
+        // one per active cascade.
         let csm = &mut self.csm;
-        let fov_y = DEFAULT_FOV;
-        csm.update(&view_mat, sun_dir, 0.1, 300.0, aspect, fov_y);
+        let fov_y = self.camera.fov;
+        // Cascades end at the last split distance or the far plane,
+        // whichever is nearer.
+        csm.update(
+            &view_mat,
+            sun_dir,
+            NEAR_PLANE,
+            far_plane(RENDER_DISTANCE),
+            aspect,
+            fov_y,
+        );
 
         // Pack cascade view-projection matrices into the uniform struct format.
         let csm_view_proj: [[[f32; 4]; 4]; 4] = [
@@ -294,6 +397,19 @@ impl State {
 
         let eye_pos = self.camera.eye_position();
         let is_underwater = self.is_underwater;
+        let water_level = self.world.read().sea_level() as f32 - 1.0;
+
+        // There is no weather yet, so it never rains and the clouds are
+        // never hidden by an overcast sky.
+        let rain_factor = 0.0;
+        let cloud_coverage = visible_cloud_coverage(self.cloud_coverage, rain_factor);
+        let light = day_light(sun_dir.y, self.min_ambient);
+        // The biome tints the ambient light, and its haze dims with the
+        // daylight so distant terrain does not glow at night.  The haze
+        // starts at 40 % of the render distance and peaks at its edge.
+        let atmosphere = self.atmosphere;
+        let haze_light = 0.12 + 0.88 * light.day_factor;
+        let fog_end = (RENDER_DISTANCE * CHUNK_SIZE) as f32;
 
         // ── Upload uniforms ───────────────────────────────────────────────── //
         self.queue.write_buffer(
@@ -306,10 +422,10 @@ impl State {
                 csm_split_distances,
                 camera_pos: eye_pos.to_array(),
                 time,
-                sun_position: [sun_x, sun_y, sun_z],
+                sun_position: sun_dir.to_array(),
                 is_underwater,
                 screen_size: [self.config.width as f32, self.config.height as f32],
-                water_level: SEA_LEVEL as f32 - 1.0,
+                water_level,
                 reflection_mode: self.reflection_mode as f32,
                 moon_position,
                 _pad1_moon: 0.0,
@@ -317,7 +433,26 @@ impl State {
                 wind_dir: [0.8, 0.6],
                 wind_speed: 1.0,
                 _pad: 0.0,
-                rain_factor: 0.0,
+                rain_factor,
+                cloud_coverage,
+                _pad_clouds: 0.0,
+                ambient_color: std::array::from_fn(|i| {
+                    light.ambient[i] * atmosphere.ambient_tint[i]
+                }),
+                day_factor: light.day_factor,
+                dawn_tint: light.dawn_tint,
+                _pad_light: 0.0,
+                fog_color: std::array::from_fn(|i| {
+                    atmosphere.fog_color[i]
+                        * haze_light
+                        * light.dawn_tint[i]
+                        * self.sky_palette.fog_tint[i]
+                }),
+                fog_strength: atmosphere.fog_strength,
+                fog_start: fog_end * 0.4,
+                fog_end,
+                _pad_fog: [0.0; 2],
+                sky_palette: self.sky_palette.to_uniform(),
             }]),
         );
 
@@ -330,11 +465,12 @@ impl State {
         let player_cx = (self.camera.position.x / CHUNK_SIZE as f32).floor() as i32;
         let player_cz = (self.camera.position.z / CHUNK_SIZE as f32).floor() as i32;
 
-        // Fewer cascades are needed at short render distances because the far
-        // splits collapse below useful thresholds.
-        let active_cascades = minerust::get_active_cascade_count(RENDER_DISTANCE);
+        // Set by the graphics quality preset; lower presets and short render
+        // distances use fewer cascades (see `GraphicsQuality::cascade_count`).
+        let active_cascades = csm.cascade_count;
 
         // ── Shadow cascade buffer upload + shadow cull ────────────────────── //
+        let scope = ProfileScope::new("shadow passes");
         let mut shadow_frustum_arrays = [[[0f32; 4]; 6]; 4];
         for i in 0..active_cascades {
             // Pack the cascade's light-space matrix into a 256-byte aligned
@@ -357,8 +493,9 @@ impl State {
             shadow_frustum_arrays[i] = frustum_planes_to_array(&shadow_frustum);
         }
 
-        // Dispatch GPU occlusion + frustum culling for each active cascade,
-        // for both opaque terrain and water chunks.
+        // Dispatch GPU frustum culling of the terrain for each active
+        // cascade.  Water is never drawn into the shadow maps, so it is not
+        // culled against them either.
         for i in 0..active_cascades {
             self.indirect_manager.dispatch_shadow_culling(
                 &mut encoder,
@@ -366,12 +503,6 @@ impl State {
                 i,
                 &shadow_frustum_arrays[i],
             );
-            self.water_indirect_manager.dispatch_shadow_culling(
-                &mut encoder,
-                &self.queue,
-                i,
-                &shadow_frustum_arrays[i],
-            );
         }
 
         // ── Shadow depth passes (one per active cascade) ──────────────────── //
@@ -397,7 +528,11 @@ impl State {
                     }),
                     stencil_ops: None,
                 }),
-                timestamp_writes: None,
+                timestamp_writes: self.render_timestamps(
+                    GpuPhase::Shadows,
+                    i == 0,
+                    i + 1 == active_cascades,
+                ),
                 occlusion_query_set: None,
                 multiview_mask: None,
             });
@@ -428,13 +563,26 @@ impl State {
                     self.indirect_manager.active_count(),
                 );
             }
+            // Leaves and glass cast shadows like the opaque terrain.
+            if self.indirect_manager.has_translucent() {
+                draw_culled(
+                    &mut shadow_pass,
+                    self.indirect_manager.shadow_translucent_draw_commands(i),
+                    self.indirect_manager.shadow_translucent_count_buffer(i),
+                    self.indirect_manager.active_count(),
+                    self.supports_indirect_count,
+                );
+            }
         }
 
+        drop(scope);
+
         // ── Mesh rebuild requests ─────────────────────────────────────────── //
+        let scope = ProfileScope::new("mesh requests");
         // Walk all chunks within RENDER_DISTANCE.  For each sub-chunk whose
-        // mesh is stale and not already being rebuilt on a worker thread,
-        // queue a rebuild request.  Requests are sorted nearest-first so the
-        // closest geometry always appears first.
+        // mesh is stale and neither being rebuilt on a worker thread nor
+        // waiting for its upload, queue a rebuild request.  Requests are
+        // sorted nearest-first so the closest geometry always appears first.
         let mut meshes_to_request: Vec<(i32, i32, i32)> = Vec::new();
         let mut chunks_rendered = 0u32;
         let mut subchunks_rendered = 0u32;
@@ -451,6 +599,7 @@ impl State {
                             }
                             if subchunk.mesh_dirty
                                 && !self.mesh_loader.is_pending(cx, cz, sy as i32)
+                                && !self.mesh_uploads.contains(cx, cz, sy as i32)
                             {
                                 meshes_to_request.push((cx, cz, sy as i32));
                             }
@@ -477,35 +626,19 @@ impl State {
         for (cx, cz, sy) in &meshes_to_request {
             self.mesh_loader.request_mesh(*cx, *cz, *sy);
         }
+        drop(scope);
 
         // ── Sky color interpolation ──────────────────────────────────────── //
-        // Three anchor colors (day, sunset, night) are blended based on the
-        // sun's Y component so the sky transitions smoothly through the day.
-        let day_factor = sun_dir.y.max(0.0).min(1.0); // 1 at noon
-        let night_factor = (-sun_dir.y).max(0.0).min(1.0); // 1 at midnight
-        let sunset_factor = 1.0 - sun_dir.y.abs(); // 1 at horizon
-
-        let day_sky = (0.53, 0.81, 0.98); // light blue
-        let sunset_sky = (1.0, 0.5, 0.2); // orange
-        let night_sky = (0.001, 0.001, 0.005); // near-black
-
-        let sky_r: f32 = (day_sky.0 * day_factor
-            + sunset_sky.0 * sunset_factor * 0.5
-            + night_sky.0 * night_factor)
-            .min(1.0);
-        let sky_g: f32 = (day_sky.1 * day_factor
-            + sunset_sky.1 * sunset_factor * 0.5
-            + night_sky.1 * night_factor)
-            .min(1.0);
-        let sky_b: f32 = (day_sky.2 * day_factor
-            + sunset_sky.2 * sunset_factor * 0.5
-            + night_sky.2 * night_factor)
-            .min(1.0);
+        // The palette's day, dusk and night horizon colors are blended based
+        // on the sun's Y component so the sky transitions smoothly through
+        // the day.
+        let [sky_r, sky_g, sky_b] = self.sky_palette.clear_color(sun_dir.y);
 
         self.chunks_rendered = chunks_rendered;
         self.subchunks_rendered = subchunks_rendered;
 
         // ── Main camera GPU cull dispatch ─────────────────────────────────── //
+        let scope = ProfileScope::new("culling dispatch");
         // The indirect manager's compute shader reads the Hi-Z texture and
         // frustum planes to populate per-chunk indirect draw arguments.
         let frustum_planes_array = frustum_planes_to_array(&frustum_planes);
@@ -529,8 +662,11 @@ impl State {
             hiz_size_f,
             [self.config.width as f32, self.config.height as f32],
         );
+        drop(scope);
 
         // ── Terrain depth prepass ─────────────────────────────────────────── //
+        // Everything from here to the text pass only records GPU commands.
+        let scope = ProfileScope::new("pass recording");
         // Fill the MSAA depth buffer first so we can resolve it and compute a
         // screen-space shadow mask before shading the terrain.
         if self.game_state != GameState::Menu {
@@ -545,6 +681,7 @@ impl State {
                     }),
                     stencil_ops: None,
                 }),
+                timestamp_writes: self.render_timestamps(GpuPhase::DepthPrepass, true, true),
                 ..Default::default()
             });
             depth_prepass.set_pipeline(&self.terrain_depth_pipeline);
@@ -572,6 +709,8 @@ impl State {
                     self.indirect_manager.active_count(),
                 );
             }
+            // Translucent blocks are left out: their depth would hide what
+            // is behind them from the shadow mask and occlude it in Hi-Z.
         }
 
         // ── Depth resolve compute pass ───────────────────────────────────── //
@@ -595,7 +734,7 @@ impl State {
         if self.game_state != GameState::Menu {
             let mut shadow_mask_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: Some("Shadow Mask Compute Pass"),
-                timestamp_writes: None,
+                timestamp_writes: self.compute_timestamps(GpuPhase::ShadowMask, true, true),
             });
             shadow_mask_pass.set_pipeline(&self.shadow_mask_pipeline);
             shadow_mask_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
@@ -610,10 +749,15 @@ impl State {
 
         // ── Hi-Z mip chain generation (compute) ───────────────────────────── //
         // Downsample the resolved depth (seeded above) into the Hi-Z pyramid.
-        for i in 0..self.hiz_bind_groups.len() {
+        let hiz_levels = self.hiz_bind_groups.len();
+        for i in 0..hiz_levels {
             let mut hiz_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: Some("Hi-Z Generation Pass Level"),
-                timestamp_writes: None,
+                timestamp_writes: self.compute_timestamps(
+                    GpuPhase::HiZ,
+                    i == 0,
+                    i + 1 == hiz_levels,
+                ),
             });
             hiz_pass.set_pipeline(&self.hiz_pipeline);
             hiz_pass.set_bind_group(0, &self.hiz_bind_groups[i], &[]);
@@ -624,35 +768,26 @@ impl State {
         }
 
         // ── Opaque pass ───────────────────────────────────────────────────── //
-        // Renders: sky dome → terrain chunks → remote player models → sun/moon.
-        // Writes to the 4× MSAA color target which is resolved simultaneously
+        // Renders: sky dome → terrain chunks → remote player models →
+        // sun/moon.
+        // Writes to the MSAA color target which is resolved simultaneously
         // into `ssr_color_view` (used by the water pass for reflections).
         {
-            let opaque_resolve_target = if self.game_state == GameState::Menu {
-                &view
-            } else {
-                &self.ssr_color_view
-            };
-
             let mut opaque_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Opaque Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.msaa_texture_view,
-                    // Resolve MSAA into the SSR color target so the water
-                    // shader can sample the opaque scene for reflections.
-                    resolve_target: Some(opaque_resolve_target),
-                    depth_slice: None,
-                    ops: wgpu::Operations {
-                        // Clear to the sky color computed above.
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: sky_r as f64,
-                            g: sky_g as f64,
-                            b: sky_b as f64,
-                            a: 1.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
+                // Resolve MSAA into the SSR color target so the water shader
+                // can sample the opaque scene for reflections.  Cleared to
+                // the sky color computed above.
+                color_attachments: &[Some(scene_color_attachment(
+                    self.msaa_texture_view.as_ref(),
+                    &self.ssr_color_view,
+                    wgpu::LoadOp::Clear(wgpu::Color {
+                        r: sky_r as f64,
+                        g: sky_g as f64,
+                        b: sky_b as f64,
+                        a: 1.0,
+                    }),
+                ))],
                 depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
                     view: &self.depth_texture,
                     depth_ops: Some(wgpu::Operations {
@@ -663,6 +798,7 @@ impl State {
                     }),
                     stencil_ops: None,
                 }),
+                timestamp_writes: self.render_timestamps(GpuPhase::Opaque, true, true),
                 ..Default::default()
             });
 
@@ -708,7 +844,7 @@ impl State {
                 );
             }
 
-            // --- Remote player models ---
+            // --- Remote player, mob and debris models ---
             // Drawn with the terrain pipeline so they receive shadow and fog
             // effects consistent with the surrounding world geometry.
             if self.player_model_num_indices > 0 {
@@ -741,28 +877,37 @@ impl State {
             opaque_pass.draw_indexed(0..6, 0, 0..1);
         }
 
-        // ── Transparent (water) pass ──────────────────────────────────────── //
-        // Loads (does not clear) the existing MSAA color and depth buffers so
-        // water is composited on top of the opaque scene.  Resolves into
-        // `scene_color_view` for the composite pass.
-        let resolve_target = if self.game_state == GameState::Menu {
-            &view
-        } else {
-            &self.scene_color_view
-        };
+        // Without MSAA the opaque scene only exists in `ssr_color_view`; the
+        // water pass samples that texture, so it cannot draw on top of it and
+        // continues on a copy in the scene color texture instead.
+        if self.msaa_texture_view.is_none() {
+            encoder.copy_texture_to_texture(
+                self.ssr_color_texture.as_image_copy(),
+                self.scene_color_texture.as_image_copy(),
+                self.ssr_color_texture.size(),
+            );
+        }
 
+        // ── Transparent (water + translucent terrain) pass ────────────────── //
+        // Loads (does not clear) the existing MSAA color and depth buffers so
+        // water, then glass and ice, are composited on top of the opaque
+        // scene.  Resolves into `scene_color_view` for the composite pass.
+        // The MSAA target is floating point, so it always resolves into the
+        // HDR scene texture, even behind the menu.
+        let translucent_draws = self.indirect_manager.sort_translucent(
+            &self.queue,
+            self.camera.position,
+            &frustum_planes,
+        );
         {
             let mut transparent_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Transparent Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.msaa_texture_view,
-                    resolve_target: Some(resolve_target), // -> scene_color_view
-                    depth_slice: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Load, // keep opaque scene color
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
+                // Load keeps the opaque scene color.
+                color_attachments: &[Some(scene_color_attachment(
+                    self.msaa_texture_view.as_ref(),
+                    &self.scene_color_view,
+                    wgpu::LoadOp::Load,
+                ))],
                 depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
                     view: &self.depth_texture,
                     depth_ops: Some(wgpu::Operations {
@@ -771,6 +916,7 @@ impl State {
                     }),
                     stencil_ops: None,
                 }),
+                timestamp_writes: self.render_timestamps(GpuPhase::Water, true, true),
                 ..Default::default()
             });
 
@@ -797,25 +943,44 @@ impl State {
                     self.water_indirect_manager.active_count(),
                 );
             }
+
+            // --- Translucent terrain ---
+            // After the water, so ice on a lake or a window onto the sea
+            // blends over it.  Back to front, as the faces write no depth.
+            if translucent_draws > 0 {
+                transparent_pass.set_pipeline(&self.translucent_pipeline);
+                transparent_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+                transparent_pass.set_bind_group(1, &self.terrain_gbuffer_bind_group, &[]);
+                transparent_pass.set_bind_group(2, &self.terrain_shadow_output_bind_group, &[]);
+                transparent_pass.set_bind_group(3, &self.shadow_mask_bind_group, &[]);
+                transparent_pass
+                    .set_vertex_buffer(0, self.indirect_manager.vertex_buffer().slice(..));
+                transparent_pass.set_index_buffer(
+                    self.indirect_manager.index_buffer().slice(..),
+                    wgpu::IndexFormat::Uint32,
+                );
+                transparent_pass.multi_draw_indexed_indirect(
+                    self.indirect_manager.sorted_translucent_draw_commands(),
+                    0,
+                    translucent_draws,
+                );
+            }
         }
 
         // ── Block outline pass ───────────────────────────────────────────── //
-        // Draw the targeted block outline before the composite pass so the
-        // resolved scene color includes the visible edges. The pass uses the
+        // Draw the targeted block outline and dig cracks (and the
+        // chunk-border overlay, when enabled) before the composite pass so the resolved scene color
+        // includes the visible edges. The pass uses the
         // MSAA color target and the main depth buffer so hidden edges are
         // rejected by depth testing instead of being painted over the scene.
         if self.game_state != GameState::Menu {
             let mut outline_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Block Outline Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.msaa_texture_view,
-                    resolve_target: Some(&self.scene_color_view),
-                    depth_slice: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Load,
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
+                color_attachments: &[Some(scene_color_attachment(
+                    self.msaa_texture_view.as_ref(),
+                    &self.scene_color_view,
+                    wgpu::LoadOp::Load,
+                ))],
                 depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
                     view: &self.depth_texture,
                     depth_ops: Some(wgpu::Operations {
@@ -827,46 +992,160 @@ impl State {
                 ..Default::default()
             });
 
-            if let Some((bx, by, bz)) = self.highlighted_block {
-                let visible_faces = {
-                    let world = self.world.read();
-                    visible_outline_faces(&*world, bx, by, bz)
-                };
-                let (outline_vertices, outline_indices) =
-                    build_block_outline(bx, by, bz, visible_faces);
-                if !outline_vertices.is_empty() && !outline_indices.is_empty() {
-                    let outline_vb =
+            // Cracks on blocks being broken, by us or by remote players.
+            // Textured block faces, blended by the translucent terrain
+            // pipeline before the line overlays go on top.
+            if self.show_dig_cracks {
+                let mut crack_vertices = Vec::new();
+                let mut crack_indices = Vec::new();
+                let targets = self
+                    .digging
+                    .target
+                    .map(|block| (block, self.digging.stage()))
+                    .into_iter()
+                    .chain(self.remote_players.values().filter_map(|p| p.digging));
+                for ((bx, by, bz), stage) in targets {
+                    let (vertices, indices) = build_block_cracks(bx, by, bz, stage);
+                    let base = crack_vertices.len() as u32;
+                    crack_vertices.extend(vertices);
+                    crack_indices.extend(indices.into_iter().map(|i| i + base));
+                }
+                if !crack_indices.is_empty() {
+                    let crack_vb =
                         self.device
                             .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                                label: Some("Block Outline VB"),
-                                contents: bytemuck::cast_slice(&outline_vertices),
+                                label: Some("Dig Crack VB"),
+                                contents: bytemuck::cast_slice(&crack_vertices),
                                 usage: wgpu::BufferUsages::VERTEX,
                             });
-                    let outline_ib =
+                    let crack_ib =
                         self.device
                             .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                                label: Some("Block Outline IB"),
-                                contents: bytemuck::cast_slice(&outline_indices),
+                                label: Some("Dig Crack IB"),
+                                contents: bytemuck::cast_slice(&crack_indices),
                                 usage: wgpu::BufferUsages::INDEX,
                             });
-                    outline_pass.set_pipeline(&self.outline_pipeline);
+                    outline_pass.set_pipeline(&self.translucent_pipeline);
                     outline_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
                     outline_pass.set_bind_group(1, &self.terrain_gbuffer_bind_group, &[]);
                     outline_pass.set_bind_group(2, &self.terrain_shadow_output_bind_group, &[]);
                     outline_pass.set_bind_group(3, &self.shadow_mask_bind_group, &[]);
-                    outline_pass.set_vertex_buffer(0, outline_vb.slice(..));
-                    outline_pass.set_index_buffer(outline_ib.slice(..), wgpu::IndexFormat::Uint32);
-                    outline_pass.draw_indexed(0..outline_indices.len() as u32, 0, 0..1);
+                    outline_pass.set_vertex_buffer(0, crack_vb.slice(..));
+                    outline_pass.set_index_buffer(crack_ib.slice(..), wgpu::IndexFormat::Uint32);
+                    outline_pass.draw_indexed(0..crack_indices.len() as u32, 0, 0..1);
+                }
+            }
+
+            let mut outline_vertices = Vec::new();
+            let mut outline_indices = Vec::new();
+            let mut append = |(vertices, indices): (Vec<OutlineVertex>, Vec<u32>)| {
+                let base = outline_vertices.len() as u32;
+                outline_vertices.extend(vertices);
+                outline_indices.extend(indices.into_iter().map(|i| i + base));
+            };
+
+            if let Some(hit) = self.highlighted_block {
+                let (bx, by, bz) = hit.block;
+                let visible_faces = {
+                    let world = self.world.read();
+                    visible_outline_faces(&*world, bx, by, bz)
+                };
+                append(build_block_outline(bx, by, bz, visible_faces));
+
+                // Tint the face the ray entered through; it is the face a
+                // new block would be placed against.  Appended to the outline
+                // geometry so both go out in a single draw.
+                if self.show_face_highlight {
+                    append(build_face_highlight(
+                        bx,
+                        by,
+                        bz,
+                        hit.normal,
+                        FACE_HIGHLIGHT_COLOR,
+                    ));
                 }
             }
+
+            // Ghost cube where a right click would place the selected block.
+            if let Some((px, py, pz)) = self.placement_preview {
+                append(build_placement_preview(px, py, pz, PLACEMENT_PREVIEW_COLOR));
+            }
+
+            // Splash droplets, as camera-facing quads; debris cubes were
+            // drawn with the models in the opaque pass.
+            if !self.particles.is_empty() {
+                append(self.particles.build_quads(self.camera.look_direction()));
+            }
+
+            // Day-cycle debug overlay: the sun and moon path around the eye,
+            // at the distance the billboards are drawn at.
+            if self.show_celestial_path {
+                append(build_celestial_path(
+                    self.camera.eye_position().to_array(),
+                    CELESTIAL_PATH_RADIUS,
+                    |fraction| sun_direction(fraction * DAY_LENGTH).to_array(),
+                    CELESTIAL_PATH_COLOR,
+                    CELESTIAL_MARKER_COLOR,
+                ));
+            }
+
+            // Chunk-border debug overlay.  Bounded by `chunk_border_radius`
+            // so its cost does not grow with the render distance.
+            if self.show_chunk_borders {
+                let center = (
+                    (self.camera.position.x.floor() as i32).div_euclid(CHUNK_SIZE),
+                    (self.camera.position.z.floor() as i32).div_euclid(CHUNK_SIZE),
+                );
+                let world = self.world.read();
+                append(build_chunk_borders(
+                    center,
+                    self.chunk_border_radius,
+                    |cx, cz| world.chunks.contains_key(&(cx, cz)),
+                    CHUNK_BORDER_COLOR,
+                ));
+            }
+
+            if !outline_vertices.is_empty() && !outline_indices.is_empty() {
+                let outline_vb =
+                    self.device
+                        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                            label: Some("Block Outline VB"),
+                            contents: bytemuck::cast_slice(&outline_vertices),
+                            usage: wgpu::BufferUsages::VERTEX,
+                        });
+                let outline_ib =
+                    self.device
+                        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                            label: Some("Block Outline IB"),
+                            contents: bytemuck::cast_slice(&outline_indices),
+                            usage: wgpu::BufferUsages::INDEX,
+                        });
+                outline_pass.set_pipeline(&self.outline_pipeline);
+                outline_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+                outline_pass.set_bind_group(1, &self.terrain_gbuffer_bind_group, &[]);
+                outline_pass.set_bind_group(2, &self.terrain_shadow_output_bind_group, &[]);
+                outline_pass.set_bind_group(3, &self.shadow_mask_bind_group, &[]);
+                outline_pass.set_vertex_buffer(0, outline_vb.slice(..));
+                outline_pass.set_index_buffer(outline_ib.slice(..), wgpu::IndexFormat::Uint32);
+                outline_pass.draw_indexed(0..outline_indices.len() as u32, 0, 0..1);
+            }
+        }
+
+        // ── Bloom passes ──────────────────────────────────────────────────── //
+        // Bright-pass, downsample and blur of `scene_color_view`; the result
+        // is added back by the composite shader.
+        if self.bloom_enabled {
+            self.encode_bloom(&mut encoder);
         }
 
         // ── Composite pass (post-processing blit) ─────────────────────────── //
         // Reads from `scene_color_view` (the fully composited opaque + water
         // scene) and writes the post-processed result directly to the
-        // swap-chain surface.  The composite shader handles underwater fog
-        // color grading, vignette, and similar full-screen effects.
-        if self.game_state != GameState::Menu {
+        // swap-chain surface.  The composite shader adds bloom and handles
+        // underwater color grading, vignette, and similar full-screen
+        // effects.  Also runs behind the menu, since the HDR scene texture
+        // is the only place the world is rendered to.
+        {
             let mut composite_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Composite Pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
@@ -879,6 +1158,7 @@ impl State {
                     },
                 })],
                 depth_stencil_attachment: None, // no depth test for a full-screen blit
+                timestamp_writes: self.render_timestamps(GpuPhase::Composite, true, true),
                 ..Default::default()
             });
 
@@ -887,12 +1167,19 @@ impl State {
             composite_pass.draw(0..3, 0..1); // full-screen triangle
         }
 
+        // ── Debug view pass ───────────────────────────────────────────────── //
+        // Replaces the composited frame with an intermediate render target
+        // when one is picked with F7 in the F3 overlay.
+        self.encode_debug_view(&mut encoder, &view);
+
         // ── UI pass ───────────────────────────────────────────────────────── //
-        // Draws the crosshair, coordinate debug overlay, and hotbar using the
-        // same `crosshair_pipeline` (alpha-blended, no depth test).  All
+        // Draws the crosshair, coordinate debug overlay, hotbar and minimap
+        // using the same `crosshair_pipeline` (alpha-blended, no depth test;
+        // the minimap's map quad uses its texture-sampling variant).  All
         // elements are drawn directly onto the swap-chain surface on top of
-        // the composited scene.
-        {
+        // the composited scene.  Skipped outside active play so none of them
+        // show through the menu or the connecting screen.
+        if self.game_state == GameState::Playing {
             let mut ui_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("UI Pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
@@ -905,15 +1192,65 @@ impl State {
                     },
                 })],
                 depth_stencil_attachment: None,
+                timestamp_writes: self.render_timestamps(GpuPhase::Ui, true, false),
                 ..Default::default()
             });
 
-            // --- Crosshair ---
             ui_pass.set_pipeline(&self.crosshair_pipeline);
             ui_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
             ui_pass.set_bind_group(1, &self.terrain_gbuffer_bind_group, &[]);
             ui_pass.set_bind_group(2, &self.terrain_shadow_output_bind_group, &[]);
             ui_pass.set_bind_group(3, &self.shadow_mask_bind_group, &[]);
+
+            // --- Damage vignette ---
+            // Red flash after a hit and pulse at low health.  Drawn first so
+            // the crosshair and hotbar stay readable on top of it.
+            let (edges, centre) = damage_vignette(
+                self.hurt_timer,
+                self.hurt_strength,
+                self.health,
+                self.game_start_time.elapsed().as_secs_f32(),
+            );
+            if edges > 0.0 {
+                let vertices = crate::ui::ui::build_damage_vignette(edges, centre);
+                match &self.damage_vignette_vertex_buffer {
+                    Some(vb) => self
+                        .queue
+                        .write_buffer(vb, 0, bytemuck::cast_slice(&vertices)),
+                    None => {
+                        self.damage_vignette_vertex_buffer = Some(self.device.create_buffer_init(
+                            &wgpu::util::BufferInitDescriptor {
+                                label: Some("Damage Vignette VB"),
+                                contents: bytemuck::cast_slice(&vertices),
+                                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                            },
+                        ));
+                        self.damage_vignette_index_buffer = Some(self.device.create_buffer_init(
+                            &wgpu::util::BufferInitDescriptor {
+                                label: Some("Damage Vignette IB"),
+                                contents: bytemuck::cast_slice(
+                                    &crate::ui::ui::DAMAGE_VIGNETTE_INDICES,
+                                ),
+                                usage: wgpu::BufferUsages::INDEX,
+                            },
+                        ));
+                    }
+                }
+                if let (Some(vb), Some(ib)) = (
+                    &self.damage_vignette_vertex_buffer,
+                    &self.damage_vignette_index_buffer,
+                ) {
+                    ui_pass.set_vertex_buffer(0, vb.slice(..));
+                    ui_pass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
+                    ui_pass.draw_indexed(
+                        0..crate::ui::ui::DAMAGE_VIGNETTE_INDICES.len() as u32,
+                        0,
+                        0..1,
+                    );
+                }
+            }
+
+            // --- Crosshair ---
             ui_pass.set_vertex_buffer(0, self.crosshair_vertex_buffer.slice(..));
             ui_pass.set_index_buffer(
                 self.crosshair_index_buffer.slice(..),
@@ -921,40 +1258,55 @@ impl State {
             );
             ui_pass.draw_indexed(0..self.num_crosshair_indices, 0, 0..1);
 
-            // --- Coordinate debug overlay ---
-            // Only drawn when `coords_vertex_buffer` has been populated (i.e.,
-            // when the player has moved to a new chunk and the overlay was
-            // rebuilt by `update`).
-            if let (Some(vb), Some(ib)) = (&self.coords_vertex_buffer, &self.coords_index_buffer) {
-                if self.coords_num_indices > 0 {
+            // --- Hotbar ---
+            // Rebuilt lazily when `hotbar_dirty` is true (e.g., after a slot
+            // change or when a survival block count changes).
+            self.refresh_hotbar_counts();
+            if self.hotbar_dirty || self.hotbar_vertex_buffer.is_none() {
+                let aspect = self.config.width as f32 / self.config.height as f32;
+                let (vb, ib, count) = crate::ui::ui::build_hotbar(
+                    &self.device,
+                    &self.hotbar,
+                    self.hotbar_slot,
+                    aspect,
+                    self.hotbar_counts.as_ref(),
+                    self.hotbar_flash,
+                );
+                self.hotbar_vertex_buffer = Some(vb);
+                self.hotbar_index_buffer = Some(ib);
+                self.hotbar_num_indices = count;
+                self.hotbar_dirty = false;
+            }
+            if let (Some(vb), Some(ib)) = (&self.hotbar_vertex_buffer, &self.hotbar_index_buffer) {
+                if self.hotbar_num_indices > 0 {
                     ui_pass.set_vertex_buffer(0, vb.slice(..));
                     ui_pass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
-                    ui_pass.draw_indexed(0..self.coords_num_indices, 0, 0..1);
+                    ui_pass.draw_indexed(0..self.hotbar_num_indices, 0, 0..1);
                 }
             }
 
-            // --- Hotbar ---
-            // Only drawn in-game (not on the menu).  Rebuilt lazily when
-            // `hotbar_dirty` is true (e.g., after a slot change).
-            if self.game_state != GameState::Menu {
-                if self.hotbar_dirty || self.hotbar_vertex_buffer.is_none() {
-                    let aspect = self.config.width as f32 / self.config.height as f32;
-                    let (vb, ib, count) =
-                        crate::ui::ui::build_hotbar(&self.device, self.hotbar_slot, aspect);
-                    self.hotbar_vertex_buffer = Some(vb);
-                    self.hotbar_index_buffer = Some(ib);
-                    self.hotbar_num_indices = count;
-                    self.hotbar_dirty = false;
-                }
-                if let (Some(vb), Some(ib)) =
-                    (&self.hotbar_vertex_buffer, &self.hotbar_index_buffer)
-                {
-                    if self.hotbar_num_indices > 0 {
-                        ui_pass.set_vertex_buffer(0, vb.slice(..));
-                        ui_pass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
-                        ui_pass.draw_indexed(0..self.hotbar_num_indices, 0, 0..1);
-                    }
-                }
+            // --- Minimap ---
+            // The frame and the facing marker go through the UI pipeline,
+            // the map in between through its texture-sampling variant.
+            if self.minimap.visible {
+                self.minimap.write_quads(
+                    &self.queue,
+                    self.config.width as f32,
+                    self.config.height as f32,
+                    self.camera.yaw,
+                );
+                ui_pass.set_vertex_buffer(0, self.minimap.vertex_buffer.slice(..));
+                ui_pass.set_index_buffer(
+                    self.minimap.index_buffer.slice(..),
+                    wgpu::IndexFormat::Uint32,
+                );
+                ui_pass.draw_indexed(minimap::FRAME_INDICES, 0, 0..1);
+                ui_pass.set_pipeline(&self.minimap_pipeline);
+                ui_pass.set_bind_group(1, &self.minimap.bind_group, &[]);
+                ui_pass.draw_indexed(minimap::MAP_INDICES, 0, 0..1);
+                ui_pass.set_pipeline(&self.crosshair_pipeline);
+                ui_pass.set_bind_group(1, &self.terrain_gbuffer_bind_group, &[]);
+                ui_pass.draw_indexed(minimap::MARKER_INDICES, 0, 0..1);
             }
         }
 
@@ -966,10 +1318,14 @@ impl State {
             let progress = (self.digging.progress / self.digging.break_time).min(1.0);
 
             // Bar dimensions in NDC space (centred horizontally, slightly
-            // below the crosshair at y = −0.05).
-            let bar_width = 0.15;
+            // below the crosshair at y = −0.05).  Horizontal sizes are
+            // relative to the screen height and divided by the aspect ratio,
+            // like the crosshair, so the bar keeps its shape on any screen.
+            let aspect = self.config.width as f32 / self.config.height as f32;
+            let bar_width = 0.27 / aspect;
             let bar_height = 0.015;
             let bar_y = -0.05;
+            let inset_x = 0.009 / aspect;
 
             let bg_color = [0.2, 0.2, 0.2];
             // Color shifts from red (0%) → yellow (50%) → green (100%).
@@ -998,14 +1354,14 @@ impl State {
                 });
             }
 
-            // Foreground quad (colored fill, inset by 0.005/0.003 on each
+            // Foreground quad (colored fill, inset by `inset_x`/0.003 on each
             // side so the gray border remains visible all around).
             let prog_width = bar_width * 2.0 * progress - bar_width;
             let fg_corners = [
-                (-bar_width + 0.005, bar_y - bar_height + 0.003),
-                (prog_width - 0.005, bar_y - bar_height + 0.003),
-                (prog_width - 0.005, bar_y + bar_height - 0.003),
-                (-bar_width + 0.005, bar_y + bar_height - 0.003),
+                (-bar_width + inset_x, bar_y - bar_height + 0.003),
+                (prog_width - inset_x, bar_y - bar_height + 0.003),
+                (prog_width - inset_x, bar_y + bar_height - 0.003),
+                (-bar_width + inset_x, bar_y + bar_height - 0.003),
             ];
             for (i, (x, y)) in fg_corners.into_iter().enumerate() {
                 vertices.push(Vertex {
@@ -1084,34 +1440,53 @@ impl State {
             progress_pass.draw_indexed(0..12, 0, 0..1);
         }
 
-        // ── Menu overlay or remote player labels ──────────────────────────── //
+        // ── Menu overlay ──────────────────────────────────────────────────── //
         if self.game_state == GameState::Menu {
             self.render_menu(&mut encoder, &view);
-        } else {
-            // Projects each remote player's world position into screen space
-            // so the text pass can draw their name above their head.
-            self.render_remote_players(
-                &view_proj,
-                self.config.width as f32,
-                self.config.height as f32,
-            );
         }
 
+        drop(scope);
+
         // ── Text pass (glyphon) ───────────────────────────────────────────── //
         // All on-screen text is batched into a single `TextRenderer::prepare`
         // call and rendered in one pass.  The individual `glyphon::Buffer`
         // objects are updated lazily (only when the underlying text changes)
         // to avoid redundant re-shaping work.
         {
+            let _scope = ProfileScope::new("text");
+
             // ---- FPS counter (always visible) ----
-            let fps_text = format!(
-                "FPS: {:.0}\nFrame: {:.2} ms\nCPU update: {:.2} ms\nChunks: {}\nSubchunks: {}",
+            // Next to the subchunks with geometry in range, the number the
+            // GPU culling pass let through (a frame or two late) shows what
+            // frustum and Hi-Z culling remove.  The world seed is listed too
+            // so players can share it.  With F3 the previous frame's CPU time
+            // per system, the GPU time per render phase and the loader queue
+            // depths follow.
+            let mut fps_text = format!(
+                "FPS: {:.0}\nFrame: {:.2} ms\nCPU update: {:.2} ms\nChunks: {}\nSubchunks: {} ({} after GPU culling)\nSeed: {}",
                 self.current_fps,
                 self.frame_time_ms,
                 self.cpu_update_ms,
                 self.chunks_rendered,
-                self.subchunks_rendered
+                self.subchunks_rendered,
+                self.indirect_manager.last_visible_count(),
+                self.world.read().seed,
             );
+            if self.show_debug_overlay {
+                let gpu_summary = match &self.gpu_timer {
+                    Some(timer) => timer.last_frame_summary(),
+                    None => "unavailable (no timestamp queries)\n".to_string(),
+                };
+                fps_text += &format!(
+                    "\n\nCPU\n{}\nGPU\n{}\nChunk queue: {}\nMesh queue: {}\nUpload queue: {}\nView: {}",
+                    profiler::last_frame_summary(),
+                    gpu_summary,
+                    self.chunk_loader.pending_count(),
+                    self.mesh_loader.pending_count(),
+                    self.mesh_uploads.len(),
+                    self.debug_view.label(),
+                );
+            }
             self.fps_buffer.set_text(
                 &mut self.font_system,
                 &fps_text,
@@ -1127,7 +1502,7 @@ impl State {
 
             // ---- Hotbar slot label (in-game only, updated on slot change) ----
             if self.game_state != GameState::Menu && self.last_hotbar_slot != self.hotbar_slot {
-                let block = crate::ui::ui::HOTBAR_SLOTS[self.hotbar_slot];
+                let block = self.hotbar[self.hotbar_slot];
                 let label = block.display_name();
                 self.hotbar_label_buffer.set_text(
                     &mut self.font_system,
@@ -1152,6 +1527,27 @@ impl State {
                 self.last_hotbar_slot = self.hotbar_slot;
             }
 
+            // ---- Chat log / input line (in-game only) ----
+            let chat_text = if self.game_state != GameState::Menu {
+                self.chat.display_text()
+            } else {
+                String::new()
+            };
+            if !chat_text.is_empty() {
+                self.chat_buffer.set_text(
+                    &mut self.font_system,
+                    &chat_text,
+                    &Attrs::new().family(Family::SansSerif),
+                    Shaping::Advanced,
+                    None,
+                );
+                self.chat_buffer.set_size(
+                    &mut self.font_system,
+                    Some(self.config.width as f32 * 0.5),
+                    Some(self.config.height as f32),
+                );
+            }
+
             // ---- Remote player name labels / menu text ----
             // In menu mode: update all menu label buffers via `prepare_menu_text`.
             // In game mode: project remote player positions and grow the label
@@ -1165,6 +1561,7 @@ impl State {
                     &view_proj,
                     self.config.width as f32,
                     self.config.height as f32,
+                    self.player_colors,
                 );
                 // Grow the buffer pool lazily so we always have at least as
                 // many buffers as there are visible remote players.
@@ -1176,12 +1573,13 @@ impl State {
                 }
                 for (i, label) in labels.iter().enumerate() {
                     let buffer = &mut self.player_label_buffers[i];
+                    let [r, g, b] = label.color.map(|c| (c * 255.0).round() as u8);
                     buffer.set_text(
                         &mut self.font_system,
                         &label.username,
                         &Attrs::new()
                             .family(Family::SansSerif)
-                            .color(Color::rgb(76, 255, 76)), // bright green name tags
+                            .color(Color::rgb(r, g, b)),
                         Shaping::Advanced,
                         None,
                     );
@@ -1232,10 +1630,17 @@ impl State {
                 let username_label_y = layout.username_label.y - 6.0;
                 let server_value_y = layout.server_field.y + 12.0;
                 let username_value_y = layout.username_field.y + 12.0;
-                let tips_y = layout.quick_card.y + 86.0;
+                let tips_y = layout.quick_card.y + 20.0;
                 let button_text_y = layout.connect_button.y + 15.0;
                 let single_text_y = layout.singleplayer_button.y + 15.0;
+                let filter_text_y = layout.filter_button.y + 9.0;
+                let quality_text_y = layout.quality_button.y + 9.0;
+                let vsync_text_y = layout.vsync_button.y + 9.0;
+                let brightness_text_y = layout.brightness_slider.y + 9.0;
                 let status_y = layout.status_pill.y + 8.0;
+                let controls_text_y = layout.controls_button.y + 5.0;
+                let preset_text_y = layout.preset_button.y + 9.0;
+                let screen = self.menu_state.screen;
 
                 text_areas.push(TextArea {
                     buffer: &self.menu_title_buffer,
@@ -1266,88 +1671,159 @@ impl State {
                     custom_glyphs: &[],
                 });
 
+                if screen != MenuScreen::Controls {
+                    text_areas.push(TextArea {
+                        buffer: &self.menu_server_label_buffer,
+                        left: layout.server_label.x + 2.0,
+                        top: server_label_y,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(140, 153, 167),
+                        custom_glyphs: &[],
+                    });
+                    text_areas.push(TextArea {
+                        buffer: &self.menu_server_value_buffer,
+                        left: layout.server_field.x + 16.0,
+                        top: server_value_y,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(248, 250, 252),
+                        custom_glyphs: &[],
+                    });
+
+                    text_areas.push(TextArea {
+                        buffer: &self.menu_username_label_buffer,
+                        left: layout.username_label.x + 2.0,
+                        top: username_label_y,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(140, 153, 167),
+                        custom_glyphs: &[],
+                    });
+                    text_areas.push(TextArea {
+                        buffer: &self.menu_username_value_buffer,
+                        left: layout.username_field.x + 16.0,
+                        top: username_value_y,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(248, 250, 252),
+                        custom_glyphs: &[],
+                    });
+
+                    text_areas.push(TextArea {
+                        buffer: &self.menu_tips_buffer,
+                        left: layout.quick_card.x + 20.0,
+                        top: tips_y,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(171, 189, 202),
+                        custom_glyphs: &[],
+                    });
+                } else {
+                    // Key binding columns: action names at the left of each
+                    // row, key names from 62 % of the row width.  The +1 px
+                    // matches the gap above each row fill.
+                    for column in 0..2 {
+                        let row = layout.binding_row(column * BINDING_ROWS_PER_COLUMN);
+                        text_areas.push(TextArea {
+                            buffer: &self.menu_binding_label_buffers[column],
+                            left: row.x + 10.0,
+                            top: row.y + 1.0,
+                            scale: 1.0,
+                            bounds: TextBounds {
+                                left: 0,
+                                top: 0,
+                                right: self.config.width as i32,
+                                bottom: self.config.height as i32,
+                            },
+                            default_color: Color::rgb(214, 224, 233),
+                            custom_glyphs: &[],
+                        });
+                        text_areas.push(TextArea {
+                            buffer: &self.menu_binding_key_buffers[column],
+                            left: row.x + row.w * 0.62,
+                            top: row.y + 1.0,
+                            scale: 1.0,
+                            bounds: TextBounds {
+                                left: 0,
+                                top: 0,
+                                right: (row.x + row.w) as i32,
+                                bottom: self.config.height as i32,
+                            },
+                            default_color: Color::rgb(242, 227, 187),
+                            custom_glyphs: &[],
+                        });
+                    }
+                }
+
+                if screen == MenuScreen::Singleplayer {
+                    text_areas.push(TextArea {
+                        buffer: &self.menu_preset_button_buffer,
+                        left: layout.preset_button.x + 14.0,
+                        top: preset_text_y,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(214, 236, 234),
+                        custom_glyphs: &[],
+                    });
+                }
+
+                if screen == MenuScreen::Main {
+                    text_areas.push(TextArea {
+                        buffer: &self.menu_controls_button_buffer,
+                        left: layout.controls_button.x + 14.0,
+                        top: controls_text_y,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(214, 236, 234),
+                        custom_glyphs: &[],
+                    });
+                }
+
+                // Buttons are centred by estimating the text width
+                // (chars × ~10.5 px) and offsetting accordingly.
+                let connect_estimate = screen.primary_label().len() as f32 * 10.5;
+                let single_estimate = screen.secondary_label().len() as f32 * 10.5;
                 text_areas.push(TextArea {
-                    buffer: &self.menu_server_label_buffer,
-                    left: layout.server_label.x + 2.0,
-                    top: server_label_y,
-                    scale: 1.0,
-                    bounds: TextBounds {
-                        left: 0,
-                        top: 0,
-                        right: self.config.width as i32,
-                        bottom: self.config.height as i32,
-                    },
-                    default_color: Color::rgb(140, 153, 167),
-                    custom_glyphs: &[],
-                });
-                text_areas.push(TextArea {
-                    buffer: &self.menu_server_value_buffer,
-                    left: layout.server_field.x + 16.0,
-                    top: server_value_y,
-                    scale: 1.0,
-                    bounds: TextBounds {
-                        left: 0,
-                        top: 0,
-                        right: self.config.width as i32,
-                        bottom: self.config.height as i32,
-                    },
-                    default_color: Color::rgb(248, 250, 252),
-                    custom_glyphs: &[],
-                });
-
-                text_areas.push(TextArea {
-                    buffer: &self.menu_username_label_buffer,
-                    left: layout.username_label.x + 2.0,
-                    top: username_label_y,
-                    scale: 1.0,
-                    bounds: TextBounds {
-                        left: 0,
-                        top: 0,
-                        right: self.config.width as i32,
-                        bottom: self.config.height as i32,
-                    },
-                    default_color: Color::rgb(140, 153, 167),
-                    custom_glyphs: &[],
-                });
-                text_areas.push(TextArea {
-                    buffer: &self.menu_username_value_buffer,
-                    left: layout.username_field.x + 16.0,
-                    top: username_value_y,
-                    scale: 1.0,
-                    bounds: TextBounds {
-                        left: 0,
-                        top: 0,
-                        right: self.config.width as i32,
-                        bottom: self.config.height as i32,
-                    },
-                    default_color: Color::rgb(248, 250, 252),
-                    custom_glyphs: &[],
-                });
-
-                text_areas.push(TextArea {
-                    buffer: &self.menu_tips_buffer,
-                    left: layout.quick_card.x + 20.0,
-                    top: tips_y,
-                    scale: 1.0,
-                    bounds: TextBounds {
-                        left: 0,
-                        top: 0,
-                        right: self.config.width as i32,
-                        bottom: self.config.height as i32,
-                    },
-                    default_color: Color::rgb(171, 189, 202),
-                    custom_glyphs: &[],
-                });
-
-                // Buttons are centred by estimating the text width
-                // (chars × ~10.5 px) and offsetting accordingly.
-                let connect_estimate = 7.0 * 10.5; // "CONNECT" ≈ 7 chars
-                let single_estimate = 12.0 * 10.5; // "SINGLEPLAYER" ≈ 12 chars
-                text_areas.push(TextArea {
-                    buffer: &self.menu_connect_button_buffer,
-                    left: layout.connect_button.x
-                        + (layout.connect_button.w - connect_estimate) * 0.5,
-                    top: button_text_y,
+                    buffer: &self.menu_connect_button_buffer,
+                    left: layout.connect_button.x
+                        + (layout.connect_button.w - connect_estimate) * 0.5,
+                    top: button_text_y,
                     scale: 1.0,
                     bounds: TextBounds {
                         left: 0,
@@ -1373,6 +1849,64 @@ impl State {
                     default_color: Color::rgb(220, 228, 236),
                     custom_glyphs: &[],
                 });
+                if screen != MenuScreen::Controls {
+                    text_areas.push(TextArea {
+                        buffer: &self.menu_filter_button_buffer,
+                        left: layout.filter_button.x + 14.0,
+                        top: filter_text_y,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(214, 236, 234),
+                        custom_glyphs: &[],
+                    });
+                    text_areas.push(TextArea {
+                        buffer: &self.menu_quality_button_buffer,
+                        left: layout.quality_button.x + 14.0,
+                        top: quality_text_y,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(214, 236, 234),
+                        custom_glyphs: &[],
+                    });
+                    text_areas.push(TextArea {
+                        buffer: &self.menu_vsync_button_buffer,
+                        left: layout.vsync_button.x + 14.0,
+                        top: vsync_text_y,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(214, 236, 234),
+                        custom_glyphs: &[],
+                    });
+                    text_areas.push(TextArea {
+                        buffer: &self.menu_brightness_buffer,
+                        left: layout.brightness_slider.x + 14.0,
+                        top: brightness_text_y,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(214, 236, 234),
+                        custom_glyphs: &[],
+                    });
+                }
 
                 // Status pill color reflects the current state:
                 //   red   → connection error
@@ -1401,26 +1935,160 @@ impl State {
             } else {
                 // ---- In-game HUD text ----
 
-                // Hotbar slot name: centred above the hotbar, clamped to the
-                // screen width.
-                let label_width = self.hotbar_label_width.min(self.config.width as f32);
-                let label_left = (self.config.width as f32 - label_width) * 0.5;
-                // 170 px above the bottom edge keeps the label above the hotbar.
-                let label_top = (self.config.height as f32 - 170.0).max(0.0);
-                text_areas.push(TextArea {
-                    buffer: &self.hotbar_label_buffer,
-                    left: label_left,
-                    top: label_top,
-                    scale: 1.0,
-                    bounds: TextBounds {
-                        left: 0,
-                        top: 0,
-                        right: self.config.width as i32,
-                        bottom: self.config.height as i32,
-                    },
-                    default_color: Color::rgb(255, 255, 255),
-                    custom_glyphs: &[],
-                });
+                if self.game_state == GameState::Playing {
+                    // Hotbar slot name: centred above the hotbar, clamped to the
+                    // screen width.  Hidden with the hotbar itself while
+                    // connecting.
+                    let label_width = self.hotbar_label_width.min(self.config.width as f32);
+                    let label_left = (self.config.width as f32 - label_width) * 0.5;
+                    // 170 px above the bottom edge keeps the label above the hotbar.
+                    let label_top = (self.config.height as f32 - 170.0).max(0.0);
+                    text_areas.push(TextArea {
+                        buffer: &self.hotbar_label_buffer,
+                        left: label_left,
+                        top: label_top,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(255, 255, 255),
+                        custom_glyphs: &[],
+                    });
+
+                    // Survival block counts in the bottom-right corner of
+                    // each hotbar slot; the buffers are empty otherwise.
+                    let (width, height) = (self.config.width as f32, self.config.height as f32);
+                    for (i, buffer) in self.hotbar_count_buffers.iter().enumerate() {
+                        let [_, y0, x1, _] = crate::ui::ui::hotbar_slot_rect(i, width / height);
+                        let text_width = buffer
+                            .layout_runs()
+                            .map(|run| run.line_w)
+                            .fold(0.0, f32::max);
+                        if text_width == 0.0 {
+                            continue;
+                        }
+                        // NDC to pixels, 4 px in from the slot's corner.
+                        let right = (x1 + 1.0) * 0.5 * width - 4.0;
+                        let bottom = (1.0 - y0) * 0.5 * height - 2.0;
+                        text_areas.push(TextArea {
+                            buffer,
+                            left: right - text_width,
+                            top: bottom - 20.0,
+                            scale: 1.0,
+                            bounds: TextBounds {
+                                left: 0,
+                                top: 0,
+                                right: self.config.width as i32,
+                                bottom: self.config.height as i32,
+                            },
+                            default_color: Color::rgb(255, 255, 255),
+                            custom_glyphs: &[],
+                        });
+                    }
+                }
+
+                // Coordinate readout: top-right, right-aligned above the
+                // minimap.
+                if self.game_state == GameState::Playing && self.show_coords {
+                    text_areas.push(TextArea {
+                        buffer: &self.coords_buffer,
+                        left: (self.config.width as f32 - self.coords_width - 16.0).max(0.0),
+                        top: 12.0,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(255, 255, 255),
+                        custom_glyphs: &[],
+                    });
+                }
+
+                // Player list: centred near the top while its key is held.
+                if self.game_state == GameState::Playing
+                    && self.show_player_list
+                    && self.network_tx.is_some()
+                {
+                    let list_width = self.player_list_width.min(self.config.width as f32);
+                    text_areas.push(TextArea {
+                        buffer: &self.player_list_buffer,
+                        left: (self.config.width as f32 - list_width) * 0.5,
+                        top: 60.0,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(255, 255, 255),
+                        custom_glyphs: &[],
+                    });
+                }
+
+                // Chunk loading readout: centred near the top while many
+                // chunks around the player are missing.
+                if self.chunk_progress.visible {
+                    let progress_width = self.chunk_progress_width.min(self.config.width as f32);
+                    text_areas.push(TextArea {
+                        buffer: &self.chunk_progress_buffer,
+                        left: (self.config.width as f32 - progress_width) * 0.5,
+                        top: 24.0,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(255, 255, 255),
+                        custom_glyphs: &[],
+                    });
+                }
+
+                // "Saving..." indicator: bottom-right corner.
+                if self.saving_indicator_visible() {
+                    text_areas.push(TextArea {
+                        buffer: &self.saving_buffer,
+                        left: (self.config.width as f32 - self.saving_width - 16.0).max(0.0),
+                        top: (self.config.height as f32 - 36.0).max(0.0),
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(255, 255, 255),
+                        custom_glyphs: &[],
+                    });
+                }
+
+                // Chat log: bottom-left, growing upwards from just above
+                // the hotbar.  26 px is the chat buffer's line height.
+                if !chat_text.is_empty() {
+                    let chat_lines = chat_text.lines().count() as f32;
+                    let chat_top = (self.config.height as f32 - 200.0 - chat_lines * 26.0).max(0.0);
+                    text_areas.push(TextArea {
+                        buffer: &self.chat_buffer,
+                        left: 10.0,
+                        top: chat_top,
+                        scale: 1.0,
+                        bounds: TextBounds {
+                            left: 0,
+                            top: 0,
+                            right: self.config.width as i32,
+                            bottom: self.config.height as i32,
+                        },
+                        default_color: Color::rgb(235, 235, 235),
+                        custom_glyphs: &[],
+                    });
+                }
 
                 // Remote player name tags (one per visible player).
                 for (i, label) in labels.iter().enumerate() {
@@ -1469,6 +2137,7 @@ impl State {
                         store: wgpu::StoreOp::Store,
                     },
                 })],
+                timestamp_writes: self.render_timestamps(GpuPhase::Ui, false, true),
                 ..Default::default()
             });
             self.text_renderer
@@ -1480,11 +2149,131 @@ impl State {
         }
 
         // ── Submit & present ──────────────────────────────────────────────── //
+        let _scope = ProfileScope::new("submit & present");
+        if let Some(timer) = &mut self.gpu_timer {
+            timer.resolve(&mut encoder);
+        }
+        self.queue.submit(std::iter::once(encoder.finish()));
+        if let Some(timer) = &mut self.gpu_timer {
+            timer.after_submit();
+        }
+        self.indirect_manager.map_visible_count();
+        output.present();
+        Ok(())
+    }
+
+    /// Draws the loading screen shown while the texture atlas loads: a plain
+    /// background with a centred "Loading..." label.
+    ///
+    /// # Errors
+    /// Returns `Err(wgpu::SurfaceError::Lost)` if the text cannot be prepared
+    /// or rendered, like [`Self::render`].
+    fn render_loading_screen(
+        &mut self,
+        output: wgpu::SurfaceTexture,
+        view: &wgpu::TextureView,
+    ) -> Result<(), wgpu::SurfaceError> {
+        let text = "Loading...";
+        self.loading_buffer.set_text(
+            &mut self.font_system,
+            text,
+            &Attrs::new().family(Family::SansSerif),
+            Shaping::Advanced,
+            None,
+        );
+        self.loading_buffer.set_size(
+            &mut self.font_system,
+            Some(self.config.width as f32),
+            Some(self.config.height as f32),
+        );
+        // Same glyph advance estimate as the hotbar label.
+        let text_width = text.chars().count() as f32 * 32.0 * 0.6;
+        let text_area = TextArea {
+            buffer: &self.loading_buffer,
+            left: (self.config.width as f32 - text_width) * 0.5,
+            top: (self.config.height as f32 - 40.0) * 0.5,
+            scale: 1.0,
+            bounds: TextBounds {
+                left: 0,
+                top: 0,
+                right: self.config.width as i32,
+                bottom: self.config.height as i32,
+            },
+            default_color: Color::rgb(255, 255, 255),
+            custom_glyphs: &[],
+        };
+        self.text_renderer
+            .prepare(
+                &self.device,
+                &self.queue,
+                &mut self.font_system,
+                &mut self.text_atlas,
+                &self.viewport,
+                [text_area],
+                &mut self.swash_cache,
+            )
+            .map_err(|e| {
+                log(LogLevel::Error, &format!("Failed to prepare text: {:?}", e));
+                wgpu::SurfaceError::Lost
+            })?;
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Loading Screen Encoder"),
+            });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Loading Screen Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    depth_slice: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.02,
+                            g: 0.03,
+                            b: 0.05,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                ..Default::default()
+            });
+            self.text_renderer
+                .render(&self.text_atlas, &self.viewport, &mut pass)
+                .map_err(|e| {
+                    log(LogLevel::Error, &format!("Failed to render text: {:?}", e));
+                    wgpu::SurfaceError::Lost
+                })?;
+        }
         self.queue.submit(std::iter::once(encoder.finish()));
         output.present();
         Ok(())
     }
 
+    /// Timestamp writes for a render pass that begins and/or ends `phase`,
+    /// or `None` when GPU timing is unavailable.
+    pub(super) fn render_timestamps(
+        &self,
+        phase: GpuPhase,
+        begin: bool,
+        end: bool,
+    ) -> Option<wgpu::RenderPassTimestampWrites<'_>> {
+        self.gpu_timer.as_ref()?.render_writes(phase, begin, end)
+    }
+
+    /// Compute-pass counterpart of [`Self::render_timestamps`].
+    fn compute_timestamps(
+        &self,
+        phase: GpuPhase,
+        begin: bool,
+        end: bool,
+    ) -> Option<wgpu::ComputePassTimestampWrites<'_>> {
+        self.gpu_timer.as_ref()?.compute_writes(phase, begin, end)
+    }
+
     /// Updates all `glyphon::Buffer` objects that display menu text.
     ///
     /// This method is called once per frame while in `GameState::Menu`.  It
@@ -1498,35 +2287,75 @@ impl State {
     /// # Active-field label decoration
     /// When a text field is selected, its label gains a `"  •  active"` suffix
     /// so the player has a clear visual indication of where keyboard input goes.
+    ///
+    /// # Singleplayer page
+    /// The server address buffers show the seed field instead and the
+    /// username buffers the sea level field.
+    ///
+    /// # Controls page
+    /// The binding buffers hold one line per action, in the order of
+    /// [`Action::ALL`], so each line sits in its [`MenuLayout::binding_row`].
     pub fn prepare_menu_text(&mut self) {
         let selected = self.menu_state.selected_field;
+        let screen = self.menu_state.screen;
 
         let title = "Minerust";
         let subtitle = "Voxel sandbox with multiplayer and custom UI";
 
         // Append an activity indicator to the label of the focused field.
-        let server_label = if selected == MenuField::ServerAddress {
-            "SERVER ADDRESS  •  active"
-        } else {
-            "SERVER ADDRESS"
+        let server_label = match (screen, selected) {
+            (MenuScreen::Main, MenuField::ServerAddress) => "SERVER ADDRESS  •  active",
+            (MenuScreen::Main, _) => "SERVER ADDRESS",
+            (MenuScreen::Singleplayer, MenuField::Seed) => "WORLD SEED  •  active",
+            (MenuScreen::Singleplayer, _) => "WORLD SEED",
+            (MenuScreen::Controls, _) => "",
         };
-        let username_label = if selected == MenuField::Username {
-            "USERNAME  •  active"
-        } else {
-            "USERNAME"
+        let username_label = match (screen, selected) {
+            (MenuScreen::Main, MenuField::Username) => "USERNAME  •  active",
+            (MenuScreen::Main, _) => "USERNAME",
+            (MenuScreen::Singleplayer, MenuField::SeaLevel) => "SEA LEVEL  •  active",
+            (MenuScreen::Singleplayer, _) => "SEA LEVEL",
+            (MenuScreen::Controls, _) => "",
         };
 
-        let server_value = self.menu_state.server_address.as_str();
-        let username_value = self.menu_state.username.as_str();
-        let tips = "TAB switch field\nENTER connect\nESC singleplayer\nF11 fullscreen";
-        let connect_button = "CONNECT";
-        let singleplayer_button = "SINGLEPLAYER";
+        let (server_value, username_value, tips) = match screen {
+            MenuScreen::Main => (
+                self.menu_state.server_address.as_str(),
+                self.menu_state.username.as_str(),
+                "TAB switch field\nENTER connect\nESC singleplayer\nF11 fullscreen",
+            ),
+            MenuScreen::Singleplayer => (
+                self.menu_state.seed.as_str(),
+                self.menu_state.sea_level.as_str(),
+                "Empty seed = random\nEmpty sea level = default\nENTER create world\nESC back",
+            ),
+            MenuScreen::Controls => ("", "", ""),
+        };
+        let connect_button = screen.primary_label();
+        let singleplayer_button = screen.secondary_label();
+        let filter_button = format!("TEXTURES: {}", self.texture_filtering.label());
+        let quality_button = format!("SHADOWS: {}", self.graphics_quality.label());
+        let vsync_button = format!("VSYNC: {}", self.vsync_mode.label());
+        let brightness = format!(
+            "BRIGHTNESS: {:.0}%",
+            self.min_ambient / MAX_MIN_AMBIENT * 100.0
+        );
 
-        // Status pill: prefer error > status > idle ready message.
+        // Status pill: prefer error > pending rebind > status > idle ready
+        // message.
         let status_text = if let Some(ref err) = self.menu_state.error_message {
             format!("ERROR: {}", err)
+        } else if let Some(action) = self.pending_rebind {
+            format!(
+                "PRESS A KEY FOR {}, ESC cancels",
+                action.label().to_uppercase()
+            )
         } else if let Some(ref status) = self.menu_state.status_message {
             format!("STATUS: {}", status)
+        } else if screen == MenuScreen::Singleplayer {
+            "READY: ENTER creates a new world, ESC goes back".to_string()
+        } else if screen == MenuScreen::Controls {
+            "READY: click an action, then press its new key".to_string()
         } else {
             "READY: ENTER joins multiplayer, ESC starts solo".to_string()
         };
@@ -1652,6 +2481,58 @@ impl State {
             Some(self.config.height as f32),
         );
 
+        self.menu_filter_button_buffer.set_text(
+            &mut self.font_system,
+            &filter_button,
+            &Attrs::new().family(Family::SansSerif),
+            Shaping::Advanced,
+            None,
+        );
+        self.menu_filter_button_buffer.set_size(
+            &mut self.font_system,
+            Some(self.config.width as f32),
+            Some(self.config.height as f32),
+        );
+
+        self.menu_quality_button_buffer.set_text(
+            &mut self.font_system,
+            &quality_button,
+            &Attrs::new().family(Family::SansSerif),
+            Shaping::Advanced,
+            None,
+        );
+        self.menu_quality_button_buffer.set_size(
+            &mut self.font_system,
+            Some(self.config.width as f32),
+            Some(self.config.height as f32),
+        );
+
+        self.menu_vsync_button_buffer.set_text(
+            &mut self.font_system,
+            &vsync_button,
+            &Attrs::new().family(Family::SansSerif),
+            Shaping::Advanced,
+            None,
+        );
+        self.menu_vsync_button_buffer.set_size(
+            &mut self.font_system,
+            Some(self.config.width as f32),
+            Some(self.config.height as f32),
+        );
+
+        self.menu_brightness_buffer.set_text(
+            &mut self.font_system,
+            &brightness,
+            &Attrs::new().family(Family::SansSerif),
+            Shaping::Advanced,
+            None,
+        );
+        self.menu_brightness_buffer.set_size(
+            &mut self.font_system,
+            Some(self.config.width as f32),
+            Some(self.config.height as f32),
+        );
+
         self.menu_status_buffer.set_text(
             &mut self.font_system,
             &status_text,
@@ -1664,6 +2545,77 @@ impl State {
             Some(self.config.width as f32),
             Some(self.config.height as f32),
         );
+
+        self.menu_controls_button_buffer.set_text(
+            &mut self.font_system,
+            "CONTROLS",
+            &Attrs::new().family(Family::SansSerif),
+            Shaping::Advanced,
+            None,
+        );
+        self.menu_controls_button_buffer.set_size(
+            &mut self.font_system,
+            Some(self.config.width as f32),
+            Some(self.config.height as f32),
+        );
+
+        self.menu_preset_button_buffer.set_text(
+            &mut self.font_system,
+            &format!("WORLD TYPE: {}", self.menu_state.world_preset.label()),
+            &Attrs::new().family(Family::SansSerif),
+            Shaping::Advanced,
+            None,
+        );
+        self.menu_preset_button_buffer.set_size(
+            &mut self.font_system,
+            Some(self.config.width as f32),
+            Some(self.config.height as f32),
+        );
+
+        // Controls page: one line per action, the left column first.  The
+        // row waiting for a key shows "..." and unbound actions a dash.
+        for (column, actions) in Action::ALL.chunks(BINDING_ROWS_PER_COLUMN).enumerate() {
+            let labels: Vec<&str> = actions.iter().map(|action| action.label()).collect();
+            let keys: Vec<String> = actions
+                .iter()
+                .map(|&action| {
+                    if self.pending_rebind == Some(action) {
+                        "...".to_string()
+                    } else {
+                        self.key_bindings
+                            .key(action)
+                            .map(key_label)
+                            .unwrap_or_else(|| "-".to_string())
+                    }
+                })
+                .collect();
+
+            self.menu_binding_label_buffers[column].set_text(
+                &mut self.font_system,
+                &labels.join("\n"),
+                &Attrs::new().family(Family::SansSerif),
+                Shaping::Advanced,
+                None,
+            );
+            self.menu_binding_label_buffers[column].set_size(
+                &mut self.font_system,
+                Some(self.config.width as f32),
+                Some(self.config.height as f32),
+            );
+
+            self.menu_binding_key_buffers[column].set_text(
+                &mut self.font_system,
+                &keys.join("\n"),
+                &Attrs::new().family(Family::SansSerif),
+                Shaping::Advanced,
+                None,
+            );
+            self.menu_binding_key_buffers[column].set_size(
+                &mut self.font_system,
+                Some(self.config.width as f32),
+                Some(self.config.height as f32),
+            );
+        }
     }
 
     /// Renders the main-menu overlay as a series of flat colored rectangles.
@@ -1679,16 +2631,21 @@ impl State {
     /// 2. Panel drop-shadow (slightly larger than the panel itself).
     /// 3. Panel background.
     /// 4. Panel top accent stripe (gold).
-    /// 5. Title badge background + left accent stripe.
-    /// 6. Quick-tips card + left accent stripe.
-    /// 7. Server address field (border + fill, highlight when active).
-    /// 8. Username field (border + fill, highlight when active).
-    /// 9. Connect button (border + fill, highlight on hover).
-    /// 10. Singleplayer button (border + fill, highlight on hover).
+    /// 5. Title badge background + left accent stripe, and the "Controls"
+    ///    button on the main page.
+    /// 6. Quick-tips card + left accent stripe + graphics quality and texture
+    ///    filtering toggles.
+    /// 7. Server address or seed field (border + fill, highlight when active).
+    /// 8. Username or sea level field (border + fill, highlight when active).
+    ///    On the singleplayer page the world preset toggle follows it.
+    /// 9. Connect / create world / reset button (border + fill, highlight on hover).
+    /// 10. Singleplayer / back button (border + fill, highlight on hover).
     /// 11. Status pill background.
     /// 12. Active-field top underline (gold, only when a field is selected).
     /// 13. Text cursor (blinking gold bar inside the active field).
     ///
+    /// On the controls page the key binding rows replace 6–8.
+    ///
     /// # Parameters
     /// - `encoder` – Command encoder to append the render pass to.
     /// - `view`    – Swap-chain texture view to draw into.
@@ -1700,9 +2657,10 @@ impl State {
 
         // Determine which interactive element the cursor is currently over so
         // hover highlight colors can be applied to the correct button.
+        let screen = self.menu_state.screen;
         let hovered = self
             .cursor_position
-            .and_then(|(x, y)| layout.hit_test(x, y));
+            .and_then(|(x, y)| layout.hit_test(screen, x, y));
 
         let mut vertices = Vec::with_capacity(96);
         let mut indices = Vec::with_capacity(144);
@@ -1793,95 +2751,282 @@ impl State {
             height,
         );
 
-        // 6a. Quick-tips card background.
-        push_rect(
-            &mut vertices,
-            &mut indices,
-            Rect {
-                x: layout.quick_card.x,
-                y: layout.quick_card.y,
-                w: layout.quick_card.w,
-                h: layout.quick_card.h,
-            },
-            [0.15, 0.19, 0.24, 0.76],
-            width,
-            height,
-        );
-
-        // 6b. Quick-tips card left accent stripe (teal, 4 px wide).
-        push_rect(
-            &mut vertices,
-            &mut indices,
-            Rect {
-                x: layout.quick_card.x,
-                y: layout.quick_card.y,
-                w: 4.0,
-                h: layout.quick_card.h,
-            },
-            [0.35, 0.8, 0.78, 1.0],
-            width,
-            height,
-        );
+        // 5c. "Controls" button in the header (main page only), styled like
+        //     the quick card toggles.
+        if screen == MenuScreen::Main {
+            let button = layout.controls_button;
+            let fill = if hovered == Some(MenuHit::Controls) {
+                [0.17, 0.27, 0.30, 1.0]
+            } else {
+                [0.13, 0.2, 0.23, 1.0]
+            };
+            push_rect(
+                &mut vertices,
+                &mut indices,
+                button,
+                [0.22, 0.5, 0.49, 1.0],
+                width,
+                height,
+            );
+            push_rect(
+                &mut vertices,
+                &mut indices,
+                Rect {
+                    x: button.x + 2.0,
+                    y: button.y + 2.0,
+                    w: button.w - 4.0,
+                    h: button.h - 4.0,
+                },
+                fill,
+                width,
+                height,
+            );
+        }
 
-        // 7. Server address field (active = slightly brighter fill).
-        let field_color = if self.menu_state.selected_field == MenuField::ServerAddress {
-            [0.13, 0.2, 0.27, 0.88]
+        if screen == MenuScreen::Controls {
+            // 6–8. Key binding rows in place of the quick card and the
+            //      fields: alternating fills, teal on hover, gold while the
+            //      row waits for its new key.
+            for (index, action) in Action::ALL.into_iter().enumerate() {
+                let row = layout.binding_row(index);
+                let fill = if self.pending_rebind == Some(action) {
+                    [0.42, 0.33, 0.12, 0.9]
+                } else if hovered == Some(MenuHit::Binding(index)) {
+                    [0.17, 0.27, 0.30, 0.9]
+                } else if index % 2 == 0 {
+                    [0.15, 0.19, 0.24, 0.76]
+                } else {
+                    [0.13, 0.17, 0.22, 0.76]
+                };
+                // 1 px gap between rows.
+                push_rect(
+                    &mut vertices,
+                    &mut indices,
+                    Rect {
+                        x: row.x,
+                        y: row.y + 1.0,
+                        w: row.w,
+                        h: row.h - 2.0,
+                    },
+                    fill,
+                    width,
+                    height,
+                );
+            }
         } else {
-            [0.13, 0.17, 0.22, 0.78]
-        };
-        // Outer dark border (1 px implied by the 2 px inset of the inner rect).
-        push_rect(
-            &mut vertices,
-            &mut indices,
-            layout.server_field,
-            [0.04, 0.05, 0.07, 0.78],
-            width,
-            height,
-        );
-        push_rect(
-            &mut vertices,
-            &mut indices,
-            Rect {
-                x: layout.server_field.x + 2.0,
-                y: layout.server_field.y + 2.0,
-                w: layout.server_field.w - 4.0,
-                h: layout.server_field.h - 4.0,
-            },
-            field_color,
-            width,
-            height,
-        );
+            // 6a. Quick-tips card background.
+            push_rect(
+                &mut vertices,
+                &mut indices,
+                Rect {
+                    x: layout.quick_card.x,
+                    y: layout.quick_card.y,
+                    w: layout.quick_card.w,
+                    h: layout.quick_card.h,
+                },
+                [0.15, 0.19, 0.24, 0.76],
+                width,
+                height,
+            );
 
-        // 8. Username field (same pattern as server field).
-        let username_color = if self.menu_state.selected_field == MenuField::Username {
-            [0.13, 0.2, 0.27, 0.88]
-        } else {
-            [0.13, 0.17, 0.22, 0.78]
-        };
-        push_rect(
-            &mut vertices,
-            &mut indices,
-            layout.username_field,
-            [0.04, 0.05, 0.07, 0.78],
-            width,
-            height,
-        );
-        push_rect(
-            &mut vertices,
-            &mut indices,
-            Rect {
-                x: layout.username_field.x + 2.0,
-                y: layout.username_field.y + 2.0,
-                w: layout.username_field.w - 4.0,
-                h: layout.username_field.h - 4.0,
-            },
-            username_color,
-            width,
-            height,
-        );
+            // 6b. Quick-tips card left accent stripe (teal, 4 px wide).
+            push_rect(
+                &mut vertices,
+                &mut indices,
+                Rect {
+                    x: layout.quick_card.x,
+                    y: layout.quick_card.y,
+                    w: 4.0,
+                    h: layout.quick_card.h,
+                },
+                [0.35, 0.8, 0.78, 1.0],
+                width,
+                height,
+            );
 
-        // 9. Connect button (brighter fill on hover).
-        let connect_fill = if matches!(hovered, Some(crate::ui::menu::MenuHit::Connect)) {
+            // 6c. Quick card toggles: VSync, graphics quality and texture
+            //     filtering (teal border, brighter on hover).
+            for (button, hit) in [
+                (layout.vsync_button, MenuHit::VSync),
+                (layout.quality_button, MenuHit::GraphicsQuality),
+                (layout.filter_button, MenuHit::TextureFiltering),
+            ] {
+                let fill = if hovered == Some(hit) {
+                    [0.17, 0.27, 0.30, 1.0]
+                } else {
+                    [0.13, 0.2, 0.23, 1.0]
+                };
+                push_rect(
+                    &mut vertices,
+                    &mut indices,
+                    button,
+                    [0.22, 0.5, 0.49, 1.0],
+                    width,
+                    height,
+                );
+                push_rect(
+                    &mut vertices,
+                    &mut indices,
+                    Rect {
+                        x: button.x + 2.0,
+                        y: button.y + 2.0,
+                        w: button.w - 4.0,
+                        h: button.h - 4.0,
+                    },
+                    fill,
+                    width,
+                    height,
+                );
+            }
+
+            // 6d. Brightness slider: same border, with the part left of the
+            //     current value filled in teal.
+            let slider = layout.brightness_slider;
+            let value = self.min_ambient / MAX_MIN_AMBIENT;
+            push_rect(
+                &mut vertices,
+                &mut indices,
+                slider,
+                [0.22, 0.5, 0.49, 1.0],
+                width,
+                height,
+            );
+            push_rect(
+                &mut vertices,
+                &mut indices,
+                Rect {
+                    x: slider.x + 2.0,
+                    y: slider.y + 2.0,
+                    w: slider.w - 4.0,
+                    h: slider.h - 4.0,
+                },
+                [0.13, 0.2, 0.23, 1.0],
+                width,
+                height,
+            );
+            let fill =
+                if hovered == Some(MenuHit::Brightness) || self.menu_state.dragging_brightness {
+                    [0.2, 0.42, 0.42, 1.0]
+                } else {
+                    [0.17, 0.34, 0.35, 1.0]
+                };
+            push_rect(
+                &mut vertices,
+                &mut indices,
+                Rect {
+                    x: slider.x + 2.0,
+                    y: slider.y + 2.0,
+                    w: (slider.w - 4.0) * value,
+                    h: slider.h - 4.0,
+                },
+                fill,
+                width,
+                height,
+            );
+
+            // 7. Server address field (active = slightly brighter fill).
+            let field_color = if matches!(
+                self.menu_state.selected_field,
+                MenuField::ServerAddress | MenuField::Seed
+            ) {
+                [0.13, 0.2, 0.27, 0.88]
+            } else {
+                [0.13, 0.17, 0.22, 0.78]
+            };
+            // Outer dark border (1 px implied by the 2 px inset of the inner rect).
+            push_rect(
+                &mut vertices,
+                &mut indices,
+                layout.server_field,
+                [0.04, 0.05, 0.07, 0.78],
+                width,
+                height,
+            );
+            push_rect(
+                &mut vertices,
+                &mut indices,
+                Rect {
+                    x: layout.server_field.x + 2.0,
+                    y: layout.server_field.y + 2.0,
+                    w: layout.server_field.w - 4.0,
+                    h: layout.server_field.h - 4.0,
+                },
+                field_color,
+                width,
+                height,
+            );
+
+            // 8. Username or sea level field (same pattern as server field).
+            {
+                let username_color = if matches!(
+                    self.menu_state.selected_field,
+                    MenuField::Username | MenuField::SeaLevel
+                ) {
+                    [0.13, 0.2, 0.27, 0.88]
+                } else {
+                    [0.13, 0.17, 0.22, 0.78]
+                };
+                push_rect(
+                    &mut vertices,
+                    &mut indices,
+                    layout.username_field,
+                    [0.04, 0.05, 0.07, 0.78],
+                    width,
+                    height,
+                );
+                push_rect(
+                    &mut vertices,
+                    &mut indices,
+                    Rect {
+                        x: layout.username_field.x + 2.0,
+                        y: layout.username_field.y + 2.0,
+                        w: layout.username_field.w - 4.0,
+                        h: layout.username_field.h - 4.0,
+                    },
+                    username_color,
+                    width,
+                    height,
+                );
+            }
+
+            // 8b. World preset toggle (singleplayer page only), styled like
+            //     the quick card toggles.
+            if screen == MenuScreen::Singleplayer {
+                let button = layout.preset_button;
+                let fill = if hovered == Some(MenuHit::WorldPreset) {
+                    [0.17, 0.27, 0.30, 1.0]
+                } else {
+                    [0.13, 0.2, 0.23, 1.0]
+                };
+                push_rect(
+                    &mut vertices,
+                    &mut indices,
+                    button,
+                    [0.22, 0.5, 0.49, 1.0],
+                    width,
+                    height,
+                );
+                push_rect(
+                    &mut vertices,
+                    &mut indices,
+                    Rect {
+                        x: button.x + 2.0,
+                        y: button.y + 2.0,
+                        w: button.w - 4.0,
+                        h: button.h - 4.0,
+                    },
+                    fill,
+                    width,
+                    height,
+                );
+            }
+        }
+
+        // 9. Connect / create world / reset button (brighter fill on hover).
+        let connect_fill = if matches!(
+            hovered,
+            Some(MenuHit::Connect | MenuHit::CreateWorld | MenuHit::ResetBindings)
+        ) {
             [0.24, 0.52, 0.84, 1.0]
         } else {
             [0.2, 0.45, 0.74, 1.0]
@@ -1908,8 +3053,8 @@ impl State {
             height,
         );
 
-        // 10. Singleplayer button (same pattern, darker palette).
-        let single_fill = if matches!(hovered, Some(crate::ui::menu::MenuHit::Singleplayer)) {
+        // 10. Singleplayer / back button (same pattern, darker palette).
+        let single_fill = if matches!(hovered, Some(MenuHit::Singleplayer | MenuHit::Back)) {
             [0.19, 0.22, 0.28, 1.0]
         } else {
             [0.16, 0.19, 0.24, 1.0]
@@ -1949,8 +3094,8 @@ impl State {
         // 12. Active-field top underline (gold, 3 px high).
         // Only drawn when a field is actually selected.
         let selected_field_x = match self.menu_state.selected_field {
-            MenuField::ServerAddress => Some(layout.server_field),
-            MenuField::Username => Some(layout.username_field),
+            MenuField::ServerAddress | MenuField::Seed => Some(layout.server_field),
+            MenuField::Username | MenuField::SeaLevel => Some(layout.username_field),
             MenuField::None => None,
         };
         if let Some(field) = selected_field_x {
@@ -1978,6 +3123,10 @@ impl State {
                 Some((layout.server_field, self.menu_state.server_address.as_str()))
             }
             MenuField::Username => Some((layout.username_field, self.menu_state.username.as_str())),
+            MenuField::Seed => Some((layout.server_field, self.menu_state.seed.as_str())),
+            MenuField::SeaLevel => {
+                Some((layout.username_field, self.menu_state.sea_level.as_str()))
+            }
             MenuField::None => None,
         };
         if let Some((field, value)) = active_field {
@@ -2044,20 +3193,4 @@ impl State {
             pass.draw_indexed(0..indices.len() as u32, 0, 0..1);
         }
     }
-
-    /// Projects remote player world positions into screen space for name-tag
-    /// rendering.
-    ///
-    /// Currently a stub; the actual projection logic is handled by
-    /// [`queue_remote_players_labels`] in the multiplayer player module and
-    /// the results are consumed directly in `render`.  This method exists as
-    /// a hook for future per-player rendering work (e.g., health bars, custom
-    /// skins) that would require a dedicated render pass rather than a text
-    /// overlay.
-    ///
-    /// # Parameters
-    /// - `_view_proj` – Combined view-projection matrix (unused by the stub).
-    /// - `_width`     – Surface width in pixels (unused by the stub).
-    /// - `_height`    – Surface height in pixels (unused by the stub).
-    pub fn render_remote_players(&mut self, _view_proj: &glam::Mat4, _width: f32, _height: f32) {}
 }
@@ -1,5 +1,6 @@
 use glyphon::Resolution;
 
+use super::bloom::{BloomTargets, HDR_FORMAT};
 use super::state::State;
 
 impl State {
@@ -17,16 +18,18 @@ impl State {
     /// | Resource | Reason |
     /// |---|---|
     /// | Surface configuration | Swap-chain must match the new pixel dimensions. |
-    /// | Depth texture (MSAA) | Multisampled depth must match the color target size. |
-    /// | MSAA color texture | Render target size changed. |
+    /// | Depth texture | Multisampled depth must match the color target size. |
+    /// | MSAA color texture | Render target size changed; absent without MSAA. |
     /// | SSR color texture + view | SSR reads scene pixels 1:1; must stay in sync. |
     /// | SSR depth texture + view | Same reason – used for refraction depth lookups. |
     /// | SSR sampler | Recreated alongside its textures for clarity. |
     /// | `water_bind_group` | References the new SSR views. |
     /// | `depth_resolve_bind_group` | References the new multisampled depth view. |
     /// | `glyphon` viewport | Text renderer needs the physical resolution for HiDPI. |
+    /// | Crosshair vertices, hotbar | NDC geometry is corrected for the aspect ratio. |
     /// | Scene color texture + view | MSAA resolve target for the composite pass. |
-    /// | `composite_bind_group` | References the new scene color view. |
+    /// | Bloom targets + bind groups | Half/quarter resolution; read the scene color view. |
+    /// | `composite_bind_group` | References the new scene color, bloom and resolved depth views. |
     /// | Hi-Z texture + mips + bind groups | Only when the mip count changes (see below). |
     ///
     /// # Hi-Z conditional rebuild
@@ -54,14 +57,13 @@ impl State {
             // ── MSAA color and depth targets ─────────────────────────────── //
             // Both must exactly match the new surface dimensions; mismatched
             // sizes cause validation errors when beginning render passes.
-            let msaa_sample_count: u32 = 4;
             self.depth_texture =
-                Self::create_depth_texture(&self.device, &self.config, msaa_sample_count);
+                Self::create_depth_texture(&self.device, &self.config, self.msaa_samples);
             self.msaa_texture_view = Self::create_msaa_texture(
                 &self.device,
                 &self.config,
-                self.surface_format,
-                msaa_sample_count,
+                HDR_FORMAT,
+                self.msaa_samples,
             );
 
             // ── SSR (Screen-Space Reflections) targets ────────────────────── //
@@ -80,9 +82,10 @@ impl State {
                 mip_level_count: 1,
                 sample_count: 1, // single-sampled – water shader cannot use MSAA textures
                 dimension: wgpu::TextureDimension::D2,
-                format: self.surface_format,
+                format: HDR_FORMAT,
                 usage: wgpu::TextureUsages::RENDER_ATTACHMENT
-                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                    | wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_SRC,
                 view_formats: &[],
             });
             self.ssr_color_view = self
@@ -148,57 +151,10 @@ impl State {
             // change.  All other bindings (uniforms, atlas, shadow map) are
             // resolution-independent and are simply re-bound from their
             // existing handles.
-            self.water_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-                layout: &self.water_bind_group_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: self.uniform_buffer.as_entire_binding(),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: wgpu::BindingResource::TextureView(&self.texture_view),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 2,
-                        resource: wgpu::BindingResource::Sampler(&self.texture_sampler),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 3,
-                        resource: wgpu::BindingResource::TextureView(&self.shadow_texture_view),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 4,
-                        resource: wgpu::BindingResource::Sampler(&self.shadow_sampler),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 5,
-                        // ← new SSR color view from the recreated texture
-                        resource: wgpu::BindingResource::TextureView(&self.ssr_color_view),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 6,
-                        // ← new SSR depth view from the recreated texture
-                        resource: wgpu::BindingResource::TextureView(&self.ssr_depth_view),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 7,
-                        resource: wgpu::BindingResource::Sampler(&self.ssr_sampler),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 8,
-                        resource: wgpu::BindingResource::TextureView(&self.flow_map_view),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 9,
-                        resource: wgpu::BindingResource::Sampler(&self.flow_sampler),
-                    },
-                ],
-                label: Some("water_bind_group"),
-            });
+            self.water_bind_group = self.create_water_bind_group();
 
             // ── Depth-resolve bind group ──────────────────────────────────── //
-            // The depth-resolve compute shader reads the multisampled depth
+            // The depth-resolve compute shader reads the (multisampled) depth
             // texture and writes both single-sampled outputs.  The bind group
             // must reference the freshly-created depth and output views.
             // Layout is retrieved from the pipeline to avoid storing a
@@ -278,6 +234,18 @@ impl State {
                 },
             );
 
+            // ── Screen-space UI geometry ──────────────────────────────────── //
+            // The crosshair and hotbar are built in NDC with the aspect ratio
+            // folded in, so they would stretch without a rebuild.
+            let (crosshair_vertices, _) =
+                minerust::build_crosshair(new_size.width as f32 / new_size.height as f32);
+            self.queue.write_buffer(
+                &self.crosshair_vertex_buffer,
+                0,
+                bytemuck::cast_slice(&crosshair_vertices),
+            );
+            self.hotbar_dirty = true;
+
             // ── Scene color texture (composite pass input) ───────────────── //
             // After all MSAA passes resolve into this texture, the composite
             // shader reads it and writes post-processed output to the swap-chain.
@@ -291,15 +259,28 @@ impl State {
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
-                format: self.surface_format,
+                format: HDR_FORMAT,
                 usage: wgpu::TextureUsages::RENDER_ATTACHMENT
-                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                    | wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_DST,
                 view_formats: &[],
             });
             self.scene_color_view = self
                 .scene_color_texture
                 .create_view(&wgpu::TextureViewDescriptor::default());
 
+            // ── Bloom targets ─────────────────────────────────────────────── //
+            // The bright-pass reads the new `scene_color_view`, and the half /
+            // quarter resolution chain follows the window size.
+            self.bloom_targets = BloomTargets::new(
+                &self.device,
+                &self.bloom_pipelines,
+                &self.bloom_config_buffer,
+                &self.scene_color_view,
+                self.config.width,
+                self.config.height,
+            );
+
             // ── Composite bind group ──────────────────────────────────────── //
             // Must reference the new `scene_color_view`.  The sampler is
             // bilinear (unlike the nearest-neighbor SSR sampler) because the
@@ -331,6 +312,20 @@ impl State {
                         binding: 2,
                         resource: wgpu::BindingResource::Sampler(&composite_sampler),
                     },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::TextureView(
+                            self.bloom_targets.output_view(),
+                        ),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: self.bloom_config_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 5,
+                        resource: wgpu::BindingResource::TextureView(&self.ssr_depth_view),
+                    },
                 ],
             });
 
@@ -1,47 +1,80 @@
+use std::io::ErrorKind;
 use std::sync::Arc;
 
 use crate::logger::{LogLevel, log};
 use crate::multiplayer::protocol::Packet;
+use crate::multiplayer::server::{GameServer, Outgoing, ServerConfig, ServerEvent};
 use crate::multiplayer::tcp::TcpServer;
 
-/// Runs a standalone dedicated multiplayer server that accepts TCP connections
-/// and relays packets between all connected clients.
+/// Runs a standalone dedicated multiplayer server that accepts TCP connections,
+/// keeps the authoritative world in a [`GameServer`] and relays player
+/// packets between all connected clients.
 ///
 /// # Lifecycle
 ///
-/// 1. Binds a [`TcpServer`] to `addr`.
-/// 2. Enters an infinite accept loop on the calling task.
-/// 3. For each accepted connection, spawns a dedicated Tokio task that owns
-///    the receive loop for that client.
-/// 4. The server runs until the process is killed; there is currently no
-///    graceful shutdown signal.
+/// 1. Binds a [`TcpServer`] to `config.bind_address()` and opens the world
+///    with [`GameServer::open`], restoring `config.world_path` if it exists.
+/// 2. Spawns the tick task, which calls [`GameServer::tick`] every
+///    [`ServerConfig::tick_interval`] and sends the packets it returns,
+///    among them the periodic `TimeSync`.
+/// 3. Enters an accept loop on the calling task.
+/// 4. For each accepted connection, reserves a player ID with
+///    [`GameServer::allocate_player_id`] and spawns a dedicated Tokio task
+///    that owns the receive loop for that client.  The ID doubles as the
+///    connection's key in the [`TcpServer`] and is released when the
+///    connection closes, so a later connection may reuse it.
+/// 5. The server runs until Ctrl-C, which stops the accept loop and calls
+///    [`GameServer::save`] so edits newer than the last periodic save
+///    (see [`ServerConfig::save_interval`]) are not lost.
 ///
 /// # Packet handling
 ///
-/// Every packet received from a client is mutated so its `player_id` field
-/// reflects the server-assigned connection ID rather than whatever the client
+/// Every packet is first passed to [`GameServer::check_packet`]; packets it
+/// rejects (absurd positions, server-only packets, …) and frames that fail
+/// to decode are logged and dropped without closing the connection.
+///
+/// Every remaining packet is mutated so its `player_id` field
+/// reflects the server-assigned player ID rather than whatever the client
 /// sent.  This prevents clients from spoofing another player's identity.
 ///
 /// | Packet variant   | Server action                                                    |
 /// |------------------|------------------------------------------------------------------|
-/// | `Connect`        | Overwrites `player_id`; sends a `ConnectAck` back to the sender.|
+/// | `Connect`        | Overwrites `player_id`; sends `ConnectAck`, `TimeSync` and      |
+/// |                  | spawn edits back.                                                |
+/// |                  | Broadcasts a `PlayerList` to everyone.                           |
 /// | `Position`       | Overwrites `player_id`; broadcast to all other clients.         |
 /// | `Rotation`       | Overwrites `player_id`; broadcast to all other clients.         |
+/// | `Respawn`        | Overwrites `player_id`; not relayed.                            |
+/// | `DiggingProgress`| Overwrites `player_id`; broadcast to all other clients.         |
 /// | `Chat`           | Overwrites `player_id`; broadcast to all other clients.         |
 /// | `Disconnect`     | Overwrites `player_id`; broadcast to all other clients.         |
+/// |                  | Broadcasts a `PlayerList` to those who remain.                   |
+/// | `BlockChange`    | Not relayed; queued for validation on the next tick.            |
+/// | `Command`        | Overwrites `player_id`; not relayed.  Dropped unless the sender |
+/// |                  | is an operator; the server runs it and sends the result          |
+/// |                  | ([`GameServer::run_command`]).                                   |
+/// | `SleepState`     | Overwrites `player_id`; not relayed.  Broadcasts `SkipNight`    |
+/// |                  | once everyone is in bed ([`GameServer::take_night_skip`]).       |
 /// | All other types  | Broadcast as-is (no mutation).                                  |
 ///
-/// On a receive error the client is considered disconnected: a synthetic
-/// `Disconnect` packet is broadcast to all remaining peers and the client is
-/// removed from the server's connection table.
+/// Every packet is also passed to [`GameServer::handle_packet`] so the
+/// server-side player table stays current.
+///
+/// On any other receive error the client is considered disconnected: a
+/// synthetic `Disconnect` packet is broadcast to all remaining peers, the
+/// client is removed from the server's connection table and the updated
+/// `PlayerList` is broadcast.
 ///
 /// # Parameters
-/// - `addr` – The `host:port` string to listen on (e.g. `"0.0.0.0:25565"`).
+/// - `config` – Listen address, port, tick rate and world file.
 ///
 /// # Errors
-/// Logs to `stderr` and returns early if the server cannot bind to `addr`.
-/// Per-client receive/send errors are logged but do not terminate the server.
-pub async fn run_dedicated_server(addr: &str) {
+/// Logs to `stderr` and returns early if the server cannot bind to the
+/// configured address or its world file cannot be loaded.  Per-client receive/send errors are logged but do
+/// not terminate the server.
+pub async fn run_dedicated_server(config: ServerConfig) {
+    let bind_address = config.bind_address();
+    let addr = bind_address.as_str();
     match TcpServer::bind(addr).await {
         Ok(server_inst) => {
             // Wrap in Arc so the handle can be cheaply cloned into each
@@ -56,15 +89,85 @@ pub async fn run_dedicated_server(addr: &str) {
             // message even if stdout is line-buffered (e.g., piped to a file).
             let _ = std::io::Write::flush(&mut std::io::stdout());
 
-            let server_seed: u32 = rand::random();
+            let mut game_inst = match GameServer::open(config) {
+                Ok(game) => game,
+                Err(e) => {
+                    log(LogLevel::Error, &e);
+                    return;
+                }
+            };
+            let server_seed = game_inst.seed();
             log(LogLevel::Info, &format!("Server world seed: {}", server_seed));
+            let events = game_inst.take_event_receiver();
+            let game = Arc::new(game_inst);
+
+            // ── Event log (spawned task) ──────────────────────────────────── //
+            // Drains the event channel so it does not grow without bound and
+            // reports joins and leaves to the operator.
+            if let Some(mut events) = events {
+                tokio::spawn(async move {
+                    while let Some(event) = events.recv().await {
+                        match event {
+                            ServerEvent::PlayerConnected(id, name) => log(
+                                LogLevel::Info,
+                                &format!("Player {} joined as '{}'", id, name),
+                            ),
+                            ServerEvent::PlayerDisconnected(id) => {
+                                log(LogLevel::Info, &format!("Player {} left", id))
+                            }
+                            _ => {}
+                        }
+                    }
+                });
+            }
+
+            // ── Tick loop (spawned task) ──────────────────────────────────── //
+            // Fixed timestep: if a tick overruns, missed ticks are skipped
+            // rather than run back to back.
+            {
+                let server = server.clone();
+                let game = game.clone();
+                tokio::spawn(async move {
+                    let mut interval = tokio::time::interval(game.config().tick_interval());
+                    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+                    loop {
+                        interval.tick().await;
+                        for message in game.tick().await {
+                            match message {
+                                Outgoing::Broadcast(packet) => {
+                                    let _ = server.broadcast(&packet).await;
+                                }
+                                Outgoing::To(id, packet) => {
+                                    let _ = server.send_to(id, &packet).await;
+                                }
+                            }
+                        }
+                    }
+                });
+            }
 
-            // Runs on the calling task forever.  Each accepted connection is
-            // handed off to a new Tokio task so `accept` is free to resume
-            // waiting for the next client immediately.
+            // Runs on the calling task until Ctrl-C.  Each accepted
+            // connection is handed off to a new Tokio task so `accept` is
+            // free to resume waiting for the next client immediately.
+            let shutdown = tokio::signal::ctrl_c();
+            tokio::pin!(shutdown);
             loop {
-                match server.accept().await {
-                    Ok((id, conn)) => {
+                let id = game.allocate_player_id().await;
+                let accepted = tokio::select! {
+                    accepted = server.accept(id) => accepted,
+                    _ = &mut shutdown => {
+                        // Save before exiting so edits made since the last
+                        // periodic save survive the shutdown.
+                        log(LogLevel::Info, "Shutting down, saving the world");
+                        game.release_player_id(id).await;
+                        if let Err(e) = game.save().await {
+                            log(LogLevel::Error, &format!("Failed to save world: {}", e));
+                        }
+                        return;
+                    }
+                };
+                match accepted {
+                    Ok(conn) => {
                         log(
                             LogLevel::Info,
                             &format!(
@@ -76,12 +179,30 @@ pub async fn run_dedicated_server(addr: &str) {
                         // Clone the Arc handle; the spawned task takes ownership
                         // of this clone so the borrow checker is satisfied.
                         let server_clone = server.clone();
+                        let game = game.clone();
 
                         // ── Per-client receive loop (spawned task) ──────── //
                         tokio::spawn(async move {
                             loop {
                                 match conn.recv().await {
                                     Ok(mut packet) => {
+                                        // ── Validation ──────────────────── //
+                                        // Packets that fail the server's
+                                        // checks are dropped, never relayed;
+                                        // the connection stays open.
+                                        if let Err(reason) =
+                                            game.check_packet(id, &mut packet).await
+                                        {
+                                            log(
+                                                LogLevel::Warning,
+                                                &format!(
+                                                    "Dropped packet from client {}: {}",
+                                                    id, reason
+                                                ),
+                                            );
+                                            continue;
+                                        }
+
                                         // ── Player-ID stamping ──────────── //
                                         // Overwrite the `player_id` field on
                                         // every packet variant that carries one.
@@ -104,6 +225,13 @@ pub async fn run_dedicated_server(addr: &str) {
                                                     seed: server_seed,
                                                 };
                                                 let _ = conn.send(&ack).await;
+                                                let time = game.time_sync_packet().await;
+                                                let _ = conn.send(&time).await;
+                                                // Bring the newcomer up to date
+                                                // with edits made around spawn.
+                                                for sync in game.spawn_sync_packets().await {
+                                                    let _ = conn.send(&sync).await;
+                                                }
                                             }
                                             Packet::Position {
                                                 ref mut player_id, ..
@@ -115,11 +243,31 @@ pub async fn run_dedicated_server(addr: &str) {
                                             } => {
                                                 *player_id = id;
                                             }
+                                            Packet::Respawn {
+                                                ref mut player_id, ..
+                                            } => {
+                                                *player_id = id;
+                                            }
+                                            Packet::DiggingProgress {
+                                                ref mut player_id, ..
+                                            } => {
+                                                *player_id = id;
+                                            }
                                             Packet::Chat {
                                                 ref mut player_id, ..
                                             } => {
                                                 *player_id = id;
                                             }
+                                            Packet::Command {
+                                                ref mut player_id, ..
+                                            } => {
+                                                *player_id = id;
+                                            }
+                                            Packet::SleepState {
+                                                ref mut player_id, ..
+                                            } => {
+                                                *player_id = id;
+                                            }
                                             Packet::Disconnect {
                                                 ref mut player_id, ..
                                             } => {
@@ -131,6 +279,61 @@ pub async fn run_dedicated_server(addr: &str) {
                                             _ => {}
                                         }
 
+                                        let _ = game.handle_packet(id, packet.clone()).await;
+
+                                        // Joins and leaves change the roster;
+                                        // everyone, the newcomer included,
+                                        // gets the full list.
+                                        if matches!(
+                                            packet,
+                                            Packet::Connect { .. } | Packet::Disconnect { .. }
+                                        ) {
+                                            let list = game.player_list_packet().await;
+                                            let _ = server_clone.broadcast(&list).await;
+                                        }
+
+                                        // Getting into bed, or the last player
+                                        // awake leaving, may end the night.
+                                        if matches!(
+                                            packet,
+                                            Packet::SleepState { .. } | Packet::Disconnect { .. }
+                                        ) && let Some(skip) = game.take_night_skip().await
+                                        {
+                                            let _ = server_clone.broadcast(&skip).await;
+                                        }
+                                        // Bed and respawn reports only update
+                                        // the server's table, never relayed.
+                                        if matches!(
+                                            packet,
+                                            Packet::SleepState { .. } | Packet::Respawn { .. }
+                                        ) {
+                                            continue;
+                                        }
+
+                                        // Block edits are answered by the tick
+                                        // loop once validated, never relayed.
+                                        if matches!(packet, Packet::BlockChange { .. }) {
+                                            continue;
+                                        }
+
+                                        // Console commands are answered by the
+                                        // server itself, never relayed.
+                                        if let Packet::Command { command, .. } = &packet {
+                                            for message in game.run_command(id, command).await {
+                                                match message {
+                                                    Outgoing::Broadcast(packet) => {
+                                                        let _ =
+                                                            server_clone.broadcast(&packet).await;
+                                                    }
+                                                    Outgoing::To(id, packet) => {
+                                                        let _ =
+                                                            server_clone.send_to(id, &packet).await;
+                                                    }
+                                                }
+                                            }
+                                            continue;
+                                        }
+
                                         // Relay the (possibly mutated) packet to
                                         // every client except the one that sent it.
                                         // Errors here are intentionally ignored:
@@ -139,9 +342,22 @@ pub async fn run_dedicated_server(addr: &str) {
                                         let _ = server_clone.broadcast_except(&packet, id).await;
                                     }
 
+                                    // ── Malformed packet ────────────────── //
+                                    // The frame was consumed, so the stream
+                                    // is still in sync; drop it and go on.
+                                    Err(e) if e.kind() == ErrorKind::InvalidData => {
+                                        log(
+                                            LogLevel::Warning,
+                                            &format!(
+                                                "Dropped malformed packet from client {}: {}",
+                                                id, e
+                                            ),
+                                        );
+                                    }
+
                                     // ── Client disconnection ────────────── //
-                                    // Any receive error is treated as a clean
-                                    // disconnect (TCP RST, EOF, decode failure).
+                                    // Any other receive error is treated as a
+                                    // clean disconnect (TCP RST, EOF).
                                     Err(_) => {
                                         log(
                                             LogLevel::Info,
@@ -164,6 +380,12 @@ pub async fn run_dedicated_server(addr: &str) {
                                         // internal table so it is no longer
                                         // included in future broadcasts.
                                         server_clone.remove_client(id).await;
+                                        game.remove_player(id).await;
+                                        let list = game.player_list_packet().await;
+                                        let _ = server_clone.broadcast(&list).await;
+                                        if let Some(skip) = game.take_night_skip().await {
+                                            let _ = server_clone.broadcast(&skip).await;
+                                        }
 
                                         // Exit the receive loop; the task ends
                                         // naturally and the connection is dropped.
@@ -176,8 +398,10 @@ pub async fn run_dedicated_server(addr: &str) {
 
                     Err(e) => {
                         // A single failed accept does not abort the server;
-                        // log the error and continue waiting for the next client.
+                        // log the error, free the unused ID and continue
+                        // waiting for the next client.
                         log(LogLevel::Error, &format!("Accept error: {}", e));
+                        game.release_player_id(id).await;
                     }
                 }
             }
@@ -0,0 +1,196 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::logger::{LogLevel, log};
+use crate::minerust_data::data;
+
+use super::daylight::{DEFAULT_MIN_AMBIENT, MAX_MIN_AMBIENT};
+use super::filtering::TextureFiltering;
+use super::graphics_quality::GraphicsQuality;
+use super::msaa::{DEFAULT_MSAA_SAMPLES, MSAA_SAMPLE_COUNTS};
+use super::present_mode::VSyncMode;
+use super::state::State;
+
+/// Name of the settings file inside the per-user config directory.
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Master volume of new installs; `--volume` or the settings file change it.
+pub const DEFAULT_VOLUME: f32 = 1.0;
+
+/// Options picked in the menu or on the command line that are kept across
+/// restarts, in the settings file of the per-user config directory.
+///
+/// The file is separate from world saves and is written whenever one of
+/// the options changes.  Options missing from the file keep their
+/// defaults and unknown ones are ignored, so files written by older or
+/// newer versions still load.  Key bindings live in their own file; see
+/// `KeyBindings`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Frame synchronisation; see `State::set_vsync_mode`.
+    pub vsync: VSyncMode,
+    /// Atlas sampling; see `State::set_texture_filtering`.
+    pub texture_filtering: TextureFiltering,
+    /// Shadow quality preset; see `State::set_graphics_quality`.
+    pub graphics_quality: GraphicsQuality,
+    /// Night-time ambient brightness, `0.0..=MAX_MIN_AMBIENT`; see
+    /// `State::min_ambient`.
+    pub brightness: f32,
+    /// Requested MSAA samples per pixel, one of `MSAA_SAMPLE_COUNTS`; see
+    /// `State::msaa_samples`.  Only read at startup.
+    pub msaa: u32,
+    /// Master volume of the block sounds, `0.0..=1.0`; see `State::volume`.
+    pub volume: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            vsync: VSyncMode::default(),
+            texture_filtering: TextureFiltering::default(),
+            graphics_quality: GraphicsQuality::default(),
+            brightness: DEFAULT_MIN_AMBIENT,
+            msaa: DEFAULT_MSAA_SAMPLES,
+            volume: DEFAULT_VOLUME,
+        }
+    }
+}
+
+impl Settings {
+    /// Path of the settings file, or `None` if the config directory is
+    /// unknown on this platform.
+    fn path() -> Option<PathBuf> {
+        data::get_project_dirs()
+            .ok()
+            .map(|dirs| dirs.config_dir().join(SETTINGS_FILE))
+    }
+
+    /// Parses the contents of a settings file, clamping out-of-range
+    /// values back into range and replacing unsupported sample counts with
+    /// the default.
+    ///
+    /// # Errors
+    /// Returns a message if `text` is not a JSON object of settings.
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut settings: Self = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        settings.brightness = if settings.brightness.is_finite() {
+            settings.brightness.clamp(0.0, MAX_MIN_AMBIENT)
+        } else {
+            DEFAULT_MIN_AMBIENT
+        };
+        if !MSAA_SAMPLE_COUNTS.contains(&settings.msaa) {
+            settings.msaa = DEFAULT_MSAA_SAMPLES;
+        }
+        settings.volume = if settings.volume.is_finite() {
+            settings.volume.clamp(0.0, 1.0)
+        } else {
+            DEFAULT_VOLUME
+        };
+        Ok(settings)
+    }
+
+    /// Loads the settings file, falling back to the defaults when there is
+    /// none or it cannot be read.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text).unwrap_or_else(|e| {
+                log(
+                    LogLevel::Warning,
+                    &format!("{}: {}; using the default settings", SETTINGS_FILE, e),
+                );
+                Self::default()
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                log(
+                    LogLevel::Warning,
+                    &format!("Could not read {}: {}", path.display(), e),
+                );
+                Self::default()
+            }
+        }
+    }
+
+    /// Writes the settings file.
+    ///
+    /// # Errors
+    /// Returns a message if the config directory is unknown or the file
+    /// cannot be written.
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path().ok_or("Could not find the config directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let text = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(&path, text + "\n").map_err(|e| e.to_string())
+    }
+}
+
+impl State {
+    /// The current value of every persisted option.
+    pub fn settings(&self) -> Settings {
+        Settings {
+            vsync: self.vsync_mode,
+            texture_filtering: self.texture_filtering,
+            graphics_quality: self.graphics_quality,
+            brightness: self.min_ambient,
+            msaa: self.msaa_samples,
+            volume: self.volume,
+        }
+    }
+
+    /// Writes the current options to the settings file, reporting a
+    /// failure in the menu.
+    pub fn save_settings(&mut self) {
+        if let Err(e) = self.settings().save() {
+            log(LogLevel::Error, &format!("Failed to save settings: {}", e));
+            self.menu_state
+                .set_error(&format!("Could not save settings: {}", e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_files_from_other_versions_still_load() {
+        let settings = Settings {
+            vsync: VSyncMode::Adaptive,
+            texture_filtering: TextureFiltering::Anisotropic,
+            graphics_quality: GraphicsQuality::High,
+            brightness: 0.2,
+            msaa: 8,
+            volume: 0.5,
+        };
+        let text = serde_json::to_string_pretty(&settings).unwrap();
+        assert_eq!(Settings::parse(&text), Ok(settings));
+
+        // Missing options keep their defaults and unknown ones are skipped.
+        let parsed = Settings::parse(r#"{ "vsync": "off", "fov": 100 }"#).unwrap();
+        assert_eq!(
+            parsed,
+            Settings {
+                vsync: VSyncMode::Off,
+                ..Settings::default()
+            }
+        );
+
+        assert_eq!(
+            Settings::parse(r#"{ "brightness": 5.0 }"#)
+                .unwrap()
+                .brightness,
+            MAX_MIN_AMBIENT
+        );
+        let parsed = Settings::parse(r#"{ "msaa": 3, "volume": -1.0 }"#).unwrap();
+        assert_eq!((parsed.msaa, parsed.volume), (DEFAULT_MSAA_SAMPLES, 0.0));
+        assert!(Settings::parse("vsync = on").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+/// Colors of the sky gradient drawn by `sky.wgsl`, blended by the height
+/// of the sun.
+///
+/// The sun, moon, stars, clouds and light scattering are drawn over the
+/// gradient and do not change with the palette.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyPalette {
+    /// Straight up at noon.
+    pub zenith_day: [f32; 3],
+    /// At the horizon at noon.
+    pub horizon_day: [f32; 3],
+    /// Straight up at midnight.
+    pub zenith_night: [f32; 3],
+    /// At the horizon at midnight.
+    pub horizon_night: [f32; 3],
+    /// At the horizon around sunrise and sunset.
+    pub dusk_low: [f32; 3],
+    /// High up around sunrise and sunset.
+    pub dusk_high: [f32; 3],
+    /// Multiplier on the biome haze color, so distant terrain fades into
+    /// this sky rather than the default blue.
+    pub fog_tint: [f32; 3],
+}
+
+impl SkyPalette {
+    /// The default blue sky.
+    pub const VANILLA: SkyPalette = SkyPalette {
+        zenith_day: [0.18, 0.44, 0.88],
+        horizon_day: [0.66, 0.82, 0.98],
+        zenith_night: [0.002, 0.005, 0.015],
+        horizon_night: [0.010, 0.014, 0.034],
+        dusk_low: [0.96, 0.40, 0.18],
+        dusk_high: [0.24, 0.10, 0.28],
+        fog_tint: [1.0, 1.0, 1.0],
+    };
+
+    /// Purple and pink, all day long.
+    pub const VAPORWAVE: SkyPalette = SkyPalette {
+        zenith_day: [0.36, 0.16, 0.62],
+        horizon_day: [0.98, 0.52, 0.78],
+        zenith_night: [0.012, 0.002, 0.030],
+        horizon_night: [0.050, 0.010, 0.070],
+        dusk_low: [1.0, 0.36, 0.62],
+        dusk_high: [0.30, 0.08, 0.48],
+        fog_tint: [1.1, 0.75, 1.0],
+    };
+
+    /// A flat grey sky, as on a cloudy day.
+    pub const OVERCAST: SkyPalette = SkyPalette {
+        zenith_day: [0.46, 0.50, 0.55],
+        horizon_day: [0.70, 0.72, 0.74],
+        zenith_night: [0.004, 0.005, 0.007],
+        horizon_night: [0.012, 0.013, 0.016],
+        dusk_low: [0.62, 0.48, 0.42],
+        dusk_high: [0.26, 0.24, 0.28],
+        fog_tint: [0.92, 0.94, 0.96],
+    };
+
+    /// The gradient colors as uploaded in `Uniforms::sky_palette`, in the
+    /// order `sky.wgsl` reads them; the fourth component is unused.
+    pub fn to_uniform(self) -> [[f32; 4]; 6] {
+        [
+            self.zenith_day,
+            self.horizon_day,
+            self.zenith_night,
+            self.horizon_night,
+            self.dusk_low,
+            self.dusk_high,
+        ]
+        .map(|[r, g, b]| [r, g, b, 0.0])
+    }
+
+    /// Color the scene is cleared to behind the sky dome when the sun's
+    /// direction has height `sun_height`: the day, dusk and night horizon
+    /// colors blended so the sky moves smoothly through the day.
+    pub fn clear_color(&self, sun_height: f32) -> [f32; 3] {
+        let day_factor = sun_height.clamp(0.0, 1.0); // 1 at noon
+        let night_factor = (-sun_height).clamp(0.0, 1.0); // 1 at midnight
+        let sunset_factor = 1.0 - sun_height.abs(); // 1 at the horizon
+        std::array::from_fn(|i| {
+            (self.horizon_day[i] * day_factor
+                + self.dusk_low[i] * sunset_factor * 0.5
+                + self.horizon_night[i] * night_factor)
+                .min(1.0)
+        })
+    }
+}
+
+impl Default for SkyPalette {
+    fn default() -> Self {
+        Self::VANILLA
+    }
+}
+
+/// Built-in sky palettes, picked with `--sky`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SkyPreset {
+    #[default]
+    Vanilla,
+    Vaporwave,
+    Overcast,
+}
+
+impl SkyPreset {
+    /// Every preset, in the order `--sky` lists them.
+    pub const ALL: [SkyPreset; 3] = [
+        SkyPreset::Vanilla,
+        SkyPreset::Vaporwave,
+        SkyPreset::Overcast,
+    ];
+
+    /// Name of the preset on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            SkyPreset::Vanilla => "vanilla",
+            SkyPreset::Vaporwave => "vaporwave",
+            SkyPreset::Overcast => "overcast",
+        }
+    }
+
+    /// The colors of this preset.
+    pub fn palette(self) -> SkyPalette {
+        match self {
+            SkyPreset::Vanilla => SkyPalette::VANILLA,
+            SkyPreset::Vaporwave => SkyPalette::VAPORWAVE,
+            SkyPreset::Overcast => SkyPalette::OVERCAST,
+        }
+    }
+}
+
+/// Parses the `--sky` value, the name of one of the [`SkyPreset`]s.
+pub fn parse_sky_preset(value: &str) -> Result<SkyPreset, String> {
+    SkyPreset::ALL
+        .into_iter()
+        .find(|preset| preset.name().eq_ignore_ascii_case(value))
+        .ok_or_else(|| {
+            let names: Vec<_> = SkyPreset::ALL.iter().map(|p| p.name()).collect();
+            format!(
+                "unknown sky `{}`, expected one of {}",
+                value,
+                names.join(", ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sky_presets_parse_and_drive_the_clear_color() {
+        assert_eq!(parse_sky_preset("vaporwave"), Ok(SkyPreset::Vaporwave));
+        assert_eq!(parse_sky_preset("Overcast"), Ok(SkyPreset::Overcast));
+        assert!(parse_sky_preset("sunny").is_err());
+
+        let palette = SkyPreset::Vaporwave.palette();
+        assert_eq!(palette.clear_color(1.0), palette.horizon_day);
+        assert_eq!(palette.clear_color(-1.0), palette.horizon_night);
+        assert_eq!(palette.to_uniform()[4], [1.0, 0.36, 0.62, 0.0]);
+    }
+}
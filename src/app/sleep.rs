@@ -0,0 +1,144 @@
+use minerust::{BlockType, is_night, next_morning};
+
+use crate::logger::{LogLevel, log};
+use crate::multiplayer::protocol::Packet;
+
+use super::state::State;
+
+/// Real seconds the clock takes to run forward from night to morning, so
+/// the light fades in instead of snapping to day.
+pub const NIGHT_SKIP_DURATION: f32 = 1.0;
+
+/// Horizontal distance in blocks from the bed centre at which a player
+/// waiting for the others to sleep gets up.
+const LEAVE_BED_DISTANCE: f32 = 2.0;
+
+/// The clock running forward to morning after everyone went to bed.
+#[derive(Debug, Clone, Copy)]
+pub struct NightSkip {
+    from: f32,
+    to: f32,
+    elapsed: f32,
+}
+
+impl NightSkip {
+    /// Starts a skip from `time` to [`next_morning`].
+    pub fn new(time: f32) -> Self {
+        Self {
+            from: time,
+            to: next_morning(time),
+            elapsed: 0.0,
+        }
+    }
+
+    /// Advances the skip by `dt` real seconds and returns the clock reading
+    /// to show, easing in and out over [`NIGHT_SKIP_DURATION`].
+    pub fn advance(&mut self, dt: f32) -> f32 {
+        self.elapsed = (self.elapsed + dt).min(NIGHT_SKIP_DURATION);
+        let t = self.elapsed / NIGHT_SKIP_DURATION;
+        self.from + (self.to - self.from) * t * t * (3.0 - 2.0 * t)
+    }
+
+    /// Returns `true` once the clock has reached the morning.
+    pub fn finished(&self) -> bool {
+        self.elapsed >= NIGHT_SKIP_DURATION
+    }
+}
+
+impl State {
+    /// Lies down in the bed at `bed`, which the player right-clicked.
+    ///
+    /// Only works at night.  In singleplayer the night is skipped at once;
+    /// on a server the player waits in bed until the server reports that
+    /// everyone is asleep (see [`State::skip_night`]).
+    pub fn use_bed(&mut self, bed: (i32, i32, i32)) {
+        if !is_night(self.time_of_day) || self.night_skip.is_some() {
+            self.chat
+                .push_message("You can only sleep at night".to_string());
+            return;
+        }
+        let Some(tx) = &self.network_tx else {
+            self.skip_night();
+            return;
+        };
+        if self.bed.is_none() {
+            self.bed = Some(bed);
+            let _ = tx.send(Packet::SleepState {
+                player_id: self.my_player_id,
+                in_bed: true,
+            });
+            self.chat
+                .push_message("Waiting for the other players to sleep".to_string());
+        }
+    }
+
+    /// Starts running the clock forward to the next morning.
+    ///
+    /// Does nothing if it is already day, e.g. when a server's
+    /// `SkipNight` arrives after this client's clock passed sunrise.
+    pub fn skip_night(&mut self) {
+        self.bed = None;
+        if !is_night(self.time_of_day) || self.night_skip.is_some() {
+            return;
+        }
+        log(LogLevel::Info, "Sleeping through the night");
+        self.night_skip = Some(NightSkip::new(self.time_of_day));
+    }
+
+    /// Advances the day-cycle clock by `dt` real seconds, or along the
+    /// running night skip.  On a server the clock also eases towards the
+    /// server's; see [`State::sync_time`].
+    ///
+    /// A player waiting in bed on a server gets up when they walk away,
+    /// the bed is broken or the night ends on its own.
+    pub fn advance_time(&mut self, dt: f32) {
+        if let Some(skip) = &mut self.night_skip {
+            self.time_of_day = skip.advance(dt);
+            if skip.finished() {
+                self.night_skip = None;
+            }
+        } else {
+            self.time_of_day += dt * self.time_scale + self.clock_sync.step(dt);
+        }
+
+        let Some((bx, by, bz)) = self.bed else {
+            return;
+        };
+        let body = self.camera.body_position();
+        let distance = (body.x - (bx as f32 + 0.5)).hypot(body.z - (bz as f32 + 0.5));
+        let still_in_bed = distance <= LEAVE_BED_DISTANCE
+            && is_night(self.time_of_day)
+            && self.world.read().get_block(bx, by, bz) == BlockType::Bed;
+        if !still_in_bed {
+            self.bed = None;
+            if let Some(tx) = &self.network_tx {
+                let _ = tx.send(Packet::SleepState {
+                    player_id: self.my_player_id,
+                    in_bed: false,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skipping_the_night_ends_just_after_sunrise() {
+        // The clock starts at noon, so half a day later is midnight.
+        let midnight = minerust::DAY_LENGTH * 0.5;
+        let morning = next_morning(midnight);
+        let mut skip = NightSkip::new(midnight);
+        let mut previous = midnight;
+        for _ in 0..9 {
+            let time = skip.advance(NIGHT_SKIP_DURATION / 10.0);
+            assert!(time > previous && time < morning);
+            previous = time;
+        }
+        assert!(!skip.finished());
+        assert!((skip.advance(1.0) - morning).abs() < 1e-3);
+        assert!(skip.finished());
+    }
+}
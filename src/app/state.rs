@@ -1,17 +1,46 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::Instant;
 
 use glyphon::{FontSystem, SwashCache, TextAtlas, TextRenderer, Viewport};
 use wgpu;
+use winit::keyboard::ModifiersState;
 use winit::window::Window;
 
 use crate::multiplayer::player::RemotePlayer;
 use crate::multiplayer::protocol::Packet;
+use crate::ui::chat::ChatState;
 use crate::ui::menu::{GameState, MenuState};
+use crate::ui::minimap::Minimap;
+use crate::ui::ui::HOTBAR_SLOTS;
 use minerust::chunk_loader::ChunkLoader;
 use minerust::render_core::csm::CsmManager;
-use minerust::{Camera, DiggingState, IndirectManager, InputState, World};
+use minerust::{
+    BlockMutation, BlockMutationQueue, BlockType, BoxEdit, Camera, DiggingState, EditHistory,
+    IndirectManager, InputState, Inventory, ParticleSystem, RaycastHit, Vertex, World,
+};
+
+use super::atmosphere::Atmosphere;
+#[cfg(feature = "audio")]
+use super::audio::AudioOutput;
+use super::bloom::{BloomPipelines, BloomTargets};
+use super::chunk_progress::ChunkProgress;
+use super::debug_view::{DebugView, DebugViewPipelines};
+use super::filtering::TextureFiltering;
+use super::frame_limiter::FrameLimiter;
+#[cfg(feature = "gamepad")]
+use super::gamepad::GamepadInput;
+use super::gpu_timer::GpuTimer;
+use super::graphics_quality::GraphicsQuality;
+use super::keybindings::{Action, KeyBindings};
+use super::present_mode::VSyncMode;
+use super::sky::SkyPalette;
+use super::sleep::NightSkip;
+use super::texture_cache::AtlasLoader;
+use super::time_sync::ClockSync;
+
+/// Remote block changes waiting for their chunk column `(cx, cz)` to load.
+pub type DeferredBlockChanges = HashMap<(i32, i32), Vec<BlockMutation>>;
 
 /// Central application state owned by the main thread.
 ///
@@ -22,14 +51,13 @@ use minerust::{Camera, DiggingState, IndirectManager, InputState, World};
 /// # Field groupings
 /// The fields are logically organized as follows (in declaration order):
 ///
-/// - **wgpu surface & device** – `surface`, `device`, `queue`, `config`,
-///   `surface_format`.
+/// - **wgpu surface & device** – `surface`, `device`, `queue`, `config`.
 /// - **Render pipelines** – one pipeline per render pass
 ///   (`render_pipeline`, `water_pipeline`, `sun_pipeline`, etc.).
 /// - **Static geometry buffers** – sun quad, crosshair.
 /// - **Uniforms & bind groups** – shared uniform buffer and per-pass bind groups.
 /// - **Render targets** – depth, MSAA, shadow cascade array, SSR, scene color,
-///   Hi-Z pyramid.
+///   bloom chain, Hi-Z pyramid.
 /// - **World & camera** – the shared `World` behind an `RwLock`, camera, and
 ///   input state.
 /// - **Frame timing & stats** – FPS counter, frame time, CPU update time.
@@ -55,14 +83,14 @@ pub struct State {
     pub queue: wgpu::Queue,
     /// Surface configuration (size, format, present mode).
     pub config: wgpu::SurfaceConfiguration,
-    /// Pixel format of the swap-chain surface (cached to avoid repeated lookups).
-    pub surface_format: wgpu::TextureFormat,
 
     // -------------------------------------------------------------------------
     // Render pipelines
     // -------------------------------------------------------------------------
     /// Main opaque terrain render pipeline.
     pub render_pipeline: wgpu::RenderPipeline,
+    /// Translucent terrain pipeline (glass, ice), blended after the water.
+    pub translucent_pipeline: wgpu::RenderPipeline,
     /// Transparent water render pipeline (blended over opaque geometry).
     pub water_pipeline: wgpu::RenderPipeline,
     /// 3-D block outline overlay pipeline.
@@ -75,6 +103,9 @@ pub struct State {
     pub shadow_pipeline: wgpu::RenderPipeline,
     /// Screen-space crosshair render pipeline.
     pub crosshair_pipeline: wgpu::RenderPipeline,
+    /// Variant of `crosshair_pipeline` whose fragment stage samples the
+    /// minimap texture.
+    pub minimap_pipeline: wgpu::RenderPipeline,
     /// Full-screen composite pipeline that resolves MSAA and applies post-FX.
     pub composite_pipeline: wgpu::RenderPipeline,
     /// Compute pipeline that resolves the MSAA depth buffer into Hi-Z seed
@@ -110,6 +141,9 @@ pub struct State {
     pub shadow_config_buffer: wgpu::Buffer,
     /// Bind group that exposes `uniform_buffer` and the texture atlas to shaders.
     pub uniform_bind_group: wgpu::BindGroup,
+    /// Layout of `uniform_bind_group`; kept alive so the bind group can be
+    /// rebuilt when the atlas sampler changes.
+    pub uniform_bind_group_layout: wgpu::BindGroupLayout,
     /// Empty placeholder bind group for terrain pipeline group(1).
     pub terrain_gbuffer_bind_group: wgpu::BindGroup,
     /// Empty placeholder bind group for terrain pipeline group(2).
@@ -135,9 +169,13 @@ pub struct State {
     // -------------------------------------------------------------------------
     /// Non-linear (sRGB) depth buffer view used by the main render pass.
     pub depth_texture: wgpu::TextureView,
-    /// MSAA resolve target view (matches the surface format).
-    pub msaa_texture_view: wgpu::TextureView,
+    /// Sample count of the scene color and depth targets; 1 when MSAA is off.
+    pub msaa_samples: u32,
+    /// Multisampled scene color target, resolved into `ssr_color_view` and
+    /// `scene_color_view`.  `None` when `msaa_samples` is 1.
+    pub msaa_texture_view: Option<wgpu::TextureView>,
     /// Full shadow cascade array texture view (all cascades as one 2-D array).
+    /// Recreated by `set_graphics_quality`.
     pub shadow_texture_view: wgpu::TextureView,
     /// Screen-space shadow mask sampled by `terrain.wgsl`.
     #[allow(dead_code)]
@@ -145,7 +183,8 @@ pub struct State {
     /// View of the screen-space shadow mask texture.
     #[allow(dead_code)]
     pub shadow_mask_view: wgpu::TextureView,
-    /// One `wgpu::TextureView` per shadow cascade for per-cascade rendering.
+    /// One `wgpu::TextureView` per rendered shadow cascade, used as the
+    /// shadow pass render targets.
     pub shadow_cascade_views: Vec<wgpu::TextureView>,
     /// GPU buffer containing the packed `CascadeData` array for all cascades.
     pub shadow_cascade_buffer: wgpu::Buffer,
@@ -159,6 +198,12 @@ pub struct State {
     pub scene_color_texture: wgpu::Texture,
     /// View of `scene_color_texture`.
     pub scene_color_view: wgpu::TextureView,
+    /// Bloom bright-pass and blur pipelines.
+    pub bloom_pipelines: BloomPipelines,
+    /// Bloom render targets and bind groups; rebuilt on resize.
+    pub bloom_targets: BloomTargets,
+    /// [`minerust::BloomConfig`] read by the bloom and composite shaders.
+    pub bloom_config_buffer: wgpu::Buffer,
     /// Color texture used as the SSR source (previous-frame or resolved).
     pub ssr_color_texture: wgpu::Texture,
     /// View of `ssr_color_texture`.
@@ -170,6 +215,7 @@ pub struct State {
     /// Sampler used when reading SSR textures in the water and composite passes.
     pub ssr_sampler: wgpu::Sampler,
     /// The 16-layer `Texture2DArray` holding all block textures.
+    /// A 1×1 placeholder until `atlas_loader` finishes.
     /// Kept alive by the bind group; annotated `#[allow(dead_code)]`.
     #[allow(dead_code)]
     pub texture_atlas: wgpu::Texture,
@@ -177,10 +223,26 @@ pub struct State {
     /// Kept alive by the bind group; annotated `#[allow(dead_code)]`.
     #[allow(dead_code)]
     pub texture_view: wgpu::TextureView,
+    /// Background load of the real texture atlas; `Some` while the loading
+    /// screen is shown.  Polled by `poll_texture_atlas`.
+    pub atlas_loader: Option<AtlasLoader>,
     /// Sampler used when reading the texture atlas in terrain/water shaders.
-    /// Kept alive by the bind group; annotated `#[allow(dead_code)]`.
-    #[allow(dead_code)]
+    /// Recreated by `set_texture_filtering`.
     pub texture_sampler: wgpu::Sampler,
+    /// Current filtering mode of `texture_sampler`.
+    pub texture_filtering: TextureFiltering,
+    /// Highest anisotropy clamp the adapter supports (1 or 16).
+    pub max_anisotropy: u16,
+    /// Frame synchronisation picked on the menu; see `set_vsync_mode`.
+    pub vsync_mode: VSyncMode,
+    /// Present modes the surface supports, which `vsync_mode` is matched
+    /// against.
+    pub present_modes: Vec<wgpu::PresentMode>,
+    /// Frame rate the game loop is held at, set with `/fpscap`; `None`
+    /// leaves it to the present mode.
+    pub fps_cap: Option<u32>,
+    /// Frame deadlines for `fps_cap`; see `State::limit_frame_rate`.
+    pub frame_limiter: FrameLimiter,
     /// Neutral flow-map texture used by the water shader.
     /// Owned by `State` so the texture stays alive as long as the view.
     #[allow(dead_code)]
@@ -216,12 +278,61 @@ pub struct State {
     pub world: Arc<parking_lot::RwLock<World>>,
     /// First-person camera (position, yaw, pitch, velocity).
     pub camera: Camera,
-    /// Block currently under the crosshair and within reach, if any.
-    pub highlighted_block: Option<(i32, i32, i32)>,
+    /// Block and face currently under the crosshair and within reach, if any.
+    pub highlighted_block: Option<RaycastHit>,
+    /// Empty cell a block would be placed in by a right click, drawn as a
+    /// ghost cube.  `None` when nothing is targeted or the block would
+    /// intersect a player.
+    pub placement_preview: Option<(i32, i32, i32)>,
+    /// Whether the targeted face gets a colored overlay on top of the block
+    /// outline (toggled with H).
+    pub show_face_highlight: bool,
+    /// Whether the chunk-border debug overlay is drawn (toggled with G).
+    pub show_chunk_borders: bool,
+    /// Whether remote players get a per-player shirt and nametag color
+    /// derived from their id (toggled with P).
+    pub player_colors: bool,
+    /// Whether blocks being broken, by this or another player, show cracks
+    /// (toggled with C).
+    pub show_dig_cracks: bool,
+    /// Whether the sun and moon path is drawn across the sky with markers
+    /// at noon, sunset, midnight and sunrise (toggled with O).
+    pub show_celestial_path: bool,
+    /// Whether the debug overlay shows the per-phase frame timing breakdown
+    /// and loader queue depths (toggled with F3).
+    pub show_debug_overlay: bool,
+    /// Render target shown in place of the frame while the F3 overlay is
+    /// open (cycled with F7).
+    pub debug_view: DebugView,
+    /// Blit pipelines that draw `debug_view`.
+    pub debug_view_pipelines: DebugViewPipelines,
+    /// Radius in chunks around the player in which chunk borders are drawn
+    /// (set with `/borders <radius>`).
+    pub chunk_border_radius: i32,
     /// Snapshot of keyboard and mouse button state updated each event.
     pub input: InputState,
+    /// Actions held on a gamepad, rebuilt every frame by `poll_gamepad` and
+    /// merged with `input`.  Stays empty without the `gamepad` feature.
+    pub pad_input: InputState,
+    /// Controller polling state; `None` if no gamepad backend is available.
+    #[cfg(feature = "gamepad")]
+    pub gamepad: Option<GamepadInput>,
+    /// Block sound output; `None` if no audio device could be opened.
+    #[cfg(feature = "audio")]
+    pub audio: Option<AudioOutput>,
+    /// Master volume from the settings or `--volume`, kept even without an
+    /// audio device so saving the settings does not reset it.
+    pub volume: f32,
+    /// Modifier keys currently held (used for Ctrl+Z / Ctrl+Y).
+    pub modifiers: ModifiersState,
     /// Block-breaking progress tracker for the currently targeted block.
     pub digging: DiggingState,
+    /// Seconds until the next creative-mode break while the button is held.
+    pub creative_break_cooldown: f32,
+    /// Blocks collected in survival mode; placing a block uses one up.
+    pub inventory: Inventory,
+    /// Time of the last non-repeat Space press, for the creative double tap.
+    pub last_jump_press: Option<Instant>,
     /// The OS window; shared with the event loop and network thread.
     pub window: Arc<Window>,
     /// Whether the cursor is captured (hidden and locked to the window center).
@@ -248,6 +359,38 @@ pub struct State {
     pub last_frame: Instant,
     /// `Instant` when the game session started (used for elapsed-time uniforms).
     pub game_start_time: Instant,
+    /// Day-cycle clock in seconds, driving the sun and moon; see
+    /// `sun_direction`.  Saved with the world.
+    pub time_of_day: f32,
+    /// How many day-cycle seconds pass per real second; set with
+    /// `/time scale` and saved with the world.
+    pub time_scale: f32,
+    /// What is left to make up of the difference to a server's clock; see
+    /// `State::sync_time`.
+    pub clock_sync: ClockSync,
+    /// The clock running forward to morning after sleeping, if any; see
+    /// `State::skip_night`.
+    pub night_skip: Option<NightSkip>,
+    /// Bed the player lies in while waiting for the other players on a
+    /// server to sleep.
+    pub bed: Option<(i32, i32, i32)>,
+    /// Player health, up to `MAX_HEALTH`; see `State::hurt`.
+    pub health: f32,
+    /// Seconds left of the red flash after the player was hurt.
+    pub hurt_timer: f32,
+    /// Strength of the running hurt flash, scaled by the damage taken.
+    pub hurt_strength: f32,
+    /// Live particles of splashes, breaking blocks and other effects.
+    pub particles: ParticleSystem,
+    /// Debris cubes thrown out by each broken block (`--break-particles`);
+    /// `0` turns them off.
+    pub break_particle_count: u32,
+    /// Seconds until the next water flow tick; see
+    /// [`minerust::World::tick_water`].
+    pub water_tick_timer: f32,
+    /// Seconds until the next random tick; see
+    /// [`minerust::World::tick_random_blocks`].
+    pub random_tick_timer: f32,
     /// Number of chunk columns that produced at least one draw call last frame.
     pub chunks_rendered: u32,
     /// Number of individual subchunks drawn last frame (post-culling).
@@ -257,18 +400,28 @@ pub struct State {
     /// Whether the GPU supports `multi_draw_indirect_count`; falls back to a
     /// fixed draw-count path when `false`.
     pub supports_indirect_count: bool,
+    /// Times the render phases on the GPU for the debug overlay; `None` when
+    /// the device lacks `TIMESTAMP_QUERY`.
+    pub gpu_timer: Option<GpuTimer>,
 
     // -------------------------------------------------------------------------
     // Streaming: chunk generation and mesh building
     // -------------------------------------------------------------------------
     /// Submits chunk generation requests to background threads and collects results.
     pub chunk_loader: ChunkLoader,
+    /// Number of chunk generation threads every `ChunkLoader` is created with.
+    pub chunk_workers: usize,
+    /// Number of mesh building threads every `MeshLoader` is created with.
+    pub mesh_workers: usize,
     /// Chunk-column X coordinate of the player's position on the last generation scan.
     pub last_gen_player_cx: i32,
     /// Chunk-column Z coordinate of the player's position on the last generation scan.
     pub last_gen_player_cz: i32,
     /// Submits subchunk mesh-build requests to background threads and collects results.
     pub mesh_loader: minerust::MeshLoader,
+    /// Finished meshes waiting for their GPU upload, drained nearest-first
+    /// under `MESH_UPLOAD_BYTES_PER_FRAME` each frame.
+    pub mesh_uploads: minerust::MeshUploadQueue,
 
     // -------------------------------------------------------------------------
     // Indirect rendering managers
@@ -283,21 +436,74 @@ pub struct State {
     // -------------------------------------------------------------------------
     /// Computes and stores the per-cascade light-space view-projection matrices.
     pub csm: CsmManager,
+    /// Shadow map resolution and cascade preset (menu toggle).  Recreated
+    /// resources are listed on `set_graphics_quality`.
+    pub graphics_quality: GraphicsQuality,
     /// Active shadow-cascade mode selector (reserved for future multi-mode support).
     pub reflection_mode: u32,
+    /// Whether the bloom post-process runs (toggled with B).
+    pub bloom_enabled: bool,
+    /// Fraction of the sky covered by clouds, set with `--clouds`; 0 turns
+    /// the cloud layer and its shadows off.
+    pub cloud_coverage: f32,
+    /// Colors of the sky gradient and tint of the haze, set with `--sky`.
+    pub sky_palette: SkyPalette,
+    /// Night-time ambient brightness set with the menu's brightness slider;
+    /// the terrain is never lit below it.
+    pub min_ambient: f32,
+    /// Ambient tint and haze of the biome the player stands in, blended
+    /// over a few seconds when they cross into another biome.
+    pub atmosphere: Atmosphere,
 
     // -------------------------------------------------------------------------
     // HUD: coordinate display
     // -------------------------------------------------------------------------
-    /// Vertex buffer for the coordinate HUD quad (rebuilt when position changes).
-    pub coords_vertex_buffer: Option<wgpu::Buffer>,
-    /// Index buffer for the coordinate HUD quad.
-    pub coords_index_buffer: Option<wgpu::Buffer>,
-    /// Number of indices in `coords_index_buffer`.
-    pub coords_num_indices: u32,
-    /// Block coordinates the coordinate HUD was last built for; used to skip
-    /// rebuilds when the player has not moved to a new block.
-    pub last_coords_position: (i32, i32, i32),
+    /// Position, chunk, facing and biome readout in the top-right corner.
+    pub coords_buffer: glyphon::Buffer,
+    /// Pre-measured pixel width of `coords_buffer` for right-aligning it.
+    pub coords_width: f32,
+    /// Text `coords_buffer` was last shaped with; used to skip re-shaping
+    /// while the readout is unchanged.
+    pub coords_text: String,
+    /// Whether the coordinate readout is shown (toggled with F4).
+    pub show_coords: bool,
+    /// Names of everyone in the session, shown while the player list key
+    /// is held in multiplayer.
+    pub player_list_buffer: glyphon::Buffer,
+    /// Pre-measured pixel width of `player_list_buffer` for centring it.
+    pub player_list_width: f32,
+    /// Names and colors `player_list_buffer` was last shaped with; used to
+    /// skip re-shaping while the roster is unchanged.
+    pub player_list_entries: Vec<(String, [f32; 3])>,
+    /// Whether the player list key is held.
+    pub show_player_list: bool,
+
+    // -------------------------------------------------------------------------
+    // World saving
+    // -------------------------------------------------------------------------
+    /// Whether the player has entered the singleplayer world, so it is
+    /// saved even while the pause menu is open over it.  Stays `false` for
+    /// the world behind the menu at launch until the player enters it, and
+    /// is cleared when a server's world replaces it.
+    pub world_entered: bool,
+    /// Background thread writing the world to disk, if a save is running.
+    pub world_save: Option<std::thread::JoinHandle<Result<(), String>>>,
+    /// When the world was last saved; the autosave runs once
+    /// `AUTOSAVE_INTERVAL` of play has passed since.
+    pub last_world_save: Instant,
+    /// The "Saving..." indicator stays up at least until this instant.
+    pub saving_indicator_until: Instant,
+    /// "Saving..." label shown in the bottom-right corner during a save.
+    pub saving_buffer: glyphon::Buffer,
+    /// Pre-measured pixel width of `saving_buffer` for right-aligning it.
+    pub saving_width: f32,
+    /// Chunks loaded around the player, and whether the loading readout
+    /// is shown.
+    pub chunk_progress: ChunkProgress,
+    /// "Loading chunks: X/Y" readout, centred near the top of the screen.
+    pub chunk_progress_buffer: glyphon::Buffer,
+    /// Pre-measured pixel width of `chunk_progress_buffer` for centring it.
+    pub chunk_progress_width: f32,
 
     // -------------------------------------------------------------------------
     // HUD: block-break progress bar
@@ -307,11 +513,22 @@ pub struct State {
     /// Index buffer for the block-break progress bar quad.
     pub progress_bar_index_buffer: Option<wgpu::Buffer>,
 
+    // -------------------------------------------------------------------------
+    // HUD: damage vignette
+    // -------------------------------------------------------------------------
+    /// Vertex buffer for the red vignette shown when hurt or low on health.
+    pub damage_vignette_vertex_buffer: Option<wgpu::Buffer>,
+    /// Index buffer for the damage vignette.
+    pub damage_vignette_index_buffer: Option<wgpu::Buffer>,
+
     // -------------------------------------------------------------------------
     // HUD: hotbar
     // -------------------------------------------------------------------------
     /// Currently selected hotbar slot index (0-based).
     pub hotbar_slot: usize,
+    /// Block in each hotbar slot; starts as [`HOTBAR_SLOTS`] and changes
+    /// when a block is picked with the middle mouse button.
+    pub hotbar: [BlockType; HOTBAR_SLOTS.len()],
     /// Vertex buffer for the hotbar background/selection quads.
     pub hotbar_vertex_buffer: Option<wgpu::Buffer>,
     /// Index buffer for the hotbar background/selection quads.
@@ -322,6 +539,15 @@ pub struct State {
     pub hotbar_dirty: bool,
     /// Slot index the hotbar was last built for; used to detect slot changes.
     pub last_hotbar_slot: usize,
+    /// Block counts the hotbar was last built with, `None` in creative mode.
+    pub hotbar_counts: Option<[u32; HOTBAR_SLOTS.len()]>,
+    /// The selected slot flashes red until this instant after trying to
+    /// place a block the inventory has none of.
+    pub empty_slot_flash_until: Instant,
+    /// Whether the hotbar was last built with the empty-slot flash.
+    pub hotbar_flash: bool,
+    /// Top-down map of the chunks around the player (toggled with M).
+    pub minimap: Minimap,
 
     // -------------------------------------------------------------------------
     // glyphon text rendering
@@ -339,6 +565,8 @@ pub struct State {
 
     /// FPS / performance stats overlay buffer.
     pub fps_buffer: glyphon::Buffer,
+    /// "Loading..." text shown while the texture atlas loads.
+    pub loading_buffer: glyphon::Buffer,
 
     // Main-menu text buffers.
     /// Large title text shown on the main menu.
@@ -359,16 +587,36 @@ pub struct State {
     pub menu_connect_button_buffer: glyphon::Buffer,
     /// "Singleplayer" button label.
     pub menu_singleplayer_button_buffer: glyphon::Buffer,
+    /// Label of the texture filtering toggle in the menu.
+    pub menu_filter_button_buffer: glyphon::Buffer,
+    /// Label of the graphics quality toggle in the menu.
+    pub menu_quality_button_buffer: glyphon::Buffer,
+    /// Label of the VSync toggle in the menu.
+    pub menu_vsync_button_buffer: glyphon::Buffer,
+    /// Label of the brightness slider in the menu.
+    pub menu_brightness_buffer: glyphon::Buffer,
     /// Status / error message shown below the buttons (e.g. "Connecting…").
     pub menu_status_buffer: glyphon::Buffer,
+    /// Label of the "Controls" button in the menu header.
+    pub menu_controls_button_buffer: glyphon::Buffer,
+    /// Label of the world preset toggle on the singleplayer page.
+    pub menu_preset_button_buffer: glyphon::Buffer,
+    /// Action names on the controls page, one buffer per column.
+    pub menu_binding_label_buffers: [glyphon::Buffer; 2],
+    /// Bound key names on the controls page, one buffer per column.
+    pub menu_binding_key_buffers: [glyphon::Buffer; 2],
 
     // In-game HUD text buffers.
     /// Item name label shown above the hotbar when the slot changes.
     pub hotbar_label_buffer: glyphon::Buffer,
     /// Pre-measured pixel width of `hotbar_label_buffer` for centering.
     pub hotbar_label_width: f32,
+    /// Block count in the corner of each hotbar slot (survival mode only).
+    pub hotbar_count_buffers: Vec<glyphon::Buffer>,
     /// One name-tag buffer per currently visible remote player.
     pub player_label_buffers: Vec<glyphon::Buffer>,
+    /// Chat log and input line shown in the bottom-left corner.
+    pub chat_buffer: glyphon::Buffer,
 
     // -------------------------------------------------------------------------
     // UI / game state
@@ -377,10 +625,35 @@ pub struct State {
     pub game_state: GameState,
     /// Tracks focus / edit state of individual menu widgets.
     pub menu_state: MenuState,
+    /// Key bound to each remappable in-game action; loaded at startup.
+    pub key_bindings: KeyBindings,
+    /// Action waiting for its new key after its row on the controls page
+    /// was clicked.  The next key press is bound to it.
+    pub pending_rebind: Option<Action>,
+    /// Chat box input line and message log; also the command line.
+    pub chat: ChatState,
     /// `1.0` when the camera eye is inside a water block; `0.0` otherwise.
     /// Passed to the composite shader to apply the underwater color tint.
     pub is_underwater: f32,
 
+    // -------------------------------------------------------------------------
+    // World editing (chat commands)
+    // -------------------------------------------------------------------------
+    /// `/fill` and `/replace` edits waiting to be applied, oldest first.
+    /// Only the front edit advances each frame, by a fixed block budget.
+    pub pending_box_edits: VecDeque<BoxEdit>,
+    /// Corners of the most recent `/fill`, used as the region for `/replace`.
+    pub last_fill_region: Option<[(i32, i32, i32); 2]>,
+    /// Undo/redo stacks covering manual placing/breaking and chat-command
+    /// edits made by the local player.
+    pub edit_history: EditHistory,
+    /// Single-block changes from the local player and the server, applied
+    /// in order under one write lock during [`State::update`].
+    pub block_mutations: BlockMutationQueue,
+    /// Block changes received from the server for chunks that were not
+    /// loaded yet, keyed by chunk column and applied when the chunk arrives.
+    pub deferred_block_changes: DeferredBlockChanges,
+
     // -------------------------------------------------------------------------
     // Multiplayer
     // -------------------------------------------------------------------------
@@ -390,6 +663,11 @@ pub struct State {
     pub my_player_id: u32,
     /// `Instant` of the last position packet sent to the server.
     pub last_position_send: Instant,
+    /// Block and crack stage last reported in a `DiggingProgress` packet,
+    /// or `None` once stopping was reported.
+    pub last_dig_sent: Option<((i32, i32, i32), u8)>,
+    /// `Instant` of the last `DiggingProgress` packet sent to the server.
+    pub last_dig_send: Instant,
     /// Tokio async runtime used by the network thread (kept alive here).
     pub network_runtime: Option<tokio::runtime::Runtime>,
     /// Receives decoded packets forwarded from the network thread.
@@ -400,7 +678,8 @@ pub struct State {
     // -------------------------------------------------------------------------
     // Remote player model geometry
     // -------------------------------------------------------------------------
-    /// Vertex buffer containing the combined geometry for all remote player models.
+    /// Vertex buffer containing the combined geometry for all remote player
+    /// and mob models.
     pub player_model_vertex_buffer: Option<wgpu::Buffer>,
     /// Index buffer for the combined remote player model geometry.
     pub player_model_index_buffer: Option<wgpu::Buffer>,
@@ -411,6 +690,14 @@ pub struct State {
     pub player_model_vertex_capacity: u32,
     /// Allocated capacity of `player_model_index_buffer` in indices.
     pub player_model_index_capacity: u32,
+    /// Number of remote players, mobs and debris cubes whose indices are
+    /// currently in `player_model_index_buffer`.  The index pattern only
+    /// depends on these counts, so it is re-uploaded only when they change.
+    pub player_model_index_counts: (usize, usize, usize),
+    /// CPU-side vertex scratch buffer, reused every frame to avoid allocating.
+    pub player_model_vertices: Vec<Vertex>,
+    /// CPU-side index scratch buffer, reused every frame to avoid allocating.
+    pub player_model_indices: Vec<u32>,
 }
 
 /// A lightweight, read-only snapshot of world state collected under the read lock.
@@ -421,13 +708,16 @@ pub struct WorldSnapshot {
     /// Chunks within `GENERATION_DISTANCE` that are not yet loaded or pending.
     /// Each entry is `(chunk_x, chunk_z, squared_distance_priority)`.
     pub missing_chunks: Vec<(i32, i32, i32)>,
-    /// Result of the block raycast: `(hit_x, hit_y, hit_z, face_nx, face_ny, face_nz)`,
-    /// or `None` if the ray missed or no mouse button is held.
-    pub raycast_result: Option<(i32, i32, i32, i32, i32, i32)>,
+    /// Chunks within `GENERATION_DISTANCE` that are already loaded.
+    pub loaded_chunks: usize,
+    /// Block and face under the crosshair, or `None` if the ray missed or the
+    /// cursor is not captured.
+    pub raycast_result: Option<RaycastHit>,
     /// Block type at the raycasted position, or `None` if the ray missed.
     pub target_block: Option<minerust::BlockType>,
-    /// Block type at the camera eye position (used for the underwater effect).
-    pub eye_block: minerust::BlockType,
+    /// Whether the camera eye is below a water surface (used for the
+    /// underwater effect).
+    pub eye_underwater: bool,
 }
 
 /// Batches all world mutations that must occur under the write lock in one frame.
@@ -439,9 +729,4 @@ pub struct WorldWriteOps {
     /// Newly generated chunks ready to be inserted into the world map.
     /// Each entry is `(chunk_x, chunk_z, chunk_data)`.
     pub completed_chunks: Vec<(i32, i32, minerust::Chunk)>,
-    /// Block coordinates to replace with `Air` this frame (player broke a block).
-    pub block_break: Option<(i32, i32, i32)>,
-    /// Block coordinates whose owning subchunk (and its neighbors) should be
-    /// marked dirty for re-meshing.
-    pub mark_dirty: Vec<(i32, i32, i32)>,
 }
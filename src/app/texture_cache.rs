@@ -1,8 +1,13 @@
 use std::fs;
 use std::path::Path;
+use std::thread;
+
+use crossbeam_channel::{Receiver, TryRecvError, bounded};
 
 use crate::logger::{LogLevel, log};
-use minerust::{TEXTURE_SIZE, generate_texture_atlas, load_texture_atlas_from_file};
+use minerust::{ATLAS_LAYERS, TEXTURE_SIZE, generate_texture_atlas, load_texture_atlas_from_file};
+
+use super::state::State;
 
 /// Manages a file-based cache for the texture atlas binary data.
 ///
@@ -32,9 +37,14 @@ impl TextureAtlasCache {
 
     /// Attempts to read and return the raw bytes stored in the cache file.
     ///
-    /// Returns `Some(bytes)` on success, or `None` if the file cannot be read.
+    /// Returns `Some(bytes)` on success, or `None` if the file cannot be read
+    /// or does not hold exactly [`ATLAS_LAYERS`] layers of [`TEXTURE_SIZE`]²
+    /// texels (e.g. a cache written before a layer was added).
     fn load(&self) -> Option<Vec<u8>> {
-        fs::read(&self.cache_path).ok()
+        let expected = (TEXTURE_SIZE * TEXTURE_SIZE * 4 * ATLAS_LAYERS) as usize;
+        fs::read(&self.cache_path)
+            .ok()
+            .filter(|data| data.len() == expected)
     }
 }
 
@@ -42,12 +52,13 @@ impl TextureAtlasCache {
 ///
 /// Each mip level is half the size of the previous level in both dimensions
 /// (clamped to a minimum of 1×1). The input atlas is assumed to consist of
-/// `16` array layers packed contiguously in memory (RGBA8, 4 bytes per texel).
+/// [`ATLAS_LAYERS`] array layers packed contiguously in memory (RGBA8, 4
+/// bytes per texel).
 /// Downsampling uses a bilinear (Triangle) filter.
 ///
 /// # Arguments
 ///
-/// * `atlas_data`   - Raw RGBA8 pixel data for all 16 layers at mip level 0.
+/// * `atlas_data`   - Raw RGBA8 pixel data for all layers at mip level 0.
 /// * `atlas_width`  - Width of a single layer in texels.
 /// * `atlas_height` - Height of a single layer in texels.
 ///
@@ -74,10 +85,11 @@ pub fn generate_texture_atlas_with_mipmaps(
         let dst_width = (atlas_width >> level).max(1);
         let dst_height = (atlas_height >> level).max(1);
 
-        let mut level_data = Vec::with_capacity((dst_width * dst_height * 4 * 16) as usize);
+        let mut level_data =
+            Vec::with_capacity((dst_width * dst_height * 4 * ATLAS_LAYERS) as usize);
 
-        // Downsample each of the 16 array layers independently.
-        for layer in 0..16 {
+        // Downsample each array layer independently.
+        for layer in 0..ATLAS_LAYERS as usize {
             let layer_size = (src_width * src_height * 4) as usize;
             let layer_offset = layer * layer_size;
             let src_data = &mip_levels[src_level as usize];
@@ -101,39 +113,156 @@ pub fn generate_texture_atlas_with_mipmaps(
     mip_levels
 }
 
-/// Uploads a texture atlas (with auto-generated mipmaps) to the GPU.
-///
-/// Creates a [`wgpu::Texture`] with format [`wgpu::TextureFormat::Rgba8UnormSrgb`],
-/// `16` array layers, and a full mipmap chain. All mip levels are written to the
-/// GPU via [`wgpu::Queue::write_texture`].
-///
-/// # Arguments
-///
-/// * `device`       - The wgpu device used to allocate the texture.
-/// * `queue`        - The wgpu queue used to upload pixel data.
-/// * `atlas_data`   - Raw RGBA8 pixel data for all 16 layers at mip level 0.
-/// * `atlas_width`  - Width of the atlas in texels.
-/// * `atlas_height` - Height of the atlas in texels.
+/// A texture atlas with its full mip chain, ready to upload.
 ///
-/// # Returns
+/// Built off the main thread by [`AtlasLoader`], since decoding and
+/// downsampling every layer takes long enough to stall the window.
+pub struct AtlasData {
+    /// Width of one layer at mip level 0, in texels.
+    pub width: u32,
+    /// Height of one layer at mip level 0, in texels.
+    pub height: u32,
+    /// RGBA8 bytes of all layers per mip level, from
+    /// [`generate_texture_atlas_with_mipmaps`].
+    pub mip_levels: Vec<Vec<u8>>,
+}
+
+impl AtlasData {
+    /// Loads or generates the atlas pixels and builds their mip chain.
+    ///
+    /// Resolution order for the atlas source data:
+    ///
+    /// 1. **Disk cache** (`assets/texture_atlas.cache`) — raw bytes written by a
+    ///    previous run; fastest path, skips all image decoding.
+    /// 2. **PNG file** (`assets/textures.png`) — decoded on first run and used
+    ///    directly; dimensions are read from the file.
+    /// 3. **Procedural generation** — fallback when neither asset is available;
+    ///    produces a [`TEXTURE_SIZE`]×[`TEXTURE_SIZE`] atlas via [`generate_texture_atlas`].
+    fn load() -> Self {
+        let cache = TextureAtlasCache::new("assets/texture_atlas.cache");
+
+        let (atlas_data, atlas_width, atlas_height) = if cache.exists() {
+            match cache.load() {
+                Some(cached_data) => {
+                    log(
+                        LogLevel::Info,
+                        &format!(
+                            "Loaded texture atlas from cache ({} bytes)",
+                            cached_data.len()
+                        ),
+                    );
+                    (cached_data, TEXTURE_SIZE, TEXTURE_SIZE)
+                }
+                None => {
+                    // Cache file exists but could not be read; fall back to generation.
+                    let data = generate_texture_atlas();
+                    (data, TEXTURE_SIZE, TEXTURE_SIZE)
+                }
+            }
+        } else {
+            match load_texture_atlas_from_file("assets/textures.png") {
+                Ok((data, width, height)) => {
+                    log(
+                        LogLevel::Info,
+                        &format!(
+                            "Loaded texture atlas from PNG ({} bytes, {}x{})",
+                            data.len(),
+                            width,
+                            height
+                        ),
+                    );
+                    (data, width, height)
+                }
+                Err(e) => {
+                    log(
+                        LogLevel::Warning,
+                        &format!(
+                            "Failed to load texture atlas from PNG: {}; falling back to procedural generation",
+                            e
+                        ),
+                    );
+                    let data = generate_texture_atlas();
+                    (data, TEXTURE_SIZE, TEXTURE_SIZE)
+                }
+            }
+        };
+
+        Self::with_mipmaps(&atlas_data, atlas_width, atlas_height)
+    }
+
+    fn with_mipmaps(atlas_data: &[u8], width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            mip_levels: generate_texture_atlas_with_mipmaps(atlas_data, width, height),
+        }
+    }
+}
+
+/// Loads the texture atlas on a background thread.
 ///
-/// A tuple of `(texture, view)` where `view` is a [`wgpu::TextureViewDimension::D2Array`]
-/// view suitable for use in shaders as a `texture2d_array`.
-pub fn create_texture_atlas_optimized(
+/// The thread runs [`AtlasData::load`] once and hands the result back over a
+/// one-slot channel, which the main thread polls every frame with
+/// [`AtlasLoader::poll`] until the atlas arrives.
+pub struct AtlasLoader {
+    result_rx: Receiver<AtlasData>,
+}
+
+impl AtlasLoader {
+    /// Starts loading the atlas.
+    ///
+    /// # Panics
+    /// Panics if the loader thread cannot be spawned.
+    pub fn spawn() -> Self {
+        let (result_tx, result_rx) = bounded::<AtlasData>(1);
+        thread::Builder::new()
+            .name("atlas-loader".to_string())
+            .spawn(move || {
+                // The receiver is gone if the window closed during loading.
+                let _ = result_tx.send(AtlasData::load());
+            })
+            .expect("Failed to spawn texture atlas loader");
+        Self { result_rx }
+    }
+
+    /// Returns the atlas once it is ready, without blocking.
+    ///
+    /// If the loader thread died without sending one (e.g. a panic while
+    /// decoding), the procedural atlas is generated here instead so the
+    /// game never waits forever.
+    pub fn poll(&self) -> Option<AtlasData> {
+        match self.result_rx.try_recv() {
+            Ok(atlas) => Some(atlas),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                log(
+                    LogLevel::Error,
+                    "Texture atlas loader stopped without a result; using the procedural atlas",
+                );
+                Some(AtlasData::with_mipmaps(
+                    &generate_texture_atlas(),
+                    TEXTURE_SIZE,
+                    TEXTURE_SIZE,
+                ))
+            }
+        }
+    }
+}
+
+/// Creates the [`ATLAS_LAYERS`]-layer `D2Array` texture and view for an atlas of
+/// `width × height` texels with `mip_level_count` levels.
+fn create_atlas_texture(
     device: &wgpu::Device,
-    queue: &wgpu::Queue,
-    atlas_data: &[u8],
-    atlas_width: u32,
-    atlas_height: u32,
+    width: u32,
+    height: u32,
+    mip_level_count: u32,
 ) -> (wgpu::Texture, wgpu::TextureView) {
-    let mip_level_count = (atlas_width.max(atlas_height) as f32).log2().floor() as u32 + 1;
-
     let texture = device.create_texture(&wgpu::TextureDescriptor {
         label: Some("Texture Atlas"),
         size: wgpu::Extent3d {
-            width: atlas_width,
-            height: atlas_height,
-            depth_or_array_layers: 16,
+            width,
+            height,
+            depth_or_array_layers: ATLAS_LAYERS,
         },
         mip_level_count,
         sample_count: 1,
@@ -142,14 +271,79 @@ pub fn create_texture_atlas_optimized(
         usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
         view_formats: &[],
     });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor {
+        label: Some("Texture Atlas View"),
+        dimension: Some(wgpu::TextureViewDimension::D2Array),
+        ..Default::default()
+    });
+    (texture, view)
+}
+
+/// Creates a 1×1 mid-grey stand-in for the atlas.
+///
+/// The bind groups need some texture in the atlas slot while [`AtlasLoader`]
+/// is still working; nothing is drawn with it, since the loading screen is
+/// shown until the real atlas replaces it.
+pub fn create_placeholder_atlas(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let (texture, view) = create_atlas_texture(device, 1, 1, 1);
+    queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        &[128u8; 4 * ATLAS_LAYERS as usize],
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(4),
+            rows_per_image: Some(1),
+        },
+        wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: ATLAS_LAYERS,
+        },
+    );
+    (texture, view)
+}
 
-    let mip_levels = generate_texture_atlas_with_mipmaps(atlas_data, atlas_width, atlas_height);
+/// Uploads a texture atlas and its mip chain to the GPU.
+///
+/// Creates a [`wgpu::Texture`] with format [`wgpu::TextureFormat::Rgba8UnormSrgb`],
+/// [`ATLAS_LAYERS`] array layers, and one mip level per entry of `atlas.mip_levels`. All
+/// mip levels are written to the GPU via [`wgpu::Queue::write_texture`].
+///
+/// # Arguments
+///
+/// * `device` - The wgpu device used to allocate the texture.
+/// * `queue`  - The wgpu queue used to upload pixel data.
+/// * `atlas`  - Pixel data with mipmaps, built by [`AtlasLoader`].
+///
+/// # Returns
+///
+/// A tuple of `(texture, view)` where `view` is a [`wgpu::TextureViewDimension::D2Array`]
+/// view suitable for use in shaders as a `texture2d_array`.
+pub fn create_texture_atlas_optimized(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    atlas: &AtlasData,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let (texture, view) = create_atlas_texture(
+        device,
+        atlas.width,
+        atlas.height,
+        atlas.mip_levels.len() as u32,
+    );
 
-    // Upload each mip level. All 16 layers are packed in a single write_texture
-    // call per level by setting depth_or_array_layers to 16.
-    for (level, level_data) in mip_levels.iter().enumerate() {
-        let mip_width = (atlas_width >> level).max(1);
-        let mip_height = (atlas_height >> level).max(1);
+    // Upload each mip level. All layers are packed in a single write_texture
+    // call per level by setting depth_or_array_layers to ATLAS_LAYERS.
+    for (level, level_data) in atlas.mip_levels.iter().enumerate() {
+        let mip_width = (atlas.width >> level).max(1);
+        let mip_height = (atlas.height >> level).max(1);
 
         queue.write_texture(
             wgpu::TexelCopyTextureInfo {
@@ -167,99 +361,38 @@ pub fn create_texture_atlas_optimized(
             wgpu::Extent3d {
                 width: mip_width,
                 height: mip_height,
-                depth_or_array_layers: 16,
+                depth_or_array_layers: ATLAS_LAYERS,
             },
         );
     }
 
-    let view = texture.create_view(&wgpu::TextureViewDescriptor {
-        label: Some("Texture Atlas View"),
-        dimension: Some(wgpu::TextureViewDimension::D2Array),
-        ..Default::default()
-    });
     (texture, view)
 }
 
-/// Loads or generates the texture atlas, then uploads it to the GPU.
-///
-/// Resolution order for the atlas source data:
-///
-/// 1. **Disk cache** (`assets/texture_atlas.cache`) — raw bytes written by a
-///    previous run; fastest path, skips all image decoding.
-/// 2. **PNG file** (`assets/textures.png`) — decoded on first run and used
-///    directly; dimensions are read from the file.
-/// 3. **Procedural generation** — fallback when neither asset is available;
-///    produces a [`TEXTURE_SIZE`]×[`TEXTURE_SIZE`] atlas via [`generate_texture_atlas`].
-///
-/// After acquiring the raw pixel data the function calls
-/// [`create_texture_atlas_optimized`] to build the GPU texture with mipmaps.
-///
-/// # Arguments
-///
-/// * `device` - The wgpu device used to allocate GPU resources.
-/// * `queue`  - The wgpu queue used to upload pixel data.
-///
-/// # Returns
-///
-/// A tuple of `(texture, view, width, height)`:
-/// - `texture` — the allocated GPU texture.
-/// - `view`    — a `D2Array` texture view ready for binding in shaders.
-/// - `width`   — atlas width in texels.
-/// - `height`  — atlas height in texels.
-pub fn load_or_generate_atlas(
-    device: &wgpu::Device,
-    queue: &wgpu::Queue,
-) -> (wgpu::Texture, wgpu::TextureView, u32, u32) {
-    let cache = TextureAtlasCache::new("assets/texture_atlas.cache");
-
-    let (atlas_data, atlas_width, atlas_height) = if cache.exists() {
-        match cache.load() {
-            Some(cached_data) => {
-                log(
-                    LogLevel::Info,
-                    &format!(
-                        "Loaded texture atlas from cache ({} bytes)",
-                        cached_data.len()
-                    ),
-                );
-                (cached_data, TEXTURE_SIZE, TEXTURE_SIZE)
-            }
-            None => {
-                // Cache file exists but could not be read; fall back to generation.
-                let data = generate_texture_atlas();
-                (data, TEXTURE_SIZE, TEXTURE_SIZE)
-            }
-        }
-    } else {
-        match load_texture_atlas_from_file("assets/textures.png") {
-            Ok((data, width, height)) => {
-                log(
-                    LogLevel::Info,
-                    &format!(
-                        "Loaded texture atlas from PNG ({} bytes, {}x{})",
-                        data.len(),
-                        width,
-                        height
-                    ),
-                );
-                (data, width, height)
-            }
-            Err(e) => {
-                log(
-                    LogLevel::Warning,
-                    &format!(
-                        "Failed to load texture atlas from PNG: {}; falling back to procedural generation",
-                        e
-                    ),
-                );
-                let data = generate_texture_atlas();
-                (data, TEXTURE_SIZE, TEXTURE_SIZE)
-            }
-        }
-    };
-
-    let (texture, view) =
-        create_texture_atlas_optimized(device, queue, &atlas_data, atlas_width, atlas_height);
-
-    (texture, view, atlas_width, atlas_height)
+impl State {
+    /// Swaps in the texture atlas once [`AtlasLoader`] has finished.
+    ///
+    /// Uploads the atlas, replaces the placeholder, and rebuilds
+    /// `uniform_bind_group` and `water_bind_group`, which hold the atlas
+    /// view.  Ends the loading screen.  Does nothing once the atlas is in.
+    pub fn poll_texture_atlas(&mut self) {
+        let Some(atlas) = self.atlas_loader.as_ref().and_then(AtlasLoader::poll) else {
+            return;
+        };
+        let (texture, view) = create_texture_atlas_optimized(&self.device, &self.queue, &atlas);
+        self.texture_atlas = texture;
+        self.texture_view = view;
+        self.uniform_bind_group = self.create_uniform_bind_group();
+        self.water_bind_group = self.create_water_bind_group();
+        self.atlas_loader = None;
+        log(
+            LogLevel::Info,
+            &format!(
+                "Texture atlas ready ({}x{}, {} mip levels)",
+                atlas.width,
+                atlas.height,
+                atlas.mip_levels.len()
+            ),
+        );
+    }
 }
@@ -0,0 +1,106 @@
+use minerust::{DAY_LENGTH, time_difference};
+
+use super::state::State;
+
+/// Real seconds over which most of a difference to the server's clock is
+/// made up; after this long about two thirds of it are.
+const SLEW_TIME: f32 = 2.0;
+
+/// Difference to the server's clock, in day-cycle seconds, beyond which the
+/// clock jumps to the server's reading instead of easing towards it, as on
+/// joining a server.
+const SNAP_DIFFERENCE: f32 = DAY_LENGTH / 16.0;
+
+/// How far the day-cycle clock is behind the server's, for a client easing
+/// it into step with the server's `TimeSync` broadcasts.
+///
+/// Small differences are made up gradually, so the sun drifts into place
+/// instead of jumping; the local clock keeps running meanwhile.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClockSync {
+    /// Day-cycle seconds still to add; negative when the clock is ahead.
+    behind: f32,
+}
+
+impl ClockSync {
+    /// Records that the server's clock read `server_time` while the local
+    /// one read `local_time`.  The difference is taken the short way round
+    /// the day, as the server keeps its clock within one day while the
+    /// local one keeps counting.
+    ///
+    /// # Returns
+    /// The jump to apply to the local clock at once: the whole difference
+    /// if it is over [`SNAP_DIFFERENCE`], otherwise `0.0` and the
+    /// difference is made up by [`ClockSync::step`].
+    pub fn sync(&mut self, server_time: f32, local_time: f32) -> f32 {
+        let difference = time_difference(local_time, server_time);
+        if difference.abs() > SNAP_DIFFERENCE {
+            self.behind = 0.0;
+            difference
+        } else {
+            self.behind = difference;
+            0.0
+        }
+    }
+
+    /// Returns the part of the difference to make up over `dt` real
+    /// seconds.
+    pub fn step(&mut self, dt: f32) -> f32 {
+        let step = self.behind * (1.0 - (-dt / SLEW_TIME).exp());
+        self.behind -= step;
+        step
+    }
+}
+
+impl State {
+    /// Takes the server's day-cycle clock reading from a `TimeSync` and
+    /// starts easing `time_of_day` towards it.
+    ///
+    /// Ignored while the night is being skipped; the server's clock is
+    /// already at the morning the skip runs to.
+    pub fn sync_time(&mut self, server_time: f32) {
+        if self.night_skip.is_some() {
+            return;
+        }
+        self.time_of_day += self.clock_sync.sync(server_time, self.time_of_day);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clocks_ease_towards_the_server_and_jump_when_far_off() {
+        // Two clients a few seconds either side of the server converge on
+        // its clock without jumping.
+        let server = 100.0;
+        let mut clocks = [(ClockSync::default(), 96.0), (ClockSync::default(), 103.0)];
+        for (sync, time) in &mut clocks {
+            assert_eq!(sync.sync(server, *time), 0.0);
+        }
+        let mut elapsed = 0.0;
+        for _ in 0..900 {
+            let dt = 1.0 / 60.0;
+            elapsed += dt;
+            for (sync, time) in &mut clocks {
+                let step = sync.step(dt);
+                assert!(step.abs() < 0.1);
+                *time += dt + step;
+            }
+        }
+        for (_, time) in clocks {
+            assert!((time - (server + elapsed)).abs() < 0.01, "{}", time);
+        }
+
+        // A server clock that wrapped round to the next day is no different.
+        let mut sync = ClockSync::default();
+        assert_eq!(sync.sync(2.0, 3.0 * DAY_LENGTH - 2.0), 0.0);
+        assert!(sync.step(100.0) > 3.9);
+
+        let mut sync = ClockSync::default();
+        let jump = sync.sync(server, server + DAY_LENGTH / 2.0 - 1.0);
+        assert!((jump + DAY_LENGTH / 2.0 - 1.0).abs() < 1e-2);
+        assert_eq!(sync.step(1.0), 0.0);
+    }
+}
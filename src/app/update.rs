@@ -1,32 +1,147 @@
 use std::time::Instant;
 
+use glam::Vec3;
+use glyphon::{Attrs, Color, Family, Shaping};
 use minerust::{
-    BlockType, CHUNK_SIZE, GENERATION_DISTANCE, MAX_CHUNKS_PER_FRAME, MAX_MESH_BUILDS_PER_FRAME,
-    NUM_SUBCHUNKS, SUBCHUNK_HEIGHT,
+    BlockType, CHUNK_SIZE, CREATIVE_BREAK_INTERVAL, DIG_PROGRESS_SEND_INTERVAL, Facing,
+    GENERATION_DISTANCE, MAX_CHUNKS_PER_FRAME, MAX_MESH_BUILDS_PER_FRAME,
+    MESH_UPLOAD_BYTES_PER_FRAME, NUM_SUBCHUNKS, RANDOM_TICK_INTERVAL, SIMULATION_DISTANCE,
+    WATER_TICK_INTERVAL, sun_direction,
 };
 
 use crate::multiplayer::network::update_network;
+use crate::multiplayer::player::roster;
 use crate::ui;
 
+use super::atmosphere::Atmosphere;
+#[cfg(feature = "audio")]
+use super::audio::BlockSound;
+use super::profiler::ProfileScope;
 use super::state::{State, WorldSnapshot, WorldWriteOps};
 
+/// Cosine of half the angle of the cone, around the horizontal look
+/// direction, whose chunks are generated before those behind the player.
+/// 60° either side covers the horizontal field of view at common aspect
+/// ratios with a little to spare for turning.
+const FACING_CONE_COS: f32 = 0.5;
+
+/// Added to the priority of chunks outside the facing cone.  Larger than
+/// any squared chunk distance, so every chunk ahead comes first.
+const BEHIND_PRIORITY: i32 = 1 << 16;
+
+/// Returns the generation priority of the chunk `(dx, dz)` chunks away from
+/// the player's, who faces the horizontal unit vector `forward`; lower is
+/// more urgent.
+///
+/// Chunks in the facing cone come before the ones behind, and nearer chunks
+/// before farther ones within each group.  The 3×3 chunks around the player
+/// always count as ahead, since physics waits for them.
+fn chunk_request_priority(dx: i32, dz: i32, forward: Vec3) -> i32 {
+    let distance_sq = dx * dx + dz * dz;
+    if distance_sq <= 2 {
+        return distance_sq;
+    }
+    let facing = (dx as f32 * forward.x + dz as f32 * forward.z) / (distance_sq as f32).sqrt();
+    if facing >= FACING_CONE_COS {
+        distance_sq
+    } else {
+        BEHIND_PRIORITY + distance_sq
+    }
+}
+
 impl State {
-    /// Rebuilds the on-screen coordinate HUD if the camera has moved since the
-    /// last update.
+    /// Refreshes the coordinate readout in the top-right corner.
     ///
-    /// When the player position changes, new vertex and index buffers are
-    /// generated and stored on `self` so the next render pass picks them up.
-    /// Does nothing if the position is unchanged.
+    /// The text is re-shaped and re-measured only when it changes, i.e. when
+    /// the camera enters a new block or turns to face another direction.
     pub fn update_coords_ui(&mut self) {
-        if let Some((vb, ib, num_indices)) = ui::ui::update_coords_ui(
-            &self.device,
-            self.camera.position,
-            &mut self.last_coords_position,
-        ) {
-            self.coords_vertex_buffer = Some(vb);
-            self.coords_index_buffer = Some(ib);
-            self.coords_num_indices = num_indices;
+        let position = self.camera.position;
+        let biome = self
+            .world
+            .read()
+            .get_biome(position.x.floor() as i32, position.z.floor() as i32);
+        let text = ui::ui::coords_text(position, self.camera.yaw, biome);
+        if text == self.coords_text {
+            return;
+        }
+
+        self.coords_buffer.set_text(
+            &mut self.font_system,
+            &text,
+            &Attrs::new().family(Family::SansSerif),
+            Shaping::Advanced,
+            None,
+        );
+        self.coords_buffer.set_size(
+            &mut self.font_system,
+            Some(self.config.width as f32),
+            Some(self.config.height as f32),
+        );
+        self.coords_width = self
+            .coords_buffer
+            .layout_runs()
+            .map(|run| run.line_w)
+            .fold(0.0, f32::max);
+        self.coords_text = text;
+    }
+
+    /// Blends the [`Atmosphere`] towards that of the biome under the player
+    /// over a frame of `dt` seconds.
+    pub fn update_atmosphere(&mut self, dt: f32) {
+        let position = self.camera.position;
+        let biome = self
+            .world
+            .read()
+            .get_biome(position.x.floor() as i32, position.z.floor() as i32);
+        self.atmosphere.blend_towards(Atmosphere::of(biome), dt);
+    }
+
+    /// Refreshes the player list shown while its key is held.
+    ///
+    /// One line per player in their nametag color, below a count of everyone
+    /// online.  Re-shaped only when someone joins, leaves or is renamed, or
+    /// per-player colors are toggled.
+    pub fn update_player_list_ui(&mut self) {
+        let entries = roster(
+            &self.remote_players,
+            self.my_player_id,
+            self.menu_state.username.trim(),
+            self.player_colors,
+        );
+        if entries == self.player_list_entries {
+            return;
         }
+
+        let header = format!("Players online: {}", entries.len());
+        let lines: Vec<String> = entries
+            .iter()
+            .map(|(name, _)| format!("\n{}", name))
+            .collect();
+        let attrs = Attrs::new().family(Family::SansSerif);
+        let spans = std::iter::once((header.as_str(), attrs.clone())).chain(
+            lines.iter().zip(&entries).map(|(line, (_, color))| {
+                let [r, g, b] = color.map(|c| (c * 255.0).round() as u8);
+                (line.as_str(), attrs.clone().color(Color::rgb(r, g, b)))
+            }),
+        );
+        self.player_list_buffer.set_rich_text(
+            &mut self.font_system,
+            spans,
+            &attrs,
+            Shaping::Advanced,
+            None,
+        );
+        self.player_list_buffer.set_size(
+            &mut self.font_system,
+            Some(self.config.width as f32),
+            Some(self.config.height as f32),
+        );
+        self.player_list_width = self
+            .player_list_buffer
+            .layout_runs()
+            .map(|run| run.line_w)
+            .fold(0.0, f32::max);
+        self.player_list_entries = entries;
     }
 
     /// Applies a completed mesh result to the GPU indirect draw buffers.
@@ -34,9 +149,10 @@ impl State {
     /// Called after a background mesh worker finishes building a subchunk.
     /// The method:
     /// 1. Updates the subchunk's index counts and clears its `mesh_dirty` flag
-    ///    under a brief write lock.
-    /// 2. Uploads the terrain and water meshes to the respective
-    ///    `IndirectManager` instances.
+    ///    under a brief write lock, unless the subchunk changed again after
+    ///    the worker read it (see `MeshResult::mesh_version`).
+    /// 2. Uploads the terrain mesh (opaque and translucent parts) and the
+    ///    water mesh to the respective `IndirectManager` instances.
     /// 3. If either upload fails (buffer full), marks the subchunk dirty again
     ///    so it will be retried on the next frame.
     ///
@@ -57,9 +173,9 @@ impl State {
             };
             let subchunk = &mut chunk.subchunks[sy as usize];
             let aabb = subchunk.aabb;
-            subchunk.num_indices = result.terrain.1.len() as u32;
+            subchunk.num_indices = (result.terrain.1.len() + result.translucent.1.len()) as u32;
             subchunk.num_water_indices = result.water.1.len() as u32;
-            subchunk.mesh_dirty = false;
+            subchunk.clear_mesh_dirty(result.mesh_version);
             aabb
         };
 
@@ -72,16 +188,16 @@ impl State {
         let terrain_uploaded = self.indirect_manager.upload_subchunk(
             &self.queue,
             key,
-            &result.terrain.0,
-            &result.terrain.1,
+            (&result.terrain.0, &result.terrain.1),
+            (&result.translucent.0, &result.translucent.1),
             &aabb_copy,
         );
 
         let water_uploaded = self.water_indirect_manager.upload_subchunk(
             &self.queue,
             key,
-            &result.water.0,
-            &result.water.1,
+            (&result.water.0, &result.water.1),
+            (&[], &[]),
             &aabb_copy,
         );
 
@@ -90,7 +206,7 @@ impl State {
         if !terrain_uploaded || !water_uploaded {
             let mut world = self.world.write();
             if let Some(chunk) = world.chunks.get_mut(&(cx, cz)) {
-                chunk.subchunks[sy as usize].mesh_dirty = true;
+                chunk.subchunks[sy as usize].mark_mesh_dirty();
             }
         }
     }
@@ -102,22 +218,46 @@ impl State {
     /// order to minimize the time the world write-lock is held:
     ///
     /// 1. **Network** – flush incoming packets and send position updates.
-    /// 2. **Delta time** – compute `dt`, clamped to 100 ms to survive hitches.
+    /// 2. **Delta time** – compute `dt`, clamped to 100 ms to survive hitches,
+    ///    and advance the day-cycle clock by it.
     /// 3. **Chunk streaming** – poll completed chunk generation results and
-    ///    determine which chunks are still missing within `GENERATION_DISTANCE`.
+    ///    determine which chunks are still missing within `GENERATION_DISTANCE`,
+    ///    counting the loaded ones for the loading readout.
     /// 4. **Read-locked snapshot** – run camera physics and collect all
-    ///    read-only world queries (raycast, eye-block check) in one pass to
-    ///    avoid repeated lock acquisitions.
-    /// 5. **Chunk requests** – sort missing chunks by squared distance and
-    ///    submit up to `MAX_CHUNKS_PER_FRAME * 2` requests to the loader.
-    /// 6. **Digging** – accumulate break progress for the targeted block.
-    /// 7. **World write** – insert newly generated chunks, break blocks, and
-    ///    evict out-of-range chunks (all in a single write-lock window).
-    /// 8. **Mesh uploads** – drain up to `MAX_MESH_BUILDS_PER_FRAME` completed
-    ///    mesh results from the background workers.
+    ///    read-only world queries (raycast, underwater check) in one pass to
+    ///    avoid repeated lock acquisitions, then derive the placement preview
+    ///    from the raycast.
+    /// 5. **Chunk requests** – sort missing chunks ahead of the player before
+    ///    those behind, then by squared distance, and submit up to
+    ///    `MAX_CHUNKS_PER_FRAME * 2` requests to the loader.
+    /// 6. **Digging** – accumulate break progress for the targeted block and
+    ///    queue the break in `block_mutations`.  With the `audio` feature,
+    ///    also play the break sound and any footstep that is due.
+    /// 7. **World write** – insert newly generated chunks, apply every queued
+    ///    local and remote block change in order, mark the touched subchunks
+    ///    dirty, evict out-of-range chunks and pick each column's mesh LOD
+    ///    (all in a single write-lock window), then apply the next slice of
+    ///    any pending `/fill` or `/replace` edit.
+    /// 8. **Mobs** – spawn, move and despawn mobs around all players.
+    /// 9. **Water and random ticks** – every `WATER_TICK_INTERVAL` seconds,
+    ///    let queued water flow one step; every `RANDOM_TICK_INTERVAL`
+    ///    seconds, random-tick the chunks within `SIMULATION_DISTANCE`.
+    /// 10. **Mesh uploads** – move up to `MAX_MESH_BUILDS_PER_FRAME` completed
+    ///     mesh results from the background workers into `mesh_uploads`, then
+    ///     upload the nearest ones within `MESH_UPLOAD_BYTES_PER_FRAME`.
+    /// 11. **Minimap** – when shown, sample chunks that were loaded or changed
+    ///     into the minimap texture.
+    ///
+    /// Before any of this, the texture atlas is swapped in once its
+    /// background load has finished (see `poll_texture_atlas`).
     pub fn update(&mut self) {
+        self.poll_texture_atlas();
+        self.update_autosave();
+
         // --- 1. Network ---
+        let scope = ProfileScope::new("network");
         self.update_network_state();
+        drop(scope);
 
         // --- 2. Delta time ---
         let now = Instant::now();
@@ -125,9 +265,13 @@ impl State {
         // tunnel through terrain or fly out of bounds.
         let dt = now.duration_since(self.last_frame).as_secs_f32().min(0.1);
         self.last_frame = now;
+        self.advance_time(dt);
+        self.update_atmosphere(dt);
 
         // --- 3. Chunk streaming ---
+        let scope = ProfileScope::new("chunk poll");
         let completed_chunks = self.chunk_loader.poll_results(MAX_CHUNKS_PER_FRAME);
+        drop(scope);
 
         let player_cx = (self.camera.position.x / CHUNK_SIZE as f32).floor() as i32;
         let player_cz = (self.camera.position.z / CHUNK_SIZE as f32).floor() as i32;
@@ -138,6 +282,7 @@ impl State {
         // Acquire the read lock once and do all read-only queries inside a
         // single block so the lock is held for the shortest possible time.
         let snapshot = {
+            let _scope = ProfileScope::new("physics & queries");
             let world = self.world.read();
 
             // Only step physics if the 3x3 chunk area around the player is loaded.
@@ -152,29 +297,31 @@ impl State {
                         break;
                     }
                 }
-                if !chunks_loaded { break; }
+                if !chunks_loaded {
+                    break;
+                }
             }
 
             if chunks_loaded {
-                self.camera.update(&*world, dt, &self.input);
+                self.camera.update(&*world, dt, &self.input.merged(&self.pad_input));
             }
 
-            // Collect chunks that need to be generated.
+            // Count the loaded chunks for the loading readout and collect
+            // the ones that need to be generated.
             let mut missing_chunks = Vec::new();
-            if player_chunk_moved || self.chunk_loader.pending_count() < 32 {
-                for cx in (player_cx - GENERATION_DISTANCE)..=(player_cx + GENERATION_DISTANCE) {
-                    for cz in (player_cz - GENERATION_DISTANCE)..=(player_cz + GENERATION_DISTANCE)
-                    {
-                        if !world.chunks.contains_key(&(cx, cz))
-                            && !self.chunk_loader.is_pending(cx, cz)
-                        {
-                            // Use squared distance as the priority so nearer
-                            // chunks are generated first (no sqrt needed).
-                            let dx = cx - player_cx;
-                            let dz = cz - player_cz;
-                            let priority = dx * dx + dz * dz;
-                            missing_chunks.push((cx, cz, priority));
-                        }
+            let mut loaded_chunks = 0;
+            let collect_missing = player_chunk_moved || self.chunk_loader.pending_count() < 32;
+            let forward = self.camera.forward();
+            for cx in (player_cx - GENERATION_DISTANCE)..=(player_cx + GENERATION_DISTANCE) {
+                for cz in (player_cz - GENERATION_DISTANCE)..=(player_cz + GENERATION_DISTANCE) {
+                    if world.chunks.contains_key(&(cx, cz)) {
+                        loaded_chunks += 1;
+                    } else if collect_missing && !self.chunk_loader.is_pending(cx, cz) {
+                        // The world fills in ahead of the player first,
+                        // nearest chunks first within each direction.
+                        let priority =
+                            chunk_request_priority(cx - player_cx, cz - player_cz, forward);
+                        missing_chunks.push((cx, cz, priority));
                     }
                 }
             }
@@ -183,10 +330,10 @@ impl State {
             // so the targeted block outline stays visible without requiring a
             // mouse button press.
             let (raycast_result, target_block) = if self.mouse_captured {
-                let raycast = self.camera.raycast(&*world, 5.0);
-                if let Some((bx, by, bz, _, _, _)) = raycast {
-                    let block = world.get_block(bx, by, bz);
-                    (Some((bx, by, bz, 0, 0, 0)), Some(block))
+                let raycast = self.camera.target(&world);
+                if let Some(hit) = raycast {
+                    let (bx, by, bz) = hit.block;
+                    (Some(hit), Some(world.get_block(bx, by, bz)))
                 } else {
                     (None, None)
                 }
@@ -194,26 +341,29 @@ impl State {
                 (None, None)
             };
 
-            // Check which block the camera eye is inside (used for the
+            // Check whether the camera eye is under water (used for the
             // underwater post-process effect).
-            let eye_pos = self.camera.eye_position();
-            let eye_block = world.get_block(
-                eye_pos.x.floor() as i32,
-                eye_pos.y.floor() as i32,
-                eye_pos.z.floor() as i32,
-            );
+            let eye_underwater = self.camera.is_head_underwater(&world);
 
             WorldSnapshot {
                 missing_chunks,
+                loaded_chunks,
                 raycast_result,
                 target_block,
-                eye_block,
+                eye_underwater,
             }
         }; // Read lock released here.
 
-        self.highlighted_block = snapshot
+        let generation_width = (2 * GENERATION_DISTANCE + 1) as usize;
+        self.update_chunk_progress(snapshot.loaded_chunks, generation_width * generation_width);
+        self.highlighted_block = snapshot.raycast_result;
+        // The ghost block is hidden where the block could not be placed.
+        self.placement_preview = snapshot
             .raycast_result
-            .map(|(bx, by, bz, _, _, _)| (bx, by, bz));
+            .map(|hit| hit.place_position())
+            .filter(|&(px, py, pz)| self.can_place_block(px, py, pz));
+        self.update_health(dt);
+        self.update_particles(dt);
 
         // Update the cached player chunk position after releasing the lock.
         if player_chunk_moved {
@@ -222,38 +372,68 @@ impl State {
         }
 
         // --- 5. Chunk requests ---
-        // Sort by ascending priority (smallest squared distance first) and cap
-        // at twice the per-frame chunk limit to allow some look-ahead.
+        let scope = ProfileScope::new("chunk requests");
+        // Sort by ascending priority (chunks ahead of the player first, then
+        // by squared distance) and cap at twice the per-frame chunk limit to
+        // allow some look-ahead.
         let mut requests = snapshot.missing_chunks;
         requests.sort_by_key(|&(_, _, priority)| priority);
         for (cx, cz, priority) in requests.into_iter().take(MAX_CHUNKS_PER_FRAME * 2) {
             self.chunk_loader.request_chunk(cx, cz, priority);
         }
+        drop(scope);
 
         // --- 6. Digging ---
-        let mut write_ops = WorldWriteOps {
+        let write_ops = WorldWriteOps {
             completed_chunks: completed_chunks
                 .into_iter()
                 .map(|r| (r.cx, r.cz, r.chunk))
                 .collect(),
-            block_break: None,
-            mark_dirty: Vec::new(),
         };
 
-        if self.input.left_mouse {
+        self.creative_break_cooldown = (self.creative_break_cooldown - dt).max(0.0);
+        if self.input.left_mouse || self.pad_input.left_mouse {
             if let Some(target_block) = snapshot.target_block {
-                if let Some((bx, by, bz, _, _, _)) = snapshot.raycast_result {
-                    let target = (bx, by, bz);
+                if let Some(hit) = snapshot.raycast_result {
+                    let target = hit.block;
+                    let (bx, by, bz) = target;
                     let break_time = target_block.break_time();
 
-                    if break_time.is_finite() && break_time > 0.0 {
+                    if self.camera.creative {
+                        // Creative breaks anything but bedrock instantly; the
+                        // cooldown keeps a held button from clearing a block
+                        // per frame.
+                        if break_time.is_finite() && self.creative_break_cooldown <= 0.0 {
+                            self.block_mutations.push_local(
+                                bx,
+                                by,
+                                bz,
+                                BlockType::Air,
+                                Facing::default(),
+                            );
+                            self.break_particles(bx, by, bz, target_block);
+                            #[cfg(feature = "audio")]
+                            self.play_block_sound(target_block, BlockSound::Break);
+                            self.creative_break_cooldown = CREATIVE_BREAK_INTERVAL;
+                        }
+                    } else if break_time.is_finite() && break_time > 0.0 {
                         if self.digging.target == Some(target) {
                             // Continue accumulating break progress on the same block.
                             self.digging.progress += dt;
                             if self.digging.progress >= break_time {
-                                // Block fully broken — schedule removal.
-                                write_ops.block_break = Some((bx, by, bz));
-                                write_ops.mark_dirty.push((bx, by, bz));
+                                // Block fully broken — schedule removal and
+                                // collect its drop.
+                                self.block_mutations.push_local(
+                                    bx,
+                                    by,
+                                    bz,
+                                    BlockType::Air,
+                                    Facing::default(),
+                                );
+                                self.collect_block(target_block);
+                                self.break_particles(bx, by, bz, target_block);
+                                #[cfg(feature = "audio")]
+                                self.play_block_sound(target_block, BlockSound::Break);
                                 self.digging.target = None;
                                 self.digging.progress = 0.0;
                             }
@@ -271,89 +451,181 @@ impl State {
                 self.digging.progress = 0.0;
             }
         } else {
-            // Left mouse released — cancel any in-progress dig.
+            // Left mouse released — cancel any in-progress dig, and let the
+            // next creative click break straight away.
             self.digging.target = None;
             self.digging.progress = 0.0;
+            self.creative_break_cooldown = 0.0;
         }
+        self.send_digging_progress();
+        #[cfg(feature = "audio")]
+        self.update_footsteps(dt);
 
         // --- 7. World write ---
         // Batch all mutations into a single write-lock window to minimize
         // contention with background generation and mesh threads.
         if !write_ops.completed_chunks.is_empty()
-            || write_ops.block_break.is_some()
-            || !write_ops.mark_dirty.is_empty()
+            || !self.block_mutations.is_empty()
+            || player_chunk_moved
         {
+            let _scope = ProfileScope::new("world write");
             let mut world = self.world.write();
 
             let mut newly_inserted_chunks = Vec::new();
             for (cx, cz, chunk) in write_ops.completed_chunks {
                 world.chunks.insert((cx, cz), chunk);
                 newly_inserted_chunks.push((cx, cz));
+                // Server edits that arrived before the chunk are applied
+                // ahead of anything queued since.
+                if let Some(changes) = self.deferred_block_changes.remove(&(cx, cz)) {
+                    self.block_mutations.requeue(changes);
+                }
             }
 
-            if let Some((bx, by, bz)) = write_ops.block_break {
-                world.set_block_player(bx, by, bz, BlockType::Air);
-                if let Some(tx) = &self.network_tx {
-                    let _ = tx.send(crate::multiplayer::protocol::Packet::BlockChange {
-                        x: bx,
-                        y: by,
-                        z: bz,
-                        block_type: BlockType::Air as u8,
-                    });
-                }
+            // Neighbors meshed before these chunks arrived drew faces along
+            // the shared border; remesh them so the covered ones are culled.
+            for &(cx, cz) in &newly_inserted_chunks {
+                world.mark_neighbor_borders_dirty(cx, cz);
             }
 
+            // Apply every queued local and remote block change in arrival
+            // order.  Each change marks its subchunks dirty before the lock
+            // is released, so mesh workers never see a block without it.
+            let applied = self.block_mutations.apply(&mut world);
+
             // Evict chunks that have moved outside the generation radius and
             // collect their identifiers so their GPU data can be freed below.
             let removed_chunks =
                 world.update_chunks_around_player(self.camera.position.x, self.camera.position.z);
 
+            // Switch columns that crossed the LOD distance to their new
+            // detail; new chunks get theirs here before they are first meshed.
+            world.update_mesh_lods(player_cx, player_cz);
+
             drop(world); // Release the write lock before GPU work.
 
-            // Mark neighbors of fully loaded chunks as dirty so their faces update.
-            for (cx, cz) in newly_inserted_chunks {
-                // To safely update boundary faces, we mark the neighboring blocks of the new chunk dirty.
-                for bx in 0..CHUNK_SIZE {
-                    for by in 0..minerust::constants::WORLD_HEIGHT as i32 {
-                        self.mark_chunk_dirty(cx * CHUNK_SIZE + bx, by, cz * CHUNK_SIZE);
-                        self.mark_chunk_dirty(cx * CHUNK_SIZE + bx, by, cz * CHUNK_SIZE + CHUNK_SIZE - 1);
-                    }
-                }
-                for bz in 0..CHUNK_SIZE {
-                    for by in 0..minerust::constants::WORLD_HEIGHT as i32 {
-                        self.mark_chunk_dirty(cx * CHUNK_SIZE, by, cz * CHUNK_SIZE + bz);
-                        self.mark_chunk_dirty(cx * CHUNK_SIZE + CHUNK_SIZE - 1, by, cz * CHUNK_SIZE + bz);
+            // Each local change is its own undo step and is sent to the
+            // server so other players see it.
+            for diffs in applied.local {
+                if let Some(tx) = &self.network_tx {
+                    for diff in &diffs {
+                        let _ = tx.send(crate::multiplayer::protocol::Packet::BlockChange {
+                            x: diff.x,
+                            y: diff.y,
+                            z: diff.z,
+                            block_type: diff.after as u8,
+                            facing: diff.after_facing as u8,
+                        });
                     }
                 }
+                self.edit_history.push(diffs);
+            }
+            // Remote changes for chunks that are not streamed in yet (e.g.
+            // the server's join-time sync) wait until the chunk arrives.
+            for change in applied.deferred {
+                let key = (
+                    change.x.div_euclid(CHUNK_SIZE),
+                    change.z.div_euclid(CHUNK_SIZE),
+                );
+                self.deferred_block_changes
+                    .entry(key)
+                    .or_default()
+                    .push(change);
             }
 
             self.remove_chunk_gpu_data(&removed_chunks);
         }
 
-        // Dirty-marking runs outside the write-lock window above to avoid
-        // holding the lock across the full neighbor scan.
-        for (bx, by, bz) in write_ops.mark_dirty {
-            self.mark_chunk_dirty(bx, by, bz);
-        }
+        // Advance any streamed `/fill` or `/replace` by one frame's budget.
+        let scope = ProfileScope::new("box edits");
+        self.step_box_edits();
+        drop(scope);
 
         // Update the underwater post-process uniform.
-        self.is_underwater = if snapshot.eye_block == BlockType::Water {
-            1.0
-        } else {
-            0.0
-        };
+        self.is_underwater = if snapshot.eye_underwater { 1.0 } else { 0.0 };
 
-        self.update_coords_ui();
+        if self.show_coords {
+            self.update_coords_ui();
+        }
+        if self.show_player_list {
+            self.update_player_list_ui();
+        }
 
-        // --- 8. Mesh uploads ---
-        // Drain completed mesh results up to the per-frame cap so a burst of
-        // ready meshes doesn't cause a single-frame GPU upload spike.
+        // --- 8. Mobs ---
+        // Mobs only exist at night, so during the day the write lock is
+        // skipped once the last one has despawned.  The server does not
+        // simulate or replicate mobs, so like random ticks below they only
+        // run in singleplayer; otherwise each client would see its own.
+        let night = sun_direction(self.time_of_day).y < 0.0;
+        if self.network_tx.is_none() && (night || !self.world.read().mobs.is_empty()) {
+            let _scope = ProfileScope::new("mobs");
+            self.world
+                .write()
+                .update_mobs(dt, &[self.camera.body_position()], night);
+        }
+
+        // --- 9. Water ---
+        // Water flows at a fixed rate regardless of frame rate; the write
+        // lock is only taken while some water is still moving.
+        self.water_tick_timer -= dt;
+        if self.water_tick_timer <= 0.0 {
+            self.water_tick_timer = WATER_TICK_INTERVAL;
+            if !self.world.read().water_updates.is_empty() {
+                let _scope = ProfileScope::new("water");
+                self.world.write().tick_water();
+            }
+        }
+
+        // Random ticks let grass spread and die back; like water they run
+        // at a fixed rate, over the chunks around the player only.  They are
+        // random and never sent to a server, so they only run in
+        // singleplayer; otherwise every client's world would drift apart.
+        self.random_tick_timer -= dt;
+        if self.random_tick_timer <= 0.0 && self.network_tx.is_none() {
+            self.random_tick_timer = RANDOM_TICK_INTERVAL;
+            let _scope = ProfileScope::new("random ticks");
+            self.world
+                .write()
+                .tick_random_blocks(player_cx, player_cz, SIMULATION_DISTANCE);
+        }
+
+        // --- 10. Mesh uploads ---
+        // Collect completed mesh results up to the per-frame cap into the
+        // upload queue, then upload nearest-first until this frame's byte
+        // budget is spent, so a burst of ready meshes (a large `/fill`, a
+        // freshly loaded area) is spread over several frames instead of
+        // causing a single-frame GPU upload spike.
+        let scope = ProfileScope::new("mesh upload");
         for _ in 0..MAX_MESH_BUILDS_PER_FRAME {
-            if let Some(result) = self.mesh_loader.poll_result() {
-                self.update_subchunk_mesh(result);
-            } else {
+            if self.mesh_uploads.is_full() {
                 break;
             }
+            let Some(result) = self.mesh_loader.poll_result() else {
+                break;
+            };
+            self.mesh_uploads.push(result);
+        }
+        let mut uploaded_bytes = 0;
+        while uploaded_bytes < MESH_UPLOAD_BYTES_PER_FRAME
+            && let Some(result) = self.mesh_uploads.pop_nearest(player_cx, player_cz)
+        {
+            uploaded_bytes += result.upload_bytes();
+            self.update_subchunk_mesh(result);
+        }
+        drop(scope);
+
+        // --- 11. Minimap ---
+        // Re-samples loaded or changed chunks every few frames; nothing to
+        // do while the minimap is hidden.
+        if self.minimap.visible {
+            let _scope = ProfileScope::new("minimap");
+            let world = self.world.read();
+            self.minimap.update(
+                &self.queue,
+                &world,
+                self.camera.position.x,
+                self.camera.position.z,
+            );
         }
     }
 
@@ -362,7 +634,7 @@ impl State {
     /// Iterates over every subchunk slot in each column and calls
     /// `remove_subchunk` on both indirect managers, zeroing the corresponding
     /// metadata slots so the GPU culling pass stops issuing draw calls for them.
-    fn remove_chunk_gpu_data(&mut self, removed_chunks: &[(i32, i32)]) {
+    pub fn remove_chunk_gpu_data(&mut self, removed_chunks: &[(i32, i32)]) {
         for &(cx, cz) in removed_chunks {
             for sy in 0..NUM_SUBCHUNKS {
                 let key = minerust::render::indirect::SubchunkKey {
@@ -377,82 +649,51 @@ impl State {
         }
     }
 
-    /// Marks the subchunk containing block `(x, y, z)` and all six of its
-    /// face-adjacent neighbors as dirty so their meshes are rebuilt.
-    ///
-    /// Neighbor dirtying is needed because a block on a chunk or subchunk
-    /// boundary affects the visible faces of the adjacent chunk/subchunk.
-    /// The six directions checked are:
-    /// - **±X**: the chunk columns to the west and east.
-    /// - **±Z**: the chunk columns to the north and south.
-    /// - **±Y**: the subchunks directly below and above within the same column.
+    /// Marks the subchunk containing block `(x, y, z)`, and each neighbor
+    /// whose faces it borders, dirty so their meshes are rebuilt.
     ///
-    /// Checks are bounds-guarded; out-of-range subchunk indices or absent
-    /// chunks are silently skipped.
+    /// Takes the world write lock; see [`minerust::World::mark_block_dirty`].
+    /// Code already holding the lock calls that directly.
     pub fn mark_chunk_dirty(&mut self, x: i32, y: i32, z: i32) {
-        let cx = (x as f32 / CHUNK_SIZE as f32).floor() as i32;
-        let cz = (z as f32 / CHUNK_SIZE as f32).floor() as i32;
-        let sy = y / SUBCHUNK_HEIGHT;
-
-        let mut world = self.world.write();
-
-        // Mark the subchunk that owns this block.
-        if let Some(chunk) = world.chunks.get_mut(&(cx, cz)) {
-            if sy >= 0 && (sy as usize) < chunk.subchunks.len() {
-                chunk.subchunks[sy as usize].mesh_dirty = true;
-            }
-        }
+        self.world.write().mark_block_dirty(x, y, z);
+    }
 
-        // Local coordinates within the chunk / subchunk — used to detect
-        // whether the block lies on a boundary face.
-        let lx = x.rem_euclid(CHUNK_SIZE);
-        let lz = z.rem_euclid(CHUNK_SIZE);
-        let ly = y.rem_euclid(SUBCHUNK_HEIGHT);
-
-        // West neighbor (block is on the -X face of its chunk column).
-        if lx == 0 {
-            if let Some(chunk) = world.chunks.get_mut(&(cx - 1, cz)) {
-                if sy >= 0 && (sy as usize) < chunk.subchunks.len() {
-                    chunk.subchunks[sy as usize].mesh_dirty = true;
-                }
-            }
-        }
-        // East neighbor (block is on the +X face of its chunk column).
-        if lx == CHUNK_SIZE - 1 {
-            if let Some(chunk) = world.chunks.get_mut(&(cx + 1, cz)) {
-                if sy >= 0 && (sy as usize) < chunk.subchunks.len() {
-                    chunk.subchunks[sy as usize].mesh_dirty = true;
-                }
-            }
-        }
-        // North neighbor (block is on the -Z face of its chunk column).
-        if lz == 0 {
-            if let Some(chunk) = world.chunks.get_mut(&(cx, cz - 1)) {
-                if sy >= 0 && (sy as usize) < chunk.subchunks.len() {
-                    chunk.subchunks[sy as usize].mesh_dirty = true;
-                }
-            }
+    /// Tells the server which block the local player is breaking and how far
+    /// along they are, so other players see the cracks.
+    ///
+    /// A packet goes out only when the block or crack stage changes, and at
+    /// most every `DIG_PROGRESS_SEND_INTERVAL` seconds.  Stopping (stage `0`)
+    /// is sent straight away so cracks never linger on other clients.
+    fn send_digging_progress(&mut self) {
+        let Some(tx) = &self.network_tx else {
+            return;
+        };
+        let current = self
+            .digging
+            .target
+            .map(|target| (target, self.digging.stage()));
+        if current == self.last_dig_sent {
+            return;
         }
-        // South neighbor (block is on the +Z face of its chunk column).
-        if lz == CHUNK_SIZE - 1 {
-            if let Some(chunk) = world.chunks.get_mut(&(cx, cz + 1)) {
-                if sy >= 0 && (sy as usize) < chunk.subchunks.len() {
-                    chunk.subchunks[sy as usize].mesh_dirty = true;
+        let ((x, y, z), stage) = match (current, self.last_dig_sent) {
+            (Some(dig), _) => {
+                if self.last_dig_send.elapsed().as_secs_f32() < DIG_PROGRESS_SEND_INTERVAL {
+                    return;
                 }
+                dig
             }
-        }
-        // Subchunk below (block is on the bottom face of its subchunk).
-        if ly == 0 && sy > 0 {
-            if let Some(chunk) = world.chunks.get_mut(&(cx, cz)) {
-                chunk.subchunks[(sy - 1) as usize].mesh_dirty = true;
-            }
-        }
-        // Subchunk above (block is on the top face of its subchunk).
-        if ly == SUBCHUNK_HEIGHT - 1 && sy < NUM_SUBCHUNKS - 1 {
-            if let Some(chunk) = world.chunks.get_mut(&(cx, cz)) {
-                chunk.subchunks[(sy + 1) as usize].mesh_dirty = true;
-            }
-        }
+            (None, Some((target, _))) => (target, 0),
+            (None, None) => return,
+        };
+        let _ = tx.send(crate::multiplayer::protocol::Packet::DiggingProgress {
+            player_id: self.my_player_id,
+            x,
+            y,
+            z,
+            stage,
+        });
+        self.last_dig_sent = current;
+        self.last_dig_send = Instant::now();
     }
 
     /// Forwards all pending network events to the multiplayer subsystem.
@@ -462,9 +703,9 @@ impl State {
     /// transitions.  Called at the very start of each frame so network state is
     /// fresh before any physics or world queries run.
     fn update_network_state(&mut self) {
-        let (new_seed, block_changes) = update_network(
+        let (new_seed, block_changes, commands, skip_night, server_time) = update_network(
             &mut self.my_player_id,
-            &self.camera.position,
+            &self.camera.body_position(),
             self.camera.yaw,
             self.camera.pitch,
             &mut self.last_position_send,
@@ -486,48 +727,74 @@ impl State {
                 // face-culling hides all geometry. Move the local player to the surface
                 // of the nearest chunks at spawn if we are switching servers.
                 // We fallback to Y=255.0 to allow gravity to pull them down safely.
+                self.camera.set_spectating(false);
                 self.camera.position = glam::Vec3::new(0.0, minerust::constants::WORLD_HEIGHT as f32 - 1.0, 0.0);
             }
+            // Edits recorded against the previous world no longer apply,
+            // and blocks mined there do not carry over.  The server's world
+            // must never be written over the singleplayer save.
+            self.world_entered = false;
+            self.edit_history.clear();
+            self.inventory.clear();
+            self.pending_box_edits.clear();
+            self.block_mutations.clear();
+            self.deferred_block_changes.clear();
+            // The server's clock runs at the normal speed.
+            self.time_scale = 1.0;
             // Clear rendering buffers and loaders to match the empty world
-            self.chunk_loader = minerust::ChunkLoader::new(seed);
-            self.mesh_loader = minerust::MeshLoader::new(
-                self.world.clone(),
-                std::thread::available_parallelism().map(|n| n.get()).unwrap_or(2),
+            // Servers always generate at the default sea level and with the
+            // default generation settings.
+            self.chunk_loader = minerust::ChunkLoader::with_worker_count(
+                self.chunk_workers,
+                seed,
+                minerust::SEA_LEVEL,
+                minerust::WorldGenConfig::default(),
             );
+            self.mesh_loader = minerust::MeshLoader::new(self.world.clone(), self.mesh_workers);
+            self.mesh_uploads.clear();
             self.indirect_manager.clear_gpu_data(&self.queue);
             self.water_indirect_manager.clear_gpu_data(&self.queue);
         }
 
-        if !block_changes.is_empty() {
-            // Drop lock before calling &mut self methods
-            for (bx, by, bz, block_type) in block_changes {
-                let bt = match block_type {
-                    0 => BlockType::Air,
-                    1 => BlockType::Grass,
-                    2 => BlockType::Dirt,
-                    3 => BlockType::Stone,
-                    4 => BlockType::Sand,
-                    5 => BlockType::Water,
-                    6 => BlockType::Wood,
-                    7 => BlockType::Leaves,
-                    8 => BlockType::Bedrock,
-                    9 => BlockType::Snow,
-                    10 => BlockType::Gravel,
-                    11 => BlockType::Clay,
-                    12 => BlockType::Ice,
-                    13 => BlockType::Cactus,
-                    14 => BlockType::DeadBush,
-                    15 => BlockType::WoodStairs,
-                    _ => BlockType::Air, // fallback
-                };
+        // Remote edits are applied in step 7 of `update`, in order with the
+        // local player's own edits.
+        for (bx, by, bz, block_type, facing) in block_changes {
+            let bt = BlockType::from_id(block_type).unwrap_or(BlockType::Air);
+            let facing = Facing::from_id(facing).unwrap_or_default();
+            self.block_mutations.push_remote(bx, by, bz, bt, facing);
+        }
 
-                {
-                    let mut world = self.world.write();
-                    world.set_block_player(bx, by, bz, bt);
-                }
+        for (player_id, command) in commands {
+            self.run_server_command(player_id, &command);
+        }
 
-                self.mark_chunk_dirty(bx, by, bz);
-            }
+        if skip_night {
+            self.skip_night();
+        }
+        if let Some(server_time) = server_time {
+            self.sync_time(server_time);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_ahead_are_generated_first() {
+        // Facing +X.
+        let forward = Vec3::X;
+        let ahead = chunk_request_priority(6, 1, forward);
+        let behind = chunk_request_priority(-2, 0, forward);
+        let beside = chunk_request_priority(0, 3, forward);
+        assert!(ahead < behind && ahead < beside);
+
+        // Distance still orders chunks within each group.
+        assert!(chunk_request_priority(2, 0, forward) < ahead);
+        assert!(behind < chunk_request_priority(-5, 0, forward));
+
+        // The chunks around the player come before everything else.
+        assert!(chunk_request_priority(-1, -1, forward) < chunk_request_priority(2, 0, forward));
+    }
+}
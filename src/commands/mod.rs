@@ -1 +1,4 @@
 mod command;
+mod parser;
+
+pub use parser::{ChatCommand, parse_command};
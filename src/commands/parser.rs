@@ -0,0 +1,305 @@
+use crate::constants::{MAX_CHUNK_BORDER_RADIUS, MAX_REGEN_RADIUS};
+use crate::core::block::BlockType;
+
+/// A fully parsed chat command, ready to be executed by the game.
+///
+/// Produced by [`parse_command`] from a line typed into the chat box.  Each
+/// variant carries already-validated arguments so the executor never has to
+/// deal with malformed input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatCommand {
+    /// `/waterfill [y]` – flood-fills the air pocket next to the targeted
+    /// block with water, up to and including world height `level`.
+    /// `None` means "up to the height of the targeted air block".
+    WaterFill { level: Option<i32> },
+    /// `/waterdrain` – removes the body of water connected to the targeted
+    /// position.
+    WaterDrain,
+    /// `/fill x1 y1 z1 x2 y2 z2 <block>` – fills the box spanned by the two
+    /// corners (inclusive) with `block`.
+    Fill {
+        from: (i32, i32, i32),
+        to: (i32, i32, i32),
+        block: BlockType,
+    },
+    /// `/replace <from> <to>` – swaps every `from` block for `to` inside the
+    /// region of the last `/fill`.
+    Replace { from: BlockType, to: BlockType },
+    /// `/borders [radius]` – toggles the chunk-border overlay, or shows it
+    /// with a new radius (in chunks) when one is given.
+    ChunkBorders { radius: Option<i32> },
+    /// `/accel [on|off]` – switches eased (accelerated) movement on or off;
+    /// `None` toggles it.
+    Acceleration { enabled: Option<bool> },
+    /// `/tp x y z` – moves the player's feet to the given world position.
+    Teleport { x: f32, y: f32, z: f32 },
+    /// `/seed` – shows the seed of the current world.
+    Seed,
+    /// `/time set <n>` – sets the day-cycle clock to `time` seconds
+    /// (0 is noon).
+    SetTime { time: f32 },
+    /// `/time scale <n>` – makes the day cycle run `scale` times as fast
+    /// (0 stops it).  Saved with the world.
+    SetTimeScale { scale: f32 },
+    /// `/give <block> <count>` – adds `count` blocks to the inventory.
+    Give { block: BlockType, count: u32 },
+    /// `/gamemode creative|survival` – switches the game mode.
+    GameMode { creative: bool },
+    /// `/fpscap <fps|off>` – holds the frame rate at `fps`; `None` (`off`
+    /// or `0`) removes the cap.
+    FpsCap { fps: Option<u32> },
+    /// `/regen [radius] [force]` – generates the chunks within `radius`
+    /// chunks of the player's again, for generator development.  Chunks
+    /// with player edits are skipped unless `force` is set.
+    RegenerateChunks { radius: i32, force: bool },
+}
+
+impl ChatCommand {
+    /// Whether a multiplayer client sends this command to the server
+    /// instead of running it: teleports, so the server's movement checks
+    /// accept the new position, and time changes, which apply to everyone.
+    /// The server answers with the command to run, see `Packet::Command`.
+    pub fn runs_on_server(&self) -> bool {
+        matches!(
+            self,
+            ChatCommand::Teleport { .. } | ChatCommand::SetTime { .. }
+        )
+    }
+}
+
+/// Parses a single chat line into a [`ChatCommand`].
+///
+/// The leading `/` is optional so the same parser can be used for both the
+/// chat box and a future console.  Command names are case-insensitive.
+///
+/// # Errors
+/// Returns a human-readable message (suitable for printing straight into the
+/// chat log) when the command is unknown or its arguments are invalid.
+pub fn parse_command(line: &str) -> Result<ChatCommand, String> {
+    let line = line.trim();
+    let line = line.strip_prefix('/').unwrap_or(line);
+    let mut parts = line.split_whitespace();
+
+    let name = match parts.next() {
+        Some(name) => name.to_ascii_lowercase(),
+        None => return Err("Empty command".to_string()),
+    };
+    let args: Vec<&str> = parts.collect();
+
+    match name.as_str() {
+        "waterfill" | "wf" => {
+            let level = match args.as_slice() {
+                [] => None,
+                [y] => Some(parse_i32(y)?),
+                _ => return Err("Usage: /waterfill [y]".to_string()),
+            };
+            Ok(ChatCommand::WaterFill { level })
+        }
+        "waterdrain" | "wd" => {
+            if !args.is_empty() {
+                return Err("Usage: /waterdrain".to_string());
+            }
+            Ok(ChatCommand::WaterDrain)
+        }
+        "fill" => match args.as_slice() {
+            [x1, y1, z1, x2, y2, z2, block] => Ok(ChatCommand::Fill {
+                from: (parse_i32(x1)?, parse_i32(y1)?, parse_i32(z1)?),
+                to: (parse_i32(x2)?, parse_i32(y2)?, parse_i32(z2)?),
+                block: parse_block(block)?,
+            }),
+            _ => Err("Usage: /fill x1 y1 z1 x2 y2 z2 <block>".to_string()),
+        },
+        "replace" => match args.as_slice() {
+            [from, to] => Ok(ChatCommand::Replace {
+                from: parse_block(from)?,
+                to: parse_block(to)?,
+            }),
+            _ => Err("Usage: /replace <from> <to>".to_string()),
+        },
+        "borders" => {
+            let radius = match args.as_slice() {
+                [] => None,
+                [r] => Some(parse_i32(r)?),
+                _ => return Err("Usage: /borders [radius]".to_string()),
+            };
+            if let Some(r) = radius
+                && !(0..=MAX_CHUNK_BORDER_RADIUS).contains(&r)
+            {
+                return Err(format!(
+                    "Radius must be between 0 and {}",
+                    MAX_CHUNK_BORDER_RADIUS
+                ));
+            }
+            Ok(ChatCommand::ChunkBorders { radius })
+        }
+        "accel" => {
+            let enabled = match args.as_slice() {
+                [] => None,
+                [arg] if arg.eq_ignore_ascii_case("on") => Some(true),
+                [arg] if arg.eq_ignore_ascii_case("off") => Some(false),
+                _ => return Err("Usage: /accel [on|off]".to_string()),
+            };
+            Ok(ChatCommand::Acceleration { enabled })
+        }
+        "tp" | "teleport" => match args.as_slice() {
+            [x, y, z] => Ok(ChatCommand::Teleport {
+                x: parse_f32(x)?,
+                y: parse_f32(y)?,
+                z: parse_f32(z)?,
+            }),
+            _ => Err("Usage: /tp x y z".to_string()),
+        },
+        "seed" => {
+            if !args.is_empty() {
+                return Err("Usage: /seed".to_string());
+            }
+            Ok(ChatCommand::Seed)
+        }
+        "time" => match args.as_slice() {
+            [set, time] if set.eq_ignore_ascii_case("set") => Ok(ChatCommand::SetTime {
+                time: parse_f32(time)?,
+            }),
+            [scale, value] if scale.eq_ignore_ascii_case("scale") => {
+                let scale = parse_f32(value)?;
+                if scale < 0.0 {
+                    return Err(format!("Time scale cannot be negative, got {}", scale));
+                }
+                Ok(ChatCommand::SetTimeScale { scale })
+            }
+            _ => Err("Usage: /time set <n> or /time scale <n>".to_string()),
+        },
+        "give" => match args.as_slice() {
+            [block, count] => {
+                let count = count
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|&count| count > 0)
+                    .ok_or_else(|| format!("Expected a positive count, got '{}'", count))?;
+                Ok(ChatCommand::Give {
+                    block: parse_block(block)?,
+                    count,
+                })
+            }
+            _ => Err("Usage: /give <block> <count>".to_string()),
+        },
+        "gamemode" | "gm" => match args.as_slice() {
+            [mode] if mode.eq_ignore_ascii_case("creative") => {
+                Ok(ChatCommand::GameMode { creative: true })
+            }
+            [mode] if mode.eq_ignore_ascii_case("survival") => {
+                Ok(ChatCommand::GameMode { creative: false })
+            }
+            _ => Err("Usage: /gamemode creative|survival".to_string()),
+        },
+        "fpscap" => match args.as_slice() {
+            [arg] if arg.eq_ignore_ascii_case("off") => Ok(ChatCommand::FpsCap { fps: None }),
+            [fps] => {
+                let fps = fps
+                    .parse::<u32>()
+                    .map_err(|_| format!("Expected a frame rate, got '{}'", fps))?;
+                Ok(ChatCommand::FpsCap {
+                    fps: (fps > 0).then_some(fps),
+                })
+            }
+            _ => Err("Usage: /fpscap <fps|off>".to_string()),
+        },
+        "regen" => {
+            let (radius, force) = match args.as_slice() {
+                [] => (0, false),
+                [arg] if arg.eq_ignore_ascii_case("force") => (0, true),
+                [r] => (parse_i32(r)?, false),
+                [r, arg] if arg.eq_ignore_ascii_case("force") => (parse_i32(r)?, true),
+                _ => return Err("Usage: /regen [radius] [force]".to_string()),
+            };
+            if !(0..=MAX_REGEN_RADIUS).contains(&radius) {
+                return Err(format!("Radius must be between 0 and {}", MAX_REGEN_RADIUS));
+            }
+            Ok(ChatCommand::RegenerateChunks { radius, force })
+        }
+        _ => Err(format!("Unknown command: /{}", name)),
+    }
+}
+
+/// Parses a signed integer argument, producing a chat-friendly error.
+fn parse_i32(arg: &str) -> Result<i32, String> {
+    arg.parse::<i32>()
+        .map_err(|_| format!("Expected a whole number, got '{}'", arg))
+}
+
+/// Parses a finite decimal argument, producing a chat-friendly error.
+fn parse_f32(arg: &str) -> Result<f32, String> {
+    arg.parse::<f32>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| format!("Expected a number, got '{}'", arg))
+}
+
+/// Parses a block name argument via [`BlockType::from_name`].
+fn parse_block(arg: &str) -> Result<BlockType, String> {
+    BlockType::from_name(arg).ok_or_else(|| format!("Unknown block '{}'", arg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn console_commands_parse_their_arguments() {
+        assert_eq!(
+            parse_command("/tp 1.5 70 -3"),
+            Ok(ChatCommand::Teleport {
+                x: 1.5,
+                y: 70.0,
+                z: -3.0
+            })
+        );
+        assert_eq!(parse_command("/seed"), Ok(ChatCommand::Seed));
+        assert_eq!(
+            parse_command("/time SET 300"),
+            Ok(ChatCommand::SetTime { time: 300.0 })
+        );
+        assert_eq!(
+            parse_command("/give stone 64"),
+            Ok(ChatCommand::Give {
+                block: BlockType::Stone,
+                count: 64
+            })
+        );
+        assert_eq!(
+            parse_command("/gamemode survival"),
+            Ok(ChatCommand::GameMode { creative: false })
+        );
+
+        assert!(parse_command("/tp 1 2").is_err());
+        assert!(parse_command("/tp 1 inf 2").is_err());
+        assert_eq!(
+            parse_command("/time scale 20"),
+            Ok(ChatCommand::SetTimeScale { scale: 20.0 })
+        );
+        assert!(parse_command("/time 300").is_err());
+        assert!(parse_command("/time scale -1").is_err());
+        assert!(parse_command("/give stone 0").is_err());
+        assert!(parse_command("/gamemode spectator").is_err());
+        assert_eq!(
+            parse_command("/fpscap 0"),
+            Ok(ChatCommand::FpsCap { fps: None })
+        );
+        assert!(parse_command("/fpscap -30").is_err());
+        assert_eq!(
+            parse_command("/regen"),
+            Ok(ChatCommand::RegenerateChunks {
+                radius: 0,
+                force: false
+            })
+        );
+        assert_eq!(
+            parse_command("/regen 2 force"),
+            Ok(ChatCommand::RegenerateChunks {
+                radius: 2,
+                force: true
+            })
+        );
+        assert!(parse_command("/regen 99").is_err());
+        assert!(parse_command("/warp").is_err());
+    }
+}
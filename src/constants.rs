@@ -5,10 +5,18 @@ pub const CHUNK_SIZE: i32 = 16;
 pub const SUBCHUNK_HEIGHT: i32 = 16;
 pub const NUM_SUBCHUNKS: i32 = WORLD_HEIGHT / SUBCHUNK_HEIGHT;
 pub const RENDER_DISTANCE: i32 = 12;
+/// Chunks from the player's column at which chunks are meshed coarsely,
+/// see [`MeshLod`](crate::core::chunk::MeshLod).
+pub const MESH_LOD_DISTANCE: i32 = 8;
 pub const SIMULATION_DISTANCE: i32 = RENDER_DISTANCE / 2;
 pub const GENERATION_DISTANCE: i32 = RENDER_DISTANCE + 2;
 pub const SEA_LEVEL: i32 = 64;
+/// Highest sea level a new world can be created with; mountain columns are
+/// only generated up to `WORLD_HEIGHT - 20`, so water above that would stop
+/// at their edges.
+pub const MAX_SEA_LEVEL: i32 = WORLD_HEIGHT - 32;
 pub const CHUNK_UNLOAD_DISTANCE: i32 = RENDER_DISTANCE + 5;
+pub const SPAWN_PLATFORM_RADIUS: i32 = 2;
 pub const TEX_GRASS_TOP: f32 = 0.0;
 pub const TEX_GRASS_SIDE: f32 = 1.0;
 pub const TEX_DIRT: f32 = 2.0;
@@ -25,12 +33,25 @@ pub const TEX_CLAY: f32 = 12.0;
 pub const TEX_ICE: f32 = 13.0;
 pub const TEX_CACTUS: f32 = 14.0;
 pub const TEX_DEAD_BUSH: f32 = 15.0;
+pub const TEX_GLASS: f32 = 16.0;
+/// First of the `DIG_STAGES` crack overlay layers; stage `s` uses
+/// `TEX_DIG_CRACKS + s - 1`.
+pub const TEX_DIG_CRACKS: f32 = 17.0;
 pub const TEXTURE_SIZE: u32 = 256;
 pub const ATLAS_SIZE: u32 = 4;
+/// Layers in the block texture array.  The first `ATLAS_SIZE²` come from the
+/// atlas grid; the rest are always generated (see
+/// [`append_generated_layers`](crate::render::texture::append_generated_layers)).
+pub const ATLAS_LAYERS: u32 = 17 + DIG_STAGES as u32;
 
 pub const MAX_CHUNKS_PER_FRAME: usize = 8;
 pub const MAX_MESH_BUILDS_PER_FRAME: usize = 8;
-pub const ASYNC_WORKER_COUNT: usize = 4;
+/// Mesh bytes uploaded to the GPU per frame; finished meshes beyond it wait
+/// in the `MeshUploadQueue`.  At least one mesh is uploaded every frame.
+pub const MESH_UPLOAD_BYTES_PER_FRAME: usize = 1024 * 1024;
+/// Finished meshes the `MeshUploadQueue` holds before the loader's results
+/// are left waiting in its channel.
+pub const MAX_PENDING_MESH_UPLOADS: usize = 256;
 
 pub const PLAYER_HEIGHT: f32 = 1.8;
 pub const PLAYER_CROUCH_HEIGHT: f32 = 1.7;
@@ -38,12 +59,101 @@ pub const PLAYER_WIDTH: f32 = 0.35;
 pub const PLAYER_BASE_SPEED: f32 = 4.8;
 pub const PLAYER_SPRINT_SPEED: f32 = 16.0;
 pub const PLAYER_JUMP_HEIGHT: f32 = 1.0;
+pub const PLAYER_GROUND_ACCELERATION: f32 = 40.0;
+pub const PLAYER_GROUND_DECELERATION: f32 = 50.0;
+pub const PLAYER_AIR_CONTROL: f32 = 0.2;
+/// How far away, in blocks, a survival player can break and place blocks.
+pub const PLAYER_REACH: f32 = 5.0;
+/// Seconds of sprinting on a full stamina bar.
+pub const PLAYER_MAX_STAMINA: f32 = 6.0;
+/// Stamina regained per second while not sprinting.
+pub const PLAYER_STAMINA_REGEN: f32 = 1.5;
+/// Once stamina runs out, sprinting stays off until it is back to this
+/// fraction of the maximum, so an empty bar does not stutter on and off.
+pub const PLAYER_STAMINA_RECOVER_FRACTION: f32 = 0.25;
+/// Upward deceleration, in blocks/s², with which water brakes a player who
+/// enters it falling faster than they can sink.
+pub const WATER_BUOYANCY: f32 = 150.0;
+
+pub const CREATIVE_FLY_SPEED: f32 = 10.9;
+pub const CREATIVE_VERTICAL_SPEED: f32 = 7.5;
+pub const CREATIVE_SPRINT_MULTIPLIER: f32 = 2.0;
+pub const CREATIVE_DOUBLE_TAP_WINDOW: f32 = 0.3;
+pub const CREATIVE_BREAK_INTERVAL: f32 = 0.25;
+/// Reach in creative mode, long enough to build without walking up to
+/// every block.
+pub const CREATIVE_REACH: f32 = 8.0;
+
+/// Starting speed of the debug spectator camera, in blocks per second.
+pub const SPECTATOR_SPEED: f32 = 20.0;
+/// Slowest and fastest spectator speeds the mouse wheel can reach.
+pub const SPECTATOR_MIN_SPEED: f32 = 1.0;
+pub const SPECTATOR_MAX_SPEED: f32 = 200.0;
+/// Factor one mouse-wheel notch multiplies the spectator speed by.
+pub const SPECTATOR_SPEED_STEP: f32 = 1.25;
+
+pub const DIG_STAGES: u8 = 10;
+pub const DIG_PROGRESS_SEND_INTERVAL: f32 = 0.1;
+
+pub const WATER_MAX_LEVEL: u8 = 8;
+pub const WATER_TICK_INTERVAL: f32 = 0.25;
+pub const WATER_UPDATES_PER_TICK: usize = 1024;
+/// Seconds between two random ticks, which drive slow block changes such
+/// as grass spreading.
+pub const RANDOM_TICK_INTERVAL: f32 = 0.05;
+/// Blocks picked at random in every non-empty sub-chunk per random tick.
+pub const RANDOM_TICKS_PER_SUBCHUNK: usize = 3;
+
+pub const MOB_CAP: usize = 16;
+pub const MOB_SPAWN_INTERVAL: f32 = 2.0;
+pub const MOB_SPAWN_MIN_DISTANCE: f32 = 24.0;
+pub const MOB_SPAWN_MAX_DISTANCE: f32 = 48.0;
+pub const MOB_DESPAWN_DISTANCE: f32 = 96.0;
+pub const MOB_GRAVITY: f32 = 25.0;
+pub const MOB_MAX_FALL_SPEED: f32 = 50.0;
+
+pub const GAMEPAD_LOOK_SENSITIVITY: f32 = 3.0;
+pub const GAMEPAD_STICK_DEADZONE: f32 = 0.2;
+
+pub const EDIT_TOOL_REACH: f32 = 64.0;
+pub const MAX_FLOOD_FILL_VOLUME: usize = 32_768;
+pub const MAX_BOX_EDIT_VOLUME: usize = 64 * 64 * 64;
+pub const BOX_EDIT_BLOCKS_PER_FRAME: usize = 4096;
+pub const EDIT_HISTORY_MAX_OPERATIONS: usize = 64;
+pub const EDIT_HISTORY_MAX_BLOCKS: usize = 1 << 20;
 
 pub const CSM_CASCADE_COUNT: usize = 4;
 pub const CSM_CASCADE_SPLITS: [f32; CSM_CASCADE_COUNT] = [16.0, 48.0, 128.0, 300.0];
 pub const CSM_SHADOW_MAP_SIZE: u32 = 2048;
+// With only two cascades the near one is widened so the far one still ends
+// at a useful distance.
+pub const CSM_LOW_CASCADE_SPLITS: [f32; 2] = [32.0, 128.0];
+
+pub const BLOOM_THRESHOLD: f32 = 1.0;
+pub const BLOOM_KNEE: f32 = 0.5;
+pub const BLOOM_INTENSITY: f32 = 0.6;
 
 pub const DEFAULT_FOV: f32 = 70.0 * std::f32::consts::PI / 180.0;
+/// Extra vertical FOV while sprinting.
+pub const SPRINT_FOV_BOOST: f32 = 6.0 * std::f32::consts::PI / 180.0;
+/// How quickly the FOV eases toward its target, per second.  The gap closes
+/// by `1 - e^(-rate * dt)` each frame, about 95 % in 0.4 s.
+pub const SPRINT_FOV_EASE_RATE: f32 = 8.0;
+
+pub const FACE_HIGHLIGHT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.18];
+pub const PLACEMENT_PREVIEW_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.6];
+pub const CHUNK_BORDER_COLOR: [f32; 4] = [0.3, 0.85, 1.0, 0.8];
+pub const DIG_CRACK_COLOR: [f32; 4] = [0.08, 0.06, 0.05, 0.8];
+pub const CELESTIAL_PATH_COLOR: [f32; 4] = [1.0, 0.9, 0.5, 0.3];
+pub const CELESTIAL_MARKER_COLOR: [f32; 4] = [1.0, 0.6, 0.2, 0.9];
+/// Distance of the sun/moon path overlay from the camera; matches the
+/// distance `sun.wgsl` places the sun and moon at.
+pub const CELESTIAL_PATH_RADIUS: f32 = 180.0;
+pub const PLAYER_SHIRT_COLOR: [f32; 3] = [0.2, 0.5, 0.9];
+pub const CHUNK_BORDER_RADIUS: i32 = 4;
+pub const MAX_CHUNK_BORDER_RADIUS: i32 = 16;
+/// Largest radius, in chunks, `/regen` accepts around the player's chunk.
+pub const MAX_REGEN_RADIUS: i32 = 4;
 
 pub const BLOCK_SIZE: f32 = 0.98;
 pub const BLOCK_OFFSET: f32 = (1.0 - BLOCK_SIZE) / 2.0;
@@ -63,6 +173,20 @@ pub fn get_mesh_worker_count() -> usize {
     ((cores.saturating_sub(2)) / 2).max(2).min(6)
 }
 
+/// Distance of the camera's near clip plane, in blocks.
+pub const NEAR_PLANE: f32 = 0.1;
+
+/// Distance of the camera's far clip plane when `render_distance` chunks are
+/// drawn.
+///
+/// Reaches the far corner of the drawn square, plus the chunk the camera is
+/// in, from any height in the world.  Going no further keeps more
+/// depth-buffer precision at small render distances.
+pub fn far_plane(render_distance: i32) -> f32 {
+    let horizontal = ((render_distance + 1) * CHUNK_SIZE) as f32 * std::f32::consts::SQRT_2;
+    horizontal.hypot(WORLD_HEIGHT as f32)
+}
+
 pub fn get_active_cascade_count(render_distance: i32) -> usize {
     match render_distance {
         0..=6 => 2,
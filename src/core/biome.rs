@@ -72,6 +72,53 @@ impl Biome {
         }
     }
 
+    /// Returns the RGB multiplier applied to the ambient light while the
+    /// player stands in this biome.
+    ///
+    /// Kept close to white so it reads as atmosphere rather than a color
+    /// filter: deserts are a touch warmer, forests and snow a touch cooler.
+    pub fn ambient_tint(&self) -> [f32; 3] {
+        match self {
+            Biome::Desert => [1.06, 1.03, 0.94],
+            Biome::Beach => [1.03, 1.02, 0.97],
+            Biome::Forest => [0.95, 1.0, 1.03],
+            Biome::Swamp => [0.96, 1.0, 0.93],
+            Biome::Tundra => [0.97, 1.0, 1.05],
+            Biome::Mountains => [0.98, 1.0, 1.03],
+            _ => [1.0, 1.0, 1.0],
+        }
+    }
+
+    /// Returns the RGB daylight color of the distance haze in this biome.
+    ///
+    /// Components are in linear `[0.0, 1.0]` space.
+    pub fn fog_color(&self) -> [f32; 3] {
+        match self {
+            Biome::Desert => [0.86, 0.80, 0.66],
+            Biome::Beach => [0.80, 0.82, 0.80],
+            Biome::Forest => [0.55, 0.66, 0.65],
+            Biome::Swamp => [0.50, 0.56, 0.46],
+            Biome::Tundra => [0.82, 0.87, 0.93],
+            Biome::Mountains => [0.70, 0.76, 0.84],
+            Biome::Ocean | Biome::River | Biome::Lake => [0.62, 0.74, 0.86],
+            Biome::Plains | Biome::Island => [0.68, 0.78, 0.88],
+        }
+    }
+
+    /// Returns how strongly the haze covers terrain at the edge of the
+    /// render distance, in `[0.0, 1.0]`.  Dry deserts and damp swamps are
+    /// the haziest.
+    pub fn fog_strength(&self) -> f32 {
+        match self {
+            Biome::Desert => 0.45,
+            Biome::Swamp => 0.4,
+            Biome::Tundra => 0.35,
+            Biome::Forest => 0.3,
+            Biome::Beach => 0.25,
+            _ => 0.2,
+        }
+    }
+
     /// Returns the minimum noise threshold above which a tree will be placed.
     ///
     /// The world generator compares this value against a `[0.0, 1.0]` noise
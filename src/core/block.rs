@@ -43,9 +43,82 @@ pub enum BlockType {
     DeadBush,
     /// Wooden stair block. Transparent for culling purposes.
     WoodStairs,
+    /// Bottom half of a stone block. Partial geometry, transparent for culling.
+    StoneSlab,
+    /// Stone stair block. Orientation is stored per block as a [`Facing`].
+    StoneStairs,
+    /// Glass. Transparent, so the faces behind it stay visible, but the
+    /// faces between two glass blocks are culled.
+    Glass,
+    /// Thin layer of snow lying on top of a block.  Partial and non-solid:
+    /// players walk through it and a block placed there replaces it.
+    SnowLayer,
+    /// Bed.  Partial, slab-like block; right-clicking it at night sleeps
+    /// through to the morning instead of placing a block against it.
+    Bed,
+    /// Cobblestone.  Rough stone that lines generated dungeons.
+    Cobblestone,
+    /// Torch.  A thin wooden post with a glowing tip; partial, and solid
+    /// only so it can be targeted and broken.
+    Torch,
 }
 
 impl BlockType {
+    /// Every block type, in declaration order (which is also the `u8` wire
+    /// encoding used by the network protocol).
+    pub const ALL: [BlockType; 23] = [
+        BlockType::Air,
+        BlockType::Grass,
+        BlockType::Dirt,
+        BlockType::Stone,
+        BlockType::Sand,
+        BlockType::Water,
+        BlockType::Wood,
+        BlockType::Leaves,
+        BlockType::Bedrock,
+        BlockType::Snow,
+        BlockType::Gravel,
+        BlockType::Clay,
+        BlockType::Ice,
+        BlockType::Cactus,
+        BlockType::DeadBush,
+        BlockType::WoodStairs,
+        BlockType::StoneSlab,
+        BlockType::StoneStairs,
+        BlockType::Glass,
+        BlockType::SnowLayer,
+        BlockType::Bed,
+        BlockType::Cobblestone,
+        BlockType::Torch,
+    ];
+
+    /// Looks up a block type by its network id (the `u8` discriminant sent in
+    /// `BlockChange` packets).
+    ///
+    /// Returns `None` for ids that do not name a block, so untrusted packets
+    /// can be rejected instead of silently mapped to air.
+    pub fn from_id(id: u8) -> Option<BlockType> {
+        Self::ALL.get(id as usize).copied()
+    }
+
+    /// Looks up a block type by name, as typed in chat commands.
+    ///
+    /// Matching is case-insensitive and ignores spaces and underscores, so
+    /// `"dead_bush"`, `"DeadBush"` and `"Dead Bush"` all resolve to
+    /// [`BlockType::DeadBush`].
+    pub fn from_name(name: &str) -> Option<BlockType> {
+        let normalize = |s: &str| {
+            s.chars()
+                .filter(|c| *c != '_' && *c != ' ')
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        };
+        let wanted = normalize(name);
+        Self::ALL
+            .into_iter()
+            .find(|block| normalize(block.display_name()) == wanted)
+    }
+
     /// Returns the base RGB color used for vertex coloring and the minimap.
     ///
     /// Components are in linear `[0.0, 1.0]` space. [`BlockType::Air`] returns
@@ -61,23 +134,29 @@ impl BlockType {
             BlockType::Wood => [0.6, 0.4, 0.2],
             BlockType::Leaves => [0.3, 0.6, 0.2],
             BlockType::Bedrock => [0.2, 0.2, 0.2],
-            BlockType::Snow => [0.95, 0.95, 0.98],
+            BlockType::Snow | BlockType::SnowLayer => [0.95, 0.95, 0.98],
             BlockType::Gravel => [0.5, 0.5, 0.52],
             BlockType::Clay => [0.65, 0.65, 0.72],
             BlockType::Ice => [0.7, 0.85, 0.95],
             BlockType::Cactus => [0.2, 0.55, 0.2],
             BlockType::DeadBush => [0.55, 0.4, 0.25],
-            BlockType::WoodStairs => [0.6, 0.4, 0.2],
+            BlockType::WoodStairs | BlockType::Torch => [0.6, 0.4, 0.2],
+            BlockType::StoneSlab | BlockType::StoneStairs => [0.55, 0.55, 0.55],
+            BlockType::Glass => [0.85, 0.92, 0.95],
+            BlockType::Bed => [0.7, 0.16, 0.16],
+            BlockType::Cobblestone => [0.42, 0.42, 0.42],
         }
     }
 
     /// Returns the RGB color for the **top** face.
     ///
-    /// Overridden for [`BlockType::Grass`], which uses a green tint on top.
-    /// All other variants fall back to [`Self::color`].
+    /// Overridden for [`BlockType::Grass`], which uses a green tint on top,
+    /// and [`BlockType::Torch`], whose tip glows orange.  All other variants
+    /// fall back to [`Self::color`].
     pub fn top_color(&self) -> [f32; 3] {
         match self {
             BlockType::Grass => [0.36, 0.7, 0.28],
+            BlockType::Torch => [1.0, 0.78, 0.3],
             _ => self.color(),
         }
     }
@@ -85,29 +164,31 @@ impl BlockType {
     /// Returns the RGB color for the **bottom** face.
     ///
     /// Overridden for [`BlockType::Grass`], which shows a dirt color on the
-    /// bottom. All other variants fall back to [`Self::color`].
+    /// bottom, and [`BlockType::Bed`], whose wooden frame shows underneath.
+    /// All other variants fall back to [`Self::color`].
     pub fn bottom_color(&self) -> [f32; 3] {
         match self {
             BlockType::Grass => [0.52, 0.37, 0.26],
+            BlockType::Bed => [0.6, 0.4, 0.2],
             _ => self.color(),
         }
     }
 
     /// Returns `true` if this block physically obstructs movement.
     ///
-    /// [`BlockType::Air`], [`BlockType::Water`], and [`BlockType::DeadBush`]
-    /// are non-solid; everything else is solid.
+    /// [`BlockType::Air`], [`BlockType::Water`], [`BlockType::DeadBush`] and
+    /// [`BlockType::SnowLayer`] are non-solid; everything else is solid.
     pub fn is_solid(&self) -> bool {
         !matches!(
             self,
-            BlockType::Air | BlockType::Water | BlockType::DeadBush
+            BlockType::Air | BlockType::Water | BlockType::DeadBush | BlockType::SnowLayer
         )
     }
 
     /// Returns `true` if this block allows light (and visibility) to pass through.
     ///
     /// Transparent blocks include: `Air`, `Water`, `Leaves`, `Ice`,
-    /// `DeadBush`, and `WoodStairs`.
+    /// `DeadBush`, `Glass`, and every partial block (see [`Self::is_partial`]).
     pub fn is_transparent(&self) -> bool {
         matches!(
             self,
@@ -116,16 +197,117 @@ impl BlockType {
                 | BlockType::Leaves
                 | BlockType::Ice
                 | BlockType::DeadBush
-                | BlockType::WoodStairs
+                | BlockType::Glass
+        ) || self.is_partial()
+    }
+
+    /// Returns `true` for blocks with partly see-through texels, which are
+    /// drawn alpha-blended after the opaque scene: `Ice` and `Glass`.
+    ///
+    /// Leaves and dead bushes are see-through too, but only cut out: every
+    /// texel is either solid or fully clear, so they are alpha-tested with
+    /// the opaque terrain instead.
+    pub fn is_translucent(&self) -> bool {
+        matches!(self, BlockType::Ice | BlockType::Glass)
+    }
+
+    /// Returns `true` if this block fills its whole cell and hides whatever
+    /// is behind it, i.e. it is not [transparent](Self::is_transparent).
+    ///
+    /// Only faces against an opaque neighbor are culled by the mesher.
+    pub fn is_opaque(&self) -> bool {
+        !self.is_transparent()
+    }
+
+    /// Returns `true` for blocks that do not fill their whole cell (slabs,
+    /// stairs, snow layers, beds and torches).
+    ///
+    /// Partial blocks are meshed with custom geometry instead of greedy
+    /// quads and collide using [`Self::collision_boxes`].
+    pub fn is_partial(&self) -> bool {
+        matches!(
+            self,
+            BlockType::WoodStairs
+                | BlockType::StoneSlab
+                | BlockType::StoneStairs
+                | BlockType::SnowLayer
+                | BlockType::Bed
+                | BlockType::Torch
         )
     }
 
+    /// Returns `true` if this block's geometry or textures depend on a
+    /// stored [`Facing`]: the step direction of stairs, the trunk axis of a
+    /// wood log.
+    pub fn is_directional(&self) -> bool {
+        matches!(
+            self,
+            BlockType::Wood | BlockType::WoodStairs | BlockType::StoneStairs
+        )
+    }
+
+    /// Returns the [`Facing`] this block is stored with when the player
+    /// places it against a face with outward `normal` while looking along
+    /// `yaw`.
+    ///
+    /// Logs run along the axis of the face they are placed on, so a log put
+    /// against a wall lies horizontally; stairs ascend away from the player.
+    pub fn placement_facing(&self, yaw: f32, normal: (i32, i32, i32)) -> Facing {
+        match self {
+            BlockType::Wood => Facing::for_log_normal(normal),
+            _ => Facing::from_yaw(yaw),
+        }
+    }
+
+    /// Returns the block's collision volume as a list of boxes in
+    /// block-local space (`[0, 1]` on every axis).
+    ///
+    /// Full blocks return the unit cube; slabs, stairs and torches return
+    /// their actual bounds, with stairs rotated according to `facing`.  The result is only
+    /// meaningful for [`solid`](Self::is_solid) blocks.
+    pub fn collision_boxes(&self, facing: Facing) -> &'static [([f32; 3], [f32; 3])] {
+        const FULL: &[([f32; 3], [f32; 3])] = &[([0.0, 0.0, 0.0], [1.0, 1.0, 1.0])];
+        const SLAB: &[([f32; 3], [f32; 3])] = &[([0.0, 0.0, 0.0], [1.0, 0.5, 1.0])];
+        const BED: &[([f32; 3], [f32; 3])] = &[([0.0, 0.0, 0.0], [1.0, 0.5625, 1.0])];
+        const TORCH: &[([f32; 3], [f32; 3])] = &[([0.4375, 0.0, 0.4375], [0.5625, 0.625, 0.5625])];
+        // Lower slab plus the raised half on the side the stairs ascend to.
+        const STAIRS_NORTH: &[([f32; 3], [f32; 3])] = &[
+            ([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]),
+            ([0.0, 0.5, 0.0], [1.0, 1.0, 0.5]),
+        ];
+        const STAIRS_EAST: &[([f32; 3], [f32; 3])] = &[
+            ([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]),
+            ([0.5, 0.5, 0.0], [1.0, 1.0, 1.0]),
+        ];
+        const STAIRS_SOUTH: &[([f32; 3], [f32; 3])] = &[
+            ([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]),
+            ([0.0, 0.5, 0.5], [1.0, 1.0, 1.0]),
+        ];
+        const STAIRS_WEST: &[([f32; 3], [f32; 3])] = &[
+            ([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]),
+            ([0.0, 0.5, 0.0], [0.5, 1.0, 1.0]),
+        ];
+
+        match self {
+            BlockType::StoneSlab => SLAB,
+            BlockType::Bed => BED,
+            BlockType::Torch => TORCH,
+            BlockType::WoodStairs | BlockType::StoneStairs => match facing {
+                Facing::North => STAIRS_NORTH,
+                Facing::East => STAIRS_EAST,
+                Facing::South => STAIRS_SOUTH,
+                Facing::West => STAIRS_WEST,
+            },
+            _ => FULL,
+        }
+    }
+
     /// Returns `true` if this block is both non-transparent and non-air.
     ///
     /// Used by [`SubChunk::check_fully_opaque`](crate::core::chunk::SubChunk::check_fully_opaque)
     /// to determine whether an entire sub-chunk can occlude its neighbors.
     pub fn is_solid_opaque(&self) -> bool {
-        !self.is_transparent() && *self != BlockType::Air
+        self.is_opaque() && *self != BlockType::Air
     }
 
     /// Returns `true` if a face of `self` should be rendered when `neighbor`
@@ -137,8 +319,12 @@ impl BlockType {
     /// 3. Any block renders against [`BlockType::Water`].
     /// 4. [`BlockType::Leaves`] renders against other leaves (avoids solid
     ///    interior artifacts).
-    /// 5. Any block renders against [`BlockType::WoodStairs`] (partial geometry).
-    /// 6. Any block renders against a transparent neighbor.
+    /// 5. Any block renders against a partial block (slab or stairs), since
+    ///    it never covers the whole shared face.
+    /// 6. Other transparent blocks never render against their own type, so
+    ///    a wall of glass or ice has no internal faces.
+    /// 7. Any block renders against a neighbor that is not
+    ///    [opaque](Self::is_opaque).
     pub fn should_render_face_against(&self, neighbor: BlockType) -> bool {
         if neighbor == BlockType::Air {
             return true;
@@ -152,16 +338,19 @@ impl BlockType {
         if *self == BlockType::Leaves && neighbor == BlockType::Leaves {
             return true;
         }
-        if neighbor == BlockType::WoodStairs {
+        if neighbor.is_partial() {
             return true;
         }
-        neighbor.is_transparent()
+        if *self == neighbor {
+            return false;
+        }
+        !neighbor.is_opaque()
     }
 
     /// Returns the time in seconds for a player to break this block by hand.
     ///
-    /// [`BlockType::Air`], [`BlockType::Water`], and [`BlockType::DeadBush`]
-    /// return `0.0` (instant). [`BlockType::Bedrock`] returns
+    /// [`BlockType::Air`], [`BlockType::Water`], [`BlockType::DeadBush`] and
+    /// [`BlockType::Torch`] return `0.0` (instant). [`BlockType::Bedrock`] returns
     /// [`f32::INFINITY`] (unbreakable).
     pub fn break_time(&self) -> f32 {
         match self {
@@ -181,6 +370,23 @@ impl BlockType {
             BlockType::Cactus => 0.4,
             BlockType::DeadBush => 0.0,
             BlockType::WoodStairs => 2.0,
+            BlockType::StoneSlab => 2.0,
+            BlockType::StoneStairs => 2.5,
+            BlockType::Glass => 0.3,
+            BlockType::SnowLayer => 0.1,
+            BlockType::Bed => 0.3,
+            BlockType::Cobblestone => 2.0,
+            BlockType::Torch => 0.0,
+        }
+    }
+
+    /// Returns the block a player receives for breaking this one in survival
+    /// mode, or `None` for blocks that cannot be collected (air, water,
+    /// bedrock, snow layers).
+    pub fn dropped_block(&self) -> Option<BlockType> {
+        match self {
+            BlockType::Air | BlockType::Water | BlockType::Bedrock | BlockType::SnowLayer => None,
+            other => Some(*other),
         }
     }
 
@@ -199,58 +405,95 @@ impl BlockType {
             BlockType::Wood => TEX_WOOD_TOP,
             BlockType::Leaves => TEX_LEAVES,
             BlockType::Bedrock => TEX_BEDROCK,
-            BlockType::Snow => TEX_SNOW,
+            BlockType::Snow | BlockType::SnowLayer => TEX_SNOW,
             BlockType::Gravel => TEX_GRAVEL,
             BlockType::Clay => TEX_CLAY,
             BlockType::Ice => TEX_ICE,
             BlockType::Cactus => TEX_CACTUS,
             BlockType::DeadBush => TEX_DEAD_BUSH,
             BlockType::WoodStairs => TEX_WOOD_TOP,
+            BlockType::StoneSlab | BlockType::StoneStairs => TEX_STONE,
+            BlockType::Glass => TEX_GLASS,
+            // The blanket is the white snow tile tinted red.
+            BlockType::Bed => TEX_SNOW,
+            // And cobblestone is the pebbly gravel tile tinted darker.
+            BlockType::Cobblestone => TEX_GRAVEL,
+            // The torch's tip is the snow tile again, tinted like a flame.
+            BlockType::Torch => TEX_SNOW,
         }
     }
 
     /// Returns the texture atlas index for the **side** faces.
     ///
     /// Overridden for [`BlockType::Grass`] (grass-side texture) and
-    /// [`BlockType::Wood`] (bark texture). All other variants fall back to
-    /// [`Self::tex_top`].
+    /// [`BlockType::Wood`] and [`BlockType::Torch`] (bark texture). All other
+    /// variants fall back to [`Self::tex_top`].
     pub fn tex_side(&self) -> f32 {
         match self {
             BlockType::Grass => TEX_GRASS_SIDE,
-            BlockType::Wood => TEX_WOOD_SIDE,
+            BlockType::Wood | BlockType::Torch => TEX_WOOD_SIDE,
             _ => self.tex_top(),
         }
     }
 
     /// Returns the texture atlas index for the **bottom** face.
     ///
-    /// Overridden for [`BlockType::Grass`] (dirt), [`BlockType::Wood`], and
-    /// [`BlockType::WoodStairs`] (wood-top). All other variants fall back to
-    /// [`Self::tex_top`].
+    /// Overridden for [`BlockType::Grass`] (dirt), [`BlockType::Wood`],
+    /// [`BlockType::WoodStairs`], [`BlockType::Bed`] and [`BlockType::Torch`]
+    /// (wood-top). All other variants fall back to [`Self::tex_top`].
     pub fn tex_bottom(&self) -> f32 {
         match self {
             BlockType::Grass => TEX_DIRT,
             BlockType::Wood => TEX_WOOD_TOP,
-            BlockType::WoodStairs => TEX_WOOD_TOP,
+            BlockType::WoodStairs | BlockType::Bed | BlockType::Torch => TEX_WOOD_TOP,
             _ => self.tex_top(),
         }
     }
 
+    /// Returns the texture atlas index for face `face` of a block stored
+    /// with `facing`.
+    ///
+    /// Faces are numbered as in the greedy mesher: 0 −X, 1 +X, 2 −Y, 3 +Y,
+    /// 4 −Z, 5 +Z.  A wood log shows its end grain on the two faces its trunk
+    /// axis ([`Facing::log_axis`]) passes through and bark on the other four;
+    /// every other block uses [`Self::tex_bottom`], [`Self::tex_top`] and
+    /// [`Self::tex_side`].
+    pub fn texture_for_face(&self, face: usize, facing: Facing) -> f32 {
+        if *self == BlockType::Wood {
+            return if face / 2 == facing.log_axis() {
+                TEX_WOOD_TOP
+            } else {
+                TEX_WOOD_SIDE
+            };
+        }
+        match face {
+            2 => self.tex_bottom(),
+            3 => self.tex_top(),
+            _ => self.tex_side(),
+        }
+    }
+
     /// Returns the PBR roughness value for this block (`0.0` = mirror, `1.0` = fully diffuse).
     ///
     /// Notable values:
-    /// - Ice / Water: `0.1` (glossy)
+    /// - Ice / Water / Glass: `0.1` (glossy)
     /// - Grass / Dirt: `1.0` (fully diffuse)
-    /// - Stone / Bedrock / Gravel / Clay: `0.7`
+    /// - Stone (incl. slabs and stairs) / Bedrock / Gravel / Cobblestone / Clay: `0.7`
     pub fn roughness(&self) -> f32 {
         match self {
-            BlockType::Stone | BlockType::Bedrock | BlockType::Gravel | BlockType::Clay => 0.7,
+            BlockType::Stone
+            | BlockType::Bedrock
+            | BlockType::Gravel
+            | BlockType::Cobblestone
+            | BlockType::Clay
+            | BlockType::StoneSlab
+            | BlockType::StoneStairs => 0.7,
             BlockType::Sand => 0.8,
-            BlockType::Grass | BlockType::Dirt | BlockType::DeadBush => 1.0,
+            BlockType::Grass | BlockType::Dirt | BlockType::DeadBush | BlockType::Bed => 1.0,
             BlockType::Leaves => 0.5,
-            BlockType::Snow => 0.8,
-            BlockType::Ice | BlockType::Water => 0.1,
-            BlockType::Wood | BlockType::Cactus | BlockType::WoodStairs => 0.6,
+            BlockType::Snow | BlockType::SnowLayer => 0.8,
+            BlockType::Ice | BlockType::Water | BlockType::Glass => 0.1,
+            BlockType::Wood | BlockType::Cactus | BlockType::WoodStairs | BlockType::Torch => 0.6,
             BlockType::Air => 1.0,
         }
     }
@@ -287,6 +530,132 @@ impl BlockType {
             BlockType::Cactus => "Cactus",
             BlockType::DeadBush => "Dead Bush",
             BlockType::WoodStairs => "Wood Stairs",
+            BlockType::StoneSlab => "Stone Slab",
+            BlockType::StoneStairs => "Stone Stairs",
+            BlockType::Glass => "Glass",
+            BlockType::SnowLayer => "Snow Layer",
+            BlockType::Bed => "Bed",
+            BlockType::Cobblestone => "Cobblestone",
+            BlockType::Torch => "Torch",
+        }
+    }
+}
+
+/// Horizontal orientation of a directional block such as stairs.
+///
+/// The variant names the direction the stairs **ascend** towards: a
+/// [`Facing::North`] staircase has its low step on the south side and its
+/// raised half towards −Z.  North is −Z and East is +X, matching
+/// [`Camera::forward`](crate::player::camera::Camera::forward).
+///
+/// The default ([`Facing::South`]) is the orientation stairs had before
+/// facings were stored, so generated structures keep their look.
+///
+/// Wood logs reuse the facing to store their trunk axis, see
+/// [`Facing::log_axis`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum Facing {
+    /// Ascends towards −Z.
+    North,
+    /// Ascends towards +X.
+    East,
+    /// Ascends towards +Z.
+    #[default]
+    South,
+    /// Ascends towards −X.
+    West,
+}
+
+impl Facing {
+    /// Every facing, in declaration order (which is also the `u8` wire
+    /// encoding used by `BlockChange` packets).
+    pub const ALL: [Facing; 4] = [Facing::North, Facing::East, Facing::South, Facing::West];
+
+    /// Looks up a facing by its network id, see [`Facing::ALL`].
+    ///
+    /// Returns `None` for ids that do not name a facing, so untrusted
+    /// packets can be rejected.
+    pub fn from_id(id: u8) -> Option<Facing> {
+        Self::ALL.get(id as usize).copied()
+    }
+
+    /// Returns the facing closest to the horizontal look direction given by
+    /// `yaw` (radians, as stored in `Camera::yaw`).
+    ///
+    /// Stairs placed with this facing rise away from the player, so they can
+    /// be walked up straight away.
+    pub fn from_yaw(yaw: f32) -> Facing {
+        let (dx, dz) = (yaw.cos(), yaw.sin());
+        if dx.abs() >= dz.abs() {
+            if dx >= 0.0 {
+                Facing::East
+            } else {
+                Facing::West
+            }
+        } else if dz >= 0.0 {
+            Facing::South
+        } else {
+            Facing::North
+        }
+    }
+
+    /// Returns the facing that stores a wood log placed against a face with
+    /// outward `normal`: the log's trunk runs along that normal's axis.
+    pub fn for_log_normal(normal: (i32, i32, i32)) -> Facing {
+        match normal {
+            (x, _, _) if x != 0 => Facing::East,
+            (_, _, z) if z != 0 => Facing::North,
+            _ => Facing::South,
+        }
+    }
+
+    /// Returns the trunk axis of a wood log stored with this facing
+    /// (0 = X, 1 = Y, 2 = Z).
+    ///
+    /// [`Facing::South`], the default, is upright, so generated trees and
+    /// logs from saves without facings stand vertically.  East and West lie
+    /// along X, North along Z.
+    pub fn log_axis(self) -> usize {
+        match self {
+            Facing::South => 1,
+            Facing::East | Facing::West => 0,
+            Facing::North => 2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grass_has_a_texture_per_face() {
+        let face = |face| BlockType::Grass.texture_for_face(face, Facing::default());
+        let (bottom, top) = (face(2), face(3));
+        assert_eq!(top, TEX_GRASS_TOP);
+        assert_eq!(bottom, TEX_DIRT);
+        for side in [0, 1, 4, 5] {
+            assert_eq!(face(side), TEX_GRASS_SIDE);
+        }
+        assert!(top != bottom && top != face(0) && bottom != face(0));
+
+        // Upright logs show end grain on top and bottom, bark on the sides.
+        let log = |face| BlockType::Wood.texture_for_face(face, Facing::default());
+        assert_eq!(
+            (log(2), log(3), log(0)),
+            (TEX_WOOD_TOP, TEX_WOOD_TOP, TEX_WOOD_SIDE)
+        );
+    }
+
+    #[test]
+    fn every_face_texture_is_an_atlas_layer() {
+        for block in BlockType::ALL {
+            for face in 0..6 {
+                let layer = block.texture_for_face(face, Facing::default());
+                assert!((0.0..ATLAS_LAYERS as f32).contains(&layer), "{block:?}");
+            }
         }
+        // Glass has its own clear tile rather than borrowing the ice one.
+        assert_ne!(BlockType::Glass.tex_top(), BlockType::Ice.tex_top());
     }
 }
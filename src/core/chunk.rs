@@ -1,7 +1,8 @@
 use glam::Vec3;
+use rustc_hash::FxHashMap;
 
 use crate::constants::*;
-use crate::core::block::BlockType;
+use crate::core::block::{BlockType, Facing};
 use crate::frustum::AABB;
 
 /// A fixed-height vertical slice of a [`Chunk`].
@@ -26,10 +27,17 @@ pub struct SubChunk {
 
     /// `true` when the GPU mesh is out of date and needs to be rebuilt.
     ///
-    /// Set to `true` on construction and on every [`SubChunk::set_block`] call.
-    /// Cleared by the meshing system after a successful upload.
+    /// Set to `true` on construction and by [`SubChunk::mark_mesh_dirty`].
+    /// Cleared by [`SubChunk::clear_mesh_dirty`] after a successful upload.
     pub mesh_dirty: bool,
 
+    /// Incremented every time the sub-chunk is marked dirty.
+    ///
+    /// Mesh workers record the version they built from, so a mesh that was
+    /// already in flight when the sub-chunk changed again does not clear
+    /// the newer dirty mark.
+    pub mesh_version: u32,
+
     /// Number of solid-geometry indices in the current GPU mesh.
     ///
     /// Used by the render pass to issue the correct `draw_indexed` call.
@@ -53,6 +61,23 @@ pub struct SubChunk {
     /// When `true`, neighboring sub-chunks can skip rendering faces that are
     /// adjacent to this one. Updated by [`SubChunk::check_fully_opaque`].
     pub is_fully_opaque: bool,
+
+    /// Orientation of directional blocks (stairs), keyed by
+    /// [`SubChunk::local_index`].
+    ///
+    /// Stored sparsely because only a handful of blocks per sub-chunk are
+    /// ever directional; blocks without an entry use [`Facing::default`].
+    /// Entries are dropped whenever [`SubChunk::set_block`] overwrites the
+    /// block.
+    pub facings: FxHashMap<u16, Facing>,
+
+    /// Fill level of water blocks that are not full, keyed by
+    /// [`SubChunk::local_index`], in `1..WATER_MAX_LEVEL`.
+    ///
+    /// Stored sparsely like [`Self::facings`]: generated water is always
+    /// full, and only water that has flowed gets an entry.  Entries are
+    /// dropped whenever [`SubChunk::set_block`] overwrites the block.
+    pub water_levels: FxHashMap<u16, u8>,
 }
 
 impl SubChunk {
@@ -74,7 +99,10 @@ impl SubChunk {
                 CHUNK_SIZE as usize],
             is_empty: true,
             is_fully_opaque: false,
+            facings: FxHashMap::default(),
+            water_levels: FxHashMap::default(),
             mesh_dirty: true,
+            mesh_version: 0,
             num_indices: 0,
             num_water_indices: 0,
             aabb: AABB::new(
@@ -109,13 +137,93 @@ impl SubChunk {
     pub fn set_block(&mut self, x: i32, y: i32, z: i32, block: BlockType) {
         if x >= 0 && x < CHUNK_SIZE && y >= 0 && y < SUBCHUNK_HEIGHT && z >= 0 && z < CHUNK_SIZE {
             self.blocks[x as usize][y as usize][z as usize] = block;
-            self.mesh_dirty = true;
+            self.mark_mesh_dirty();
             if block != BlockType::Air {
                 self.is_empty = false;
             }
+            if !self.facings.is_empty() {
+                self.facings.remove(&Self::local_index(x, y, z));
+            }
+            if !self.water_levels.is_empty() {
+                self.water_levels.remove(&Self::local_index(x, y, z));
+            }
         }
     }
 
+    /// Packs in-bounds local coordinates into the key used by
+    /// [`Self::facings`] (x-major, then y, then z).
+    pub fn local_index(x: i32, y: i32, z: i32) -> u16 {
+        ((x * SUBCHUNK_HEIGHT + y) * CHUNK_SIZE + z) as u16
+    }
+
+    /// Returns the facing of the block at local position `(x, y, z)`, or
+    /// [`Facing::default`] if none was stored.
+    pub fn get_facing(&self, x: i32, y: i32, z: i32) -> Facing {
+        self.facings
+            .get(&Self::local_index(x, y, z))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Stores the facing of the block at local position `(x, y, z)` and marks
+    /// the mesh dirty.  Out-of-bounds writes are silently ignored.
+    ///
+    /// Must be called **after** [`Self::set_block`], which clears it.
+    pub fn set_facing(&mut self, x: i32, y: i32, z: i32, facing: Facing) {
+        if (0..CHUNK_SIZE).contains(&x)
+            && (0..SUBCHUNK_HEIGHT).contains(&y)
+            && (0..CHUNK_SIZE).contains(&z)
+        {
+            self.facings.insert(Self::local_index(x, y, z), facing);
+            self.mark_mesh_dirty();
+        }
+    }
+
+    /// Returns the fill level of the water block at local position
+    /// `(x, y, z)`: `0` if it is not water, otherwise `1..=WATER_MAX_LEVEL`.
+    pub fn get_water_level(&self, x: i32, y: i32, z: i32) -> u8 {
+        if self.get_block(x, y, z) != BlockType::Water {
+            return 0;
+        }
+        self.water_levels
+            .get(&Self::local_index(x, y, z))
+            .copied()
+            .unwrap_or(WATER_MAX_LEVEL)
+    }
+
+    /// Stores the fill level of the water block at local position
+    /// `(x, y, z)` and marks the mesh dirty.  Out-of-bounds writes are
+    /// silently ignored.
+    ///
+    /// Must be called **after** [`Self::set_block`], which clears it.
+    /// [`WATER_MAX_LEVEL`] is stored as the absence of an entry.
+    pub fn set_water_level(&mut self, x: i32, y: i32, z: i32, level: u8) {
+        if (0..CHUNK_SIZE).contains(&x)
+            && (0..SUBCHUNK_HEIGHT).contains(&y)
+            && (0..CHUNK_SIZE).contains(&z)
+        {
+            let index = Self::local_index(x, y, z);
+            if level >= WATER_MAX_LEVEL {
+                self.water_levels.remove(&index);
+            } else {
+                self.water_levels.insert(index, level);
+            }
+            self.mark_mesh_dirty();
+        }
+    }
+
+    /// Flags the mesh for a rebuild and bumps [`Self::mesh_version`].
+    pub fn mark_mesh_dirty(&mut self) {
+        self.mesh_dirty = true;
+        self.mesh_version = self.mesh_version.wrapping_add(1);
+    }
+
+    /// Clears the dirty flag after a mesh built from `built_version` was
+    /// uploaded, unless the sub-chunk has changed again since.
+    pub fn clear_mesh_dirty(&mut self, built_version: u32) {
+        self.mesh_dirty = self.mesh_version != built_version;
+    }
+
     /// Scans all blocks and updates [`Self::is_empty`].
     ///
     /// Prefer this over relying solely on the incremental flag when blocks may
@@ -155,6 +263,38 @@ impl SubChunk {
     }
 }
 
+/// Level of detail a chunk column is meshed at.
+///
+/// Distant columns are meshed [`Coarse`](MeshLod::Coarse), with every
+/// 2×2×2 group of blocks drawn as one cube, which cuts their geometry where
+/// the lost detail is too small to see.  See [`MeshLod::for_distance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MeshLod {
+    /// One cube per block.
+    #[default]
+    Full,
+    /// One cube per 2×2×2 blocks, see `World::build_coarse_subchunk_mesh`.
+    Coarse,
+}
+
+impl MeshLod {
+    /// LOD for a column `distance` chunks from the player's column
+    /// (Chebyshev distance), given the LOD it is meshed at now.
+    ///
+    /// Columns turn coarse at [`MESH_LOD_DISTANCE`] but only return to full
+    /// detail one chunk nearer, so walking back and forth across a chunk
+    /// border does not remesh the ring at the threshold every time.
+    pub fn for_distance(current: MeshLod, distance: i32) -> MeshLod {
+        if distance >= MESH_LOD_DISTANCE {
+            MeshLod::Coarse
+        } else if distance < MESH_LOD_DISTANCE - 1 {
+            MeshLod::Full
+        } else {
+            current
+        }
+    }
+}
+
 /// A full-height vertical column of [`SubChunk`]s at a fixed `(x, z)` position.
 ///
 /// A chunk spans [`WORLD_HEIGHT`] blocks vertically, divided into
@@ -170,6 +310,18 @@ pub struct Chunk {
     /// Used to prioritize saving and to distinguish generated chunks from
     /// player-modified ones.
     pub player_modified: bool,
+
+    /// Height of the topmost [opaque](BlockType::is_opaque) block of each
+    /// column, indexed as `heightmap[x][z]`, or `-1` for a column without
+    /// one.
+    ///
+    /// Kept up to date by [`Chunk::set_block`]; read it through
+    /// [`Chunk::opaque_height`].
+    heightmap: [[i32; CHUNK_SIZE as usize]; CHUNK_SIZE as usize],
+
+    /// Detail the column's sub-chunks are meshed at, chosen by distance
+    /// from the player in `World::update_mesh_lods`.
+    pub mesh_lod: MeshLod,
 }
 
 impl Chunk {
@@ -184,6 +336,8 @@ impl Chunk {
         Chunk {
             subchunks,
             player_modified: false,
+            heightmap: [[-1; CHUNK_SIZE as usize]; CHUNK_SIZE as usize],
+            mesh_lod: MeshLod::Full,
         }
     }
 
@@ -203,13 +357,135 @@ impl Chunk {
     /// Sets the block at world-space column-local position `(x, y, z)`.
     ///
     /// `x` and `z` are in chunk-local space `[0, CHUNK_SIZE)`.
-    /// Silently ignores writes where `y` is outside `[0, WORLD_HEIGHT)`.
+    /// Silently ignores writes outside the chunk.
+    ///
+    /// Also updates the column's [opaque height](Self::opaque_height): an
+    /// opaque block above it raises it, and removing the block at it scans
+    /// down for the next opaque one.
     pub fn set_block(&mut self, x: i32, y: i32, z: i32, block: BlockType) {
-        if y < 0 || y >= WORLD_HEIGHT {
+        if !(0..WORLD_HEIGHT).contains(&y)
+            || !(0..CHUNK_SIZE).contains(&x)
+            || !(0..CHUNK_SIZE).contains(&z)
+        {
             return;
         }
         let subchunk_idx = (y / SUBCHUNK_HEIGHT) as usize;
         let local_y = y % SUBCHUNK_HEIGHT;
         self.subchunks[subchunk_idx].set_block(x, local_y, z, block);
+
+        let top = self.heightmap[x as usize][z as usize];
+        if block.is_opaque() && y > top {
+            self.heightmap[x as usize][z as usize] = y;
+        } else if !block.is_opaque() && y == top {
+            self.heightmap[x as usize][z as usize] = (0..y)
+                .rev()
+                .find(|&below| self.get_block(x, below, z).is_opaque())
+                .unwrap_or(-1);
+        }
+    }
+
+    /// Returns the height of the topmost [opaque](BlockType::is_opaque)
+    /// block in column `(x, z)`, or `None` if the column has none.
+    ///
+    /// A cached lookup, unlike [`Self::surface_block`], which scans the
+    /// column and also stops at transparent blocks.  `x` and `z` are in
+    /// chunk-local space `[0, CHUNK_SIZE)`; other columns return `None`.
+    pub fn opaque_height(&self, x: i32, z: i32) -> Option<i32> {
+        if !(0..CHUNK_SIZE).contains(&x) || !(0..CHUNK_SIZE).contains(&z) {
+            return None;
+        }
+        let top = self.heightmap[x as usize][z as usize];
+        (top >= 0).then_some(top)
+    }
+
+    /// Returns the facing of the block at column-local position `(x, y, z)`.
+    pub fn get_facing(&self, x: i32, y: i32, z: i32) -> Facing {
+        if !(0..WORLD_HEIGHT).contains(&y) {
+            return Facing::default();
+        }
+        let subchunk_idx = (y / SUBCHUNK_HEIGHT) as usize;
+        let local_y = y % SUBCHUNK_HEIGHT;
+        self.subchunks[subchunk_idx].get_facing(x, local_y, z)
+    }
+
+    /// Stores the facing of the block at column-local position `(x, y, z)`.
+    ///
+    /// Silently ignores writes where `y` is outside `[0, WORLD_HEIGHT)`.
+    pub fn set_facing(&mut self, x: i32, y: i32, z: i32, facing: Facing) {
+        if !(0..WORLD_HEIGHT).contains(&y) {
+            return;
+        }
+        let subchunk_idx = (y / SUBCHUNK_HEIGHT) as usize;
+        let local_y = y % SUBCHUNK_HEIGHT;
+        self.subchunks[subchunk_idx].set_facing(x, local_y, z, facing);
+    }
+
+    /// Returns the water fill level at column-local position `(x, y, z)`;
+    /// see [`SubChunk::get_water_level`].
+    pub fn get_water_level(&self, x: i32, y: i32, z: i32) -> u8 {
+        if !(0..WORLD_HEIGHT).contains(&y) {
+            return 0;
+        }
+        let subchunk_idx = (y / SUBCHUNK_HEIGHT) as usize;
+        let local_y = y % SUBCHUNK_HEIGHT;
+        self.subchunks[subchunk_idx].get_water_level(x, local_y, z)
+    }
+
+    /// Stores the water fill level at column-local position `(x, y, z)`.
+    ///
+    /// Silently ignores writes where `y` is outside `[0, WORLD_HEIGHT)`.
+    pub fn set_water_level(&mut self, x: i32, y: i32, z: i32, level: u8) {
+        if !(0..WORLD_HEIGHT).contains(&y) {
+            return;
+        }
+        let subchunk_idx = (y / SUBCHUNK_HEIGHT) as usize;
+        let local_y = y % SUBCHUNK_HEIGHT;
+        self.subchunks[subchunk_idx].set_water_level(x, local_y, z, level);
+    }
+
+    /// Returns the height and type of the topmost non-air block in column
+    /// `(x, z)`, or `None` if the column is all air.
+    ///
+    /// `x` and `z` are in chunk-local space `[0, CHUNK_SIZE)`.  Empty
+    /// sub-chunks are skipped without reading their blocks.
+    pub fn surface_block(&self, x: i32, z: i32) -> Option<(i32, BlockType)> {
+        for (sy, subchunk) in self.subchunks.iter().enumerate().rev() {
+            if subchunk.is_empty {
+                continue;
+            }
+            for local_y in (0..SUBCHUNK_HEIGHT).rev() {
+                let block = subchunk.get_block(x, local_y, z);
+                if block != BlockType::Air {
+                    return Some((sy as i32 * SUBCHUNK_HEIGHT + local_y, block));
+                }
+            }
+        }
+        None
+    }
+
+    /// Sum of the sub-chunks' [`SubChunk::mesh_version`]s.
+    ///
+    /// Changes whenever any sub-chunk is marked dirty, so callers that cache
+    /// something derived from the blocks can tell when to rebuild it.
+    pub fn content_version(&self) -> u32 {
+        self.subchunks.iter().fold(0u32, |sum, subchunk| {
+            sum.wrapping_add(subchunk.mesh_version)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surface_block_finds_the_topmost_block() {
+        let mut chunk = Chunk::new(0, 0);
+        assert_eq!(chunk.surface_block(3, 5), None);
+
+        chunk.set_block(3, 10, 5, BlockType::Stone);
+        chunk.set_block(3, 70, 5, BlockType::Water);
+        assert_eq!(chunk.surface_block(3, 5), Some((70, BlockType::Water)));
+        assert_eq!(chunk.surface_block(4, 5), None);
     }
 }
@@ -149,4 +149,10 @@ register_items! {
     "minecraft:ice" => "Ice", true, 64, None, 100;
     "minerust:cactus" => "Cactus", true, 64, None, 1;
     "minerust:WoodStairs" => "Wood Stairs", true, 64, None, 5;
+    "minerust:StoneSlab" => "Stone Slab", true, 64, None, 3;
+    "minerust:StoneStairs" => "Stone Stairs", true, 64, None, 5;
+    "minerust:glass" => "Glass", true, 64, None, 1;
+    "minerust:bed" => "Bed", true, 1, None, 1;
+    "minerust:cobblestone" => "Cobblestone", true, 64, None, 1;
+    "minerust:torch" => "Torch", true, 64, None, 1;
 }
@@ -1 +1,40 @@
+use rand::{random, random_range};
 
+use crate::core::mobs::entity::MobEntity;
+
+/// Wandering state of a passive mob.
+///
+/// The mob alternates between standing still and walking in a straight line
+/// in a random direction; each phase lasts a random few seconds.  There is no
+/// pathfinding: obstacles are only handled by the jump in the mob's physics.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Wander {
+    /// `true` while walking, `false` while idling.
+    pub walking: bool,
+    /// Seconds left in the current phase.
+    pub timer: f32,
+}
+
+/// Advances the wandering AI by `dt` seconds and sets the mob's horizontal
+/// velocity and facing accordingly.
+pub fn think(mob: &mut MobEntity, dt: f32) {
+    mob.wander.timer -= dt;
+    if mob.wander.timer <= 0.0 {
+        mob.wander.walking = random::<f32>() < 0.6;
+        if mob.wander.walking {
+            mob.yaw = random_range(0.0..std::f32::consts::TAU);
+            mob.wander.timer = random_range(2.0..5.0);
+        } else {
+            mob.wander.timer = random_range(1.0..4.0);
+        }
+    }
+
+    if mob.wander.walking {
+        // Yaw 0 faces +Z, matching how the model is rotated.
+        mob.velocity.x = -mob.yaw.sin() * mob.kind.walk_speed;
+        mob.velocity.z = mob.yaw.cos() * mob.kind.walk_speed;
+    } else {
+        mob.velocity.x = 0.0;
+        mob.velocity.z = 0.0;
+    }
+}
@@ -0,0 +1,116 @@
+use glam::Vec3;
+
+use super::ai::passive_mob::{self, Wander};
+use super::mob::Mob;
+use crate::constants::{MOB_GRAVITY, MOB_MAX_FALL_SPEED, PLAYER_HEIGHT, PLAYER_WIDTH};
+use crate::player::camera::{body_intersects_box, check_body_collision};
+use crate::world::World;
+
+/// A live mob in the world: one of the registered [`Mob`] kinds plus its
+/// physical state.
+///
+/// Mobs are humanoid and share the player's body size, halved for
+/// [`MobVariant::Baby`](super::mob::MobVariant::Baby).  They are spawned,
+/// updated and despawned by [`World::update_mobs`].
+#[derive(Debug, Clone)]
+pub struct MobEntity {
+    /// What kind of mob this is.
+    pub kind: &'static Mob,
+    /// Foot-level world-space position; the bottom center of the body.
+    pub position: Vec3,
+    pub velocity: Vec3,
+    /// Facing in radians, using the player model convention (0 = +Z).
+    pub yaw: f32,
+    /// `true` when resting on a solid surface.
+    pub on_ground: bool,
+    /// Wandering AI state.
+    pub wander: Wander,
+}
+
+impl MobEntity {
+    /// Creates a mob of `kind` standing still at `position`, facing `yaw`.
+    pub fn new(kind: &'static Mob, position: Vec3, yaw: f32) -> Self {
+        Self {
+            kind,
+            position,
+            velocity: Vec3::ZERO,
+            yaw,
+            on_ground: false,
+            wander: Wander::default(),
+        }
+    }
+
+    /// Half of the body's horizontal extent, like [`PLAYER_WIDTH`].
+    pub fn half_width(&self) -> f32 {
+        PLAYER_WIDTH * self.kind.variant.scale()
+    }
+
+    /// Height of the body.
+    pub fn height(&self) -> f32 {
+        PLAYER_HEIGHT * self.kind.variant.scale()
+    }
+
+    /// Returns `true` if this mob's body at `pos` would overlap a solid block.
+    pub fn collides_at(&self, world: &World, pos: Vec3) -> bool {
+        check_body_collision(world, pos, self.half_width(), self.height())
+    }
+
+    /// Returns `true` if this mob's body overlaps the block at `(bx, by, bz)`.
+    pub fn intersects_block(&self, bx: i32, by: i32, bz: i32) -> bool {
+        let min = Vec3::new(bx as f32, by as f32, bz as f32);
+        body_intersects_box(
+            self.position,
+            self.half_width(),
+            self.height(),
+            min,
+            min + Vec3::ONE,
+        )
+    }
+
+    /// Runs the AI and then moves the mob by `dt` seconds.
+    pub fn update(&mut self, world: &World, dt: f32) {
+        passive_mob::think(self, dt);
+        self.step_physics(world, dt);
+    }
+
+    /// Applies gravity and moves the body one axis at a time, stopping at
+    /// solid blocks the same way [`Camera::update`](crate::player::Camera::update)
+    /// does.  A mob walking into a wall while on the ground jumps, which is
+    /// enough to climb single-block steps.
+    fn step_physics(&mut self, world: &World, dt: f32) {
+        self.velocity.y = (self.velocity.y - MOB_GRAVITY * dt).max(-MOB_MAX_FALL_SPEED);
+        let new_pos = self.position + self.velocity * dt;
+
+        let mut blocked = false;
+        let next_x = Vec3::new(new_pos.x, self.position.y, self.position.z);
+        if !self.collides_at(world, next_x) {
+            self.position.x = new_pos.x;
+        } else {
+            blocked = true;
+        }
+        let next_z = Vec3::new(self.position.x, self.position.y, new_pos.z);
+        if !self.collides_at(world, next_z) {
+            self.position.z = new_pos.z;
+        } else {
+            blocked = true;
+        }
+        let next_y = Vec3::new(self.position.x, new_pos.y, self.position.z);
+        if !self.collides_at(world, next_y) {
+            self.position.y = new_pos.y;
+            self.on_ground = false;
+        } else {
+            if self.velocity.y < 0.0 {
+                self.on_ground = true;
+            }
+            self.velocity.y = 0.0;
+        }
+
+        if blocked && self.on_ground {
+            // A little above `jump_height` so the body clears the step.
+            self.velocity.y = (2.0 * MOB_GRAVITY * (self.kind.jump_height + 0.25)).sqrt();
+            self.on_ground = false;
+        }
+
+        self.position.y = self.position.y.max(1.0);
+    }
+}
@@ -1,18 +1,31 @@
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MobType {
     Neutral,
     Hostile,
     Passive,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MobVariant {
     Normal,
     Baby,
     Elder,
 }
 
+impl MobVariant {
+    /// Size of the body and model relative to a normal adult.
+    pub fn scale(self) -> f32 {
+        match self {
+            MobVariant::Baby => 0.5,
+            MobVariant::Normal | MobVariant::Elder => 1.0,
+        }
+    }
+}
+
+#[derive(Debug)]
 pub struct Mob {
     pub id: &'static str,
     pub name: &'static str,
@@ -23,6 +36,10 @@ pub struct Mob {
     pub damage: f32,
     pub mob_type: MobType,
     pub variant: MobVariant,
+    /// Color of the head and arms.
+    pub skin_color: [f32; 3],
+    /// Color of the torso; the legs use a darker shade of it.
+    pub body_color: [f32; 3],
 }
 
 pub static MOBS: Lazy<HashMap<&'static str, &'static Mob>> = Lazy::new(|| {
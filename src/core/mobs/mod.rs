@@ -1,4 +1,5 @@
 pub mod ai;
+pub mod entity;
 pub mod mob;
 pub mod mobs_registry;
 mod villager;
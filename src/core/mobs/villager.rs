@@ -10,6 +10,8 @@ pub static VILLAGER: &Mob = &Mob {
     damage: 0.0,
     mob_type: MobType::Passive,
     variant: MobVariant::Normal,
+    skin_color: [0.8, 0.6, 0.45],
+    body_color: [0.45, 0.3, 0.2],
 };
 
 pub static BABY_VILLAGER: &Mob = &Mob {
@@ -22,4 +24,6 @@ pub static BABY_VILLAGER: &Mob = &Mob {
     damage: 0.0,
     mob_type: MobType::Passive,
     variant: MobVariant::Baby,
+    skin_color: [0.8, 0.6, 0.45],
+    body_color: [0.45, 0.3, 0.2],
 };
@@ -10,6 +10,8 @@ pub static ZOMBIE: &Mob = &Mob {
     damage: 3.0,
     mob_type: MobType::Hostile,
     variant: MobVariant::Normal,
+    skin_color: [0.35, 0.6, 0.3],
+    body_color: [0.2, 0.55, 0.6],
 };
 
 pub static BABY_ZOMBIE: &Mob = &Mob {
@@ -22,4 +24,6 @@ pub static BABY_ZOMBIE: &Mob = &Mob {
     damage: 4.0,
     mob_type: MobType::Hostile,
     variant: MobVariant::Baby,
+    skin_color: [0.35, 0.6, 0.3],
+    body_color: [0.2, 0.55, 0.6],
 };
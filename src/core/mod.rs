@@ -9,8 +9,9 @@ pub mod vertex;
 pub mod mobs;
 
 pub use biome::Biome;
-pub use block::BlockType;
-pub use chunk::{Chunk, SubChunk};
+pub use block::{BlockType, Facing};
+pub use chunk::{Chunk, MeshLod, SubChunk};
 pub use game_item::GameItem;
-pub use uniforms::{ShadowConfig, Uniforms};
+pub use mobs::entity::MobEntity;
+pub use uniforms::{BloomConfig, ShadowConfig, Uniforms};
 pub use vertex::Vertex;
@@ -95,6 +95,57 @@ pub struct Uniforms {
     /// Used by the sky shader to desaturate the atmosphere and dim the sun
     /// / cloud response under overcast conditions.
     pub rain_factor: f32,
+
+    /// Fraction of the sky covered by clouds in the range `[0.0, 1.0]`,
+    /// already faded out for heavy rain.
+    ///
+    /// Drives the sky shader's cloud layer and the cloud shadows added to
+    /// the terrain shadow mask; `0.0` disables both.
+    pub cloud_coverage: f32,
+    /// Explicit padding to keep the struct 16-byte aligned.
+    pub _pad_clouds: f32,
+
+    /// Ambient light color of the terrain for the current time of day,
+    /// never darker than the player's minimum ambient setting.
+    ///
+    /// Packed with [`Self::day_factor`] to fill a `vec4` alignment slot.
+    pub ambient_color: [f32; 3],
+
+    /// Eased daylight amount in the range `[0.0, 1.0]`, scaling the direct
+    /// sun and fill light on the terrain.
+    pub day_factor: f32,
+
+    /// Tint multiplied onto the lit terrain, warm around sunrise and sunset
+    /// and white otherwise.
+    ///
+    /// Packed with [`Self::_pad_light`] to fill a `vec4` alignment slot.
+    pub dawn_tint: [f32; 3],
+    /// Explicit padding to keep the struct 16-byte aligned.
+    pub _pad_light: f32,
+
+    /// Color of the distance haze on the terrain, following the biome the
+    /// player stands in and darkened at night.
+    ///
+    /// Packed with [`Self::fog_strength`] to fill a `vec4` alignment slot.
+    pub fog_color: [f32; 3],
+
+    /// Haze blended over terrain at [`Self::fog_end`], in `[0.0, 1.0]`.
+    pub fog_strength: f32,
+
+    /// Horizontal distance from the camera, in blocks, where the haze starts.
+    pub fog_start: f32,
+
+    /// Horizontal distance from the camera, in blocks, where the haze
+    /// reaches [`Self::fog_strength`].
+    pub fog_end: f32,
+
+    /// Explicit padding to keep the struct 16-byte aligned.
+    pub _pad_fog: [f32; 2],
+
+    /// Colors of the sky gradient as `[r, g, b, unused]`: the day zenith
+    /// and horizon, the night zenith and horizon, then the dusk colors low
+    /// and high in the sky.
+    pub sky_palette: [[f32; 4]; 6],
 }
 
 /// Small shadow-specific configuration uploaded separately from the main
@@ -109,6 +160,29 @@ pub struct ShadowConfig {
     pub shadow_map_size: f32,
     /// Number of PCF taps used when filtering the shadow map.
     pub pcf_samples: u32,
+    /// Number of cascades rendered this frame (1–4).  The shader never
+    /// selects a cascade at or beyond this index, and treats the last active
+    /// one as the end of shadow coverage.
+    pub cascade_count: u32,
+    /// Explicit padding so the buffer remains 16 bytes wide.
+    pub _pad: u32,
+}
+
+/// Bloom settings shared by the bloom passes and the composite shader.
+///
+/// Uploaded separately like [`ShadowConfig`] and only rewritten when bloom is
+/// toggled, so it does not need to live in the per-frame [`Uniforms`].
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+pub struct BloomConfig {
+    /// Scene brightness above which pixels start to glow.  `1.0` keeps
+    /// regular lit surfaces out of the bloom; only HDR sources exceed it.
+    pub threshold: f32,
+    /// Width of the soft transition below `threshold`.
+    pub knee: f32,
+    /// Strength of the blurred glow added back in the composite pass.
+    /// `0.0` disables bloom.
+    pub intensity: f32,
     /// Explicit padding so the buffer remains 16 bytes wide.
-    pub _pad: [u32; 2],
+    pub _pad: f32,
 }
@@ -20,7 +20,7 @@ use bytemuck::{Pod, Zeroable};
 /// # Packed Data Bits (32 bits total)
 /// | Bits  | Purpose        | Range         |
 /// |-------|----------------|---------------|
-/// | 0-2   | Normal Index   | 0-5 (cardinal)|
+/// | 0-2   | Normal Index   | 0-5 (cardinal), 6 (water surface) |
 /// | 3-10  | Texture Index  | 0-255         |
 /// | 11-12 | UV Corner      | 0-3           |
 /// | 13-18 | Color R (6-bit)| 0-63          |
@@ -35,9 +35,16 @@ pub struct Vertex {
 }
 
 impl Vertex {
+    /// Normal index of the exposed top surface of a water body.
+    ///
+    /// The surface faces +Y like index 3, but `water.wgsl` only animates
+    /// waves on faces with this index, so flat tops (water under a block)
+    /// and submerged faces stay still.
+    pub const WATER_SURFACE_NORMAL: u8 = 6;
+
     /// Packs normal, color, texture, corner, and dimensions into the 32-bit `packed` field.
     pub fn pack(
-        normal_idx: u8,  // 0-5 (3 bits)
+        normal_idx: u8,  // 0-6 (3 bits)
         color: [f32; 3], // 0.0-1.0 (11 bits: 4R, 4G, 3B)
         tex_index: u8,   // 0-255 (8 bits)
         corner_idx: u8,  // 0-3 (2 bits)
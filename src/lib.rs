@@ -51,22 +51,34 @@ pub mod chunk_loader {
     pub use crate::world::loader::*;
 }
 
-mod commands;
+pub mod commands;
 pub mod constants;
 mod logger;
 mod minerust_data;
 pub mod save;
 mod shader_utils;
 
+pub use commands::{ChatCommand, parse_command};
 pub use constants::*;
 pub use constants::{get_active_cascade_count, get_chunk_worker_count, get_mesh_worker_count};
-pub use core::{Biome, BlockType, Chunk, GameItem, ShadowConfig, SubChunk, Uniforms, Vertex};
-pub use player::{Camera, DiggingState, InputState};
+pub use core::{
+    Biome, BlockType, BloomConfig, Chunk, Facing, GameItem, MeshLod, MobEntity, ShadowConfig,
+    SubChunk, Uniforms, Vertex,
+};
+pub use player::{Camera, DiggingState, InputState, Inventory, MovementTuning, RaycastHit};
 pub use render::{
-    AABB, DrawIndexedIndirect, IndirectManager, MeshLoader, SubchunkKey, add_greedy_quad, add_quad,
-    build_block_outline, build_crosshair, build_player_model, extract_frustum_planes,
-    generate_texture_atlas, load_texture_atlas_from_file,
+    AABB, DrawIndexedIndirect, IndirectManager, MAX_PARTICLES, MeshLoader, MeshUploadQueue,
+    Particle, ParticleLook, ParticleSystem, SubchunkKey, add_greedy_quad, add_quad,
+    append_mob_model, append_player_model, build_block_cracks, build_block_outline,
+    build_celestial_path, build_chunk_borders, build_crosshair, build_face_highlight,
+    build_placement_preview, build_player_model, extract_frustum_planes, generate_texture_atlas,
+    load_texture_atlas_from_file,
 };
 pub use save::{DEFAULT_WORLD_FILE, SavedWorld, load_world, save_world};
 pub use vertex::OutlineVertex;
-pub use world::{ChunkGenResult, ChunkGenerator, ChunkLoader, World};
+pub use world::{
+    AppliedMutations, BlockDiff, BlockMutation, BlockMutationQueue, BoxEdit, BoxEditKind,
+    ChunkGenResult, ChunkGenerator, ChunkLoader, DAY_LENGTH, EditHistory, MutationSource,
+    TerrainShape, WaterUpdateQueue, World, WorldGenConfig, WorldPreset, is_night, next_morning,
+    sun_direction, time_difference,
+};
@@ -28,13 +28,20 @@ pub enum ClientEvent {
     PlayerJoined(PlayerId, String),
     /// A remote player left the session.
     PlayerLeft(PlayerId),
+    /// The server's full roster: `(player ID, username, [x, y, z])` for
+    /// every connected player, the local one included.
+    PlayerList(Vec<(PlayerId, String, [f32; 3])>),
     /// A remote player moved to world position `(x, y, z)`.
     PlayerMoved(PlayerId, f32, f32, f32),
     /// A remote player rotated; yaw and pitch are quantized to `u8`
     /// (0 = 0°, 255 ≈ 359°) to reduce bandwidth.
     PlayerRotated(PlayerId, u8, u8),
-    /// A block at `(x, y, z)` was changed to `block_type`.
-    BlockChanged(i32, i32, i32, u8),
+    /// A block at `(x, y, z)` was changed to `block_type`, stored with the
+    /// given facing id.
+    BlockChanged(i32, i32, i32, u8, u8),
+    /// A remote player's progress breaking the block at `(x, y, z)`; the
+    /// last field is the crack stage, `0` once they stopped.
+    PlayerDigging(PlayerId, i32, i32, i32, u8),
     /// A chat message arrived from `player_id`.
     ChatMessage(PlayerId, String),
     /// A pong response arrived; `timestamp` is the Unix-millisecond value
@@ -272,10 +279,22 @@ impl GameClient {
                 y,
                 z,
                 block_type,
+                facing,
             } => {
                 let _ = self
                     .event_tx
-                    .send(ClientEvent::BlockChanged(x, y, z, block_type));
+                    .send(ClientEvent::BlockChanged(x, y, z, block_type, facing));
+            }
+            Packet::DiggingProgress {
+                player_id,
+                x,
+                y,
+                z,
+                stage,
+            } => {
+                let _ = self
+                    .event_tx
+                    .send(ClientEvent::PlayerDigging(player_id, x, y, z, stage));
             }
             Packet::Chat { player_id, message } => {
                 let _ = self
@@ -295,6 +314,9 @@ impl GameClient {
             Packet::Disconnect { player_id } => {
                 let _ = self.event_tx.send(ClientEvent::PlayerLeft(player_id));
             }
+            Packet::PlayerList { players } => {
+                let _ = self.event_tx.send(ClientEvent::PlayerList(players));
+            }
             Packet::Pong { timestamp } => {
                 let _ = self.event_tx.send(ClientEvent::Pong(timestamp));
             }
@@ -396,17 +418,26 @@ impl GameClient {
         }
     }
 
-    /// Notifies the server that block `(x, y, z)` was changed to `block_type`.
+    /// Notifies the server that block `(x, y, z)` was changed to `block_type`
+    /// with facing id `facing`.
     ///
     /// Unlike position and rotation, block changes do not require a connected
     /// `player_id` in the packet (the server identifies the sender from the
     /// connection), so this helper does not gate on `player_id`.
-    pub async fn send_block_change(&self, x: i32, y: i32, z: i32, block_type: u8) -> Result<()> {
+    pub async fn send_block_change(
+        &self,
+        x: i32,
+        y: i32,
+        z: i32,
+        block_type: u8,
+        facing: u8,
+    ) -> Result<()> {
         self.send(&Packet::BlockChange {
             x,
             y,
             z,
             block_type,
+            facing,
         })
         .await
     }
@@ -147,6 +147,58 @@ pub fn connect_to_server(
 // update_network
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Switches the local player to the ID the server assigned in `ConnectAck`,
+/// replacing the provisional `0` (or the ID of an earlier session).
+///
+/// Every remote player is dropped: the entries are left over from a previous
+/// connection, and the server reuses freed IDs, so one of them may now name
+/// someone else or even the local player.  The `PlayerList` the server sends
+/// right after the ack fills the map again.
+pub fn adopt_player_id(
+    my_player_id: &mut u32,
+    remote_players: &mut std::collections::HashMap<u32, RemotePlayer>,
+    assigned: u32,
+) {
+    *my_player_id = assigned;
+    remote_players.clear();
+}
+
+/// Applies a `PlayerList` roster: players missing from it are gone, and new
+/// ones are placed at their last known position so they show up before they
+/// first move.  The local player is skipped.
+fn apply_player_list(
+    players: Vec<(u32, String, [f32; 3])>,
+    my_player_id: u32,
+    remote_players: &mut std::collections::HashMap<u32, RemotePlayer>,
+) {
+    remote_players.retain(|id, _| players.iter().any(|(p, _, _)| p == id));
+    for (player_id, username, [x, y, z]) in players {
+        if player_id == my_player_id {
+            continue;
+        }
+        if let Some(player) = remote_players.get_mut(&player_id) {
+            // Keep the interpolated pose; movement packets are newer than
+            // the list's position.
+            player.username = username;
+        } else {
+            remote_players.insert(player_id, RemotePlayer::new(x, y, z, username));
+        }
+    }
+}
+
+/// What [`update_network`] received for the caller to apply: the new world
+/// seed from a `ConnectAck`, block changes as
+/// `(x, y, z, block id, facing id)`, server
+/// commands as `(issuing player, command)`, whether the server skipped
+/// the night and the latest server clock reading.
+pub type NetworkUpdate = (
+    Option<u32>,
+    Vec<(i32, i32, i32, u8, u8)>,
+    Vec<(u32, String)>,
+    bool,
+    Option<f32>,
+);
+
 /// Per-frame network update: sends the local player's position/rotation and
 /// processes all packets that have arrived since the last frame.
 ///
@@ -170,12 +222,16 @@ pub fn connect_to_server(
 ///
 /// | Packet | Action |
 /// |---|---|
-/// | `ConnectAck { success: true }` | Store assigned `player_id`, transition to `Playing`, capture mouse cursor. |
+/// | `ConnectAck { success: true }` | Adopt the assigned `player_id` and drop stale remote players, transition to `Playing`, capture mouse cursor. |
 /// | `ConnectAck { success: false }` | Transition back to `Menu`. |
 /// | `Position` | Update or insert the remote player's position (self is filtered out). |
 /// | `Rotation` | Update the remote player's yaw/pitch after decoding. |
-/// | `Connect` | Insert or update the remote player's username (used as "player joined" event). |
+/// | `Connect` | Insert or update the remote player's username (used as "player joined" event); ignored for the local ID. |
 /// | `Disconnect` | Remove the remote player from the map. |
+/// | `DiggingProgress` | Set or clear the remote player's cracked block. |
+/// | `Command` | Returned for the caller to run (see `State::run_server_command`). |
+/// | `SkipNight` | Reported to the caller, which runs the clock forward to morning. |
+/// | `TimeSync` | The latest reading is returned for the caller to ease its clock towards. |
 /// | All other packets | Silently ignored (`_ => {}`). |
 ///
 /// # Parameters
@@ -186,10 +242,13 @@ pub fn connect_to_server(
 /// - `last_position_send`  – Timestamp of the last position/rotation send; reset to `Instant::now()` after each send.
 /// - `network_tx`          – Send channel to the Tokio send task; `None` when not connected.
 /// - `network_rx`          – Receive channel from the Tokio receive task; `None` when not connected.
-/// - `remote_players`      – Live map of all known remote players; mutated by Position, Rotation, Connect, Disconnect packets.
+/// - `remote_players`      – Live map of all known remote players; mutated by Position, Rotation, Connect, Disconnect, PlayerList, DiggingProgress packets.
 /// - `game_state`          – Transitioned to `Playing` on `ConnectAck { success: true }` or back to `Menu` on failure.
 /// - `mouse_captured`      – Set to `true` when the game transitions to `Playing` so mouse delta drives camera rotation.
 /// - `window`              – Used to lock the OS cursor when transitioning to `Playing`.
+///
+/// # Returns
+/// Everything the caller must apply to the game state, see [`NetworkUpdate`].
 pub fn update_network(
     my_player_id: &mut u32,
     camera_pos: &glam::Vec3,
@@ -202,9 +261,12 @@ pub fn update_network(
     game_state: &mut GameState,
     mouse_captured: &mut bool,
     window: &Window,
-) -> (Option<u32>, Vec<(i32, i32, i32, u8)>) {
+) -> NetworkUpdate {
     let mut new_seed = None;
     let mut block_changes = Vec::new();
+    let mut commands = Vec::new();
+    let mut skip_night = false;
+    let mut server_time = None;
 
     // ── Outgoing: position and rotation (throttled to 20 Hz) ─────────────── //
     if last_position_send.elapsed().as_millis() > 50 {
@@ -243,7 +305,7 @@ pub fn update_network(
                 // ---- ConnectAck: server accepted or rejected our Connect ---- //
                 Packet::ConnectAck { success, player_id, seed } => {
                     if success {
-                        *my_player_id = player_id;
+                        adopt_player_id(my_player_id, remote_players, player_id);
                         new_seed = Some(seed);
                         log(
                             LogLevel::Info,
@@ -277,9 +339,9 @@ pub fn update_network(
                     // Filter out echoed packets for the local player.
                     if player_id != *my_player_id {
                         if let Some(player) = remote_players.get_mut(&player_id) {
-                            player.x = x;
-                            player.y = y;
-                            player.z = z;
+                            // Stored as the interpolation target; the model
+                            // glides there over the next frames.
+                            player.apply_position(x, y, z);
                         } else {
                             // First position packet for a player we haven't seen
                             // yet — create a placeholder entry.  The username will
@@ -287,14 +349,7 @@ pub fn update_network(
                             // arrives (which may already be queued behind this one).
                             remote_players.insert(
                                 player_id,
-                                RemotePlayer {
-                                    x,
-                                    y,
-                                    z,
-                                    yaw: 0.0,
-                                    pitch: 0.0,
-                                    username: format!("Player{}", player_id),
-                                },
+                                RemotePlayer::new(x, y, z, format!("Player{}", player_id)),
                             );
                         }
                     }
@@ -308,8 +363,7 @@ pub fn update_network(
                 } => {
                     if player_id != *my_player_id {
                         if let Some(player) = remote_players.get_mut(&player_id) {
-                            player.yaw = decode_yaw(yaw);
-                            player.pitch = decode_pitch(pitch);
+                            player.apply_rotation(decode_yaw(yaw), decode_pitch(pitch));
                         }
                         // If the player is unknown we silently drop the rotation
                         // packet; the next Position packet will create the entry.
@@ -331,22 +385,16 @@ pub fn update_network(
                             player_id, username
                         ),
                     );
-                    if let Some(player) = remote_players.get_mut(&player_id) {
+                    if player_id == *my_player_id {
+                        // Never a remote player, whatever the server relays.
+                    } else if let Some(player) = remote_players.get_mut(&player_id) {
                         player.username = username;
                     } else {
                         // Player may not have sent a Position yet; create an
                         // entry at a sensible default world position.
-                        remote_players.insert(
-                            player_id,
-                            RemotePlayer {
-                                x: 0.0,
-                                y: 70.0, // above ground level, not inside terrain
-                                z: 0.0,
-                                yaw: 0.0,
-                                pitch: 0.0,
-                                username,
-                            },
-                        );
+                        // y = 70 keeps it above ground level, not inside terrain.
+                        remote_players
+                            .insert(player_id, RemotePlayer::new(0.0, 70.0, 0.0, username));
                     }
                 }
 
@@ -359,14 +407,49 @@ pub fn update_network(
                     );
                 }
 
+                // ---- PlayerList: the server's roster changed --------------- //
+                // Sent on every join and leave.
+                Packet::PlayerList { players } => {
+                    apply_player_list(players, *my_player_id, remote_players);
+                }
+
                 // ---- BlockChange: remote player broke/placed a block ------- //
                 Packet::BlockChange {
                     x,
                     y,
                     z,
                     block_type,
+                    facing,
+                } => {
+                    block_changes.push((x, y, z, block_type, facing));
+                }
+
+                // ---- DiggingProgress: a remote player is breaking a block -- //
+                Packet::DiggingProgress {
+                    player_id,
+                    x,
+                    y,
+                    z,
+                    stage,
                 } => {
-                    block_changes.push((x, y, z, block_type));
+                    if let Some(player) = remote_players.get_mut(&player_id) {
+                        player.digging = (stage > 0).then_some(((x, y, z), stage));
+                    }
+                }
+
+                // ---- Command: the server approved a console command -------- //
+                Packet::Command { player_id, command } => {
+                    commands.push((player_id, command));
+                }
+
+                // ---- SkipNight: every player is in bed --------------------- //
+                Packet::SkipNight => {
+                    skip_night = true;
+                }
+
+                // ---- TimeSync: the server's day-cycle clock ---------------- //
+                Packet::TimeSync { world_time } => {
+                    server_time = Some(world_time);
                 }
 
                 // Other packet types (Chat, Pong, etc.) are not
@@ -376,5 +459,65 @@ pub fn update_network(
         }
     }
 
-    (new_seed, block_changes)
+    (new_seed, block_changes, commands, skip_night, server_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::multiplayer::server::{GameServer, ServerConfig};
+
+    /// Applies the roster the server broadcasts after a join or leave.
+    async fn sync_roster(
+        server: &GameServer,
+        my_player_id: u32,
+        remote_players: &mut HashMap<u32, RemotePlayer>,
+    ) {
+        let Packet::PlayerList { players } = server.player_list_packet().await else {
+            unreachable!()
+        };
+        apply_player_list(players, my_player_id, remote_players);
+    }
+
+    async fn join(server: &GameServer, username: &str) -> u32 {
+        let id = server.allocate_player_id().await;
+        let connect = Packet::Connect {
+            player_id: id,
+            username: username.to_string(),
+        };
+        server.handle_packet(id, connect).await.unwrap();
+        id
+    }
+
+    #[tokio::test]
+    async fn reconnecting_leaves_no_stale_remote_players() {
+        let server = GameServer::new(ServerConfig::default(), 1);
+        let mut my_player_id = 0;
+        let mut remote_players = HashMap::new();
+
+        let first_id = join(&server, "walker").await;
+        adopt_player_id(&mut my_player_id, &mut remote_players, first_id);
+        let other = join(&server, "idler").await;
+        sync_roster(&server, my_player_id, &mut remote_players).await;
+        assert_ne!(first_id, other);
+        assert!(remote_players.contains_key(&other));
+
+        // The other player leaves while we drop out, and their ID is the
+        // one handed back to us on reconnect.
+        server.remove_player(other).await;
+        server.remove_player(first_id).await;
+        let second_id = join(&server, "walker").await;
+        assert_eq!(second_id, first_id.min(other));
+        adopt_player_id(&mut my_player_id, &mut remote_players, second_id);
+        sync_roster(&server, my_player_id, &mut remote_players).await;
+
+        assert_eq!(my_player_id, second_id);
+        assert!(remote_players.is_empty(), "{:?}", remote_players.keys());
+        let third = join(&server, "idler").await;
+        assert!(third != second_id);
+        sync_roster(&server, my_player_id, &mut remote_players).await;
+        assert_eq!(remote_players.keys().copied().collect::<Vec<_>>(), [third]);
+    }
 }
@@ -1,8 +1,27 @@
+use std::time::Instant;
+
+/// Expected time between two position packets from the same player, in
+/// seconds.  Matches the 20 Hz send rate in `update_network`.
+const INTERPOLATION_INTERVAL: f32 = 0.05;
+
+/// How far past the latest packet a pose may be extrapolated, as a fraction
+/// of [`INTERPOLATION_INTERVAL`].  Beyond this the player simply holds still,
+/// so a lost or late packet cannot send them flying off along their last
+/// velocity.
+const MAX_EXTRAPOLATION: f32 = 0.5;
+
 /// Represents a remote player in the game world, storing their position,
 /// orientation, and display name.
 ///
 /// Received from the server and used for rendering other players
 /// and their nametags in the world.
+///
+/// `x`, `y`, `z` and `yaw` always hold the most recent values received from
+/// the server.  The `prev_*` fields hold the pose the player was displayed
+/// at when that packet arrived, so [`RemotePlayer::interpolated_pose`] can
+/// blend smoothly between the two instead of snapping at the network rate.
+/// Position and rotation arrive in separate packets and are blended on
+/// separate clocks, so turning does not restart the movement blend.
 #[derive(Debug, Clone)]
 pub struct RemotePlayer {
     /// World-space X coordinate.
@@ -17,6 +36,159 @@ pub struct RemotePlayer {
     pub pitch: f32,
     /// The player's display name, shown above their head as a nametag.
     pub username: String,
+    /// Displayed X coordinate at the time of the last update.
+    pub prev_x: f32,
+    /// Displayed Y coordinate at the time of the last update.
+    pub prev_y: f32,
+    /// Displayed Z coordinate at the time of the last update.
+    pub prev_z: f32,
+    /// Displayed yaw at the time of the last update, in radians.
+    pub prev_yaw: f32,
+    /// When the last position packet was applied.
+    pub last_update: Instant,
+    /// When the last rotation packet was applied.
+    pub last_rotation: Instant,
+    /// Block the player is breaking and its crack stage
+    /// (`1..=DIG_STAGES`), if any.
+    pub digging: Option<((i32, i32, i32), u8)>,
+}
+
+impl RemotePlayer {
+    /// Creates a player standing still at `(x, y, z)` facing yaw `0`.
+    pub fn new(x: f32, y: f32, z: f32, username: String) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            yaw: 0.0,
+            pitch: 0.0,
+            username,
+            prev_x: x,
+            prev_y: y,
+            prev_z: z,
+            prev_yaw: 0.0,
+            last_update: Instant::now(),
+            last_rotation: Instant::now(),
+            digging: None,
+        }
+    }
+
+    /// Records a new authoritative position received from the server.
+    ///
+    /// The currently displayed pose becomes the interpolation start point so
+    /// the model continues smoothly from wherever it was drawn last frame.
+    pub fn apply_position(&mut self, x: f32, y: f32, z: f32) {
+        let now = Instant::now();
+        let (prev_x, prev_y, prev_z, _) = self.interpolated_pose(now);
+        self.prev_x = prev_x;
+        self.prev_y = prev_y;
+        self.prev_z = prev_z;
+        self.last_update = now;
+        self.x = x;
+        self.y = y;
+        self.z = z;
+    }
+
+    /// Records a new authoritative look direction received from the server.
+    ///
+    /// Only the yaw blend restarts from the displayed yaw; the position keeps
+    /// blending towards the last position packet.
+    pub fn apply_rotation(&mut self, yaw: f32, pitch: f32) {
+        let now = Instant::now();
+        self.prev_yaw = self.interpolated_pose(now).3;
+        self.last_rotation = now;
+        self.yaw = yaw;
+        self.pitch = pitch;
+    }
+
+    /// Returns the `(x, y, z, yaw)` pose to draw at `now`.
+    ///
+    /// Linearly blends from the `prev_*` pose to the latest packet over one
+    /// [`INTERPOLATION_INTERVAL`], then extrapolates along the same motion
+    /// for at most [`MAX_EXTRAPOLATION`] of an interval.  Position and yaw
+    /// each count from their own latest packet.  Yaw takes the shortest way
+    /// around the circle so a turn through ±π does not spin the model the
+    /// long way round.
+    pub fn interpolated_pose(&self, now: Instant) -> (f32, f32, f32, f32) {
+        let progress = |since: Instant| {
+            let elapsed = now.saturating_duration_since(since).as_secs_f32();
+            (elapsed / INTERPOLATION_INTERVAL).clamp(0.0, 1.0 + MAX_EXTRAPOLATION)
+        };
+        let t = progress(self.last_update);
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        let yaw_delta = (self.yaw - self.prev_yaw + std::f32::consts::PI)
+            .rem_euclid(std::f32::consts::TAU)
+            - std::f32::consts::PI;
+
+        (
+            lerp(self.prev_x, self.x),
+            lerp(self.prev_y, self.y),
+            lerp(self.prev_z, self.z),
+            self.prev_yaw + yaw_delta * progress(self.last_rotation),
+        )
+    }
+}
+
+/// Nametag color used when per-player colors are off.
+pub const DEFAULT_NAMETAG_COLOR: [f32; 3] = [0.3, 1.0, 0.3];
+
+/// Returns the color that identifies player `id` on both their model's shirt
+/// and their nametag.
+///
+/// Hues are spaced by the golden ratio, so consecutive ids (the server hands
+/// them out in order) land far apart on the color wheel and the same id
+/// always gets the same color on every client.
+pub fn player_color(id: u32) -> [f32; 3] {
+    const GOLDEN_RATIO_CONJUGATE: f32 = 0.618_034;
+    const SATURATION: f32 = 0.65;
+    const VALUE: f32 = 0.95;
+
+    let hue = (id as f32 * GOLDEN_RATIO_CONJUGATE).fract() * 6.0;
+    let chroma = VALUE * SATURATION;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = VALUE - chroma;
+    [r + m, g + m, b + m]
+}
+
+/// Returns the nametag color of player `id`: [`player_color`] when
+/// per-player colors are on, [`DEFAULT_NAMETAG_COLOR`] otherwise.
+pub fn nametag_color(id: u32, player_colors: bool) -> [f32; 3] {
+    if player_colors {
+        player_color(id)
+    } else {
+        DEFAULT_NAMETAG_COLOR
+    }
+}
+
+/// Returns the name and nametag color of every player in the session,
+/// ordered by ID, for the player list overlay.
+///
+/// The local player, who is not in `remote_players`, is listed as
+/// `local_name` under `local_id`.
+pub fn roster(
+    remote_players: &std::collections::HashMap<u32, RemotePlayer>,
+    local_id: u32,
+    local_name: &str,
+    player_colors: bool,
+) -> Vec<(String, [f32; 3])> {
+    let mut players: Vec<(u32, &str)> = remote_players
+        .iter()
+        .map(|(&id, player)| (id, player.username.as_str()))
+        .chain(std::iter::once((local_id, local_name)))
+        .collect();
+    players.sort_unstable_by_key(|&(id, _)| id);
+    players
+        .into_iter()
+        .map(|(id, name)| (name.to_string(), nametag_color(id, player_colors)))
+        .collect()
 }
 
 /// A resolved screen-space label for a remote player, ready to be passed
@@ -31,12 +203,14 @@ pub struct PlayerLabel {
     pub screen_x: f32,
     /// Vertical screen position in pixels (origin at top edge).
     pub screen_y: f32,
+    /// Text color, matching the player's model when per-player colors are on.
+    pub color: [f32; 3],
 }
 
 /// Projects all remote players' nametag positions into screen space and
 /// returns a list of labels ready for rendering.
 ///
-/// For each player a point `2.2` units above their feet is transformed by
+/// For each player a point `2.2` units above their interpolated feet position is transformed by
 /// `view_proj` into clip space. Players behind the camera (`w ≤ 0`) are
 /// culled and produce no label. The surviving clip-space positions are
 /// converted to pixel coordinates using the standard NDC-to-screen mapping:
@@ -52,6 +226,8 @@ pub struct PlayerLabel {
 /// * `view_proj`      - Combined view–projection matrix for the local camera.
 /// * `width`          - Render target width in pixels.
 /// * `height`         - Render target height in pixels.
+/// * `player_colors`  - Color each label with [`player_color`] instead of
+///   [`DEFAULT_NAMETAG_COLOR`].
 ///
 /// # Returns
 ///
@@ -62,12 +238,16 @@ pub fn queue_remote_players_labels(
     view_proj: &glam::Mat4,
     width: f32,
     height: f32,
+    player_colors: bool,
 ) -> Vec<PlayerLabel> {
     let mut labels = Vec::new();
+    let now = Instant::now();
 
-    for (_id, player) in remote_players {
-        // Place the label origin slightly above the player's head.
-        let pos = glam::Vec4::new(player.x, player.y + 2.2, player.z, 1.0);
+    for (&id, player) in remote_players {
+        // Place the label origin slightly above the player's head, following
+        // the same interpolated pose the model is drawn at.
+        let (x, y, z, _) = player.interpolated_pose(now);
+        let pos = glam::Vec4::new(x, y + 2.2, z, 1.0);
         let clip_pos = *view_proj * pos;
 
         // Cull players behind the camera; w ≤ 0 means the point is at or
@@ -80,8 +260,32 @@ pub fn queue_remote_players_labels(
                 username: player.username.clone(),
                 screen_x,
                 screen_y,
+                color: nametag_color(id, player_colors),
             });
         }
     }
     labels
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn turning_does_not_restart_the_movement_blend() {
+        let mut player = RemotePlayer::new(0.0, 0.0, 0.0, "Walker".to_string());
+        player.apply_position(1.0, 0.0, 0.0);
+        let moved_at = player.last_update;
+        player.apply_rotation(1.0, 0.0);
+
+        // Halfway through the position blend, and a rotation arriving later
+        // leaves it there.
+        let halfway = moved_at + Duration::from_secs_f32(INTERPOLATION_INTERVAL / 2.0);
+        let (x, ..) = player.interpolated_pose(halfway);
+        assert!((x - 0.5).abs() < 1e-3, "{}", x);
+        assert_eq!(player.last_update, moved_at);
+        let done = moved_at + Duration::from_secs_f32(INTERPOLATION_INTERVAL);
+        assert!((player.interpolated_pose(done).0 - 1.0).abs() < 1e-3);
+    }
+}
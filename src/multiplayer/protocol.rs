@@ -72,6 +72,26 @@ pub enum Packet {
         pitch: u8,
     },
 
+    /// Sent by a client when the local player died and was put back at the
+    /// world spawn point.
+    ///
+    /// The server checks the position against its own spawn point and, if
+    /// it matches, moves the player there without applying the speed cap
+    /// (see `GameServer::check_packet`).  Not relayed; the `Position`
+    /// packets that follow move the player on other clients.
+    ///
+    /// Packet ID: `0x12`
+    Respawn {
+        /// The player who respawned.
+        player_id: PlayerId,
+        /// World-space X coordinate of the spawn point (f32 LE).
+        x: f32,
+        /// World-space Y coordinate of the spawn point (f32 LE).
+        y: f32,
+        /// World-space Z coordinate of the spawn point (f32 LE).
+        z: f32,
+    },
+
     /// Notifies clients that a single block in the world has changed.
     ///
     /// Packet ID: `0x20`
@@ -84,6 +104,30 @@ pub enum Packet {
         z: i32,
         /// Numeric block type ID. `0` conventionally represents air.
         block_type: u8,
+        /// Orientation of stairs and logs as a `Facing` id (see
+        /// `Facing::ALL`); the default facing for other blocks.
+        facing: u8,
+    },
+
+    /// Reports how far a player has got breaking a block, so other players
+    /// can draw the cracks on it.
+    ///
+    /// Sent only when the target or stage changes, at most every
+    /// `DIG_PROGRESS_SEND_INTERVAL` seconds.
+    ///
+    /// Packet ID: `0x21`
+    DiggingProgress {
+        /// The player doing the breaking.
+        player_id: PlayerId,
+        /// Block X coordinate (i32 LE).
+        x: i32,
+        /// Block Y coordinate (i32 LE).
+        y: i32,
+        /// Block Z coordinate (i32 LE).
+        z: i32,
+        /// Crack stage, `1..=DIG_STAGES`.  `0` means the player stopped
+        /// digging (block broken or dig cancelled) and clears the cracks.
+        stage: u8,
     },
 
     /// A chat message sent by a player.
@@ -96,6 +140,21 @@ pub enum Packet {
         message: String,
     },
 
+    /// A console command that runs on the server (see
+    /// `ChatCommand::runs_on_server`), as typed without the leading `/`.
+    ///
+    /// Sent by a client to ask for the command; the server checks it and
+    /// sends it back, to the issuer or to everyone, as the command each
+    /// client then runs locally.
+    ///
+    /// Packet ID: `0x31`
+    Command {
+        /// The player who issued the command.
+        player_id: PlayerId,
+        /// UTF-8 command line, length-prefixed on the wire.
+        command: String,
+    },
+
     /// Signals that a player has left the session.
     ///
     /// Packet ID: `0x40`
@@ -104,6 +163,51 @@ pub enum Packet {
         player_id: PlayerId,
     },
 
+    /// Everyone currently connected, sent by the server to all clients
+    /// whenever a player joins or leaves.
+    ///
+    /// Lets clients show players who have not moved since they joined.
+    /// Wire payload: a `u16 LE` entry count, then per entry the player ID,
+    /// the length-prefixed username and the `x`, `y`, `z` position (f32 LE).
+    ///
+    /// Packet ID: `0x41`
+    PlayerList {
+        /// `(player ID, username, [x, y, z])` for every connected player.
+        players: Vec<(PlayerId, String, [f32; 3])>,
+    },
+
+    /// Sent by a client when the local player gets into or out of a bed.
+    ///
+    /// The server counts the players in bed and sends
+    /// [`Packet::SkipNight`] once all of them are.
+    ///
+    /// Packet ID: `0x50`
+    SleepState {
+        /// The player who got into or out of bed.
+        player_id: PlayerId,
+        /// `true` when the player lay down, `false` when they got up.
+        in_bed: bool,
+    },
+
+    /// Sent by the server to everyone once every connected player is in
+    /// bed; each client runs its clock forward to the next morning.
+    ///
+    /// Packet ID: `0x51`
+    SkipNight,
+
+    /// Sent by the server to everyone every few seconds, and to a newcomer
+    /// on joining, with the authoritative day-cycle clock.  Clients ease
+    /// their own clock towards it.
+    ///
+    /// Wire payload: the clock reading as an `f32 LE`.
+    ///
+    /// Packet ID: `0x52`
+    TimeSync {
+        /// Seconds on the server's day-cycle clock, within
+        /// `0.0..DAY_LENGTH`; see `sun_direction`.
+        world_time: f32,
+    },
+
     /// Latency probe sent to the remote peer. Expects a matching [`Packet::Pong`].
     ///
     /// Packet ID: `0xFE`
@@ -131,9 +235,16 @@ impl Packet {
             Packet::ConnectAck { .. } => 0x02,
             Packet::Position { .. } => 0x10,
             Packet::Rotation { .. } => 0x11,
+            Packet::Respawn { .. } => 0x12,
             Packet::BlockChange { .. } => 0x20,
+            Packet::DiggingProgress { .. } => 0x21,
             Packet::Chat { .. } => 0x30,
+            Packet::Command { .. } => 0x31,
             Packet::Disconnect { .. } => 0x40,
+            Packet::PlayerList { .. } => 0x41,
+            Packet::SleepState { .. } => 0x50,
+            Packet::SkipNight => 0x51,
+            Packet::TimeSync { .. } => 0x52,
             Packet::Ping { .. } => 0xFE,
             Packet::Pong { .. } => 0xFF,
         }
@@ -167,7 +278,7 @@ impl Packet {
                 buf.extend_from_slice(&player_id.to_le_bytes());
                 buf.extend_from_slice(&seed.to_le_bytes());
             }
-            Packet::Position { player_id, x, y, z } => {
+            Packet::Position { player_id, x, y, z } | Packet::Respawn { player_id, x, y, z } => {
                 buf.extend_from_slice(&player_id.to_le_bytes());
                 buf.extend_from_slice(&x.to_le_bytes());
                 buf.extend_from_slice(&y.to_le_bytes());
@@ -187,19 +298,56 @@ impl Packet {
                 y,
                 z,
                 block_type,
+                facing,
             } => {
                 buf.extend_from_slice(&x.to_le_bytes());
                 buf.extend_from_slice(&y.to_le_bytes());
                 buf.extend_from_slice(&z.to_le_bytes());
                 buf.push(*block_type);
+                buf.push(*facing);
+            }
+            Packet::DiggingProgress {
+                player_id,
+                x,
+                y,
+                z,
+                stage,
+            } => {
+                buf.extend_from_slice(&player_id.to_le_bytes());
+                buf.extend_from_slice(&x.to_le_bytes());
+                buf.extend_from_slice(&y.to_le_bytes());
+                buf.extend_from_slice(&z.to_le_bytes());
+                buf.push(*stage);
             }
             Packet::Chat { player_id, message } => {
                 buf.extend_from_slice(&player_id.to_le_bytes());
                 write_string(&mut buf, message);
             }
+            Packet::Command { player_id, command } => {
+                buf.extend_from_slice(&player_id.to_le_bytes());
+                write_string(&mut buf, command);
+            }
             Packet::Disconnect { player_id } => {
                 buf.extend_from_slice(&player_id.to_le_bytes());
             }
+            Packet::PlayerList { players } => {
+                buf.extend_from_slice(&(players.len() as u16).to_le_bytes());
+                for (player_id, username, position) in players {
+                    buf.extend_from_slice(&player_id.to_le_bytes());
+                    write_string(&mut buf, username);
+                    for coord in position {
+                        buf.extend_from_slice(&coord.to_le_bytes());
+                    }
+                }
+            }
+            Packet::SleepState { player_id, in_bed } => {
+                buf.extend_from_slice(&player_id.to_le_bytes());
+                buf.push(if *in_bed { 1 } else { 0 });
+            }
+            Packet::SkipNight => {}
+            Packet::TimeSync { world_time } => {
+                buf.extend_from_slice(&world_time.to_le_bytes());
+            }
             Packet::Ping { timestamp } | Packet::Pong { timestamp } => {
                 buf.extend_from_slice(&timestamp.to_le_bytes());
             }
@@ -269,6 +417,13 @@ impl Packet {
                 let z = read_f32(&mut cursor)?;
                 Ok(Packet::Position { player_id, x, y, z })
             }
+            0x12 => {
+                let player_id = read_u32(&mut cursor)?;
+                let x = read_f32(&mut cursor)?;
+                let y = read_f32(&mut cursor)?;
+                let z = read_f32(&mut cursor)?;
+                Ok(Packet::Respawn { player_id, x, y, z })
+            }
             0x11 => {
                 let player_id = read_u32(&mut cursor)?;
                 let mut angles = [0u8; 2];
@@ -283,13 +438,29 @@ impl Packet {
                 let x = read_i32(&mut cursor)?;
                 let y = read_i32(&mut cursor)?;
                 let z = read_i32(&mut cursor)?;
-                let mut bt = [0u8; 1];
-                cursor.read_exact(&mut bt)?;
+                let mut block = [0u8; 2];
+                cursor.read_exact(&mut block)?;
                 Ok(Packet::BlockChange {
                     x,
                     y,
                     z,
-                    block_type: bt[0],
+                    block_type: block[0],
+                    facing: block[1],
+                })
+            }
+            0x21 => {
+                let player_id = read_u32(&mut cursor)?;
+                let x = read_i32(&mut cursor)?;
+                let y = read_i32(&mut cursor)?;
+                let z = read_i32(&mut cursor)?;
+                let mut stage = [0u8; 1];
+                cursor.read_exact(&mut stage)?;
+                Ok(Packet::DiggingProgress {
+                    player_id,
+                    x,
+                    y,
+                    z,
+                    stage: stage[0],
                 })
             }
             0x30 => {
@@ -297,10 +468,45 @@ impl Packet {
                 let message = read_string(&mut cursor)?;
                 Ok(Packet::Chat { player_id, message })
             }
+            0x31 => {
+                let player_id = read_u32(&mut cursor)?;
+                let command = read_string(&mut cursor)?;
+                Ok(Packet::Command { player_id, command })
+            }
             0x40 => {
                 let player_id = read_u32(&mut cursor)?;
                 Ok(Packet::Disconnect { player_id })
             }
+            0x41 => {
+                let mut count = [0u8; 2];
+                cursor.read_exact(&mut count)?;
+                // Not pre-allocated from the count: a corrupt frame would
+                // otherwise ask for up to 65535 entries before failing.
+                let mut players = Vec::new();
+                for _ in 0..u16::from_le_bytes(count) {
+                    let player_id = read_u32(&mut cursor)?;
+                    let username = read_string(&mut cursor)?;
+                    let x = read_f32(&mut cursor)?;
+                    let y = read_f32(&mut cursor)?;
+                    let z = read_f32(&mut cursor)?;
+                    players.push((player_id, username, [x, y, z]));
+                }
+                Ok(Packet::PlayerList { players })
+            }
+            0x50 => {
+                let player_id = read_u32(&mut cursor)?;
+                let mut b = [0u8; 1];
+                cursor.read_exact(&mut b)?;
+                Ok(Packet::SleepState {
+                    player_id,
+                    in_bed: b[0] != 0,
+                })
+            }
+            0x51 => Ok(Packet::SkipNight),
+            0x52 => {
+                let world_time = read_f32(&mut cursor)?;
+                Ok(Packet::TimeSync { world_time })
+            }
             0xFE => {
                 let timestamp = read_u64(&mut cursor)?;
                 Ok(Packet::Ping { timestamp })
@@ -415,3 +621,30 @@ fn read_i32(cursor: &mut Cursor<&[u8]>) -> Result<i32> {
     cursor.read_exact(&mut bytes)?;
     Ok(i32::from_le_bytes(bytes))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_changes_keep_their_facing() {
+        let packet = Packet::BlockChange {
+            x: -3,
+            y: 70,
+            z: 12,
+            block_type: 17,
+            facing: 3,
+        };
+        let Packet::BlockChange {
+            x,
+            y,
+            z,
+            block_type,
+            facing,
+        } = Packet::from_bytes(&packet.to_bytes()).unwrap()
+        else {
+            panic!("decoded to another packet");
+        };
+        assert_eq!((x, y, z, block_type, facing), (-3, 70, 12, 17, 3));
+    }
+}
@@ -1,11 +1,38 @@
 #![allow(dead_code)]
 
+use crate::logger::{LogLevel, log};
 use crate::multiplayer::protocol::{Packet, PlayerId};
 use crate::multiplayer::transport::TransportType;
-use std::collections::HashMap;
+use glam::Vec3;
+use minerust::{
+    BlockType, CHUNK_SIZE, ChatCommand, ChunkGenerator, DAY_LENGTH, DIG_STAGES, Facing, Inventory,
+    RENDER_DISTANCE, SUBCHUNK_HEIGHT, SavedWorld, WORLD_HEIGHT, World, is_night, load_world,
+    next_morning, parse_command, save_world,
+};
+use std::collections::{BTreeSet, HashMap};
 use std::io::Result;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::{RwLock, mpsc};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock, mpsc};
+
+/// Longest chat message relayed, in characters; longer ones are cut off.
+/// Matches the client's chat input limit.
+pub const MAX_CHAT_LENGTH: usize = 128;
+
+/// Longest username accepted, in characters; longer ones are cut off.
+/// Matches the limit of the menu's username field.
+pub const MAX_USERNAME_LENGTH: usize = 16;
+
+/// Extra time, in seconds, added to the interval between two `Position`
+/// packets before applying the speed cap, so updates that arrive bunched up
+/// after network jitter are not mistaken for teleports.
+const POSITION_LATENCY_ALLOWANCE: f32 = 0.5;
+
+/// Farthest, in blocks horizontally, a `Respawn` packet may put a player
+/// from the server's own spawn point.
+const RESPAWN_TOLERANCE: f32 = 1.0;
 
 /// Server-side snapshot of a connected player's state.
 ///
@@ -29,6 +56,13 @@ pub struct PlayerInfo {
     /// Compressed pitch angle. `0`–`255` maps to `-90°`–`+90°`.
     /// See `encode_pitch` / `decode_pitch` in the protocol module.
     pub pitch: u8,
+    /// When the last accepted [`Packet::Position`] (or the `Connect`, a
+    /// teleport or a [`Packet::Respawn`]) arrived; the speed cap in [`GameServer::check_packet`] measures from
+    /// here.
+    pub last_moved: Instant,
+    /// Whether the player is lying in a bed, from their last
+    /// [`Packet::SleepState`].
+    pub in_bed: bool,
 }
 
 /// High-level game events emitted by [`GameServer::handle_packet`].
@@ -46,26 +80,132 @@ pub enum ServerEvent {
     /// A player's look direction has changed. Fields: `(id, yaw, pitch)`.
     /// Both angles use the compressed byte encoding from the protocol module.
     PlayerRotated(PlayerId, u8, u8),
-    /// A block in the world has been placed or broken.
+    /// A block edit passed validation and was applied to the server world.
     /// Fields: `(x, y, z, block_type)`.
     BlockChanged(i32, i32, i32, u8),
     /// A player has sent a chat message. Fields: `(id, message)`.
     ChatMessage(PlayerId, String),
 }
 
-/// Core multiplayer server: maintains connected player state and converts
-/// incoming [`Packet`]s into [`ServerEvent`]s.
+/// Player-edited blocks by position, as `(block id, facing id)`.
+type ModifiedBlocks = HashMap<(i32, i32, i32), (u8, u8)>;
+
+/// A `BlockChange` received from a client, queued until the next tick.
+#[derive(Debug, Clone, Copy)]
+pub struct BlockEditRequest {
+    /// Player that sent the edit.
+    pub player_id: PlayerId,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    /// Requested block, as the raw network id.
+    pub block_type: u8,
+    /// Requested facing, as the raw network id.
+    pub facing: u8,
+}
+
+/// Hands out unique player IDs, reusing the ones freed by disconnects.
+///
+/// The lowest freed ID is handed out first, so IDs stay small on a
+/// long-running server; a fresh ID is only taken when none is free.  An ID
+/// is never handed out again until it has been released.
+#[derive(Debug)]
+struct PlayerIdAllocator {
+    /// Lowest ID never handed out.  Starts at `1`; `0` is what clients send
+    /// before they know their ID.
+    next: PlayerId,
+    /// Released IDs waiting to be reused.
+    free: BTreeSet<PlayerId>,
+}
+
+impl PlayerIdAllocator {
+    fn new() -> Self {
+        Self {
+            next: 1,
+            free: BTreeSet::new(),
+        }
+    }
+
+    fn allocate(&mut self) -> PlayerId {
+        if let Some(id) = self.free.pop_first() {
+            return id;
+        }
+        let id = self.next;
+        self.next += 1;
+        id
+    }
+
+    /// Makes `id` available again.  IDs that were never handed out are
+    /// ignored, as are repeated releases.
+    fn release(&mut self, id: PlayerId) {
+        if id != 0 && id < self.next {
+            self.free.insert(id);
+        }
+    }
+}
+
+/// A packet produced by [`GameServer::tick`] for the transport layer to send.
+#[derive(Debug, Clone)]
+pub enum Outgoing {
+    /// Send to every connected client, including the one that caused it.
+    Broadcast(Packet),
+    /// Send to a single client only.
+    To(PlayerId, Packet),
+}
+
+/// Core multiplayer server: maintains connected player state, owns the
+/// authoritative [`World`] and converts incoming [`Packet`]s into
+/// [`ServerEvent`]s.
 ///
 /// `GameServer` is intentionally transport-agnostic — it receives already-
 /// decoded packets and emits events over an unbounded channel, leaving
 /// network I/O to the transport layer. Shared player state is protected by
 /// an [`RwLock`] so multiple async tasks can read concurrently while writes
 /// remain exclusive.
+///
+/// World changes are not applied as packets arrive.  Block edits are queued
+/// and resolved in [`GameServer::tick`], which the transport layer calls at
+/// the fixed rate from [`ServerConfig::tick_rate`]; the tick returns the
+/// canonical packets to send back out.
+///
+/// With [`ServerConfig::world_path`] set, accepted edits are also written to
+/// that file (at most once per [`ServerConfig::save_interval`]) and restored
+/// by [`GameServer::open`] on the next start.
+///
+/// The server also owns the day-cycle clock.  It runs on with the ticks and
+/// is sent out as [`Packet::TimeSync`], so every client shows the same time
+/// of day.
 pub struct GameServer {
-    /// The network transport this server is running on (TCP, UDP, …).
-    transport_type: TransportType,
+    /// Settings the server was started with.
+    config: ServerConfig,
+    /// Seed of the authoritative world, sent to clients in `ConnectAck`.
+    seed: u32,
+    /// Authoritative world.  Chunks are generated on demand the first time
+    /// an edit touches them; see [`GameServer::load_chunks`].
+    world: Mutex<World>,
+    /// Copy of the world's generator, shared with the blocking tasks that
+    /// generate chunks while the world stays unlocked.
+    generator: Arc<ChunkGenerator>,
+    /// Block edits received since the last tick, in arrival order.
+    pending_edits: Mutex<Vec<BlockEditRequest>>,
+    /// Every block changed by players, keyed by position, holding the
+    /// current block and facing network ids.  Sent to newcomers so they see
+    /// existing edits.
+    modified_blocks: Mutex<ModifiedBlocks>,
+    /// Number of ticks run so far.
+    tick_count: AtomicU64,
+    /// Authoritative day-cycle clock in seconds, as in `State::time_of_day`
+    /// but kept within `0.0..DAY_LENGTH`, so a server running for days on
+    /// end does not lose the precision to advance it.
+    world_time: Mutex<f32>,
+    /// Tick of the oldest edit not yet written to the world file, or `None`
+    /// if the file is up to date.
+    unsaved_since: Mutex<Option<u64>>,
     /// Thread-safe map of all currently connected players, keyed by player ID.
     players: Arc<RwLock<HashMap<PlayerId, PlayerInfo>>>,
+    /// Source of the IDs of new connections; see
+    /// [`GameServer::allocate_player_id`].
+    player_ids: Mutex<PlayerIdAllocator>,
     /// Sender half of the server-event channel. Cloned into async tasks as needed.
     event_tx: mpsc::UnboundedSender<ServerEvent>,
     /// Receiver half of the server-event channel.
@@ -75,18 +215,69 @@ pub struct GameServer {
 }
 
 impl GameServer {
-    /// Creates a new `GameServer` that will accept connections over
-    /// `transport_type`.
+    /// Creates a new `GameServer` for `config` whose world uses `seed`.
+    ///
+    /// Initialises the internal event channel, an empty player map and an
+    /// empty world (chunks are generated lazily).  Call
+    /// [`GameServer::take_event_receiver`] to obtain the event stream before
+    /// starting to process packets.
+    pub fn new(config: ServerConfig, seed: u32) -> Self {
+        Self::with_world(
+            config,
+            World::new_empty_with_seed(seed),
+            HashMap::new(),
+            0.0,
+        )
+    }
+
+    /// Creates a `GameServer` for `config`, restoring the world saved at
+    /// [`ServerConfig::world_path`].
     ///
-    /// Initialises the internal event channel and an empty player map.
-    /// Call [`GameServer::take_event_receiver`] to obtain the event stream
-    /// before starting to process packets.
-    pub fn new(transport_type: TransportType) -> Self {
+    /// Starts a fresh world with a random seed when no path is configured or
+    /// the file does not exist yet; the first accepted edit creates it.
+    ///
+    /// # Errors
+    /// Returns an error if the world file exists but cannot be read.
+    pub fn open(config: ServerConfig) -> std::result::Result<Self, String> {
+        let saved = match &config.world_path {
+            Some(path) if path.exists() => load_world(path)
+                .map_err(|e| format!("Could not load world {}: {}", path.display(), e))?,
+            _ => return Ok(Self::new(config, rand::random())),
+        };
+
+        let mut world = World::new_empty_with_config(saved.seed, saved.sea_level, saved.gen_config);
+        let modified = Self::restore_edits(&mut world, &saved);
+        log(
+            LogLevel::Info,
+            &format!(
+                "Restored {} edited blocks in {} chunks",
+                modified.len(),
+                saved.chunks.len()
+            ),
+        );
+        Ok(Self::with_world(config, world, modified, saved.time_of_day))
+    }
+
+    fn with_world(
+        config: ServerConfig,
+        world: World,
+        modified_blocks: ModifiedBlocks,
+        world_time: f32,
+    ) -> Self {
         let (event_tx, event_rx) = mpsc::unbounded_channel();
 
         Self {
-            transport_type,
+            config,
+            seed: world.seed,
+            generator: Arc::new(world.generator().clone()),
+            world: Mutex::new(world),
+            pending_edits: Mutex::new(Vec::new()),
+            modified_blocks: Mutex::new(modified_blocks),
+            tick_count: AtomicU64::new(0),
+            world_time: Mutex::new(world_time.rem_euclid(DAY_LENGTH)),
+            unsaved_since: Mutex::new(None),
             players: Arc::new(RwLock::new(HashMap::new())),
+            player_ids: Mutex::new(PlayerIdAllocator::new()),
             event_tx,
             event_rx: Some(event_rx),
         }
@@ -103,7 +294,22 @@ impl GameServer {
 
     /// Returns the [`TransportType`] this server was configured with.
     pub fn transport_type(&self) -> TransportType {
-        self.transport_type
+        self.config.transport
+    }
+
+    /// Returns the configuration the server was created with.
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    /// Returns the seed of the server's world.
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// Returns the number of ticks run so far.
+    pub fn tick_count(&self) -> u64 {
+        self.tick_count.load(Ordering::Relaxed)
     }
 
     /// Processes a single decoded packet received from `player_id`.
@@ -116,9 +322,11 @@ impl GameServer {
     /// |------------------|-------------------------|---------------------------|
     /// | `Connect`        | Inserts player at spawn | `PlayerConnected`         |
     /// | `Position`       | Updates `x`, `y`, `z`  | `PlayerMoved`             |
+    /// | `Respawn`        | Updates `x`, `y`, `z`  | `PlayerMoved`             |
     /// | `Rotation`       | Updates `yaw`, `pitch`  | `PlayerRotated`           |
-    /// | `BlockChange`    | —                       | `BlockChanged`            |
+    /// | `BlockChange`    | Queued for next tick    | — (see [`GameServer::tick`]) |
     /// | `Chat`           | —                       | `ChatMessage`             |
+    /// | `SleepState`     | Updates `in_bed`        | —                         |
     /// | `Disconnect`     | Removes player          | `PlayerDisconnected`      |
     ///
     /// New players spawned by `Connect` are placed at `(0, 64, 0)` facing
@@ -139,6 +347,8 @@ impl GameServer {
                     z: 0.0,
                     yaw: 0,
                     pitch: 128, // ~horizontal: maps to 0° pitch
+                    last_moved: Instant::now(),
+                    in_bed: false,
                 };
 
                 {
@@ -151,13 +361,14 @@ impl GameServer {
                     .send(ServerEvent::PlayerConnected(player_id, username));
             }
 
-            Packet::Position { x, y, z, .. } => {
+            Packet::Position { x, y, z, .. } | Packet::Respawn { x, y, z, .. } => {
                 {
                     let mut players = self.players.write().await;
                     if let Some(player) = players.get_mut(&player_id) {
                         player.x = x;
                         player.y = y;
                         player.z = z;
+                        player.last_moved = Instant::now();
                     }
                 }
 
@@ -185,10 +396,16 @@ impl GameServer {
                 y,
                 z,
                 block_type,
+                facing,
             } => {
-                let _ = self
-                    .event_tx
-                    .send(ServerEvent::BlockChanged(x, y, z, block_type));
+                self.pending_edits.lock().await.push(BlockEditRequest {
+                    player_id,
+                    x,
+                    y,
+                    z,
+                    block_type,
+                    facing,
+                });
             }
 
             Packet::Chat { message, .. } => {
@@ -197,6 +414,13 @@ impl GameServer {
                     .send(ServerEvent::ChatMessage(player_id, message));
             }
 
+            Packet::SleepState { in_bed, .. } => {
+                let mut players = self.players.write().await;
+                if let Some(player) = players.get_mut(&player_id) {
+                    player.in_bed = in_bed;
+                }
+            }
+
             Packet::Disconnect { .. } => {
                 {
                     let mut players = self.players.write().await;
@@ -217,15 +441,526 @@ impl GameServer {
         Ok(())
     }
 
+    /// Checks a packet received from `player_id` before it is handled or
+    /// relayed, fixing up what can be fixed in place.
+    ///
+    /// | Packet variant    | Check                                                   |
+    /// |-------------------|---------------------------------------------------------|
+    /// | `Connect`         | Username trimmed, cut to [`MAX_USERNAME_LENGTH`], non-empty. |
+    /// | `Position`        | Finite, near the world and within the speed cap.        |
+    /// | `Respawn`         | Finite, near the world and at the world spawn point.    |
+    /// | `DiggingProgress` | Stage at most `DIG_STAGES`.                              |
+    /// | `Chat`            | Cut to [`MAX_CHAT_LENGTH`] characters.                  |
+    /// | `Command`         | From an operator; a server-run command; a teleport target near the world. |
+    /// | `ConnectAck`      | Always rejected; only the server sends it.              |
+    /// | `PlayerList`      | Always rejected; only the server sends it.              |
+    /// | `SkipNight`       | Always rejected; only the server sends it.              |
+    /// | `TimeSync`        | Always rejected; only the server sends it.              |
+    ///
+    /// Apart from `Connect`, `Ping` and `Pong`, packets from a connection
+    /// that has not sent `Connect` yet are rejected.  Block edits are checked
+    /// later, in [`GameServer::tick`].
+    ///
+    /// The speed cap allows [`ServerConfig::max_player_speed`] blocks per
+    /// second horizontally since the last accepted position, plus
+    /// [`POSITION_LATENCY_ALLOWANCE`].  Falling is not capped.
+    ///
+    /// # Errors
+    /// Returns the reason the packet must be dropped instead of handled.
+    pub async fn check_packet(
+        &self,
+        player_id: PlayerId,
+        packet: &mut Packet,
+    ) -> std::result::Result<(), String> {
+        let players = self.players.read().await;
+        let sender = players.get(&player_id);
+        if sender.is_none()
+            && !matches!(
+                packet,
+                Packet::Connect { .. } | Packet::Ping { .. } | Packet::Pong { .. }
+            )
+        {
+            return Err("sender has not connected".to_string());
+        }
+
+        match packet {
+            Packet::Connect { username, .. } => {
+                *username = truncate_chars(username.trim(), MAX_USERNAME_LENGTH);
+                if username.is_empty() {
+                    return Err("empty username".to_string());
+                }
+            }
+            Packet::ConnectAck { .. } => {
+                return Err("ConnectAck is only sent by the server".to_string());
+            }
+            Packet::PlayerList { .. } => {
+                return Err("PlayerList is only sent by the server".to_string());
+            }
+            Packet::SkipNight => {
+                return Err("SkipNight is only sent by the server".to_string());
+            }
+            Packet::TimeSync { .. } => {
+                return Err("TimeSync is only sent by the server".to_string());
+            }
+            Packet::Position { x, y, z, .. } => {
+                if !(x.is_finite() && y.is_finite() && z.is_finite()) {
+                    return Err("non-finite position".to_string());
+                }
+                if !world_y_is_plausible(*y) {
+                    return Err(format!("y = {} is far outside the world", y));
+                }
+                if let Some(player) = sender {
+                    let distance = (*x - player.x).hypot(*z - player.z);
+                    let elapsed = player.last_moved.elapsed().as_secs_f32();
+                    let allowed =
+                        self.config.max_player_speed * (elapsed + POSITION_LATENCY_ALLOWANCE);
+                    if distance > allowed {
+                        return Err(format!(
+                            "moved {:.1} blocks in {:.2} s (at most {:.1} allowed)",
+                            distance, elapsed, allowed
+                        ));
+                    }
+                }
+            }
+            Packet::Respawn { x, y, z, .. } => {
+                if !(x.is_finite() && y.is_finite() && z.is_finite()) {
+                    return Err("non-finite position".to_string());
+                }
+                if !world_y_is_plausible(*y) {
+                    return Err(format!("y = {} is far outside the world", y));
+                }
+                let (spawn_x, _, spawn_z) = self.generator.find_spawn_point();
+                let distance = (*x - spawn_x).hypot(*z - spawn_z);
+                if distance > RESPAWN_TOLERANCE {
+                    return Err(format!(
+                        "respawned {:.1} blocks away from the spawn point",
+                        distance
+                    ));
+                }
+            }
+            Packet::DiggingProgress { stage, .. } if *stage > DIG_STAGES => {
+                return Err(format!("crack stage {} out of range", stage));
+            }
+            Packet::Chat { message, .. } if message.chars().count() > MAX_CHAT_LENGTH => {
+                *message = truncate_chars(message, MAX_CHAT_LENGTH);
+            }
+            Packet::Command { .. }
+                if !sender.is_some_and(|player| self.config.is_operator(&player.username)) =>
+            {
+                return Err("only operators may run server commands".to_string());
+            }
+            Packet::Command { command, .. } => match parse_command(command) {
+                Ok(ChatCommand::Teleport { y, .. }) if !world_y_is_plausible(y) => {
+                    return Err(format!("teleport to y = {} is far outside the world", y));
+                }
+                Ok(parsed) if parsed.runs_on_server() => {}
+                Ok(_) => return Err(format!("/{} does not run on the server", command)),
+                Err(e) => return Err(e),
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Runs one fixed-timestep server tick.
+    ///
+    /// Drains the queued block edits, generates the chunks they touch that
+    /// are not loaded yet (see [`GameServer::load_chunks`]) and validates
+    /// each one against the world.  Accepted edits are applied, remembered for join-time sync,
+    /// reported as [`ServerEvent::BlockChanged`] and broadcast to everyone
+    /// as the canonical result.  Rejected edits are answered with the
+    /// block's actual state so the sender's optimistic local edit is undone.
+    ///
+    /// The day-cycle clock moves on by one tick interval, and every
+    /// [`ServerConfig::time_sync_interval`] it is broadcast as a
+    /// [`Packet::TimeSync`].
+    ///
+    /// Once the oldest unsaved edit is [`ServerConfig::save_interval`] old,
+    /// the world file is rewritten; a failed save is logged and retried on
+    /// the next tick.
+    ///
+    /// # Returns
+    /// The packets the transport layer should send, in order.
+    pub async fn tick(&self) -> Vec<Outgoing> {
+        let tick = self.tick_count();
+        let requests = std::mem::take(&mut *self.pending_edits.lock().await);
+        let mut outgoing = Vec::with_capacity(requests.len());
+        let mut accepted = false;
+
+        if !requests.is_empty() {
+            self.load_chunks(
+                requests
+                    .iter()
+                    .map(|request| {
+                        (
+                            request.x.div_euclid(CHUNK_SIZE),
+                            request.z.div_euclid(CHUNK_SIZE),
+                        )
+                    })
+                    .collect(),
+            )
+            .await;
+            let players = self.players.read().await;
+            let mut world = self.world.lock().await;
+            let mut modified = self.modified_blocks.lock().await;
+
+            for request in requests {
+                let (x, y, z) = (request.x, request.y, request.z);
+                match Self::validate_edit(&world, &players, &request) {
+                    Ok((block, facing)) => {
+                        world.set_block_player(x, y, z, block);
+                        if block.is_directional() {
+                            world.set_facing(x, y, z, facing);
+                        }
+                        modified.insert((x, y, z), (block as u8, facing as u8));
+                        accepted = true;
+                        let _ = self
+                            .event_tx
+                            .send(ServerEvent::BlockChanged(x, y, z, block as u8));
+                        outgoing.push(Outgoing::Broadcast(Packet::BlockChange {
+                            x,
+                            y,
+                            z,
+                            block_type: block as u8,
+                            facing: facing as u8,
+                        }));
+                    }
+                    Err(reason) => {
+                        log(
+                            LogLevel::Warning,
+                            &format!(
+                                "Rejected block change from player {} at ({}, {}, {}): {}",
+                                request.player_id, x, y, z, reason
+                            ),
+                        );
+                        outgoing.push(Outgoing::To(
+                            request.player_id,
+                            Packet::BlockChange {
+                                x,
+                                y,
+                                z,
+                                block_type: world.get_block(x, y, z) as u8,
+                                facing: world.get_facing(x, y, z) as u8,
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+
+        let world_time = {
+            let mut world_time = self.world_time.lock().await;
+            *world_time =
+                (*world_time + self.config.tick_interval().as_secs_f32()).rem_euclid(DAY_LENGTH);
+            *world_time
+        };
+        if tick.is_multiple_of(self.config.time_sync_interval_ticks()) {
+            outgoing.push(Outgoing::Broadcast(Packet::TimeSync { world_time }));
+        }
+
+        if self.config.world_path.is_some() {
+            let due = {
+                let mut unsaved_since = self.unsaved_since.lock().await;
+                if accepted && unsaved_since.is_none() {
+                    *unsaved_since = Some(tick);
+                }
+                unsaved_since.is_some_and(|since| tick - since >= self.config.save_interval_ticks())
+            };
+            if due && let Err(e) = self.save().await {
+                log(LogLevel::Error, &format!("Failed to save world: {}", e));
+            }
+        }
+
+        self.tick_count.fetch_add(1, Ordering::Relaxed);
+        outgoing
+    }
+
+    /// Runs a console command from `player_id` that passed
+    /// [`GameServer::check_packet`] and returns the `Command` packets that
+    /// make the clients run it.
+    ///
+    /// A teleport also moves the player in the server's table, so the
+    /// positions sent from the new spot pass the speed check, and goes back
+    /// to the issuer only.  A time change sets the server's clock and goes
+    /// to everyone.
+    ///
+    /// # Returns
+    /// The packets the transport layer should send, in order.
+    pub async fn run_command(&self, player_id: PlayerId, command: &str) -> Vec<Outgoing> {
+        let packet = Packet::Command {
+            player_id,
+            command: command.to_string(),
+        };
+        match parse_command(command) {
+            Ok(ChatCommand::Teleport { x, y, z }) => {
+                if let Some(player) = self.players.write().await.get_mut(&player_id) {
+                    player.x = x;
+                    player.y = y;
+                    player.z = z;
+                    player.last_moved = Instant::now();
+                }
+                let _ = self
+                    .event_tx
+                    .send(ServerEvent::PlayerMoved(player_id, x, y, z));
+                vec![Outgoing::To(player_id, packet)]
+            }
+            Ok(ChatCommand::SetTime { time }) => {
+                *self.world_time.lock().await = time.rem_euclid(DAY_LENGTH);
+                vec![Outgoing::Broadcast(packet)]
+            }
+            // Anything else was already rejected by `check_packet`.
+            _ => Vec::new(),
+        }
+    }
+
+    /// Writes every player-modified chunk to [`ServerConfig::world_path`].
+    ///
+    /// The snapshot is taken under the world lock; the file itself is
+    /// written on a blocking thread so the tick task is not stalled on disk
+    /// I/O.  Does nothing when no world path is configured.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be written.  The edits stay
+    /// marked unsaved, so the next tick tries again.
+    pub async fn save(&self) -> std::result::Result<(), String> {
+        let Some(path) = self.config.world_path.clone() else {
+            return Ok(());
+        };
+
+        let (saved, since) = {
+            let world = self.world.lock().await;
+            let since = self.unsaved_since.lock().await.take();
+            let world_time = *self.world_time.lock().await;
+            // A server has no local player of its own; spawn stands in for
+            // one.
+            let saved = SavedWorld::from_world(
+                &world,
+                (0.0, 64.0, 0.0),
+                (0.0, 0.0),
+                false,
+                &Inventory::new(),
+                world_time,
+                1.0,
+            );
+            (saved, since)
+        };
+
+        let result = tokio::task::spawn_blocking(move || save_world(&path, &saved))
+            .await
+            .map_err(|e| format!("Save task failed: {}", e))
+            .and_then(|result| result);
+        if result.is_err()
+            && let Some(since) = since
+        {
+            let mut unsaved_since = self.unsaved_since.lock().await;
+            *unsaved_since = Some(unsaved_since.map_or(since, |current| current.min(since)));
+        }
+        result
+    }
+
+    /// Applies the chunks of `saved` on top of freshly generated terrain in
+    /// `world` and returns every block that differs, keyed like the
+    /// server's `modified_blocks` map.
+    ///
+    /// Saves store whole subchunks, so diffing against the generator is what
+    /// recovers the individual edits; a subchunk missing from the save was
+    /// empty and any generated block inside it must have been removed.
+    fn restore_edits(world: &mut World, saved: &SavedWorld) -> ModifiedBlocks {
+        let mut modified = HashMap::new();
+        for chunk in &saved.chunks {
+            world.ensure_chunk_generated(chunk.cx, chunk.cz);
+            for sy in 0..(WORLD_HEIGHT / SUBCHUNK_HEIGHT) {
+                let blocks = chunk.subchunks.get(&(sy as u8));
+                let facings: HashMap<u16, Facing> = chunk
+                    .facings
+                    .get(&(sy as u8))
+                    .map_or_else(HashMap::new, |facings| facings.iter().copied().collect());
+                let mut i = 0;
+                for lx in 0..CHUNK_SIZE {
+                    for ly in 0..SUBCHUNK_HEIGHT {
+                        for lz in 0..CHUNK_SIZE {
+                            // Same x → y → z order as `SavedWorld::from_world`,
+                            // which is also the facings' local index.
+                            let block = blocks.map_or(BlockType::Air, |blocks| blocks[i]);
+                            let facing = facings.get(&(i as u16)).copied().unwrap_or_default();
+                            i += 1;
+                            let x = chunk.cx * CHUNK_SIZE + lx;
+                            let y = sy * SUBCHUNK_HEIGHT + ly;
+                            let z = chunk.cz * CHUNK_SIZE + lz;
+                            if world.get_block(x, y, z) != block
+                                || world.get_facing(x, y, z) != facing
+                            {
+                                world.set_block_player(x, y, z, block);
+                                if block.is_directional() {
+                                    world.set_facing(x, y, z, facing);
+                                }
+                                modified.insert((x, y, z), (block as u8, facing as u8));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        modified
+    }
+
+    /// Generates every chunk in `chunks` that is not loaded yet and adds it
+    /// to the world.
+    ///
+    /// Generation runs on a blocking task with the world unlocked, so
+    /// connections waiting on the world are not held up meanwhile.  A chunk
+    /// loaded by someone else in the meantime is kept; a failed task is
+    /// logged and leaves its chunks unloaded.
+    async fn load_chunks(&self, mut chunks: Vec<(i32, i32)>) {
+        chunks.sort_unstable();
+        chunks.dedup();
+        {
+            let world = self.world.lock().await;
+            chunks.retain(|key| !world.chunks.contains_key(key));
+        }
+        if chunks.is_empty() {
+            return;
+        }
+
+        let generator = self.generator.clone();
+        let generated = tokio::task::spawn_blocking(move || {
+            chunks
+                .into_iter()
+                .map(|(cx, cz)| ((cx, cz), generator.generate_chunk(cx, cz)))
+                .collect::<Vec<_>>()
+        })
+        .await;
+        match generated {
+            Ok(generated) => {
+                let mut world = self.world.lock().await;
+                for (key, chunk) in generated {
+                    world.chunks.entry(key).or_insert(chunk);
+                }
+            }
+            Err(e) => log(
+                LogLevel::Error,
+                &format!("Chunk generation task failed: {}", e),
+            ),
+        }
+    }
+
+    /// Checks a queued block edit against the world and returns the block
+    /// and facing to store; blocks that are not
+    /// [directional](BlockType::is_directional) get the default facing.
+    ///
+    /// The target chunk must already be loaded; [`GameServer::tick`] loads
+    /// it first, so the check always sees real terrain.
+    ///
+    /// # Errors
+    /// - The sender is not a connected player.
+    /// - `y` is outside the world or the block or facing id is unknown.
+    /// - The target chunk is not loaded.
+    /// - The edit would break or place bedrock.
+    /// - A block would be placed into a connected player; see
+    ///   `World::can_place_block`.
+    fn validate_edit(
+        world: &World,
+        players: &HashMap<PlayerId, PlayerInfo>,
+        request: &BlockEditRequest,
+    ) -> std::result::Result<(BlockType, Facing), String> {
+        if !players.contains_key(&request.player_id) {
+            return Err("sender has not connected".to_string());
+        }
+        if !(0..WORLD_HEIGHT).contains(&request.y) {
+            return Err("outside the world height".to_string());
+        }
+        let block = BlockType::from_id(request.block_type)
+            .ok_or_else(|| format!("unknown block id {}", request.block_type))?;
+        let facing = Facing::from_id(request.facing)
+            .ok_or_else(|| format!("unknown facing id {}", request.facing))?;
+
+        let chunk = (
+            request.x.div_euclid(CHUNK_SIZE),
+            request.z.div_euclid(CHUNK_SIZE),
+        );
+        if !world.chunks.contains_key(&chunk) {
+            return Err("chunk is not loaded".to_string());
+        }
+        let current = world.get_block(request.x, request.y, request.z);
+        if current == BlockType::Bedrock || block == BlockType::Bedrock {
+            return Err("bedrock cannot be edited".to_string());
+        }
+        if block != BlockType::Air {
+            let positions: Vec<Vec3> = players
+                .values()
+                .map(|player| Vec3::new(player.x, player.y, player.z))
+                .collect();
+            if !world.can_place_block(request.x, request.y, request.z, &positions) {
+                return Err("a player is in the way".to_string());
+            }
+        }
+        let facing = if block.is_directional() {
+            facing
+        } else {
+            Facing::default()
+        };
+        Ok((block, facing))
+    }
+
+    /// Returns the current network id of `(x, y, z)` in the server's world,
+    /// generating its chunk first if needed.
+    pub async fn block_at(&self, x: i32, y: i32, z: i32) -> u8 {
+        self.load_chunks(vec![(x.div_euclid(CHUNK_SIZE), z.div_euclid(CHUNK_SIZE))])
+            .await;
+        self.world.lock().await.get_block(x, y, z) as u8
+    }
+
+    /// Returns a `BlockChange` packet for every player-modified block within
+    /// [`ServerConfig::spawn_sync_radius`] chunks of spawn.
+    ///
+    /// Sent to a client right after its `ConnectAck` so a newcomer sees the
+    /// edits other players have already made around spawn.
+    pub async fn spawn_sync_packets(&self) -> Vec<Packet> {
+        let radius = self.config.spawn_sync_radius;
+        self.modified_blocks
+            .lock()
+            .await
+            .iter()
+            .filter(|((x, _, z), _)| {
+                x.div_euclid(CHUNK_SIZE).abs() <= radius && z.div_euclid(CHUNK_SIZE).abs() <= radius
+            })
+            .map(|(&(x, y, z), &(block_type, facing))| Packet::BlockChange {
+                x,
+                y,
+                z,
+                block_type,
+                facing,
+            })
+            .collect()
+    }
+
+    /// Reserves a player ID for a new connection.
+    ///
+    /// The ID is unique among connected players and is sent back to the
+    /// client in `ConnectAck`.  It stays reserved until
+    /// [`GameServer::remove_player`] releases it, after which a later
+    /// connection may get it again.
+    pub async fn allocate_player_id(&self) -> PlayerId {
+        self.player_ids.lock().await.allocate()
+    }
+
+    /// Returns an ID from [`GameServer::allocate_player_id`] that was never
+    /// used, e.g. because accepting the connection failed.
+    pub async fn release_player_id(&self, player_id: PlayerId) {
+        self.player_ids.lock().await.release(player_id);
+    }
+
     /// Forcibly removes a player from the server without requiring a
-    /// [`Packet::Disconnect`] from the client.
+    /// [`Packet::Disconnect`] from the client, and releases their ID for
+    /// reuse.
     ///
     /// Emits a [`ServerEvent::PlayerDisconnected`] event. Intended for use
-    /// when a transport-level error or timeout is detected by the network
-    /// layer.
+    /// when the connection closes, once nothing else will be sent under the
+    /// ID.
     pub async fn remove_player(&self, player_id: PlayerId) {
         let mut players = self.players.write().await;
         players.remove(&player_id);
+        self.release_player_id(player_id).await;
         let _ = self
             .event_tx
             .send(ServerEvent::PlayerDisconnected(player_id));
@@ -241,6 +976,54 @@ impl GameServer {
         players.values().cloned().collect()
     }
 
+    /// Returns a [`Packet::PlayerList`] of every connected player, ordered by
+    /// ID.
+    ///
+    /// Broadcast whenever a player joins or leaves so every client knows the
+    /// full roster, including players who have not moved yet.
+    pub async fn player_list_packet(&self) -> Packet {
+        let players = self.players.read().await;
+        let mut list: Vec<_> = players
+            .values()
+            .map(|p| (p.id, p.username.clone(), [p.x, p.y, p.z]))
+            .collect();
+        list.sort_unstable_by_key(|(id, _, _)| *id);
+        Packet::PlayerList { players: list }
+    }
+
+    /// Returns a [`Packet::SkipNight`] for everyone if every connected
+    /// player is in bed, and gets them all out of bed again.
+    ///
+    /// The server's clock jumps to the next morning at once; clients run
+    /// theirs forward to the same morning.  Checked whenever a player gets
+    /// into bed or leaves, since a player leaving can leave only sleepers
+    /// behind.
+    pub async fn take_night_skip(&self) -> Option<Packet> {
+        let mut players = self.players.write().await;
+        if players.is_empty() || !players.values().all(|player| player.in_bed) {
+            return None;
+        }
+        for player in players.values_mut() {
+            player.in_bed = false;
+        }
+        let mut world_time = self.world_time.lock().await;
+        if is_night(*world_time) {
+            *world_time = next_morning(*world_time).rem_euclid(DAY_LENGTH);
+        }
+        Some(Packet::SkipNight)
+    }
+
+    /// Returns a [`Packet::TimeSync`] with the server's day-cycle clock.
+    ///
+    /// Sent to a client right after its `ConnectAck`, so a newcomer starts
+    /// at the server's time of day instead of waiting for the next
+    /// broadcast.
+    pub async fn time_sync_packet(&self) -> Packet {
+        Packet::TimeSync {
+            world_time: *self.world_time.lock().await,
+        }
+    }
+
     /// Returns the number of currently connected players.
     ///
     /// Acquires a brief read lock on the player map.
@@ -265,6 +1048,27 @@ pub struct ServerConfig {
     /// Maximum number of simultaneously connected players. Connections beyond
     /// this limit should be rejected with a failed [`Packet::ConnectAck`].
     pub max_players: usize,
+    /// Server ticks per second.  Queued block edits are validated and
+    /// broadcast once per tick.
+    pub tick_rate: u32,
+    /// Radius in chunks around spawn whose modified blocks are sent to a
+    /// newly connected client.
+    pub spawn_sync_radius: i32,
+    /// File the world's player edits are saved to and restored from.
+    /// `None` keeps the world in memory only.
+    pub world_path: Option<PathBuf>,
+    /// Fastest horizontal speed, in blocks per second, a client may report
+    /// moving at; faster `Position` updates are dropped as teleports.
+    pub max_player_speed: f32,
+    /// How long an edit may stay unsaved.  Bursts of edits within this
+    /// window are written together, so a busy server is not constantly
+    /// rewriting the file.
+    pub save_interval: Duration,
+    /// How often the day-cycle clock is broadcast to the clients.
+    pub time_sync_interval: Duration,
+    /// Usernames of the players allowed to run server commands such as
+    /// `/tp` and `/time set`; everyone else's are dropped.
+    pub operators: Vec<String>,
 }
 
 impl Default for ServerConfig {
@@ -273,12 +1077,26 @@ impl Default for ServerConfig {
     /// - Port `25565` (conventional Minecraft-style game port)
     /// - TCP transport
     /// - Up to `100` concurrent players
+    /// - `20` ticks per second
+    /// - Join-time sync of edits within the client render distance of spawn
+    /// - Clients may move up to `40` blocks per second horizontally
+    /// - No world file; edits are saved at most every `30` seconds once one
+    ///   is set
+    /// - The clock is sent out every `5` seconds
+    /// - No operators, so nobody may run server commands
     fn default() -> Self {
         Self {
             address: "0.0.0.0".to_string(),
             port: 25565,
             transport: TransportType::Tcp,
             max_players: 100,
+            tick_rate: 20,
+            spawn_sync_radius: RENDER_DISTANCE,
+            max_player_speed: 40.0,
+            world_path: None,
+            save_interval: Duration::from_secs(30),
+            time_sync_interval: Duration::from_secs(5),
+            operators: Vec::new(),
         }
     }
 }
@@ -289,4 +1107,459 @@ impl ServerConfig {
     pub fn bind_address(&self) -> String {
         format!("{}:{}", self.address, self.port)
     }
+
+    /// Returns `true` if `username` is one of the
+    /// [`ServerConfig::operators`], ignoring case.
+    pub fn is_operator(&self, username: &str) -> bool {
+        self.operators
+            .iter()
+            .any(|op| op.eq_ignore_ascii_case(username))
+    }
+
+    /// Length of one server tick.  A `tick_rate` of `0` is treated as `1`.
+    pub fn tick_interval(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.tick_rate.max(1) as f64)
+    }
+
+    /// [`ServerConfig::save_interval`] rounded up to whole ticks.
+    pub fn save_interval_ticks(&self) -> u64 {
+        (self.save_interval.as_secs_f64() * self.tick_rate.max(1) as f64).ceil() as u64
+    }
+
+    /// [`ServerConfig::time_sync_interval`] rounded up to whole ticks, at
+    /// least one.
+    pub fn time_sync_interval_ticks(&self) -> u64 {
+        ((self.time_sync_interval.as_secs_f64() * self.tick_rate.max(1) as f64).ceil() as u64)
+            .max(1)
+    }
+}
+
+/// Whether a player's feet at height `y` are near enough to the world to be
+/// a position worth accepting.
+fn world_y_is_plausible(y: f32) -> bool {
+    let height = WORLD_HEIGHT as f32;
+    (-height..=2.0 * height).contains(&y)
+}
+
+/// Returns the first `max` characters of `text`.
+fn truncate_chars(text: &str, max: usize) -> String {
+    text.chars().take(max).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A server with "walker" connected as player 1 and an operator.
+    async fn connected_server() -> GameServer {
+        let config = ServerConfig {
+            operators: vec!["Walker".to_string()],
+            ..Default::default()
+        };
+        let server = GameServer::new(config, 1);
+        server
+            .handle_packet(
+                1,
+                Packet::Connect {
+                    player_id: 1,
+                    username: "walker".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        server
+    }
+
+    #[tokio::test]
+    async fn bad_packets_are_rejected() {
+        let server = connected_server().await;
+        let mut bad = [
+            Packet::Position {
+                player_id: 1,
+                x: 5000.0,
+                y: 64.0,
+                z: 0.0,
+            },
+            Packet::Position {
+                player_id: 1,
+                x: f32::NAN,
+                y: 64.0,
+                z: 0.0,
+            },
+            Packet::DiggingProgress {
+                player_id: 1,
+                x: 0,
+                y: 0,
+                z: 0,
+                stage: DIG_STAGES + 1,
+            },
+            Packet::ConnectAck {
+                success: true,
+                player_id: 1,
+                seed: 0,
+            },
+            Packet::PlayerList {
+                players: Vec::new(),
+            },
+            Packet::SkipNight,
+            Packet::TimeSync { world_time: 0.0 },
+        ];
+        for packet in &mut bad {
+            assert!(
+                server.check_packet(1, packet).await.is_err(),
+                "{:?}",
+                packet
+            );
+        }
+
+        // Strangers are ignored, while the connected player carries on.
+        let mut chat = Packet::Chat {
+            player_id: 2,
+            message: "hi".to_string(),
+        };
+        assert!(server.check_packet(2, &mut chat).await.is_err());
+        let mut step = Packet::Position {
+            player_id: 1,
+            x: 1.0,
+            y: 64.0,
+            z: 0.0,
+        };
+        assert!(server.check_packet(1, &mut step).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn respawning_resets_the_speed_check() {
+        let server = connected_server().await;
+        let mut far = Packet::Position {
+            player_id: 1,
+            x: 3000.0,
+            y: 80.0,
+            z: 0.0,
+        };
+        assert!(server.check_packet(1, &mut far).await.is_err());
+
+        // Only a respawn at the server's own spawn point is accepted.
+        let mut cheat = Packet::Respawn {
+            player_id: 1,
+            x: 3000.0,
+            y: 80.0,
+            z: 0.0,
+        };
+        assert!(server.check_packet(1, &mut cheat).await.is_err());
+
+        let (x, y, z) = server.generator.find_spawn_point();
+        let mut respawn = Packet::Respawn {
+            player_id: 1,
+            x,
+            y,
+            z,
+        };
+        server.check_packet(1, &mut respawn).await.unwrap();
+        server.handle_packet(1, respawn).await.unwrap();
+        let mut step = Packet::Position {
+            player_id: 1,
+            x: x + 1.0,
+            y,
+            z,
+        };
+        assert!(server.check_packet(1, &mut step).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn teleports_run_on_the_server() {
+        let server = connected_server().await;
+        let mut local_only = Packet::Command {
+            player_id: 1,
+            command: "give stone 64".to_string(),
+        };
+        assert!(server.check_packet(1, &mut local_only).await.is_err());
+
+        let mut teleport = Packet::Command {
+            player_id: 1,
+            command: "tp 3000 80 0".to_string(),
+        };
+        server.check_packet(1, &mut teleport).await.unwrap();
+        let sent = server.run_command(1, "tp 3000 80 0").await;
+        assert!(matches!(
+            sent.as_slice(),
+            [Outgoing::To(1, Packet::Command { .. })]
+        ));
+
+        // The far jump does not trip the speed check afterwards.
+        let mut step = Packet::Position {
+            player_id: 1,
+            x: 3001.0,
+            y: 80.0,
+            z: 0.0,
+        };
+        assert!(server.check_packet(1, &mut step).await.is_ok());
+
+        // Players who are not operators may not teleport or set the time.
+        server
+            .handle_packet(
+                2,
+                Packet::Connect {
+                    player_id: 2,
+                    username: "idler".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        for command in ["tp 3000 80 0", "time set 0"] {
+            let mut packet = Packet::Command {
+                player_id: 2,
+                command: command.to_string(),
+            };
+            assert!(server.check_packet(2, &mut packet).await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn long_text_is_clamped() {
+        let server = connected_server().await;
+        let mut chat = Packet::Chat {
+            player_id: 1,
+            message: "é".repeat(MAX_CHAT_LENGTH * 4),
+        };
+        server.check_packet(1, &mut chat).await.unwrap();
+        let Packet::Chat { message, .. } = chat else {
+            unreachable!()
+        };
+        assert_eq!(message.chars().count(), MAX_CHAT_LENGTH);
+
+        let mut connect = Packet::Connect {
+            player_id: 2,
+            username: format!("  {}  ", "n".repeat(100)),
+        };
+        server.check_packet(2, &mut connect).await.unwrap();
+        let Packet::Connect { username, .. } = connect else {
+            unreachable!()
+        };
+        assert_eq!(username, "n".repeat(MAX_USERNAME_LENGTH));
+    }
+
+    #[tokio::test]
+    async fn the_server_keeps_the_time_of_day() {
+        let server = connected_server().await;
+        let sent = server.tick().await;
+        assert!(matches!(
+            sent.as_slice(),
+            [Outgoing::Broadcast(Packet::TimeSync { .. })]
+        ));
+        assert!(server.tick().await.is_empty());
+
+        // Sleeping through the night moves the server's clock to morning.
+        server.run_command(1, "time set 900").await;
+        assert!(is_night(900.0));
+        server
+            .handle_packet(
+                1,
+                Packet::SleepState {
+                    player_id: 1,
+                    in_bed: true,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(server.take_night_skip().await.is_some());
+        let Packet::TimeSync { world_time } = server.time_sync_packet().await else {
+            unreachable!()
+        };
+        assert_eq!(world_time, next_morning(900.0));
+
+        // However long the server runs, its clock stays within one day.
+        server.run_command(1, "time set 10000000").await;
+        server.tick().await;
+        let Packet::TimeSync { world_time } = server.time_sync_packet().await else {
+            unreachable!()
+        };
+        assert!((0.0..DAY_LENGTH).contains(&world_time));
+    }
+
+    #[tokio::test]
+    async fn blocks_cannot_be_placed_into_players() {
+        // The player stands at (0, 64, 0), filling the cells at y 64 and 65.
+        let server = connected_server().await;
+        for (y, block) in [(65, BlockType::Stone), (66, BlockType::Stone)] {
+            server
+                .handle_packet(
+                    1,
+                    Packet::BlockChange {
+                        x: 0,
+                        y,
+                        z: 0,
+                        block_type: block as u8,
+                        facing: 0,
+                    },
+                )
+                .await
+                .unwrap();
+        }
+        let sent = server.tick().await;
+        let edits: Vec<_> = sent
+            .iter()
+            .filter(|message| !matches!(message, Outgoing::Broadcast(Packet::TimeSync { .. })))
+            .collect();
+        // The first is undone for the sender only, the second goes through.
+        assert!(matches!(
+            edits.as_slice(),
+            [
+                Outgoing::To(1, Packet::BlockChange { y: 65, .. }),
+                Outgoing::Broadcast(Packet::BlockChange { y: 66, .. }),
+            ]
+        ));
+        assert_ne!(server.block_at(0, 65, 0).await, BlockType::Stone as u8);
+    }
+
+    #[tokio::test]
+    async fn edits_survive_a_restart() {
+        let path = std::env::temp_dir().join(format!(
+            "minerust-server-test-{}.minerust",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let config = ServerConfig {
+            world_path: Some(path.clone()),
+            save_interval: Duration::ZERO,
+            ..Default::default()
+        };
+
+        let server = GameServer::open(config.clone()).unwrap();
+        server
+            .handle_packet(
+                1,
+                Packet::Connect {
+                    player_id: 1,
+                    username: "builder".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        server
+            .handle_packet(
+                1,
+                Packet::BlockChange {
+                    x: 3,
+                    y: 200,
+                    z: -5,
+                    block_type: BlockType::StoneStairs as u8,
+                    facing: Facing::West as u8,
+                },
+            )
+            .await
+            .unwrap();
+        server.tick().await;
+        let seed = server.seed();
+        drop(server);
+
+        let restarted = GameServer::open(config).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(restarted.seed(), seed);
+        assert_eq!(
+            restarted.block_at(3, 200, -5).await,
+            BlockType::StoneStairs as u8
+        );
+        let sync = restarted.spawn_sync_packets().await;
+        assert_eq!(sync.len(), 1);
+        assert!(matches!(
+            sync[0],
+            Packet::BlockChange { x: 3, y: 200, z: -5, block_type, facing }
+                if block_type == BlockType::StoneStairs as u8 && facing == Facing::West as u8
+        ));
+    }
+
+    #[tokio::test]
+    async fn player_list_tracks_membership() {
+        let server = connected_server().await;
+        server
+            .handle_packet(
+                2,
+                Packet::Connect {
+                    player_id: 2,
+                    username: "idler".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+
+        // Survives the wire, so clients see the same roster.
+        let bytes = server.player_list_packet().await.to_bytes();
+        let Packet::PlayerList { players } = Packet::from_bytes(&bytes).unwrap() else {
+            panic!("decoded to a different packet");
+        };
+        assert_eq!(
+            players,
+            vec![
+                (1, "walker".to_string(), [0.0, 64.0, 0.0]),
+                (2, "idler".to_string(), [0.0, 64.0, 0.0]),
+            ]
+        );
+
+        server.remove_player(1).await;
+        let Packet::PlayerList { players } = server.player_list_packet().await else {
+            unreachable!()
+        };
+        assert_eq!(players.len(), 1);
+        assert_eq!(players[0].0, 2);
+    }
+
+    #[tokio::test]
+    async fn night_is_skipped_once_everyone_is_in_bed() {
+        let server = connected_server().await;
+        server
+            .handle_packet(
+                2,
+                Packet::Connect {
+                    player_id: 2,
+                    username: "sleeper".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        let sleep = |player_id, in_bed| Packet::SleepState { player_id, in_bed };
+
+        server.handle_packet(2, sleep(2, true)).await.unwrap();
+        assert!(server.take_night_skip().await.is_none());
+
+        // Getting up again before the last player lies down counts.
+        server.handle_packet(1, sleep(1, true)).await.unwrap();
+        server.handle_packet(2, sleep(2, false)).await.unwrap();
+        assert!(server.take_night_skip().await.is_none());
+
+        server.handle_packet(2, sleep(2, true)).await.unwrap();
+        assert!(matches!(
+            server.take_night_skip().await,
+            Some(Packet::SkipNight)
+        ));
+        // Everyone wakes up, so the next night needs everyone again.
+        server.handle_packet(1, sleep(1, true)).await.unwrap();
+        assert!(server.take_night_skip().await.is_none());
+
+        // The last player still awake leaving skips the night too.
+        server.remove_player(2).await;
+        assert!(server.take_night_skip().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn player_ids_are_reused_but_never_shared() {
+        let server = GameServer::new(ServerConfig::default(), 1);
+        let ids = [
+            server.allocate_player_id().await,
+            server.allocate_player_id().await,
+            server.allocate_player_id().await,
+        ];
+        assert_eq!(ids, [1, 2, 3]);
+
+        server.remove_player(2).await;
+        server.remove_player(1).await;
+        // A second release of the same ID must not hand it out twice.
+        server.remove_player(1).await;
+        assert_eq!(server.allocate_player_id().await, 1);
+        assert_eq!(server.allocate_player_id().await, 2);
+        assert_eq!(server.allocate_player_id().await, 4);
+
+        // IDs the allocator never handed out are not taken into the pool.
+        server.release_player_id(9).await;
+        assert_eq!(server.allocate_player_id().await, 5);
+    }
 }
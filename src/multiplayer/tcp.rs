@@ -6,16 +6,16 @@ use std::collections::HashMap;
 use std::io::{Error, ErrorKind, Result};
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::RwLock;
 
 /// Maximum number of bytes accepted for a single packet payload.
 ///
-/// Packets whose length header exceeds this value are rejected with
-/// [`ErrorKind::InvalidData`] to guard against memory exhaustion from
-/// malformed or malicious clients.
+/// Packets whose length header exceeds this value are skipped without being
+/// buffered and rejected with [`ErrorKind::InvalidData`] to guard against
+/// memory exhaustion from malformed or malicious clients.
 const READ_BUFFER_SIZE: usize = 4096;
 
 /// An established, framed TCP connection to a single peer.
@@ -94,11 +94,13 @@ impl TcpConnection {
     /// # Errors
     ///
     /// - [`ErrorKind::NotConnected`] if the connection has been closed.
-    /// - [`ErrorKind::InvalidData`] if the declared packet length exceeds
-    ///   [`READ_BUFFER_SIZE`], guarding against oversized or malformed frames.
-    /// - Any I/O error from the underlying socket reads (e.g.
+    /// - [`ErrorKind::InvalidData`] if the frame was read but rejected: its
+    ///   declared length exceeds [`READ_BUFFER_SIZE`] or
+    ///   [`Packet::from_bytes`] could not decode it.  The whole frame has been
+    ///   consumed, so the connection stays usable and the caller may simply
+    ///   drop the packet and call `recv` again.
+    /// - Any other I/O error from the underlying socket reads (e.g.
     ///   [`ErrorKind::UnexpectedEof`] if the peer closed the connection mid-packet).
-    /// - Any deserialisation error returned by [`Packet::from_bytes`].
     pub async fn recv(&self) -> Result<Packet> {
         if !self.connected.load(Ordering::Relaxed) {
             return Err(Error::new(ErrorKind::NotConnected, "Connection closed"));
@@ -112,6 +114,16 @@ impl TcpConnection {
         let len = u16::from_le_bytes(len_buf) as usize;
 
         if len > READ_BUFFER_SIZE {
+            // Skip the body so the next read starts at a frame boundary.
+            let skipped =
+                tokio::io::copy(&mut (&mut *reader).take(len as u64), &mut tokio::io::sink())
+                    .await?;
+            if skipped < len as u64 {
+                return Err(Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "Connection closed mid-packet",
+                ));
+            }
             return Err(Error::new(ErrorKind::InvalidData, "Packet too large"));
         }
 
@@ -120,7 +132,9 @@ impl TcpConnection {
         data[0..2].copy_from_slice(&len_buf);
         reader.read_exact(&mut data[2..]).await?;
 
-        Packet::from_bytes(&data)
+        // A truncated payload surfaces as `UnexpectedEof` from the decoder;
+        // report it as bad data since the socket itself is fine.
+        Packet::from_bytes(&data).map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))
     }
 
     /// Marks the connection as closed and shuts down the write half of the socket.
@@ -152,10 +166,10 @@ impl TcpConnection {
 
 /// A TCP server that accepts client connections and broadcasts [`Packet`]s.
 ///
-/// Each accepted connection is stored in a shared map keyed by a
-/// monotonically increasing `u32` connection ID. This ID is independent of
-/// the game-level [`PlayerId`](crate::multiplayer::protocol::PlayerId) and is
-/// assigned solely by the transport layer.
+/// Each accepted connection is stored in a shared map keyed by the `u32`
+/// ID passed to [`TcpServer::accept`].  The dedicated server uses the
+/// game-level [`PlayerId`](crate::multiplayer::protocol::PlayerId) handed out
+/// by `GameServer`, so packets can be addressed by player.
 ///
 /// # Lifecycle
 ///
@@ -171,8 +185,6 @@ pub struct TcpServer {
     listener: Option<TcpListener>,
     /// All currently tracked client connections, keyed by connection ID.
     connections: Arc<RwLock<HashMap<u32, Arc<TcpConnection>>>>,
-    /// Monotonically increasing source for connection IDs. Starts at `1`.
-    next_id: AtomicU32,
     /// Set to `false` by [`TcpServer::stop`] to signal the accept loop to exit.
     running: AtomicBool,
 }
@@ -197,27 +209,27 @@ impl TcpServer {
         Ok(Self {
             listener: Some(listener),
             connections: Arc::new(RwLock::new(HashMap::new())),
-            next_id: AtomicU32::new(1),
             running: AtomicBool::new(true),
         })
     }
 
-    /// Waits for the next incoming TCP connection and registers it.
+    /// Waits for the next incoming TCP connection and registers it as `id`.
     ///
-    /// Assigns a unique connection ID, enables `TCP_NODELAY` to reduce
-    /// latency for small packets, wraps the stream in a [`TcpConnection`],
-    /// and inserts it into the internal connection map.
+    /// Enables `TCP_NODELAY` to reduce latency for small packets, wraps the
+    /// stream in a [`TcpConnection`], and inserts it into the internal
+    /// connection map.  The caller must keep `id` unique among registered
+    /// connections until [`TcpServer::remove_client`] is called for it.
     ///
     /// # Returns
     ///
-    /// A `(connection_id, connection)` pair. The caller should spawn an async
-    /// task that loops on [`TcpConnection::recv`] for the returned connection.
+    /// The new connection. The caller should spawn an async task that loops
+    /// on [`TcpConnection::recv`] for it.
     ///
     /// # Errors
     ///
     /// - [`ErrorKind::NotConnected`] if the listener has been taken (server stopped).
     /// - Any I/O error from the underlying `accept` or `set_nodelay` calls.
-    pub async fn accept(&self) -> Result<(u32, Arc<TcpConnection>)> {
+    pub async fn accept(&self, id: u32) -> Result<Arc<TcpConnection>> {
         let listener = self
             .listener
             .as_ref()
@@ -227,7 +239,6 @@ impl TcpServer {
         // Disable Nagle's algorithm so small game packets are sent immediately.
         stream.set_nodelay(true)?;
 
-        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
         let connection = Arc::new(TcpConnection::new(stream, addr));
 
         {
@@ -238,7 +249,7 @@ impl TcpServer {
             LogLevel::Info,
             &format!("[TCP Server] Client {} connected from {}", id, addr),
         );
-        Ok((id, connection))
+        Ok(connection)
     }
 
     /// Sends `packet` to every currently registered client connection.
@@ -286,6 +297,22 @@ impl TcpServer {
         Ok(())
     }
 
+    /// Sends `packet` to the single client with `id`.
+    ///
+    /// A missing client (e.g. one that disconnected since the packet was
+    /// produced) is silently skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the send error if the client exists but the write fails.
+    pub async fn send_to(&self, id: u32, packet: &Packet) -> Result<()> {
+        let conn = self.connections.read().await.get(&id).cloned();
+        match conn {
+            Some(conn) => conn.send(packet).await,
+            None => Ok(()),
+        }
+    }
+
     /// Removes the client with `id` from the connection map.
     ///
     /// Does not close the underlying socket — the caller is responsible for
@@ -444,3 +471,49 @@ impl Default for TcpClient {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a connected `(client stream, server-side connection)` pair.
+    async fn connection_pair() -> (TcpStream, TcpConnection) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (stream, addr) = listener.accept().await.unwrap();
+        (client, TcpConnection::new(stream, addr))
+    }
+
+    #[tokio::test]
+    async fn malformed_frames_are_dropped_without_closing_the_connection() {
+        let (mut client, conn) = connection_pair().await;
+
+        // Unknown packet id.
+        client.write_all(&[1, 0, 0x99]).await.unwrap();
+        // Position packet cut short after the player id.
+        client.write_all(&[5, 0, 0x10, 1, 0, 0, 0]).await.unwrap();
+        // Oversized frame.
+        let oversized = READ_BUFFER_SIZE + 1;
+        client
+            .write_all(&(oversized as u16).to_le_bytes())
+            .await
+            .unwrap();
+        client.write_all(&vec![0u8; oversized]).await.unwrap();
+        // A valid packet afterwards must still arrive.
+        client
+            .write_all(&Packet::Ping { timestamp: 7 }.to_bytes())
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            let err = conn.recv().await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+        assert!(matches!(
+            conn.recv().await.unwrap(),
+            Packet::Ping { timestamp: 7 }
+        ));
+    }
+}
@@ -1,10 +1,46 @@
 use glam::{Mat4, Vec3};
 
 use crate::constants::*;
-use crate::core::block::BlockType;
+use crate::core::block::Facing;
 use crate::player::input::InputState;
 use crate::world::World;
 
+/// Longest distance [`Camera::sweep_axis`] moves the body between
+/// collision tests; below the thinnest collision box (half a block), so no
+/// block can be stepped over.
+const SWEEP_STEP: f32 = 0.25;
+
+/// Bisection rounds used to place the body against the block it hit,
+/// accurate to `SWEEP_STEP / 2^8` ≈ 0.001 blocks.
+const SWEEP_REFINE_ITERATIONS: u32 = 8;
+
+/// Tuning for how horizontal movement reaches its target speed.
+///
+/// With `acceleration` off (the default) the player snaps to full speed and
+/// stops dead, as keyboard movement always has.  With it on, horizontal
+/// velocity ramps toward the target at `ground_acceleration` /
+/// `ground_deceleration` (blocks/s²), and both rates are scaled by
+/// `air_control` while airborne so a jump cannot be fully redirected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovementTuning {
+    pub acceleration: bool,
+    pub ground_acceleration: f32,
+    pub ground_deceleration: f32,
+    /// Fraction of the ground rates available in the air, in `0.0..=1.0`.
+    pub air_control: f32,
+}
+
+impl Default for MovementTuning {
+    fn default() -> Self {
+        Self {
+            acceleration: false,
+            ground_acceleration: PLAYER_GROUND_ACCELERATION,
+            ground_deceleration: PLAYER_GROUND_DECELERATION,
+            air_control: PLAYER_AIR_CONTROL,
+        }
+    }
+}
+
 /// First-person camera that doubles as the player's physical body.
 ///
 /// Owns the player's world-space position, look angles, and physics state.
@@ -27,18 +63,75 @@ pub struct Camera {
     /// moves upward or away from the ground, set when downward collision is detected.
     pub on_ground: bool,
 
-    /// `true` when at least one block overlapping the player's body is [`BlockType::Water`].
+    /// `true` when the player's feet or mid-body are below a water surface.
     ///
     /// Switches the physics constants to underwater values (reduced gravity,
     /// lower speed, swim controls).
     pub in_water: bool,
+
+    /// Acceleration settings applied to horizontal movement.
+    pub movement: MovementTuning,
+
+    /// `true` while flying in creative mode: no gravity or collision, and
+    /// blocks break instantly.  Change it with [`Camera::set_creative`].
+    pub creative: bool,
+
+    /// Seconds of sprinting left, in `0.0..=PLAYER_MAX_STAMINA`.
+    ///
+    /// Drains while sprinting and regenerates otherwise.  When it reaches
+    /// zero, sprinting is disabled until it is back to
+    /// [`PLAYER_STAMINA_RECOVER_FRACTION`] of the maximum.
+    pub stamina: f32,
+
+    /// `true` from the moment stamina runs out until it has recovered.
+    exhausted: bool,
+
+    /// `true` while actually sprinting: moving with sprint held and enough
+    /// stamina, or moving with `fly_sprint` held in creative mode.
+    pub sprinting: bool,
+
+    /// How far away, in blocks, blocks can be broken and placed in survival
+    /// mode.  Starts at [`PLAYER_REACH`].
+    pub reach: f32,
+
+    /// Reach used instead of [`Self::reach`] in creative mode.  Starts at
+    /// [`CREATIVE_REACH`].
+    pub creative_reach: f32,
+
+    /// Vertical field of view to render with, in radians.  Eases toward
+    /// `DEFAULT_FOV + SPRINT_FOV_BOOST` while [`Self::sprinting`] and back
+    /// to [`DEFAULT_FOV`] afterwards.
+    pub fov: f32,
+
+    /// Where the body was left when spectating started; `Some` exactly
+    /// while spectating.  See [`Camera::set_spectating`].
+    spectator_anchor: Option<Vec3>,
+
+    /// Spectator flight speed in blocks per second.  Starts at
+    /// [`SPECTATOR_SPEED`]; change it with
+    /// [`Camera::adjust_spectator_speed`].
+    pub spectator_speed: f32,
+
+    /// Blocks fallen since the player last stood on the ground, swam or
+    /// moved upwards.
+    fall_distance: f32,
+
+    /// A fall that ended on the ground, kept until [`Camera::take_fall`].
+    landed_fall: Option<f32>,
+
+    /// Speed at which the player last entered water, kept until
+    /// [`Camera::take_splash`].
+    splash: Option<f32>,
 }
 
 impl Camera {
     /// Creates a new camera at the given world-space spawn position.
     ///
     /// Yaw and pitch are initialized to `0.0` (looking toward +X).
-    /// Velocity is zero and both `on_ground` and `in_water` are `false`.
+    /// Velocity is zero, both `on_ground` and `in_water` are `false`,
+    /// movement uses the default (instant) [`MovementTuning`], stamina is
+    /// full, reach is [`PLAYER_REACH`] ([`CREATIVE_REACH`] in creative mode)
+    /// and the FOV is [`DEFAULT_FOV`].
     pub fn new(spawn: (f32, f32, f32)) -> Self {
         Camera {
             position: Vec3::new(spawn.0, spawn.1, spawn.2),
@@ -47,9 +140,91 @@ impl Camera {
             velocity: Vec3::ZERO,
             on_ground: false,
             in_water: false,
+            movement: MovementTuning::default(),
+            creative: false,
+            stamina: PLAYER_MAX_STAMINA,
+            exhausted: false,
+            sprinting: false,
+            reach: PLAYER_REACH,
+            creative_reach: CREATIVE_REACH,
+            fov: DEFAULT_FOV,
+            spectator_anchor: None,
+            spectator_speed: SPECTATOR_SPEED,
+            fall_distance: 0.0,
+            landed_fall: None,
+            splash: None,
         }
     }
 
+    /// Switches between creative flight and survival movement.
+    ///
+    /// Vertical velocity is cleared either way, so leaving flight while
+    /// climbing does not carry the climb into survival physics; gravity takes
+    /// over from rest instead.
+    pub fn set_creative(&mut self, creative: bool) {
+        self.creative = creative;
+        self.velocity.y = 0.0;
+        self.on_ground = false;
+        self.fall_distance = 0.0;
+    }
+
+    /// `true` while the camera is detached from the body in spectator mode.
+    pub fn spectating(&self) -> bool {
+        self.spectator_anchor.is_some()
+    }
+
+    /// Detaches the camera from the body for debugging, or puts it back.
+    ///
+    /// While spectating the camera flies freely through blocks (see
+    /// [`Camera::update_spectating`]) and [`Camera::target`] finds nothing,
+    /// so no block can be broken or placed.  The body stays where it was,
+    /// and leaving spectator mode returns the camera there at rest.  The
+    /// game mode is left untouched.
+    pub fn set_spectating(&mut self, spectating: bool) {
+        if spectating == self.spectating() {
+            return;
+        }
+        if spectating {
+            self.spectator_anchor = Some(self.position);
+        } else if let Some(body) = self.spectator_anchor.take() {
+            self.position = body;
+            self.on_ground = false;
+        }
+        self.velocity = Vec3::ZERO;
+        self.sprinting = false;
+        self.fall_distance = 0.0;
+    }
+
+    /// Returns how many blocks the player fell if they landed on the ground
+    /// since the last call, measured from the top of the jump or the edge
+    /// they walked off.  Landing in water ends a fall without reporting it.
+    pub fn take_fall(&mut self) -> Option<f32> {
+        self.landed_fall.take()
+    }
+
+    /// Returns the speed, in blocks per second, at which the player entered
+    /// water if they did since the last call.
+    pub fn take_splash(&mut self) -> Option<f32> {
+        self.splash.take()
+    }
+
+    /// Foot position of the player's body: [`Self::position`], or where the
+    /// body was left while spectating.
+    ///
+    /// Saving, multiplayer and mobs use this, so spectating never moves the
+    /// player as far as the world is concerned.
+    pub fn body_position(&self) -> Vec3 {
+        self.spectator_anchor.unwrap_or(self.position)
+    }
+
+    /// Multiplies [`Self::spectator_speed`] by [`SPECTATOR_SPEED_STEP`] per
+    /// step (negative steps slow down), within
+    /// [`SPECTATOR_MIN_SPEED`]`..=`[`SPECTATOR_MAX_SPEED`].
+    pub fn adjust_spectator_speed(&mut self, steps: f32) {
+        self.spectator_speed = (self.spectator_speed * SPECTATOR_SPEED_STEP.powf(steps))
+            .clamp(SPECTATOR_MIN_SPEED, SPECTATOR_MAX_SPEED);
+    }
+
     /// Returns the horizontal forward unit vector based on the current yaw.
     ///
     /// Y is always `0.0`; use [`Camera::look_direction`] for the full 3-D
@@ -81,64 +256,85 @@ impl Camera {
         Mat4::look_at_rh(eye, target, Vec3::Y)
     }
 
-    /// Returns `true` if the block at the player's feet or mid-body is [`BlockType::Water`].
+    /// Returns the perspective projection for the current [`Self::fov`],
+    /// clipping at [`NEAR_PLANE`] and [`far_plane`]`(RENDER_DISTANCE)`.
     ///
-    /// Checks two sample points: the foot block (`position.y`) and a mid-body
-    /// block (`position.y + 0.9`) to handle partial submersion.
+    /// The one place the projection is built, so the rendered view and the
+    /// shadow cascades fitted to it use the same clip planes.  Uses OpenGL
+    /// depth conventions like [`Self::view_matrix`].
+    pub fn projection_matrix(&self, aspect: f32) -> Mat4 {
+        Mat4::perspective_rh(self.fov, aspect, NEAR_PLANE, far_plane(RENDER_DISTANCE))
+    }
+
+    /// Returns `true` if the player's feet or mid-body are below a water
+    /// surface.
+    ///
+    /// Checks two sample points: the feet (`position.y`) and the mid-body
+    /// (`position.y + 0.9`) to handle partial submersion.  Shallow water only
+    /// counts up to its fill level, see [`World::is_point_in_water`].
     fn check_in_water(&self, world: &World) -> bool {
-        let feet_block = world.get_block(
-            self.position.x.floor() as i32,
-            self.position.y.floor() as i32,
-            self.position.z.floor() as i32,
-        );
-        let body_block = world.get_block(
-            self.position.x.floor() as i32,
-            (self.position.y + 0.9).floor() as i32,
-            self.position.z.floor() as i32,
-        );
-        feet_block == BlockType::Water || body_block == BlockType::Water
-    }
-
-    /// Returns `true` if the block at the eye position is [`BlockType::Water`].
+        world.is_point_in_water(self.position)
+            || world.is_point_in_water(self.position + Vec3::new(0.0, 0.9, 0.0))
+    }
+
+    /// Returns `true` if the eye position is below a water surface.
     ///
     /// Used by the renderer to apply underwater post-processing effects.
     pub fn is_head_underwater(&self, world: &World) -> bool {
-        let eye = self.eye_position();
-        let block = world.get_block(
-            eye.x.floor() as i32,
-            eye.y.floor() as i32,
-            eye.z.floor() as i32,
-        );
-        block == BlockType::Water
+        world.is_point_in_water(self.eye_position())
     }
 
     /// Advances the player simulation by one frame.
     ///
-    /// Each call performs the following steps in order:
+    /// While spectating this hands off to [`Camera::update_spectating`], and
+    /// in creative mode to [`Camera::update_flying`].  Otherwise each call performs the following steps in order:
     /// 1. Detects water submersion via [`Camera::check_in_water`].
-    /// 2. Select physics constants (speed, gravity, drag) based on water state and sprint input.
+    /// 2. Select physics constants (speed, gravity, drag) based on water state and
+    ///    whether the player sprints, see [`Camera::update_stamina`].
     /// 3. Accumulates a movement direction from `input` and scales it to `base_speed`.
-    /// 4. Applies gravity, jump impulse, and drag.
-    /// 5. Resolves collisions on each axis independently using [`Camera::check_collision`].
-    /// 6. Clamps Y to a minimum of `1.0` to prevent falling out of the world.
+    /// 4. Sets the horizontal velocity to that target, or eases toward it when
+    ///    [`MovementTuning::acceleration`] is enabled.
+    /// 5. Applies gravity, jump impulse, and drag.
+    /// 6. Sweeps the body along each axis independently with
+    ///    [`Camera::sweep_axis`], so a fast or long frame stops at the first
+    ///    wall instead of skipping over it.
+    /// 7. Clamps Y to a minimum of `1.0` to prevent falling out of the world.
+    ///
+    /// Either way the render FOV then eases toward its sprint or normal value.
     ///
     /// # Parameters
     /// - `world` — used for block queries during collision and water detection.
     /// - `dt` — delta time in seconds since the last frame.
     /// - `input` — current frame's digital input state.
     pub fn update(&mut self, world: &World, dt: f32, input: &InputState) {
+        if self.spectating() {
+            self.update_spectating(dt, input);
+            return;
+        }
+        let was_in_water = self.in_water;
         self.in_water = self.check_in_water(world);
+        if self.in_water && !was_in_water {
+            self.splash = Some(self.velocity.length());
+        }
+        if self.creative {
+            self.update_flying(dt, input);
+            self.update_fov(dt);
+            return;
+        }
+
+        let moving = self.move_direction(input) != Vec3::ZERO;
+        let sprinting = self.update_stamina(dt, input.sprint && moving);
 
         let (base_speed, gravity, max_fall_speed, jump_velocity, horizontal_drag, vertical_drag) =
             if self.in_water {
-                let speed = if input.sprint {
+                let speed = if sprinting {
                     PLAYER_SPRINT_SPEED * 0.331
                 } else {
                     PLAYER_BASE_SPEED * 0.331
                 };
                 (speed, 6.0, 3.0, 4.0, 0.9, 0.95)
             } else {
-                let speed = if input.sprint {
+                let speed = if sprinting {
                     PLAYER_SPRINT_SPEED
                 } else {
                     PLAYER_BASE_SPEED
@@ -146,28 +342,31 @@ impl Camera {
                 (speed, 25.0, 50.0, 8.0, 1.0, 1.0)
             };
 
-        let mut move_dir = Vec3::ZERO;
+        let move_dir = self.move_direction(input) * base_speed;
 
-        if input.forward {
-            move_dir += self.forward();
-        }
-        if input.backward {
-            move_dir -= self.forward();
-        }
-        if input.left {
-            move_dir -= self.right();
-        }
-        if input.right {
-            move_dir += self.right();
-        }
-
-        if move_dir.length_squared() > 0.0 {
-            move_dir = move_dir.normalize() * base_speed;
+        let target_x = move_dir.x * horizontal_drag;
+        let target_z = move_dir.z * horizontal_drag;
+        if self.movement.acceleration {
+            // Ramp toward the target at a fixed rate instead of snapping.
+            let speeding_up = move_dir.length_squared() > 0.0;
+            let mut rate = if speeding_up {
+                self.movement.ground_acceleration
+            } else {
+                self.movement.ground_deceleration
+            };
+            if !self.on_ground && !self.in_water {
+                rate *= self.movement.air_control;
+            }
+            let current = Vec3::new(self.velocity.x, 0.0, self.velocity.z);
+            let delta = Vec3::new(target_x, 0.0, target_z) - current;
+            let step = delta.clamp_length_max(rate * dt);
+            self.velocity.x += step.x;
+            self.velocity.z += step.z;
+        } else {
+            self.velocity.x = target_x;
+            self.velocity.z = target_z;
         }
 
-        self.velocity.x = move_dir.x * horizontal_drag;
-        self.velocity.z = move_dir.z * horizontal_drag;
-
         if self.in_water {
             if input.jump {
                 self.velocity.y = jump_velocity;
@@ -177,7 +376,13 @@ impl Camera {
                 self.velocity.y -= gravity * dt;
                 self.velocity.y *= vertical_drag;
             }
-            self.velocity.y = self.velocity.y.clamp(-max_fall_speed * 2.0, jump_velocity);
+            // A dive is braked over a short distance by buoyancy rather
+            // than stopped dead at the sinking speed.
+            let max_sink_speed = max_fall_speed * 2.0;
+            if self.velocity.y < -max_sink_speed {
+                self.velocity.y = (self.velocity.y + WATER_BUOYANCY * dt).min(-max_sink_speed);
+            }
+            self.velocity.y = self.velocity.y.min(jump_velocity);
         } else {
             if input.jump && self.on_ground {
                 self.velocity.y = jump_velocity;
@@ -187,22 +392,18 @@ impl Camera {
             self.velocity.y = self.velocity.y.max(-max_fall_speed);
         }
 
-        let new_pos = self.position + self.velocity * dt;
+        let delta = self.velocity * dt;
 
-        if !self.check_collision(world, new_pos.x, self.position.y, self.position.z) {
-            self.position.x = new_pos.x;
-        } else {
+        if self.sweep_axis(world, 0, delta.x) {
             self.velocity.x = 0.0;
         }
 
-        if !self.check_collision(world, self.position.x, self.position.y, new_pos.z) {
-            self.position.z = new_pos.z;
-        } else {
+        if self.sweep_axis(world, 2, delta.z) {
             self.velocity.z = 0.0;
         }
 
-        if !self.check_collision(world, self.position.x, new_pos.y, self.position.z) {
-            self.position.y = new_pos.y;
+        let (start_y, was_on_ground) = (self.position.y, self.on_ground);
+        if !self.sweep_axis(world, 1, delta.y) {
             if !self.in_water {
                 self.on_ground = false;
             }
@@ -213,41 +414,193 @@ impl Camera {
             self.velocity.y = 0.0;
         }
 
+        // Only the distance the physics moved the body counts, so a
+        // teleport never ends in a fall.
+        let fallen = (start_y - self.position.y).max(0.0);
+        if self.on_ground {
+            if !was_on_ground && !self.in_water {
+                self.landed_fall = Some(self.fall_distance + fallen);
+            }
+            self.fall_distance = 0.0;
+        } else if self.in_water || fallen == 0.0 {
+            self.fall_distance = 0.0;
+        } else {
+            self.fall_distance += fallen;
+        }
+
         self.position.y = self.position.y.max(1.0);
+        self.update_fov(dt);
     }
 
-    /// Returns `true` if the player AABB centered at `(x, y, z)` overlaps any solid block.
+    /// Drains or regenerates stamina for one frame and returns whether the
+    /// player sprints this frame.
     ///
-    /// Iterates over all blocks within the bounding box defined by
-    /// [`PLAYER_WIDTH`] and [`PLAYER_HEIGHT`] and delegates intersection
-    /// testing to [`check_intersection`].
+    /// `wants_sprint` is sprint held while moving.  Holding sprint in place
+    /// neither drains stamina nor counts as sprinting, and the sprint-to-sink
+    /// control in water keeps working when exhausted.
+    fn update_stamina(&mut self, dt: f32, wants_sprint: bool) -> bool {
+        let sprinting = wants_sprint && !self.exhausted;
+        if sprinting {
+            self.stamina -= dt;
+            if self.stamina <= 0.0 {
+                self.stamina = 0.0;
+                self.exhausted = true;
+            }
+        } else {
+            self.stamina = (self.stamina + PLAYER_STAMINA_REGEN * dt).min(PLAYER_MAX_STAMINA);
+            if self.stamina >= PLAYER_MAX_STAMINA * PLAYER_STAMINA_RECOVER_FRACTION {
+                self.exhausted = false;
+            }
+        }
+        self.sprinting = sprinting;
+        sprinting
+    }
+
+    /// Eases [`Self::fov`] toward the sprint FOV while sprinting and back
+    /// to [`DEFAULT_FOV`] otherwise.  Frame-rate independent.
+    fn update_fov(&mut self, dt: f32) {
+        let target = if self.sprinting {
+            DEFAULT_FOV + SPRINT_FOV_BOOST
+        } else {
+            DEFAULT_FOV
+        };
+        let t = 1.0 - (-SPRINT_FOV_EASE_RATE * dt).exp();
+        self.fov += (target - self.fov) * t;
+    }
+
+    /// Creative-mode flight: moves straight through blocks with no gravity.
     ///
-    /// Used by [`Camera::update`] for per-axis collision resolution.
-    pub fn check_collision(&self, world: &World, x: f32, y: f32, z: f32) -> bool {
-        let player_width = PLAYER_WIDTH;
-        let player_height = PLAYER_HEIGHT;
-
-        let min_x = (x - player_width).floor() as i32;
-        let max_x = (x + player_width).floor() as i32;
-        let min_y = y.floor() as i32;
-        let max_y = (y + player_height).floor() as i32;
-        let min_z = (z - player_width).floor() as i32;
-        let max_z = (z + player_width).floor() as i32;
-
-        for bx in min_x..=max_x {
-            for by in min_y..=max_y {
-                for bz in min_z..=max_z {
-                    if world.is_solid(bx, by, bz) {
-                        if check_intersection(Vec3::new(x, y, z), bx, by, bz) {
-                            return true;
-                        }
-                    }
+    /// WASD moves horizontally at [`CREATIVE_FLY_SPEED`], jump / sprint move
+    /// up / down at [`CREATIVE_VERTICAL_SPEED`], and `fly_sprint` multiplies
+    /// both by [`CREATIVE_SPRINT_MULTIPLIER`].  Releasing every key stops the
+    /// player in place.  Flying costs no stamina, which regenerates instead.
+    fn update_flying(&mut self, dt: f32, input: &InputState) {
+        let direction = self.move_direction(input);
+        self.update_stamina(dt, false);
+        self.sprinting = input.fly_sprint && direction != Vec3::ZERO;
+
+        let mut velocity = direction * CREATIVE_FLY_SPEED;
+        if input.jump {
+            velocity.y += CREATIVE_VERTICAL_SPEED;
+        }
+        if input.sprint {
+            velocity.y -= CREATIVE_VERTICAL_SPEED;
+        }
+        if input.fly_sprint {
+            velocity *= CREATIVE_SPRINT_MULTIPLIER;
+        }
+
+        self.velocity = velocity;
+        self.position += velocity * dt;
+        self.position.y = self.position.y.max(1.0);
+        self.on_ground = false;
+    }
+
+    /// Spectator flight: moves the detached camera with no physics at all.
+    ///
+    /// Forward / backward follow the full look direction, pitch included,
+    /// strafing stays horizontal, and jump / sprint move straight up / down.
+    /// Everything moves at [`Self::spectator_speed`], through blocks and
+    /// below the bottom of the world, and releasing every key stops.
+    /// Stamina and the FOV are left alone.
+    fn update_spectating(&mut self, dt: f32, input: &InputState) {
+        let mut direction = Vec3::ZERO;
+        if input.forward {
+            direction += self.look_direction();
+        }
+        if input.backward {
+            direction -= self.look_direction();
+        }
+        if input.left {
+            direction -= self.right();
+        }
+        if input.right {
+            direction += self.right();
+        }
+        if input.jump {
+            direction += Vec3::Y;
+        }
+        if input.sprint {
+            direction -= Vec3::Y;
+        }
+
+        self.velocity = direction.normalize_or_zero() * self.spectator_speed;
+        self.position += self.velocity * dt;
+        self.on_ground = false;
+        self.in_water = false;
+    }
+
+    /// Returns the unit horizontal direction requested by the WASD keys, or
+    /// zero when none (or only opposing keys) are held.
+    fn move_direction(&self, input: &InputState) -> Vec3 {
+        let mut move_dir = Vec3::ZERO;
+
+        if input.forward {
+            move_dir += self.forward();
+        }
+        if input.backward {
+            move_dir -= self.forward();
+        }
+        if input.left {
+            move_dir -= self.right();
+        }
+        if input.right {
+            move_dir += self.right();
+        }
+
+        move_dir.normalize_or_zero()
+    }
+
+    /// Moves the body by `delta` along `axis` (0 = X, 1 = Y, 2 = Z), stopping
+    /// against the first solid block in the way.  Returns `true` if it was
+    /// blocked.
+    ///
+    /// The move is split into sub-steps of at most [`SWEEP_STEP`] and every
+    /// one is tested like [`Camera::check_collision`], so a large delta
+    /// cannot jump across a thin wall.  Inside the sub-step that collides the
+    /// contact point is found by bisection, leaving the body flush against
+    /// the obstacle.
+    fn sweep_axis(&mut self, world: &World, axis: usize, delta: f32) -> bool {
+        if delta == 0.0 {
+            return false;
+        }
+        let steps = (delta.abs() / SWEEP_STEP).ceil() as u32;
+        let step = delta / steps as f32;
+        let collides_at = |position: Vec3, offset: f32| {
+            let mut moved = position;
+            moved[axis] += offset;
+            check_body_collision(world, moved, PLAYER_WIDTH, PLAYER_HEIGHT)
+        };
+
+        for _ in 0..steps {
+            if !collides_at(self.position, step) {
+                self.position[axis] += step;
+                continue;
+            }
+            // Largest free fraction of this step.
+            let (mut free, mut blocked) = (0.0, 1.0);
+            for _ in 0..SWEEP_REFINE_ITERATIONS {
+                let mid = (free + blocked) * 0.5;
+                if collides_at(self.position, step * mid) {
+                    blocked = mid;
+                } else {
+                    free = mid;
                 }
             }
+            self.position[axis] += step * free;
+            return true;
         }
         false
     }
 
+    /// Returns `true` if the player AABB centered at `(x, y, z)` overlaps any solid block.
+    ///
+    /// Player-sized wrapper around [`check_body_collision`].  Used by
+    /// [`Camera::sweep_axis`] for per-axis collision resolution.
+    pub fn check_collision(&self, world: &World, x: f32, y: f32, z: f32) -> bool {
+        check_body_collision(world, Vec3::new(x, y, z), PLAYER_WIDTH, PLAYER_HEIGHT)
+    }
+
     /// Returns `true` if the player's current AABB intersects the block at `(bx, by, bz)`.
     ///
     /// Convenience wrapper around [`check_intersection`] using [`Self::position`].
@@ -258,39 +611,176 @@ impl Camera {
     /// Casts a ray from the eye position along the look direction and returns
     /// the first solid block hit within `max_dist` world units.
     ///
-    /// Steps along the ray in increments of `0.1` units. Returns
-    /// `Some((hit_x, hit_y, hit_z, prev_x, prev_y, prev_z))` where the first
-    /// three components are the coordinates of the block that was hit and the
-    /// last three are the coordinates of the last empty block before the hit
-    /// (used for block placement). Returns `None` if no solid block is found
-    /// within `max_dist`.
-    pub fn raycast(&self, world: &World, max_dist: f32) -> Option<(i32, i32, i32, i32, i32, i32)> {
-        let dir = self.look_direction();
-        let eye = self.eye_position();
-        let mut pos = Vec3::new(eye.x, eye.y, eye.z);
-        let step = 0.1;
-        let mut prev = (
-            pos.x.floor() as i32,
-            pos.y.floor() as i32,
-            pos.z.floor() as i32,
-        );
-
-        for _ in 0..(max_dist / step) as i32 {
-            pos += dir * step;
-            let current = (
-                pos.x.floor() as i32,
-                pos.y.floor() as i32,
-                pos.z.floor() as i32,
-            );
-            if current != prev {
-                if world.is_solid(current.0, current.1, current.2) {
-                    return Some((current.0, current.1, current.2, prev.0, prev.1, prev.2));
+    /// See [`raycast_blocks`] for the traversal.  The block containing the
+    /// eye itself is never reported.  Returns `None` if no solid block is
+    /// found within `max_dist`.
+    pub fn raycast(&self, world: &World, max_dist: f32) -> Option<RaycastHit> {
+        raycast_blocks(
+            self.eye_position(),
+            self.look_direction(),
+            max_dist,
+            |x, y, z| world.is_solid(x, y, z),
+        )
+    }
+
+    /// Reach for the current game mode: [`Self::creative_reach`] in creative
+    /// mode, [`Self::reach`] otherwise.
+    pub fn current_reach(&self) -> f32 {
+        if self.creative {
+            self.creative_reach
+        } else {
+            self.reach
+        }
+    }
+
+    /// Returns the block under the crosshair if it is within
+    /// [`Camera::current_reach`].
+    ///
+    /// Breaking, placing and the placement preview all target through this,
+    /// so they always agree on what is in reach.  Nothing is targeted while
+    /// spectating.
+    pub fn target(&self, world: &World) -> Option<RaycastHit> {
+        if self.spectating() {
+            return None;
+        }
+        self.raycast(world, self.current_reach())
+    }
+}
+
+/// A block hit by [`Camera::raycast`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaycastHit {
+    /// World coordinates of the block that was hit.
+    pub block: (i32, i32, i32),
+    /// Outward normal of the face the ray entered through; exactly one
+    /// component is `±1`, e.g. `(0, 1, 0)` for the top face.
+    pub normal: (i32, i32, i32),
+}
+
+impl RaycastHit {
+    /// The empty block in front of the hit face, where a new block would be
+    /// placed.
+    pub fn place_position(&self) -> (i32, i32, i32) {
+        (
+            self.block.0 + self.normal.0,
+            self.block.1 + self.normal.1,
+            self.block.2 + self.normal.2,
+        )
+    }
+}
+
+/// Walks the voxel grid from `origin` along `dir` and returns the first block
+/// for which `is_solid` is `true`, within `max_dist` world units.
+///
+/// Uses a DDA traversal (Amanatides & Woo): the ray advances exactly one
+/// block boundary at a time, so every block it passes through is visited
+/// once and the crossed boundary directly gives the hit face normal.  The
+/// block containing `origin` is not tested.
+///
+/// `dir` does not need to be normalized, but `max_dist` is measured in
+/// multiples of its length.
+pub fn raycast_blocks(
+    origin: Vec3,
+    dir: Vec3,
+    max_dist: f32,
+    mut is_solid: impl FnMut(i32, i32, i32) -> bool,
+) -> Option<RaycastHit> {
+    let origin = origin.to_array();
+    let dir = dir.to_array();
+    let mut cell = origin.map(|c| c.floor() as i32);
+
+    // Per axis: direction of travel, ray distance between two boundaries, and
+    // ray distance to the next boundary.  Axes the ray is parallel to never
+    // cross a boundary.
+    let mut step = [0i32; 3];
+    let mut t_delta = [f32::INFINITY; 3];
+    let mut t_max = [f32::INFINITY; 3];
+    for axis in 0..3 {
+        if dir[axis] > 0.0 {
+            step[axis] = 1;
+            t_delta[axis] = 1.0 / dir[axis];
+            t_max[axis] = ((cell[axis] + 1) as f32 - origin[axis]) / dir[axis];
+        } else if dir[axis] < 0.0 {
+            step[axis] = -1;
+            t_delta[axis] = -1.0 / dir[axis];
+            t_max[axis] = (origin[axis] - cell[axis] as f32) / -dir[axis];
+        }
+    }
+
+    loop {
+        // Cross whichever boundary comes first.
+        let axis = if t_max[0] <= t_max[1] && t_max[0] <= t_max[2] {
+            0
+        } else if t_max[1] <= t_max[2] {
+            1
+        } else {
+            2
+        };
+        if t_max[axis] > max_dist {
+            return None;
+        }
+        cell[axis] += step[axis];
+        t_max[axis] += t_delta[axis];
+
+        if is_solid(cell[0], cell[1], cell[2]) {
+            let mut normal = [0; 3];
+            normal[axis] = -step[axis];
+            return Some(RaycastHit {
+                block: (cell[0], cell[1], cell[2]),
+                normal: (normal[0], normal[1], normal[2]),
+            });
+        }
+    }
+}
+
+/// Returns `true` if a body rooted at `pos` overlaps any solid block.
+///
+/// The body is an AABB extending `half_width` units in ±X and ±Z from `pos`
+/// and `height` units upward from `pos.y`.  Iterates over all blocks within
+/// it and tests against each block's [`BlockType::collision_boxes`], so
+/// partial blocks such as slabs and stairs collide with their actual bounds.
+///
+/// Shared by the player ([`Camera::check_collision`]) and mobs.
+///
+/// [`BlockType::collision_boxes`]: crate::core::block::BlockType::collision_boxes
+pub fn check_body_collision(world: &World, pos: Vec3, half_width: f32, height: f32) -> bool {
+    let min_x = (pos.x - half_width).floor() as i32;
+    let max_x = (pos.x + half_width).floor() as i32;
+    let min_y = pos.y.floor() as i32;
+    let max_y = (pos.y + height).floor() as i32;
+    let min_z = (pos.z - half_width).floor() as i32;
+    let max_z = (pos.z + half_width).floor() as i32;
+
+    for bx in min_x..=max_x {
+        for by in min_y..=max_y {
+            for bz in min_z..=max_z {
+                let block = world.get_block(bx, by, bz);
+                if !block.is_solid() {
+                    continue;
+                }
+                // Slabs and stairs only block the part of the cell they
+                // actually occupy.
+                let facing = if block.is_directional() {
+                    world.get_facing(bx, by, bz)
+                } else {
+                    Facing::default()
+                };
+                let origin = Vec3::new(bx as f32, by as f32, bz as f32);
+                for &(min, max) in block.collision_boxes(facing) {
+                    if body_intersects_box(
+                        pos,
+                        half_width,
+                        height,
+                        origin + Vec3::from(min),
+                        origin + Vec3::from(max),
+                    ) {
+                        return true;
+                    }
                 }
-                prev = current;
             }
         }
-        None
     }
+    false
 }
 
 /// Returns `true` if the player AABB rooted at `pos` overlaps the unit block at `(bx, by, bz)`.
@@ -299,27 +789,268 @@ impl Camera {
 /// and [`PLAYER_HEIGHT`] units upward from `pos.y`. Uses a standard
 /// axis-aligned box vs. box intersection test.
 pub fn check_intersection(pos: Vec3, bx: i32, by: i32, bz: i32) -> bool {
-    let player_width = PLAYER_WIDTH;
-    let player_height = PLAYER_HEIGHT;
-
-    let block_min_x = bx as f32;
-    let block_max_x = (bx + 1) as f32;
-    let block_min_y = by as f32;
-    let block_max_y = (by + 1) as f32;
-    let block_min_z = bz as f32;
-    let block_max_z = (bz + 1) as f32;
-
-    let player_min_x = pos.x - player_width;
-    let player_max_x = pos.x + player_width;
-    let player_min_y = pos.y;
-    let player_max_y = pos.y + player_height;
-    let player_min_z = pos.z - player_width;
-    let player_max_z = pos.z + player_width;
-
-    player_max_x > block_min_x
-        && player_min_x < block_max_x
-        && player_max_y > block_min_y
-        && player_min_y < block_max_y
-        && player_max_z > block_min_z
-        && player_min_z < block_max_z
+    let min = Vec3::new(bx as f32, by as f32, bz as f32);
+    check_box_intersection(pos, min, min + Vec3::ONE)
+}
+
+/// Returns `true` if the player AABB rooted at `pos` overlaps the world-space
+/// box spanning `block_min` to `block_max`.
+///
+/// Used for partial blocks whose collision volume is smaller than a full cell
+/// (see [`BlockType::collision_boxes`]).
+///
+/// [`BlockType::collision_boxes`]: crate::core::block::BlockType::collision_boxes
+pub fn check_box_intersection(pos: Vec3, block_min: Vec3, block_max: Vec3) -> bool {
+    body_intersects_box(pos, PLAYER_WIDTH, PLAYER_HEIGHT, block_min, block_max)
+}
+
+/// Standard axis-aligned box vs. box test between a body rooted at `pos`
+/// (see [`check_body_collision`]) and the box spanning `block_min` to
+/// `block_max`.
+pub(crate) fn body_intersects_box(
+    pos: Vec3,
+    half_width: f32,
+    height: f32,
+    block_min: Vec3,
+    block_max: Vec3,
+) -> bool {
+    let body_min_x = pos.x - half_width;
+    let body_max_x = pos.x + half_width;
+    let body_min_y = pos.y;
+    let body_max_y = pos.y + height;
+    let body_min_z = pos.z - half_width;
+    let body_max_z = pos.z + half_width;
+
+    body_max_x > block_min.x
+        && body_min_x < block_max.x
+        && body_max_y > block_min.y
+        && body_min_y < block_max.y
+        && body_max_z > block_min.z
+        && body_min_z < block_max.z
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::block::BlockType;
+    use crate::core::chunk::Chunk;
+
+    #[test]
+    fn raycast_straight_down_hits_top_face() {
+        let hit = raycast_blocks(
+            Vec3::new(0.5, 5.5, 0.5),
+            Vec3::new(0.0, -1.0, 0.0),
+            10.0,
+            |x, y, z| (x, y, z) == (0, 0, 0),
+        )
+        .expect("ray should hit the block below");
+
+        assert_eq!(hit.block, (0, 0, 0));
+        assert_eq!(hit.normal, (0, 1, 0));
+        assert_eq!(hit.place_position(), (0, 1, 0));
+    }
+
+    #[test]
+    fn long_frame_stops_at_a_thin_wall() {
+        // Stone floor at y = 9 and a one-block wall across x = 5.
+        let mut world = World::new_empty_with_seed(1);
+        for cx in -1..=2 {
+            for cz in -1..=1 {
+                world.chunks.insert((cx, cz), Chunk::new(cx, cz));
+            }
+        }
+        for x in -8..40 {
+            for z in -8..8 {
+                world.set_block(x, 9, z, BlockType::Stone);
+            }
+        }
+        for y in 10..13 {
+            for z in -8..8 {
+                world.set_block(5, y, z, BlockType::Stone);
+            }
+        }
+
+        // Facing +X; one 5 s update covers about 20 blocks and would land
+        // well past the wall if only the destination were tested.
+        let mut camera = Camera::new((0.5, 10.0, 0.5));
+        let input = InputState {
+            forward: true,
+            ..Default::default()
+        };
+        camera.update(&world, 5.0, &input);
+
+        assert!((camera.position.x - (5.0 - PLAYER_WIDTH)).abs() < 0.01);
+        assert_eq!(camera.velocity.x, 0.0);
+        assert!((camera.position.y - 10.0).abs() < 0.01);
+        assert!(camera.on_ground);
+    }
+
+    #[test]
+    fn landing_reports_the_height_fallen() {
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        for x in 0..16 {
+            for z in 0..16 {
+                world.set_block(x, 9, z, BlockType::Stone);
+            }
+        }
+        let mut camera = Camera::new((8.5, 30.0, 8.5));
+        let input = InputState::default();
+        for _ in 0..120 {
+            camera.update(&world, 1.0 / 60.0, &input);
+        }
+        assert!(camera.on_ground);
+        let fall = camera.take_fall().expect("the landing should be reported");
+        assert!((fall - 20.0).abs() < 0.01, "fell {} blocks", fall);
+        assert_eq!(camera.take_fall(), None);
+
+        // Teleporting is not falling.
+        camera.position.y = 25.0;
+        camera.on_ground = false;
+        camera.update(&world, 1.0 / 60.0, &input);
+        camera.position.y = 10.0;
+        for _ in 0..10 {
+            camera.update(&world, 1.0 / 60.0, &input);
+        }
+        assert!(camera.take_fall().is_none_or(|fall| fall < 0.1));
+    }
+
+    #[test]
+    fn buoyancy_brakes_a_dive_gradually() {
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        for y in 1..40 {
+            world.set_block(8, y, 8, BlockType::Water);
+        }
+        let mut camera = Camera::new((8.5, 60.0, 8.5));
+        camera.velocity.y = -40.0;
+        let input = InputState::default();
+        let dt = 1.0 / 60.0;
+        while !camera.in_water {
+            camera.update(&world, dt, &input);
+        }
+        let splash = camera.take_splash().expect("entering water should splash");
+        assert!(splash > 20.0);
+        assert_eq!(camera.take_splash(), None);
+
+        // Still diving a few frames in, then sinking at the swimming speed.
+        for _ in 0..3 {
+            camera.update(&world, dt, &input);
+        }
+        assert!(camera.velocity.y < -20.0);
+        for _ in 0..60 {
+            camera.update(&world, dt, &input);
+        }
+        assert!(camera.in_water);
+        assert!(camera.velocity.y >= -6.0);
+    }
+
+    #[test]
+    fn creative_mode_reaches_further() {
+        // A stone block 6.5 blocks in front of the eye, along +X.
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        world.set_block(7, 11, 0, BlockType::Stone);
+
+        let mut camera = Camera::new((0.5, 11.5 - PLAYER_HEIGHT, 0.5));
+        assert_eq!(camera.current_reach(), PLAYER_REACH);
+        assert_eq!(camera.target(&world), None);
+
+        camera.set_creative(true);
+        assert_eq!(camera.current_reach(), CREATIVE_REACH);
+        let hit = camera.target(&world).expect("block should be in reach");
+        assert_eq!(hit.block, (7, 11, 0));
+    }
+
+    #[test]
+    fn leaving_creative_does_not_keep_flight_climb() {
+        let mut camera = Camera::new((0.0, 80.0, 0.0));
+        camera.set_creative(true);
+        let input = InputState {
+            jump: true,
+            ..Default::default()
+        };
+        camera.update_flying(0.5, &input);
+        assert_eq!(camera.position.y, 80.0 + CREATIVE_VERTICAL_SPEED * 0.5);
+
+        camera.set_creative(false);
+        assert!(!camera.creative);
+        assert_eq!(camera.velocity.y, 0.0);
+    }
+
+    #[test]
+    fn spectator_flies_through_blocks_and_returns_to_the_body() {
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        for x in 0..16 {
+            for z in 0..16 {
+                world.set_block(x, 9, z, BlockType::Stone);
+            }
+        }
+        let body = Vec3::new(8.5, 10.0, 8.5);
+        let mut camera = Camera::new((body.x, body.y, body.z));
+        camera.set_creative(true);
+        camera.set_spectating(true);
+
+        // Sinking through the floor and below the bottom of the world.
+        let input = InputState {
+            sprint: true,
+            ..Default::default()
+        };
+        camera.update(&world, 1.0, &input);
+        assert_eq!(camera.position.y, body.y - SPECTATOR_SPEED);
+        assert_eq!(camera.body_position(), body);
+        assert!(camera.creative);
+
+        // Looking at the floor targets nothing while spectating.
+        camera.position = body;
+        camera.pitch = -1.5;
+        assert_eq!(camera.target(&world), None);
+
+        camera.set_spectating(false);
+        assert_eq!(camera.position, body);
+        assert_eq!(camera.velocity, Vec3::ZERO);
+        assert!(camera.target(&world).is_some());
+    }
+
+    #[test]
+    fn sprint_stops_when_stamina_runs_out_until_it_recovers() {
+        let mut camera = Camera::new((0.0, 80.0, 0.0));
+        let dt = 0.1;
+        let mut sprinted = 0.0;
+        while camera.update_stamina(dt, true) {
+            sprinted += dt;
+            assert!(sprinted <= PLAYER_MAX_STAMINA + dt, "stamina never ran out");
+        }
+        assert!(camera.exhausted);
+        assert!(!camera.sprinting);
+
+        // A little stamina is not enough to sprint again.
+        camera.update_stamina(dt, false);
+        assert!(!camera.update_stamina(dt, true));
+
+        let recover = PLAYER_MAX_STAMINA * PLAYER_STAMINA_RECOVER_FRACTION;
+        while camera.stamina < recover {
+            camera.update_stamina(dt, false);
+        }
+        assert!(camera.update_stamina(dt, true));
+    }
+
+    #[test]
+    fn fov_eases_to_sprint_fov_and_back() {
+        let mut camera = Camera::new((0.0, 80.0, 0.0));
+        camera.sprinting = true;
+        camera.update_fov(0.05);
+        assert!(camera.fov > DEFAULT_FOV && camera.fov < DEFAULT_FOV + SPRINT_FOV_BOOST);
+        for _ in 0..60 {
+            camera.update_fov(0.05);
+        }
+        assert!((camera.fov - (DEFAULT_FOV + SPRINT_FOV_BOOST)).abs() < 1e-3);
+
+        camera.sprinting = false;
+        for _ in 0..60 {
+            camera.update_fov(0.05);
+        }
+        assert!((camera.fov - DEFAULT_FOV).abs() < 1e-3);
+    }
 }
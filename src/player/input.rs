@@ -1,4 +1,6 @@
-#[derive(Default)]
+use crate::constants::DIG_STAGES;
+
+#[derive(Default, Clone, Copy)]
 pub struct InputState {
     pub forward: bool,
     pub backward: bool,
@@ -6,13 +8,44 @@ pub struct InputState {
     pub right: bool,
     pub jump: bool,
     pub sprint: bool,
+    /// Sprint while flying in creative mode, where `sprint` descends instead.
+    pub fly_sprint: bool,
     pub left_mouse: bool,
     pub right_mouse: bool,
 }
 
+impl InputState {
+    /// Combines two input sources: an action is held if either holds it.
+    pub fn merged(&self, other: &InputState) -> InputState {
+        InputState {
+            forward: self.forward || other.forward,
+            backward: self.backward || other.backward,
+            left: self.left || other.left,
+            right: self.right || other.right,
+            jump: self.jump || other.jump,
+            sprint: self.sprint || other.sprint,
+            fly_sprint: self.fly_sprint || other.fly_sprint,
+            left_mouse: self.left_mouse || other.left_mouse,
+            right_mouse: self.right_mouse || other.right_mouse,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct DiggingState {
     pub target: Option<(i32, i32, i32)>,
     pub progress: f32,
     pub break_time: f32,
 }
+
+impl DiggingState {
+    /// Crack stage of the block being broken: `0` when not digging,
+    /// otherwise `1..=DIG_STAGES` as `progress` approaches `break_time`.
+    pub fn stage(&self) -> u8 {
+        if self.target.is_none() || self.break_time <= 0.0 {
+            return 0;
+        }
+        let fraction = (self.progress / self.break_time).clamp(0.0, 1.0);
+        ((fraction * DIG_STAGES as f32) as u8).min(DIG_STAGES - 1) + 1
+    }
+}
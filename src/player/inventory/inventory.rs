@@ -1,11 +1,73 @@
-use crate::GameItem;
-pub struct InventoryItem {
-    pub item: GameItem,
-    pub item_name: String,
-    pub quantity: u32,
-}
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::core::block::BlockType;
 
+/// Blocks the player carries in survival mode, counted per type.
+///
+/// Breaking a block adds its [drop](BlockType::dropped_block) and placing
+/// one takes it out again; creative mode ignores the inventory.  Saved with
+/// the world in [`SavedWorld`](crate::SavedWorld).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Inventory {
-    pub slots: [Option<InventoryItem>; 36], // 3 * 9 + 9 hotbar
-    pub selected_slot: usize,
+    counts: HashMap<BlockType, u32>,
+}
+
+impl Inventory {
+    /// Creates an empty inventory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns how many blocks of type `block` are carried.
+    pub fn count(&self, block: BlockType) -> u32 {
+        self.counts.get(&block).copied().unwrap_or(0)
+    }
+
+    /// Adds `amount` blocks of type `block`.
+    pub fn add(&mut self, block: BlockType, amount: u32) {
+        if amount > 0 {
+            let count = self.counts.entry(block).or_insert(0);
+            *count = count.saturating_add(amount);
+        }
+    }
+
+    /// Removes one block of type `block`, returning `false` (and changing
+    /// nothing) if none is carried.
+    pub fn take(&mut self, block: BlockType) -> bool {
+        match self.counts.get_mut(&block) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                if *count == 0 {
+                    self.counts.remove(&block);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Empties the inventory, e.g. when a different world is entered.
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placing_uses_up_mined_blocks() {
+        let mut inventory = Inventory::new();
+        assert!(!inventory.take(BlockType::Stone));
+
+        inventory.add(BlockType::Stone, 2);
+        assert_eq!(inventory.count(BlockType::Stone), 2);
+        assert!(inventory.take(BlockType::Stone));
+        assert!(inventory.take(BlockType::Stone));
+        assert!(!inventory.take(BlockType::Stone));
+        assert_eq!(inventory.count(BlockType::Stone), 0);
+        assert_eq!(inventory, Inventory::new());
+    }
 }
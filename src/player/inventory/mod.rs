@@ -1 +1,3 @@
 pub mod inventory;
+
+pub use inventory::Inventory;
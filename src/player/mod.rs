@@ -3,5 +3,6 @@ pub mod input;
 mod inventory;
 mod player_stats;
 
-pub use camera::Camera;
+pub use camera::{Camera, MovementTuning, RaycastHit};
 pub use input::{DiggingState, InputState};
+pub use inventory::Inventory;
@@ -1,6 +1,11 @@
+use std::sync::Arc;
+
 use bytemuck::{Pod, Zeroable};
+use parking_lot::Mutex;
 use rustc_hash::FxHashMap;
 
+use glam::{Vec3, Vec4};
+
 use crate::core::vertex::Vertex;
 use crate::render::frustum::AABB;
 
@@ -16,6 +21,16 @@ const MAX_VERTICES: usize = 20_000_000;
 /// Maximum number of indices across all subchunks in the unified index buffer.
 const MAX_INDICES: usize = 60_000_000;
 
+/// Where `visible_count_staging` is in its copy → map → read cycle.
+enum CountReadback {
+    /// Free to receive the next culling pass's count.
+    Idle,
+    /// A copy was recorded; it is mapped once the frame is submitted.
+    Copied,
+    /// Mapping was requested; the callback stores its outcome here.
+    Mapping(Arc<Mutex<Option<bool>>>),
+}
+
 /// GPU-side arguments for a single `draw_indexed_indirect` call.
 ///
 /// The memory layout matches the `VkDrawIndexedIndirectCommand` / wgpu
@@ -48,6 +63,9 @@ pub struct SubchunkGpuMeta {
     pub aabb_max: [f32; 4],
     /// Packed draw arguments: `[index_count, index_offset, vertex_offset, 1]`.
     pub draw_data: [u32; 4],
+    /// Draw arguments of the translucent part, stored right after the opaque
+    /// part: `[index_count, index_offset, vertex_offset, 0]`.
+    pub translucent_draw: [u32; 4],
 }
 
 /// Uniform data consumed by the GPU culling compute shader.
@@ -93,10 +111,60 @@ struct SubchunkAlloc {
     index_offset: u32,
     /// Number of indices belonging to this subchunk.
     index_count: u32,
+    /// How many of the trailing indices belong to translucent blocks.
+    translucent_index_count: u32,
+    /// How many of the leading vertices belong to opaque blocks.
+    opaque_vertex_count: u32,
+    /// World-space bounds, for sorting and culling the translucent part.
+    aabb_min: Vec3,
+    /// See `aabb_min`.
+    aabb_max: Vec3,
     /// Slot in the `SubchunkGpuMeta` array assigned to this subchunk.
     slot_index: usize,
 }
 
+impl SubchunkAlloc {
+    /// Draw command of the translucent part.
+    fn translucent_draw(&self) -> DrawIndexedIndirect {
+        DrawIndexedIndirect {
+            index_count: self.translucent_index_count,
+            instance_count: 1,
+            first_index: self.index_offset + self.index_count - self.translucent_index_count,
+            base_vertex: (self.vertex_offset + self.opaque_vertex_count) as i32,
+            first_instance: 0,
+        }
+    }
+}
+
+/// Returns the translucent draw commands of the allocations in `allocs`
+/// that lie at least partly inside `frustum_planes`, farthest from
+/// `camera_pos` first.
+///
+/// Translucent faces do not write depth, so they only blend correctly when
+/// drawn back to front; sorting whole subchunks by the distance to their
+/// centers gets that right between subchunks.
+fn translucent_draws_back_to_front<'a>(
+    allocs: impl Iterator<Item = &'a SubchunkAlloc>,
+    camera_pos: Vec3,
+    frustum_planes: &[Vec4; 6],
+) -> Vec<DrawIndexedIndirect> {
+    let mut visible: Vec<(f32, DrawIndexedIndirect)> = allocs
+        .filter(|alloc| {
+            alloc.translucent_index_count > 0
+                && AABB::new(alloc.aabb_min, alloc.aabb_max).is_visible(frustum_planes)
+        })
+        .map(|alloc| {
+            let center = (alloc.aabb_min + alloc.aabb_max) * 0.5;
+            (
+                center.distance_squared(camera_pos),
+                alloc.translucent_draw(),
+            )
+        })
+        .collect();
+    visible.sort_unstable_by(|a, b| b.0.total_cmp(&a.0));
+    visible.into_iter().map(|(_, draw)| draw).collect()
+}
+
 /// A contiguous run of free elements inside a unified buffer.
 #[derive(Debug, Clone, Copy)]
 struct FreeBlock {
@@ -114,6 +182,14 @@ struct FreeBlock {
 /// occlusion culling each frame, writing surviving draw commands into a separate
 /// indirect command buffer that is consumed by the main render pass.
 ///
+/// Every subchunk may carry an opaque and a translucent part (see
+/// [`upload_subchunk`](Self::upload_subchunk)).  Both live in the same
+/// allocation, but the culling pass writes them to two command lists, so the
+/// renderer can draw all translucent geometry after all opaque geometry.
+/// The camera draws its translucent list from
+/// [`sort_translucent`](Self::sort_translucent) instead, which orders it
+/// back to front for blending; the GPU-culled one feeds the shadow passes.
+///
 /// Shadow cascades each get their own command + count buffers so culling can be
 /// dispatched independently per cascade without CPU readbacks.
 pub struct IndirectManager {
@@ -127,6 +203,13 @@ pub struct IndirectManager {
     draw_commands_buffer: wgpu::Buffer,
     /// Output buffer for draw commands that survive the culling pass.
     visible_draw_commands_buffer: wgpu::Buffer,
+    /// Output buffer for the visible subchunks' translucent draw commands.
+    /// Only filled because the culling shader is shared with the shadow
+    /// cascades; the camera draws `sorted_translucent_buffer` instead.
+    translucent_draw_commands_buffer: wgpu::Buffer,
+    /// Translucent draw commands sorted back to front on the CPU; see
+    /// [`sort_translucent`](Self::sort_translucent).
+    sorted_translucent_buffer: wgpu::Buffer,
 
     /// Per-slot AABB and draw-argument metadata consumed by the culling shader.
     subchunk_meta_buffer: wgpu::Buffer,
@@ -134,8 +217,13 @@ pub struct IndirectManager {
     /// Atomic counter incremented by the culling shader for each visible subchunk.
     visible_count_buffer: wgpu::Buffer,
     /// CPU-readable staging copy of `visible_count_buffer` (for debugging/stats).
-    #[allow(dead_code)]
     visible_count_staging: wgpu::Buffer,
+    /// Progress of the copy of `visible_count_buffer` into the staging buffer.
+    count_readback: CountReadback,
+    /// Subchunks that survived the most recent culling pass read back.
+    last_visible_count: u32,
+    /// Atomic counter of the commands in `translucent_draw_commands_buffer`.
+    translucent_visible_count_buffer: wgpu::Buffer,
 
     /// Map from subchunk identity to its current buffer allocation.
     allocations: FxHashMap<SubchunkKey, SubchunkAlloc>,
@@ -145,6 +233,8 @@ pub struct IndirectManager {
     next_index_offset: u32,
     /// Number of subchunks currently allocated.
     active_subchunk_count: u32,
+    /// Number of allocated subchunks with translucent geometry.
+    translucent_subchunk_count: u32,
     /// One past the highest slot index ever assigned; bounds the culling dispatch.
     max_slot_bound: u32,
     /// Stack of recycled metadata slot indices ready for reuse.
@@ -169,6 +259,10 @@ pub struct IndirectManager {
     shadow_visible_commands: Vec<wgpu::Buffer>,
     /// One visible-count atomic buffer per shadow cascade.
     shadow_visible_counts: Vec<wgpu::Buffer>,
+    /// One translucent indirect command output buffer per shadow cascade.
+    shadow_translucent_commands: Vec<wgpu::Buffer>,
+    /// One translucent visible-count atomic buffer per shadow cascade.
+    shadow_translucent_counts: Vec<wgpu::Buffer>,
     /// Pre-built bind groups for each shadow cascade culling pass.
     shadow_bind_groups: Vec<wgpu::BindGroup>,
     /// Per-cascade uniform buffers (frustum planes differ per cascade).
@@ -216,6 +310,22 @@ impl IndirectManager {
             mapped_at_creation: false,
         });
 
+        let translucent_draw_commands_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Translucent Draw Commands Buffer"),
+            size: (MAX_SUBCHUNKS * size_of::<DrawIndexedIndirect>()) as u64,
+            usage: wgpu::BufferUsages::INDIRECT
+                | wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let sorted_translucent_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Sorted Translucent Draw Commands Buffer"),
+            size: (MAX_SUBCHUNKS * size_of::<DrawIndexedIndirect>()) as u64,
+            usage: wgpu::BufferUsages::INDIRECT | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         let subchunk_meta_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Subchunk Metadata Buffer"),
             size: (MAX_SUBCHUNKS * size_of::<SubchunkGpuMeta>()) as u64,
@@ -240,6 +350,15 @@ impl IndirectManager {
             mapped_at_creation: false,
         });
 
+        let translucent_visible_count_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Translucent Visible Count Buffer"),
+            size: 4,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::INDIRECT
+                | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         let cull_uniforms_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Cull Uniforms Buffer"),
             size: size_of::<CullUniforms>() as u64,
@@ -259,6 +378,8 @@ impl IndirectManager {
         //   3 – visible count atomic (read-write storage)
         //   4 – Hi-Z texture (non-filtered float)
         //   5 – Hi-Z sampler (non-filtering)
+        //   6 – visible translucent draw commands output (read-write storage)
+        //   7 – visible translucent count atomic (read-write storage)
         let cull_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("Cull Bind Group Layout"),
@@ -319,6 +440,26 @@ impl IndirectManager {
                         ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::NonFiltering),
                         count: None,
                     },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 6,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 7,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
                 ],
             });
 
@@ -353,13 +494,19 @@ impl IndirectManager {
             unified_index_buffer,
             draw_commands_buffer,
             visible_draw_commands_buffer,
+            translucent_draw_commands_buffer,
+            sorted_translucent_buffer,
             subchunk_meta_buffer,
             visible_count_buffer,
             visible_count_staging,
+            count_readback: CountReadback::Idle,
+            last_visible_count: 0,
+            translucent_visible_count_buffer,
             allocations: FxHashMap::default(),
             next_vertex_offset: 0,
             next_index_offset: 0,
             active_subchunk_count: 0,
+            translucent_subchunk_count: 0,
             max_slot_bound: 0,
             // Pre-populate the free-slot stack in reverse so slot 0 is popped first.
             free_slots: {
@@ -376,6 +523,8 @@ impl IndirectManager {
             hiz_sampler,
             shadow_visible_commands: Vec::new(),
             shadow_visible_counts: Vec::new(),
+            shadow_translucent_commands: Vec::new(),
+            shadow_translucent_counts: Vec::new(),
             shadow_bind_groups: Vec::new(),
             shadow_uniform_buffers: Vec::new(),
             coalesce_counter: 0,
@@ -415,6 +564,14 @@ impl IndirectManager {
                     binding: 5,
                     resource: wgpu::BindingResource::Sampler(&self.hiz_sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: self.translucent_draw_commands_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 7,
+                    resource: self.translucent_visible_count_buffer.as_entire_binding(),
+                },
             ],
         }));
     }
@@ -422,8 +579,8 @@ impl IndirectManager {
     /// Allocates per-cascade GPU buffers and bind groups for shadow culling.
     ///
     /// Creates four sets of resources (one per shadow cascade).  Each cascade
-    /// gets its own indirect command buffer, visible-count buffer, uniform
-    /// buffer, and bind group.  A shared 1×1 dummy Hi-Z texture is bound for
+    /// gets its own opaque and translucent indirect command and visible-count
+    /// buffers, uniform buffer, and bind group.  A shared 1×1 dummy Hi-Z texture is bound for
     /// shadow passes because shadow culling skips the occlusion test.
     ///
     /// Must be called once after [`new`] before [`dispatch_shadow_culling`].
@@ -465,6 +622,24 @@ impl IndirectManager {
                 mapped_at_creation: false,
             });
 
+            let translucent_commands = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(&format!("Shadow Translucent Draw Commands Buffer {}", i)),
+                size: (MAX_SUBCHUNKS * size_of::<DrawIndexedIndirect>()) as u64,
+                usage: wgpu::BufferUsages::INDIRECT
+                    | wgpu::BufferUsages::STORAGE
+                    | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+
+            let translucent_count = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(&format!("Shadow Translucent Count Buffer {}", i)),
+                size: 4,
+                usage: wgpu::BufferUsages::STORAGE
+                    | wgpu::BufferUsages::INDIRECT
+                    | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+
             let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
                 label: Some(&format!("Shadow Cull Uniforms Buffer {}", i)),
                 size: size_of::<CullUniforms>() as u64,
@@ -500,11 +675,21 @@ impl IndirectManager {
                         binding: 5,
                         resource: wgpu::BindingResource::Sampler(&self.hiz_sampler),
                     },
+                    wgpu::BindGroupEntry {
+                        binding: 6,
+                        resource: translucent_commands.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 7,
+                        resource: translucent_count.as_entire_binding(),
+                    },
                 ],
             });
 
             self.shadow_visible_commands.push(visible_commands);
             self.shadow_visible_counts.push(visible_count);
+            self.shadow_translucent_commands.push(translucent_commands);
+            self.shadow_translucent_counts.push(translucent_count);
             self.shadow_bind_groups.push(bind_group);
             self.shadow_uniform_buffers.push(uniform_buffer);
         }
@@ -512,8 +697,11 @@ impl IndirectManager {
 
     /// Uploads or replaces a subchunk's geometry in the unified buffers.
     ///
-    /// If `vertices` or `indices` is empty the existing allocation for `key`
-    /// is freed and `true` is returned immediately.
+    /// `opaque` and `translucent` are `(vertices, indices)` pairs, each
+    /// indexing its own vertices.  They share one allocation, translucent
+    /// part last, and are emitted into separate command lists by the culling
+    /// pass.  If both have no indices the existing allocation for `key` is
+    /// freed and `true` is returned immediately.
     ///
     /// If a previous allocation exists for `key` it is freed before the new
     /// geometry is written, so callers do not need to call [`remove_subchunk`]
@@ -526,13 +714,14 @@ impl IndirectManager {
         &mut self,
         queue: &wgpu::Queue,
         key: SubchunkKey,
-        vertices: &[Vertex],
-        indices: &[u32],
+        opaque: (&[Vertex], &[u32]),
+        translucent: (&[Vertex], &[u32]),
         aabb: &AABB,
     ) -> bool {
         // Empty geometry means the subchunk should be removed.
-        if vertices.is_empty() || indices.is_empty() {
+        if opaque.1.is_empty() && translucent.1.is_empty() {
             if let Some(old_alloc) = self.allocations.remove(&key) {
+                self.forget_translucent(&old_alloc);
                 if old_alloc.vertex_count > 0 {
                     Self::add_free_block(
                         &mut self.free_vertex_blocks,
@@ -559,6 +748,7 @@ impl IndirectManager {
 
         // Release the old allocation so its regions can be reused below.
         if let Some(old_alloc) = self.allocations.remove(&key) {
+            self.forget_translucent(&old_alloc);
             if old_alloc.vertex_count > 0 {
                 Self::add_free_block(
                     &mut self.free_vertex_blocks,
@@ -580,8 +770,11 @@ impl IndirectManager {
             self.free_slots.push(old_alloc.slot_index);
         }
 
-        let vertex_count = vertices.len() as u32;
-        let index_count = indices.len() as u32;
+        let opaque_vertex_count = opaque.0.len() as u32;
+        let opaque_index_count = opaque.1.len() as u32;
+        let translucent_index_count = translucent.1.len() as u32;
+        let vertex_count = opaque_vertex_count + translucent.0.len() as u32;
+        let index_count = opaque_index_count + translucent_index_count;
 
         // Try to reuse a free block; fall back to the high-water mark.
         let vertex_alloc =
@@ -666,31 +859,62 @@ impl IndirectManager {
             vertex_count,
             index_offset,
             index_count,
+            translucent_index_count,
+            opaque_vertex_count,
+            aabb_min: aabb.min,
+            aabb_max: aabb.max,
             slot_index,
         };
 
-        // Upload vertex data at the allocated offset.
+        // Upload vertex data at the allocated offset, translucent after opaque.
         let vertex_byte_offset = alloc.vertex_offset as u64 * size_of::<Vertex>() as u64;
         queue.write_buffer(
             &self.unified_vertex_buffer,
             vertex_byte_offset,
-            bytemuck::cast_slice(vertices),
+            bytemuck::cast_slice(opaque.0),
         );
+        if !translucent.0.is_empty() {
+            queue.write_buffer(
+                &self.unified_vertex_buffer,
+                vertex_byte_offset + opaque.0.len() as u64 * size_of::<Vertex>() as u64,
+                bytemuck::cast_slice(translucent.0),
+            );
+        }
 
-        // Upload index data at the allocated offset.
+        // Upload index data at the allocated offset.  Translucent indices
+        // stay relative to their own vertices; their draw command's base
+        // vertex skips the opaque ones.
         let index_byte_offset = alloc.index_offset as u64 * size_of::<u32>() as u64;
         queue.write_buffer(
             &self.unified_index_buffer,
             index_byte_offset,
-            bytemuck::cast_slice(indices),
+            bytemuck::cast_slice(opaque.1),
         );
+        if !translucent.1.is_empty() {
+            queue.write_buffer(
+                &self.unified_index_buffer,
+                index_byte_offset + opaque.1.len() as u64 * size_of::<u32>() as u64,
+                bytemuck::cast_slice(translucent.1),
+            );
+        }
 
         // Write the culling metadata for this slot.
         let subchunk_meta = SubchunkGpuMeta {
             aabb_min: [aabb.min.x, aabb.min.y, aabb.min.z, 0.0],
             // Slot index is packed into the w component of aabb_max.
             aabb_max: [aabb.max.x, aabb.max.y, aabb.max.z, slot_index as f32],
-            draw_data: [index_count, alloc.index_offset, alloc.vertex_offset, 1],
+            draw_data: [
+                opaque_index_count,
+                alloc.index_offset,
+                alloc.vertex_offset,
+                1,
+            ],
+            translucent_draw: [
+                translucent_index_count,
+                alloc.index_offset + opaque_index_count,
+                alloc.vertex_offset + opaque_vertex_count,
+                0,
+            ],
         };
         let meta_byte_offset = slot_index * size_of::<SubchunkGpuMeta>();
         queue.write_buffer(
@@ -706,6 +930,9 @@ impl IndirectManager {
         if !reused_index {
             self.next_index_offset += index_count;
         }
+        if translucent_index_count > 0 {
+            self.translucent_subchunk_count += 1;
+        }
         self.allocations.insert(key, alloc);
         self.active_subchunk_count = self.allocations.len() as u32;
         self.max_slot_bound = self.max_slot_bound.max(slot_index as u32 + 1);
@@ -713,6 +940,13 @@ impl IndirectManager {
         true
     }
 
+    /// Updates the translucent subchunk count for an allocation being freed.
+    fn forget_translucent(&mut self, alloc: &SubchunkAlloc) {
+        if alloc.translucent_index_count > 0 {
+            self.translucent_subchunk_count -= 1;
+        }
+    }
+
     /// Returns the metadata slot index assigned to `key`, if it is allocated.
     pub fn get_slot_index(&self, key: &SubchunkKey) -> Option<usize> {
         self.allocations.get(key).map(|a| a.slot_index)
@@ -725,11 +959,13 @@ impl IndirectManager {
     /// currently allocated.
     pub fn remove_subchunk(&mut self, queue: &wgpu::Queue, key: SubchunkKey) {
         if let Some(alloc) = self.allocations.remove(&key) {
+            self.forget_translucent(&alloc);
             // Zero the metadata slot so the culling shader ignores it.
             let subchunk_meta = SubchunkGpuMeta {
                 aabb_min: [0.0; 4],
                 aabb_max: [0.0; 4],
                 draw_data: [0, 0, 0, 0],
+                translucent_draw: [0, 0, 0, 0],
             };
             let meta_byte_offset = alloc.slot_index * size_of::<SubchunkGpuMeta>();
             queue.write_buffer(
@@ -849,6 +1085,7 @@ impl IndirectManager {
                 aabb_min: [0.0; 4],
                 aabb_max: [0.0; 4],
                 draw_data: [0, 0, 0, 0],
+                translucent_draw: [0, 0, 0, 0],
             };
             let meta_byte_offset = alloc.slot_index * size_of::<SubchunkGpuMeta>();
             queue.write_buffer(
@@ -862,6 +1099,7 @@ impl IndirectManager {
         self.next_vertex_offset = 0;
         self.next_index_offset = 0;
         self.active_subchunk_count = 0;
+        self.translucent_subchunk_count = 0;
         self.max_slot_bound = 0;
         self.free_vertex_blocks.clear();
         self.free_index_blocks.clear();
@@ -877,9 +1115,11 @@ impl IndirectManager {
     /// One workgroup of 64 threads is launched per 64 subchunk slots.
     ///
     /// Does nothing if no subchunks are currently allocated or if the bind
-    /// group has not yet been created via [`update_bind_group`].
+    /// group has not yet been created via [`update_bind_group`].  Unless an
+    /// earlier count is still being read back, the surviving count is copied
+    /// for [`last_visible_count`](Self::last_visible_count).
     pub fn dispatch_culling(
-        &self,
+        &mut self,
         encoder: &mut wgpu::CommandEncoder,
         queue: &wgpu::Queue,
         view_proj: &glam::Mat4,
@@ -889,11 +1129,17 @@ impl IndirectManager {
         screen_size: [f32; 2],
     ) {
         if self.active_subchunk_count == 0 {
+            self.last_visible_count = 0;
             return;
         }
 
-        // Reset the visible counter before the pass writes into it.
+        // Reset the visible counters before the pass writes into them.
         queue.write_buffer(&self.visible_count_buffer, 0, &0u32.to_le_bytes());
+        queue.write_buffer(
+            &self.translucent_visible_count_buffer,
+            0,
+            &0u32.to_le_bytes(),
+        );
 
         let active = self.max_slot_bound;
 
@@ -912,19 +1158,75 @@ impl IndirectManager {
             let bytes_to_clear = (active as u64) * size_of::<DrawIndexedIndirect>() as u64;
             if bytes_to_clear > 0 {
                 encoder.clear_buffer(&self.visible_draw_commands_buffer, 0, Some(bytes_to_clear));
+                encoder.clear_buffer(
+                    &self.translucent_draw_commands_buffer,
+                    0,
+                    Some(bytes_to_clear),
+                );
             }
 
-            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("Culling Pass"),
-                timestamp_writes: None,
+            {
+                let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("Culling Pass"),
+                    timestamp_writes: None,
+                });
+                cpass.set_pipeline(&self.cull_pipeline);
+                cpass.set_bind_group(0, bind_group, &[]);
+
+                // Round up to a full workgroup; the shader discards out-of-range threads.
+                let workgroup_count = (active + 63) / 64;
+                cpass.dispatch_workgroups(workgroup_count, 1, 1);
+            }
+
+            if matches!(self.count_readback, CountReadback::Idle) {
+                encoder.copy_buffer_to_buffer(
+                    &self.visible_count_buffer,
+                    0,
+                    &self.visible_count_staging,
+                    0,
+                    4,
+                );
+                self.count_readback = CountReadback::Copied;
+            }
+        }
+    }
+
+    /// Starts mapping the visible count copied by [`dispatch_culling`]
+    /// once the frame that recorded it has been submitted.
+    ///
+    /// [`dispatch_culling`]: Self::dispatch_culling
+    pub fn map_visible_count(&mut self) {
+        if !matches!(self.count_readback, CountReadback::Copied) {
+            return;
+        }
+        let result = Arc::new(Mutex::new(None));
+        let callback_result = Arc::clone(&result);
+        self.visible_count_staging
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |mapped| {
+                *callback_result.lock() = Some(mapped.is_ok());
             });
-            cpass.set_pipeline(&self.cull_pipeline);
-            cpass.set_bind_group(0, bind_group, &[]);
+        self.count_readback = CountReadback::Mapping(result);
+    }
 
-            // Round up to a full workgroup; the shader discards out-of-range threads.
-            let workgroup_count = (active + 63) / 64;
-            cpass.dispatch_workgroups(workgroup_count, 1, 1);
+    /// Picks up the visible count once its mapping has finished, without
+    /// waiting for the GPU.  Call once per frame before culling.
+    pub fn poll_visible_count(&mut self, device: &wgpu::Device) {
+        let CountReadback::Mapping(result) = &self.count_readback else {
+            return;
+        };
+        // Never waits; only runs the callbacks of mappings that are done.
+        let _ = device.poll(wgpu::PollType::Poll);
+        let Some(mapped) = result.lock().take() else {
+            return;
+        };
+        if mapped {
+            let data = self.visible_count_staging.slice(..).get_mapped_range();
+            self.last_visible_count = u32::from_le_bytes(data[..4].try_into().unwrap());
+            drop(data);
+            self.visible_count_staging.unmap();
         }
+        self.count_readback = CountReadback::Idle;
     }
 
     /// Returns a reference to the unified vertex buffer.
@@ -942,6 +1244,41 @@ impl IndirectManager {
         &self.visible_draw_commands_buffer
     }
 
+    /// Writes the translucent draw commands of the subchunks inside
+    /// `frustum_planes` to [`sorted_translucent_draw_commands`], farthest
+    /// from `camera_pos` first.
+    ///
+    /// # Returns
+    /// The number of commands written.
+    ///
+    /// [`sorted_translucent_draw_commands`]: Self::sorted_translucent_draw_commands
+    pub fn sort_translucent(
+        &self,
+        queue: &wgpu::Queue,
+        camera_pos: Vec3,
+        frustum_planes: &[Vec4; 6],
+    ) -> u32 {
+        if !self.has_translucent() {
+            return 0;
+        }
+        let draws =
+            translucent_draws_back_to_front(self.allocations.values(), camera_pos, frustum_planes);
+        if !draws.is_empty() {
+            queue.write_buffer(
+                &self.sorted_translucent_buffer,
+                0,
+                bytemuck::cast_slice(&draws),
+            );
+        }
+        draws.len() as u32
+    }
+
+    /// Returns the translucent draw commands written by
+    /// [`sort_translucent`](Self::sort_translucent).
+    pub fn sorted_translucent_draw_commands(&self) -> &wgpu::Buffer {
+        &self.sorted_translucent_buffer
+    }
+
     /// Returns the shadow cascade's indirect draw command buffer.
     pub fn shadow_draw_commands(&self, cascade_idx: usize) -> &wgpu::Buffer {
         &self.shadow_visible_commands[cascade_idx]
@@ -957,11 +1294,37 @@ impl IndirectManager {
         &self.shadow_visible_counts[cascade_idx]
     }
 
+    /// Returns the shadow cascade's translucent indirect draw command buffer.
+    pub fn shadow_translucent_draw_commands(&self, cascade_idx: usize) -> &wgpu::Buffer {
+        &self.shadow_translucent_commands[cascade_idx]
+    }
+
+    /// Returns the translucent visible-count buffer for the given shadow cascade.
+    pub fn shadow_translucent_count_buffer(&self, cascade_idx: usize) -> &wgpu::Buffer {
+        &self.shadow_translucent_counts[cascade_idx]
+    }
+
+    /// Returns `true` if any allocated subchunk has translucent geometry.
+    ///
+    /// While it is `false` the translucent command lists are empty and the
+    /// renderer skips drawing them, leaving a single terrain draw per pass.
+    pub fn has_translucent(&self) -> bool {
+        self.translucent_subchunk_count > 0
+    }
+
     /// Returns the number of subchunks currently allocated.
     pub fn active_count(&self) -> u32 {
         self.active_subchunk_count
     }
 
+    /// Returns how many subchunks the GPU culling pass let through, read
+    /// back from a recent frame (usually one or two frames old) so reading
+    /// it never stalls.  Compare with [`active_count`](Self::active_count)
+    /// to see how much frustum and occlusion culling removes.
+    pub fn last_visible_count(&self) -> u32 {
+        self.last_visible_count
+    }
+
     /// Dispatches a frustum culling compute pass for one shadow cascade.
     ///
     /// Shadow culling uses the cascade's own frustum planes but skips Hi-Z
@@ -979,12 +1342,17 @@ impl IndirectManager {
             return;
         }
 
-        // Reset the per-cascade visible counter.
+        // Reset the per-cascade visible counters.
         queue.write_buffer(
             &self.shadow_visible_counts[cascade_idx],
             0,
             &0u32.to_le_bytes(),
         );
+        queue.write_buffer(
+            &self.shadow_translucent_counts[cascade_idx],
+            0,
+            &0u32.to_le_bytes(),
+        );
 
         let active = self.max_slot_bound;
 
@@ -1010,6 +1378,11 @@ impl IndirectManager {
                 0,
                 Some(bytes_to_clear),
             );
+            encoder.clear_buffer(
+                &self.shadow_translucent_commands[cascade_idx],
+                0,
+                Some(bytes_to_clear),
+            );
         }
 
         let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
@@ -1038,6 +1411,7 @@ impl IndirectManager {
         self.next_vertex_offset = 0;
         self.next_index_offset = 0;
         self.active_subchunk_count = 0;
+        self.translucent_subchunk_count = 0;
         self.max_slot_bound = 0;
         self.free_slots.clear();
         self.free_slots.extend((0..MAX_SUBCHUNKS).rev());
@@ -1045,3 +1419,49 @@ impl IndirectManager {
         self.free_index_blocks.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::frustum::extract_frustum_planes;
+    use glam::Mat4;
+
+    fn alloc_at(x: f32, index_offset: u32, translucent_index_count: u32) -> SubchunkAlloc {
+        SubchunkAlloc {
+            vertex_offset: 0,
+            vertex_count: 8,
+            index_offset,
+            index_count: 12,
+            translucent_index_count,
+            opaque_vertex_count: 4,
+            aabb_min: Vec3::new(x, 0.0, -8.0),
+            aabb_max: Vec3::new(x + 16.0, 16.0, 8.0),
+            slot_index: 0,
+        }
+    }
+
+    #[test]
+    fn translucent_draws_go_back_to_front() {
+        // Looking down +X from the origin.
+        let view = Mat4::look_at_rh(Vec3::ZERO, Vec3::X, Vec3::Y);
+        let proj = Mat4::perspective_rh(1.2, 1.0, 0.1, 500.0);
+        let planes = extract_frustum_planes(&(proj * view));
+
+        let allocs = [
+            alloc_at(16.0, 100, 6),
+            alloc_at(64.0, 200, 6),
+            // No translucent part, so never drawn.
+            alloc_at(32.0, 300, 0),
+            // Behind the camera.
+            alloc_at(-64.0, 400, 6),
+        ];
+        let draws = translucent_draws_back_to_front(allocs.iter(), Vec3::ZERO, &planes);
+        let first_indices: Vec<u32> = draws.iter().map(|d| d.first_index).collect();
+        assert_eq!(first_indices, [206, 106]);
+        assert!(
+            draws
+                .iter()
+                .all(|d| d.index_count == 6 && d.base_vertex == 4)
+        );
+    }
+}
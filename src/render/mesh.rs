@@ -1,3 +1,10 @@
+use glam::Vec3;
+
+use crate::constants::{
+    CHUNK_SIZE, DIG_STAGES, NUM_SUBCHUNKS, SUBCHUNK_HEIGHT, TEX_DIG_CRACKS, WORLD_HEIGHT,
+};
+use crate::core::block::{BlockType, Facing};
+use crate::core::mobs::entity::MobEntity;
 use crate::core::vertex::{OutlineVertex, Vertex};
 
 /// Adds a single quad (two triangles) to the vertex and index buffers.
@@ -127,21 +134,480 @@ pub fn add_greedy_quad(
     ]);
 }
 
+/// Adds a greedy-merged block face lying on an axis-aligned plane, wound so
+/// it faces outward.
+///
+/// `face_dir` is a face direction as in `World::build_subchunk_mesh`
+/// (0 = −X, 1 = +X, 2 = −Y, 3 = +Y, 4 = −Z, 5 = +Z) and `bounds` holds the
+/// face's min and max corners, which are equal along the face normal.  The
+/// texture repeats once per block across the face (see [`add_greedy_quad`]).
+pub fn add_block_face(
+    vertices: &mut Vec<Vertex>,
+    indices: &mut Vec<u32>,
+    face_dir: i32,
+    bounds: [[f32; 3]; 2],
+    color: [f32; 3],
+    tex_index: f32,
+) {
+    let [[x0, y0, z0], [x1, y1, z1]] = bounds;
+    let (corners, normal, width, height) = match face_dir {
+        0 => (
+            [[x0, y0, z0], [x0, y0, z1], [x0, y1, z1], [x0, y1, z0]],
+            [-1.0, 0.0, 0.0],
+            z1 - z0,
+            y1 - y0,
+        ),
+        1 => (
+            [[x1, y0, z1], [x1, y0, z0], [x1, y1, z0], [x1, y1, z1]],
+            [1.0, 0.0, 0.0],
+            z1 - z0,
+            y1 - y0,
+        ),
+        2 => (
+            [[x0, y0, z1], [x0, y0, z0], [x1, y0, z0], [x1, y0, z1]],
+            [0.0, -1.0, 0.0],
+            z1 - z0,
+            x1 - x0,
+        ),
+        3 => (
+            [[x0, y1, z0], [x0, y1, z1], [x1, y1, z1], [x1, y1, z0]],
+            [0.0, 1.0, 0.0],
+            z1 - z0,
+            x1 - x0,
+        ),
+        4 => (
+            [[x1, y0, z0], [x0, y0, z0], [x0, y1, z0], [x1, y1, z0]],
+            [0.0, 0.0, -1.0],
+            x1 - x0,
+            y1 - y0,
+        ),
+        5 => (
+            [[x0, y0, z1], [x1, y0, z1], [x1, y1, z1], [x0, y1, z1]],
+            [0.0, 0.0, 1.0],
+            x1 - x0,
+            y1 - y0,
+        ),
+        _ => unreachable!(),
+    };
+    let [v0, v1, v2, v3] = corners;
+    add_greedy_quad(
+        vertices, indices, v0, v1, v2, v3, normal, color, tex_index, 1.0, 0.0, width, height,
+    );
+}
+
+/// Adds a merged top surface of a water body to the vertex and index
+/// buffers.
+///
+/// Like [`add_greedy_quad`] for an upward-facing quad, but tagged with
+/// [`Vertex::WATER_SURFACE_NORMAL`] so the water shader animates waves on
+/// it.  The corners are `(x0, z0)`–`(x1, z1)` at height `y`.
+///
+/// # Arguments
+/// * `vertices` - Mutable reference to the vertex buffer to append to.
+/// * `indices` - Mutable reference to the index buffer to append to.
+/// * `min` - World-space `(x0, y, z0)` corner of the surface.
+/// * `size` - Extent of the surface along X and Z, in blocks (1–16 each).
+/// * `color` - RGB color applied to all four vertices.
+/// * `tex_index` - Index into the texture array sampler.
+pub fn add_water_surface_quad(
+    vertices: &mut Vec<Vertex>,
+    indices: &mut Vec<u32>,
+    min: [f32; 3],
+    size: [f32; 2],
+    color: [f32; 3],
+    tex_index: f32,
+) {
+    let [x0, y, z0] = min;
+    let (x1, z1) = (x0 + size[0], z0 + size[1]);
+    let n_idx = Vertex::WATER_SURFACE_NORMAL;
+    let (w, h) = (size[1] as u8, size[0] as u8);
+    let base_idx = vertices.len() as u32;
+
+    for (position, corner) in [
+        ([x0, y, z0], 1),
+        ([x0, y, z1], 2),
+        ([x1, y, z1], 3),
+        ([x1, y, z0], 0),
+    ] {
+        vertices.push(Vertex {
+            position,
+            packed: Vertex::pack(n_idx, color, tex_index as u8, corner, w, h),
+        });
+    }
+    indices.extend_from_slice(&[
+        base_idx,
+        base_idx + 1,
+        base_idx + 2,
+        base_idx,
+        base_idx + 2,
+        base_idx + 3,
+    ]);
+}
+
+/// A block-local quad of a partial block: `(corners, normal, culled)`.
+///
+/// Corners are in `[0, 1]` block space, wound like [`add_quad`] expects.
+type PartialQuad = ([[f32; 3]; 4], [f32; 3], bool);
+
+/// Block-local quads of a south-ascending stair.
+///
+/// Faces with `culled == false` (the step tread and riser) lie inside the
+/// cell and are always emitted; the rest sit on the cell boundary and are
+/// skipped when the neighbor on that side hides them.
+const STAIRS_QUADS: [PartialQuad; 10] = [
+    // Bottom.
+    (
+        [
+            [0.0, 0.0, 1.0],
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 0.0, 1.0],
+        ],
+        [0.0, -1.0, 0.0],
+        true,
+    ),
+    // Lower tread at Y+0.5, front half.
+    (
+        [
+            [0.0, 0.5, 0.0],
+            [0.0, 0.5, 0.5],
+            [1.0, 0.5, 0.5],
+            [1.0, 0.5, 0.0],
+        ],
+        [0.0, 1.0, 0.0],
+        false,
+    ),
+    // Upper top, back half.
+    (
+        [
+            [0.0, 1.0, 0.5],
+            [0.0, 1.0, 1.0],
+            [1.0, 1.0, 1.0],
+            [1.0, 1.0, 0.5],
+        ],
+        [0.0, 1.0, 0.0],
+        true,
+    ),
+    // Front (lower half only).
+    (
+        [
+            [1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0],
+            [0.0, 0.5, 0.0],
+            [1.0, 0.5, 0.0],
+        ],
+        [0.0, 0.0, -1.0],
+        true,
+    ),
+    // Step riser at Z+0.5.
+    (
+        [
+            [1.0, 0.5, 0.5],
+            [0.0, 0.5, 0.5],
+            [0.0, 1.0, 0.5],
+            [1.0, 1.0, 0.5],
+        ],
+        [0.0, 0.0, -1.0],
+        false,
+    ),
+    // Back (full height).
+    (
+        [
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [1.0, 1.0, 1.0],
+            [0.0, 1.0, 1.0],
+        ],
+        [0.0, 0.0, 1.0],
+        true,
+    ),
+    // Left: lower half and upper-back quarter.
+    (
+        [
+            [0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.0, 0.5, 1.0],
+            [0.0, 0.5, 0.0],
+        ],
+        [-1.0, 0.0, 0.0],
+        true,
+    ),
+    (
+        [
+            [0.0, 0.5, 0.5],
+            [0.0, 0.5, 1.0],
+            [0.0, 1.0, 1.0],
+            [0.0, 1.0, 0.5],
+        ],
+        [-1.0, 0.0, 0.0],
+        true,
+    ),
+    // Right: lower half and upper-back quarter.
+    (
+        [
+            [1.0, 0.0, 1.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 0.5, 0.0],
+            [1.0, 0.5, 1.0],
+        ],
+        [1.0, 0.0, 0.0],
+        true,
+    ),
+    (
+        [
+            [1.0, 0.5, 1.0],
+            [1.0, 0.5, 0.5],
+            [1.0, 1.0, 0.5],
+            [1.0, 1.0, 1.0],
+        ],
+        [1.0, 0.0, 0.0],
+        true,
+    ),
+];
+
+/// Block-local quads of a full-width block `height` tall, standing on the
+/// bottom of its cell, in the same format as [`STAIRS_QUADS`].
+///
+/// The top lies inside the cell for any `height` below 1, so it never
+/// touches the neighbor above and is always visible.
+const fn flat_block_quads(height: f32) -> [PartialQuad; 6] {
+    [
+        // Bottom.
+        (
+            [
+                [0.0, 0.0, 1.0],
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 0.0, 1.0],
+            ],
+            [0.0, -1.0, 0.0],
+            true,
+        ),
+        // Top.
+        (
+            [
+                [0.0, height, 0.0],
+                [0.0, height, 1.0],
+                [1.0, height, 1.0],
+                [1.0, height, 0.0],
+            ],
+            [0.0, 1.0, 0.0],
+            false,
+        ),
+        // Front / back.
+        (
+            [
+                [1.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0],
+                [0.0, height, 0.0],
+                [1.0, height, 0.0],
+            ],
+            [0.0, 0.0, -1.0],
+            true,
+        ),
+        (
+            [
+                [0.0, 0.0, 1.0],
+                [1.0, 0.0, 1.0],
+                [1.0, height, 1.0],
+                [0.0, height, 1.0],
+            ],
+            [0.0, 0.0, 1.0],
+            true,
+        ),
+        // Left / right.
+        (
+            [
+                [0.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0],
+                [0.0, height, 1.0],
+                [0.0, height, 0.0],
+            ],
+            [-1.0, 0.0, 0.0],
+            true,
+        ),
+        (
+            [
+                [1.0, 0.0, 1.0],
+                [1.0, 0.0, 0.0],
+                [1.0, height, 0.0],
+                [1.0, height, 1.0],
+            ],
+            [1.0, 0.0, 0.0],
+            true,
+        ),
+    ]
+}
+
+/// Block-local quads of a bottom slab.
+const SLAB_QUADS: [PartialQuad; 6] = flat_block_quads(0.5);
+
+/// Height of a [`BlockType::SnowLayer`] within its cell.
+const SNOW_LAYER_HEIGHT: f32 = 0.125;
+
+/// Block-local quads of a snow layer.
+const SNOW_LAYER_QUADS: [PartialQuad; 6] = flat_block_quads(SNOW_LAYER_HEIGHT);
+
+/// Height of a [`BlockType::Bed`] within its cell.
+const BED_HEIGHT: f32 = 0.5625;
+
+/// Block-local quads of a bed.
+const BED_QUADS: [PartialQuad; 6] = flat_block_quads(BED_HEIGHT);
+
+/// Half the width of a [`BlockType::Torch`] post.
+const TORCH_HALF_WIDTH: f32 = 0.0625;
+
+/// Height of a [`BlockType::Torch`] within its cell.
+const TORCH_HEIGHT: f32 = 0.625;
+
+/// Block-local quads of a torch: a thin post standing in the middle of the
+/// cell, in the same format as [`STAIRS_QUADS`].
+///
+/// Only the bottom touches the cell boundary; the sides and top lie inside
+/// the cell and are always visible.
+const TORCH_QUADS: [PartialQuad; 6] = {
+    let (lo, hi, h) = (0.5 - TORCH_HALF_WIDTH, 0.5 + TORCH_HALF_WIDTH, TORCH_HEIGHT);
+    [
+        // Bottom.
+        (
+            [[lo, 0.0, hi], [lo, 0.0, lo], [hi, 0.0, lo], [hi, 0.0, hi]],
+            [0.0, -1.0, 0.0],
+            true,
+        ),
+        // Top.
+        (
+            [[lo, h, lo], [lo, h, hi], [hi, h, hi], [hi, h, lo]],
+            [0.0, 1.0, 0.0],
+            false,
+        ),
+        // Front / back.
+        (
+            [[hi, 0.0, lo], [lo, 0.0, lo], [lo, h, lo], [hi, h, lo]],
+            [0.0, 0.0, -1.0],
+            false,
+        ),
+        (
+            [[lo, 0.0, hi], [hi, 0.0, hi], [hi, h, hi], [lo, h, hi]],
+            [0.0, 0.0, 1.0],
+            false,
+        ),
+        // Left / right.
+        (
+            [[lo, 0.0, lo], [lo, 0.0, hi], [lo, h, hi], [lo, h, lo]],
+            [-1.0, 0.0, 0.0],
+            false,
+        ),
+        (
+            [[hi, 0.0, hi], [hi, 0.0, lo], [hi, h, lo], [hi, h, hi]],
+            [1.0, 0.0, 0.0],
+            false,
+        ),
+    ]
+};
+
+/// Adds the reduced geometry of a partial block (slab, stairs, snow layer,
+/// bed or torch) to the vertex and index buffers.
+///
+/// Stairs are described once in a south-ascending orientation and rotated
+/// about the block's vertical centre line to match `facing`; the other
+/// blocks ignore `facing`.
+///
+/// # Arguments
+/// * `vertices` - Mutable reference to the vertex buffer to append to.
+/// * `indices` - Mutable reference to the index buffer to append to.
+/// * `block` - The partial block type (see [`BlockType::is_partial`]).
+/// * `origin` - World-space position of the block's minimum corner.
+/// * `facing` - Direction the stairs ascend towards.
+/// * `visible` - Whether each boundary face may be drawn, indexed in world
+///   space as `[-X, +X, -Y, +Y, -Z, +Z]`.
+pub fn add_partial_block(
+    vertices: &mut Vec<Vertex>,
+    indices: &mut Vec<u32>,
+    block: BlockType,
+    origin: [f32; 3],
+    facing: Facing,
+    visible: [bool; 6],
+) {
+    let quads: &[PartialQuad] = match block {
+        BlockType::StoneSlab => &SLAB_QUADS,
+        BlockType::SnowLayer => &SNOW_LAYER_QUADS,
+        BlockType::Bed => &BED_QUADS,
+        BlockType::Torch => &TORCH_QUADS,
+        _ => &STAIRS_QUADS,
+    };
+    let facing = if block.is_directional() {
+        facing
+    } else {
+        Facing::South
+    };
+
+    // Rotation about the vertical axis taking +Z (the canonical ascent
+    // direction) to `facing`.  All four are proper rotations, so the
+    // winding order of every quad is preserved.
+    let rotate_dir = |[x, y, z]: [f32; 3]| -> [f32; 3] {
+        match facing {
+            Facing::South => [x, y, z],
+            Facing::North => [-x, y, -z],
+            Facing::East => [z, y, -x],
+            Facing::West => [-z, y, x],
+        }
+    };
+    let rotate_point = |[x, y, z]: [f32; 3]| -> [f32; 3] {
+        let [rx, ry, rz] = rotate_dir([x - 0.5, y, z - 0.5]);
+        [origin[0] + rx + 0.5, origin[1] + ry, origin[2] + rz + 0.5]
+    };
+
+    let r = block.roughness();
+    let m = block.metallic();
+
+    for &(corners, normal, culled) in quads {
+        let normal = rotate_dir(normal);
+        let face = match normal {
+            [x, _, _] if x < -0.5 => 0,
+            [x, _, _] if x > 0.5 => 1,
+            [_, y, _] if y < -0.5 => 2,
+            [_, y, _] if y > 0.5 => 3,
+            [_, _, z] if z < -0.5 => 4,
+            _ => 5,
+        };
+        if culled && !visible[face] {
+            continue;
+        }
+        let (tex, color) = match face {
+            2 => (block.tex_bottom(), block.bottom_color()),
+            3 => (block.tex_top(), block.top_color()),
+            _ => (block.tex_side(), block.color()),
+        };
+        add_quad(
+            vertices,
+            indices,
+            rotate_point(corners[0]),
+            rotate_point(corners[1]),
+            rotate_point(corners[2]),
+            rotate_point(corners[3]),
+            normal,
+            color,
+            tex,
+            r,
+            m,
+        );
+    }
+}
+
 /// Builds the geometry for a screen-space crosshair overlay.
 ///
 /// Produces two orthogonal rectangles (a horizontal bar and a vertical bar)
-/// centered at the origin in normalized device coordinates. The aspect ratio
-/// correction uses a hardcoded 16:9 ratio so the crosshair appears square
-/// on widescreen displays.
+/// centered at the origin in normalized device coordinates.  Sizes are
+/// relative to the screen height; horizontal ones are divided by `aspect`
+/// (width / height) so the crosshair stays a square plus at any resolution.
+/// Rebuild it whenever the window is resized.
 ///
 /// Returns a tuple of `(vertices, indices)` ready to be uploaded to the GPU.
-pub fn build_crosshair() -> (Vec<Vertex>, Vec<u32>) {
+pub fn build_crosshair(aspect: f32) -> (Vec<Vertex>, Vec<u32>) {
     let size = 0.02;
     let thickness = 0.001;
     let n_idx = Vertex::pack_normal([0.0, 0.0, 1.0]);
 
-    // Compensate for widescreen aspect ratio so the crosshair isn't stretched.
-    let aspect = 16.0 / 9.0;
     let size_x = size / aspect;
     let thickness_x = thickness / aspect;
 
@@ -267,36 +733,14 @@ pub fn build_block_outline(
     let mut indices = Vec::new();
 
     let mut push_segment = |a: [f32; 3], b: [f32; 3]| {
-        let base = vertices.len() as u32;
-        vertices.push(OutlineVertex {
-            position: a,
-            other: [b[0], b[1], b[2], -1.0],
-            color: packed_color,
-            uv: [half_width_px, 0.0],
-            tex_index: 0.0,
-        });
-        vertices.push(OutlineVertex {
-            position: a,
-            other: [b[0], b[1], b[2], 1.0],
-            color: packed_color,
-            uv: [half_width_px, 0.0],
-            tex_index: 0.0,
-        });
-        vertices.push(OutlineVertex {
-            position: b,
-            other: [a[0], a[1], a[2], -1.0],
-            color: packed_color,
-            uv: [half_width_px, 0.0],
-            tex_index: 0.0,
-        });
-        vertices.push(OutlineVertex {
-            position: b,
-            other: [a[0], a[1], a[2], 1.0],
-            color: packed_color,
-            uv: [half_width_px, 0.0],
-            tex_index: 0.0,
-        });
-        indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+        push_outline_segment(
+            &mut vertices,
+            &mut indices,
+            a,
+            b,
+            packed_color,
+            half_width_px,
+        );
     };
 
     for (face_idx, &visible) in visible_faces.iter().enumerate() {
@@ -312,38 +756,484 @@ pub fn build_block_outline(
 
     (vertices, indices)
 }
+
+/// Appends one screen-space thick line segment from `a` to `b`.
+///
+/// The segment is a quad of four [`OutlineVertex`]es; each vertex carries the
+/// opposite endpoint in `other` and the side (`±1`) in `other.w`, so the
+/// outline shader can extrude it to `half_width_px` on either side.
+fn push_outline_segment(
+    vertices: &mut Vec<OutlineVertex>,
+    indices: &mut Vec<u32>,
+    a: [f32; 3],
+    b: [f32; 3],
+    packed_color: [u8; 4],
+    half_width_px: f32,
+) {
+    let base = vertices.len() as u32;
+    for (position, other) in [(a, b), (b, a)] {
+        for side in [-1.0, 1.0] {
+            vertices.push(OutlineVertex {
+                position,
+                other: [other[0], other[1], other[2], side],
+                color: packed_color,
+                uv: [half_width_px, 0.0],
+                tex_index: 0.0,
+            });
+        }
+    }
+    indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+}
+
+/// Builds the chunk-border debug overlay around the chunk `center`.
+///
+/// Only chunks within `radius` chunks (Chebyshev distance) of `center` are
+/// considered, so the cost is bounded by the radius rather than by how many
+/// chunks are loaded.  A vertical line is drawn at every chunk corner that
+/// touches at least one loaded chunk in range, and the player's own chunk
+/// additionally gets a horizontal ring at every sub-chunk boundary.
+///
+/// # Arguments
+/// * `center` - Chunk coordinates `(cx, cz)` the player is standing in.
+/// * `radius` - Radius in chunks; `0` outlines only the current chunk.
+/// * `is_loaded` - Returns `true` if the chunk `(cx, cz)` is loaded.
+/// * `color` - RGBA line color.
+pub fn build_chunk_borders(
+    center: (i32, i32),
+    radius: i32,
+    is_loaded: impl Fn(i32, i32) -> bool,
+    color: [f32; 4],
+) -> (Vec<OutlineVertex>, Vec<u32>) {
+    let packed_color = Vertex::pack_color_rgba(color);
+    let half_width_px = 1.0;
+    let top = WORLD_HEIGHT as f32;
+    let (cx, cz) = center;
+    let in_range_and_loaded =
+        |x: i32, z: i32| (x - cx).abs() <= radius && (z - cz).abs() <= radius && is_loaded(x, z);
+
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    // Corner (gx, gz) is shared by the chunks (gx-1..=gx, gz-1..=gz).
+    for gx in (cx - radius)..=(cx + radius + 1) {
+        for gz in (cz - radius)..=(cz + radius + 1) {
+            let touches_loaded = in_range_and_loaded(gx, gz)
+                || in_range_and_loaded(gx - 1, gz)
+                || in_range_and_loaded(gx, gz - 1)
+                || in_range_and_loaded(gx - 1, gz - 1);
+            if !touches_loaded {
+                continue;
+            }
+            let x = (gx * CHUNK_SIZE) as f32;
+            let z = (gz * CHUNK_SIZE) as f32;
+            push_outline_segment(
+                &mut vertices,
+                &mut indices,
+                [x, 0.0, z],
+                [x, top, z],
+                packed_color,
+                half_width_px,
+            );
+        }
+    }
+
+    if is_loaded(cx, cz) {
+        let min_x = (cx * CHUNK_SIZE) as f32;
+        let min_z = (cz * CHUNK_SIZE) as f32;
+        let max_x = min_x + CHUNK_SIZE as f32;
+        let max_z = min_z + CHUNK_SIZE as f32;
+        for level in 0..=NUM_SUBCHUNKS {
+            let y = (level * SUBCHUNK_HEIGHT) as f32;
+            let corners = [
+                [min_x, y, min_z],
+                [max_x, y, min_z],
+                [max_x, y, max_z],
+                [min_x, y, max_z],
+            ];
+            for i in 0..4 {
+                push_outline_segment(
+                    &mut vertices,
+                    &mut indices,
+                    corners[i],
+                    corners[(i + 1) % 4],
+                    packed_color,
+                    half_width_px,
+                );
+            }
+        }
+    }
+
+    (vertices, indices)
+}
+
+/// Builds a translucent overlay covering one face of the block at `(x, y, z)`.
+///
+/// Used to show which face of the targeted block the player is pointing at,
+/// i.e. the face a new block would be placed against.  The quad is emitted
+/// as [`OutlineVertex`]es with zero line width so it can be drawn by the
+/// outline pipeline in the same pass as [`build_block_outline`]; each
+/// vertex points at the diagonally opposite corner so the shader's
+/// screen-space direction is never degenerate.
+///
+/// # Arguments
+/// * `x`, `y`, `z` - Block grid position.
+/// * `normal` - Outward normal of the face to cover, e.g. `(0, 1, 0)` for
+///   the top face (see `RaycastHit::normal`).
+/// * `color` - RGBA overlay color; keep alpha low for a subtle tint.
+pub fn build_face_highlight(
+    x: i32,
+    y: i32,
+    z: i32,
+    normal: (i32, i32, i32),
+    color: [f32; 4],
+) -> (Vec<OutlineVertex>, Vec<u32>) {
+    // Slightly further out than the outline pad so the two never z-fight.
+    let pad = 0.006;
+    let min = [x as f32, y as f32, z as f32];
+    let max = [min[0] + 1.0, min[1] + 1.0, min[2] + 1.0];
+
+    let (axis, plane) = match normal {
+        (1, _, _) => (0, max[0] + pad),
+        (-1, _, _) => (0, min[0] - pad),
+        (_, 1, _) => (1, max[1] + pad),
+        (_, -1, _) => (1, min[1] - pad),
+        (_, _, 1) => (2, max[2] + pad),
+        _ => (2, min[2] - pad),
+    };
+    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+    let corner = |a: f32, b: f32| {
+        let mut p = [0.0; 3];
+        p[axis] = plane;
+        p[u] = a;
+        p[v] = b;
+        p
+    };
+    let corners = [
+        corner(min[u], min[v]),
+        corner(max[u], min[v]),
+        corner(max[u], max[v]),
+        corner(min[u], max[v]),
+    ];
+
+    let packed_color = Vertex::pack_color_rgba(color);
+    let vertices = (0..4)
+        .map(|i| {
+            let opposite = corners[(i + 2) % 4];
+            OutlineVertex {
+                position: corners[i],
+                other: [opposite[0], opposite[1], opposite[2], 0.0],
+                color: packed_color,
+                uv: [0.0, 0.0],
+                tex_index: 0.0,
+            }
+        })
+        .collect();
+
+    (vertices, vec![0, 1, 2, 0, 2, 3])
+}
+
+/// Builds a ghost cube for the empty cell `(x, y, z)` a block would be
+/// placed in.
+///
+/// The cube is inset slightly from the cell so it never z-fights with the
+/// faces of its neighbors, including the targeted block's outline.  Its
+/// twelve edges are drawn as thin lines in `color` and its six faces as a
+/// fill at a quarter of `color`'s alpha, both for the outline pipeline (see
+/// [`build_face_highlight`] for how the faces are encoded).
+///
+/// # Arguments
+/// * `x`, `y`, `z` - Grid position of the cell, e.g. `RaycastHit::place_position`.
+/// * `color` - RGBA edge color.
+pub fn build_placement_preview(
+    x: i32,
+    y: i32,
+    z: i32,
+    color: [f32; 4],
+) -> (Vec<OutlineVertex>, Vec<u32>) {
+    let inset = 0.02;
+    let min = [x as f32 + inset, y as f32 + inset, z as f32 + inset];
+    let max = [
+        x as f32 + 1.0 - inset,
+        y as f32 + 1.0 - inset,
+        z as f32 + 1.0 - inset,
+    ];
+    let corner = |i: usize| {
+        [
+            if i & 1 != 0 { max[0] } else { min[0] },
+            if i & 2 != 0 { max[1] } else { min[1] },
+            if i & 4 != 0 { max[2] } else { min[2] },
+        ]
+    };
+
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    // Faces first so the edges blend on top of them.  Corner indices are
+    // bit masks (1 = +X, 2 = +Y, 4 = +Z); each face lists its corners in
+    // order around the face.
+    let fill_color = Vertex::pack_color_rgba([color[0], color[1], color[2], color[3] * 0.25]);
+    let faces: [[usize; 4]; 6] = [
+        [1, 3, 7, 5], // +X
+        [0, 4, 6, 2], // -X
+        [2, 6, 7, 3], // +Y
+        [0, 1, 5, 4], // -Y
+        [4, 5, 7, 6], // +Z
+        [0, 2, 3, 1], // -Z
+    ];
+    for face in faces {
+        let base = vertices.len() as u32;
+        for i in 0..4 {
+            let opposite = corner(face[(i + 2) % 4]);
+            vertices.push(OutlineVertex {
+                position: corner(face[i]),
+                other: [opposite[0], opposite[1], opposite[2], 0.0],
+                color: fill_color,
+                uv: [0.0, 0.0],
+                tex_index: 0.0,
+            });
+        }
+        indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    // Edges: every pair of corners differing in exactly one axis bit.
+    let packed_color = Vertex::pack_color_rgba(color);
+    for a in 0..8 {
+        for axis_bit in [1, 2, 4] {
+            if a & axis_bit == 0 {
+                push_outline_segment(
+                    &mut vertices,
+                    &mut indices,
+                    corner(a),
+                    corner(a | axis_bit),
+                    packed_color,
+                    1.0,
+                );
+            }
+        }
+    }
+
+    (vertices, indices)
+}
+
+/// Builds the crack overlay for a block that is being broken.
+///
+/// Every face of the block at `(x, y, z)` gets a quad textured with the
+/// crack layer of `stage` (see [`TEX_DIG_CRACKS`]), from nothing at `0` to
+/// the full pattern at [`DIG_STAGES`].  The quads are [`Vertex`]es for the
+/// translucent terrain pipeline, so the cracks are lit like the block and
+/// blended over it; faces covered by neighbouring blocks are rejected by the
+/// depth test.
+pub fn build_block_cracks(x: i32, y: i32, z: i32, stage: u8) -> (Vec<Vertex>, Vec<u32>) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    if stage == 0 {
+        return (vertices, indices);
+    }
+    let tex_index = TEX_DIG_CRACKS + (stage.min(DIG_STAGES) - 1) as f32;
+    // Further out than the face highlight so the three never z-fight.
+    let pad = 0.007;
+    let min = Vec3::new(x as f32, y as f32, z as f32) - pad;
+    let max = min + (1.0 + 2.0 * pad);
+    for face_dir in 0..6 {
+        add_block_face(
+            &mut vertices,
+            &mut indices,
+            face_dir,
+            [min.to_array(), max.to_array()],
+            [1.0, 1.0, 1.0],
+            tex_index,
+        );
+    }
+    (vertices, indices)
+}
+
+/// Number of straight segments the celestial path circle is split into.
+const CELESTIAL_PATH_SEGMENTS: usize = 96;
+
+/// Builds the day-cycle debug overlay: the circle the sun travels along
+/// (the moon follows the same circle, opposite the sun) with a cross at
+/// noon, sunset, midnight and sunrise.
+///
+/// # Arguments
+/// * `center` - Point the circle is centred on, normally the camera eye.
+/// * `radius` - Distance of the circle from `center`.
+/// * `direction_at` - Unit direction of the sun at a fraction `0.0..1.0` of
+///   the day, counted from noon.
+/// * `color` - RGBA color of the circle.
+/// * `marker_color` - RGBA color of the four markers.
+pub fn build_celestial_path(
+    center: [f32; 3],
+    radius: f32,
+    direction_at: impl Fn(f32) -> [f32; 3],
+    color: [f32; 4],
+    marker_color: [f32; 4],
+) -> (Vec<OutlineVertex>, Vec<u32>) {
+    let center = Vec3::from(center);
+    let point = |fraction: f32| (center + Vec3::from(direction_at(fraction)) * radius).to_array();
+
+    let mut vertices = Vec::with_capacity((CELESTIAL_PATH_SEGMENTS + 8) * 4);
+    let mut indices = Vec::with_capacity((CELESTIAL_PATH_SEGMENTS + 8) * 6);
+
+    let packed_color = Vertex::pack_color_rgba(color);
+    for i in 0..CELESTIAL_PATH_SEGMENTS {
+        push_outline_segment(
+            &mut vertices,
+            &mut indices,
+            point(i as f32 / CELESTIAL_PATH_SEGMENTS as f32),
+            point((i + 1) as f32 / CELESTIAL_PATH_SEGMENTS as f32),
+            packed_color,
+            1.0,
+        );
+    }
+
+    // Each marker is a cross of two arms, one along the path and one across
+    // it, so it reads the same from any viewing angle.
+    let packed_marker = Vertex::pack_color_rgba(marker_color);
+    let arm = radius * 0.04;
+    for fraction in [0.0, 0.25, 0.5, 0.75] {
+        let at = Vec3::from(direction_at(fraction));
+        let along = (Vec3::from(direction_at(fraction + 0.01)) - at).normalize_or_zero();
+        let across = at.cross(along);
+        let spot = center + at * radius;
+        for axis in [along, across] {
+            push_outline_segment(
+                &mut vertices,
+                &mut indices,
+                (spot - axis * arm).to_array(),
+                (spot + axis * arm).to_array(),
+                packed_marker,
+                1.5,
+            );
+        }
+    }
+
+    (vertices, indices)
+}
+
+/// Appends an axis-aligned box, rotated around the Y-axis through `origin`
+/// by `yaw`, to `vertices` and `indices`.
+///
+/// The box is defined by its center offset from `origin` and its
+/// half-extents. All six faces are emitted with outward normals and a flat
+/// `color`.  Shared by the player and mob models.
+fn add_yawed_box(
+    vertices: &mut Vec<Vertex>,
+    indices: &mut Vec<u32>,
+    origin: [f32; 3],
+    yaw: f32,
+    center: [f32; 3],
+    half_extents: [f32; 3],
+    color: [f32; 3],
+) {
+    let [x, y, z] = origin;
+    let [cx, cy, cz] = center;
+    let [hw, hh, hd] = half_extents;
+
+    // Eight corners of the un-rotated box.
+    let corners = [
+        (-hw, -hh, -hd),
+        (hw, -hh, -hd),
+        (hw, hh, -hd),
+        (-hw, hh, -hd),
+        (-hw, -hh, hd),
+        (hw, -hh, hd),
+        (hw, hh, hd),
+        (-hw, hh, hd),
+    ];
+
+    // Apply yaw rotation and translate to world space.
+    let (sin_yaw, cos_yaw) = yaw.sin_cos();
+    let transformed = corners.map(|(dx, dy, dz)| {
+        let (ox, oz) = (cx + dx, cz + dz);
+        let (rx, rz) = (ox * cos_yaw - oz * sin_yaw, ox * sin_yaw + oz * cos_yaw);
+        [x + rx, y + cy + dy, z + rz]
+    });
+
+    // Each face is a list of corner indices and its outward normal.
+    let faces = [
+        ([4, 5, 6, 7], [0.0_f32, 0.0, 1.0]), // Front  (+Z)
+        ([1, 0, 3, 2], [0.0, 0.0, -1.0]),    // Back   (-Z)
+        ([5, 1, 2, 6], [1.0, 0.0, 0.0]),     // Right  (+X)
+        ([0, 4, 7, 3], [-1.0, 0.0, 0.0]),    // Left   (-X)
+        ([7, 6, 2, 3], [0.0, 1.0, 0.0]),     // Top    (+Y)
+        ([0, 1, 5, 4], [0.0, -1.0, 0.0]),    // Bottom (-Y)
+    ];
+
+    for (face_indices, normal) in faces {
+        let n_idx = Vertex::pack_normal(normal);
+        let base_idx = vertices.len() as u32;
+        for (i, &idx) in face_indices.iter().enumerate() {
+            vertices.push(Vertex {
+                position: transformed[idx],
+                packed: Vertex::pack(n_idx, color, 255, i as u8, 1, 1),
+            });
+        }
+        indices.extend_from_slice(&[
+            base_idx,
+            base_idx + 1,
+            base_idx + 2,
+            base_idx,
+            base_idx + 2,
+            base_idx + 3,
+        ]);
+    }
+}
+
 /// Builds a simple block-based player model at the given world position and yaw.
 ///
+/// Convenience wrapper around [`append_player_model`] for a single model.
+///
+/// # Returns
+/// A tuple of `(vertices, indices)` ready to be uploaded to the GPU.
+pub fn build_player_model(
+    x: f32,
+    y: f32,
+    z: f32,
+    yaw: f32,
+    shirt_color: [f32; 3],
+) -> (Vec<Vertex>, Vec<u32>) {
+    let mut vertices = Vec::with_capacity(PLAYER_MODEL_VERTEX_COUNT);
+    let mut indices = Vec::with_capacity(PLAYER_MODEL_INDEX_COUNT);
+    append_player_model(&mut vertices, &mut indices, x, y, z, yaw, shirt_color);
+    (vertices, indices)
+}
+
+/// Number of vertices [`append_player_model`] emits per player.
+const PLAYER_MODEL_VERTEX_COUNT: usize = 8 * 6 * 4;
+
+/// Number of indices [`append_player_model`] emits per player.
+const PLAYER_MODEL_INDEX_COUNT: usize = 8 * 6 * 6;
+
+/// Appends a simple block-based player model at the given world position and
+/// yaw to `vertices` and `indices`.
+///
 /// The model consists of eight axis-aligned boxes (head, torso, two arms, two
 /// upper legs, and two lower legs/shoes) that are rotated around the Y-axis by
-/// `yaw` before being placed in world space.
+/// `yaw` before being placed in world space.  Indices are offset by the
+/// current length of `vertices`, so several models can share one buffer pair.
+/// The index pattern depends only on how many models precede this one, never
+/// on their pose.
 ///
 /// All geometry uses `tex_index = -1.0` to signal that no texture should be
 /// sampled; shading relies purely on vertex colors.
 ///
 /// # Arguments
+/// * `vertices`, `indices` - Buffers the model is appended to.
 /// * `x`, `y`, `z` - World-space origin at the player's feet.
 /// * `yaw` - Rotation around the Y-axis in radians (0 = facing +Z).
-///
-/// # Returns
-/// A tuple of `(vertices, indices)` ready to be uploaded to the GPU.
-pub fn build_player_model(x: f32, y: f32, z: f32, yaw: f32) -> (Vec<Vertex>, Vec<u32>) {
-    let mut vertices = Vec::with_capacity(2000);
-    let mut indices = Vec::with_capacity(1000);
-
-    let cos_yaw = yaw.cos();
-    let sin_yaw = yaw.sin();
-
-    // Rotates a 2-D offset `(dx, dz)` around the Y-axis by the outer `yaw`.
-    let rotate = |dx: f32, dz: f32| -> (f32, f32) {
-        (dx * cos_yaw - dz * sin_yaw, dx * sin_yaw + dz * cos_yaw)
-    };
-
-    // Appends a yaw-rotated, axis-aligned box to `vertices` and `indices`.
-    //
-    // The box is defined by its center offset from the player origin
-    // `(cx, cy, cz)` and half-extents `(hw, hh, hd)`. All six faces are
-    // emitted with correct outward normals and a flat `color`.
+/// * `shirt_color` - Color of the torso and sleeves, which tells players
+///   apart; [`crate::constants::PLAYER_SHIRT_COLOR`] is the default blue.
+pub fn append_player_model(
+    vertices: &mut Vec<Vertex>,
+    indices: &mut Vec<u32>,
+    x: f32,
+    y: f32,
+    z: f32,
+    yaw: f32,
+    shirt_color: [f32; 3],
+) {
+    // Appends a yaw-rotated box with center offset `(cx, cy, cz)` from the
+    // player origin and half-extents `(hw, hh, hd)`.
     let add_box = |vertices: &mut Vec<Vertex>,
                    indices: &mut Vec<u32>,
                    cx: f32,
@@ -353,79 +1243,30 @@ pub fn build_player_model(x: f32, y: f32, z: f32, yaw: f32) -> (Vec<Vertex>, Vec
                    hh: f32,
                    hd: f32,
                    color: [f32; 3]| {
-        // Eight corners of the un-rotated box.
-        let corners = [
-            (-hw, -hh, -hd),
-            (hw, -hh, -hd),
-            (hw, hh, -hd),
-            (-hw, hh, -hd),
-            (-hw, -hh, hd),
-            (hw, -hh, hd),
-            (hw, hh, hd),
-            (-hw, hh, hd),
-        ];
-
-        // Apply yaw rotation and translate to world space.
-        let transformed: Vec<[f32; 3]> = corners
-            .iter()
-            .map(|&(dx, dy, dz)| {
-                let (rx, rz) = rotate(cx + dx, cz + dz);
-                [x + rx, y + cy + dy, z + rz]
-            })
-            .collect();
-
-        // Each face is a list of corner indices and its outward normal.
-        let faces = [
-            ([4, 5, 6, 7], [0.0_f32, 0.0, 1.0]), // Front  (+Z)
-            ([1, 0, 3, 2], [0.0, 0.0, -1.0]),    // Back   (-Z)
-            ([5, 1, 2, 6], [1.0, 0.0, 0.0]),     // Right  (+X)
-            ([0, 4, 7, 3], [-1.0, 0.0, 0.0]),    // Left   (-X)
-            ([7, 6, 2, 3], [0.0, 1.0, 0.0]),     // Top    (+Y)
-            ([0, 1, 5, 4], [0.0, -1.0, 0.0]),    // Bottom (-Y)
-        ];
-
-        for (face_indices, normal) in faces {
-            let n_idx = Vertex::pack_normal(normal);
-            let base_idx = vertices.len() as u32;
-            for (i, &idx) in face_indices.iter().enumerate() {
-                vertices.push(Vertex {
-                    position: transformed[idx],
-                    packed: Vertex::pack(n_idx, color, 255, i as u8, 1, 1),
-                });
-            }
-            indices.extend_from_slice(&[
-                base_idx,
-                base_idx + 1,
-                base_idx + 2,
-                base_idx,
-                base_idx + 2,
-                base_idx + 3,
-            ]);
-        }
+        add_yawed_box(
+            vertices,
+            indices,
+            [x, y, z],
+            yaw,
+            [cx, cy, cz],
+            [hw, hh, hd],
+            color,
+        );
     };
 
     let skin_color = [0.9, 0.75, 0.6]; // Light skin tone.
-    let shirt_color = [0.2, 0.5, 0.9]; // Blue shirt / sleeves.
     let pants_color = [0.3, 0.25, 0.2]; // Brown trousers.
     let shoes_color = [0.15, 0.15, 0.15]; // Dark shoes.
 
     // Head – centered 1.75 units above the feet.
     add_box(
-        &mut vertices,
-        &mut indices,
-        0.0,
-        1.75,
-        0.0,
-        0.25,
-        0.25,
-        0.25,
-        skin_color,
+        vertices, indices, 0.0, 1.75, 0.0, 0.25, 0.25, 0.25, skin_color,
     );
 
     // Torso.
     add_box(
-        &mut vertices,
-        &mut indices,
+        vertices,
+        indices,
         0.0,
         1.125,
         0.0,
@@ -437,8 +1278,8 @@ pub fn build_player_model(x: f32, y: f32, z: f32, yaw: f32) -> (Vec<Vertex>, Vec
 
     // Right arm.
     add_box(
-        &mut vertices,
-        &mut indices,
+        vertices,
+        indices,
         -0.375,
         1.125,
         0.0,
@@ -450,8 +1291,8 @@ pub fn build_player_model(x: f32, y: f32, z: f32, yaw: f32) -> (Vec<Vertex>, Vec
 
     // Left arm.
     add_box(
-        &mut vertices,
-        &mut indices,
+        vertices,
+        indices,
         0.375,
         1.125,
         0.0,
@@ -463,8 +1304,8 @@ pub fn build_player_model(x: f32, y: f32, z: f32, yaw: f32) -> (Vec<Vertex>, Vec
 
     // Right upper leg (trousers).
     add_box(
-        &mut vertices,
-        &mut indices,
+        vertices,
+        indices,
         -0.125,
         0.5,
         0.0,
@@ -476,8 +1317,8 @@ pub fn build_player_model(x: f32, y: f32, z: f32, yaw: f32) -> (Vec<Vertex>, Vec
 
     // Left upper leg (trousers).
     add_box(
-        &mut vertices,
-        &mut indices,
+        vertices,
+        indices,
         0.125,
         0.5,
         0.0,
@@ -489,8 +1330,8 @@ pub fn build_player_model(x: f32, y: f32, z: f32, yaw: f32) -> (Vec<Vertex>, Vec
 
     // Right lower leg (shoe).
     add_box(
-        &mut vertices,
-        &mut indices,
+        vertices,
+        indices,
         -0.125,
         0.125,
         0.0,
@@ -502,8 +1343,8 @@ pub fn build_player_model(x: f32, y: f32, z: f32, yaw: f32) -> (Vec<Vertex>, Vec
 
     // Left lower leg (shoe).
     add_box(
-        &mut vertices,
-        &mut indices,
+        vertices,
+        indices,
         0.125,
         0.125,
         0.0,
@@ -512,6 +1353,87 @@ pub fn build_player_model(x: f32, y: f32, z: f32, yaw: f32) -> (Vec<Vertex>, Vec
         0.125,
         shoes_color,
     );
+}
 
-    (vertices, indices)
+/// Appends a block-based model of `mob` at its position and facing to
+/// `vertices` and `indices`.
+///
+/// The model is the player's silhouette with six boxes (head, torso, two arms
+/// and two legs), colored from the mob's [`Mob`](crate::core::mobs::mob::Mob)
+/// definition and scaled by its variant, so babies are half size.  Like
+/// [`append_player_model`], geometry is untextured and indices are offset by
+/// the current length of `vertices`.
+pub fn append_mob_model(vertices: &mut Vec<Vertex>, indices: &mut Vec<u32>, mob: &MobEntity) {
+    let s = mob.kind.variant.scale();
+    let origin = mob.position.to_array();
+    let skin = mob.kind.skin_color;
+    let body = mob.kind.body_color;
+    let legs = body.map(|c| c * 0.6);
+
+    // (center, half-extents, color) of each box at normal size.
+    let boxes = [
+        ([0.0, 1.75, 0.0], [0.25, 0.25, 0.25], skin),    // Head
+        ([0.0, 1.125, 0.0], [0.25, 0.375, 0.125], body), // Torso
+        ([-0.375, 1.125, 0.0], [0.125, 0.375, 0.125], skin), // Right arm
+        ([0.375, 1.125, 0.0], [0.125, 0.375, 0.125], skin), // Left arm
+        ([-0.125, 0.375, 0.0], [0.125, 0.375, 0.125], legs), // Right leg
+        ([0.125, 0.375, 0.0], [0.125, 0.375, 0.125], legs), // Left leg
+    ];
+    for (center, half_extents, color) in boxes {
+        add_yawed_box(
+            vertices,
+            indices,
+            origin,
+            mob.yaw,
+            center.map(|c| c * s),
+            half_extents.map(|h| h * s),
+            color,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crosshair_arms_are_equally_long_in_pixels() {
+        // 3440×1440 ultrawide: NDC spans width / 2 pixels per unit in x and
+        // height / 2 in y.
+        let (width, height) = (3440.0, 1440.0);
+        let (vertices, _) = build_crosshair(width / height);
+        let extent = |axis: usize| {
+            vertices
+                .iter()
+                .map(|v| v.position[axis].abs())
+                .fold(0.0, f32::max)
+        };
+        let arm_x = extent(0) * width / 2.0;
+        let arm_y = extent(1) * height / 2.0;
+        assert!((arm_x - arm_y).abs() < 0.01);
+    }
+
+    #[test]
+    fn torch_is_a_post_inside_its_collision_box() {
+        let (mut vertices, mut indices) = (Vec::new(), Vec::new());
+        add_partial_block(
+            &mut vertices,
+            &mut indices,
+            BlockType::Torch,
+            [0.0; 3],
+            Facing::default(),
+            [false; 6],
+        );
+
+        // Standing on a hidden face, only the bottom is dropped.
+        assert_eq!(vertices.len(), 5 * 4);
+        let [(min, max)] = BlockType::Torch.collision_boxes(Facing::default()) else {
+            panic!("a torch is a single box");
+        };
+        for vertex in &vertices {
+            for axis in 0..3 {
+                assert!((min[axis]..=max[axis]).contains(&vertex.position[axis]));
+            }
+        }
+    }
 }
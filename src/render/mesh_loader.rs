@@ -2,9 +2,9 @@ use crate::core::vertex::Vertex;
 use crate::logger::{LogLevel, log};
 use crate::world::World;
 use crossbeam_channel::{Receiver, Sender, bounded};
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 use std::sync::Arc;
-use std::thread;
+use std::thread::{self, JoinHandle};
 
 /// A request to build the terrain and water meshes for one subchunk.
 pub struct MeshRequest {
@@ -24,10 +24,16 @@ pub struct MeshResult {
     pub cz: i32,
     /// Vertical index of the subchunk within its chunk column.
     pub sy: i32,
-    /// Terrain mesh as `(vertices, indices)`.
+    /// Opaque terrain mesh as `(vertices, indices)`.
     pub terrain: (Vec<Vertex>, Vec<u32>),
+    /// Mesh of see-through blocks (leaves, glass, ice, ...) as
+    /// `(vertices, indices)`, drawn after the opaque terrain.
+    pub translucent: (Vec<Vertex>, Vec<u32>),
     /// Water mesh as `(vertices, indices)`.
     pub water: (Vec<Vertex>, Vec<u32>),
+    /// The subchunk's [`mesh_version`](crate::core::chunk::SubChunk::mesh_version)
+    /// when the mesh was built, read under the same lock as the blocks.
+    pub mesh_version: u32,
 }
 
 /// Asynchronous mesh-building system backed by a fixed pool of worker threads.
@@ -49,6 +55,8 @@ pub struct MeshLoader {
     /// Set of subchunk keys `(cx, cz, sy)` that have been queued but not yet
     /// collected, used to deduplicate in-flight requests.
     pending: HashSet<(i32, i32, i32)>,
+    /// Handles of the worker threads, joined by [`MeshLoader::shutdown`].
+    workers: Vec<JoinHandle<()>>,
 }
 
 impl MeshLoader {
@@ -65,21 +73,25 @@ impl MeshLoader {
         let (request_tx, request_rx) = bounded::<MeshRequest>(256);
         let (result_tx, result_rx) = bounded::<MeshResult>(256);
 
+        let mut workers = Vec::with_capacity(worker_count);
         for i in 0..worker_count {
             let rx = request_rx.clone();
             let tx = result_tx.clone();
             let world = Arc::clone(&world);
 
-            thread::Builder::new()
+            let worker = thread::Builder::new()
                 .name(format!("mesh-worker-{}", i))
                 .spawn(move || {
                     // Block until a request arrives; exit when the sender is dropped.
                     while let Ok(req) = rx.recv() {
-                        let meshes = {
+                        let (meshes, mesh_version) = {
                             // Hold the read lock only for the duration of mesh
                             // building, then release it before sending the result.
                             let world_read = world.read();
-                            world_read.build_subchunk_mesh(req.cx, req.cz, req.sy)
+                            (
+                                world_read.build_subchunk_mesh_at_lod(req.cx, req.cz, req.sy),
+                                world_read.subchunk_mesh_version(req.cx, req.cz, req.sy),
+                            )
                         };
 
                         if tx
@@ -88,7 +100,9 @@ impl MeshLoader {
                                 cz: req.cz,
                                 sy: req.sy,
                                 terrain: meshes.0,
-                                water: meshes.1,
+                                translucent: meshes.1,
+                                water: meshes.2,
+                                mesh_version,
                             })
                             .is_err()
                         {
@@ -99,15 +113,34 @@ impl MeshLoader {
                     }
                 })
                 .expect("Failed to spawn mesh worker");
+            workers.push(worker);
         }
 
         Self {
             request_tx,
             result_rx,
             pending: HashSet::new(),
+            workers,
         }
     }
 
+    /// Stops the worker threads and waits for them to exit.
+    ///
+    /// Works like [`ChunkLoader::shutdown`](crate::ChunkLoader::shutdown):
+    /// each worker builds at most one more mesh before exiting.  The caller
+    /// must not hold the world lock, since a worker may be waiting for a
+    /// read lock to finish its mesh.
+    pub fn shutdown(&mut self) {
+        let (request_tx, _) = bounded(0);
+        let (_, result_rx) = bounded(0);
+        self.request_tx = request_tx;
+        self.result_rx = result_rx;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+        self.pending.clear();
+    }
+
     /// Enqueues a mesh-build request for the subchunk at `(cx, cz, sy)`.
     ///
     /// Does nothing if the subchunk is already in the pending set, preventing
@@ -155,4 +188,132 @@ impl MeshLoader {
     pub fn is_pending(&self, cx: i32, cz: i32, sy: i32) -> bool {
         self.pending.contains(&(cx, cz, sy))
     }
+
+    /// Returns the number of subchunk meshes currently in flight (queued but
+    /// not yet collected).
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+impl MeshResult {
+    /// Bytes this result writes to the GPU buffers when uploaded.
+    pub fn upload_bytes(&self) -> usize {
+        let vertices = self.terrain.0.len() + self.translucent.0.len() + self.water.0.len();
+        let indices = self.terrain.1.len() + self.translucent.1.len() + self.water.1.len();
+        vertices * std::mem::size_of::<Vertex>() + indices * std::mem::size_of::<u32>()
+    }
+}
+
+/// Finished meshes waiting for their GPU upload.
+///
+/// Collected [`MeshResult`]s are parked here so the uploads of a large
+/// remesh burst can be spread over several frames under a byte budget.
+/// [`pop_nearest`](Self::pop_nearest) hands out the subchunk closest to the
+/// player first.  The queue holds at most `capacity` results; callers stop
+/// polling the [`MeshLoader`] while it [`is_full`](Self::is_full), which
+/// leaves the rest in the loader's result channel.
+pub struct MeshUploadQueue {
+    results: VecDeque<MeshResult>,
+    capacity: usize,
+}
+
+impl MeshUploadQueue {
+    /// Creates an empty queue holding up to `capacity` results.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            results: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Queues `result`.  A result still waiting for the same subchunk is
+    /// replaced, since the new mesh was built from newer blocks.
+    pub fn push(&mut self, result: MeshResult) {
+        let key = (result.cx, result.cz, result.sy);
+        match self
+            .results
+            .iter_mut()
+            .find(|queued| (queued.cx, queued.cz, queued.sy) == key)
+        {
+            Some(queued) => *queued = result,
+            None => self.results.push_back(result),
+        }
+    }
+
+    /// Removes and returns the queued result whose chunk column is closest
+    /// to `(player_cx, player_cz)`, oldest first among equals.
+    pub fn pop_nearest(&mut self, player_cx: i32, player_cz: i32) -> Option<MeshResult> {
+        let nearest = self
+            .results
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, result)| {
+                let dx = result.cx - player_cx;
+                let dz = result.cz - player_cz;
+                dx * dx + dz * dz
+            })
+            .map(|(i, _)| i)?;
+        self.results.remove(nearest)
+    }
+
+    /// Returns `true` if a mesh for `(cx, cz, sy)` is waiting for upload.
+    pub fn contains(&self, cx: i32, cz: i32, sy: i32) -> bool {
+        self.results
+            .iter()
+            .any(|result| (result.cx, result.cz, result.sy) == (cx, cz, sy))
+    }
+
+    /// Number of results waiting for upload.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns `true` if nothing is waiting for upload.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Returns `true` once the queue holds `capacity` results.
+    pub fn is_full(&self) -> bool {
+        self.results.len() >= self.capacity
+    }
+
+    /// Drops every queued result, e.g. when the world is replaced.
+    pub fn clear(&mut self) {
+        self.results.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(cx: i32, cz: i32, sy: i32, mesh_version: u32) -> MeshResult {
+        MeshResult {
+            cx,
+            cz,
+            sy,
+            terrain: (Vec::new(), Vec::new()),
+            translucent: (Vec::new(), Vec::new()),
+            water: (Vec::new(), Vec::new()),
+            mesh_version,
+        }
+    }
+
+    #[test]
+    fn uploads_nearest_first_and_keeps_only_the_newest_mesh() {
+        let mut queue = MeshUploadQueue::new(4);
+        queue.push(result(5, 0, 0, 1));
+        queue.push(result(1, 1, 0, 1));
+        queue.push(result(5, 0, 0, 2));
+        assert_eq!(queue.len(), 2);
+        assert!(queue.contains(5, 0, 0));
+
+        let first = queue.pop_nearest(0, 0).unwrap();
+        assert_eq!((first.cx, first.cz), (1, 1));
+        let second = queue.pop_nearest(0, 0).unwrap();
+        assert_eq!((second.cx, second.mesh_version), (5, 2));
+        assert!(queue.pop_nearest(0, 0).is_none());
+    }
 }
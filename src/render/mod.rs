@@ -2,6 +2,7 @@ pub mod frustum;
 pub mod indirect;
 pub mod mesh;
 pub mod mesh_loader;
+pub mod particles;
 pub mod texture;
 
 pub mod atlas_map;
@@ -9,7 +10,11 @@ pub mod atlas_map;
 pub use frustum::{AABB, extract_frustum_planes};
 pub use indirect::{DrawIndexedIndirect, IndirectManager, SubchunkKey};
 pub use mesh::{
-    add_greedy_quad, add_quad, build_block_outline, build_crosshair, build_player_model,
+    add_greedy_quad, add_partial_block, add_quad, add_water_surface_quad, append_mob_model,
+    append_player_model, build_block_cracks, build_block_outline, build_celestial_path,
+    build_chunk_borders, build_crosshair, build_face_highlight, build_placement_preview,
+    build_player_model,
 };
-pub use mesh_loader::MeshLoader;
+pub use mesh_loader::{MeshLoader, MeshUploadQueue};
+pub use particles::{MAX_PARTICLES, Particle, ParticleLook, ParticleSystem};
 pub use texture::{generate_texture_atlas, load_texture_atlas_from_file};
@@ -0,0 +1,263 @@
+use glam::Vec3;
+
+use crate::core::block::{BlockType, Facing};
+use crate::core::vertex::{OutlineVertex, Vertex};
+use crate::render::mesh::add_block_face;
+use crate::world::terrain::face_color;
+
+/// Most particles alive at once.  Bursts spawned while the cap is reached
+/// are cut short, so a flurry of effects costs no more than one screen full
+/// of particles.
+pub const MAX_PARTICLES: usize = 256;
+
+/// Downward acceleration of particles, in blocks/s².
+const PARTICLE_GRAVITY: f32 = 16.0;
+
+/// Rate, per second, at which a landed particle's sliding speed dies down.
+const GROUND_DRAG: f32 = 6.0;
+
+/// Fraction of its lifetime over which a block particle shrinks away at
+/// the end; cubes are opaque, so they cannot fade out like droplets.
+const SHRINK_FRACTION: f32 = 0.25;
+
+/// How a particle is drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParticleLook {
+    /// A square of one RGBA color facing the camera, fading out with age;
+    /// see [`ParticleSystem::build_quads`].
+    Flat([f32; 4]),
+    /// A small cube with the textures and colors of a block, shrinking
+    /// away at the end of its life; see [`ParticleSystem::append_cubes`].
+    Block(BlockType),
+}
+
+/// A short-lived speck thrown around by an effect.
+#[derive(Debug, Clone, Copy)]
+pub struct Particle {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    /// Seconds since the particle was spawned.
+    age: f32,
+    /// Seconds the particle lives.
+    pub lifetime: f32,
+    /// Half the side of the square or cube, in blocks.
+    pub half_size: f32,
+    pub look: ParticleLook,
+}
+
+impl Particle {
+    /// Creates a particle at `position` that lives for `lifetime` seconds.
+    pub fn new(
+        position: Vec3,
+        velocity: Vec3,
+        lifetime: f32,
+        half_size: f32,
+        look: ParticleLook,
+    ) -> Self {
+        Self {
+            position,
+            velocity,
+            age: 0.0,
+            lifetime,
+            half_size,
+            look,
+        }
+    }
+
+    /// Fraction of the lifetime left, from `1.0` at spawn down to `0.0`.
+    fn life_left(&self) -> f32 {
+        (1.0 - self.age / self.lifetime).clamp(0.0, 1.0)
+    }
+}
+
+/// The live particles of every effect (water splashes, breaking blocks),
+/// simulated on the CPU with gravity and rebuilt into geometry each frame.
+///
+/// Flat particles go through the outline pipeline, so they are
+/// alpha-blended and depth-tested against the scene; block particles are
+/// terrain geometry and are drawn with the terrain pipeline.
+#[derive(Debug, Default)]
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+}
+
+impl ParticleSystem {
+    /// Returns `true` when no particle is alive.
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Number of live particles.
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Number of live [`ParticleLook::Block`] particles, each of which
+    /// [`Self::append_cubes`] turns into one cube.
+    pub fn block_count(&self) -> usize {
+        self.particles
+            .iter()
+            .filter(|p| matches!(p.look, ParticleLook::Block(_)))
+            .count()
+    }
+
+    /// Adds `particle` unless [`MAX_PARTICLES`] are already alive.
+    ///
+    /// # Returns
+    /// `false` if the particle was dropped because of the cap.
+    pub fn spawn(&mut self, particle: Particle) -> bool {
+        if self.particles.len() >= MAX_PARTICLES {
+            return false;
+        }
+        self.particles.push(particle);
+        true
+    }
+
+    /// Moves the particles on by `dt` seconds and drops the expired ones.
+    ///
+    /// A particle whose bottom would move into a point that `is_solid`
+    /// lands instead: it stops falling and slides on, slowed by
+    /// [`GROUND_DRAG`].
+    pub fn update(&mut self, dt: f32, is_solid: impl Fn(Vec3) -> bool) {
+        for particle in &mut self.particles {
+            particle.velocity.y -= PARTICLE_GRAVITY * dt;
+            let bottom = Vec3::new(0.0, particle.half_size, 0.0);
+            let next = particle.position + particle.velocity * dt;
+            if is_solid(next - bottom) {
+                let drag = (-GROUND_DRAG * dt).exp();
+                particle.velocity =
+                    Vec3::new(particle.velocity.x * drag, 0.0, particle.velocity.z * drag);
+                let slid = particle.position + particle.velocity * dt;
+                if !is_solid(slid - bottom) {
+                    particle.position = slid;
+                }
+            } else {
+                particle.position = next;
+            }
+            particle.age += dt;
+        }
+        self.particles.retain(|p| p.age < p.lifetime);
+    }
+
+    /// Builds one quad per [`ParticleLook::Flat`] particle, facing a camera
+    /// that looks along `look`, for the outline pipeline.  Particles fade
+    /// out as they age.
+    pub fn build_quads(&self, look: Vec3) -> (Vec<OutlineVertex>, Vec<u32>) {
+        let right = look.cross(Vec3::Y).try_normalize().unwrap_or(Vec3::X);
+        let up = right.cross(look).normalize();
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for particle in &self.particles {
+            let ParticleLook::Flat([r, g, b, a]) = particle.look else {
+                continue;
+            };
+            let color = Vertex::pack_color_rgba([r, g, b, a * particle.life_left()]);
+            let (dx, dy) = (right * particle.half_size, up * particle.half_size);
+            let corners = [
+                particle.position - dx - dy,
+                particle.position + dx - dy,
+                particle.position + dx + dy,
+                particle.position - dx + dy,
+            ];
+            let base = vertices.len() as u32;
+            for i in 0..4 {
+                let opposite = corners[(i + 2) % 4];
+                vertices.push(OutlineVertex {
+                    position: corners[i].to_array(),
+                    other: [opposite.x, opposite.y, opposite.z, 0.0],
+                    color,
+                    uv: [0.0, 0.0],
+                    tex_index: 0.0,
+                });
+            }
+            indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+        }
+        (vertices, indices)
+    }
+
+    /// Appends an axis-aligned cube for every [`ParticleLook::Block`]
+    /// particle to terrain geometry, each face textured and colored like
+    /// the block's.  Cubes shrink away over the last [`SHRINK_FRACTION`] of
+    /// their life.
+    pub fn append_cubes(&self, vertices: &mut Vec<Vertex>, indices: &mut Vec<u32>) {
+        for particle in &self.particles {
+            let ParticleLook::Block(block) = particle.look else {
+                continue;
+            };
+            let half = particle.half_size * (particle.life_left() / SHRINK_FRACTION).min(1.0);
+            let min = (particle.position - Vec3::splat(half)).to_array();
+            let max = (particle.position + Vec3::splat(half)).to_array();
+            for face in 0..6 {
+                add_block_face(
+                    vertices,
+                    indices,
+                    face,
+                    [min, max],
+                    face_color(block, face, None),
+                    block.texture_for_face(face as usize, Facing::default()),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn droplet() -> Particle {
+        Particle::new(Vec3::ZERO, Vec3::Y, 0.5, 0.05, ParticleLook::Flat([1.0; 4]))
+    }
+
+    #[test]
+    fn particles_are_capped_and_expire() {
+        let mut particles = ParticleSystem::default();
+        particles.spawn(Particle::new(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            0.5,
+            0.1,
+            ParticleLook::Block(BlockType::Grass),
+        ));
+        for _ in 1..MAX_PARTICLES {
+            assert!(particles.spawn(droplet()));
+        }
+        assert!(!particles.spawn(droplet()));
+        assert_eq!(particles.len(), MAX_PARTICLES);
+
+        // Each look is built for its own pipeline.
+        let (quad_vertices, quad_indices) = particles.build_quads(Vec3::X);
+        assert_eq!(quad_vertices.len(), (MAX_PARTICLES - 1) * 4);
+        assert_eq!(quad_indices.len(), (MAX_PARTICLES - 1) * 6);
+        let (mut vertices, mut indices) = (Vec::new(), Vec::new());
+        particles.append_cubes(&mut vertices, &mut indices);
+        assert_eq!((vertices.len(), indices.len()), (24, 36));
+
+        // Every particle is gone once its lifetime has passed.
+        for _ in 0..10 {
+            particles.update(0.1, |_| false);
+        }
+        assert!(particles.is_empty());
+    }
+
+    #[test]
+    fn particles_land_on_solid_ground() {
+        let mut particles = ParticleSystem::default();
+        particles.spawn(Particle::new(
+            Vec3::new(0.5, 1.2, 0.5),
+            Vec3::new(2.0, 0.0, 0.0),
+            10.0,
+            0.05,
+            ParticleLook::Block(BlockType::Stone),
+        ));
+        for _ in 0..60 {
+            particles.update(1.0 / 60.0, |p| p.y < 1.0);
+        }
+        let particle = particles.particles[0];
+        assert!(particle.position.y - particle.half_size >= 1.0);
+        assert_eq!(particle.velocity.y, 0.0);
+        // Drag slows the slide down without stopping it dead.
+        assert!(particle.velocity.x < 2.0 && particle.velocity.x > 0.0);
+        assert!(particle.position.x > 0.6);
+    }
+}
@@ -1,10 +1,12 @@
+use glam::Vec2;
 use image::GenericImageView;
 use std::path::Path;
 
-use crate::constants::{ATLAS_SIZE, TEXTURE_SIZE};
+use crate::constants::{ATLAS_LAYERS, ATLAS_SIZE, DIG_CRACK_COLOR, DIG_STAGES, TEXTURE_SIZE};
 
 /// Loads a 4×4 grid texture atlas from disk and extracts its 16 tiles into a
 /// flat, layer-ordered byte array suitable for upload as a `Texture2DArray`.
+/// The layers past the grid are appended by [`append_generated_layers`].
 ///
 /// The atlas image must be laid out as a 4-column, 4-row grid of equal-sized
 /// square tiles.  Tiles are read in row-major order (left-to-right,
@@ -66,10 +68,105 @@ pub fn load_texture_atlas_from_file<P: AsRef<Path>>(
         }
     }
 
+    append_generated_layers(&mut layers, tile_w);
     Ok((layers, tile_w, tile_h))
 }
 
-/// Procedurally generates a 16-layer RGBA8 texture array at runtime.
+/// Appends the texture layers that have no tile in the 4×4 atlas grid,
+/// generated at `tile_size × tile_size` so they match the grid tiles.
+///
+/// `layers` must hold exactly the `ATLAS_SIZE²` grid layers; afterwards it
+/// holds all [`ATLAS_LAYERS`].
+///
+/// | Index  | Block       | Notes                                               |
+/// |--------|-------------|-----------------------------------------------------|
+/// | 16     | Glass       | Clear pane (alpha 40) in a pale frame, with a glint |
+/// | 17–26  | Dig cracks  | One layer per dig stage, transparent but the cracks |
+pub fn append_generated_layers(layers: &mut Vec<u8>, tile_size: u32) {
+    let grid_layers = ATLAS_SIZE * ATLAS_SIZE;
+    layers.reserve(((ATLAS_LAYERS - grid_layers) * tile_size * tile_size * 4) as usize);
+
+    // The frame is one sixteenth of the tile wide, at least one texel.
+    let frame = (tile_size / 16).max(1);
+    for y in 0..tile_size {
+        for x in 0..tile_size {
+            let edge = x < frame || y < frame || x >= tile_size - frame || y >= tile_size - frame;
+            // A short diagonal glint in the top-left corner of the pane.
+            let glint = x.abs_diff(y) <= frame / 4 && (2 * frame..tile_size / 3).contains(&x);
+            let texel = if edge {
+                [210, 225, 230, 255]
+            } else if glint {
+                [240, 248, 250, 120]
+            } else {
+                [200, 220, 228, 40]
+            };
+            layers.extend_from_slice(&texel);
+        }
+    }
+
+    // Cracks are about three texels wide on the generated atlas, and never
+    // thinner than one and a half texels on a small file atlas.
+    let half_width = (0.75 / tile_size as f32).max(0.006);
+    // For each texel, the first crack segment passing through it, so a
+    // stage shows the texels whose segment is among its first ones.
+    let first_segment: Vec<usize> = (0..tile_size * tile_size)
+        .map(|i| {
+            let p = (Vec2::new((i % tile_size) as f32, (i / tile_size) as f32) + 0.5)
+                / tile_size as f32;
+            CRACK_SEGMENTS
+                .iter()
+                .position(|&(a, b)| segment_distance(p, a.into(), b.into()) < half_width)
+                .unwrap_or(CRACK_SEGMENTS.len())
+        })
+        .collect();
+    let crack = DIG_CRACK_COLOR.map(|c| (c * 255.0) as u8);
+    for stage in 1..=DIG_STAGES as usize {
+        let segment_count = CRACK_SEGMENTS.len() * stage / DIG_STAGES as usize;
+        for &segment in &first_segment {
+            let texel = if segment < segment_count {
+                crack
+            } else {
+                [0; 4]
+            };
+            layers.extend_from_slice(&texel);
+        }
+    }
+}
+
+/// Crack pattern of the dig crack layers, as line segments in tile
+/// coordinates `0.0..1.0`.  Ordered from the center outward so each stage
+/// extends the cracks of the one before.
+const CRACK_SEGMENTS: [([f32; 2], [f32; 2]); 20] = [
+    ([0.5, 0.5], [0.62, 0.38]),
+    ([0.5, 0.5], [0.36, 0.58]),
+    ([0.62, 0.38], [0.7, 0.22]),
+    ([0.36, 0.58], [0.24, 0.7]),
+    ([0.62, 0.38], [0.8, 0.44]),
+    ([0.36, 0.58], [0.3, 0.4]),
+    ([0.7, 0.22], [0.66, 0.06]),
+    ([0.24, 0.7], [0.1, 0.76]),
+    ([0.8, 0.44], [0.94, 0.36]),
+    ([0.3, 0.4], [0.14, 0.34]),
+    ([0.5, 0.5], [0.56, 0.68]),
+    ([0.56, 0.68], [0.72, 0.8]),
+    ([0.72, 0.8], [0.78, 0.95]),
+    ([0.14, 0.34], [0.06, 0.18]),
+    ([0.7, 0.22], [0.84, 0.16]),
+    ([0.24, 0.7], [0.3, 0.88]),
+    ([0.56, 0.68], [0.46, 0.84]),
+    ([0.3, 0.4], [0.38, 0.24]),
+    ([0.8, 0.44], [0.86, 0.6]),
+    ([0.38, 0.24], [0.34, 0.1]),
+];
+
+/// Distance from `p` to the segment from `a` to `b`.
+fn segment_distance(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = ((p - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0);
+    p.distance(a + ab * t)
+}
+
+/// Procedurally generates the full RGBA8 texture array at runtime.
 ///
 /// Each layer is a `TEXTURE_SIZE × TEXTURE_SIZE` tile whose appearance is
 /// driven by a fast integer hash function, avoiding any file I/O.  This is
@@ -98,6 +195,8 @@ pub fn load_texture_atlas_from_file<P: AsRef<Path>>(
 /// | 13    | Ice           | Light blue, semi-transparent (alpha 220)   |
 /// | 14    | Cactus        | Green with darker border                   |
 /// | 15    | Dead bush     | Sparse brown branches, mostly transparent  |
+///
+/// The glass layer after these comes from [`append_generated_layers`].
 pub fn generate_texture_atlas() -> Vec<u8> {
     let total_pixels = (TEXTURE_SIZE * TEXTURE_SIZE * ATLAS_SIZE * ATLAS_SIZE) as usize;
     let mut data = vec![0u8; total_pixels * 4];
@@ -134,7 +233,7 @@ pub fn generate_texture_atlas() -> Vec<u8> {
         ((n ^ (n >> 16)) & 0xFF) as u8
     };
 
-    for tex_idx in 0..16u32 {
+    for tex_idx in 0..ATLAS_SIZE * ATLAS_SIZE {
         for y in 0..TEXTURE_SIZE {
             for x in 0..TEXTURE_SIZE {
                 let (r, g, b, a) = match tex_idx {
@@ -335,5 +434,6 @@ pub fn generate_texture_atlas() -> Vec<u8> {
         }
     }
 
+    append_generated_layers(&mut data, TEXTURE_SIZE);
     data
 }
@@ -1,6 +1,8 @@
 use glam::{Mat4, Vec3, Vec4};
 
-use crate::constants::{CSM_CASCADE_COUNT, CSM_CASCADE_SPLITS};
+use crate::constants::{
+    CSM_CASCADE_COUNT, CSM_CASCADE_SPLITS, CSM_LOW_CASCADE_SPLITS, CSM_SHADOW_MAP_SIZE,
+};
 #[derive(Debug, Clone, Copy)]
 pub struct CascadeData {
     pub view_proj: Mat4,
@@ -18,14 +20,38 @@ impl Default for CascadeData {
 
 pub struct CsmManager {
     pub cascades: [CascadeData; CSM_CASCADE_COUNT],
+    /// Number of cascades fitted by `update`; the remaining slots repeat the
+    /// last active cascade.
+    pub cascade_count: usize,
+    /// Resolution of one cascade layer, used to snap the matrices to texels.
+    pub shadow_map_size: u32,
+}
+
+/// Returns the far distance of each cascade when `count` cascades are used.
+pub fn cascade_splits(count: usize) -> &'static [f32] {
+    match count {
+        0..=2 => &CSM_LOW_CASCADE_SPLITS,
+        n => &CSM_CASCADE_SPLITS[..n.min(CSM_CASCADE_COUNT)],
+    }
 }
 
 impl CsmManager {
     pub fn new() -> Self {
         Self {
             cascades: [CascadeData::default(); CSM_CASCADE_COUNT],
+            cascade_count: CSM_CASCADE_COUNT,
+            shadow_map_size: CSM_SHADOW_MAP_SIZE,
         }
     }
+
+    /// Changes the number of cascades and the shadow map resolution used by
+    /// subsequent `update` calls.  `cascade_count` is clamped to
+    /// `1..=CSM_CASCADE_COUNT`.
+    pub fn configure(&mut self, cascade_count: usize, shadow_map_size: u32) {
+        self.cascade_count = cascade_count.clamp(1, CSM_CASCADE_COUNT);
+        self.shadow_map_size = shadow_map_size;
+    }
+
     pub fn update(
         &mut self,
         camera_view: &Mat4,
@@ -37,14 +63,15 @@ impl CsmManager {
     ) {
         let inv_view = camera_view.inverse();
 
+        let count = self.cascade_count;
         let mut split_distances = [0.0_f32; CSM_CASCADE_COUNT + 1];
         split_distances[0] = near;
 
-        for i in 0..CSM_CASCADE_COUNT {
-            split_distances[i + 1] = CSM_CASCADE_SPLITS[i].min(far);
+        for (i, split) in cascade_splits(count).iter().enumerate() {
+            split_distances[i + 1] = split.min(far);
         }
 
-        for cascade_idx in 0..CSM_CASCADE_COUNT {
+        for cascade_idx in 0..count {
             let cascade_near = split_distances[cascade_idx];
             let cascade_far = split_distances[cascade_idx + 1];
 
@@ -84,11 +111,8 @@ impl CsmManager {
             );
 
             let shadow_matrix = light_proj * light_view;
-            let shadow_matrix = snap_to_texel_grid(
-                shadow_matrix,
-                center,
-                crate::constants::CSM_SHADOW_MAP_SIZE as f32,
-            );
+            let shadow_matrix =
+                snap_to_texel_grid(shadow_matrix, center, self.shadow_map_size as f32);
 
             let opengl_to_wgpu = Mat4::from_cols_array(&[
                 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.5, 0.0, 0.0, 0.0, 0.5, 1.0,
@@ -99,6 +123,13 @@ impl CsmManager {
                 split_distance: cascade_far,
             };
         }
+
+        // Unused slots still end up in the uniform arrays; repeating the last
+        // cascade keeps the split distances monotonic.
+        let last = self.cascades[count - 1];
+        for cascade in &mut self.cascades[count..] {
+            *cascade = last;
+        }
     }
 }
 impl Default for CsmManager {
@@ -1,45 +1,81 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
-use std::path::Path;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
 
-use crate::block::BlockType;
+use crate::block::{BlockType, Facing};
 use crate::constants::*;
+use crate::player::Inventory;
+use crate::world::WorldGenConfig;
+
+/// Bytes every versioned save file starts with.  Files without them are
+/// the unversioned saves of the first releases, read as version 1.
+const SAVE_MAGIC: [u8; 4] = *b"MRSW";
+
+/// Version of the [`SavedWorld`] layout written by [`save_world`].
+///
+/// Bump it whenever a field is added to or removed from the saved
+/// structures, keep a copy of the previous layout, and teach
+/// [`load_world`] to migrate it, so existing worlds keep loading.
+pub const SAVE_VERSION: u32 = 2;
 
 #[derive(Serialize, Deserialize)]
 pub struct SavedChunk {
     pub cx: i32,
     pub cz: i32,
     pub subchunks: HashMap<u8, Vec<BlockType>>, // sy -> block data
+    pub facings: HashMap<u8, Vec<(u16, Facing)>>, // sy -> (local index, facing)
+    pub water_levels: HashMap<u8, Vec<(u16, u8)>>, // sy -> (local index, level)
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct SavedWorld {
+    /// Layout version the world was saved with; [`SAVE_VERSION`] once
+    /// loaded, since older layouts are migrated.
+    pub version: u32,
     pub seed: u32,
+    /// Height the world's water was generated up to; see
+    /// [`World::sea_level`](crate::World::sea_level).
+    pub sea_level: i32,
+    /// Generation features the world was created with; see
+    /// [`World::gen_config`](crate::World::gen_config).
+    pub gen_config: WorldGenConfig,
     pub player_x: f32,
     pub player_y: f32,
     pub player_z: f32,
     pub player_yaw: f32,
     pub player_pitch: f32,
+    pub creative: bool,
+    /// Blocks collected in survival mode.
+    pub inventory: Inventory,
+    /// Day-cycle clock in seconds; see [`crate::sun_direction`].
+    pub time_of_day: f32,
+    /// How many day-cycle seconds pass per real second.
+    pub time_scale: f32,
     pub chunks: Vec<SavedChunk>,
 }
 
 impl SavedWorld {
-    pub fn from_world<S: std::hash::BuildHasher>(
-        chunks: &HashMap<(i32, i32), crate::chunk::Chunk, S>,
-        seed: u32,
+    pub fn from_world(
+        world: &crate::World,
         player_pos: (f32, f32, f32),
         player_rot: (f32, f32),
+        creative: bool,
+        inventory: &Inventory,
+        time_of_day: f32,
+        time_scale: f32,
     ) -> Self {
         let mut saved_chunks = Vec::new();
 
-        for (&(cx, cz), chunk) in chunks.iter() {
+        for (&(cx, cz), chunk) in world.chunks.iter() {
             if !chunk.player_modified {
                 continue;
             }
 
             let mut saved_subchunks = HashMap::new();
+            let mut saved_facings = HashMap::new();
+            let mut saved_water_levels = HashMap::new();
             for (sy, subchunk) in chunk.subchunks.iter().enumerate() {
                 // Check if subchunk is actually modified or just empty
                 if subchunk.is_empty {
@@ -57,38 +93,326 @@ impl SavedWorld {
                     }
                 }
                 saved_subchunks.insert(sy as u8, blocks);
+                if !subchunk.facings.is_empty() {
+                    let facings = subchunk.facings.iter().map(|(&i, &f)| (i, f)).collect();
+                    saved_facings.insert(sy as u8, facings);
+                }
+                if !subchunk.water_levels.is_empty() {
+                    let levels = subchunk
+                        .water_levels
+                        .iter()
+                        .map(|(&i, &l)| (i, l))
+                        .collect();
+                    saved_water_levels.insert(sy as u8, levels);
+                }
             }
 
             saved_chunks.push(SavedChunk {
                 cx,
                 cz,
                 subchunks: saved_subchunks,
+                facings: saved_facings,
+                water_levels: saved_water_levels,
             });
         }
 
         SavedWorld {
-            seed,
+            version: SAVE_VERSION,
+            seed: world.seed,
+            sea_level: world.sea_level(),
+            gen_config: world.gen_config(),
             player_x: player_pos.0,
             player_y: player_pos.1,
             player_z: player_pos.2,
             player_yaw: player_rot.0,
             player_pitch: player_rot.1,
+            creative,
+            inventory: inventory.clone(),
+            time_of_day,
+            time_scale,
             chunks: saved_chunks,
         }
     }
 }
 
+/// Layout of version 1 saves, from before the format was versioned.
+mod v1 {
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    use crate::block::BlockType;
+
+    #[derive(Deserialize)]
+    pub struct SavedChunk {
+        pub cx: i32,
+        pub cz: i32,
+        pub subchunks: HashMap<u8, Vec<BlockType>>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct SavedWorld {
+        pub seed: u32,
+        pub player_x: f32,
+        pub player_y: f32,
+        pub player_z: f32,
+        pub player_yaw: f32,
+        pub player_pitch: f32,
+        pub chunks: Vec<SavedChunk>,
+    }
+}
+
+impl From<v1::SavedWorld> for SavedWorld {
+    /// Upgrades a version 1 save.  The fields it lacks get the values a new
+    /// world starts with: default sea level and generation, survival mode
+    /// with an empty inventory, noon, and no stairs facings or partial
+    /// water levels in the saved chunks.
+    fn from(old: v1::SavedWorld) -> Self {
+        SavedWorld {
+            version: SAVE_VERSION,
+            seed: old.seed,
+            sea_level: SEA_LEVEL,
+            gen_config: WorldGenConfig::default(),
+            player_x: old.player_x,
+            player_y: old.player_y,
+            player_z: old.player_z,
+            player_yaw: old.player_yaw,
+            player_pitch: old.player_pitch,
+            creative: false,
+            inventory: Inventory::new(),
+            time_of_day: 0.0,
+            time_scale: 1.0,
+            chunks: old
+                .chunks
+                .into_iter()
+                .map(|chunk| SavedChunk {
+                    cx: chunk.cx,
+                    cz: chunk.cz,
+                    subchunks: chunk.subchunks,
+                    facings: HashMap::new(),
+                    water_levels: HashMap::new(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Writes `world` in the current save layout, behind [`SAVE_MAGIC`].
+fn write_world<W: Write>(mut writer: W, world: &SavedWorld) -> Result<(), String> {
+    writer
+        .write_all(&SAVE_MAGIC)
+        .map_err(|e| format!("Write error: {}", e))?;
+    bincode::serialize_into(writer, world).map_err(|e| format!("Serialization error: {}", e))
+}
+
+/// Reads a world saved in any known layout, migrating older ones to the
+/// current [`SavedWorld`].
+fn read_world<R: Read>(mut reader: R) -> Result<SavedWorld, String> {
+    let mut magic = [0u8; 4];
+    reader
+        .read_exact(&mut magic)
+        .map_err(|e| format!("Read error: {}", e))?;
+    if magic != SAVE_MAGIC {
+        // Unversioned save: the magic bytes were the start of the world.
+        let old: v1::SavedWorld = bincode::deserialize_from(magic.chain(reader))
+            .map_err(|e| format!("Deserialization error: {}", e))?;
+        return Ok(old.into());
+    }
+
+    // The version is the first field of every versioned layout.
+    let mut version = [0u8; 4];
+    reader
+        .read_exact(&mut version)
+        .map_err(|e| format!("Read error: {}", e))?;
+    match u32::from_le_bytes(version) {
+        SAVE_VERSION => bincode::deserialize_from(version.chain(reader))
+            .map_err(|e| format!("Deserialization error: {}", e)),
+        newer if newer > SAVE_VERSION => Err(format!(
+            "Saved by a newer version of the game (save version {}, this build reads up to {})",
+            newer, SAVE_VERSION
+        )),
+        unknown => Err(format!("Unknown save version {}", unknown)),
+    }
+}
+
+/// Saves a world to `path`.
+///
+/// The world is written to a temporary file next to `path`, which then
+/// replaces it, so a crash or full disk mid-write leaves the previous save
+/// intact instead of a truncated one.
+///
+/// # Errors
+/// Returns a message if the file cannot be written or moved into place;
+/// `path` is left untouched then.
 pub fn save_world<P: AsRef<Path>>(path: P, world: &SavedWorld) -> Result<(), String> {
+    let path = path.as_ref();
+    let tmp = temp_path(path);
+    let result = write_world_file(&tmp, world).and_then(|()| {
+        std::fs::rename(&tmp, path)
+            .map_err(|e| format!("Could not replace {}: {}", path.display(), e))
+    });
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Writes `world` to a new file at `path` and flushes it to disk.
+fn write_world_file(path: &Path, world: &SavedWorld) -> Result<(), String> {
     let file = File::create(path).map_err(|e| format!("Could not create file: {}", e))?;
-    let writer = BufWriter::new(file);
-    bincode::serialize_into(writer, world).map_err(|e| format!("Serialization error: {}", e))
+    let mut writer = BufWriter::new(file);
+    write_world(&mut writer, world)?;
+    let file = writer
+        .into_inner()
+        .map_err(|e| format!("Write error: {}", e.error()))?;
+    file.sync_all().map_err(|e| format!("Write error: {}", e))
 }
 
+/// `path` with `.tmp` appended, in the same directory so the final rename
+/// stays on one file system.
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".tmp");
+    PathBuf::from(name)
+}
+
+/// Loads a saved world, upgrading saves from older versions of the game.
+///
+/// # Errors
+/// Returns a message if the file cannot be read, is not a save, or was
+/// written by a newer version of the game.
 pub fn load_world<P: AsRef<Path>>(path: P) -> Result<SavedWorld, String> {
     let file = File::open(path).map_err(|e| format!("Could not open file: {}", e))?;
-    let reader = BufReader::new(file);
-    bincode::deserialize_from(reader).map_err(|e| format!("Deserialization error: {}", e))
+    read_world(BufReader::new(file))
 }
 
 pub const WORLD_FILE_EXTENSION: &str = "minerust";
 pub const DEFAULT_WORLD_FILE: &str = "world.minerust";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    /// Writes the version 1 layout, as the first releases did.
+    #[derive(Serialize)]
+    struct SavedChunkV1 {
+        cx: i32,
+        cz: i32,
+        subchunks: HashMap<u8, Vec<BlockType>>,
+    }
+
+    #[derive(Serialize)]
+    struct SavedWorldV1 {
+        seed: u32,
+        player_x: f32,
+        player_y: f32,
+        player_z: f32,
+        player_yaw: f32,
+        player_pitch: f32,
+        chunks: Vec<SavedChunkV1>,
+    }
+
+    #[test]
+    fn version_1_saves_load_with_defaults() {
+        let blocks = vec![BlockType::Stone; 16 * 16 * 16];
+        let old = SavedWorldV1 {
+            seed: 42,
+            player_x: 1.0,
+            player_y: 70.0,
+            player_z: -3.0,
+            player_yaw: 0.5,
+            player_pitch: -0.25,
+            chunks: vec![SavedChunkV1 {
+                cx: 2,
+                cz: -1,
+                subchunks: HashMap::from([(4, blocks.clone())]),
+            }],
+        };
+        let bytes = bincode::serialize(&old).unwrap();
+
+        let world = read_world(&bytes[..]).expect("a version 1 save should load");
+        assert_eq!(world.version, SAVE_VERSION);
+        assert_eq!(world.seed, 42);
+        assert_eq!(
+            (world.player_x, world.player_y, world.player_z),
+            (1.0, 70.0, -3.0)
+        );
+        assert_eq!((world.player_yaw, world.player_pitch), (0.5, -0.25));
+        assert_eq!(world.sea_level, SEA_LEVEL);
+        assert_eq!(world.gen_config, WorldGenConfig::default());
+        assert!(!world.creative);
+        assert_eq!(world.inventory, Inventory::new());
+        assert_eq!((world.time_of_day, world.time_scale), (0.0, 1.0));
+        let chunk = &world.chunks[0];
+        assert_eq!((chunk.cx, chunk.cz), (2, -1));
+        assert_eq!(chunk.subchunks[&4], blocks);
+        assert!(chunk.facings.is_empty() && chunk.water_levels.is_empty());
+
+        // Saving it again writes the current layout, which reads back as is.
+        let mut bytes = Vec::new();
+        write_world(&mut bytes, &world).unwrap();
+        assert!(bytes.starts_with(&SAVE_MAGIC));
+        let reloaded = read_world(&bytes[..]).unwrap();
+        assert_eq!(reloaded.seed, 42);
+        assert_eq!(reloaded.chunks[0].subchunks[&4], blocks);
+    }
+
+    #[test]
+    fn the_day_cycle_is_restored_on_load() {
+        use crate::world::{DAY_LENGTH, sun_direction};
+
+        // Shortly before midnight, with the clock running at triple speed.
+        let time_of_day = DAY_LENGTH * 0.45;
+        let world = SavedWorld::from_world(
+            &crate::World::new_empty_with_seed(3),
+            (0.0, 64.0, 0.0),
+            (0.0, 0.0),
+            false,
+            &Inventory::new(),
+            time_of_day,
+            3.0,
+        );
+        let mut bytes = Vec::new();
+        write_world(&mut bytes, &world).unwrap();
+
+        let loaded = read_world(&bytes[..]).unwrap();
+        assert_eq!((loaded.time_of_day, loaded.time_scale), (time_of_day, 3.0));
+        let sun = sun_direction(loaded.time_of_day);
+        assert!(sun.y < 0.0, "the sun should still be down, at {}", sun);
+        assert_eq!(sun, sun_direction(time_of_day));
+    }
+
+    #[test]
+    fn saving_replaces_the_previous_file() {
+        let path = std::env::temp_dir().join(format!(
+            "minerust-save-test-{}.{}",
+            std::process::id(),
+            WORLD_FILE_EXTENSION
+        ));
+        std::fs::write(&path, b"an older, longer save file").unwrap();
+        let world = SavedWorld::from_world(
+            &crate::World::new_empty_with_seed(7),
+            (0.0, 64.0, 0.0),
+            (0.0, 0.0),
+            false,
+            &Inventory::new(),
+            0.0,
+            1.0,
+        );
+
+        save_world(&path, &world).unwrap();
+        let loaded = load_world(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(loaded.unwrap().seed, 7);
+        assert!(!temp_path(&path).exists());
+    }
+
+    #[test]
+    fn saves_from_a_newer_version_are_rejected() {
+        let mut bytes = SAVE_MAGIC.to_vec();
+        bytes.extend_from_slice(&(SAVE_VERSION + 1).to_le_bytes());
+        bytes.extend_from_slice(&[0; 64]);
+        let error = read_world(&bytes[..]).err().expect("should not load");
+        assert!(error.contains("newer version"), "{}", error);
+    }
+}
@@ -0,0 +1,102 @@
+// Bloom: bright-pass + downsample, then a separable Gaussian blur at quarter
+// resolution.  Every pass is a full-screen triangle reading one texture.
+
+struct BloomConfig {
+    threshold: f32,
+    knee: f32,
+    intensity: f32,
+    _pad: f32,
+};
+
+@group(0) @binding(0)
+var source_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var bloom_sampler: sampler;
+
+@group(0) @binding(2)
+var<uniform> bloom: BloomConfig;
+
+var<private> positions: array<vec2<f32>, 3> = array<vec2<f32>, 3>(
+    vec2<f32>(-1.0, -1.0),
+    vec2<f32>(3.0, -1.0),
+    vec2<f32>(-1.0, 3.0)
+);
+
+var<private> uvs: array<vec2<f32>, 3> = array<vec2<f32>, 3>(
+    vec2<f32>(0.0, 1.0),
+    vec2<f32>(2.0, 1.0),
+    vec2<f32>(0.0, -1.0)
+);
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = vec4<f32>(positions[vertex_index], 0.0, 1.0);
+    out.uv = uvs[vertex_index];
+    return out;
+}
+
+fn source_texel() -> vec2<f32> {
+    return 1.0 / vec2<f32>(textureDimensions(source_texture));
+}
+
+// Four bilinear taps one source texel from the centre: a 4x4 box filter,
+// which halves the resolution without the shimmer of a single tap.
+fn box_downsample(uv: vec2<f32>) -> vec3<f32> {
+    let d = source_texel();
+    var sum = textureSample(source_texture, bloom_sampler, uv + vec2<f32>(-d.x, -d.y)).rgb;
+    sum += textureSample(source_texture, bloom_sampler, uv + vec2<f32>(d.x, -d.y)).rgb;
+    sum += textureSample(source_texture, bloom_sampler, uv + vec2<f32>(-d.x, d.y)).rgb;
+    sum += textureSample(source_texture, bloom_sampler, uv + vec2<f32>(d.x, d.y)).rgb;
+    return sum * 0.25;
+}
+
+@fragment
+fn fs_prefilter(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = box_downsample(in.uv);
+    let brightness = max(color.r, max(color.g, color.b));
+
+    // Quadratic soft knee so the glow fades in instead of popping on.
+    let knee = max(bloom.knee, 1e-4);
+    let soft = clamp(brightness - bloom.threshold + knee, 0.0, 2.0 * knee);
+    let soft_curve = soft * soft / (4.0 * knee);
+    let contribution = max(soft_curve, brightness - bloom.threshold) / max(brightness, 1e-4);
+
+    return vec4<f32>(color * contribution, 1.0);
+}
+
+@fragment
+fn fs_downsample(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(box_downsample(in.uv), 1.0);
+}
+
+// 9-tap Gaussian folded into 5 bilinear taps.
+fn gaussian_blur(uv: vec2<f32>, direction: vec2<f32>) -> vec3<f32> {
+    let step = source_texel() * direction;
+    let offsets = array<f32, 3>(0.0, 1.3846153846, 3.2307692308);
+    let weights = array<f32, 3>(0.2270270270, 0.3162162162, 0.0702702703);
+
+    var sum = textureSample(source_texture, bloom_sampler, uv).rgb * weights[0];
+    for (var i = 1; i < 3; i++) {
+        let offset = step * offsets[i];
+        sum += textureSample(source_texture, bloom_sampler, uv + offset).rgb * weights[i];
+        sum += textureSample(source_texture, bloom_sampler, uv - offset).rgb * weights[i];
+    }
+    return sum;
+}
+
+@fragment
+fn fs_blur_h(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(gaussian_blur(in.uv, vec2<f32>(1.0, 0.0)), 1.0);
+}
+
+@fragment
+fn fs_blur_v(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(gaussian_blur(in.uv, vec2<f32>(0.0, 1.0)), 1.0);
+}
@@ -23,6 +23,24 @@ var scene_texture: texture_2d<f32>;
 @group(0) @binding(2)
 var composite_sampler: sampler;
 
+struct BloomConfig {
+    threshold: f32,
+    knee: f32,
+    intensity: f32,
+    _pad: f32,
+};
+
+// Blurred bright-pass from the bloom passes, at quarter resolution.
+@group(0) @binding(3)
+var bloom_texture: texture_2d<f32>;
+
+@group(0) @binding(4)
+var<uniform> bloom: BloomConfig;
+
+// Resolved opaque scene depth (R32Float), for placing caustics in the world.
+@group(0) @binding(5)
+var scene_depth: texture_2d<f32>;
+
 var<private> positions: array<vec2<f32>, 3> = array<vec2<f32>, 3>(
     vec2<f32>(-1.0, -1.0),
     vec2<f32>(3.0, -1.0),
@@ -48,8 +66,70 @@ fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
     return out;
 }
 
+// Caustics fade out between these distances from the camera, in blocks,
+// before the lattice gets finer than a pixel.
+const CAUSTIC_FADE_START: f32 = 24.0;
+const CAUSTIC_FADE_END: f32 = 48.0;
+
+// World position of the opaque surface seen at `uv` in xyz, with w = 1, or
+// all zeros where only the sky is behind it.
+fn scene_world_pos(uv: vec2<f32>) -> vec4<f32> {
+    let sz = vec2<i32>(uniforms.screen_size);
+    let px = clamp(vec2<i32>(uv * uniforms.screen_size), vec2<i32>(0), sz - vec2<i32>(1));
+    let d = textureLoad(scene_depth, px, 0).r;
+    if d >= 1.0 {
+        return vec4<f32>(0.0);
+    }
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, (1.0 - uv.y) * 2.0 - 1.0, d, 1.0);
+    let wh = uniforms.inv_view_proj * ndc;
+    return vec4<f32>(wh.xyz / wh.w, 1.0);
+}
+
+// Underwater look, applied to the final image only so the SSR source and
+// the water surface seen from above are never tinted.
+fn underwater_grade(color: vec3<f32>, uv: vec2<f32>) -> vec3<f32> {
+    // Flatten contrast toward mid grey, then shift to blue-green.
+    var graded = mix(vec3<f32>(0.45), color, 0.75);
+    graded *= vec3<f32>(0.55, 0.85, 0.9);
+
+    // Caustics: two warped sine lattices drifting in opposite directions;
+    // their product forms the bright, shifting network of light lines.
+    // They are laid over the world's XZ plane so they stay put on the
+    // terrain as the camera turns.
+    let world = scene_world_pos(uv);
+    if world.w > 0.0 {
+        let p = world.xz * 1.2;
+        let t = uniforms.time;
+        let w1 = sin(p.x + t * 1.3 + sin(p.y * 0.7 + t * 0.9));
+        let w2 = sin(p.y * 1.1 - t * 1.1 + sin(p.x * 0.8 - t * 0.6));
+        let caustic = pow(clamp(w1 * w2 * 0.5 + 0.5, 0.0, 1.0), 3.0);
+        let fade = 1.0 - smoothstep(
+            CAUSTIC_FADE_START,
+            CAUSTIC_FADE_END,
+            distance(world.xyz, uniforms.camera_pos),
+        );
+        graded += vec3<f32>(0.06, 0.12, 0.11) * caustic * fade;
+    }
+
+    // Darken the screen edges slightly.
+    let edge = distance(uv, vec2<f32>(0.5));
+    graded *= 1.0 - smoothstep(0.35, 0.85, edge) * 0.45;
+
+    return graded;
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(scene_texture, composite_sampler, in.uv);
+    let scene = textureSample(scene_texture, composite_sampler, in.uv);
+    // Bilinear upsampling of the quarter-resolution glow is smooth enough
+    // after the blur.  Intensity 0 means bloom is disabled.
+    var color = scene.rgb;
+    if bloom.intensity > 0.0 {
+        color += textureSample(bloom_texture, composite_sampler, in.uv).rgb * bloom.intensity;
+    }
+    if uniforms.is_underwater > 0.5 {
+        color = underwater_grade(color, in.uv);
+    }
+    return vec4<f32>(color, scene.a);
 }
 
@@ -2,6 +2,7 @@ struct SubchunkMeta {
     aabb_min: vec4<f32>,
     aabb_max: vec4<f32>,
     draw_data: vec4<u32>,
+    translucent_draw: vec4<u32>,
 }
 
 struct DrawIndexedIndirect {
@@ -39,6 +40,12 @@ var hiz_texture: texture_2d<f32>;
 @group(0) @binding(5)
 var hiz_sampler: sampler;
 
+@group(0) @binding(6)
+var<storage, read_write> translucent_draw_commands: array<DrawIndexedIndirect>;
+
+@group(0) @binding(7)
+var<storage, read_write> translucent_visible_count: atomic<u32>;
+
 fn aabb_vs_plane(aabb_min: vec3<f32>, aabb_max: vec3<f32>, plane: vec4<f32>) -> bool {
     let p = vec3<f32>(
         select(aabb_min.x, aabb_max.x, plane.x > 0.0),
@@ -152,10 +159,21 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
         return;
     }
 
-    let slot = atomicAdd(&visible_count, 1u);
-    draw_commands[slot].index_count    = subchunk.draw_data.x;
-    draw_commands[slot].instance_count = 1u;
-    draw_commands[slot].first_index    = subchunk.draw_data.y;
-    draw_commands[slot].base_vertex    = i32(subchunk.draw_data.z);
-    draw_commands[slot].first_instance = 0u;
+    if subchunk.draw_data.x > 0u {
+        let slot = atomicAdd(&visible_count, 1u);
+        draw_commands[slot].index_count    = subchunk.draw_data.x;
+        draw_commands[slot].instance_count = 1u;
+        draw_commands[slot].first_index    = subchunk.draw_data.y;
+        draw_commands[slot].base_vertex    = i32(subchunk.draw_data.z);
+        draw_commands[slot].first_instance = 0u;
+    }
+
+    if subchunk.translucent_draw.x > 0u {
+        let slot = atomicAdd(&translucent_visible_count, 1u);
+        translucent_draw_commands[slot].index_count    = subchunk.translucent_draw.x;
+        translucent_draw_commands[slot].instance_count = 1u;
+        translucent_draw_commands[slot].first_index    = subchunk.translucent_draw.y;
+        translucent_draw_commands[slot].base_vertex    = i32(subchunk.translucent_draw.z);
+        translucent_draw_commands[slot].first_instance = 0u;
+    }
 }
\ No newline at end of file
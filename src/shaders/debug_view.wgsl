@@ -0,0 +1,62 @@
+// Full-screen views of intermediate render targets, drawn over the
+// composited frame when a debug view is picked (see `app/debug_view.rs`).
+// Texels are fetched with `textureLoad`, so the R32Float SSR depth needs
+// no filtering sampler.
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+
+// Near clip plane of the camera projection (`NEAR_PLANE` in constants.rs).
+const NEAR_PLANE: f32 = 0.1;
+
+// Distance, in blocks, at which the depth view reaches mid grey.
+const DEPTH_VIEW_SCALE: f32 = 48.0;
+
+var<private> positions: array<vec2<f32>, 3> = array<vec2<f32>, 3>(
+    vec2<f32>(-1.0, -1.0),
+    vec2<f32>(3.0, -1.0),
+    vec2<f32>(-1.0, 3.0)
+);
+
+var<private> uvs: array<vec2<f32>, 3> = array<vec2<f32>, 3>(
+    vec2<f32>(0.0, 1.0),
+    vec2<f32>(2.0, 1.0),
+    vec2<f32>(0.0, -1.0)
+);
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = vec4<f32>(positions[vertex_index], 0.0, 1.0);
+    out.uv = uvs[vertex_index];
+    return out;
+}
+
+fn load_source(uv: vec2<f32>) -> vec4<f32> {
+    let size = textureDimensions(source);
+    let texel = min(vec2<u32>(uv * vec2<f32>(size)), size - vec2<u32>(1u));
+    return textureLoad(source, texel, 0);
+}
+
+// HDR color, clamped like the composite pass does on the swap-chain.
+@fragment
+fn fs_color(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(clamp(load_source(in.uv).rgb, vec3<f32>(0.0), vec3<f32>(1.0)), 1.0);
+}
+
+// Device depth turned into view distance, black up close and fading to
+// white far away; the sky (depth 1) is white.
+@fragment
+fn fs_depth(in: VertexOutput) -> @location(0) vec4<f32> {
+    let depth = load_source(in.uv).r;
+    // With the far plane much farther than the near plane the projection
+    // maps view distance z to 1 - NEAR_PLANE / z.
+    let distance = NEAR_PLANE / max(1.0 - depth, 1e-6);
+    let shade = distance / (distance + DEPTH_VIEW_SCALE);
+    return vec4<f32>(vec3<f32>(shade), 1.0);
+}
@@ -1,5 +1,6 @@
-@group(0) @binding(0)
-var msaa_depth: texture_depth_multisampled_2d;
+// The `scene_depth` binding and its accessors, `depth_sample_count` and
+// `load_depth`, are prepended at pipeline creation to match the scene's
+// sample count (see `app/msaa.rs`).
 
 @group(0) @binding(1)
 var hiz_seed: texture_storage_2d<r32float, write>;
@@ -8,19 +9,20 @@ var hiz_seed: texture_storage_2d<r32float, write>;
 var ssr_depth: texture_storage_2d<r32float, write>;
 
 fn resolve_min_max_depth(coords: vec2<u32>) -> vec2<f32> {
-    let s0 = textureLoad(msaa_depth, vec2<i32>(coords), 0);
-    let s1 = textureLoad(msaa_depth, vec2<i32>(coords), 1);
-    let s2 = textureLoad(msaa_depth, vec2<i32>(coords), 2);
-    let s3 = textureLoad(msaa_depth, vec2<i32>(coords), 3);
-
-    let min_depth = min(min(s0, s1), min(s2, s3));
-    let max_depth = max(max(s0, s1), max(s2, s3));
+    let pixel = vec2<i32>(coords);
+    var min_depth = load_depth(pixel, 0);
+    var max_depth = min_depth;
+    for (var i = 1; i < i32(depth_sample_count()); i++) {
+        let d = load_depth(pixel, i);
+        min_depth = min(min_depth, d);
+        max_depth = max(max_depth, d);
+    }
     return vec2<f32>(min_depth, max_depth);
 }
 
 @compute @workgroup_size(16, 16)
 fn main(@builtin(global_invocation_id) id: vec3<u32>) {
-    let size = textureDimensions(msaa_depth);
+    let size = textureDimensions(scene_depth);
     if id.x >= size.x || id.y >= size.y {
         return;
     }
@@ -12,6 +12,29 @@ struct Uniforms {
     _reflection_mode: f32,
     moon_position: vec3<f32>,
     _pad1: f32,
+    // Scalars rather than `wind_dir: vec2<f32>`, which would be padded to
+    // an 8-byte offset and shift the fields after it.
+    _moon_intensity: f32,
+    _wind_dir_x: f32,
+    _wind_dir_z: f32,
+    _wind_speed: f32,
+    _pad2: f32,
+    _rain_factor: f32,
+    cloud_coverage: f32,
+    _pad3: f32,
+    _ambient_color: vec3<f32>,
+    _day_factor: f32,
+    _dawn_tint: vec3<f32>,
+    _pad_light: f32,
+    _fog_color: vec3<f32>,
+    _fog_strength: f32,
+    _fog_start: f32,
+    _fog_end: f32,
+    _pad_fog0: f32,
+    _pad_fog1: f32,
+    // Gradient colors picked with `--sky`, in the order of `SKY_*` below;
+    // `.w` is unused.
+    sky_palette: array<vec4<f32>, 6>,
 };
 
 @group(0) @binding(0)
@@ -20,6 +43,14 @@ var<uniform> uniforms: Uniforms;
 const TAU: f32 = 6.28318530718;
 const PI: f32 = 3.14159265359;
 
+// Rows of `uniforms.sky_palette` (see `SkyPalette::to_uniform`).
+const SKY_ZENITH_DAY: u32 = 0u;
+const SKY_HORIZON_DAY: u32 = 1u;
+const SKY_ZENITH_NIGHT: u32 = 2u;
+const SKY_HORIZON_NIGHT: u32 = 3u;
+const SKY_DUSK_LOW: u32 = 4u;
+const SKY_DUSK_HIGH: u32 = 5u;
+
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) packed: u32,
@@ -69,12 +100,12 @@ fn atmospheric_gradient(view_dir: vec3<f32>, sun_dir: vec3<f32>) -> vec3<f32> {
     let night = smoothstep(0.10, -0.10, sun_h);
     let dusk = 1.0 - smoothstep(0.02, 0.34, abs(sun_h));
 
-    let zenith_day = vec3<f32>(0.18, 0.44, 0.88);
-    let horizon_day = vec3<f32>(0.66, 0.82, 0.98);
-    let zenith_night = vec3<f32>(0.002, 0.005, 0.015);
-    let horizon_night = vec3<f32>(0.010, 0.014, 0.034);
-    let dusk_low = vec3<f32>(0.96, 0.40, 0.18);
-    let dusk_high = vec3<f32>(0.24, 0.10, 0.28);
+    let zenith_day = uniforms.sky_palette[SKY_ZENITH_DAY].rgb;
+    let horizon_day = uniforms.sky_palette[SKY_HORIZON_DAY].rgb;
+    let zenith_night = uniforms.sky_palette[SKY_ZENITH_NIGHT].rgb;
+    let horizon_night = uniforms.sky_palette[SKY_HORIZON_NIGHT].rgb;
+    let dusk_low = uniforms.sky_palette[SKY_DUSK_LOW].rgb;
+    let dusk_high = uniforms.sky_palette[SKY_DUSK_HIGH].rgb;
 
     var sky = mix(horizon_day, zenith_day, pow(h, 0.78)) * day;
     sky += mix(horizon_night, zenith_night, pow(h, 0.72)) * night;
@@ -140,7 +171,11 @@ fn cloud_noise(p: vec2<f32>) -> f32 {
     return a * 0.34 + b * 0.26 + c * 0.22 + d * 0.18;
 }
 
-fn cloud_layer(view_dir: vec3<f32>, sun_dir: vec3<f32>, time: f32) -> vec3<f32> {
+// `coverage` is the fraction of the sky under cloud; 0 draws none.
+fn cloud_layer(view_dir: vec3<f32>, sun_dir: vec3<f32>, time: f32, coverage: f32) -> vec3<f32> {
+    if coverage <= 0.0 {
+        return vec3<f32>(0.0);
+    }
     let sun_h = sun_dir.y;
     let day = smoothstep(-0.03, 0.20, sun_h);
     let dusk = 1.0 - smoothstep(0.04, 0.30, abs(sun_h));
@@ -160,9 +195,11 @@ fn cloud_layer(view_dir: vec3<f32>, sun_dir: vec3<f32>, time: f32) -> vec3<f32>
 
     let n = cloud_noise(p + drift * 0.18);
     let n2 = cloud_noise(p * 1.8 - drift * 0.8);
-    let coverage = smoothstep(0.60, 0.90, n * 0.70 + n2 * 0.30);
+    // The default coverage of 0.5 starts the clouds at a noise value of 0.6.
+    let threshold = mix(0.95, 0.25, coverage);
+    let cover = smoothstep(threshold, threshold + 0.30, n * 0.70 + n2 * 0.30);
     let wisps = smoothstep(0.52, 0.78, cloud_noise(p * 2.9 + drift * 1.3));
-    let layer = coverage * band * band;
+    let layer = cover * band * band;
     if layer <= 0.001 {
         return vec3<f32>(0.0);
     }
@@ -282,7 +319,7 @@ fn fs_sky(in: VertexOutput) -> @location(0) vec4<f32> {
     sky += atmospheric_scatter(view_dir, sun_dir);
     sky += horizon_haze(view_dir, sun_dir);
     sky += sun_glow(view_dir, sun_dir);
-    sky += cloud_layer(view_dir, sun_dir, uniforms.time);
+    sky += cloud_layer(view_dir, sun_dir, uniforms.time, uniforms.cloud_coverage);
     sky += star_field(view_dir, uniforms.time, night);
     sky += moon_disk(view_dir, moon_dir, night);
 
@@ -17,6 +17,9 @@ struct Uniforms {
 @group(0) @binding(0)
 var<uniform> uniforms: Uniforms;
 
+// Extra brightness of the sun disc above the display range.
+const SUN_HDR_INTENSITY: f32 = 4.0;
+
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) packed: u32,
@@ -91,7 +94,9 @@ fn fs_sun(in: VertexOutput) -> @location(0) vec4<f32> {
     let halo_tint = mix(base_color, vec3<f32>(1.0, 0.72, 0.50), reddening * 0.8);
 
     var final_color = vec3<f32>(0.0);
-    final_color += disk_color * disk_mask * 1.75;
+    // The disc is far brighter than any lit surface so the bloom threshold
+    // picks it up; the scene target is floating point and keeps the excess.
+    final_color += disk_color * disk_mask * 1.75 * SUN_HDR_INTENSITY;
     final_color += halo_tint * inner_halo * 0.16;
     final_color += halo_tint * outer_halo * 0.06;
 
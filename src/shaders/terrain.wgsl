@@ -7,11 +7,37 @@ struct Uniforms {
     time:                f32,
     sun_position:        vec3<f32>,
     is_underwater:       f32,
+    _screen_size:        vec2<f32>,
+    _water_level:        f32,
+    _reflection_mode:    f32,
+    _moon_position:      vec3<f32>,
+    _pad1_moon:          f32,
+    // Scalars rather than `wind_dir: vec2<f32>`, which would be padded to
+    // an 8-byte offset and shift the fields after it.
+    _moon_intensity:     f32,
+    _wind_dir_x:         f32,
+    _wind_dir_z:         f32,
+    _wind_speed:         f32,
+    _pad:                f32,
+    _rain_factor:        f32,
+    cloud_coverage:      f32,
+    _pad_clouds:         f32,
+    ambient_color:       vec3<f32>,
+    day_factor:          f32,
+    dawn_tint:           vec3<f32>,
+    _pad_light:          f32,
+    fog_color:           vec3<f32>,
+    fog_strength:        f32,
+    fog_start:           f32,
+    fog_end:             f32,
+    _pad_fog0:           f32,
+    _pad_fog1:           f32,
 };
 
 struct ShadowConfig {
     shadow_map_size: f32,
     pcf_samples:     u32,
+    cascade_count:   u32,
 }
 
 @group(0) @binding(0) var<uniform> uniforms:       Uniforms;
@@ -31,6 +57,16 @@ struct ShadowConfig {
 const PI:               f32 = 3.14159265359;
 const MAX_PCF_SAMPLES:  i32 = 16;
 
+// Cloud shadows: a noise layer at CLOUD_HEIGHT, roughly CLOUD_SIZE blocks
+// per cloud, drifting along CLOUD_WIND at CLOUD_DRIFT_SPEED blocks per
+// second.  A fully clouded spot keeps 1 - CLOUD_SHADOW_STRENGTH of the
+// direct sunlight.
+const CLOUD_HEIGHT:          f32 = 192.0;
+const CLOUD_SIZE:            f32 = 48.0;
+const CLOUD_WIND:            vec2<f32> = vec2<f32>(0.94, 0.35);
+const CLOUD_DRIFT_SPEED:     f32 = 1.5;
+const CLOUD_SHADOW_STRENGTH: f32 = 0.45;
+
 fn world_space_noise(world_pos: vec3<f32>) -> f32 {
     let p = vec2<u32>(bitcast<u32>(world_pos.x) ^ 0x9e3779b9u,
                       bitcast<u32>(world_pos.z) ^ 0x517cc1b7u);
@@ -95,7 +131,9 @@ fn sample_cascade_pcf(
     return mix(1.0, shadow / f32(pcf_samples), edge_fade);
 }
 
-fn select_cascade_with_blend(view_depth: f32) -> vec2<f32> {
+// Only the first `last_cascade` splits are boundaries between rendered
+// cascades; everything beyond them falls into the last one.
+fn select_cascade_with_blend(view_depth: f32, last_cascade: i32) -> vec2<f32> {
     let bf = 0.10;
     let splits = array<f32, 3>(
         uniforms.csm_split_distances.x,
@@ -103,7 +141,7 @@ fn select_cascade_with_blend(view_depth: f32) -> vec2<f32> {
         uniforms.csm_split_distances.z,
     );
 
-    for (var i = 0; i < 3; i++) {
+    for (var i = 0; i < last_cascade; i++) {
         let blend_start = splits[i] * (1.0 - bf);
         if view_depth < blend_start { return vec2<f32>(f32(i), 0.0); }
         if view_depth < splits[i] {
@@ -111,7 +149,7 @@ fn select_cascade_with_blend(view_depth: f32) -> vec2<f32> {
             return vec2<f32>(f32(i), smoothstep(0.0, 1.0, t));
         }
     }
-    return vec2<f32>(3.0, 0.0);
+    return vec2<f32>(f32(last_cascade), 0.0);
 }
 
 fn calculate_shadow(
@@ -131,18 +169,44 @@ fn calculate_shadow(
     let shadow_map_size = max(shadow_config.shadow_map_size, 1.0);
     let radius = 3.0 / shadow_map_size;
 
-    let cb = select_cascade_with_blend(view_depth);
+    let last = clamp(i32(shadow_config.cascade_count), 1, 4) - 1;
+    let cb = select_cascade_with_blend(view_depth, last);
     let ci = i32(cb.x);
 
-    let shadow_a = sample_cascade_pcf(world_pos, ci, bias, rot, radius, ci == 3);
+    let shadow_a = sample_cascade_pcf(world_pos, ci, bias, rot, radius, ci == last);
 
-    if cb.y > 0.001 && ci < 3 {
-        let shadow_b = sample_cascade_pcf(world_pos, ci + 1, bias, rot, radius, (ci + 1) == 3);
+    if cb.y > 0.001 && ci < last {
+        let shadow_b = sample_cascade_pcf(world_pos, ci + 1, bias, rot, radius, (ci + 1) == last);
         return mix(shadow_a, shadow_b, cb.y);
     }
     return shadow_a;
 }
 
+fn cloud_noise(p: vec2<f32>) -> f32 {
+    let a = sin(dot(p, vec2<f32>(1.3, 1.7)) + 0.3) * 0.5 + 0.5;
+    let b = sin(dot(p, vec2<f32>(2.1, 1.2)) + 1.7) * 0.5 + 0.5;
+    let c = sin(dot(p, vec2<f32>(3.7, 2.9)) + 2.6) * 0.5 + 0.5;
+    let d = sin(dot(p, vec2<f32>(5.4, 4.1)) + 4.2) * 0.5 + 0.5;
+    return a * 0.34 + b * 0.26 + c * 0.22 + d * 0.18;
+}
+
+// Fraction of direct sunlight reaching `world_pos` through the cloud
+// layer: the noise is sampled where the ray toward the sun crosses
+// CLOUD_HEIGHT, so the shadows slide across the ground as the sun moves.
+fn cloud_shadow(world_pos: vec3<f32>, sun_dir: vec3<f32>) -> f32 {
+    let coverage = uniforms.cloud_coverage;
+    if coverage <= 0.0 || sun_dir.y < 0.05 { return 1.0; }
+
+    let to_layer = max(CLOUD_HEIGHT - world_pos.y, 0.0) / sun_dir.y;
+    let p = world_pos.xz + sun_dir.xz * to_layer + CLOUD_WIND * uniforms.time * CLOUD_DRIFT_SPEED;
+    let n = cloud_noise(p / CLOUD_SIZE) * 0.70 + cloud_noise(p / CLOUD_SIZE * 1.8) * 0.30;
+
+    // Same coverage-to-threshold mapping as the sky's cloud layer.
+    let threshold = mix(0.95, 0.25, coverage);
+    let cover = smoothstep(threshold, threshold + 0.30, n);
+    return 1.0 - cover * CLOUD_SHADOW_STRENGTH;
+}
+
 @compute @workgroup_size(8, 8, 1)
 fn compute_shadow(@builtin(global_invocation_id) gid: vec3<u32>) {
     let tex_size = textureDimensions(ssr_depth);
@@ -172,7 +236,8 @@ fn compute_shadow(@builtin(global_invocation_id) gid: vec3<u32>) {
 
     var shadow_factor = 1.0;
     if (sun_dir.y > 0.0) {
-        shadow_factor = calculate_shadow(world_pos, vec3<f32>(0.0, 1.0, 0.0), sun_dir, view_depth);
+        shadow_factor = calculate_shadow(world_pos, vec3<f32>(0.0, 1.0, 0.0), sun_dir, view_depth)
+            * cloud_shadow(world_pos, sun_dir);
     }
 
     textureStore(output_shadow, gid.xy, vec4<f32>(shadow_factor, 0.0, 0.0, 0.0));
@@ -237,24 +302,15 @@ fn vs_shadow(model: VertexInput) -> @builtin(position) vec4<f32> {
     return uniforms.view_proj * vec4<f32>(model.position, 1.0);
 }
 
-@fragment
-fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    let tex = textureSample(texture_atlas, texture_sampler, fract(in.uv), i32(in.tex_index + 0.5));
-    if tex.a < 0.5 { discard; }
-
-    let shadow_tex_size = vec2<f32>(textureDimensions(shadow_mask));
-    let screen_uv = in.clip_position.xy / shadow_tex_size;
-    let shadow = textureSampleLevel(shadow_mask, point_sampler, screen_uv, 0.0).r;
-
+// Lit and fogged color of a terrain fragment with texel color `tex_rgb`
+// and sunlight factor `shadow` (0 = fully shadowed).
+fn shade_terrain(in: VertexOutput, tex_rgb: vec3<f32>, shadow: f32) -> vec3<f32> {
     let sun_dir = normalize(uniforms.sun_position);
 
-    let day_factor      = clamp(sun_dir.y, 0.0, 1.0);
-    let twilight_factor = smoothstep(-0.1, 0.15, sun_dir.y) * smoothstep(0.4, 0.0, sun_dir.y);
-
-    let ambient = max(
-        mix(0.005, 0.38, day_factor),
-        0.18 * twilight_factor,
-    );
+    // Eased day factor and ambient color, computed on the CPU from the sun
+    // height and the minimum ambient setting.
+    let day_factor = uniforms.day_factor;
+    let ambient    = uniforms.ambient_color;
 
     let normal = normalize(in.normal);
 
@@ -267,27 +323,56 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     else if abs(normal.x) > 0.5 { face_shade = 0.7; }
     else                        { face_shade = 0.8; }
 
-    let total_light = (ambient + sun_diff + fill_diff) * face_shade;
-    var lit = tex.rgb * total_light;
-
-    let sunset_factor = 1.0 - abs(sun_dir.y);
-    if sunset_factor > 0.3 && sun_dir.y > -0.2 {
-        lit *= mix(vec3<f32>(1.0), vec3<f32>(1.0, 0.85, 0.7), sunset_factor * 0.5);
-    }
+    let total_light = (ambient + vec3<f32>(sun_diff + fill_diff)) * face_shade;
+    let lit = tex_rgb * total_light * uniforms.dawn_tint;
 
     let dist = length(in.world_pos.xz - uniforms.camera_pos.xz);
     let is_underwater = uniforms.is_underwater > 0.5;
 
     var final_color = lit;
 
+    // Distance fog only; the tint and caustics are applied to the whole
+    // frame in composite.wgsl.  Above water, a light biome-colored haze
+    // builds up towards the edge of the render distance.
     if is_underwater {
-        final_color *= vec3<f32>(0.4, 0.7, 1.0);
-        let caustic = sin(in.world_pos.x * 0.5 + uniforms.time * 2.0)
-                    * sin(in.world_pos.z * 0.5 + uniforms.time * 1.5) * 0.1 + 0.9;
-        final_color *= caustic;
         final_color = mix(final_color, vec3<f32>(0.05, 0.15, 0.3),
                           clamp(dist / 24.0, 0.0, 1.0) * 0.5);
+    } else {
+        let haze = smoothstep(uniforms.fog_start, uniforms.fog_end, dist) * uniforms.fog_strength;
+        final_color = mix(final_color, uniforms.fog_color, haze);
+    }
+
+    return final_color;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let tex = textureSample(texture_atlas, texture_sampler, fract(in.uv), i32(in.tex_index + 0.5));
+    if tex.a < 0.5 { discard; }
+
+    let shadow_tex_size = vec2<f32>(textureDimensions(shadow_mask));
+    let screen_uv = in.clip_position.xy / shadow_tex_size;
+    let shadow = textureSampleLevel(shadow_mask, point_sampler, screen_uv, 0.0).r;
+
+    return vec4<f32>(shade_terrain(in, tex.rgb, shadow), 1.0);
+}
+
+// Translucent terrain (glass, ice), alpha-blended over the finished opaque
+// scene.  It is left out of the depth prepass so it does not hide what is
+// behind it, which also means the screen-space shadow mask holds the shadow
+// of that surface rather than this one; the shadow is sampled here instead.
+@fragment
+fn fs_translucent(in: VertexOutput) -> @location(0) vec4<f32> {
+    let tex = textureSample(texture_atlas, texture_sampler, fract(in.uv), i32(in.tex_index + 0.5));
+    if tex.a < 0.01 { discard; }
+
+    let sun_dir = normalize(uniforms.sun_position);
+    var shadow = 1.0;
+    if sun_dir.y > 0.0 {
+        let view_depth = length(in.world_pos - uniforms.camera_pos);
+        shadow = calculate_shadow(in.world_pos, normalize(in.normal), sun_dir, view_depth)
+            * cloud_shadow(in.world_pos, sun_dir);
     }
 
-    return vec4<f32>(final_color, 1.0);
+    return vec4<f32>(shade_terrain(in, tex.rgb, shadow), tex.a);
 }
@@ -22,6 +22,18 @@ var shadow_map: texture_depth_2d;
 @group(0) @binding(4)
 var shadow_sampler: sampler_comparison;
 
+// Minimap surface colors, one texel per block column, wrapped around so
+// column (x, z) is at texel (x, z) mod `MINIMAP_TEXTURE_SIZE`.
+@group(1) @binding(0)
+var minimap_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var minimap_sampler: sampler;
+
+// `MINIMAP_TEXTURE_SIZE` must match `ui/minimap.rs`; the span is
+// `2 * MINIMAP_RADIUS * CHUNK_SIZE` from there.
+const MINIMAP_TEXTURE_SIZE: f32 = 192.0;
+const MINIMAP_SPAN: f32 = 160.0;
+
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) packed:   u32,
@@ -46,7 +58,12 @@ fn vs_ui(model: VertexInput) -> VertexOutput {
     var out: VertexOutput;
     out.clip_position = vec4<f32>(model.position.xy, 0.0, 1.0);
     out.color = vec4<f32>(r, g, b, a);
-    out.uv = vec2<f32>(0.0, 0.0);
+    // Corner index (BL, BR, TR, TL) → UV with v = 0 at the top edge.
+    let corner = (model.packed >> 11u) & 0x3u;
+    out.uv = vec2<f32>(
+        select(0.0, 1.0, corner == 1u || corner == 2u),
+        select(1.0, 0.0, corner >= 2u),
+    );
     out.tex_index = f32((model.packed >> 3u) & 0xFFu);
     return out;
 }
@@ -55,3 +72,13 @@ fn vs_ui(model: VertexInput) -> VertexOutput {
 fn fs_ui(in: VertexOutput) -> @location(0) vec4<f32> {
     return in.color;
 }
+
+// Minimap quad: the UV spans `MINIMAP_SPAN` block columns centred on the
+// camera, north (-Z) up.  Columns that are not loaded have zero alpha and
+// let the frame behind the quad show through.
+@fragment
+fn fs_minimap(in: VertexOutput) -> @location(0) vec4<f32> {
+    let column = uniforms.camera_pos.xz + (in.uv - 0.5) * MINIMAP_SPAN;
+    let surface = textureSample(minimap_texture, minimap_sampler, column / MINIMAP_TEXTURE_SIZE);
+    return vec4<f32>(surface.rgb, surface.a * in.color.a);
+}
@@ -10,7 +10,6 @@ const SSR_THICKNESS:     f32 = 0.06;
 const SSR_EDGE_FADE:     f32 = 0.06;
 const SSR_FADE_DISTANCE: f32 = 300.0;
 
-const SHADOW_MAP_SIZE: f16 = 2048.0;
 const PCF_SAMPLES:     i32 = 16;
 
 const LOD_FAR: f32 = 300.0;
@@ -23,6 +22,32 @@ const WATER_LEVEL_OFFSET:  f32 = 0.15;
 const WATER_ROUGHNESS_MIN: f32 = 0.03;
 const WATER_ROUGHNESS_MAX: f32 = 0.14;
 
+// Time-of-day response.  Daylight ramps from 0 to 1 as the sun's height
+// (sun direction y) goes from DAYLIGHT_SUN_LOW to DAYLIGHT_SUN_HIGH; at 0 the
+// water is scaled by the NIGHT_* factors below.
+const DAYLIGHT_SUN_LOW:        f32 = -0.12;
+const DAYLIGHT_SUN_HIGH:       f32 = 0.35;
+const NIGHT_WATER_BRIGHTNESS:  f32 = 0.10;
+const NIGHT_WATER_SATURATION:  f32 = 0.35;
+const NIGHT_REFLECTION_FACTOR: f32 = 0.35;
+const NIGHT_RIPPLE_FACTOR:     f32 = 0.40;
+
+// Sun glint.  The GGX highlight is compressed to [0, 1) and scaled by a
+// time-of-day strength that is SUN_GLINT_LOW while the sun is near the
+// horizon and falls to SUN_GLINT_NOON once it is SUN_GLINT_HIGH_SUN high, so
+// the glint peaks at sunrise and sunset and never blows out without bloom.
+const SUN_GLINT_LOW:      f32 = 1.35;
+const SUN_GLINT_NOON:     f32 = 0.55;
+const SUN_GLINT_HIGH_SUN: f32 = 0.55;
+
+// Depth fade.  The water thickness along the view ray (surface to the
+// terrain behind it, from the resolved scene depth) fades the water out over
+// the first SHORE_FADE_DEPTH blocks and reaches the deep colour and
+// DEEP_WATER_OPACITY at DEEP_WATER_DEPTH.
+const SHORE_FADE_DEPTH:   f32 = 0.8;
+const DEEP_WATER_DEPTH:   f32 = 10.0;
+const DEEP_WATER_OPACITY: f32 = 0.78;
+
 const FOAM_THRESHOLD: f32 = 0.30;
 const FOAM_INTENSITY: f32 = 0.70;
 
@@ -65,11 +90,15 @@ struct VertexInput {
     @location(1) packed:    u32,
 };
 
+// Normal index the mesher gives the exposed top surface of a water body
+// (Vertex::WATER_SURFACE_NORMAL); only these faces get waves.
+const WATER_SURFACE_NORMAL: u32 = 6u;
+
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) world_pos:    vec3<f32>,
-    @location(1) wave_normal:  vec4<f32>,
-    @location(2) jacobian:     f32,
+    @location(1) face_normal:  vec3<f32>,
+    @location(2) @interpolate(flat) is_surface: u32,
 };
 
 const SW_K:     array<f32, 4> = array(0.38,  0.84,  2.10,  5.60);
@@ -186,6 +215,9 @@ fn calculate_gerstner(pos: vec3<f32>, time: f32) -> GerstnerResult {
     return result;
 }
 
+// Surfaces are greedy-merged into quads up to a sub-chunk wide, far too
+// coarse to displace per vertex, so the vertex stage only lowers them below
+// the block top; the waves themselves are evaluated per pixel in fs_water.
 @vertex
 fn vs_water(model: VertexInput) -> VertexOutput {
     var out: VertexOutput;
@@ -193,28 +225,21 @@ fn vs_water(model: VertexInput) -> VertexOutput {
 
     let n_idx   = model.packed & 0x7u;
 
-    let normals = array<vec3<f32>, 6>(
+    let normals = array<vec3<f32>, 7>(
         vec3<f32>(-1.0, 0.0, 0.0), vec3<f32>(1.0, 0.0, 0.0),
         vec3<f32>(0.0, -1.0, 0.0), vec3<f32>(0.0, 1.0, 0.0),
-        vec3<f32>(0.0, 0.0, -1.0), vec3<f32>(0.0, 0.0, 1.0)
+        vec3<f32>(0.0, 0.0, -1.0), vec3<f32>(0.0, 0.0, 1.0),
+        vec3<f32>(0.0, 1.0, 0.0)
     );
 
-    var wave_normal = normals[n_idx % 6u];
-    var foam_val    = 0.0;
-    var jac_val     = 1.0;
-
-    if n_idx == 3u {
-        let w  = calculate_gerstner(pos, uniforms.time);
-        pos   += w.displacement - vec3(0.0, WATER_LEVEL_OFFSET, 0.0);
-        wave_normal = w.normal;
-        foam_val    = w.foam;
-        jac_val     = w.jacobian;
+    if n_idx == WATER_SURFACE_NORMAL {
+        pos.y -= WATER_LEVEL_OFFSET;
     }
 
     out.clip_position = uniforms.view_proj * vec4(pos, 1.0);
     out.world_pos     = pos;
-    out.wave_normal   = vec4(normalize(wave_normal), foam_val);
-    out.jacobian      = jac_val;
+    out.face_normal   = normals[min(n_idx, 6u)];
+    out.is_surface    = u32(n_idx == WATER_SURFACE_NORMAL);
     return out;
 }
 
@@ -228,7 +253,7 @@ fn ggx_distribution(ndh: f32, roughness: f32) -> f32 {
     let a  = roughness * roughness;
     let a2 = a * a;
     let d  = ndh * ndh * (a2 - 1.0) + 1.0;
-    return a2 / (PI * d * d + 0.0001);
+    return a2 / max(PI * d * d, 1e-8);
 }
 
 fn ggx_spec_simple(normal: vec3<f32>, view: vec3<f32>, light: vec3<f32>, roughness: f32) -> f32 {
@@ -238,6 +263,12 @@ fn ggx_spec_simple(normal: vec3<f32>, view: vec3<f32>, light: vec3<f32>, roughne
     return ggx_distribution(ndh, roughness) * ndl;
 }
 
+// Maps an unbounded highlight to [0, 1) so the peak of a sharp lobe stays
+// bright without clipping.
+fn compress_highlight(spec: f32) -> f32 {
+    return spec / (1.0 + spec);
+}
+
 fn fbm_normal_perturb(p: vec2<f32>, t: f32) -> vec2<f32> {
     let a = sin(vec4(
         p.x * 3.1  + t * 0.9,   p.y * 2.7  - t * 1.1,
@@ -277,6 +308,26 @@ fn sky_reflection_color(view_dir: vec3<f32>, sun_dir: vec3<f32>, moon_intensity:
     return sky * 1.15;
 }
 
+// 0 at night, 1 at midday, smooth through dawn and dusk.
+fn water_daylight(sun_h: f32) -> f32 {
+    return smoothstep(DAYLIGHT_SUN_LOW, DAYLIGHT_SUN_HIGH, sun_h);
+}
+
+// Strength of the sun glint: fades in as the sun rises, peaks while it is
+// low and settles to SUN_GLINT_NOON higher up.
+fn sun_glint_strength(sun_h: f32) -> f32 {
+    let risen = smoothstep(-0.02, 0.05, sun_h);
+    let high  = smoothstep(0.05, SUN_GLINT_HIGH_SUN, sun_h);
+    return risen * mix(SUN_GLINT_LOW, SUN_GLINT_NOON, high);
+}
+
+// Darkens and desaturates the water body color as daylight fades.
+fn apply_daylight(color: vec3<f32>, daylight: f32) -> vec3<f32> {
+    let luma  = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+    let night = mix(vec3<f32>(luma), color, NIGHT_WATER_SATURATION) * NIGHT_WATER_BRIGHTNESS;
+    return mix(night, color, daylight);
+}
+
 fn sample_depth(uv: vec2<f32>) -> f32 {
     let sz = vec2<i32>(uniforms.screen_size);
     let px = clamp(vec2<i32>(uv * uniforms.screen_size), vec2<i32>(0), sz - vec2<i32>(1));
@@ -289,6 +340,20 @@ fn reconstruct_world(uv: vec2<f32>, d: f32) -> vec3<f32> {
     return wh.xyz / wh.w;
 }
 
+// Distance the view ray travels through water between the surface at
+// `world_pos` (`dist` from the camera) and the opaque scene behind it.
+// Pixels where only the sky is behind the water (depth at the far plane)
+// count as deep water rather than reconstructing a point at infinity.
+fn water_thickness(frag_xy: vec2<f32>, world_pos: vec3<f32>, dist: f32) -> f32 {
+    let uv = frag_xy / uniforms.screen_size;
+    let d  = sample_depth(uv);
+    if d >= 1.0 {
+        return DEEP_WATER_DEPTH;
+    }
+    let behind = reconstruct_world(uv, d);
+    return max(distance(behind, uniforms.camera_pos) - dist, 0.0);
+}
+
 fn ssr_trace(world_pos: vec3<f32>, refl_dir: vec3<f32>) -> vec4<f32> {
     let dir  = normalize(refl_dir);
     var ray  = world_pos + dir * 0.3;
@@ -372,7 +437,7 @@ fn calculate_shadow(world_pos: vec3<f32>, sun_dir: vec3<f32>) -> f32 {
     if any(uv < vec2(0.0)) || any(uv > vec2(1.0)) { return 1.0; }
 
     let bias  = 0.003;
-    let texel = 1.0 / f32(SHADOW_MAP_SIZE);
+    let texel = 1.0 / f32(textureDimensions(shadow_map).x);
     let rot   = hash21(world_pos.xz) * TAU;
     let s     = sin(rot);
     let c     = cos(rot);
@@ -396,43 +461,64 @@ fn fs_water(in: VertexOutput) -> @location(0) vec4<f32> {
     let view_dir  = to_camera / dist;
     let sun_dir   = normalize(uniforms.sun_position);
     let day       = f16(clamp(sun_dir.y, 0.0, 1.0));
+    let daylight  = water_daylight(sun_dir.y);
     let t         = uniforms.time;
 
-    let wave_n_raw = in.wave_normal.xyz;
-    let foam_val   = f16(in.wave_normal.w);
-
-    let perturb_blend = f16(clamp(1.0 - dist / 120.0, 0.0, 1.0));
-    var normal = wave_n_raw;
-    if perturb_blend > f16(0.005) {
-        let perturb = fbm_normal_perturb(in.world_pos.xz * 0.15, t) * f32(perturb_blend);
-        normal = normalize(wave_n_raw + vec3(perturb.x, 0.0, perturb.y));
-    }
+    // Side, bottom and covered faces keep their flat normal; only the open
+    // surface gets the wave normal, foam and ripples.
+    var normal   = in.face_normal;
+    var foam_val = f16(0.0);
+    var jacobian = 1.0;
+
+    if in.is_surface != 0u {
+        let w    = calculate_gerstner(in.world_pos, t);
+        normal   = w.normal;
+        foam_val = f16(w.foam);
+        jacobian = w.jacobian;
+
+        // Fewer small ripples at night so the surface reads as calm.
+        let ripple = mix(NIGHT_RIPPLE_FACTOR, 1.0, daylight);
+
+        let perturb_blend = f16(clamp(1.0 - dist / 120.0, 0.0, 1.0) * ripple);
+        if perturb_blend > f16(0.005) {
+            let perturb = fbm_normal_perturb(in.world_pos.xz * 0.15, t) * f32(perturb_blend);
+            normal = normalize(normal + vec3(perturb.x, 0.0, perturb.y));
+        }
 
-    let micro_blend = f16(clamp(1.0 - dist / 50.0, 0.0, 1.0));
-    if micro_blend > f16(0.005) {
-        let mp = 0.08 * sin(vec2(
-            in.world_pos.x * 11.3 + t * 2.1,
-            in.world_pos.z * 9.7  - t * 1.8
-        )) * f32(micro_blend);
-        normal = normalize(normal + vec3(mp.x, 0.0, mp.y));
+        let micro_blend = f16(clamp(1.0 - dist / 50.0, 0.0, 1.0) * ripple);
+        if micro_blend > f16(0.005) {
+            let mp = 0.08 * sin(vec2(
+                in.world_pos.x * 11.3 + t * 2.1,
+                in.world_pos.z * 9.7  - t * 1.8
+            )) * f32(micro_blend);
+            normal = normalize(normal + vec3(mp.x, 0.0, mp.y));
+        }
     }
 
     let cos_theta = f16(max(dot(view_dir, normal), 0.0));
-    let fresnel   = schlick_fresnel(cos_theta, f16(FRESNEL_R0));
+    let fresnel   = schlick_fresnel(cos_theta, f16(FRESNEL_R0))
+                  * f16(mix(NIGHT_REFLECTION_FACTOR, 1.0, daylight));
     let grazing   = f16(smoothstep(0.25, 0.98, f32(f16(1.0) - cos_theta)));
 
-    let depth_t     = f16(clamp(f32(f16(1.0) - cos_theta * f16(1.4)), 0.0, 1.0));
-    var water_color = mix(WATER_COLOR_SHALLOW, WATER_COLOR_DEEP, f32(depth_t));
+    // Seen from below the surface the scene behind is above the water, so
+    // the depth fade only applies from above.
+    var thickness = DEEP_WATER_DEPTH;
+    if uniforms.is_underwater < 0.5 {
+        thickness = water_thickness(in.clip_position.xy, in.world_pos, dist);
+    }
+    let shore_fade = smoothstep(0.0, SHORE_FADE_DEPTH, thickness);
+    let deep_t     = smoothstep(0.0, DEEP_WATER_DEPTH, thickness);
+
+    let angle_t     = f16(clamp(f32(f16(1.0) - cos_theta * f16(1.4)), 0.0, 1.0));
+    let depth_t     = max(f32(angle_t), deep_t);
+    var water_color = mix(WATER_COLOR_SHALLOW, WATER_COLOR_DEEP, depth_t);
 
     let wave_pulse = f16(clamp(in.world_pos.y * 1.4 + 0.5, 0.0, 1.0));
     water_color   *= f32(mix(f16(0.75), f16(1.18), wave_pulse));
 
     let shadow  = calculate_shadow(in.world_pos, sun_dir);
     let ambient = f16(mix(0.01, 0.35, f32(day)));
-    let night_factor = f32(1.0 - day);
-    let night_dark = vec3(0.0, 0.0, 0.01);
-    let night_mix = clamp(night_factor * 1.5, 0.0, 1.0);
-    water_color = mix(water_color, night_dark, night_mix);
+    water_color = apply_daylight(water_color, daylight);
 
     var refl_dir   = reflect(-view_dir, normal);
     refl_dir.y     = max(refl_dir.y, 0.001);
@@ -454,17 +540,19 @@ fn fs_water(in: VertexOutput) -> @location(0) vec4<f32> {
     let reflection_mix = f16(clamp(f32(fresnel * (f16(0.78) - grazing * f16(0.20))), 0.0, 0.80));
     water_color = mix(water_color, refl_color, f32(reflection_mix));
 
-    if sun_dir.y > 0.0 {
-        let jac_rough   = f16(clamp(1.0 - in.jacobian, 0.0, 1.0));
+    let glint = sun_glint_strength(sun_dir.y);
+    if glint > 0.0 {
+        let jac_rough   = f16(clamp(1.0 - jacobian, 0.0, 1.0));
         let roughness   = f32(mix(f16(WATER_ROUGHNESS_MIN), f16(WATER_ROUGHNESS_MAX), jac_rough)
                         * mix(f16(1.0), f16(0.82), f16(1.0) - day));
-        let spec        = ggx_spec_simple(normal, view_dir, sun_dir, roughness);
+        let spec        = compress_highlight(ggx_spec_simple(normal, view_dir, sun_dir, roughness));
         let spec_color  = mix(vec3(1.0, 0.97, 0.88), vec3(1.0, 0.84, 0.58), f32(f16(1.0) - day));
-        water_color    += spec_color * spec * f32(mix(f16(1.45), f16(1.8), day)) * shadow;
+        water_color    += spec_color * spec * glint * shadow;
 
         if uniforms.moon_intensity > 0.01 && day < f16(0.2) {
             let moon_dir  = normalize(uniforms.moon_position);
-            let spec_moon = ggx_spec_simple(normal, view_dir, moon_dir, WATER_ROUGHNESS_MIN * 0.5);
+            let spec_moon = compress_highlight(
+                ggx_spec_simple(normal, view_dir, moon_dir, WATER_ROUGHNESS_MIN * 0.5));
             water_color  += vec3(0.82, 0.88, 1.0) * spec_moon * uniforms.moon_intensity
                             * f32(f16(1.0) - day) * 0.6;
         }
@@ -484,7 +572,8 @@ fn fs_water(in: VertexOutput) -> @location(0) vec4<f32> {
                        f32(f16(0.85) * day + f16(0.15) * (f16(1.0) - day)));
     water_color  = mix(water_color, fog_col, f32(fog_t * fog_t));
 
-    let alpha = f16(WATER_OPACITY) + fresnel * f16(0.30);
+    let opacity = mix(WATER_OPACITY, DEEP_WATER_OPACITY, deep_t);
+    let alpha   = (opacity + f32(fresnel) * 0.30) * shore_fade;
 
-    return vec4(water_color, clamp(f32(alpha), 0.0, 1.0));
+    return vec4(water_color, clamp(alpha, 0.0, 1.0));
 }
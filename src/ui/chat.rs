@@ -0,0 +1,93 @@
+use std::time::{Duration, Instant};
+
+/// Maximum number of characters accepted in the chat input line.
+const MAX_INPUT_LEN: usize = 128;
+/// Number of past messages kept in the chat log.
+const MAX_LOG_LINES: usize = 8;
+/// How long a message stays on screen while the chat box is closed.
+const MESSAGE_LIFETIME: Duration = Duration::from_secs(8);
+
+/// Runtime state for the in-game chat box and its message log.
+///
+/// The chat box doubles as the command line: lines starting with `/` are
+/// parsed as commands by the game, everything else is treated as plain chat.
+/// Input events should be forwarded to [`ChatState::handle_char`] and
+/// [`ChatState::handle_backspace`] while [`ChatState::open`] is `true`.
+#[derive(Debug, Clone, Default)]
+pub struct ChatState {
+    /// `true` while the input line is visible and captures the keyboard.
+    pub open: bool,
+    /// Text typed so far on the input line.
+    pub input: String,
+    /// Recent messages (command feedback, chat), oldest first, with the
+    /// time they were posted so they can fade out.
+    pub log: Vec<(String, Instant)>,
+}
+
+impl ChatState {
+    /// Opens the input line, pre-filled with `prefix` (e.g. `"/"` when the
+    /// player pressed the slash key).
+    pub fn open_with(&mut self, prefix: &str) {
+        self.open = true;
+        self.input.clear();
+        self.input.push_str(prefix);
+    }
+
+    /// Closes the input line and discards any unsent text.
+    pub fn close(&mut self) {
+        self.open = false;
+        self.input.clear();
+    }
+
+    /// Appends `ch` to the input line.
+    ///
+    /// Control characters are ignored and the line is capped at
+    /// [`MAX_INPUT_LEN`] characters.
+    pub fn handle_char(&mut self, ch: char) {
+        if !ch.is_control() && self.input.chars().count() < MAX_INPUT_LEN {
+            self.input.push(ch);
+        }
+    }
+
+    /// Removes the last character from the input line.
+    pub fn handle_backspace(&mut self) {
+        self.input.pop();
+    }
+
+    /// Closes the chat box and returns the submitted line, or `None` if it
+    /// was blank.
+    pub fn submit(&mut self) -> Option<String> {
+        let line = self.input.trim().to_string();
+        self.close();
+        if line.is_empty() { None } else { Some(line) }
+    }
+
+    /// Appends a message to the chat log, dropping the oldest line once the
+    /// log is full.
+    pub fn push_message(&mut self, message: impl Into<String>) {
+        self.log.push((message.into(), Instant::now()));
+        if self.log.len() > MAX_LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+
+    /// Builds the text shown in the chat overlay.
+    ///
+    /// While the box is open the whole log is shown followed by the input
+    /// line; while closed only messages younger than [`MESSAGE_LIFETIME`]
+    /// are shown.  Returns an empty string when there is nothing to draw.
+    pub fn display_text(&self) -> String {
+        let mut lines: Vec<&str> = self
+            .log
+            .iter()
+            .filter(|(_, posted)| self.open || posted.elapsed() < MESSAGE_LIFETIME)
+            .map(|(message, _)| message.as_str())
+            .collect();
+        let prompt;
+        if self.open {
+            prompt = format!("> {}_", self.input);
+            lines.push(&prompt);
+        }
+        lines.join("\n")
+    }
+}
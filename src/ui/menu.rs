@@ -1,3 +1,12 @@
+use minerust::{MAX_SEA_LEVEL, SEA_LEVEL, WorldPreset};
+
+/// Number of key binding rows per column on the controls page.
+pub const BINDING_ROWS_PER_COLUMN: usize = 13;
+
+/// Height of one key binding row on the controls page, in pixels.  Also the
+/// line height of the binding text, so each line sits in its row.
+pub const BINDING_ROW_HEIGHT: f32 = 17.0;
+
 /// The top-level game mode, used to drive which systems are active each frame.
 ///
 /// Transitions flow: `Menu` → `Connecting` → `Playing`, and back to `Menu`
@@ -20,6 +29,44 @@ impl Default for GameState {
     }
 }
 
+/// Which page of the main menu is shown.
+///
+/// All pages share one [`MenuLayout`]: the singleplayer page puts the seed
+/// field in the server address slot and the sea level field in the username
+/// slot, adds the world preset button below them, and relabels the two
+/// action buttons.  The controls page replaces
+/// the fields and the quick card with the key binding rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MenuScreen {
+    /// Multiplayer connection form.
+    #[default]
+    Main,
+    /// New singleplayer world form with the seed and sea level fields and
+    /// the world preset.
+    Singleplayer,
+    /// Key binding list; clicking a row rebinds that action.
+    Controls,
+}
+
+impl MenuScreen {
+    /// Label of the left-hand action button.
+    pub fn primary_label(self) -> &'static str {
+        match self {
+            MenuScreen::Main => "CONNECT",
+            MenuScreen::Singleplayer => "CREATE WORLD",
+            MenuScreen::Controls => "RESET DEFAULTS",
+        }
+    }
+
+    /// Label of the right-hand action button.
+    pub fn secondary_label(self) -> &'static str {
+        match self {
+            MenuScreen::Main => "SINGLEPLAYER",
+            MenuScreen::Singleplayer | MenuScreen::Controls => "BACK",
+        }
+    }
+}
+
 /// Identifies which text input field in the main menu currently has focus.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MenuField {
@@ -27,6 +74,10 @@ pub enum MenuField {
     ServerAddress,
     /// The player username field.
     Username,
+    /// The world seed field on the singleplayer page.
+    Seed,
+    /// The sea level field on the singleplayer page.
+    SeaLevel,
     /// No field is focused; keyboard input is ignored.
     None,
 }
@@ -50,6 +101,16 @@ pub struct MenuState {
     pub server_address: String,
     /// Text entered in the username field. Capped at 16 characters.
     pub username: String,
+    /// Text entered in the world seed field. Capped at 20 characters;
+    /// empty means a random seed.  See [`MenuState::seed`].
+    pub seed: String,
+    /// Text entered in the sea level field. Capped at 3 characters; empty
+    /// means [`SEA_LEVEL`].  See [`MenuState::sea_level`].
+    pub sea_level: String,
+    /// Generation preset of the world created on the singleplayer page.
+    pub world_preset: WorldPreset,
+    /// The page currently shown.
+    pub screen: MenuScreen,
     /// The field that currently receives keyboard input.
     pub selected_field: MenuField,
     /// An error message to display to the player (e.g. connection refused).
@@ -57,20 +118,29 @@ pub struct MenuState {
     pub error_message: Option<String>,
     /// A transient status message (e.g. "Connecting…"). `None` when idle.
     pub status_message: Option<String>,
+    /// Whether the left button was pressed on the brightness slider and is
+    /// still held, so cursor movement keeps dragging the slider.
+    pub dragging_brightness: bool,
 }
 
 impl Default for MenuState {
     /// Returns a `MenuState` pre-filled with sensible defaults:
     /// - Server address: `"127.0.0.1:25565"`
     /// - Username: `"Player"`
-    /// - No focused field, no messages.
+    /// - Empty seed and sea level, default world preset, main page.
+    /// - No focused field, no messages, no slider drag.
     fn default() -> Self {
         Self {
             server_address: "127.0.0.1:25565".to_string(),
             username: "Player".to_string(),
+            seed: String::new(),
+            sea_level: String::new(),
+            world_preset: WorldPreset::Default,
+            screen: MenuScreen::Main,
             selected_field: MenuField::None,
             error_message: None,
             status_message: None,
+            dragging_brightness: false,
         }
     }
 }
@@ -90,6 +160,8 @@ impl MenuState {
     /// length limits are enforced:
     /// - Server address: 50 characters.
     /// - Username: 16 characters.
+    /// - Seed: 20 characters.
+    /// - Sea level: 3 characters.
     pub fn handle_char(&mut self, ch: char) {
         if !ch.is_ascii_control() {
             match self.selected_field {
@@ -103,6 +175,16 @@ impl MenuState {
                         self.username.push(ch);
                     }
                 }
+                MenuField::Seed => {
+                    if self.seed.len() < 20 {
+                        self.seed.push(ch);
+                    }
+                }
+                MenuField::SeaLevel => {
+                    if self.sea_level.len() < 3 {
+                        self.sea_level.push(ch);
+                    }
+                }
                 MenuField::None => {}
             }
         }
@@ -119,21 +201,105 @@ impl MenuState {
             MenuField::Username => {
                 self.username.pop();
             }
+            MenuField::Seed => {
+                self.seed.pop();
+            }
+            MenuField::SeaLevel => {
+                self.sea_level.pop();
+            }
             MenuField::None => {}
         }
     }
 
     /// Advances focus to the next field in tab order.
     ///
-    /// Cycles: `None` → `ServerAddress` → `Username` → `None`.
+    /// On the main page this cycles `None` → `ServerAddress` → `Username` →
+    /// `None`; on the singleplayer page it cycles `None` → `Seed` →
+    /// `SeaLevel` → `None`.
     pub fn next_field(&mut self) {
-        self.selected_field = match self.selected_field {
-            MenuField::None => MenuField::ServerAddress,
-            MenuField::ServerAddress => MenuField::Username,
-            MenuField::Username => MenuField::None,
+        self.selected_field = match (self.screen, self.selected_field) {
+            (MenuScreen::Singleplayer, MenuField::Seed) => MenuField::SeaLevel,
+            (MenuScreen::Singleplayer, MenuField::SeaLevel) => MenuField::None,
+            (MenuScreen::Singleplayer, _) => MenuField::Seed,
+            (MenuScreen::Controls, _) => MenuField::None,
+            (MenuScreen::Main, MenuField::None) => MenuField::ServerAddress,
+            (MenuScreen::Main, MenuField::ServerAddress) => MenuField::Username,
+            (MenuScreen::Main, _) => MenuField::None,
         };
     }
 
+    /// Switches to the singleplayer page with the seed field focused.
+    pub fn open_singleplayer(&mut self) {
+        self.screen = MenuScreen::Singleplayer;
+        self.selected_field = MenuField::Seed;
+        self.clear_error();
+    }
+
+    /// Returns from the singleplayer page to the main page.
+    pub fn close_singleplayer(&mut self) {
+        self.screen = MenuScreen::Main;
+        self.selected_field = MenuField::None;
+        self.clear_error();
+    }
+
+    /// Switches to the controls page.  No field is focused there.
+    pub fn open_controls(&mut self) {
+        self.screen = MenuScreen::Controls;
+        self.selected_field = MenuField::None;
+        self.status_message = None;
+        self.clear_error();
+    }
+
+    /// Returns from the controls page to the main page.
+    pub fn close_controls(&mut self) {
+        self.screen = MenuScreen::Main;
+        self.status_message = None;
+        self.clear_error();
+    }
+
+    /// Parses the seed field.
+    ///
+    /// # Returns
+    /// `None` when the field is empty (the caller picks a random seed).  A
+    /// number is used as-is, with negative values wrapping into the `u32`
+    /// range; any other text is hashed (32-bit FNV-1a), so word seeds always
+    /// give the same world.
+    pub fn seed(&self) -> Option<u32> {
+        let text = self.seed.trim();
+        if text.is_empty() {
+            return None;
+        }
+        if let Ok(n) = text.parse::<i64>() {
+            return Some(n as u32);
+        }
+        let hash = text.bytes().fold(0x811c_9dc5u32, |hash, byte| {
+            (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+        });
+        Some(hash)
+    }
+
+    /// Parses the sea level field.
+    ///
+    /// # Returns
+    /// [`SEA_LEVEL`] when the field is empty, otherwise the number entered.
+    ///
+    /// # Errors
+    /// Returns a message for the status pill if the text is not a whole
+    /// number between 1 and [`MAX_SEA_LEVEL`].
+    pub fn sea_level(&self) -> Result<i32, String> {
+        let text = self.sea_level.trim();
+        if text.is_empty() {
+            return Ok(SEA_LEVEL);
+        }
+        match text.parse::<i32>() {
+            Ok(level) if (1..=MAX_SEA_LEVEL).contains(&level) => Ok(level),
+            _ => Err(format!(
+                "Sea level must be a number from 1 to {}",
+                MAX_SEA_LEVEL
+            )),
+        }
+    }
+
     /// Directly sets keyboard focus to `field`.
     ///
     /// Pass [`MenuField::None`] to remove focus from all fields.
@@ -184,6 +350,31 @@ pub enum MenuHit {
     Connect,
     /// The "Singleplayer" button was clicked.
     Singleplayer,
+    /// The seed field on the singleplayer page was clicked.
+    Seed,
+    /// The sea level field on the singleplayer page was clicked.
+    SeaLevel,
+    /// The world preset button on the singleplayer page was clicked.
+    WorldPreset,
+    /// The "Create world" button on the singleplayer page was clicked.
+    CreateWorld,
+    /// The "Back" button on the singleplayer page was clicked.
+    Back,
+    /// The texture filtering toggle in the quick card was clicked.
+    TextureFiltering,
+    /// The graphics quality toggle in the quick card was clicked.
+    GraphicsQuality,
+    /// The VSync toggle in the quick card was clicked.
+    VSync,
+    /// The brightness slider in the quick card was clicked.
+    Brightness,
+    /// The "Controls" button in the header of the main page was clicked.
+    Controls,
+    /// A key binding row on the controls page was clicked; holds the row
+    /// index, counted down the left column and then the right one.
+    Binding(usize),
+    /// The "Reset defaults" button on the controls page was clicked.
+    ResetBindings,
 }
 
 /// An axis-aligned rectangle in screen-space pixels.
@@ -224,12 +415,19 @@ impl Rect {
 /// │ server_label     │              │
 /// │ server_field     │  quick_card  │
 /// │ username_label   │              │
-/// │ username_field   │              │
+/// │ username_field   │ [filter]     │
+/// │                  │ [brightness] │
 /// ├──────────────────┴──────────────┤
 /// │ status_pill                     │
 /// │ [connect_button] [singleplayer] │
 /// └─────────────────────────────────┘
 /// ```
+///
+/// On the [`MenuScreen::Singleplayer`] page `server_field` holds the seed,
+/// `username_field` the sea level, and the buttons become "Create world" and
+/// "Back".  On the [`MenuScreen::Controls`] page `bindings_area` takes the
+/// place of the fields and the quick card (see [`MenuLayout::binding_row`]),
+/// and the buttons become "Reset defaults" and "Back".
 #[derive(Debug, Clone, Copy)]
 pub struct MenuLayout {
     /// Outer panel rectangle; the visual backdrop for all other elements.
@@ -244,14 +442,29 @@ pub struct MenuLayout {
     pub username_label: Rect,
     /// Username text input field.
     pub username_field: Rect,
+    /// World preset toggle below `username_field` (singleplayer page only).
+    pub preset_button: Rect,
     /// Right-hand card area (quick-connect history or tips).
     pub quick_card: Rect,
+    /// VSync toggle above `quality_button`.
+    pub vsync_button: Rect,
+    /// Graphics quality toggle above `filter_button`.
+    pub quality_button: Rect,
+    /// Texture filtering toggle above `brightness_slider`.
+    pub filter_button: Rect,
+    /// Minimum ambient light slider along the bottom of `quick_card`.
+    pub brightness_slider: Rect,
     /// "Connect" action button.
     pub connect_button: Rect,
     /// "Singleplayer" action button.
     pub singleplayer_button: Rect,
     /// Pill-shaped status / error message bar above the buttons.
     pub status_pill: Rect,
+    /// "Controls" button in the top-right corner of `header`.
+    pub controls_button: Rect,
+    /// Area between the header and `status_pill` holding the key binding
+    /// rows of the controls page.
+    pub bindings_area: Rect,
 }
 
 impl MenuLayout {
@@ -270,10 +483,11 @@ impl MenuLayout {
         let w = width as f32;
         let h = height as f32;
 
-        // Panel: centred, clamped to [560, 820] wide and [520, 640] tall,
-        // with a minimum 24 px margin on each side.
+        // Panel: centred, clamped to [560, 820] wide and [626, 686] tall,
+        // with a minimum 24 px margin on each side.  The minimum height
+        // keeps the quick card clear of the status pill.
         let panel_w = (w * 0.62).clamp(560.0, 820.0).min(w - 48.0);
-        let panel_h = (h * 0.76).clamp(520.0, 640.0).min(h - 48.0);
+        let panel_h = (h * 0.76).clamp(626.0, 686.0).min(h - 48.0);
         let panel_x = (w - panel_w) * 0.5;
         let panel_y = (h - panel_h) * 0.5;
 
@@ -301,7 +515,8 @@ impl MenuLayout {
         let button_gap = 14.0;
         let button_w = ((panel_w - 80.0) - button_gap) * 0.5;
         let button_x = panel_x + 40.0;
-        let quick_card_h = 230.0;
+        let quick_card_h = 322.0;
+        let status_y = panel_y + panel_h - 168.0;
 
         Self {
             panel: Rect {
@@ -340,12 +555,42 @@ impl MenuLayout {
                 w: field_w,
                 h: field_h,
             },
+            preset_button: Rect {
+                x: content_left,
+                y: username_field_y + field_h + 18.0,
+                w: field_w,
+                h: 40.0,
+            },
             quick_card: Rect {
                 x: content_left + field_w + gap,
                 y: content_top + 14.0,
                 w: right_card_w,
                 h: quick_card_h,
             },
+            vsync_button: Rect {
+                x: content_left + field_w + gap + 16.0,
+                y: content_top + 14.0 + quick_card_h - 192.0,
+                w: right_card_w - 32.0,
+                h: 40.0,
+            },
+            quality_button: Rect {
+                x: content_left + field_w + gap + 16.0,
+                y: content_top + 14.0 + quick_card_h - 146.0,
+                w: right_card_w - 32.0,
+                h: 40.0,
+            },
+            filter_button: Rect {
+                x: content_left + field_w + gap + 16.0,
+                y: content_top + 14.0 + quick_card_h - 100.0,
+                w: right_card_w - 32.0,
+                h: 40.0,
+            },
+            brightness_slider: Rect {
+                x: content_left + field_w + gap + 16.0,
+                y: content_top + 14.0 + quick_card_h - 54.0,
+                w: right_card_w - 32.0,
+                h: 40.0,
+            },
             connect_button: Rect {
                 x: button_x,
                 y: button_y,
@@ -360,36 +605,127 @@ impl MenuLayout {
             },
             status_pill: Rect {
                 x: content_left,
-                y: panel_y + panel_h - 168.0,
+                y: status_y,
                 w: panel_w - 80.0,
                 h: 36.0,
             },
+            // Kept above the subtitle line of the header.
+            controls_button: Rect {
+                x: panel_x + panel_w - 24.0 - 150.0,
+                y: panel_y + 26.0,
+                w: 150.0,
+                h: 32.0,
+            },
+            bindings_area: Rect {
+                x: content_left,
+                y: content_top + 14.0,
+                w: panel_w - 80.0,
+                h: status_y - 14.0 - (content_top + 14.0),
+            },
+        }
+    }
+
+    /// Bounds of the key binding row at `index` on the controls page.
+    ///
+    /// Rows are [`BINDING_ROW_HEIGHT`] tall and fill the left column of
+    /// `bindings_area` first, [`BINDING_ROWS_PER_COLUMN`] to a column.
+    /// Both columns fit at the panel's minimum height.
+    pub fn binding_row(&self, index: usize) -> Rect {
+        let gap = 18.0;
+        let column_w = (self.bindings_area.w - gap) * 0.5;
+        let column = (index / BINDING_ROWS_PER_COLUMN) as f32;
+        let row = (index % BINDING_ROWS_PER_COLUMN) as f32;
+        Rect {
+            x: self.bindings_area.x + column * (column_w + gap),
+            y: self.bindings_area.y + row * BINDING_ROW_HEIGHT,
+            w: column_w,
+            h: BINDING_ROW_HEIGHT,
         }
     }
 
     /// Tests whether the point `(px, py)` intersects any interactive element
-    /// and returns the corresponding [`MenuHit`].
+    /// of `screen` and returns the corresponding [`MenuHit`].
     ///
     /// Elements are tested in priority order: input fields before buttons.
     /// Returns `None` if the point does not fall inside any interactive region.
     ///
     /// # Arguments
     ///
+    /// * `screen` - The menu page currently shown.
     /// * `px` - Cursor X position in pixels from the left edge of the window.
     /// * `py` - Cursor Y position in pixels from the top edge of the window.
-    pub fn hit_test(&self, px: f32, py: f32) -> Option<MenuHit> {
+    pub fn hit_test(&self, screen: MenuScreen, px: f32, py: f32) -> Option<MenuHit> {
+        if screen == MenuScreen::Controls {
+            return self.hit_test_controls(px, py);
+        }
+        if screen == MenuScreen::Main && self.controls_button.contains(px, py) {
+            return Some(MenuHit::Controls);
+        }
+        let singleplayer = screen == MenuScreen::Singleplayer;
         if self.server_field.contains(px, py) {
-            return Some(MenuHit::ServerAddress);
+            return Some(if singleplayer {
+                MenuHit::Seed
+            } else {
+                MenuHit::ServerAddress
+            });
         }
         if self.username_field.contains(px, py) {
-            return Some(MenuHit::Username);
+            return Some(if singleplayer {
+                MenuHit::SeaLevel
+            } else {
+                MenuHit::Username
+            });
+        }
+        if singleplayer && self.preset_button.contains(px, py) {
+            return Some(MenuHit::WorldPreset);
         }
         if self.connect_button.contains(px, py) {
-            return Some(MenuHit::Connect);
+            return Some(if singleplayer {
+                MenuHit::CreateWorld
+            } else {
+                MenuHit::Connect
+            });
         }
         if self.singleplayer_button.contains(px, py) {
-            return Some(MenuHit::Singleplayer);
+            return Some(if singleplayer {
+                MenuHit::Back
+            } else {
+                MenuHit::Singleplayer
+            });
+        }
+        if self.filter_button.contains(px, py) {
+            return Some(MenuHit::TextureFiltering);
+        }
+        if self.quality_button.contains(px, py) {
+            return Some(MenuHit::GraphicsQuality);
+        }
+        if self.vsync_button.contains(px, py) {
+            return Some(MenuHit::VSync);
+        }
+        if self.brightness_slider.contains(px, py) {
+            return Some(MenuHit::Brightness);
         }
         None
     }
+
+    /// Slider position under cursor X coordinate `px`, from `0.0` at the
+    /// left edge of `brightness_slider` to `1.0` at its right edge.  Points
+    /// beyond either edge clamp to it, so a drag can run past the slider.
+    pub fn brightness_slider_value(&self, px: f32) -> f32 {
+        ((px - self.brightness_slider.x) / self.brightness_slider.w).clamp(0.0, 1.0)
+    }
+
+    /// [`MenuLayout::hit_test`] for the controls page, which has only the
+    /// binding rows and the two action buttons.
+    fn hit_test_controls(&self, px: f32, py: f32) -> Option<MenuHit> {
+        if self.connect_button.contains(px, py) {
+            return Some(MenuHit::ResetBindings);
+        }
+        if self.singleplayer_button.contains(px, py) {
+            return Some(MenuHit::Back);
+        }
+        (0..BINDING_ROWS_PER_COLUMN * 2)
+            .find(|&index| self.binding_row(index).contains(px, py))
+            .map(MenuHit::Binding)
+    }
 }
@@ -0,0 +1,324 @@
+use minerust::{CHUNK_SIZE, SEA_LEVEL, Vertex, World};
+use wgpu::util::DeviceExt;
+
+/// Chunks shown on each side of the player's chunk.
+const MINIMAP_RADIUS: i32 = 5;
+
+/// Chunk slots along each side of the minimap texture.  One more than the
+/// `2 * MINIMAP_RADIUS + 1` chunks the visible area can touch, so no two of
+/// them ever share a slot.
+const SLOTS: i32 = 2 * MINIMAP_RADIUS + 2;
+
+/// Side of the minimap texture in texels (one texel per block column).
+/// Must match `MINIMAP_TEXTURE_SIZE` in `ui.wgsl`, whose `MINIMAP_SPAN`
+/// (block columns shown across the map) is `2 * MINIMAP_RADIUS * CHUNK_SIZE`.
+const MINIMAP_TEXTURE_SIZE: u32 = (SLOTS * CHUNK_SIZE) as u32;
+
+/// Frames between two refreshes of the minimap texture.
+const REFRESH_INTERVAL: u32 = 10;
+
+/// Chunks re-sampled per refresh at most; the rest wait for the next one.
+const MAX_CHUNKS_PER_REFRESH: usize = 8;
+
+/// Side of the minimap on screen and its distance from the window edges,
+/// in pixels.  The top margin leaves room for the coordinate overlay.
+const SCREEN_SIZE: f32 = 176.0;
+const SCREEN_MARGIN: f32 = 16.0;
+const SCREEN_TOP: f32 = 48.0;
+
+/// Index ranges of the three parts of the minimap quads.
+pub const FRAME_INDICES: std::ops::Range<u32> = 0..6;
+pub const MAP_INDICES: std::ops::Range<u32> = 6..12;
+pub const MARKER_INDICES: std::ops::Range<u32> = 12..15;
+
+/// The chunk sampled into a slot of the minimap texture.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Slot {
+    chunk: (i32, i32),
+    /// [`minerust::Chunk::content_version`] when it was sampled.
+    version: u32,
+}
+
+/// A top-down map of the loaded chunks around the player.
+///
+/// Each block column is one texel colored by the top color of its surface
+/// block, shaded by height.  The texture wraps around: column `(x, z)` is
+/// stored at texel `(x, z) mod MINIMAP_TEXTURE_SIZE`, so the player moving
+/// only needs the newly visible chunks sampled, and the shader centres the
+/// map on the camera by offsetting its UVs.
+///
+/// Every [`REFRESH_INTERVAL`] frames [`Minimap::update`] samples the chunks
+/// that were loaded or changed since they were last sampled and uploads just
+/// their slots.
+pub struct Minimap {
+    /// Toggled with the minimap key (M by default).
+    pub visible: bool,
+    texture: wgpu::Texture,
+    /// Layout of `bind_group`, used by the minimap pipeline at group 1.
+    pub bind_group_layout: wgpu::BindGroupLayout,
+    pub bind_group: wgpu::BindGroup,
+    /// Frame, map and facing marker, rewritten every frame by
+    /// [`Minimap::write_quads`].
+    pub vertex_buffer: wgpu::Buffer,
+    pub index_buffer: wgpu::Buffer,
+    /// CPU copy of the texture, RGBA8.
+    pixels: Vec<u8>,
+    slots: Vec<Option<Slot>>,
+    frames_until_refresh: u32,
+}
+
+impl Minimap {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Minimap Texture"),
+            size: wgpu::Extent3d {
+                width: MINIMAP_TEXTURE_SIZE,
+                height: MINIMAP_TEXTURE_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            // Same linear colors as the vertex-colored UI quads.
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        // Repeat addressing is what makes the wrapped-around texture work.
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Minimap Sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("minimap_bind_group_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Minimap Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Minimap VB"),
+            size: (11 * size_of::<Vertex>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        // Frame quad, map quad, marker triangle.
+        let indices: [u32; 15] = [0, 1, 2, 0, 2, 3, 4, 5, 6, 4, 6, 7, 8, 9, 10];
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Minimap IB"),
+            contents: bytemuck::cast_slice(&indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        Self {
+            visible: false,
+            texture,
+            bind_group_layout,
+            bind_group,
+            vertex_buffer,
+            index_buffer,
+            pixels: vec![0; (MINIMAP_TEXTURE_SIZE * MINIMAP_TEXTURE_SIZE * 4) as usize],
+            slots: vec![None; (SLOTS * SLOTS) as usize],
+            frames_until_refresh: 0,
+        }
+    }
+
+    /// Samples chunks that were loaded, unloaded or changed around
+    /// `(player_x, player_z)` into the texture.  Call once per frame; the
+    /// work only happens every [`REFRESH_INTERVAL`] frames.
+    pub fn update(&mut self, queue: &wgpu::Queue, world: &World, player_x: f32, player_z: f32) {
+        if self.frames_until_refresh > 0 {
+            self.frames_until_refresh -= 1;
+            return;
+        }
+        self.frames_until_refresh = REFRESH_INTERVAL;
+
+        let center_x = (player_x.floor() as i32).div_euclid(CHUNK_SIZE);
+        let center_z = (player_z.floor() as i32).div_euclid(CHUNK_SIZE);
+        let mut budget = MAX_CHUNKS_PER_REFRESH;
+        for cx in center_x - MINIMAP_RADIUS..=center_x + MINIMAP_RADIUS {
+            for cz in center_z - MINIMAP_RADIUS..=center_z + MINIMAP_RADIUS {
+                let slot_index = (cz.rem_euclid(SLOTS) * SLOTS + cx.rem_euclid(SLOTS)) as usize;
+                let wanted = world.chunks.get(&(cx, cz)).map(|chunk| Slot {
+                    chunk: (cx, cz),
+                    version: chunk.content_version(),
+                });
+                if self.slots[slot_index] == wanted {
+                    continue;
+                }
+                // Clearing an unloaded chunk is cheap, so only sampling
+                // counts against the budget.
+                if wanted.is_some() {
+                    if budget == 0 {
+                        continue;
+                    }
+                    budget -= 1;
+                }
+                self.sample_chunk(world, cx, cz, wanted.is_some());
+                self.slots[slot_index] = wanted;
+                self.upload_slot(queue, cx, cz);
+            }
+        }
+    }
+
+    /// Writes the surface colors of chunk `(cx, cz)` into its slot, or
+    /// clears the slot when the chunk is not `loaded`.
+    fn sample_chunk(&mut self, world: &World, cx: i32, cz: i32, loaded: bool) {
+        let chunk = world.chunks.get(&(cx, cz)).filter(|_| loaded);
+        for lz in 0..CHUNK_SIZE {
+            for lx in 0..CHUNK_SIZE {
+                let texel = ((cz * CHUNK_SIZE + lz).rem_euclid(MINIMAP_TEXTURE_SIZE as i32)
+                    * MINIMAP_TEXTURE_SIZE as i32
+                    + (cx * CHUNK_SIZE + lx).rem_euclid(MINIMAP_TEXTURE_SIZE as i32))
+                    as usize;
+                let rgba = match chunk.and_then(|chunk| chunk.surface_block(lx, lz)) {
+                    Some((y, block)) => {
+                        // Brighter above sea level, darker below, so hills
+                        // and valleys stand out from flat ground.
+                        let shade = 1.0 + ((y - SEA_LEVEL) as f32 / 96.0).clamp(-0.3, 0.3);
+                        let [r, g, b] = block.top_color();
+                        [
+                            ((r * shade).min(1.0) * 255.0) as u8,
+                            ((g * shade).min(1.0) * 255.0) as u8,
+                            ((b * shade).min(1.0) * 255.0) as u8,
+                            255,
+                        ]
+                    }
+                    None => [0; 4],
+                };
+                self.pixels[texel * 4..texel * 4 + 4].copy_from_slice(&rgba);
+            }
+        }
+    }
+
+    /// Uploads the slot of chunk `(cx, cz)` from `pixels`.
+    fn upload_slot(&self, queue: &wgpu::Queue, cx: i32, cz: i32) {
+        let x = (cx.rem_euclid(SLOTS) * CHUNK_SIZE) as u32;
+        let z = (cz.rem_euclid(SLOTS) * CHUNK_SIZE) as u32;
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y: z, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &self.pixels,
+            wgpu::TexelCopyBufferLayout {
+                offset: ((z * MINIMAP_TEXTURE_SIZE + x) * 4) as u64,
+                bytes_per_row: Some(MINIMAP_TEXTURE_SIZE * 4),
+                rows_per_image: Some(MINIMAP_TEXTURE_SIZE),
+            },
+            wgpu::Extent3d {
+                width: CHUNK_SIZE as u32,
+                height: CHUNK_SIZE as u32,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    /// Writes the frame, map and facing marker for a `width × height`
+    /// window and a player facing `yaw`.
+    ///
+    /// The map sits in the top-right corner below the coordinate overlay,
+    /// north up; the marker at its centre points along the camera's
+    /// horizontal forward direction.
+    pub fn write_quads(&self, queue: &wgpu::Queue, width: f32, height: f32, yaw: f32) {
+        let to_ndc = |x: f32, y: f32| [x / width * 2.0 - 1.0, 1.0 - y / height * 2.0, 0.0];
+        let normal = Vertex::pack_normal([0.0, 0.0, 1.0]);
+        let vertex = |x: f32, y: f32, color: [f32; 4], corner: u8| Vertex {
+            position: to_ndc(x, y),
+            packed: Vertex::pack_ui(normal, color, 0, corner),
+        };
+        // BL, BR, TR, TL in pixel space (y down).
+        let quad = |x0: f32, y0: f32, x1: f32, y1: f32, color: [f32; 4]| {
+            [
+                vertex(x0, y1, color, 0),
+                vertex(x1, y1, color, 1),
+                vertex(x1, y0, color, 2),
+                vertex(x0, y0, color, 3),
+            ]
+        };
+
+        let x0 = width - SCREEN_MARGIN - SCREEN_SIZE;
+        let y0 = SCREEN_TOP;
+        let (x1, y1) = (x0 + SCREEN_SIZE, y0 + SCREEN_SIZE);
+        let border = 3.0;
+        let frame = quad(
+            x0 - border,
+            y0 - border,
+            x1 + border,
+            y1 + border,
+            [0.08, 0.1, 0.13, 0.8],
+        );
+        let map = quad(x0, y0, x1, y1, [1.0, 1.0, 1.0, 0.9]);
+
+        // Forward is (cos yaw, sin yaw) in world XZ; with north (-Z) up
+        // and Y down in pixel space that is the same (cos, sin) on screen.
+        let (center_x, center_y) = ((x0 + x1) * 0.5, (y0 + y1) * 0.5);
+        let (dir_x, dir_y) = (yaw.cos(), yaw.sin());
+        let (side_x, side_y) = (-dir_y, dir_x);
+        let (length, half_width) = (9.0, 5.0);
+        let marker_color = [1.0, 0.25, 0.2, 1.0];
+        let marker = [
+            vertex(
+                center_x + dir_x * length,
+                center_y + dir_y * length,
+                marker_color,
+                0,
+            ),
+            vertex(
+                center_x - dir_x * length * 0.5 + side_x * half_width,
+                center_y - dir_y * length * 0.5 + side_y * half_width,
+                marker_color,
+                1,
+            ),
+            vertex(
+                center_x - dir_x * length * 0.5 - side_x * half_width,
+                center_y - dir_y * length * 0.5 - side_y * half_width,
+                marker_color,
+                2,
+            ),
+        ];
+
+        let mut vertices = Vec::with_capacity(11);
+        vertices.extend(frame);
+        vertices.extend(map);
+        vertices.extend(marker);
+        queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+    }
+}
@@ -1,3 +1,5 @@
+pub mod chat;
 pub mod menu;
+pub mod minimap;
 mod toolbar;
 pub mod ui;
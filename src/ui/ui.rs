@@ -1,7 +1,9 @@
-use minerust::{BlockType, Vertex};
+use minerust::{Biome, BlockType, CHUNK_SIZE, Facing, Vertex};
 use wgpu::util::DeviceExt;
 
-/// The fixed set of block types assigned to hotbar slots 0–8, left to right.
+/// The block types assigned to hotbar slots 0–8, left to right, when the
+/// game starts.  Picking a block with the middle mouse button replaces the
+/// block of the selected slot (see [`pick_block_slot`]).
 ///
 /// The index of a block in this array corresponds directly to its hotbar slot
 /// number. Use [`block_type_to_index`] to perform the reverse lookup.
@@ -13,8 +15,8 @@ pub const HOTBAR_SLOTS: [BlockType; 9] = [
     BlockType::Wood,
     BlockType::Leaves,
     BlockType::Gravel,
-    BlockType::Clay,
-    BlockType::Ice,
+    BlockType::StoneSlab,
+    BlockType::StoneStairs,
 ];
 
 /// Returns the hotbar slot index of `block` as an `f32`, or `None` if the
@@ -29,15 +31,54 @@ pub fn block_type_to_index(block: BlockType) -> Option<f32> {
         .map(|i| i as f32)
 }
 
+/// Puts a picked `block` into the hotbar `slots` and returns the slot to
+/// select.
+///
+/// A block already in the hotbar selects its slot so it is never held
+/// twice; any other block replaces the block of the `selected` slot.
+pub fn pick_block_slot(
+    slots: &mut [BlockType; HOTBAR_SLOTS.len()],
+    selected: usize,
+    block: BlockType,
+) -> usize {
+    if let Some(slot) = slots.iter().position(|&b| b == block) {
+        return slot;
+    }
+    slots[selected] = block;
+    selected
+}
+
+/// Width of one hotbar slot in NDC units.
+const HOTBAR_SLOT_SIZE: f32 = 0.08;
+
+/// Returns the NDC rectangle `[x0, y0, x1, y1]` covered by hotbar slot
+/// `slot`, with `aspect` as in [`build_hotbar`].
+///
+/// Shared with the renderer, which places the survival block counts in the
+/// slots' corners.
+pub fn hotbar_slot_rect(slot: usize, aspect: f32) -> [f32; 4] {
+    let slot_count = HOTBAR_SLOTS.len() as f32;
+    let slot_h = HOTBAR_SLOT_SIZE * aspect;
+    let gap = 0.004_f32;
+    let total_w = slot_count * HOTBAR_SLOT_SIZE + (slot_count - 1.0) * gap;
+    let start_x = -total_w * 0.5;
+    let bottom_y = -0.95_f32;
+
+    let x0 = start_x + slot as f32 * (HOTBAR_SLOT_SIZE + gap);
+    [x0, bottom_y, x0 + HOTBAR_SLOT_SIZE, bottom_y + slot_h]
+}
+
 /// Builds GPU vertex and index buffers for the HUD hotbar.
 ///
 /// Generates a row of nine block-preview slots centred horizontally at the
 /// bottom of the screen. Each slot is made up of three layered quads:
 ///
-/// 1. **Border quad** — white for the selected slot, dark gray otherwise.
+/// 1. **Border quad** — white for the selected slot (red while `flash` is
+///    set), dark gray otherwise.
 /// 2. **Background quad** — slightly lighter gray for the selected slot.
 /// 3. **Block-color quad** — filled with the block's representative color,
-///    inset by a fixed padding fraction of the slot size.
+///    inset by a fixed padding fraction of the slot size.  Dimmed for slots
+///    whose entry in `counts` is zero.
 ///
 /// All coordinates are in normalized device coordinates (NDC): X and Y both
 /// range from `-1.0` (left / bottom) to `+1.0` (right / top). The `aspect`
@@ -47,9 +88,14 @@ pub fn block_type_to_index(block: BlockType) -> Option<f32> {
 /// # Arguments
 ///
 /// * `device`        - wgpu device used to allocate the GPU buffers.
+/// * `slots`         - Block shown in each slot, left to right.
 /// * `selected_slot` - Index (0–8) of the currently active hotbar slot.
 /// * `aspect`        - Viewport height divided by width (`h / w`). Multiplied
-///                     into all Y-axis sizes to maintain square slots.
+///   into all Y-axis sizes to maintain square slots.
+/// * `counts`        - Blocks carried per slot in survival mode; `None` in
+///   creative mode, where every slot is available.
+/// * `flash`         - Marks the selected slot as empty after a failed
+///   placement.
 ///
 /// # Returns
 ///
@@ -57,17 +103,13 @@ pub fn block_type_to_index(block: BlockType) -> Option<f32> {
 /// and drawn with `draw_indexed`.
 pub fn build_hotbar(
     device: &wgpu::Device,
+    slots: &[BlockType; HOTBAR_SLOTS.len()],
     selected_slot: usize,
     aspect: f32,
+    counts: Option<&[u32; HOTBAR_SLOTS.len()]>,
+    flash: bool,
 ) -> (wgpu::Buffer, wgpu::Buffer, u32) {
-    let slot_count = HOTBAR_SLOTS.len() as f32;
-    let slot_size = 0.08_f32;
-    let slot_h = slot_size * aspect;
-    let gap = 0.004_f32;
-    let total_w = slot_count * slot_size + (slot_count - 1.0) * gap;
-    let start_x = -total_w * 0.5;
-    let bottom_y = -0.95_f32;
-
+    let slot_size = HOTBAR_SLOT_SIZE;
     let normal = Vertex::pack_normal([0.0, 0.0, 1.0]);
 
     let mut vertices: Vec<Vertex> = Vec::new();
@@ -87,14 +129,13 @@ pub fn build_hotbar(
         indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
     };
 
-    for i in 0..HOTBAR_SLOTS.len() {
-        let x0 = start_x + i as f32 * (slot_size + gap);
-        let x1 = x0 + slot_size;
-        let y0 = bottom_y;
-        let y1 = y0 + slot_h;
+    for (i, &block) in slots.iter().enumerate() {
+        let [x0, y0, x1, y1] = hotbar_slot_rect(i, aspect);
 
         // Layer 1: border — bright for the selected slot, dim otherwise.
-        let border_color = if i == selected_slot {
+        let border_color = if i == selected_slot && flash {
+            [0.9, 0.15, 0.15]
+        } else if i == selected_slot {
             [1.0, 1.0, 1.0]
         } else {
             [0.4, 0.4, 0.4]
@@ -117,9 +158,13 @@ pub fn build_hotbar(
         );
 
         // Layer 3: block color swatch — inset by 18% of slot size on all sides.
-        let block = HOTBAR_SLOTS[i];
         let [r, g, b] = block.color();
-        let block_color = [r, g, b];
+        let dim = if counts.is_some_and(|c| c[i] == 0) {
+            0.3
+        } else {
+            1.0
+        };
+        let block_color = [r * dim, g * dim, b * dim];
         let pad = slot_size * 0.18;
         let pad_h = pad * aspect;
         add_quad(x0 + pad, y0 + pad_h, x1 - pad, y1 - pad_h, block_color);
@@ -138,201 +183,76 @@ pub fn build_hotbar(
     (vb, ib, indices.len() as u32)
 }
 
-/// Rebuilds the coordinate overlay GPU buffers when the camera position
-/// changes, returning `None` if the integer-truncated position is unchanged.
-///
-/// Renders the player's current world coordinates as a white seven-segment
-/// style text label (e.g. `"X:128 Y:64 Z:-32"`) in the top-right corner of
-/// the screen. Characters are drawn as a series of thick line segments
-/// (quads) using [`get_char_segments`] for the segment layout.
-///
-/// # Change detection
-///
-/// Coordinates are compared at integer granularity. `last_coords_position` is
-/// updated in-place when a change is detected and left untouched otherwise,
-/// allowing the caller to reuse the previous buffers without re-uploading.
+/// Formats the coordinate readout shown in the top-right corner.
 ///
-/// # Arguments
-///
-/// * `device`               - wgpu device used to allocate new GPU buffers.
-/// * `camera_pos`           - Current camera position in world space.
-/// * `last_coords_position` - Mutable cache of the last rendered `(x, y, z)`
-///                            as integers. Updated on every rebuild.
-///
-/// # Returns
-///
-/// `Some((vertex_buffer, index_buffer, index_count))` when the buffers were
-/// rebuilt, or `None` when the position has not changed since the last call.
-pub fn update_coords_ui(
-    device: &wgpu::Device,
-    camera_pos: glam::Vec3,
-    last_coords_position: &mut (i32, i32, i32),
-) -> Option<(wgpu::Buffer, wgpu::Buffer, u32)> {
-    let x = camera_pos.x;
-    let y = camera_pos.y;
-    let z = camera_pos.z;
-
-    let current_pos = (x as i32, y as i32, z as i32);
-    if current_pos == *last_coords_position {
-        return None;
-    }
-    *last_coords_position = current_pos;
-
-    let text = format!("X:{:.0} Y:{:.0} Z:{:.0}", x, y, z);
-
-    let mut vertices = Vec::with_capacity(500);
-    let mut indices = Vec::with_capacity(250);
-
-    // Visual metrics for the stroke-based font.
-    let char_width = 0.018;
-    let char_height = 0.032;
-    let line_thickness = 0.004;
-    let char_spacing = char_width * 0.6; // advance for a space character
-    let gap_spacing = char_width + 0.005; // advance for a normal character
+/// Lists the block the camera is in, its chunk column, the cardinal
+/// direction the camera faces (see [`Facing::from_yaw`]) and `biome`, e.g.
+/// `"X: 128  Y: 64  Z: -32   Chunk: 8, -2   Facing: North   Biome: Plains"`.
+pub fn coords_text(position: glam::Vec3, yaw: f32, biome: Biome) -> String {
+    let [x, y, z] = position.floor().as_ivec3().to_array();
+    format!(
+        "X: {}  Y: {}  Z: {}   Chunk: {}, {}   Facing: {:?}   Biome: {:?}",
+        x,
+        y,
+        z,
+        x.div_euclid(CHUNK_SIZE),
+        z.div_euclid(CHUNK_SIZE),
+        Facing::from_yaw(yaw),
+        biome,
+    )
+}
 
-    // Pre-compute total text width so the label can be right-aligned.
-    let mut total_width = 0.0;
-    for ch in text.chars() {
-        if ch == ' ' {
-            total_width += char_spacing;
-        } else {
-            total_width += gap_spacing;
-        }
-    }
+/// Half-size, in NDC units, of the inner rectangle of the damage vignette
+/// where the tint has faded to its centre opacity.
+const VIGNETTE_INNER: [f32; 2] = [0.55, 0.45];
 
-    // Anchor the label 0.02 NDC units from the right edge, near the top.
-    let start_x = 0.98 - total_width;
-    let start_y = 0.95;
+/// Indices for the vertices of [`build_damage_vignette`]: a trapezoid along
+/// each screen edge, then the quad in the middle.
+pub const DAMAGE_VIGNETTE_INDICES: [u32; 30] = [
+    0, 1, 5, 0, 5, 4, // bottom
+    1, 2, 6, 1, 6, 5, // right
+    2, 3, 7, 2, 7, 6, // top
+    3, 0, 4, 3, 4, 7, // left
+    4, 5, 6, 4, 6, 7, // middle
+];
 
-    let mut cursor_x = start_x;
-    let cursor_y = start_y;
-    let color = [1.0, 1.0, 1.0];
+/// Builds the full-screen red vignette shown when the player is hurt or
+/// low on health, for drawing with [`DAMAGE_VIGNETTE_INDICES`].
+///
+/// The screen corners get `edges` opacity and an inner rectangle `centre`
+/// opacity; the UI shader interpolates in between, so the red fades from
+/// the edges towards the middle.
+pub fn build_damage_vignette(edges: f32, centre: f32) -> [Vertex; 8] {
     let normal = Vertex::pack_normal([0.0, 0.0, 1.0]);
-
-    // Appends a screen-space line segment as a quad with width `line_thickness`.
-    // The quad is extruded perpendicular to the segment direction so it always
-    // appears as a constant-width stroke regardless of angle.
-    // Segments shorter than 0.001 NDC units are skipped to avoid divide-by-zero.
-    let add_segment = |x1: f32,
-                       y1: f32,
-                       x2: f32,
-                       y2: f32,
-                       verts: &mut Vec<Vertex>,
-                       inds: &mut Vec<u32>| {
-        let base_idx = verts.len() as u32;
-        let dx = x2 - x1;
-        let dy = y2 - y1;
-        let len = (dx * dx + dy * dy).sqrt();
-        if len < 0.001 {
-            return;
-        }
-        // Perpendicular offset vector, scaled to half the desired thickness.
-        let nx = -dy / len * line_thickness * 0.5;
-        let ny = dx / len * line_thickness * 0.5;
-
-        // BL, BR, TR, TL (corner_idx 0..3)
-        let corners = [
-            (x1 - nx, y1 - ny),
-            (x2 - nx, y2 - ny),
-            (x2 + nx, y2 + ny),
-            (x1 + nx, y1 + ny),
-        ];
-        for (i, &(px, py)) in corners.iter().enumerate() {
-            verts.push(Vertex {
-                position: [px, py, 0.0],
-                packed: Vertex::pack_ui(normal, [color[0], color[1], color[2], 1.0], 0, i as u8),
-            });
-        }
-        inds.extend_from_slice(&[
-            base_idx,
-            base_idx + 1,
-            base_idx + 2,
-            base_idx,
-            base_idx + 2,
-            base_idx + 3,
-        ]);
-    };
-
-    for ch in text.chars() {
-        if ch == ' ' {
-            cursor_x += char_spacing;
-            continue;
-        }
-
-        // Scale each abstract segment coordinate into screen space and emit.
-        let segments = get_char_segments(ch);
-        for (x1, y1, x2, y2) in segments {
-            let px1 = cursor_x + x1 * char_width;
-            let py1 = cursor_y - char_height + y1 * char_height;
-            let px2 = cursor_x + x2 * char_width;
-            let py2 = cursor_y - char_height + y2 * char_height;
-            add_segment(px1, py1, px2, py2, &mut vertices, &mut indices);
+    let [ix, iy] = VIGNETTE_INNER;
+    let corners = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)];
+    std::array::from_fn(|i| {
+        let ((x, y), alpha) = if i < 4 {
+            (corners[i], edges)
+        } else {
+            let (x, y) = corners[i - 4];
+            ((x * ix, y * iy), centre)
+        };
+        Vertex {
+            position: [x, y, 0.0],
+            packed: Vertex::pack_ui(normal, [1.0, 0.0, 0.0, alpha], 0, (i % 4) as u8),
         }
-
-        cursor_x += gap_spacing;
-    }
-
-    if vertices.is_empty() {
-        return None;
-    }
-
-    let vb = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-        label: Some("Coords Vertex Buffer"),
-        contents: bytemuck::cast_slice(&vertices),
-        usage: wgpu::BufferUsages::VERTEX,
-    });
-
-    let ib = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-        label: Some("Coords Index Buffer"),
-        contents: bytemuck::cast_slice(&indices),
-        usage: wgpu::BufferUsages::INDEX,
-    });
-
-    Some((vb, ib, indices.len() as u32))
+    })
 }
 
-/// Returns the stroke segments that define `ch` in a simple seven-segment
-/// style font.
-///
-/// Each segment is a tuple `(x1, y1, x2, y2)` in a normalized `[0, 1]²`
-/// glyph cell where `(0, 0)` is the bottom-left corner and `(1, 1)` is the
-/// top-right. The caller is responsible for scaling these coordinates into
-/// screen space.
-///
-/// Supported characters: `0`–`9`, `X`, `Y`, `Z`, `:`, `.`, `-`.
-/// Any unrecognized character returns an empty `Vec`, producing no visible
-/// output (effectively a blank glyph).
-fn get_char_segments(ch: char) -> Vec<(f32, f32, f32, f32)> {
-    // Named aliases for the seven standard segment positions.
-    let seg_top = (0.0, 1.0, 1.0, 1.0); // top horizontal
-    let seg_tr = (1.0, 1.0, 1.0, 0.5); // top-right vertical
-    let seg_br = (1.0, 0.5, 1.0, 0.0); // bottom-right vertical
-    let seg_bot = (0.0, 0.0, 1.0, 0.0); // bottom horizontal
-    let seg_bl = (0.0, 0.5, 0.0, 0.0); // bottom-left vertical
-    let seg_tl = (0.0, 1.0, 0.0, 0.5); // top-left vertical
-    let seg_mid = (0.0, 0.5, 1.0, 0.5); // middle horizontal
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    match ch {
-        '0' => vec![seg_top, seg_tr, seg_br, seg_bot, seg_bl, seg_tl],
-        '1' => vec![seg_tr, seg_br],
-        '2' => vec![seg_top, seg_tr, seg_mid, seg_bl, seg_bot],
-        '3' => vec![seg_top, seg_tr, seg_mid, seg_br, seg_bot],
-        '4' => vec![seg_tl, seg_mid, seg_tr, seg_br],
-        '5' => vec![seg_top, seg_tl, seg_mid, seg_br, seg_bot],
-        '6' => vec![seg_top, seg_tl, seg_mid, seg_br, seg_bot, seg_bl],
-        '7' => vec![seg_top, seg_tr, seg_br],
-        '8' => vec![seg_top, seg_tr, seg_br, seg_bot, seg_bl, seg_tl, seg_mid],
-        '9' => vec![seg_top, seg_tr, seg_br, seg_bot, seg_tl, seg_mid],
-        'X' => vec![(0.0, 1.0, 1.0, 0.0), (0.0, 0.0, 1.0, 1.0)],
-        'Y' => vec![
-            (0.0, 1.0, 0.5, 0.5),
-            (1.0, 1.0, 0.5, 0.5),
-            (0.5, 0.5, 0.5, 0.0),
-        ],
-        'Z' => vec![seg_top, (1.0, 1.0, 0.0, 0.0), seg_bot],
-        ':' => vec![(0.4, 0.7, 0.6, 0.7), (0.4, 0.3, 0.6, 0.3)],
-        '.' => vec![(0.4, 0.1, 0.6, 0.1)],
-        '-' => vec![seg_mid],
-        _ => vec![],
+    #[test]
+    fn picking_selects_or_replaces_a_slot() {
+        let mut slots = HOTBAR_SLOTS;
+        // Already in the hotbar: just select it.
+        assert_eq!(pick_block_slot(&mut slots, 0, BlockType::Sand), 3);
+        assert_eq!(slots, HOTBAR_SLOTS);
+        // New block: goes into the selected slot.
+        assert_eq!(pick_block_slot(&mut slots, 2, BlockType::Glass), 2);
+        assert_eq!(slots[2], BlockType::Glass);
+        assert_eq!(slots.iter().filter(|&&b| b == BlockType::Glass).count(), 1);
     }
 }
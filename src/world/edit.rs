@@ -0,0 +1,326 @@
+use std::collections::VecDeque;
+
+use rustc_hash::FxHashSet;
+
+use crate::constants::*;
+use crate::core::block::{BlockType, Facing};
+use crate::world::history::BlockDiff;
+use crate::world::terrain::World;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// World editing tools
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The six face-adjacent neighbour offsets used by the flood fill.
+const NEIGHBOURS: [(i32, i32, i32); 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
+
+impl World {
+    /// Fills the air pocket connected to `start` with water, up to and
+    /// including world height `level`.
+    ///
+    /// This is a one-shot, worldedit-style operation: every reachable air
+    /// block at or below `level` is converted in a single call, independent
+    /// of any gradual fluid simulation.  Solid blocks (and existing water)
+    /// act as the basin walls.
+    ///
+    /// The fill is all-or-nothing: if the connected region exceeds
+    /// `max_volume` blocks or leaks into an unloaded chunk, nothing is
+    /// changed and an error is returned.  This prevents an accidental fill
+    /// of open terrain from flooding the whole loaded world.
+    ///
+    /// # Parameters
+    /// - `start`      – World position of the first air block to fill.
+    /// - `level`      – Highest Y that may receive water.
+    /// - `max_volume` – Maximum number of blocks the fill may touch.
+    ///
+    /// # Returns
+    /// A [`BlockDiff`] for every block that was changed, so the caller can
+    /// remesh the affected sub-chunks, replicate the edit and record it for
+    /// undo.
+    pub fn flood_fill_water(
+        &mut self,
+        start: (i32, i32, i32),
+        level: i32,
+        max_volume: usize,
+    ) -> Result<Vec<BlockDiff>, String> {
+        if start.1 > level {
+            return Err(format!(
+                "Target is above the fill level (y {} > {})",
+                start.1, level
+            ));
+        }
+        let region = self.collect_connected(start, BlockType::Air, level, max_volume)?;
+        Ok(region
+            .into_iter()
+            .map(|(x, y, z)| self.set_block_recorded(x, y, z, BlockType::Water, Facing::default()))
+            .collect())
+    }
+
+    /// Removes the body of water connected to `start`, replacing it with air.
+    ///
+    /// Counterpart to [`World::flood_fill_water`] with the same
+    /// all-or-nothing volume limit, so aiming at an ocean reports an error
+    /// instead of draining it.
+    ///
+    /// # Returns
+    /// A [`BlockDiff`] for every block that was changed.
+    pub fn drain_water(
+        &mut self,
+        start: (i32, i32, i32),
+        max_volume: usize,
+    ) -> Result<Vec<BlockDiff>, String> {
+        let region =
+            self.collect_connected(start, BlockType::Water, WORLD_HEIGHT - 1, max_volume)?;
+        Ok(region
+            .into_iter()
+            .map(|(x, y, z)| self.set_block_recorded(x, y, z, BlockType::Air, Facing::default()))
+            .collect())
+    }
+
+    /// Breadth-first search over face-connected blocks of type `matches`,
+    /// starting at `start` and never rising above `max_y`.
+    ///
+    /// # Errors
+    /// - `start` is not of type `matches`.
+    /// - The region grows beyond `max_volume` blocks.
+    /// - The region touches a chunk that is not loaded (its contents are
+    ///   unknown, so the basin cannot be proven closed).
+    fn collect_connected(
+        &self,
+        start: (i32, i32, i32),
+        matches: BlockType,
+        max_y: i32,
+        max_volume: usize,
+    ) -> Result<Vec<(i32, i32, i32)>, String> {
+        if !self.is_block_loaded(start.0, start.1, start.2) {
+            return Err("Target is outside the loaded world".to_string());
+        }
+        if self.get_block(start.0, start.1, start.2) != matches {
+            return Err(format!("Target is not {}", matches.display_name()));
+        }
+
+        let mut visited: FxHashSet<(i32, i32, i32)> = FxHashSet::default();
+        let mut queue = VecDeque::new();
+        let mut region = Vec::new();
+        visited.insert(start);
+        queue.push_back(start);
+
+        while let Some((x, y, z)) = queue.pop_front() {
+            region.push((x, y, z));
+            if region.len() > max_volume {
+                return Err(format!(
+                    "Region exceeds the limit of {} blocks; is the basin closed?",
+                    max_volume
+                ));
+            }
+
+            for (dx, dy, dz) in NEIGHBOURS {
+                let next = (x + dx, y + dy, z + dz);
+                // Bedrock sits at y = 0, so the bottom of the world is a
+                // natural wall; the ceiling is capped by `max_y`.
+                if next.1 < 0 || next.1 > max_y || visited.contains(&next) {
+                    continue;
+                }
+                if self.get_block(next.0, next.1, next.2) != matches {
+                    continue;
+                }
+                if !self.is_block_loaded(next.0, next.1, next.2) {
+                    return Err("Region reaches an unloaded chunk".to_string());
+                }
+                visited.insert(next);
+                queue.push_back(next);
+            }
+        }
+
+        Ok(region)
+    }
+
+    /// Returns `true` if the chunk column containing `(x, _, z)` is loaded
+    /// and `y` is inside the world's vertical range.
+    pub fn is_block_loaded(&self, x: i32, y: i32, z: i32) -> bool {
+        if !(0..WORLD_HEIGHT).contains(&y) {
+            return false;
+        }
+        let cx = x.div_euclid(CHUNK_SIZE);
+        let cz = z.div_euclid(CHUNK_SIZE);
+        self.chunks.contains_key(&(cx, cz))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Box edits (/fill, /replace)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// What a [`BoxEdit`] does to each block inside its region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoxEditKind {
+    /// Overwrite every block with the given type.
+    Fill(BlockType),
+    /// Swap every block of type `from` for `to`; other blocks are untouched.
+    Replace { from: BlockType, to: BlockType },
+}
+
+/// An axis-aligned box edit that is applied a slice at a time.
+///
+/// Large regions are streamed across frames with [`BoxEdit::step`] so a
+/// 64×64×64 fill does not stall the game for a whole frame.  Blocks are
+/// visited bottom layer first, so a partially applied fill looks like it is
+/// being built up from the ground.
+#[derive(Debug, Clone)]
+pub struct BoxEdit {
+    /// Inclusive minimum corner in world space.
+    pub min: (i32, i32, i32),
+    /// Inclusive maximum corner in world space.
+    pub max: (i32, i32, i32),
+    /// Operation applied to each block.
+    pub kind: BoxEditKind,
+    /// Every block changed so far, so the whole edit can be undone as one
+    /// operation once it finishes.
+    pub applied: Vec<BlockDiff>,
+    /// Index of the next block to visit, in `0..volume()`.
+    cursor: usize,
+}
+
+impl BoxEdit {
+    /// Creates an edit covering the box spanned by corners `a` and `b`
+    /// (inclusive, in any order).
+    ///
+    /// # Errors
+    /// Returns a message if the box reaches outside the world height or
+    /// holds more than [`MAX_BOX_EDIT_VOLUME`] blocks.
+    pub fn new(a: (i32, i32, i32), b: (i32, i32, i32), kind: BoxEditKind) -> Result<Self, String> {
+        let min = (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2));
+        let max = (a.0.max(b.0), a.1.max(b.1), a.2.max(b.2));
+        if min.1 < 0 || max.1 >= WORLD_HEIGHT {
+            return Err(format!(
+                "Region must lie between y = 0 and y = {}",
+                WORLD_HEIGHT - 1
+            ));
+        }
+
+        // In i64, so corners far apart cannot overflow the subtraction.
+        let size = |min: i32, max: i32| i64::from(max) - i64::from(min) + 1;
+        let volume = size(min.0, max.0)
+            .checked_mul(size(min.1, max.1))
+            .and_then(|v| v.checked_mul(size(min.2, max.2)));
+        match volume {
+            Some(volume) if volume <= MAX_BOX_EDIT_VOLUME as i64 => {}
+            Some(volume) => {
+                return Err(format!(
+                    "Region of {} blocks exceeds the limit of {}",
+                    volume, MAX_BOX_EDIT_VOLUME
+                ));
+            }
+            None => {
+                return Err(format!(
+                    "Region exceeds the limit of {} blocks",
+                    MAX_BOX_EDIT_VOLUME
+                ));
+            }
+        }
+
+        Ok(Self {
+            min,
+            max,
+            kind,
+            applied: Vec::new(),
+            cursor: 0,
+        })
+    }
+
+    /// Total number of blocks inside the box, at most
+    /// [`MAX_BOX_EDIT_VOLUME`].
+    pub fn volume(&self) -> usize {
+        let sx = (self.max.0 - self.min.0 + 1) as usize;
+        let sy = (self.max.1 - self.min.1 + 1) as usize;
+        let sz = (self.max.2 - self.min.2 + 1) as usize;
+        sx * sy * sz
+    }
+
+    /// Returns `true` once every block in the box has been visited.
+    pub fn is_done(&self) -> bool {
+        self.cursor >= self.volume()
+    }
+
+    /// Visits up to `budget` more blocks and applies the edit to them.
+    ///
+    /// Writes go through [`World::set_block_recorded`], so touched chunks are
+    /// flagged for saving.  Blocks in unloaded chunks and bedrock, which
+    /// cannot be broken, are skipped.
+    ///
+    /// # Returns
+    /// A [`BlockDiff`] for every block that actually changed in this step,
+    /// so the caller can remesh the affected sub-chunks and replicate the
+    /// edit.  The same diffs are appended to [`Self::applied`].
+    pub fn step(&mut self, world: &mut World, budget: usize) -> Vec<BlockDiff> {
+        let sx = (self.max.0 - self.min.0 + 1) as usize;
+        let sz = (self.max.2 - self.min.2 + 1) as usize;
+        let end = (self.cursor + budget).min(self.volume());
+        let mut changed = Vec::new();
+
+        for index in self.cursor..end {
+            let x = self.min.0 + (index % sx) as i32;
+            let z = self.min.2 + ((index / sx) % sz) as i32;
+            let y = self.min.1 + (index / (sx * sz)) as i32;
+
+            if !world.is_block_loaded(x, y, z) {
+                continue;
+            }
+            let current = world.get_block(x, y, z);
+            if current == BlockType::Bedrock {
+                continue;
+            }
+            let target = match self.kind {
+                BoxEditKind::Fill(block) => block,
+                BoxEditKind::Replace { from, to } if current == from => to,
+                BoxEditKind::Replace { .. } => continue,
+            };
+            if current != target {
+                changed.push(world.set_block_recorded(x, y, z, target, Facing::default()));
+            }
+        }
+
+        self.cursor = end;
+        self.applied.extend_from_slice(&changed);
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::chunk::Chunk;
+
+    #[test]
+    fn box_edits_stay_inside_the_world_and_spare_bedrock() {
+        let fill = BoxEditKind::Fill(BlockType::Stone);
+        assert!(BoxEdit::new((0, 300, 0), (0, 400, 0), fill).is_err());
+        assert!(BoxEdit::new((0, -1, 0), (0, 10, 0), fill).is_err());
+        assert!(BoxEdit::new((-2_000_000_000, 0, 0), (2_000_000_000, 0, 0), fill).is_err());
+        assert!(
+            BoxEdit::new(
+                (-2_000_000_000, 0, -2_000_000_000),
+                (2_000_000_000, 255, 2_000_000_000),
+                fill
+            )
+            .is_err()
+        );
+
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        world.set_block(0, 0, 0, BlockType::Bedrock);
+        let mut edit = BoxEdit::new((0, 0, 0), (1, 1, 0), fill).unwrap();
+        assert_eq!(edit.volume(), 4);
+        assert_eq!(edit.step(&mut world, 64).len(), 3);
+        assert!(edit.is_done());
+        assert_eq!(world.get_block(0, 0, 0), BlockType::Bedrock);
+        assert_eq!(world.get_block(1, 0, 0), BlockType::Stone);
+    }
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+/// Overall relief of the generated terrain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TerrainShape {
+    /// Hills and mountains squashed towards sea level.
+    Flat,
+    /// The regular terrain.
+    #[default]
+    Normal,
+    /// Land raised further above sea level, for taller mountains.
+    Mountainous,
+    /// A single flat grass plane with no biomes, caves or decorations.
+    Superflat,
+}
+
+impl TerrainShape {
+    /// Factor applied to the height of the land above sea level.
+    pub fn amplitude(self) -> f64 {
+        match self {
+            TerrainShape::Flat => 0.3,
+            TerrainShape::Normal => 1.0,
+            TerrainShape::Mountainous => 1.7,
+            TerrainShape::Superflat => 0.0,
+        }
+    }
+}
+
+/// Switches for the optional parts of world generation.
+///
+/// Stored in [`SavedWorld`](crate::SavedWorld) so a reloaded world keeps
+/// generating new chunks the same way.  The default generates the regular
+/// world.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorldGenConfig {
+    /// Relief of the terrain; see [`TerrainShape`].
+    pub terrain: TerrainShape,
+    /// Whether caves and cave entrances are carved out of the terrain and
+    /// dungeons are built underground.
+    pub caves: bool,
+    /// Multiplier on the chance of a tree where a biome allows one; `0.0`
+    /// generates no trees.
+    pub tree_density: f32,
+}
+
+impl Default for WorldGenConfig {
+    fn default() -> Self {
+        Self {
+            terrain: TerrainShape::Normal,
+            caves: true,
+            tree_density: 1.0,
+        }
+    }
+}
+
+/// Ready-made [`WorldGenConfig`]s offered on the singleplayer page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorldPreset {
+    #[default]
+    Default,
+    /// Gentle terrain with the hills flattened out.
+    Flat,
+    /// Exaggerated terrain with taller mountains and more trees.
+    Amplified,
+    /// A flat grass plane for building and testing.
+    Superflat,
+}
+
+impl WorldPreset {
+    /// Every preset, in the order the menu cycles through them.
+    pub const ALL: [WorldPreset; 4] = [
+        WorldPreset::Default,
+        WorldPreset::Flat,
+        WorldPreset::Amplified,
+        WorldPreset::Superflat,
+    ];
+
+    /// Name shown on the menu button.
+    pub fn label(self) -> &'static str {
+        match self {
+            WorldPreset::Default => "DEFAULT",
+            WorldPreset::Flat => "FLAT",
+            WorldPreset::Amplified => "AMPLIFIED",
+            WorldPreset::Superflat => "SUPERFLAT",
+        }
+    }
+
+    /// The preset after this one, wrapping around to the first.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Generation settings of this preset.
+    pub fn config(self) -> WorldGenConfig {
+        let default = WorldGenConfig::default();
+        match self {
+            WorldPreset::Default => default,
+            WorldPreset::Flat => WorldGenConfig {
+                terrain: TerrainShape::Flat,
+                ..default
+            },
+            WorldPreset::Amplified => WorldGenConfig {
+                terrain: TerrainShape::Mountainous,
+                tree_density: 1.5,
+                ..default
+            },
+            WorldPreset::Superflat => WorldGenConfig {
+                terrain: TerrainShape::Superflat,
+                caves: false,
+                tree_density: 0.0,
+            },
+        }
+    }
+}
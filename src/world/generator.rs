@@ -4,7 +4,41 @@ use crate::constants::*;
 use crate::core::biome::Biome;
 use crate::core::block::BlockType;
 use crate::core::chunk::Chunk;
+use crate::world::gen_config::{TerrainShape, WorldGenConfig};
 use crate::world::spline::TerrainSpline;
+use crate::world::structures::Structure;
+use crate::world::structures::dungeon::{DUNGEON_HEIGHT, DUNGEON_SIZE, Dungeon};
+
+/// Height from which columns outside the Tundra start getting a
+/// [`BlockType::SnowLayer`] on top.
+const SNOW_LINE: i32 = 130;
+
+/// Blocks above [`SNOW_LINE`] over which snow goes from sparse to covering
+/// every column.
+const SNOW_LINE_FADE: i32 = 12;
+
+/// Height of the first air block above a [`TerrainShape::Superflat`] world:
+/// the grass plane lies at [`SEA_LEVEL`].
+const SUPERFLAT_SURFACE: i32 = SEA_LEVEL + 1;
+
+/// Side, in blocks, of the square regions the world is split into for
+/// dungeon placement.  Each region holds at most one dungeon, lying wholly
+/// inside it; a multiple of [`CHUNK_SIZE`] so every chunk is in exactly
+/// one region.
+const DUNGEON_REGION: i32 = 64;
+
+/// One region in this many gets a dungeon.
+const DUNGEON_RARITY: u32 = 3;
+
+/// Lowest floor height of a dungeon, clear of the bedrock.
+const DUNGEON_MIN_Y: i32 = 8;
+
+/// Minimum rock, in blocks, between a dungeon's ceiling and the surface.
+const DUNGEON_COVER: i32 = 8;
+
+/// Salt mixed into the region hashes so dungeon placement does not follow
+/// the per-column decoration hashes.
+const DUNGEON_SALT: i32 = -7919;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // ChunkGenerator
@@ -66,10 +100,30 @@ pub struct ChunkGenerator {
     noise_cave_warp_z: FastNoiseLite,
     noise_surface_entrance: FastNoiseLite,
     pub seed: u32,
+    /// Oceans, lakes and rivers are filled with water below this height.
+    /// The terrain itself is always shaped around [`SEA_LEVEL`], so a higher
+    /// value floods the world instead of raising it.
+    pub sea_level: i32,
+    /// Optional generation features; see [`WorldGenConfig`].
+    pub config: WorldGenConfig,
+    /// Template of the generated dungeon rooms.
+    dungeon: Structure,
 }
 
 impl ChunkGenerator {
     pub fn new(seed: u32) -> Self {
+        Self::with_sea_level(seed, SEA_LEVEL)
+    }
+
+    /// Creates a generator whose water fills up to `sea_level` instead of
+    /// [`SEA_LEVEL`].
+    pub fn with_sea_level(seed: u32, sea_level: i32) -> Self {
+        Self::with_config(seed, sea_level, WorldGenConfig::default())
+    }
+
+    /// Creates a generator with water up to `sea_level` and the features
+    /// chosen in `config`.
+    pub fn with_config(seed: u32, sea_level: i32, config: WorldGenConfig) -> Self {
         ChunkGenerator {
             noise_continents: Self::create_fbm_noise(seed, 0.0018),
             noise_terrain: Self::create_fbm_noise(seed.wrapping_add(1), 0.013),
@@ -93,6 +147,9 @@ impl ChunkGenerator {
             noise_cave_warp_z: Self::create_fbm_noise(seed.wrapping_add(31), 0.014),
             noise_surface_entrance: Self::create_fbm_noise(seed.wrapping_add(40), 0.015),
             seed,
+            sea_level,
+            config,
+            dungeon: Dungeon::new().structure,
         }
     }
 
@@ -137,6 +194,10 @@ impl ChunkGenerator {
     // ── Public chunk generation ───────────────────────────────────────────── //
 
     pub fn generate_chunk(&self, cx: i32, cz: i32) -> Chunk {
+        if self.config.terrain == TerrainShape::Superflat {
+            return self.generate_superflat_chunk(cx, cz);
+        }
+
         let mut chunk = Chunk::new(cx, cz);
         let base_x = cx * CHUNK_SIZE;
         let base_z = cz * CHUNK_SIZE;
@@ -168,7 +229,7 @@ impl ChunkGenerator {
                 let idx = bx as usize * buf_size + bz as usize;
                 let biome = self.get_biome(world_x, world_z);
                 buf_biome[idx] = biome;
-                buf_height[idx] = self.calculate_base_height_with_biome(world_x, world_z, biome);
+                buf_height[idx] = self.shaped_height(world_x, world_z, biome);
             }
         }
 
@@ -240,7 +301,7 @@ impl ChunkGenerator {
                 let max_y = if matches!(biome, Biome::Mountains | Biome::Island) {
                     WORLD_HEIGHT - 20
                 } else {
-                    (surface_height + 5).max(SEA_LEVEL)
+                    (surface_height + 5).max(self.sea_level)
                 };
 
                 for y in 0..max_y {
@@ -261,8 +322,8 @@ impl ChunkGenerator {
                         if block != BlockType::Air {
                             chunk.set_block(lx, y, lz, block);
                         }
-                    } else if y >= surface_height && y < SEA_LEVEL {
-                        if biome == Biome::Tundra && y == SEA_LEVEL - 1 {
+                    } else if y >= surface_height && y < self.sea_level {
+                        if biome == Biome::Tundra && y == self.sea_level - 1 {
                             chunk.set_block(lx, y, lz, BlockType::Ice);
                         } else {
                             chunk.set_block(lx, y, lz, BlockType::Water);
@@ -272,6 +333,53 @@ impl ChunkGenerator {
             }
         }
 
+        // ── Passes 3–5: caves ─────────────────────────────────────────────── //
+        if self.config.caves {
+            self.carve_caves(&mut chunk, cx, cz, &biome_map, &height_map);
+        }
+
+        // ── Pass 5b: dungeons ─────────────────────────────────────────────── //
+        // Placed after the caves so a cave passing through a room cannot
+        // hollow out its walls, and switched off along with them.
+        if self.config.caves
+            && let Some(origin) = self.dungeon_origin(
+                (cx * CHUNK_SIZE).div_euclid(DUNGEON_REGION),
+                (cz * CHUNK_SIZE).div_euclid(DUNGEON_REGION),
+            )
+        {
+            self.dungeon.place_in_chunk(&mut chunk, cx, cz, origin);
+        }
+
+        // ── Pass 6: surface decorations ───────────────────────────────────── //
+        self.generate_decorations(&mut chunk, cx, cz, &biome_map, &height_map);
+
+        // ── Pass 7: snow cover ────────────────────────────────────────────── //
+        // Runs after the decorations so a tree's canopy, not the ground
+        // underneath it, is the top of its column.
+        self.generate_snow_cover(&mut chunk, cx, cz, &biome_map);
+
+        // ── Pass 8: sub-chunk metadata ────────────────────────────────────── //
+        for subchunk in &mut chunk.subchunks {
+            subchunk.check_empty();
+            subchunk.check_fully_opaque();
+        }
+
+        chunk
+    }
+
+    /// Carves caves, decorates their floors and ceilings, and opens
+    /// entrance shafts to the surface.
+    fn carve_caves(
+        &self,
+        chunk: &mut Chunk,
+        cx: i32,
+        cz: i32,
+        biome_map: &[[Biome; CHUNK_SIZE as usize]; CHUNK_SIZE as usize],
+        height_map: &[[i32; CHUNK_SIZE as usize]; CHUNK_SIZE as usize],
+    ) {
+        let base_x = cx * CHUNK_SIZE;
+        let base_z = cz * CHUNK_SIZE;
+
         // ── Pass 3: cave carving ──────────────────────────────────────────── //
         let mut cave_entrance_map = [[false; CHUNK_SIZE as usize]; CHUNK_SIZE as usize];
         for lx in 0..CHUNK_SIZE {
@@ -384,7 +492,7 @@ impl ChunkGenerator {
                 if matches!(
                     biome,
                     Biome::Ocean | Biome::River | Biome::Lake | Biome::Beach
-                ) || height <= SEA_LEVEL + 3
+                ) || height <= self.sea_level + 3
                 {
                     continue;
                 }
@@ -398,7 +506,7 @@ impl ChunkGenerator {
 
                 let max_shaft_depth = 24;
                 let shaft_start = height - 1;
-                let shaft_end = (shaft_start - max_shaft_depth).max(SEA_LEVEL + 1);
+                let shaft_end = (shaft_start - max_shaft_depth).max(self.sea_level + 1);
 
                 'shaft: for y in (shaft_end..=shaft_start).rev() {
                     for dx in -shaft_radius..=shaft_radius {
@@ -423,19 +531,69 @@ impl ChunkGenerator {
                 }
             }
         }
+    }
 
-        // ── Pass 6: surface decorations ───────────────────────────────────── //
-        self.generate_decorations(&mut chunk, cx, cz, &biome_map, &height_map);
-
-        // ── Pass 7: sub-chunk metadata ────────────────────────────────────── //
+    /// Generates a [`TerrainShape::Superflat`] chunk: bedrock, stone, three
+    /// layers of dirt and grass on top at [`SEA_LEVEL`], flooded only when
+    /// the sea level is raised above it.
+    fn generate_superflat_chunk(&self, cx: i32, cz: i32) -> Chunk {
+        let mut chunk = Chunk::new(cx, cz);
+        for lx in 0..CHUNK_SIZE {
+            for lz in 0..CHUNK_SIZE {
+                for y in 0..SUPERFLAT_SURFACE.max(self.sea_level) {
+                    let block = if y == 0 {
+                        BlockType::Bedrock
+                    } else if y >= SUPERFLAT_SURFACE {
+                        BlockType::Water
+                    } else if y == SUPERFLAT_SURFACE - 1 {
+                        BlockType::Grass
+                    } else if y >= SUPERFLAT_SURFACE - 4 {
+                        BlockType::Dirt
+                    } else {
+                        BlockType::Stone
+                    };
+                    chunk.set_block(lx, y, lz, block);
+                }
+            }
+        }
         for subchunk in &mut chunk.subchunks {
             subchunk.check_empty();
             subchunk.check_fully_opaque();
         }
-
         chunk
     }
 
+    /// Returns where the dungeon of region `(rx, rz)` is anchored, or `None`
+    /// if the region has none.
+    ///
+    /// Depends only on the seed and the region, so every chunk a room
+    /// overlaps agrees on where it is and builds its own part of it, and a
+    /// room split across chunk borders comes out whole.
+    fn dungeon_origin(&self, rx: i32, rz: i32) -> Option<(i32, i32, i32)> {
+        let hash = self.position_hash_3d(rx, DUNGEON_SALT, rz);
+        if !hash.is_multiple_of(DUNGEON_RARITY) {
+            return None;
+        }
+        let spread = (DUNGEON_REGION - DUNGEON_SIZE + 1) as u32;
+        let x = rx * DUNGEON_REGION + ((hash >> 4) % spread) as i32;
+        let z = rz * DUNGEON_REGION + ((hash >> 12) % spread) as i32;
+
+        // The lowest corner decides, so no part of the ceiling ends up
+        // near the surface or the sea floor.
+        let last = DUNGEON_SIZE - 1;
+        let surface = [(x, z), (x + last, z), (x, z + last), (x + last, z + last)]
+            .into_iter()
+            .map(|(x, z)| self.get_terrain_height_pub(x, z))
+            .min()?;
+        let highest = surface - DUNGEON_COVER - DUNGEON_HEIGHT;
+        if highest < DUNGEON_MIN_Y {
+            return None;
+        }
+        let depth_hash = self.position_hash_3d(rx, DUNGEON_SALT - 1, rz);
+        let y = DUNGEON_MIN_Y + (depth_hash % (highest - DUNGEON_MIN_Y + 1) as u32) as i32;
+        Some((x, y, z))
+    }
+
     // ── Public forwarding accessors ───────────────────────────────────────── //
 
     /// Returns the raw (unblended) terrain height at `(x, z)`.
@@ -445,8 +603,49 @@ impl ChunkGenerator {
     /// blending requires a full noise buffer and is only meaningful at chunk
     /// granularity (done inside `generate_chunk`).
     pub fn get_terrain_height_pub(&self, x: i32, z: i32) -> i32 {
+        if self.config.terrain == TerrainShape::Superflat {
+            return SUPERFLAT_SURFACE;
+        }
         let biome = self.get_biome(x, z);
-        (self.calculate_base_height_with_biome(x, z, biome) as i32).clamp(1, WORLD_HEIGHT - 20)
+        (self.shaped_height(x, z, biome) as i32).clamp(1, WORLD_HEIGHT - 20)
+    }
+
+    /// Searches outward from the origin in a spiral of expanding radii to find
+    /// a suitable player spawn position.
+    ///
+    /// A column is acceptable when its terrain height is at or above sea level
+    /// and its biome is not Ocean, River, or Lake (the player would spawn
+    /// underwater or on an unstable floor).
+    ///
+    /// The returned Y coordinate places the player one block above the surface
+    /// with a small XZ offset so the player doesn't fall into a 1×1 crevice
+    /// at exactly (0, y, 0).
+    ///
+    /// # Returns
+    /// `(x, y, z)` in world space.  Falls back to `(0.5, 80.0, 0.5)` if no
+    /// suitable column is found within radius 50 (which should never happen in
+    /// practice for non-degenerate worlds).
+    pub fn find_spawn_point(&self) -> (f32, f32, f32) {
+        for radius in 0..50 {
+            for dx in -radius..=radius {
+                for dz in -radius..=radius {
+                    let x = dx;
+                    let z = dz;
+                    let height = self.get_terrain_height_pub(x, z);
+                    let biome = self.get_biome(x, z);
+
+                    if height >= self.sea_level
+                        && !matches!(biome, Biome::Ocean | Biome::River | Biome::Lake)
+                    {
+                        // +0.3 / +0.5 offsets prevent the player from being
+                        // centred on a block edge and avoid false collision
+                        // positives at the moment of spawn.
+                        return (x as f32 + 0.3, (height + 1) as f32, z as f32 + 0.5);
+                    }
+                }
+            }
+        }
+        (0.5, 80.0, 0.5) // fallback
     }
 
     pub fn is_cave_entrance_pub(&self, x: i32, z: i32, surface_height: i32) -> bool {
@@ -465,6 +664,10 @@ impl ChunkGenerator {
     /// (scale 0.005, Z offset +200) so biome boundaries and height boundaries
     /// are always coherent — no more mismatched warp between the two systems.
     pub fn get_biome(&self, x: i32, z: i32) -> Biome {
+        if self.config.terrain == TerrainShape::Superflat {
+            return Biome::Plains;
+        }
+
         let fx = x as f32;
         let fz = z as f32;
 
@@ -542,6 +745,20 @@ impl ChunkGenerator {
 
     // ── Terrain height ────────────────────────────────────────────────────── //
 
+    /// [`Self::calculate_base_height_with_biome`] with the land above
+    /// [`SEA_LEVEL`] scaled by the configured [`TerrainShape::amplitude`].
+    /// Sea floors keep their depth.
+    fn shaped_height(&self, x: i32, z: i32, biome: Biome) -> f64 {
+        let height = self.calculate_base_height_with_biome(x, z, biome);
+        match self.config.terrain {
+            TerrainShape::Normal => height,
+            shape => {
+                let land = (height - SEA_LEVEL as f64).max(0.0);
+                height - land + land * shape.amplitude()
+            }
+        }
+    }
+
     /// Core height function for a single `(x, z)` sample given a pre-computed `biome`.
     ///
     /// Domain warp uses the **same scale (0.005) and Z-offset (+200)** as
@@ -750,7 +967,7 @@ impl ChunkGenerator {
     }
 
     fn is_cave_entrance(&self, x: i32, z: i32, surface_height: i32) -> bool {
-        if surface_height <= SEA_LEVEL + 2 {
+        if surface_height <= self.sea_level + 2 {
             return false;
         }
 
@@ -795,7 +1012,7 @@ impl ChunkGenerator {
     }
 
     fn is_surface_cave_entrance(&self, x: i32, z: i32, surface_height: i32) -> bool {
-        if surface_height <= SEA_LEVEL + 3 {
+        if surface_height <= self.sea_level + 3 {
             return false;
         }
 
@@ -918,7 +1135,7 @@ impl ChunkGenerator {
                 } else if depth_from_surface > 0 {
                     BlockType::Sand
                 } else if y == surface_height - 1 {
-                    if biome == Biome::Island && y > SEA_LEVEL + 2 {
+                    if biome == Biome::Island && y > self.sea_level + 2 {
                         BlockType::Grass
                     } else {
                         BlockType::Sand
@@ -979,7 +1196,7 @@ impl ChunkGenerator {
                 } else if depth_from_surface > 1 {
                     BlockType::Dirt
                 } else if y == surface_height - 1 {
-                    if y <= SEA_LEVEL + 1 {
+                    if y <= self.sea_level + 1 {
                         BlockType::Clay
                     } else {
                         BlockType::Grass
@@ -1024,7 +1241,7 @@ impl ChunkGenerator {
                 let height = height_map[lx as usize][lz as usize];
                 let hash = self.position_hash(world_x, world_z);
 
-                if height <= SEA_LEVEL {
+                if height <= self.sea_level {
                     continue;
                 }
 
@@ -1035,7 +1252,7 @@ impl ChunkGenerator {
                     let density_threshold = biome.tree_density() as f32;
 
                     if tree_noise > density_threshold {
-                        if hash % 100 < 18 {
+                        if ((hash % 100) as f32) < 18.0 * self.config.tree_density {
                             let ground = chunk.get_block(lx, height - 1, lz);
                             if matches!(ground, BlockType::Grass | BlockType::Dirt) {
                                 let is_large =
@@ -1080,6 +1297,51 @@ impl ChunkGenerator {
         }
     }
 
+    // ── Snow cover ────────────────────────────────────────────────────────── //
+
+    /// Covers the exposed top of cold columns with a [`BlockType::SnowLayer`].
+    ///
+    /// Every Tundra column is covered.  Elsewhere snow starts at
+    /// [`SNOW_LINE`] with a ragged edge: the share of covered columns grows
+    /// over [`SNOW_LINE_FADE`] blocks until everything above is white.
+    /// Which columns make the cut depends only on the seed and position.
+    ///
+    /// Only the topmost block of a column can be covered, and only when it
+    /// is [solid and opaque](BlockType::is_solid_opaque), so snow never
+    /// lies under leaves, overhangs or cave ceilings, nor on water or ice.
+    fn generate_snow_cover(
+        &self,
+        chunk: &mut Chunk,
+        cx: i32,
+        cz: i32,
+        biome_map: &[[Biome; CHUNK_SIZE as usize]; CHUNK_SIZE as usize],
+    ) {
+        let base_x = cx * CHUNK_SIZE;
+        let base_z = cz * CHUNK_SIZE;
+
+        for lx in 0..CHUNK_SIZE {
+            for lz in 0..CHUNK_SIZE {
+                let Some((top, block)) = chunk.surface_block(lx, lz) else {
+                    continue;
+                };
+                if top + 1 >= WORLD_HEIGHT || !block.is_solid_opaque() {
+                    continue;
+                }
+
+                let covered = if biome_map[lx as usize][lz as usize] == Biome::Tundra {
+                    true
+                } else {
+                    let above_line = top - SNOW_LINE;
+                    let hash = self.position_hash(base_x + lx, base_z + lz);
+                    above_line >= 0 && (hash % SNOW_LINE_FADE as u32) as i32 <= above_line
+                };
+                if covered {
+                    chunk.set_block(lx, top + 1, lz, BlockType::SnowLayer);
+                }
+            }
+        }
+    }
+
     // ── Tree placement ────────────────────────────────────────────────────── //
 
     fn can_place_tree(&self, chunk: &Chunk, lx: i32, y: i32, lz: i32, is_large: bool) -> bool {
@@ -1237,6 +1499,82 @@ impl ChunkGenerator {
 
 impl Clone for ChunkGenerator {
     fn clone(&self) -> Self {
-        ChunkGenerator::new(self.seed)
+        ChunkGenerator::with_config(self.seed, self.sea_level, self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Seed whose chunk `(0, 0)` is pinned by [`GOLDEN_CHUNK_HASH`].
+    const GOLDEN_SEED: u32 = 12345;
+
+    /// [`block_hash`] of chunk `(0, 0)` generated from [`GOLDEN_SEED`] with
+    /// the default sea level and [`WorldGenConfig`].
+    ///
+    /// Saved worlds only store edited chunks and regenerate the rest, so
+    /// any change to this value moves the terrain under existing saves.
+    /// When a generator change is meant to alter the terrain, run
+    /// `cargo test --lib golden_chunk -- --nocapture` and copy the printed
+    /// hash here.
+    const GOLDEN_CHUNK_HASH: u64 = 0x5752_9197_0d3c_aa61;
+
+    /// 64-bit FNV-1a over every block of `chunk`, bottom sub-chunk first.
+    fn block_hash(chunk: &Chunk) -> u64 {
+        let mut hash = 0xcbf2_9ce4_8422_2325u64;
+        for subchunk in &chunk.subchunks {
+            for plane in &subchunk.blocks {
+                for row in plane {
+                    for &block in row {
+                        hash = (hash ^ block as u64).wrapping_mul(0x0100_0000_01b3);
+                    }
+                }
+            }
+        }
+        hash
+    }
+
+    #[test]
+    fn dungeons_are_whole_across_chunk_borders() {
+        let generator = ChunkGenerator::new(GOLDEN_SEED);
+        // The first dungeon straddling a chunk border along x.
+        let (ox, oy, oz) = (0..8)
+            .flat_map(|rx| (0..8).map(move |rz| (rx, rz)))
+            .filter_map(|(rx, rz)| generator.dungeon_origin(rx, rz))
+            .find(|&(x, _, _)| x / CHUNK_SIZE != (x + DUNGEON_SIZE - 1) / CHUNK_SIZE)
+            .expect("no dungeon across a chunk border");
+
+        // Later template entries overwrite earlier ones, as when placed.
+        let expected: HashMap<_, _> = Dungeon::new()
+            .structure
+            .blocks
+            .iter()
+            .map(|&(x, y, z, block)| ((ox + x, oy + y, oz + z), block))
+            .collect();
+        let mut chunks = HashMap::new();
+        for (&(x, y, z), &block) in &expected {
+            let (cx, cz) = (x.div_euclid(CHUNK_SIZE), z.div_euclid(CHUNK_SIZE));
+            let chunk = chunks
+                .entry((cx, cz))
+                .or_insert_with(|| generator.generate_chunk(cx, cz));
+            let found = chunk.get_block(x - cx * CHUNK_SIZE, y, z - cz * CHUNK_SIZE);
+            assert_eq!(found, block, "dungeon block at {:?}", (x, y, z));
+        }
+        assert!(chunks.len() >= 2);
+    }
+
+    #[test]
+    fn golden_chunk_matches_recorded_hash() {
+        let hash = block_hash(&ChunkGenerator::new(GOLDEN_SEED).generate_chunk(0, 0));
+        println!(
+            "chunk (0, 0) of seed {} hashes to {:#018x}",
+            GOLDEN_SEED, hash
+        );
+        assert_eq!(
+            hash, GOLDEN_CHUNK_HASH,
+            "terrain generation changed; update GOLDEN_CHUNK_HASH if that was intended"
+        );
     }
 }
@@ -0,0 +1,180 @@
+use std::collections::VecDeque;
+
+use crate::core::block::{BlockType, Facing};
+use crate::world::terrain::World;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Edit history (undo / redo)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The change made to a single block: its position and its state before and
+/// after the edit.
+///
+/// Sixteen bytes per block, so even a maximum-size `/fill` can be kept in the
+/// history without much memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockDiff {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub before: BlockType,
+    pub after: BlockType,
+    pub before_facing: Facing,
+    pub after_facing: Facing,
+}
+
+impl BlockDiff {
+    /// Returns the diff that undoes this one (before and after swapped).
+    pub fn inverted(&self) -> BlockDiff {
+        BlockDiff {
+            before: self.after,
+            after: self.before,
+            before_facing: self.after_facing,
+            after_facing: self.before_facing,
+            ..*self
+        }
+    }
+}
+
+/// Bounded undo/redo stacks of block edits.
+///
+/// Every entry is one player-visible operation (a placed block, a broken
+/// block, a whole `/fill`), stored as the list of [`BlockDiff`]s it made.
+/// The oldest operations are dropped once either `max_operations` entries
+/// or `max_blocks` diffs in total are stored.
+///
+/// The history only records diffs; applying them is done by the caller via
+/// [`World::apply_diffs`] so it can also remesh and replicate the change.
+#[derive(Debug, Clone)]
+pub struct EditHistory {
+    undo: VecDeque<Vec<BlockDiff>>,
+    redo: Vec<Vec<BlockDiff>>,
+    /// Total number of diffs across both stacks.
+    blocks: usize,
+    max_operations: usize,
+    max_blocks: usize,
+}
+
+impl EditHistory {
+    /// Creates an empty history with the given limits.
+    pub fn new(max_operations: usize, max_blocks: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            blocks: 0,
+            max_operations,
+            max_blocks,
+        }
+    }
+
+    /// Records a new operation and clears the redo stack.
+    ///
+    /// Empty operations (e.g. a `/replace` that matched nothing) are ignored
+    /// so they do not use up an undo step.
+    pub fn push(&mut self, operation: Vec<BlockDiff>) {
+        if operation.is_empty() {
+            return;
+        }
+        self.blocks -= self.redo.drain(..).map(|op| op.len()).sum::<usize>();
+        self.blocks += operation.len();
+        self.undo.push_back(operation);
+
+        // Always keep the newest operation, even if it alone exceeds the
+        // block budget.
+        while self.undo.len() > 1
+            && (self.undo.len() > self.max_operations || self.blocks > self.max_blocks)
+        {
+            if let Some(dropped) = self.undo.pop_front() {
+                self.blocks -= dropped.len();
+            }
+        }
+    }
+
+    /// Pops the most recent operation and moves it to the redo stack.
+    ///
+    /// # Returns
+    /// The diffs that revert the operation, in the order they must be
+    /// applied, or `None` if there is nothing to undo.
+    pub fn undo(&mut self) -> Option<Vec<BlockDiff>> {
+        let operation = self.undo.pop_back()?;
+        let reverted = operation.iter().rev().map(BlockDiff::inverted).collect();
+        self.redo.push(operation);
+        Some(reverted)
+    }
+
+    /// Pops the most recently undone operation and moves it back to the
+    /// undo stack.
+    ///
+    /// # Returns
+    /// The diffs that re-apply the operation, or `None` if there is nothing
+    /// to redo.
+    pub fn redo(&mut self) -> Option<Vec<BlockDiff>> {
+        let operation = self.redo.pop()?;
+        let reapplied = operation.clone();
+        self.undo.push_back(operation);
+        Some(reapplied)
+    }
+
+    /// Forgets every recorded operation, e.g. when the world is replaced.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.blocks = 0;
+    }
+}
+
+impl World {
+    /// Sets the block at `(x, y, z)` as a player edit and returns the
+    /// resulting [`BlockDiff`].
+    ///
+    /// `facing` is only stored for [directional](BlockType::is_directional)
+    /// blocks.  Every edit that should be undoable goes through here so the
+    /// previous state is captured in one place.
+    pub fn set_block_recorded(
+        &mut self,
+        x: i32,
+        y: i32,
+        z: i32,
+        block: BlockType,
+        facing: Facing,
+    ) -> BlockDiff {
+        let before = self.get_block(x, y, z);
+        let before_facing = self.get_facing(x, y, z);
+        let after_facing = if block.is_directional() {
+            facing
+        } else {
+            Facing::default()
+        };
+
+        self.set_block_player(x, y, z, block);
+        if block.is_directional() {
+            self.set_facing(x, y, z, after_facing);
+        }
+        self.schedule_water_update(x, y, z);
+
+        BlockDiff {
+            x,
+            y,
+            z,
+            before,
+            after: block,
+            before_facing,
+            after_facing,
+        }
+    }
+
+    /// Writes the `after` state of every diff into the world.
+    ///
+    /// Used to replay undo/redo operations from [`EditHistory`].  Diffs are
+    /// applied unconditionally, so an undo also reverts any later change to
+    /// the same block made by someone else.
+    pub fn apply_diffs(&mut self, diffs: &[BlockDiff]) {
+        for diff in diffs {
+            self.set_block_player(diff.x, diff.y, diff.z, diff.after);
+            if diff.after.is_directional() {
+                self.set_facing(diff.x, diff.y, diff.z, diff.after_facing);
+            }
+            self.schedule_water_update(diff.x, diff.y, diff.z);
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use std::cmp::Ordering;
 use std::collections::HashSet;
-use std::thread;
+use std::thread::{self, JoinHandle};
 
 use crossbeam_channel::{Receiver, Sender, TryRecvError, bounded};
 
 use crate::core::chunk::Chunk;
+use crate::world::gen_config::WorldGenConfig;
 use crate::world::generator::ChunkGenerator;
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -114,6 +115,8 @@ pub struct ChunkLoader {
     pending: HashSet<(i32, i32)>,
     /// Number of worker threads created at construction time.
     worker_count: usize,
+    /// Handles of the worker threads, joined by [`ChunkLoader::shutdown`].
+    workers: Vec<JoinHandle<()>>,
 }
 
 impl ChunkLoader {
@@ -121,8 +124,17 @@ impl ChunkLoader {
     ///
     /// The worker count is typically `num_physical_cpus - 1` (clamped to a
     /// sensible range) so the main render thread retains at least one core.
-    pub fn new(seed: u32) -> Self {
-        Self::with_worker_count(crate::constants::get_chunk_worker_count(), seed)
+    ///
+    /// Chunks are generated from `seed` with water filling up to `sea_level`
+    /// (see [`World::sea_level`](crate::World::sea_level)) and the features
+    /// chosen in `config`.
+    pub fn new(seed: u32, sea_level: i32, config: WorldGenConfig) -> Self {
+        Self::with_worker_count(
+            crate::constants::get_chunk_worker_count(),
+            seed,
+            sea_level,
+            config,
+        )
     }
 
     /// Creates a loader with exactly `num_workers` background threads.
@@ -130,8 +142,9 @@ impl ChunkLoader {
     /// Each worker thread:
     /// 1. Clones the shared `request_rx` receiver (crossbeam channels are
     ///    multi-consumer safe).
-    /// 2. Constructs an independent [`ChunkGenerator`] from `seed` so no
-    ///    generator state is shared between threads.
+    /// 2. Constructs an independent [`ChunkGenerator`] from `seed`,
+    ///    `sea_level` and `config` so no generator state is shared between
+    ///    threads.
     /// 3. Enters a blocking `rx.recv()` loop, generating chunks on demand and
     ///    sending results back via `result_tx`.
     /// 4. Exits when `request_rx` is disconnected (i.e., when `ChunkLoader`
@@ -139,17 +152,23 @@ impl ChunkLoader {
     ///
     /// # Panics
     /// Panics if any worker thread cannot be spawned.
-    pub fn with_worker_count(num_workers: usize, seed: u32) -> Self {
+    pub fn with_worker_count(
+        num_workers: usize,
+        seed: u32,
+        sea_level: i32,
+        config: WorldGenConfig,
+    ) -> Self {
         let (request_tx, request_rx) = bounded::<ChunkGenRequest>(256);
         let (result_tx, result_rx) = bounded::<ChunkGenResult>(256);
 
+        let mut workers = Vec::with_capacity(num_workers);
         for worker_id in 0..num_workers {
             let rx = request_rx.clone();
             let tx = result_tx.clone();
             // Each worker owns its own generator — no mutex needed.
-            let generator = ChunkGenerator::new(seed);
+            let generator = ChunkGenerator::with_config(seed, sea_level, config);
 
-            thread::Builder::new()
+            let worker = thread::Builder::new()
                 .name(format!("chunk-gen-{}", worker_id))
                 .spawn(move || {
                     loop {
@@ -175,6 +194,7 @@ impl ChunkLoader {
                     }
                 })
                 .expect("Failed to spawn chunk generation worker");
+            workers.push(worker);
         }
 
         ChunkLoader {
@@ -182,7 +202,27 @@ impl ChunkLoader {
             result_rx,
             pending: HashSet::new(),
             worker_count: num_workers,
+            workers,
+        }
+    }
+
+    /// Stops the worker threads and waits for them to exit.
+    ///
+    /// Both channel ends held by the loader are swapped for disconnected
+    /// ones, so each worker exits the next time it tries to send a result or
+    /// finds the request channel empty.  A worker finishes at most one more
+    /// chunk, which keeps this quick even with a full queue.  Dropping a
+    /// loader without calling this still stops the workers, just without
+    /// waiting for them.
+    pub fn shutdown(&mut self) {
+        let (request_tx, _) = bounded(0);
+        let (_, result_rx) = bounded(0);
+        self.request_tx = request_tx;
+        self.result_rx = result_rx;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
         }
+        self.pending.clear();
     }
 
     // ── Request submission ────────────────────────────────────────────────── //
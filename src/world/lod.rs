@@ -0,0 +1,492 @@
+use crate::constants::*;
+use crate::core::biome::Biome;
+use crate::core::block::{BlockType, Facing};
+use crate::core::chunk::MeshLod;
+use crate::core::vertex::Vertex;
+use crate::render::mesh::{add_block_face, add_water_surface_quad};
+use crate::world::terrain::{World, face_color};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Mesh level of detail
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Edge length, in blocks, of one cell of a coarse mesh.
+const CELL: i32 = 2;
+
+/// Cells along X and Z of a sub-chunk.
+const CELLS_XZ: i32 = CHUNK_SIZE / CELL;
+
+/// Cells along Y of a sub-chunk.
+const CELLS_Y: i32 = SUBCHUNK_HEIGHT / CELL;
+
+/// Blocks out of a cell's eight that must fill it for the cell to be drawn.
+const CELL_FILL_THRESHOLD: usize = 4;
+
+/// Opaque, translucent and water `(vertices, indices)`, as returned by
+/// [`World::build_subchunk_mesh`].
+type SubchunkMeshes = (
+    (Vec<Vertex>, Vec<u32>),
+    (Vec<Vertex>, Vec<u32>),
+    (Vec<Vertex>, Vec<u32>),
+);
+
+/// Whether `block` counts towards filling a coarse cell.  Dead bushes,
+/// snow layers and torches are too thin to survive the downsampling.
+fn fills_cell(block: BlockType) -> bool {
+    !matches!(
+        block,
+        BlockType::Air | BlockType::DeadBush | BlockType::SnowLayer | BlockType::Torch
+    )
+}
+
+impl World {
+    /// Picks the [`MeshLod`] of every loaded chunk column from its distance
+    /// to the player's column and marks the sub-chunks of each column whose
+    /// LOD changed dirty, so they are remeshed at the new detail.  Its
+    /// neighbors are marked too, as faces along a border between LODs are
+    /// culled differently (see [`World::meshed_at_other_lod`]).
+    ///
+    /// # Returns
+    /// The number of columns whose LOD changed.
+    pub fn update_mesh_lods(&mut self, player_cx: i32, player_cz: i32) -> usize {
+        let mut changed = Vec::new();
+        for (&(cx, cz), chunk) in self.chunks.iter_mut() {
+            let distance = (cx - player_cx).abs().max((cz - player_cz).abs());
+            let lod = MeshLod::for_distance(chunk.mesh_lod, distance);
+            if lod != chunk.mesh_lod {
+                chunk.mesh_lod = lod;
+                for subchunk in &mut chunk.subchunks {
+                    subchunk.mark_mesh_dirty();
+                }
+                changed.push((cx, cz));
+            }
+        }
+        for &(cx, cz) in &changed {
+            self.mark_neighbor_borders_dirty(cx, cz);
+        }
+        changed.len()
+    }
+
+    /// Whether the block column at world `(wx, wz)` lies outside chunk column
+    /// `(chunk_x, chunk_z)`, in a loaded column meshed at a LOD other than
+    /// `lod`.
+    ///
+    /// The two meshes do not agree on which blocks are solid along such a
+    /// border, so faces there are drawn as if against air rather than culled;
+    /// otherwise the border shows cracks wherever one side dropped a block
+    /// the other side culled against.
+    pub(crate) fn meshed_at_other_lod(
+        &self,
+        chunk_x: i32,
+        chunk_z: i32,
+        lod: MeshLod,
+        wx: i32,
+        wz: i32,
+    ) -> bool {
+        let key = (wx.div_euclid(CHUNK_SIZE), wz.div_euclid(CHUNK_SIZE));
+        key != (chunk_x, chunk_z)
+            && self
+                .chunks
+                .get(&key)
+                .is_some_and(|chunk| chunk.mesh_lod != lod)
+    }
+
+    /// Builds the mesh of a sub-chunk at the [`MeshLod`] of its column: the
+    /// full mesh of [`World::build_subchunk_mesh`] or the coarse one of
+    /// [`World::build_coarse_subchunk_mesh`].
+    pub fn build_subchunk_mesh_at_lod(
+        &self,
+        chunk_x: i32,
+        chunk_z: i32,
+        subchunk_y: i32,
+    ) -> SubchunkMeshes {
+        let lod = self
+            .chunks
+            .get(&(chunk_x, chunk_z))
+            .map_or(MeshLod::Full, |chunk| chunk.mesh_lod);
+        match lod {
+            MeshLod::Full => self.build_subchunk_mesh(chunk_x, chunk_z, subchunk_y),
+            MeshLod::Coarse => self.build_coarse_subchunk_mesh(chunk_x, chunk_z, subchunk_y),
+        }
+    }
+
+    /// Builds a coarse mesh of a sub-chunk for distant columns.
+    ///
+    /// Every 2×2×2 group of blocks becomes one cell, drawn as a single cube
+    /// of the majority block type when at least half of its blocks are
+    /// filled (ties go to the upper layer, so grass stays on top of dirt).
+    /// Cell faces are then greedy-merged like full-detail faces, each quad
+    /// covering twice as many blocks.  Water cells only draw their surface
+    /// against open air, and partial blocks are drawn as full cubes.  Faces
+    /// against a full-detail column are never culled, so the border to it
+    /// stays closed.
+    ///
+    /// The result has the same layout as [`World::build_subchunk_mesh`] and
+    /// goes through the same upload and culling path.
+    pub fn build_coarse_subchunk_mesh(
+        &self,
+        chunk_x: i32,
+        chunk_z: i32,
+        subchunk_y: i32,
+    ) -> SubchunkMeshes {
+        let mut vertices = Vec::with_capacity(1024);
+        let mut indices = Vec::with_capacity(512);
+        let mut translucent_vertices = Vec::new();
+        let mut translucent_indices = Vec::new();
+        let mut water_vertices = Vec::new();
+        let mut water_indices = Vec::new();
+
+        let base_x = chunk_x * CHUNK_SIZE;
+        let base_y = subchunk_y * SUBCHUNK_HEIGHT;
+        let base_z = chunk_z * CHUNK_SIZE;
+
+        // ── Cell cache setup ──────────────────────────────────────────────── //
+        // 1-cell padding on all sides so neighbor cells never need to be
+        // downsampled again during the face visibility and merge tests.
+        const S: usize = CELLS_XZ as usize + 2;
+        const SH: usize = CELLS_Y as usize + 2;
+        let mut cell_cache = [BlockType::Air; S * SH * S];
+
+        // Unloaded neighboring chunks read as full Water/Air below/above sea
+        // level, as in `build_subchunk_mesh`.
+        let fetch = |wx: i32, wy: i32, wz: i32| -> BlockType {
+            if !(0..WORLD_HEIGHT).contains(&wy) {
+                return BlockType::Air;
+            }
+            let key = (wx.div_euclid(CHUNK_SIZE), wz.div_euclid(CHUNK_SIZE));
+            match self.chunks.get(&key) {
+                Some(chunk) => {
+                    chunk.get_block(wx.rem_euclid(CHUNK_SIZE), wy, wz.rem_euclid(CHUNK_SIZE))
+                }
+                None if wy < self.sea_level() => BlockType::Water,
+                None => BlockType::Air,
+            }
+        };
+
+        // Downsample the cell whose lowest corner block is (wx, wy, wz).
+        let downsample = |wx: i32, wy: i32, wz: i32| -> BlockType {
+            // At most eight distinct types; the upper layer is counted first
+            // so it wins ties below.
+            let mut counts: Vec<(BlockType, usize)> = Vec::with_capacity(8);
+            let mut filled = 0;
+            for dy in (0..CELL).rev() {
+                for dx in 0..CELL {
+                    for dz in 0..CELL {
+                        let block = fetch(wx + dx, wy + dy, wz + dz);
+                        if !fills_cell(block) {
+                            continue;
+                        }
+                        filled += 1;
+                        match counts.iter_mut().find(|(b, _)| *b == block) {
+                            Some((_, count)) => *count += 1,
+                            None => counts.push((block, 1)),
+                        }
+                    }
+                }
+            }
+            if filled < CELL_FILL_THRESHOLD {
+                return BlockType::Air;
+            }
+            let mut majority = counts[0];
+            for &(block, count) in &counts[1..] {
+                if count > majority.1 {
+                    majority = (block, count);
+                }
+            }
+            majority.0
+        };
+
+        // Layout is [px][py][pz] linearised as `px * SH * S + py * S + pz`,
+        // with (px, py, pz) being padded cell coords.
+        for px in 0..S as i32 {
+            for py in 0..SH as i32 {
+                for pz in 0..S as i32 {
+                    let index = (px as usize) * SH * S + (py as usize) * S + (pz as usize);
+                    cell_cache[index] = downsample(
+                        base_x + (px - 1) * CELL,
+                        base_y + (py - 1) * CELL,
+                        base_z + (pz - 1) * CELL,
+                    );
+                }
+            }
+        }
+
+        // Cell lookup in sub-chunk-local cell coordinates.
+        let get_cell = |cx: i32, cy: i32, cz: i32| -> BlockType {
+            cell_cache[((cx + 1) as usize) * SH * S + ((cy + 1) as usize) * S + (cz + 1) as usize]
+        };
+
+        // Biome cache, sampled once per cell column at its center.
+        let mut biome_map = [[None::<Biome>; CELLS_XZ as usize]; CELLS_XZ as usize];
+
+        // Faces merge only when all fields are equal; colors are quantized
+        // as in `build_subchunk_mesh`.
+        #[derive(Clone, Copy, PartialEq)]
+        struct CellFace {
+            block: BlockType,
+            color: [u8; 3],
+            tex_index: u8,
+        }
+
+        for face_dir in 0..6 {
+            let (slice_count, dim1_size, dim2_size) = match face_dir {
+                0 | 1 => (CELLS_XZ, CELLS_Y, CELLS_XZ),
+                2 | 3 => (CELLS_Y, CELLS_XZ, CELLS_XZ),
+                4 | 5 => (CELLS_XZ, CELLS_XZ, CELLS_Y),
+                _ => unreachable!(),
+            };
+            let (ox, oy, oz) = match face_dir {
+                0 => (-1, 0, 0),
+                1 => (1, 0, 0),
+                2 => (0, -1, 0),
+                3 => (0, 1, 0),
+                4 => (0, 0, -1),
+                _ => (0, 0, 1),
+            };
+
+            for slice in 0..slice_count {
+                let mut mask: Vec<Option<CellFace>> = vec![None; (dim1_size * dim2_size) as usize];
+
+                for d1 in 0..dim1_size {
+                    for d2 in 0..dim2_size {
+                        let (cx, cy, cz) = match face_dir {
+                            0 | 1 => (slice, d1, d2),
+                            2 | 3 => (d1, slice, d2),
+                            _ => (d1, d2, slice),
+                        };
+                        let block = get_cell(cx, cy, cz);
+                        let neighbor = if self.meshed_at_other_lod(
+                            chunk_x,
+                            chunk_z,
+                            MeshLod::Coarse,
+                            base_x + (cx + ox) * CELL,
+                            base_z + (cz + oz) * CELL,
+                        ) {
+                            BlockType::Air
+                        } else {
+                            get_cell(cx + ox, cy + oy, cz + oz)
+                        };
+
+                        let visible = if block == BlockType::Water {
+                            face_dir == 3 && neighbor == BlockType::Air
+                        } else {
+                            block != BlockType::Air && block.should_render_face_against(neighbor)
+                        };
+                        if !visible {
+                            continue;
+                        }
+
+                        let biome = if block == BlockType::Grass || block == BlockType::Leaves {
+                            let column = &mut biome_map[cx as usize][cz as usize];
+                            if column.is_none() {
+                                *column = Some(self.get_biome(
+                                    base_x + cx * CELL + CELL / 2,
+                                    base_z + cz * CELL + CELL / 2,
+                                ));
+                            }
+                            *column
+                        } else {
+                            None
+                        };
+                        let color = face_color(block, face_dir, biome);
+                        mask[(d1 * dim2_size + d2) as usize] = Some(CellFace {
+                            block,
+                            color: color.map(|c| ((c * 255.0) as u8) & 0xFC),
+                            tex_index: block.texture_for_face(face_dir as usize, Facing::default())
+                                as u8,
+                        });
+                    }
+                }
+
+                // ── Greedy merge and emit quads ───────────────────────────── //
+                for d1 in 0..dim1_size {
+                    let mut d2 = 0;
+                    while d2 < dim2_size {
+                        let Some(face) = mask[(d1 * dim2_size + d2) as usize] else {
+                            d2 += 1;
+                            continue;
+                        };
+
+                        let mut width = 1;
+                        while d2 + width < dim2_size
+                            && mask[(d1 * dim2_size + d2 + width) as usize] == Some(face)
+                        {
+                            width += 1;
+                        }
+                        let mut height = 1;
+                        while d1 + height < dim1_size
+                            && (0..width).all(|w| {
+                                mask[((d1 + height) * dim2_size + d2 + w) as usize] == Some(face)
+                            })
+                        {
+                            height += 1;
+                        }
+                        for h in 0..height {
+                            for w in 0..width {
+                                mask[((d1 + h) * dim2_size + d2 + w) as usize] = None;
+                            }
+                        }
+
+                        // The merged cells as a box in cell coordinates,
+                        // scaled to world space and flattened onto the face.
+                        let (lo, hi) = match face_dir {
+                            0 | 1 => ([slice, d1, d2], [slice + 1, d1 + height, d2 + width]),
+                            2 | 3 => ([d1, slice, d2], [d1 + height, slice + 1, d2 + width]),
+                            _ => ([d1, d2, slice], [d1 + height, d2 + width, slice + 1]),
+                        };
+                        let base = [base_x, base_y, base_z];
+                        let mut min = [0.0; 3];
+                        let mut max = [0.0; 3];
+                        for axis in 0..3 {
+                            min[axis] = (base[axis] + lo[axis] * CELL) as f32;
+                            max[axis] = (base[axis] + hi[axis] * CELL) as f32;
+                        }
+                        let axis = face_dir as usize / 2;
+                        if face_dir % 2 == 0 {
+                            max[axis] = min[axis];
+                        } else {
+                            min[axis] = max[axis];
+                        }
+
+                        let color = face.color.map(|c| c as f32 / 255.0);
+                        let tex_index = face.tex_index as f32;
+                        if face.block == BlockType::Water {
+                            add_water_surface_quad(
+                                &mut water_vertices,
+                                &mut water_indices,
+                                min,
+                                [max[0] - min[0], max[2] - min[2]],
+                                color,
+                                tex_index,
+                            );
+                        } else {
+                            let (target_verts, target_inds) = if face.block.is_translucent() {
+                                (&mut translucent_vertices, &mut translucent_indices)
+                            } else {
+                                (&mut vertices, &mut indices)
+                            };
+                            add_block_face(
+                                target_verts,
+                                target_inds,
+                                face_dir,
+                                [min, max],
+                                color,
+                                tex_index,
+                            );
+                        }
+
+                        d2 += width;
+                    }
+                }
+            }
+        }
+
+        (
+            (vertices, indices),
+            (translucent_vertices, translucent_indices),
+            (water_vertices, water_indices),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::chunk::Chunk;
+
+    #[test]
+    fn lod_switches_with_hysteresis() {
+        use MeshLod::*;
+        assert_eq!(MeshLod::for_distance(Full, MESH_LOD_DISTANCE), Coarse);
+        assert_eq!(MeshLod::for_distance(Full, MESH_LOD_DISTANCE - 1), Full);
+        assert_eq!(MeshLod::for_distance(Coarse, MESH_LOD_DISTANCE - 1), Coarse);
+        assert_eq!(MeshLod::for_distance(Coarse, MESH_LOD_DISTANCE - 2), Full);
+    }
+
+    #[test]
+    fn coarse_mesh_keeps_the_surface_with_fewer_faces() {
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        // A bumpy grass surface that full detail cannot merge much.
+        for x in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                let top = 6 + (x + z) % 3;
+                for y in 0..top {
+                    world.set_block(x, y, z, BlockType::Dirt);
+                }
+                world.set_block(x, top, z, BlockType::Grass);
+            }
+        }
+
+        let ((full, _), _, _) = world.build_subchunk_mesh(0, 0, 0);
+        let ((coarse, _), _, _) = world.build_coarse_subchunk_mesh(0, 0, 0);
+        assert!(
+            coarse.len() * 2 < full.len(),
+            "{} vs {}",
+            coarse.len(),
+            full.len()
+        );
+        // Every quad lies on the 2-block cell grid, with grass on top.
+        assert!(
+            coarse
+                .iter()
+                .all(|v| v.position.iter().all(|&p| p % 2.0 == 0.0))
+        );
+        let top_y = coarse.iter().map(|v| v.position[1]).fold(0.0, f32::max);
+        assert!((8.0..=10.0).contains(&top_y));
+    }
+
+    #[test]
+    fn faces_along_an_lod_border_are_not_culled() {
+        let mut world = World::new_empty_with_seed(1);
+        for cx in 0..2 {
+            world.chunks.insert((cx, 0), Chunk::new(cx, 0));
+        }
+        for x in 0..2 * CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                for y in 0..4 {
+                    world.set_block(x, y, z, BlockType::Stone);
+                }
+            }
+        }
+        let border = CHUNK_SIZE as f32;
+        let faces_on_border = |vertices: &[Vertex]| {
+            vertices
+                .iter()
+                .filter(|v| v.position[0] == border && v.packed & 0x7 < 2)
+                .count()
+        };
+
+        // Both columns at full detail cull the solid border away.
+        let ((full, _), _, _) = world.build_subchunk_mesh(0, 0, 0);
+        assert_eq!(faces_on_border(&full), 0);
+
+        // With the eastern column coarse, both sides close the border.
+        world.chunks.get_mut(&(1, 0)).unwrap().mesh_lod = MeshLod::Coarse;
+        let ((full, _), _, _) = world.build_subchunk_mesh(0, 0, 0);
+        let ((coarse, _), _, _) = world.build_coarse_subchunk_mesh(1, 0, 0);
+        assert!(faces_on_border(&full) > 0);
+        assert!(faces_on_border(&coarse) > 0);
+    }
+
+    #[test]
+    fn crossing_the_lod_distance_remeshes_the_column() {
+        let mut world = World::new_empty_with_seed(1);
+        for cx in -1..=1 {
+            for cz in -1..=1 {
+                let mut chunk = Chunk::new(cx, cz);
+                for subchunk in &mut chunk.subchunks {
+                    subchunk.clear_mesh_dirty(subchunk.mesh_version);
+                }
+                world.chunks.insert((cx, cz), chunk);
+            }
+        }
+
+        assert_eq!(world.update_mesh_lods(0, 0), 0);
+        assert_eq!(world.update_mesh_lods(MESH_LOD_DISTANCE + 1, 0), 9);
+        let chunk = &world.chunks[&(1, 0)];
+        assert_eq!(chunk.mesh_lod, MeshLod::Coarse);
+        assert!(chunk.subchunks.iter().all(|s| s.mesh_dirty));
+        assert_eq!(world.update_mesh_lods(MESH_LOD_DISTANCE + 1, 0), 0);
+    }
+}
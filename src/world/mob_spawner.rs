@@ -0,0 +1,113 @@
+use glam::Vec3;
+use rand::{random, random_range};
+
+use crate::constants::{
+    MOB_CAP, MOB_DESPAWN_DISTANCE, MOB_SPAWN_INTERVAL, MOB_SPAWN_MAX_DISTANCE,
+    MOB_SPAWN_MIN_DISTANCE, WORLD_HEIGHT,
+};
+use crate::core::block::BlockType;
+use crate::core::mobs::entity::MobEntity;
+use crate::core::mobs::mobs_registry::all_mobs;
+use crate::world::World;
+
+/// Horizontal distance between two points, ignoring height.
+fn horizontal_distance(a: Vec3, b: Vec3) -> f32 {
+    (a.x - b.x).hypot(a.z - b.z)
+}
+
+impl World {
+    /// Spawns, moves and despawns mobs for one frame.
+    ///
+    /// Mobs only exist at night: when `night` is `false` (daybreak) every
+    /// mob is removed.  At night a spawn is attempted on average every
+    /// [`MOB_SPAWN_INTERVAL`] seconds until [`MOB_CAP`] mobs are alive, and
+    /// mobs farther than [`MOB_DESPAWN_DISTANCE`] from every player are
+    /// removed.
+    ///
+    /// # Arguments
+    /// * `dt` - Frame time in seconds.
+    /// * `players` - Foot positions of all players; mobs spawn around them.
+    /// * `night` - Whether the sun is below the horizon.
+    pub fn update_mobs(&mut self, dt: f32, players: &[Vec3], night: bool) {
+        if !night {
+            self.mobs.clear();
+            return;
+        }
+
+        self.mobs.retain(|mob| {
+            players
+                .iter()
+                .any(|&p| horizontal_distance(mob.position, p) <= MOB_DESPAWN_DISTANCE)
+        });
+
+        // Mobs read the world while moving, so take them out of it first.
+        let mut mobs = std::mem::take(&mut self.mobs);
+        for mob in &mut mobs {
+            mob.update(self, dt);
+        }
+        self.mobs = mobs;
+
+        if self.mobs.len() < MOB_CAP
+            && !players.is_empty()
+            && random::<f32>() < dt / MOB_SPAWN_INTERVAL
+            && let Some(mob) = self.try_spawn_mob(players)
+        {
+            self.mobs.push(mob);
+        }
+    }
+
+    /// Picks a random spot in the spawn ring around a random player and
+    /// returns a random mob standing on it, if it is a grass block with
+    /// room above and no player is too close.
+    fn try_spawn_mob(&self, players: &[Vec3]) -> Option<MobEntity> {
+        let center = players[random_range(0..players.len())];
+        let angle = random_range(0.0..std::f32::consts::TAU);
+        let distance = random_range(MOB_SPAWN_MIN_DISTANCE..MOB_SPAWN_MAX_DISTANCE);
+        let x = (center.x + angle.cos() * distance).floor() as i32;
+        let z = (center.z + angle.sin() * distance).floor() as i32;
+
+        // Highest non-air block in the column; unloaded chunks are all air.
+        let y = (0..WORLD_HEIGHT)
+            .rev()
+            .find(|&y| self.get_block(x, y, z) != BlockType::Air)?;
+        if self.get_block(x, y, z) != BlockType::Grass {
+            return None;
+        }
+
+        let position = Vec3::new(x as f32 + 0.5, (y + 1) as f32, z as f32 + 0.5);
+        if players
+            .iter()
+            .any(|&p| horizontal_distance(position, p) < MOB_SPAWN_MIN_DISTANCE)
+        {
+            return None;
+        }
+
+        let kinds = all_mobs();
+        let kind = kinds[random_range(0..kinds.len())];
+        let mob = MobEntity::new(kind, position, random_range(0.0..std::f32::consts::TAU));
+        (!mob.collides_at(self, position)).then_some(mob)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mob_at(x: f32, z: f32) -> MobEntity {
+        MobEntity::new(all_mobs()[0], Vec3::new(x, 80.0, z), 0.0)
+    }
+
+    #[test]
+    fn far_mobs_despawn_and_daybreak_clears_all() {
+        let mut world = World::new_empty_with_seed(1);
+        world.mobs = vec![mob_at(10.0, 0.0), mob_at(MOB_DESPAWN_DISTANCE + 10.0, 0.0)];
+
+        // No chunks are loaded, so nothing can spawn in their place.
+        world.update_mobs(0.05, &[Vec3::ZERO], true);
+        assert_eq!(world.mobs.len(), 1);
+        assert!(world.mobs[0].position.x < MOB_DESPAWN_DISTANCE);
+
+        world.update_mobs(0.05, &[Vec3::ZERO], false);
+        assert!(world.mobs.is_empty());
+    }
+}
@@ -1,10 +1,24 @@
 mod device_info;
+pub mod edit;
+pub mod gen_config;
 pub mod generator;
+pub mod history;
 pub mod loader;
+mod lod;
+pub mod mob_spawner;
+pub mod mutation;
 mod spline;
 pub mod structures;
 pub mod terrain;
+pub mod time;
+pub mod water;
 
+pub use edit::{BoxEdit, BoxEditKind};
+pub use gen_config::{TerrainShape, WorldGenConfig, WorldPreset};
 pub use generator::ChunkGenerator;
+pub use history::{BlockDiff, EditHistory};
 pub use loader::{ChunkGenResult, ChunkLoader};
+pub use mutation::{AppliedMutations, BlockMutation, BlockMutationQueue, MutationSource};
 pub use terrain::World;
+pub use time::{DAY_LENGTH, is_night, next_morning, sun_direction, time_difference};
+pub use water::WaterUpdateQueue;
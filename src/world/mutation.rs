@@ -0,0 +1,464 @@
+use std::collections::VecDeque;
+
+use crate::constants::*;
+use crate::core::block::{BlockType, Facing};
+use crate::world::history::BlockDiff;
+use crate::world::terrain::World;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Serialized block mutations
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Where a queued block change came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutationSource {
+    /// Placed or broken by the local player: recorded for undo and sent to
+    /// the server once applied.
+    Local,
+    /// Received from the server: applied as-is.
+    Remote,
+}
+
+/// One block change waiting in a [`BlockMutationQueue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockMutation {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub block: BlockType,
+    /// Only kept for [directional](BlockType::is_directional) blocks.
+    pub facing: Facing,
+    pub source: MutationSource,
+}
+
+/// What [`BlockMutationQueue::apply`] did.
+#[derive(Debug, Default)]
+pub struct AppliedMutations {
+    /// The diffs of each applied local change, in application order, for
+    /// the caller to record in the edit history and replicate to the server.
+    /// A change that removes the support of a [`BlockType::SnowLayer`]
+    /// also carries the diff clearing that layer.
+    pub local: Vec<Vec<BlockDiff>>,
+    /// Remote changes whose chunk is not loaded yet.  The caller keeps them
+    /// and pushes them again once the chunk arrives.
+    pub deferred: Vec<BlockMutation>,
+}
+
+/// FIFO of single-block changes from every source, applied together once per
+/// frame.
+///
+/// Local edits (placing, breaking) and changes received from the server are
+/// pushed as they happen and only written to the world by
+/// [`BlockMutationQueue::apply`], which the caller runs under one write lock.
+/// Changes are applied strictly in push order, so two edits of the same block
+/// in the same frame always resolve to the later one, and every change marks
+/// the sub-chunks it touches dirty in the same critical section: a mesh
+/// worker never sees a new block without its dirty mark.
+#[derive(Debug, Default)]
+pub struct BlockMutationQueue {
+    pending: VecDeque<BlockMutation>,
+}
+
+impl BlockMutationQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a block placed (or, with [`BlockType::Air`], broken) by the
+    /// local player.
+    pub fn push_local(&mut self, x: i32, y: i32, z: i32, block: BlockType, facing: Facing) {
+        self.push(BlockMutation {
+            x,
+            y,
+            z,
+            block,
+            facing,
+            source: MutationSource::Local,
+        });
+    }
+
+    /// Queues a block change received from the server.
+    pub fn push_remote(&mut self, x: i32, y: i32, z: i32, block: BlockType, facing: Facing) {
+        self.push(BlockMutation {
+            x,
+            y,
+            z,
+            block,
+            facing,
+            source: MutationSource::Remote,
+        });
+    }
+
+    /// Queues an already built mutation behind everything pending.
+    pub fn push(&mut self, mutation: BlockMutation) {
+        self.pending.push_back(mutation);
+    }
+
+    /// Puts previously [deferred](AppliedMutations::deferred) changes back
+    /// at the front of the queue, keeping their relative order.
+    ///
+    /// They arrived before anything queued since, so they must also be
+    /// applied first: otherwise a newer change to the same block received
+    /// while its chunk was loading would be overwritten by an older one.
+    pub fn requeue(&mut self, mutations: impl IntoIterator<Item = BlockMutation>) {
+        let mutations: Vec<_> = mutations.into_iter().collect();
+        for mutation in mutations.into_iter().rev() {
+            self.pending.push_front(mutation);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Drops every queued change, e.g. when the world is replaced.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Applies and removes every queued change, oldest first.
+    ///
+    /// Each applied change marks the sub-chunk that owns the block, and any
+    /// neighbour it borders, dirty via [`World::mark_block_dirty`].  Local
+    /// changes to unloaded blocks are dropped, since the player can only
+    /// target loaded ones; remote ones are handed back in
+    /// [`AppliedMutations::deferred`].
+    pub fn apply(&mut self, world: &mut World) -> AppliedMutations {
+        let mut applied = AppliedMutations::default();
+        for mutation in self.pending.drain(..) {
+            let BlockMutation { x, y, z, .. } = mutation;
+            if !world.is_block_loaded(x, y, z) {
+                if mutation.source == MutationSource::Remote {
+                    applied.deferred.push(mutation);
+                }
+                continue;
+            }
+
+            match mutation.source {
+                MutationSource::Local => {
+                    let mut diffs =
+                        vec![world.set_block_recorded(x, y, z, mutation.block, mutation.facing)];
+                    // Snow cannot float: it goes with the block it lay on.
+                    if !mutation.block.is_solid()
+                        && world.get_block(x, y + 1, z) == BlockType::SnowLayer
+                    {
+                        diffs.push(world.set_block_recorded(
+                            x,
+                            y + 1,
+                            z,
+                            BlockType::Air,
+                            Facing::default(),
+                        ));
+                        world.mark_block_dirty(x, y + 1, z);
+                    }
+                    applied.local.push(diffs);
+                }
+                MutationSource::Remote => {
+                    world.set_block_player(x, y, z, mutation.block);
+                    if mutation.block.is_directional() {
+                        world.set_facing(x, y, z, mutation.facing);
+                    }
+                    world.schedule_water_update(x, y, z);
+                }
+            }
+            world.mark_block_dirty(x, y, z);
+        }
+        applied
+    }
+}
+
+impl World {
+    /// Marks the sub-chunk containing block `(x, y, z)`, and each neighbouring
+    /// sub-chunk whose faces the block borders, dirty so their meshes are
+    /// rebuilt.
+    ///
+    /// A block on a chunk or sub-chunk boundary affects the visible faces of
+    /// the adjacent one, so up to six neighbours (±X, ±Z columns and the
+    /// sub-chunks above and below) are marked as well.  Unloaded chunks and
+    /// out-of-range heights are skipped.
+    pub fn mark_block_dirty(&mut self, x: i32, y: i32, z: i32) {
+        if !(0..WORLD_HEIGHT).contains(&y) {
+            return;
+        }
+        let cx = x.div_euclid(CHUNK_SIZE);
+        let cz = z.div_euclid(CHUNK_SIZE);
+        let sy = y / SUBCHUNK_HEIGHT;
+        let lx = x.rem_euclid(CHUNK_SIZE);
+        let lz = z.rem_euclid(CHUNK_SIZE);
+        let ly = y.rem_euclid(SUBCHUNK_HEIGHT);
+
+        let mut targets = vec![(cx, cz, sy)];
+        if lx == 0 {
+            targets.push((cx - 1, cz, sy));
+        }
+        if lx == CHUNK_SIZE - 1 {
+            targets.push((cx + 1, cz, sy));
+        }
+        if lz == 0 {
+            targets.push((cx, cz - 1, sy));
+        }
+        if lz == CHUNK_SIZE - 1 {
+            targets.push((cx, cz + 1, sy));
+        }
+        if ly == 0 && sy > 0 {
+            targets.push((cx, cz, sy - 1));
+        }
+        if ly == SUBCHUNK_HEIGHT - 1 && sy < NUM_SUBCHUNKS - 1 {
+            targets.push((cx, cz, sy + 1));
+        }
+
+        for (cx, cz, sy) in targets {
+            if let Some(chunk) = self.chunks.get_mut(&(cx, cz))
+                && let Some(subchunk) = chunk.subchunks.get_mut(sy as usize)
+            {
+                subchunk.mark_mesh_dirty();
+            }
+        }
+    }
+
+    /// Marks every sub-chunk of the four loaded columns around chunk
+    /// `(cx, cz)` dirty after that chunk was inserted.
+    ///
+    /// A column meshed while its neighbour was missing drew faces against
+    /// the assumed air (or water) at the shared border; rebuilding it now
+    /// culls the ones the new chunk covers.  The new chunk's own sub-chunks
+    /// start dirty anyway.
+    pub fn mark_neighbor_borders_dirty(&mut self, cx: i32, cz: i32) {
+        for neighbor in [(cx - 1, cz), (cx + 1, cz), (cx, cz - 1), (cx, cz + 1)] {
+            if let Some(chunk) = self.chunks.get_mut(&neighbor) {
+                for subchunk in &mut chunk.subchunks {
+                    subchunk.mark_mesh_dirty();
+                }
+            }
+        }
+    }
+
+    /// Returns [`SubChunk::mesh_version`](crate::core::chunk::SubChunk::mesh_version)
+    /// of the given sub-chunk, or `0` if it is not loaded.
+    pub fn subchunk_mesh_version(&self, cx: i32, cz: i32, sy: i32) -> u32 {
+        self.chunks
+            .get(&(cx, cz))
+            .and_then(|chunk| chunk.subchunks.get(sy as usize))
+            .map_or(0, |subchunk| subchunk.mesh_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use super::*;
+    use crate::core::chunk::Chunk;
+
+    /// A 3×3 grid of empty chunks around the origin with every dirty flag
+    /// cleared, so the test can see exactly what a change marks.
+    fn clean_world() -> World {
+        let mut world = World::new_empty_with_seed(1);
+        for cx in -1..=1 {
+            for cz in -1..=1 {
+                world.chunks.insert((cx, cz), Chunk::new(cx, cz));
+            }
+        }
+        for chunk in world.chunks.values_mut() {
+            for subchunk in &mut chunk.subchunks {
+                subchunk.clear_mesh_dirty(subchunk.mesh_version);
+            }
+        }
+        world
+    }
+
+    /// Keys `(cx, cz, sy)` of every sub-chunk whose mesh is marked dirty.
+    fn dirty_subchunks(world: &World) -> HashSet<(i32, i32, i32)> {
+        let mut dirty = HashSet::new();
+        for (&(cx, cz), chunk) in &world.chunks {
+            for (sy, subchunk) in chunk.subchunks.iter().enumerate() {
+                if subchunk.mesh_dirty {
+                    dirty.insert((cx, cz, sy as i32));
+                }
+            }
+        }
+        dirty
+    }
+
+    #[test]
+    fn changes_mark_the_subchunks_they_border() {
+        let top = SUBCHUNK_HEIGHT - 1;
+        let cases = [
+            // Interior: only its own sub-chunk.
+            ((4, 4, 4), vec![(0, 0, 0)]),
+            // Chunk borders: the column across the border too.
+            ((0, 4, 4), vec![(0, 0, 0), (-1, 0, 0)]),
+            ((4, 4, CHUNK_SIZE - 1), vec![(0, 0, 0), (0, 1, 0)]),
+            // Chunk corner on a sub-chunk border: three neighbours.
+            (
+                (CHUNK_SIZE - 1, top, 0),
+                vec![(0, 0, 0), (1, 0, 0), (0, -1, 0), (0, 0, 1)],
+            ),
+            // Sub-chunk border: the one below.
+            ((4, SUBCHUNK_HEIGHT, 4), vec![(0, 0, 1), (0, 0, 0)]),
+            // Bottom of the world: nothing below to mark.
+            ((4, 0, 4), vec![(0, 0, 0)]),
+        ];
+        for ((x, y, z), expected) in cases {
+            let mut world = clean_world();
+            let mut queue = BlockMutationQueue::new();
+            queue.push_local(x, y, z, BlockType::Stone, Facing::default());
+            queue.apply(&mut world);
+            assert_eq!(
+                dirty_subchunks(&world),
+                expected.into_iter().collect(),
+                "sub-chunks marked by a change at {x},{y},{z}"
+            );
+        }
+    }
+
+    #[test]
+    fn interleaved_local_and_remote_changes_apply_in_order() {
+        let mut world = clean_world();
+        let mut queue = BlockMutationQueue::new();
+        let palette = [
+            BlockType::Air,
+            BlockType::Stone,
+            BlockType::Dirt,
+            BlockType::Sand,
+        ];
+
+        // Deterministic LCG so the test is reproducible.  Coordinates stay in
+        // a small box straddling the chunk corner at the origin and one
+        // sub-chunk boundary, so many changes hit the same blocks and
+        // boundaries from both sources.
+        let mut seed = 0x2545_f491_u32;
+        let mut next = |range: i32| {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            ((seed >> 8) % range as u32) as i32
+        };
+
+        let mut expected: HashMap<(i32, i32, i32), BlockType> = HashMap::new();
+        let mut local_count = 0;
+        for _ in 0..5000 {
+            let x = next(6) - 3;
+            let y = SUBCHUNK_HEIGHT - 3 + next(6);
+            let z = next(6) - 3;
+            let block = palette[next(palette.len() as i32) as usize];
+            if next(2) == 0 {
+                queue.push_local(x, y, z, block, Facing::default());
+                local_count += 1;
+            } else {
+                queue.push_remote(x, y, z, block, Facing::default());
+            }
+            expected.insert((x, y, z), block);
+        }
+        // A remote change for a chunk that is not loaded is handed back.
+        queue.push_remote(5 * CHUNK_SIZE, 10, 0, BlockType::Stone, Facing::default());
+
+        let applied = queue.apply(&mut world);
+
+        assert!(queue.is_empty());
+        assert_eq!(applied.local.len(), local_count);
+        assert_eq!(applied.deferred.len(), 1);
+        assert_eq!(applied.deferred[0].x, 5 * CHUNK_SIZE);
+
+        for (&(x, y, z), &block) in &expected {
+            assert_eq!(world.get_block(x, y, z), block, "block at {x},{y},{z}");
+        }
+        // The box covers the two sub-chunks of each column around the
+        // corner, and no change reaches past them.
+        let around_corner: HashSet<_> = [(-1, -1), (-1, 0), (0, -1), (0, 0)]
+            .into_iter()
+            .flat_map(|(cx, cz)| [(cx, cz, 0), (cx, cz, 1)])
+            .collect();
+        assert_eq!(dirty_subchunks(&world), around_corner);
+
+        // Once the chunk loads, the deferred change goes ahead of a newer
+        // one for the same block that was queued in the meantime.
+        world.chunks.insert((5, 0), Chunk::new(5, 0));
+        queue.push_remote(5 * CHUNK_SIZE, 10, 0, BlockType::Sand, Facing::default());
+        queue.requeue(applied.deferred);
+        queue.apply(&mut world);
+        assert_eq!(world.get_block(5 * CHUNK_SIZE, 10, 0), BlockType::Sand);
+    }
+
+    #[test]
+    fn remote_stairs_keep_their_facing() {
+        let mut world = clean_world();
+        let mut queue = BlockMutationQueue::new();
+        queue.push_remote(4, 4, 4, BlockType::WoodStairs, Facing::West);
+        queue.push_remote(5, 4, 4, BlockType::Stone, Facing::West);
+        queue.apply(&mut world);
+
+        assert_eq!(world.get_facing(4, 4, 4), Facing::West);
+        assert_eq!(world.get_facing(5, 4, 4), Facing::default());
+    }
+
+    #[test]
+    fn breaking_the_block_under_snow_clears_it() {
+        let mut world = clean_world();
+        let mut queue = BlockMutationQueue::new();
+        world.set_block(4, 4, 4, BlockType::Dirt);
+        world.set_block(4, 5, 4, BlockType::SnowLayer);
+
+        queue.push_local(4, 4, 4, BlockType::Air, Facing::default());
+        let applied = queue.apply(&mut world);
+
+        assert_eq!(world.get_block(4, 5, 4), BlockType::Air);
+        assert_eq!(applied.local.len(), 1);
+        let changed: Vec<_> = applied.local[0].iter().map(|d| (d.y, d.before)).collect();
+        assert_eq!(changed, [(4, BlockType::Dirt), (5, BlockType::SnowLayer)]);
+
+        // Placing a block keeps the snow it now carries.
+        queue.push_local(4, 4, 4, BlockType::Stone, Facing::default());
+        queue.push_local(4, 5, 4, BlockType::SnowLayer, Facing::default());
+        queue.apply(&mut world);
+        queue.push_local(4, 4, 4, BlockType::Sand, Facing::default());
+        queue.apply(&mut world);
+        assert_eq!(world.get_block(4, 5, 4), BlockType::SnowLayer);
+    }
+
+    #[test]
+    fn mesh_built_before_a_change_keeps_the_subchunk_dirty() {
+        let mut world = clean_world();
+        let mut queue = BlockMutationQueue::new();
+
+        // A mesh worker snapshots the version, then the block changes before
+        // its result is uploaded.
+        let built_version = world.subchunk_mesh_version(0, 0, 0);
+        queue.push_remote(4, 4, 4, BlockType::Stone, Facing::default());
+        queue.apply(&mut world);
+
+        let subchunk = &mut world.chunks.get_mut(&(0, 0)).unwrap().subchunks[0];
+        subchunk.clear_mesh_dirty(built_version);
+        assert!(subchunk.mesh_dirty);
+
+        subchunk.clear_mesh_dirty(subchunk.mesh_version);
+        assert!(!subchunk.mesh_dirty);
+    }
+
+    #[test]
+    fn seam_faces_are_culled_once_the_neighbor_arrives() {
+        let mut world = clean_world();
+        world.chunks.remove(&(1, 0));
+        world.set_block(CHUNK_SIZE - 1, 1, 0, BlockType::Stone);
+        let is_pos_x = |packed: u32| packed & 0x7 == 1;
+
+        // Meshed alone: the +X face at the border is drawn against the
+        // missing neighbour.
+        let ((vertices, _), _, _) = world.build_subchunk_mesh(0, 0, 0);
+        assert_eq!(vertices.iter().filter(|v| is_pos_x(v.packed)).count(), 4);
+        let subchunk = &mut world.chunks.get_mut(&(0, 0)).unwrap().subchunks[0];
+        subchunk.clear_mesh_dirty(subchunk.mesh_version);
+
+        let mut neighbor = Chunk::new(1, 0);
+        neighbor.set_block(0, 1, 0, BlockType::Stone);
+        world.chunks.insert((1, 0), neighbor);
+        world.mark_neighbor_borders_dirty(1, 0);
+
+        assert!(world.chunks[&(0, 0)].subchunks[0].mesh_dirty);
+        let ((vertices, _), _, _) = world.build_subchunk_mesh(0, 0, 0);
+        assert_eq!(vertices.len(), 5 * 4);
+        assert!(!vertices.iter().any(|v| is_pos_x(v.packed)));
+    }
+}
@@ -0,0 +1,67 @@
+use super::Structure;
+use crate::core::block::BlockType;
+
+/// Outer width and depth of a dungeon room, walls included.
+pub const DUNGEON_SIZE: i32 = 9;
+
+/// Outer height of a dungeon room, floor and ceiling included.
+pub const DUNGEON_HEIGHT: i32 = 6;
+
+/// Abandoned underground room: a cobblestone shell patched with stone
+/// around a hollow interior, with a doorway in every wall and rubble on
+/// the floor.
+///
+/// Rooms are placed by the chunk generator; see
+/// [`ChunkGenerator::generate_chunk`](crate::world::ChunkGenerator::generate_chunk).
+#[derive(Debug, Clone)]
+pub struct Dungeon {
+    pub structure: Structure,
+}
+
+impl Dungeon {
+    pub fn new() -> Self {
+        let mut structure = Structure::new("Dungeon", vec![]);
+
+        let last = DUNGEON_SIZE - 1;
+        for x in 0..DUNGEON_SIZE {
+            for y in 0..DUNGEON_HEIGHT {
+                for z in 0..DUNGEON_SIZE {
+                    let shell = x == 0
+                        || x == last
+                        || z == 0
+                        || z == last
+                        || y == 0
+                        || y == DUNGEON_HEIGHT - 1;
+                    let block = if !shell {
+                        BlockType::Air
+                    } else if (x * 7 + y * 3 + z * 5) % 4 == 0 {
+                        BlockType::Stone
+                    } else {
+                        BlockType::Cobblestone
+                    };
+                    structure.blocks.push((x, y, z, block));
+                }
+            }
+        }
+
+        let middle = DUNGEON_SIZE / 2;
+        for y in 1..=2 {
+            structure.blocks.push((middle, y, 0, BlockType::Air));
+            structure.blocks.push((middle, y, last, BlockType::Air));
+            structure.blocks.push((0, y, middle, BlockType::Air));
+            structure.blocks.push((last, y, middle, BlockType::Air));
+        }
+
+        for (x, z) in [(1, 1), (2, 1), (1, 2), (last - 1, last - 1)] {
+            structure.blocks.push((x, 1, z, BlockType::Gravel));
+        }
+
+        Self { structure }
+    }
+}
+
+impl Default for Dungeon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
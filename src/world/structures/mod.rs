@@ -2,3 +2,5 @@ pub mod structure;
 pub use structure::Structure;
 pub mod house;
 pub use house::House;
+pub mod dungeon;
+pub use dungeon::Dungeon;
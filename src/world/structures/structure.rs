@@ -1,4 +1,6 @@
+use crate::constants::CHUNK_SIZE;
 use crate::core::block::BlockType;
+use crate::core::chunk::Chunk;
 
 #[derive(Debug, Clone)]
 pub struct Structure {
@@ -28,4 +30,22 @@ impl Structure {
         self.blocks.push((x, y, z, block));
         self
     }
+
+    /// Writes the part of the structure anchored at world position `origin`
+    /// that falls inside chunk `(cx, cz)`; blocks outside it are skipped.
+    ///
+    /// Later blocks overwrite earlier ones at the same offset.  Calling
+    /// this for every chunk a structure overlaps builds it whole, whatever
+    /// order the chunks are generated in.
+    pub fn place_in_chunk(&self, chunk: &mut Chunk, cx: i32, cz: i32, origin: (i32, i32, i32)) {
+        let (ox, oy, oz) = origin;
+        for &(x, y, z, block) in &self.blocks {
+            chunk.set_block(
+                ox + x - cx * CHUNK_SIZE,
+                oy + y,
+                oz + z - cz * CHUNK_SIZE,
+                block,
+            );
+        }
+    }
 }
@@ -1,12 +1,17 @@
 use crate::constants::*;
 use crate::core::biome::Biome;
-use crate::core::block::BlockType;
-use crate::core::chunk::Chunk;
+use crate::core::block::{BlockType, Facing};
+use crate::core::chunk::{Chunk, MeshLod};
+use crate::core::mobs::entity::MobEntity;
 use crate::core::vertex::Vertex;
-use crate::render::mesh::{add_greedy_quad, add_quad};
+use crate::player::camera::check_intersection;
+use crate::render::mesh::{add_block_face, add_partial_block, add_quad, add_water_surface_quad};
+use crate::world::gen_config::WorldGenConfig;
 use crate::world::generator::ChunkGenerator;
+use crate::world::water::WaterUpdateQueue;
+use glam::Vec3;
 use parking_lot::RwLock;
-use rand::random;
+use rand::{random, random_range};
 use rustc_hash::FxHashMap;
 use std::sync::Arc;
 use std::thread;
@@ -62,6 +67,12 @@ pub struct World {
     /// Terrain generator used for synchronous chunk generation.  Worker threads
     /// in `ChunkLoader` each hold their own clone of this generator.
     generator: ChunkGenerator,
+
+    /// Mobs currently alive; spawned and despawned by [`World::update_mobs`].
+    pub mobs: Vec<MobEntity>,
+
+    /// Water blocks waiting to flow; drained by [`World::tick_water`].
+    pub water_updates: WaterUpdateQueue,
 }
 
 impl World {
@@ -73,12 +84,26 @@ impl World {
 
     /// Creates a new empty world with the given `seed`.
     pub fn new_empty_with_seed(seed: u32) -> Self {
+        Self::new_empty_with_sea_level(seed, SEA_LEVEL)
+    }
+
+    /// Creates a new empty world with the given `seed` whose water fills up
+    /// to `sea_level`; see [`ChunkGenerator::sea_level`].
+    pub fn new_empty_with_sea_level(seed: u32, sea_level: i32) -> Self {
+        Self::new_empty_with_config(seed, sea_level, WorldGenConfig::default())
+    }
+
+    /// Creates a new empty world with water up to `sea_level` whose chunks
+    /// are generated with the features chosen in `config`.
+    pub fn new_empty_with_config(seed: u32, sea_level: i32, config: WorldGenConfig) -> Self {
         World {
             chunks: FxHashMap::default(),
             last_cleanup_cx: i32::MIN,
             last_cleanup_cz: i32::MIN,
             seed,
-            generator: ChunkGenerator::new(seed),
+            generator: ChunkGenerator::with_config(seed, sea_level, config),
+            mobs: Vec::new(),
+            water_updates: WaterUpdateQueue::default(),
         }
     }
 
@@ -89,11 +114,41 @@ impl World {
     /// chunk X and Z, giving the player visible terrain immediately on spawn
     /// without waiting for the background `ChunkLoader`.
     pub fn new_with_seed(seed: u32) -> Self {
-        let mut world = Self::new_empty_with_seed(seed);
+        Self::new_with_sea_level(seed, SEA_LEVEL)
+    }
+
+    /// Like [`World::new_with_seed`], with water filling up to `sea_level`.
+    pub fn new_with_sea_level(seed: u32, sea_level: i32) -> Self {
+        Self::new_with_config(seed, sea_level, WorldGenConfig::default())
+    }
+
+    /// Like [`World::new_with_sea_level`], generating with `config`.
+    pub fn new_with_config(seed: u32, sea_level: i32, config: WorldGenConfig) -> Self {
+        let mut world = Self::new_empty_with_config(seed, sea_level, config);
         world.generate_chunks_in_radius(0, 0, RENDER_DISTANCE);
         world
     }
 
+    /// Height below which this world's oceans are filled with water.
+    ///
+    /// Everything that depends on the water line (the water shader, chunk
+    /// loaders, saves) should read it from here rather than [`SEA_LEVEL`].
+    pub fn sea_level(&self) -> i32 {
+        self.generator.sea_level
+    }
+
+    /// Generation features this world was created with; saved alongside
+    /// the seed so reloading keeps generating the same terrain.
+    pub fn gen_config(&self) -> WorldGenConfig {
+        self.generator.config
+    }
+
+    /// Terrain generator of this world, for generating chunks on another
+    /// thread and adding them to `chunks` afterwards.
+    pub fn generator(&self) -> &ChunkGenerator {
+        &self.generator
+    }
+
     /// Generates all chunks within `radius` of `(center_cx, center_cz)` on the
     /// calling thread.
     pub fn generate_chunks_in_radius(&mut self, center_cx: i32, center_cz: i32, radius: i32) {
@@ -121,8 +176,7 @@ impl World {
         outer_radius: i32,
     ) {
         thread::spawn(move || {
-            let seed = world.read().seed;
-            let generator = ChunkGenerator::new(seed);
+            let generator = world.read().generator.clone();
 
             for cx in (center_cx - outer_radius)..=(center_cx + outer_radius) {
                 for cz in (center_cz - outer_radius)..=(center_cz + outer_radius) {
@@ -151,6 +205,36 @@ impl World {
         self.generate_chunk(cx, cz);
     }
 
+    /// Throws away loaded chunk `(cx, cz)` and generates it again, for
+    /// trying out generator changes without reloading the world.
+    ///
+    /// Generation runs synchronously on the calling thread.  The new
+    /// sub-chunks continue the old ones' mesh versions and start dirty, so
+    /// a mesh of the old blocks still in flight is never taken for theirs,
+    /// and the neighbours' borders are remeshed against the new blocks.
+    /// Chunks a player has edited are kept unless `force` is set.
+    ///
+    /// # Returns
+    /// `true` if the chunk was regenerated; `false` if it is not loaded, or
+    /// was edited and `force` is not set.
+    pub fn regenerate_chunk(&mut self, cx: i32, cz: i32, force: bool) -> bool {
+        let Some(old) = self.chunks.get(&(cx, cz)) else {
+            return false;
+        };
+        if old.player_modified && !force {
+            return false;
+        }
+        let mut chunk = self.generator.generate_chunk(cx, cz);
+        chunk.mesh_lod = old.mesh_lod;
+        for (subchunk, old) in chunk.subchunks.iter_mut().zip(&old.subchunks) {
+            subchunk.mesh_version = old.mesh_version;
+            subchunk.mark_mesh_dirty();
+        }
+        self.chunks.insert((cx, cz), chunk);
+        self.mark_neighbor_borders_dirty(cx, cz);
+        true
+    }
+
     /// Unloads chunks that have moved outside `CHUNK_UNLOAD_DISTANCE` of the
     /// player's current chunk column.
     ///
@@ -322,6 +406,74 @@ impl World {
         self.get_block(x, y, z).is_solid()
     }
 
+    /// Returns `true` if a block placed at `(x, y, z)` would be inside the
+    /// world height and not end up inside anyone: none of the `players`,
+    /// given by their foot positions, nor any of the [`Self::mobs`]
+    /// overlaps the cell.
+    ///
+    /// Every placement path checks this, so no one can be walled in or
+    /// suffocated by a block placed into them, nor build under the bedrock
+    /// floor.
+    pub fn can_place_block(&self, x: i32, y: i32, z: i32, players: &[Vec3]) -> bool {
+        (0..WORLD_HEIGHT).contains(&y)
+            && !players.iter().any(|&pos| check_intersection(pos, x, y, z))
+            && !self.mobs.iter().any(|mob| mob.intersects_block(x, y, z))
+    }
+
+    /// Returns the height of the topmost opaque block in column `(x, z)`,
+    /// or `None` if the column has none or its chunk is not loaded.
+    ///
+    /// Read from the per-column cache that [`Chunk::set_block`] maintains,
+    /// so it costs one hash-map lookup.
+    pub fn opaque_height(&self, x: i32, z: i32) -> Option<i32> {
+        let cx = x.div_euclid(CHUNK_SIZE);
+        let cz = z.div_euclid(CHUNK_SIZE);
+        self.chunks
+            .get(&(cx, cz))?
+            .opaque_height(x.rem_euclid(CHUNK_SIZE), z.rem_euclid(CHUNK_SIZE))
+    }
+
+    /// Returns `true` if no opaque block lies above `(x, y, z)`, so it sees
+    /// the sky.
+    ///
+    /// The block itself may be opaque: the top of a grass column is
+    /// exposed.  Leaves, glass and other transparent blocks do not shade
+    /// what is under them.  Unloaded columns count as exposed, matching
+    /// [`Self::get_block`] reading them as air.
+    pub fn is_sky_exposed(&self, x: i32, y: i32, z: i32) -> bool {
+        self.opaque_height(x, z).is_none_or(|top| top <= y)
+    }
+
+    /// Returns the stored [`Facing`] of the block at `(x, y, z)`.
+    ///
+    /// Blocks without a stored orientation (including every non-directional
+    /// block and unloaded chunks) return [`Facing::default`].
+    pub fn get_facing(&self, x: i32, y: i32, z: i32) -> Facing {
+        let cx = x.div_euclid(CHUNK_SIZE);
+        let cz = z.div_euclid(CHUNK_SIZE);
+        match self.chunks.get(&(cx, cz)) {
+            Some(chunk) => chunk.get_facing(x.rem_euclid(CHUNK_SIZE), y, z.rem_euclid(CHUNK_SIZE)),
+            None => Facing::default(),
+        }
+    }
+
+    /// Sets the orientation of the directional block at `(x, y, z)`.
+    ///
+    /// Call after [`set_block`]/[`set_block_player`], which reset the facing
+    /// of the overwritten block.  Silently no-ops for unloaded chunks.
+    pub fn set_facing(&mut self, x: i32, y: i32, z: i32, facing: Facing) {
+        let cx = x.div_euclid(CHUNK_SIZE);
+        let cz = z.div_euclid(CHUNK_SIZE);
+        if let Some(chunk) = self.chunks.get_mut(&(cx, cz)) {
+            chunk.set_facing(
+                x.rem_euclid(CHUNK_SIZE),
+                y,
+                z.rem_euclid(CHUNK_SIZE),
+                facing,
+            );
+        }
+    }
+
     // ── Occlusion culling ─────────────────────────────────────────────────── //
 
     /// Returns `true` if sub-chunk `(cx, cz, sy)` is fully occluded and can
@@ -376,44 +528,122 @@ impl World {
         false
     }
 
-    // ── Spawn point search ────────────────────────────────────────────────── //
+    // ── Random ticks ──────────────────────────────────────────────────────── //
+
+    /// Runs one random tick over the loaded chunks within `radius` of chunk
+    /// `(center_cx, center_cz)`.
+    ///
+    /// [`RANDOM_TICKS_PER_SUBCHUNK`] random blocks of every non-empty
+    /// sub-chunk get a [`Self::random_tick_block`], so the work per tick is
+    /// capped by the radius no matter how much of the world is loaded, and
+    /// slow changes such as grass spreading happen all over at once.
+    ///
+    /// # Returns
+    /// The number of blocks that changed.
+    pub fn tick_random_blocks(&mut self, center_cx: i32, center_cz: i32, radius: i32) -> usize {
+        let mut picks = Vec::new();
+        for cx in (center_cx - radius)..=(center_cx + radius) {
+            for cz in (center_cz - radius)..=(center_cz + radius) {
+                let Some(chunk) = self.chunks.get(&(cx, cz)) else {
+                    continue;
+                };
+                for (sy, subchunk) in chunk.subchunks.iter().enumerate() {
+                    if subchunk.is_empty {
+                        continue;
+                    }
+                    for _ in 0..RANDOM_TICKS_PER_SUBCHUNK {
+                        picks.push((
+                            cx * CHUNK_SIZE + random_range(0..CHUNK_SIZE),
+                            sy as i32 * SUBCHUNK_HEIGHT + random_range(0..SUBCHUNK_HEIGHT),
+                            cz * CHUNK_SIZE + random_range(0..CHUNK_SIZE),
+                        ));
+                    }
+                }
+            }
+        }
+
+        picks
+            .into_iter()
+            .filter(|&(x, y, z)| self.random_tick_block(x, y, z))
+            .count()
+    }
 
-    /// Searches outward from the origin in a spiral of expanding radii to find
-    /// a suitable player spawn position.
+    /// Applies the random-tick rules to the block at `(x, y, z)` and returns
+    /// `true` if it or a neighbour changed.
     ///
-    /// A column is acceptable when its terrain height is at or above sea level
-    /// and its biome is not Ocean, River, or Lake (the player would spawn
-    /// underwater or on an unstable floor).
+    /// - Grass under an opaque block loses its light and turns to dirt.
+    /// - Otherwise grass spreads to one random block of the surrounding
+    ///   3×3×3 cube if that block is [sky-exposed](Self::is_sky_exposed)
+    ///   dirt.
     ///
-    /// The returned Y coordinate places the player one block above the surface
-    /// with a small XZ offset so the player doesn't fall into a 1×1 crevice
-    /// at exactly (0, y, 0).
+    /// Changes are stored as player edits so they survive a save.
+    fn random_tick_block(&mut self, x: i32, y: i32, z: i32) -> bool {
+        if self.get_block(x, y, z) != BlockType::Grass {
+            return false;
+        }
+
+        if self.get_block(x, y + 1, z).is_opaque() {
+            self.set_block_player(x, y, z, BlockType::Dirt);
+            self.mark_block_dirty(x, y, z);
+            return true;
+        }
+
+        let tx = x + random_range(-1..=1);
+        let ty = y + random_range(-1..=1);
+        let tz = z + random_range(-1..=1);
+        if self.get_block(tx, ty, tz) != BlockType::Dirt || !self.is_sky_exposed(tx, ty, tz) {
+            return false;
+        }
+        self.set_block_player(tx, ty, tz, BlockType::Grass);
+        self.mark_block_dirty(tx, ty, tz);
+        true
+    }
+
+    // ── Spawn point search ────────────────────────────────────────────────── //
+
+    /// Finds the spawn point of this world's terrain; see
+    /// [`ChunkGenerator::find_spawn_point`].
     ///
     /// # Returns
-    /// `(x, y, z)` in world space.  Falls back to `(0.5, 80.0, 0.5)` if no
-    /// suitable column is found within radius 50 (which should never happen in
-    /// practice for non-degenerate worlds).
+    /// `(x, y, z)` in world space.
     pub fn find_spawn_point(&self) -> (f32, f32, f32) {
-        for radius in 0..50 {
-            for dx in -radius..=radius {
-                for dz in -radius..=radius {
-                    let x = dx;
-                    let z = dz;
-                    let height = self.get_terrain_height(x, z);
-                    let biome = self.get_biome(x, z);
-
-                    if height >= SEA_LEVEL
-                        && !matches!(biome, Biome::Ocean | Biome::River | Biome::Lake)
-                    {
-                        // +0.3 / +0.5 offsets prevent the player from being
-                        // centred on a block edge and avoid false collision
-                        // positives at the moment of spawn.
-                        return (x as f32 + 0.3, (height + 1) as f32, z as f32 + 0.5);
-                    }
+        self.generator.find_spawn_point()
+    }
+
+    /// Finds a spawn point with [`find_spawn_point`](Self::find_spawn_point)
+    /// and makes sure the player has something to stand on there.
+    ///
+    /// The spawn search only looks at terrain height, so the block under the
+    /// chosen position can still be air (a cave opening at the surface, or
+    /// the fallback position over void).  In that case a square platform
+    /// reaching [`SPAWN_PLATFORM_RADIUS`] blocks out from the spawn column is
+    /// built directly beneath the player: grass on top of a layer of stone.
+    /// A radius of `0` disables the platform.
+    ///
+    /// Platform blocks are stored as player edits so they survive a save and
+    /// reload, and are skipped in chunks that are not loaded, so generate the
+    /// chunks around the spawn first.
+    ///
+    /// # Returns
+    /// `(x, y, z)` in world space, with the player's feet on the platform if
+    /// one was built.
+    pub fn find_safe_spawn_point(&mut self) -> (f32, f32, f32) {
+        let spawn = self.find_spawn_point();
+        let x = spawn.0.floor() as i32;
+        let y = spawn.1.floor() as i32;
+        let z = spawn.2.floor() as i32;
+
+        if SPAWN_PLATFORM_RADIUS > 0 && !self.is_solid(x, y - 1, z) {
+            for px in (x - SPAWN_PLATFORM_RADIUS)..=(x + SPAWN_PLATFORM_RADIUS) {
+                for pz in (z - SPAWN_PLATFORM_RADIUS)..=(z + SPAWN_PLATFORM_RADIUS) {
+                    self.set_block_player(px, y - 1, pz, BlockType::Grass);
+                    self.set_block_player(px, y - 2, pz, BlockType::Stone);
+                    self.mark_block_dirty(px, y - 1, pz);
+                    self.mark_block_dirty(px, y - 2, pz);
                 }
             }
         }
-        (0.5, 80.0, 0.5) // fallback
+        spawn
     }
 
     // ── Mesh generation ───────────────────────────────────────────────────── //
@@ -431,13 +661,13 @@ impl World {
     /// hash-map access — they always hit the cache.  Blocks in unloaded
     /// neighboring chunks default to `Water` below sea level and `Air` above.
     ///
-    /// ## 2. WoodStairs (custom geometry, pre-pass)
+    /// ## 2. Partial blocks (custom geometry, pre-pass)
     ///
-    /// Stair blocks require non-axis-aligned geometry (two horizontally
-    /// stacked half-blocks) that cannot be expressed as a single full-face
-    /// quad.  They are rendered with individual `add_quad` calls in a
-    /// dedicated pre-pass loop and then **excluded** from the greedy meshing
-    /// loop via an explicit `continue`.
+    /// Slabs, stairs and snow layers ([`BlockType::is_partial`]) have reduced
+    /// geometry that cannot be expressed as full-face quads.  They are
+    /// rendered by [`add_partial_block`] in a dedicated pre-pass loop (stairs
+    /// rotated to their stored [`Facing`]) and then **excluded** from the
+    /// greedy meshing loop via an explicit `continue`.
     ///
     /// ## 3. Greedy meshing (main pass, 6 face directions)
     ///
@@ -446,17 +676,20 @@ impl World {
     /// a. **Populates a `mask`** – a 2-D array of [`FaceAttrs`] for the
     ///    current slice.  A slot is active when the block on the near side
     ///    should render a face against its neighbor on the far side.
-    ///    Water blocks are handled specially: they are emitted immediately as
-    ///    individual quads rather than entering the mask (no greedy merging for
-    ///    water, since water faces never share the same texture/color).
-    ///    Stair blocks are also skipped here (already handled above).
+    ///    Water blocks are handled specially: the exposed top surface (water
+    ///    with air above) enters the mask keyed by its fill level, while all
+    ///    other water faces (sides, bottoms, tops under a block) are emitted
+    ///    immediately as individual quads.  Partial blocks are also skipped
+    ///    here (already handled above).
     ///
     /// b. **Greedy merges** – scans the mask in row-major order.  Starting
     ///    from each active cell, extends a rectangle first along `d2` (width)
     ///    until the next cell differs, then along `d1` (height) checking
     ///    that every cell in the expanded row matches.  The merged rectangle
-    ///    is emitted as a single `add_greedy_quad` call and the covered
-    ///    cells are marked inactive.
+    ///    is emitted as a single `add_greedy_quad` call (or
+    ///    `add_water_surface_quad` into the water mesh for water surfaces,
+    ///    which `water.wgsl` animates with waves) and the covered cells are
+    ///    marked inactive.
     ///
     /// The greedy approach dramatically reduces vertex count for large flat
     /// surfaces (e.g., a 16×16 grass top becomes one quad instead of 256).
@@ -492,17 +725,27 @@ impl World {
     /// - `subchunk_y` – Sub-chunk vertical index within the column.
     ///
     /// # Returns
-    /// A pair of `(vertices, indices)` tuples:
-    /// - First tuple: opaque geometry.
-    /// - Second tuple: water (translucent) geometry.
+    /// Three `(vertices, indices)` tuples:
+    /// - First tuple: opaque geometry, including slabs, stairs and cut-out
+    ///   blocks such as leaves.
+    /// - Second tuple: faces of translucent blocks
+    ///   ([`BlockType::is_translucent`]: glass and ice), so they can be
+    ///   blended over everything opaque.
+    /// - Third tuple: water geometry.
     pub fn build_subchunk_mesh(
         &self,
         chunk_x: i32,
         chunk_z: i32,
         subchunk_y: i32,
-    ) -> ((Vec<Vertex>, Vec<u32>), (Vec<Vertex>, Vec<u32>)) {
+    ) -> (
+        (Vec<Vertex>, Vec<u32>),
+        (Vec<Vertex>, Vec<u32>),
+        (Vec<Vertex>, Vec<u32>),
+    ) {
         let mut vertices = Vec::with_capacity(4096);
         let mut indices = Vec::with_capacity(2048);
+        let mut translucent_vertices = Vec::new();
+        let mut translucent_indices = Vec::new();
         let mut water_vertices = Vec::with_capacity(1024);
         let mut water_indices = Vec::with_capacity(512);
 
@@ -518,23 +761,29 @@ impl World {
         const SH: usize = SUBCHUNK_HEIGHT as usize + PAD * 2; // 18
 
         let mut block_cache = [BlockType::Air; S * SH * S];
+        // Water fill levels, parallel to `block_cache` (0 for non-water).
+        let mut level_cache = [0u8; S * SH * S];
 
-        // Fetch a block from the world, defaulting to Water/Air for unloaded
-        // neighboring chunks (below/above sea level respectively).
-        let fetch = |wx: i32, wy: i32, wz: i32| -> BlockType {
+        // Fetch a block and its water level from the world, defaulting to
+        // full Water/Air for unloaded neighboring chunks (below/above sea
+        // level respectively).
+        let fetch = |wx: i32, wy: i32, wz: i32| -> (BlockType, u8) {
             if wy < 0 || wy >= WORLD_HEIGHT {
-                return BlockType::Air;
+                return (BlockType::Air, 0);
             }
             let cx = wx.div_euclid(CHUNK_SIZE);
             let cz = wz.div_euclid(CHUNK_SIZE);
             let lx = wx.rem_euclid(CHUNK_SIZE);
             let lz = wz.rem_euclid(CHUNK_SIZE);
             if let Some(chunk) = self.chunks.get(&(cx, cz)) {
-                chunk.get_block(lx, wy, lz)
-            } else if wy < SEA_LEVEL {
-                BlockType::Water // fill unloaded ocean columns with water
+                (
+                    chunk.get_block(lx, wy, lz),
+                    chunk.get_water_level(lx, wy, lz),
+                )
+            } else if wy < self.sea_level() {
+                (BlockType::Water, WATER_MAX_LEVEL) // fill unloaded ocean columns with water
             } else {
-                BlockType::Air
+                (BlockType::Air, 0)
             }
         };
 
@@ -546,8 +795,8 @@ impl World {
                     let wx = base_x + px - PAD as i32;
                     let wy = base_y + py - PAD as i32;
                     let wz = base_z + pz - PAD as i32;
-                    block_cache[(px as usize) * SH * S + (py as usize) * S + (pz as usize)] =
-                        fetch(wx, wy, wz);
+                    let index = (px as usize) * SH * S + (py as usize) * S + (pz as usize);
+                    (block_cache[index], level_cache[index]) = fetch(wx, wy, wz);
                 }
             }
         }
@@ -565,6 +814,31 @@ impl World {
             get_block_fast(wx - base_x, wy - base_y, wz - base_z)
         };
 
+        // Neighbor a face is culled against.  Columns meshed coarse count as
+        // air, so no face along the border to one is culled against a block
+        // the coarse mesh may have dropped.
+        let get_neighbor = |wx: i32, wy: i32, wz: i32| -> BlockType {
+            if self.meshed_at_other_lod(chunk_x, chunk_z, MeshLod::Full, wx, wz) {
+                BlockType::Air
+            } else {
+                get_block_world(wx, wy, wz)
+            }
+        };
+
+        // Water surface height within the block at sub-chunk-local
+        // coordinates, in `[0, 1]`; see `World::water_surface_height`.
+        let water_height = |lx: i32, ly: i32, lz: i32| -> f32 {
+            let px = (lx + PAD as i32) as usize;
+            let py = (ly + PAD as i32) as usize;
+            let pz = (lz + PAD as i32) as usize;
+            let level = level_cache[px * SH * S + py * S + pz];
+            if level > 0 && get_block_fast(lx, ly + 1, lz) == BlockType::Water {
+                1.0
+            } else {
+                level as f32 / WATER_MAX_LEVEL as f32
+            }
+        };
+
         // Biome cache: queried lazily, at most once per XZ column.
         let mut biome_map: [[Option<Biome>; CHUNK_SIZE as usize]; CHUNK_SIZE as usize] =
             [[None; CHUNK_SIZE as usize]; CHUNK_SIZE as usize];
@@ -578,6 +852,9 @@ impl World {
             block: BlockType,
             color: [u8; 3],
             tex_index: u8,
+            /// Fill level of an exposed water surface (0 for other faces), so
+            /// only surfaces at the same height merge.
+            water_level: u8,
             is_active: bool,
         }
 
@@ -587,6 +864,7 @@ impl World {
                     block: BlockType::Air,
                     color: [0, 0, 0],
                     tex_index: 0,
+                    water_level: 0,
                     is_active: false,
                 }
             }
@@ -604,202 +882,53 @@ impl World {
             ]
         };
 
-        // ── Pass 1: WoodStairs custom geometry ────────────────────────────── //
-        // Stair blocks are composed of two non-unit-height quads that cannot
-        // be expressed as standard greedy-merged full faces.  They are emitted
-        // here with explicit `add_quad` calls and excluded from pass 2.
+        // ── Pass 1: partial blocks (slabs, stairs, snow layers) ──────────── //
+        // Slabs, stairs and snow layers do not fill their cell, so they cannot be expressed
+        // as greedy-merged full faces.  Their reduced geometry is emitted here
+        // by `add_partial_block` and they are excluded from pass 2.
         for lx in 0..CHUNK_SIZE {
             for ly in 0..SUBCHUNK_HEIGHT {
                 for lz in 0..CHUNK_SIZE {
-                    let y = base_y + ly;
-                    let world_x = base_x + lx;
-                    let world_z = base_z + lz;
-                    let block = get_block_world(world_x, y, world_z);
-
-                    if block == BlockType::Air {
+                    let block = get_block_fast(lx, ly, lz);
+                    if !block.is_partial() {
                         continue;
                     }
 
-                    let is_water = block == BlockType::Water;
-                    let (target_verts, target_inds) = if is_water {
-                        (&mut water_vertices, &mut water_indices)
+                    let y = base_y + ly;
+                    let world_x = base_x + lx;
+                    let world_z = base_z + lz;
+                    let neighbors = [
+                        get_neighbor(world_x - 1, y, world_z), // 0: −X
+                        get_neighbor(world_x + 1, y, world_z), // 1: +X
+                        get_neighbor(world_x, y - 1, world_z), // 2: −Y
+                        get_neighbor(world_x, y + 1, world_z), // 3: +Y
+                        get_neighbor(world_x, y, world_z - 1), // 4: −Z
+                        get_neighbor(world_x, y, world_z + 1), // 5: +Z
+                    ];
+                    let facing = if block.is_directional() {
+                        self.get_facing(world_x, y, world_z)
                     } else {
-                        (&mut vertices, &mut indices)
+                        Facing::default()
                     };
 
-                    if block == BlockType::WoodStairs {
-                        let x = world_x as f32;
-                        let y_f = y as f32;
-                        let z = world_z as f32;
-                        let color = block.color();
-                        let tex_top = block.tex_top();
-                        let tex_side = block.tex_side();
-                        let r = block.roughness();
-                        let m = block.metallic();
-
-                        let neighbors = [
-                            get_block_world(world_x - 1, y, world_z), // 0: −X
-                            get_block_world(world_x + 1, y, world_z), // 1: +X
-                            get_block_world(world_x, y - 1, world_z), // 2: −Y
-                            get_block_world(world_x, y + 1, world_z), // 3: +Y
-                            get_block_world(world_x, y, world_z - 1), // 4: −Z
-                            get_block_world(world_x, y, world_z + 1), // 5: +Z
-                        ];
-
-                        // Bottom face (full, conditional on −Y neighbor).
-                        if block.should_render_face_against(neighbors[2]) {
-                            add_quad(
-                                target_verts,
-                                target_inds,
-                                [x, y_f, z + 1.0],
-                                [x, y_f, z],
-                                [x + 1.0, y_f, z],
-                                [x + 1.0, y_f, z + 1.0],
-                                [0.0, -1.0, 0.0],
-                                color,
-                                tex_top,
-                                r,
-                                m,
-                            );
-                        }
-                        // Lower half-top (always visible: the step tread at Y+0.5,
-                        // front half Z=[0, 0.5]).
-                        add_quad(
-                            target_verts,
-                            target_inds,
-                            [x, y_f + 0.5, z],
-                            [x, y_f + 0.5, z + 0.5],
-                            [x + 1.0, y_f + 0.5, z + 0.5],
-                            [x + 1.0, y_f + 0.5, z],
-                            [0.0, 1.0, 0.0],
-                            color,
-                            tex_top,
-                            r,
-                            m,
-                        );
-                        // Upper full-top (conditional on +Y neighbor).
-                        if block.should_render_face_against(neighbors[3]) {
-                            add_quad(
-                                target_verts,
-                                target_inds,
-                                [x, y_f + 1.0, z + 0.5],
-                                [x, y_f + 1.0, z + 1.0],
-                                [x + 1.0, y_f + 1.0, z + 1.0],
-                                [x + 1.0, y_f + 1.0, z + 0.5],
-                                [0.0, 1.0, 0.0],
-                                color,
-                                tex_top,
-                                r,
-                                m,
-                            );
-                        }
-                        // Front face (−Z, lower half only, conditional).
-                        if block.should_render_face_against(neighbors[4]) {
-                            add_quad(
-                                target_verts,
-                                target_inds,
-                                [x + 1.0, y_f, z],
-                                [x, y_f, z],
-                                [x, y_f + 0.5, z],
-                                [x + 1.0, y_f + 0.5, z],
-                                [0.0, 0.0, -1.0],
-                                color,
-                                tex_side,
-                                r,
-                                m,
-                            );
-                        }
-                        // Step riser (always visible: the vertical face between
-                        // the lower and upper treads at Z+0.5).
-                        add_quad(
-                            target_verts,
-                            target_inds,
-                            [x + 1.0, y_f + 0.5, z + 0.5],
-                            [x, y_f + 0.5, z + 0.5],
-                            [x, y_f + 1.0, z + 0.5],
-                            [x + 1.0, y_f + 1.0, z + 0.5],
-                            [0.0, 0.0, -1.0],
-                            color,
-                            tex_side,
-                            r,
-                            m,
-                        );
-                        // Back face (+Z, full height, conditional).
-                        if block.should_render_face_against(neighbors[5]) {
-                            add_quad(
-                                target_verts,
-                                target_inds,
-                                [x, y_f, z + 1.0],
-                                [x + 1.0, y_f, z + 1.0],
-                                [x + 1.0, y_f + 1.0, z + 1.0],
-                                [x, y_f + 1.0, z + 1.0],
-                                [0.0, 0.0, 1.0],
-                                color,
-                                tex_side,
-                                r,
-                                m,
-                            );
-                        }
-                        // Left face (−X): two quads – lower half and upper-back half.
-                        if block.should_render_face_against(neighbors[0]) {
-                            add_quad(
-                                target_verts,
-                                target_inds,
-                                [x, y_f, z],
-                                [x, y_f, z + 1.0],
-                                [x, y_f + 0.5, z + 1.0],
-                                [x, y_f + 0.5, z],
-                                [-1.0, 0.0, 0.0],
-                                color,
-                                tex_side,
-                                r,
-                                m,
-                            );
-                            add_quad(
-                                target_verts,
-                                target_inds,
-                                [x, y_f + 0.5, z + 0.5],
-                                [x, y_f + 0.5, z + 1.0],
-                                [x, y_f + 1.0, z + 1.0],
-                                [x, y_f + 1.0, z + 0.5],
-                                [-1.0, 0.0, 0.0],
-                                color,
-                                tex_side,
-                                r,
-                                m,
-                            );
-                        }
-                        // Right face (+X): two quads – lower half and upper-back half.
-                        if block.should_render_face_against(neighbors[1]) {
-                            add_quad(
-                                target_verts,
-                                target_inds,
-                                [x + 1.0, y_f, z + 1.0],
-                                [x + 1.0, y_f, z],
-                                [x + 1.0, y_f + 0.5, z],
-                                [x + 1.0, y_f + 0.5, z + 1.0],
-                                [1.0, 0.0, 0.0],
-                                color,
-                                tex_side,
-                                r,
-                                m,
-                            );
-                            add_quad(
-                                target_verts,
-                                target_inds,
-                                [x + 1.0, y_f + 0.5, z + 1.0],
-                                [x + 1.0, y_f + 0.5, z + 0.5],
-                                [x + 1.0, y_f + 1.0, z + 0.5],
-                                [x + 1.0, y_f + 1.0, z + 1.0],
-                                [1.0, 0.0, 0.0],
-                                color,
-                                tex_side,
-                                r,
-                                m,
-                            );
+                    // Side faces between two equal slabs or snow layers
+                    // coincide exactly, so neither is drawn; stairs may be
+                    // turned differently and keep theirs.
+                    let mut visible = neighbors.map(|n| block.should_render_face_against(n));
+                    for face in [0, 1, 4, 5] {
+                        if neighbors[face] == block && !block.is_directional() {
+                            visible[face] = false;
                         }
-                        continue; // skip greedy pass for this block
                     }
+
+                    add_partial_block(
+                        &mut vertices,
+                        &mut indices,
+                        block,
+                        [world_x as f32, y as f32, world_z as f32],
+                        facing,
+                        visible,
+                    );
                 }
             }
         }
@@ -835,20 +964,68 @@ impl World {
                         let world_z = base_z + lz;
                         let block = get_block_world(world_x, y, world_z);
 
-                        // Water is emitted immediately (no greedy merge).
+                        // The exposed top of a water body goes through the
+                        // greedy merge like any other face, so a lake surface
+                        // becomes a few large quads the water shader animates
+                        // with waves.  All other water faces are emitted
+                        // immediately (no greedy merge).
+                        if block == BlockType::Water
+                            && face_dir == 3
+                            && get_block_world(world_x, y + 1, world_z) == BlockType::Air
+                        {
+                            let px = (lx + PAD as i32) as usize;
+                            let py = (ly + PAD as i32) as usize;
+                            let pz = (lz + PAD as i32) as usize;
+                            let idx = (d1 * dim2_size + d2) as usize;
+                            mask[idx] = FaceAttrs {
+                                block,
+                                color: quantize_color(block.color()),
+                                tex_index: block.tex_top() as u8,
+                                water_level: level_cache[px * SH * S + py * S + pz],
+                                is_active: true,
+                            };
+                            continue;
+                        }
                         if block == BlockType::Water {
-                            let neighbors = [
-                                get_block_world(world_x - 1, y, world_z),
-                                get_block_world(world_x + 1, y, world_z),
-                                get_block_world(world_x, y - 1, world_z),
-                                get_block_world(world_x, y + 1, world_z),
-                                get_block_world(world_x, y, world_z - 1),
-                                get_block_world(world_x, y, world_z + 1),
-                            ];
-
-                            if block.should_render_face_against(neighbors[face_dir as usize]) {
+                            let neighbor = match face_dir {
+                                0 => get_block_world(world_x - 1, y, world_z),
+                                1 => get_block_world(world_x + 1, y, world_z),
+                                2 => get_block_world(world_x, y - 1, world_z),
+                                3 => get_block_world(world_x, y + 1, world_z),
+                                4 => get_block_world(world_x, y, world_z - 1),
+                                5 => get_block_world(world_x, y, world_z + 1),
+                                _ => unreachable!(),
+                            };
+
+                            // Vertical extent of the face within the block.
+                            // Sides span from the neighbor's water surface (0
+                            // for air) up to our own, so lower water next to
+                            // higher water shows the step between them; the
+                            // top sits at our surface and also shows under a
+                            // solid block when the water does not reach it.
+                            let height = water_height(lx, ly, lz);
+                            let extent = match face_dir {
+                                2 if neighbor == BlockType::Air => Some((0.0, 0.0)),
+                                3 if neighbor != BlockType::Water && height < 1.0 => {
+                                    Some((height, height))
+                                }
+                                0 | 1 | 4 | 5 if neighbor == BlockType::Air => Some((0.0, height)),
+                                0 | 1 | 4 | 5 if neighbor == BlockType::Water => {
+                                    let (nx, nz) = match face_dir {
+                                        0 => (lx - 1, lz),
+                                        1 => (lx + 1, lz),
+                                        4 => (lx, lz - 1),
+                                        _ => (lx, lz + 1),
+                                    };
+                                    Some((water_height(nx, ly, nz), height))
+                                }
+                                _ => None,
+                            };
+
+                            if let Some((bottom, top)) = extent {
                                 let x = world_x as f32;
-                                let y_f = y as f32;
+                                let y_lo = y as f32 + bottom;
+                                let y_hi = y as f32 + top;
                                 let z = world_z as f32;
                                 let color = block.color();
                                 let tex = block.tex_top();
@@ -858,26 +1035,26 @@ impl World {
                                 // One quad per visible face; direction determines
                                 // vertex winding so normals point outward.
                                 match face_dir {
-                                    0 => add_quad(
+                                    0 if y_lo < y_hi => add_quad(
                                         &mut water_vertices,
                                         &mut water_indices,
-                                        [x, y_f, z],
-                                        [x, y_f, z + 1.0],
-                                        [x, y_f + 1.0, z + 1.0],
-                                        [x, y_f + 1.0, z],
+                                        [x, y_lo, z],
+                                        [x, y_lo, z + 1.0],
+                                        [x, y_hi, z + 1.0],
+                                        [x, y_hi, z],
                                         [-1.0, 0.0, 0.0],
                                         color,
                                         tex,
                                         r,
                                         m,
                                     ),
-                                    1 => add_quad(
+                                    1 if y_lo < y_hi => add_quad(
                                         &mut water_vertices,
                                         &mut water_indices,
-                                        [x + 1.0, y_f, z + 1.0],
-                                        [x + 1.0, y_f, z],
-                                        [x + 1.0, y_f + 1.0, z],
-                                        [x + 1.0, y_f + 1.0, z + 1.0],
+                                        [x + 1.0, y_lo, z + 1.0],
+                                        [x + 1.0, y_lo, z],
+                                        [x + 1.0, y_hi, z],
+                                        [x + 1.0, y_hi, z + 1.0],
                                         [1.0, 0.0, 0.0],
                                         color,
                                         tex,
@@ -887,10 +1064,10 @@ impl World {
                                     2 => add_quad(
                                         &mut water_vertices,
                                         &mut water_indices,
-                                        [x, y_f, z + 1.0],
-                                        [x, y_f, z],
-                                        [x + 1.0, y_f, z],
-                                        [x + 1.0, y_f, z + 1.0],
+                                        [x, y_lo, z + 1.0],
+                                        [x, y_lo, z],
+                                        [x + 1.0, y_lo, z],
+                                        [x + 1.0, y_lo, z + 1.0],
                                         [0.0, -1.0, 0.0],
                                         color,
                                         tex,
@@ -900,36 +1077,36 @@ impl World {
                                     3 => add_quad(
                                         &mut water_vertices,
                                         &mut water_indices,
-                                        [x, y_f + 1.0, z],
-                                        [x, y_f + 1.0, z + 1.0],
-                                        [x + 1.0, y_f + 1.0, z + 1.0],
-                                        [x + 1.0, y_f + 1.0, z],
+                                        [x, y_hi, z],
+                                        [x, y_hi, z + 1.0],
+                                        [x + 1.0, y_hi, z + 1.0],
+                                        [x + 1.0, y_hi, z],
                                         [0.0, 1.0, 0.0],
                                         color,
                                         tex,
                                         r,
                                         m,
                                     ),
-                                    4 => add_quad(
+                                    4 if y_lo < y_hi => add_quad(
                                         &mut water_vertices,
                                         &mut water_indices,
-                                        [x + 1.0, y_f, z],
-                                        [x, y_f, z],
-                                        [x, y_f + 1.0, z],
-                                        [x + 1.0, y_f + 1.0, z],
+                                        [x + 1.0, y_lo, z],
+                                        [x, y_lo, z],
+                                        [x, y_hi, z],
+                                        [x + 1.0, y_hi, z],
                                         [0.0, 0.0, -1.0],
                                         color,
                                         tex,
                                         r,
                                         m,
                                     ),
-                                    5 => add_quad(
+                                    5 if y_lo < y_hi => add_quad(
                                         &mut water_vertices,
                                         &mut water_indices,
-                                        [x, y_f, z + 1.0],
-                                        [x + 1.0, y_f, z + 1.0],
-                                        [x + 1.0, y_f + 1.0, z + 1.0],
-                                        [x, y_f + 1.0, z + 1.0],
+                                        [x, y_lo, z + 1.0],
+                                        [x + 1.0, y_lo, z + 1.0],
+                                        [x + 1.0, y_hi, z + 1.0],
+                                        [x, y_hi, z + 1.0],
                                         [0.0, 0.0, 1.0],
                                         color,
                                         tex,
@@ -943,7 +1120,7 @@ impl World {
                         }
 
                         // Skip Air and Stairs (handled in pass 1 or by transparency).
-                        if block == BlockType::Air || block == BlockType::WoodStairs {
+                        if block == BlockType::Air || block.is_partial() {
                             continue;
                         }
 
@@ -958,7 +1135,7 @@ impl World {
                             5 => (world_x, y, world_z + 1),
                             _ => unreachable!(),
                         };
-                        let neighbor = get_block_world(nx, ny, nz);
+                        let neighbor = get_neighbor(nx, ny, nz);
 
                         // Face is only visible when the current block "should
                         // render" against its neighbor (transparent, different
@@ -980,41 +1157,23 @@ impl World {
                             None
                         };
 
-                        // Select the face color based on direction and block type.
-                        let color = match face_dir {
-                            2 => block.bottom_color(), // bottom face
-                            3 => {
-                                // Top face: grass uses biome colour.
-                                if block == BlockType::Grass {
-                                    biome.map(|b| b.grass_color()).unwrap_or([0.4, 0.8, 0.2])
-                                } else {
-                                    block.top_color()
-                                }
-                            }
-                            _ => {
-                                // Side face: leaves use biome color.
-                                if block == BlockType::Grass {
-                                    block.color()
-                                } else if block == BlockType::Leaves {
-                                    biome.map(|b| b.leaves_color()).unwrap_or([0.2, 0.6, 0.2])
-                                } else {
-                                    block.color()
-                                }
-                            }
-                        };
+                        let color = face_color(block, face_dir, biome);
 
-                        // Select the atlas texture index by face direction.
-                        let tex_index = match face_dir {
-                            2 => block.tex_bottom(),
-                            3 => block.tex_top(),
-                            _ => block.tex_side(),
+                        // Select the atlas texture index by face direction;
+                        // logs also depend on their stored trunk axis.
+                        let facing = if block.is_directional() {
+                            self.get_facing(world_x, y, world_z)
+                        } else {
+                            Facing::default()
                         };
+                        let tex_index = block.texture_for_face(face_dir as usize, facing);
 
                         let idx = (d1 * dim2_size + d2) as usize;
                         mask[idx] = FaceAttrs {
                             block,
                             color: quantize_color(color),
                             tex_index: tex_index as u8,
+                            water_level: 0,
                             is_active: true,
                         };
                     }
@@ -1064,8 +1223,11 @@ impl World {
                             }
                         }
 
-                        let _block = face.block;
-                        let (target_verts, target_inds) = (&mut vertices, &mut indices);
+                        let (target_verts, target_inds) = if face.block.is_translucent() {
+                            (&mut translucent_vertices, &mut translucent_indices)
+                        } else {
+                            (&mut vertices, &mut indices)
+                        };
 
                         let color = [
                             face.color[0] as f32 / 255.0,
@@ -1073,9 +1235,26 @@ impl World {
                             face.color[2] as f32 / 255.0,
                         ];
                         let tex_index = face.tex_index as f32;
-                        let roughness = 1.0;
-                        let metallic = 0.0;
 
+                        // Water surfaces only come from top faces and sit at
+                        // their fill level rather than the top of the block.
+                        if face.block == BlockType::Water {
+                            let surface = face.water_level as f32 / WATER_MAX_LEVEL as f32;
+                            add_water_surface_quad(
+                                &mut water_vertices,
+                                &mut water_indices,
+                                [
+                                    (base_x + d1) as f32,
+                                    (base_y + slice) as f32 + surface,
+                                    (base_z + d2) as f32,
+                                ],
+                                [height as f32, width as f32],
+                                color,
+                                tex_index,
+                            );
+                            d2 += width;
+                            continue;
+                        }
                         // Convert (slice, d1, d2, width, height) back to world-
                         // space corner coordinates for the merged quad.
                         let (x0, y0, z0, x1, y1, z1) = match face_dir {
@@ -1118,102 +1297,16 @@ impl World {
                             _ => unreachable!(),
                         };
 
-                        // Emit the merged quad with outward-facing winding.
-                        // `add_greedy_quad` takes explicit width/height so the
-                        // UV coordinates tile correctly across the merged surface.
-                        match face_dir {
-                            0 => add_greedy_quad(
-                                target_verts,
-                                target_inds,
-                                [x0, y0, z0],
-                                [x0, y0, z1],
-                                [x0, y1, z1],
-                                [x0, y1, z0],
-                                [-1.0, 0.0, 0.0],
-                                color,
-                                tex_index,
-                                roughness,
-                                metallic,
-                                width as f32,
-                                height as f32,
-                            ),
-                            1 => add_greedy_quad(
-                                target_verts,
-                                target_inds,
-                                [x1, y0, z1],
-                                [x1, y0, z0],
-                                [x1, y1, z0],
-                                [x1, y1, z1],
-                                [1.0, 0.0, 0.0],
-                                color,
-                                tex_index,
-                                roughness,
-                                metallic,
-                                width as f32,
-                                height as f32,
-                            ),
-                            2 => add_greedy_quad(
-                                target_verts,
-                                target_inds,
-                                [x0, y0, z1],
-                                [x0, y0, z0],
-                                [x1, y0, z0],
-                                [x1, y0, z1],
-                                [0.0, -1.0, 0.0],
-                                color,
-                                tex_index,
-                                roughness,
-                                metallic,
-                                width as f32,
-                                height as f32,
-                            ),
-                            3 => add_greedy_quad(
-                                target_verts,
-                                target_inds,
-                                [x0, y1, z0],
-                                [x0, y1, z1],
-                                [x1, y1, z1],
-                                [x1, y1, z0],
-                                [0.0, 1.0, 0.0],
-                                color,
-                                tex_index,
-                                roughness,
-                                metallic,
-                                width as f32,
-                                height as f32,
-                            ),
-                            4 => add_greedy_quad(
-                                target_verts,
-                                target_inds,
-                                [x1, y0, z0],
-                                [x0, y0, z0],
-                                [x0, y1, z0],
-                                [x1, y1, z0],
-                                [0.0, 0.0, -1.0],
-                                color,
-                                tex_index,
-                                roughness,
-                                metallic,
-                                height as f32,
-                                width as f32,
-                            ),
-                            5 => add_greedy_quad(
-                                target_verts,
-                                target_inds,
-                                [x0, y0, z1],
-                                [x1, y0, z1],
-                                [x1, y1, z1],
-                                [x0, y1, z1],
-                                [0.0, 0.0, 1.0],
-                                color,
-                                tex_index,
-                                roughness,
-                                metallic,
-                                height as f32,
-                                width as f32,
-                            ),
-                            _ => {}
-                        }
+                        // Emit the merged quad with outward-facing winding;
+                        // the texture tiles once per block across it.
+                        add_block_face(
+                            target_verts,
+                            target_inds,
+                            face_dir,
+                            [[x0, y0, z0], [x1, y1, z1]],
+                            color,
+                            tex_index,
+                        );
 
                         d2 += width; // advance past the merged run
                     }
@@ -1221,6 +1314,456 @@ impl World {
             }
         }
 
-        ((vertices, indices), (water_vertices, water_indices))
+        (
+            (vertices, indices),
+            (translucent_vertices, translucent_indices),
+            (water_vertices, water_indices),
+        )
+    }
+}
+
+/// Color of the `face_dir` face of `block` (see the face direction table on
+/// [`World::build_subchunk_mesh`]).
+///
+/// Grass tops and leaves take their color from `biome`, which is only
+/// needed for those two blocks; the rest use the block's own colors.
+pub(crate) fn face_color(block: BlockType, face_dir: i32, biome: Option<Biome>) -> [f32; 3] {
+    match face_dir {
+        2 => block.bottom_color(),
+        3 if block == BlockType::Grass => biome.map(|b| b.grass_color()).unwrap_or([0.4, 0.8, 0.2]),
+        3 => block.top_color(),
+        _ if block == BlockType::Leaves => {
+            biome.map(|b| b.leaves_color()).unwrap_or([0.2, 0.6, 0.2])
+        }
+        _ => block.color(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mobs::mobs_registry::all_mobs;
+    use crate::world::gen_config::WorldPreset;
+
+    #[test]
+    fn spawn_over_air_gets_a_platform() {
+        // Chunks full of air: the spawn search still picks a spot from the
+        // terrain noise, but there is nothing to stand on.
+        let mut world = World::new_empty_with_seed(7);
+        for cx in -4..=4 {
+            for cz in -4..=4 {
+                world.chunks.insert((cx, cz), Chunk::new(cx, cz));
+            }
+        }
+
+        let (x, y, z) = world.find_safe_spawn_point();
+        let (bx, by, bz) = (x.floor() as i32, y.floor() as i32, z.floor() as i32);
+        assert_eq!(world.get_block(bx, by - 1, bz), BlockType::Grass);
+        assert_eq!(world.get_block(bx, by - 2, bz), BlockType::Stone);
+        assert_eq!(
+            world.get_block(
+                bx + SPAWN_PLATFORM_RADIUS,
+                by - 1,
+                bz - SPAWN_PLATFORM_RADIUS
+            ),
+            BlockType::Grass
+        );
+        assert_eq!(world.get_block(bx, by, bz), BlockType::Air);
+    }
+
+    #[test]
+    fn raised_sea_level_floods_low_ground() {
+        let level = SEA_LEVEL + 16;
+        let default = ChunkGenerator::new(7);
+        let flooded = ChunkGenerator::with_sea_level(7, level);
+        let water_at = |chunk: &Chunk, y: i32| {
+            let mut count = 0;
+            for lx in 0..CHUNK_SIZE {
+                for lz in 0..CHUNK_SIZE {
+                    if chunk.get_block(lx, y, lz) == BlockType::Water {
+                        count += 1;
+                    }
+                }
+            }
+            count
+        };
+
+        let (mut default_water, mut flooded_water) = (0, 0);
+        for cx in -2..=2 {
+            for cz in -2..=2 {
+                default_water += water_at(&default.generate_chunk(cx, cz), level - 1);
+                flooded_water += water_at(&flooded.generate_chunk(cx, cz), level - 1);
+            }
+        }
+
+        assert_eq!(default_water, 0);
+        assert!(flooded_water > 0);
+        assert_eq!(World::new_empty_with_sea_level(7, level).sea_level(), level);
+    }
+
+    #[test]
+    fn same_seed_generates_identical_worlds() {
+        // Chunks are compared block by block: this is what lets saves
+        // store only edited chunks and regenerate the rest on load.
+        let same_blocks = |a: &Chunk, b: &Chunk| {
+            a.subchunks
+                .iter()
+                .zip(&b.subchunks)
+                .all(|(a, b)| a.blocks == b.blocks)
+        };
+        // The ring `new_with_seed` pre-generates, at a radius that keeps the
+        // test quick in debug builds.
+        let generate = || {
+            let mut world = World::new_empty_with_seed(99);
+            world.generate_chunks_in_radius(0, 0, 2);
+            world
+        };
+        let first = generate();
+        let second = generate();
+        assert_eq!(first.chunks.len(), second.chunks.len());
+        for (pos, chunk) in &first.chunks {
+            assert!(
+                same_blocks(chunk, &second.chunks[pos]),
+                "chunk {:?} differs",
+                pos
+            );
+        }
+
+        // Chunks streamed in later come from the chunk loader's own clone
+        // of the generator and must match too.
+        let loader_generator = first.generator.clone();
+        assert!(same_blocks(
+            &first.chunks[&(2, -1)],
+            &loader_generator.generate_chunk(2, -1)
+        ));
+    }
+
+    #[test]
+    fn regenerating_restores_generated_blocks_of_unedited_chunks() {
+        let mut world = World::new_empty_with_seed(99);
+        world.generate_chunks_in_radius(0, 0, 1);
+        let surface = world.get_terrain_height(3, 4);
+        let generated = world.get_block(3, surface, 4);
+        let version = world.chunks[&(0, 0)].subchunks[0].mesh_version;
+
+        // An edit by the generator itself (as when trying out a change).
+        world.set_block(3, surface, 4, BlockType::Glass);
+        assert!(world.regenerate_chunk(0, 0, false));
+        assert_eq!(world.get_block(3, surface, 4), generated);
+        let subchunk = &world.chunks[&(0, 0)].subchunks[0];
+        assert!(subchunk.mesh_dirty && subchunk.mesh_version > version);
+
+        // Player edits survive unless forced.
+        world.set_block_player(3, surface, 4, BlockType::Glass);
+        assert!(!world.regenerate_chunk(0, 0, false));
+        assert_eq!(world.get_block(3, surface, 4), BlockType::Glass);
+        assert!(world.regenerate_chunk(0, 0, true));
+        assert_eq!(world.get_block(3, surface, 4), generated);
+        assert!(!world.chunks[&(0, 0)].player_modified);
+
+        assert!(!world.regenerate_chunk(5, 5, true));
+    }
+
+    #[test]
+    fn superflat_preset_generates_a_grass_plane() {
+        let generator = ChunkGenerator::with_config(7, SEA_LEVEL, WorldPreset::Superflat.config());
+        for (cx, cz) in [(0, 0), (-3, 5)] {
+            let chunk = generator.generate_chunk(cx, cz);
+            for lx in 0..CHUNK_SIZE {
+                for lz in 0..CHUNK_SIZE {
+                    assert_eq!(chunk.get_block(lx, 0, lz), BlockType::Bedrock);
+                    assert_eq!(chunk.get_block(lx, SEA_LEVEL - 1, lz), BlockType::Dirt);
+                    assert_eq!(chunk.get_block(lx, SEA_LEVEL, lz), BlockType::Grass);
+                    assert_eq!(chunk.get_block(lx, SEA_LEVEL + 1, lz), BlockType::Air);
+                }
+            }
+        }
+        assert_eq!(generator.get_terrain_height_pub(100, -40), SEA_LEVEL + 1);
+    }
+
+    #[test]
+    fn gen_config_shapes_terrain_and_toggles_caves() {
+        let solid_blocks = |chunk: &Chunk| {
+            let mut blocks = Vec::new();
+            for lx in 0..CHUNK_SIZE {
+                for lz in 0..CHUNK_SIZE {
+                    for y in 0..WORLD_HEIGHT {
+                        if chunk.get_block(lx, y, lz).is_solid() {
+                            blocks.push((lx, y, lz));
+                        }
+                    }
+                }
+            }
+            blocks
+        };
+        // Highest block over a 3×3 chunk area.
+        let top = |config: WorldGenConfig| {
+            let generator = ChunkGenerator::with_config(7, SEA_LEVEL, config);
+            let mut top = 0;
+            for cx in -1..=1 {
+                for cz in -1..=1 {
+                    let chunk = generator.generate_chunk(cx, cz);
+                    for (_, y, _) in solid_blocks(&chunk) {
+                        top = top.max(y);
+                    }
+                }
+            }
+            top
+        };
+
+        let default_top = top(WorldGenConfig::default());
+        assert!(top(WorldPreset::Flat.config()) < default_top);
+        assert!(top(WorldPreset::Amplified.config()) > default_top);
+
+        // Chunk (0, -40) of seed 7 has a cave under it.
+        let caveless = ChunkGenerator::with_config(
+            7,
+            SEA_LEVEL,
+            WorldGenConfig {
+                caves: false,
+                ..WorldGenConfig::default()
+            },
+        );
+        let with_caves = solid_blocks(&ChunkGenerator::new(7).generate_chunk(0, -40)).len();
+        assert!(solid_blocks(&caveless.generate_chunk(0, -40)).len() > with_caves);
+    }
+
+    #[test]
+    fn heightmap_follows_placed_and_removed_blocks() {
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((-1, 0), Chunk::new(-1, 0));
+        world.set_block(-3, 10, 4, BlockType::Stone);
+        assert_eq!(world.opaque_height(-3, 4), Some(10));
+        assert!(world.is_sky_exposed(-3, 10, 4));
+
+        // A block above raises it and shades everything below ...
+        world.set_block_player(-3, 20, 4, BlockType::Dirt);
+        assert_eq!(world.opaque_height(-3, 4), Some(20));
+        assert!(!world.is_sky_exposed(-3, 10, 4));
+        assert!(!world.is_sky_exposed(-3, 15, 4));
+
+        // ... but not through glass, and removing it drops back down.
+        world.set_block_player(-3, 30, 4, BlockType::Glass);
+        assert_eq!(world.opaque_height(-3, 4), Some(20));
+        world.set_block_player(-3, 20, 4, BlockType::Air);
+        assert_eq!(world.opaque_height(-3, 4), Some(10));
+        assert!(world.is_sky_exposed(-3, 10, 4));
+
+        world.set_block(-3, 10, 4, BlockType::Air);
+        assert_eq!(world.opaque_height(-3, 4), None);
+        assert!(world.is_sky_exposed(-3, 0, 4));
+    }
+
+    #[test]
+    fn covered_grass_dies_and_exposed_dirt_grows_grass() {
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        world.set_block(4, 4, 4, BlockType::Grass);
+        world.set_block(4, 5, 4, BlockType::Stone);
+        world.set_block(10, 4, 10, BlockType::Grass);
+        world.set_block(11, 4, 10, BlockType::Dirt);
+
+        let mut ticks = 0;
+        while world.get_block(4, 4, 4) != BlockType::Dirt
+            || world.get_block(11, 4, 10) != BlockType::Grass
+        {
+            world.tick_random_blocks(0, 0, 0);
+            ticks += 1;
+            assert!(ticks < 1_000_000, "no change after {ticks} ticks");
+        }
+
+        // Dirt under cover never turns to grass.
+        world.set_block(11, 4, 10, BlockType::Dirt);
+        world.set_block(11, 8, 10, BlockType::Stone);
+        for _ in 0..20_000 {
+            world.tick_random_blocks(0, 0, 0);
+        }
+        assert_eq!(world.get_block(11, 4, 10), BlockType::Dirt);
+    }
+
+    #[test]
+    fn snow_only_covers_exposed_tops_in_the_cold() {
+        let generator = ChunkGenerator::new(7);
+        let (cx, cz) = (-64..64)
+            .flat_map(|cx| (-64..64).map(move |cz| (cx, cz)))
+            .find(|&(cx, cz)| {
+                generator.get_biome(cx * CHUNK_SIZE + 8, cz * CHUNK_SIZE + 8) == Biome::Tundra
+            })
+            .expect("no tundra near the origin");
+        let snow_in = |chunk: &Chunk| {
+            let mut snow = Vec::new();
+            for lx in 0..CHUNK_SIZE {
+                for lz in 0..CHUNK_SIZE {
+                    for y in 0..WORLD_HEIGHT {
+                        if chunk.get_block(lx, y, lz) == BlockType::SnowLayer {
+                            snow.push((lx, y, lz));
+                        }
+                    }
+                }
+            }
+            snow
+        };
+
+        let chunk = generator.generate_chunk(cx, cz);
+        let snow = snow_in(&chunk);
+        assert!(!snow.is_empty());
+        for &(lx, y, lz) in &snow {
+            assert!(chunk.get_block(lx, y - 1, lz).is_solid_opaque());
+            assert_eq!(chunk.surface_block(lx, lz), Some((y, BlockType::SnowLayer)));
+        }
+        assert_eq!(
+            snow_in(&ChunkGenerator::new(7).generate_chunk(cx, cz)),
+            snow
+        );
+    }
+
+    #[test]
+    fn snow_layers_side_by_side_share_no_faces() {
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        world.set_block(0, 1, 0, BlockType::SnowLayer);
+        world.set_block(1, 1, 0, BlockType::SnowLayer);
+
+        let ((vertices, _), _, _) = world.build_subchunk_mesh(0, 0, 0);
+        // Two tops, two bottoms and the six outer sides.
+        assert_eq!(vertices.len(), 10 * 4);
+        assert!(vertices.iter().all(|v| v.position[1] <= 1.125));
+    }
+
+    #[test]
+    fn open_water_surface_is_merged_and_tagged_for_waves() {
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        for x in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                world.set_block(x, 9, z, BlockType::Stone);
+                world.set_block(x, 10, z, BlockType::Water);
+            }
+        }
+        let is_surface = |v: &Vertex| v.packed & 0x7 == Vertex::WATER_SURFACE_NORMAL as u32;
+
+        let (_, _, (water, _)) = world.build_subchunk_mesh(0, 0, 0);
+        let surface: Vec<_> = water.iter().filter(|v| is_surface(v)).collect();
+        assert_eq!(surface.len(), 4);
+        assert!(surface.iter().all(|v| v.position[1] == 11.0));
+
+        // A block on top leaves a still, untagged top under it.
+        world.set_block(0, 11, 0, BlockType::Stone);
+        world.set_water_level(0, 10, 0, WATER_MAX_LEVEL / 2);
+        let (_, _, (water, _)) = world.build_subchunk_mesh(0, 0, 0);
+        assert!(
+            water
+                .iter()
+                .filter(|v| is_surface(v))
+                .all(|v| v.position[0] > 0.0 || v.position[2] > 0.0)
+        );
+        assert!(
+            water
+                .iter()
+                .any(|v| v.packed & 0x7 == 3 && v.position[1] == 10.5)
+        );
+    }
+
+    #[test]
+    fn see_through_blocks_are_meshed_apart_from_opaque_ones() {
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        world.set_block(0, 1, 0, BlockType::Stone);
+        world.set_block(4, 1, 0, BlockType::Glass);
+        // Leaves are only cut out, so they stay with the opaque blocks.
+        world.set_block(0, 1, 8, BlockType::Leaves);
+
+        let ((opaque, _), (translucent, _), _) = world.build_subchunk_mesh(0, 0, 0);
+        assert_eq!(opaque.len(), 2 * 6 * 4);
+        assert!(opaque.iter().all(|v| v.position[0] <= 1.0));
+        assert_eq!(translucent.len(), 6 * 4);
+        assert!(translucent.iter().all(|v| v.position[0] >= 4.0));
+    }
+
+    #[test]
+    fn logs_show_end_grain_along_their_axis() {
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        world.set_block(0, 1, 0, BlockType::Wood);
+        world.set_facing(0, 1, 0, Facing::for_log_normal((1, 0, 0)));
+
+        let ((vertices, _), _, _) = world.build_subchunk_mesh(0, 0, 0);
+        assert_eq!(vertices.len(), 6 * 4);
+        for v in &vertices {
+            let normal = v.packed & 0x7;
+            let tex = ((v.packed >> 3) & 0xFF) as f32;
+            let expected = if normal <= 1 {
+                TEX_WOOD_TOP
+            } else {
+                TEX_WOOD_SIDE
+            };
+            assert_eq!(tex, expected, "face {}", normal);
+        }
+    }
+
+    #[test]
+    fn glass_keeps_outer_faces_and_culls_shared_ones() {
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        world.set_block(0, 1, 0, BlockType::Glass);
+
+        let (_, (vertices, _), _) = world.build_subchunk_mesh(0, 0, 0);
+        assert_eq!(vertices.len(), 6 * 4);
+
+        // A second glass block on +X: the pair merges into one quad per
+        // side, and nothing is drawn on the plane between them.
+        world.set_block(1, 1, 0, BlockType::Glass);
+        let (_, (vertices, _), _) = world.build_subchunk_mesh(0, 0, 0);
+        assert_eq!(vertices.len(), 6 * 4);
+        let is_x_face = |v: &&Vertex| v.packed & 0x7 <= 1;
+        assert!(
+            vertices
+                .iter()
+                .filter(is_x_face)
+                .all(|v| v.position[0] != 1.0)
+        );
+    }
+
+    #[test]
+    fn blocks_cannot_be_placed_into_players_or_mobs() {
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+
+        // A player standing on the ground at (0.5, 64, 0.5) fills the two
+        // cells above it, and reaches into the neighbour it leans over.
+        let player = Vec3::new(0.5, 64.0, 0.5);
+        assert!(!world.can_place_block(0, 64, 0, &[player]));
+        assert!(!world.can_place_block(0, 65, 0, &[player]));
+        assert!(!world.can_place_block(1, 64, 0, &[Vec3::new(0.9, 64.0, 0.5)]));
+        assert!(world.can_place_block(0, 66, 0, &[player]));
+        assert!(world.can_place_block(0, 63, 0, &[player]));
+        assert!(world.can_place_block(1, 64, 0, &[player]));
+
+        // Any one player in the way is enough, and so is a mob.
+        let elsewhere = Vec3::new(8.5, 64.0, 8.5);
+        assert!(!world.can_place_block(0, 64, 0, &[elsewhere, player]));
+        world.mobs.push(MobEntity::new(all_mobs()[0], player, 0.0));
+        assert!(!world.can_place_block(0, 64, 0, &[elsewhere]));
+    }
+
+    #[test]
+    fn the_world_rests_on_unbreakable_bedrock() {
+        let generator = ChunkGenerator::new(7);
+        for (cx, cz) in [(0, 0), (4, -9)] {
+            let chunk = generator.generate_chunk(cx, cz);
+            for lx in 0..CHUNK_SIZE {
+                for lz in 0..CHUNK_SIZE {
+                    assert_eq!(chunk.get_block(lx, 0, lz), BlockType::Bedrock);
+                }
+            }
+        }
+        assert!(!BlockType::Bedrock.break_time().is_finite());
+
+        // Nothing can be built under the floor or over the ceiling.
+        let mut world = World::new_empty_with_seed(1);
+        world.chunks.insert((0, 0), Chunk::new(0, 0));
+        assert!(!world.can_place_block(0, -1, 0, &[]));
+        assert!(!world.can_place_block(0, WORLD_HEIGHT, 0, &[]));
+        assert!(world.can_place_block(0, 1, 0, &[]));
     }
 }
@@ -0,0 +1,74 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use glam::Vec3;
+
+/// Angular speed of the sun, in radians per second of the day-cycle clock.
+const DAY_CYCLE_SPEED: f32 = 0.005;
+
+/// Length of a full day in seconds of the day-cycle clock (≈21 minutes).
+pub const DAY_LENGTH: f32 = TAU / DAY_CYCLE_SPEED;
+
+/// Angle of the sun above the horizon, in radians, at which a skipped night
+/// ends.
+const MORNING_SUN_ANGLE: f32 = 0.2;
+
+/// Unit vector pointing at the sun when the day-cycle clock reads `time`
+/// seconds.
+///
+/// The sun orbits in the Y-Z plane at [`DAY_CYCLE_SPEED`], so a full day
+/// takes [`DAY_LENGTH`] seconds.  Y is +1 at noon and −1 at midnight; the
+/// clock starts at noon.
+pub fn sun_direction(time: f32) -> Vec3 {
+    // Offset by π/2 so the sun starts at noon (Y = +1) rather than the
+    // horizon.
+    let sun_angle = time * DAY_CYCLE_SPEED + FRAC_PI_2;
+    Vec3::new(0.0, sun_angle.sin(), sun_angle.cos())
+}
+
+/// Returns `true` while the sun is below the horizon.
+pub fn is_night(time: f32) -> bool {
+    sun_direction(time).y < 0.0
+}
+
+/// First clock reading after `time` at which the sun stands
+/// [`MORNING_SUN_ANGLE`] above the eastern horizon.
+pub fn next_morning(time: f32) -> f32 {
+    // `sun_direction` puts the sun at angle `time / DAY_LENGTH * TAU + π/2`,
+    // which is `MORNING_SUN_ANGLE` once per day.
+    let first = (MORNING_SUN_ANGLE - FRAC_PI_2) / TAU * DAY_LENGTH;
+    first + ((time - first) / DAY_LENGTH).ceil() * DAY_LENGTH
+}
+
+/// Returns the difference `to - from` between two clock readings, taken the
+/// short way round the day, so it lies in `-DAY_LENGTH / 2..DAY_LENGTH / 2`.
+///
+/// Readings a whole number of days apart show the same sky, so a clock
+/// kept within one day compares equal to one that has run for many.
+pub fn time_difference(from: f32, to: f32) -> f32 {
+    (to - from + DAY_LENGTH / 2.0).rem_euclid(DAY_LENGTH) - DAY_LENGTH / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mornings_follow_nights_whatever_the_day() {
+        // The clock starts at noon, so half a day later is midnight.
+        let midnight = DAY_LENGTH * 0.5;
+        assert!(!is_night(0.0) && is_night(midnight));
+        let morning = next_morning(midnight);
+        assert!(morning > midnight && morning - midnight < DAY_LENGTH * 0.5);
+        assert!(!is_night(morning));
+        assert!((sun_direction(morning).y - MORNING_SUN_ANGLE.sin()).abs() < 1e-3);
+        // Several days in, the skip still lands on the same day's morning.
+        assert!(
+            (next_morning(midnight + 3.0 * DAY_LENGTH) - morning - 3.0 * DAY_LENGTH).abs() < 0.1
+        );
+
+        // Differences go the short way round the day.
+        assert!((time_difference(10.0, 3.0 * DAY_LENGTH + 12.0) - 2.0).abs() < 1e-2);
+        assert!((time_difference(DAY_LENGTH - 1.0, 1.0) - 2.0).abs() < 1e-2);
+        assert!((time_difference(1.0, DAY_LENGTH - 1.0) + 2.0).abs() < 1e-2);
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::VecDeque;
+
+use glam::Vec3;
+use rustc_hash::FxHashSet;
+
+use crate::constants::{CHUNK_SIZE, WATER_MAX_LEVEL, WATER_UPDATES_PER_TICK};
+use crate::core::block::BlockType;
+use crate::world::World;
+
+/// Horizontal neighbours water spreads to, in the order they are tried.
+const HORIZONTAL: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// Blocks whose water may need to move, in the order they were scheduled.
+///
+/// Each block is queued at most once; scheduling it again while it is still
+/// waiting is a no-op, which keeps a flood from queuing the same blocks over
+/// and over.
+#[derive(Default)]
+pub struct WaterUpdateQueue {
+    pending: VecDeque<(i32, i32, i32)>,
+    queued: FxHashSet<(i32, i32, i32)>,
+}
+
+impl WaterUpdateQueue {
+    /// Queues the block at `(x, y, z)` unless it is already waiting.
+    pub fn push(&mut self, x: i32, y: i32, z: i32) {
+        if self.queued.insert((x, y, z)) {
+            self.pending.push_back((x, y, z));
+        }
+    }
+
+    /// Removes and returns the oldest queued block.
+    pub fn pop(&mut self) -> Option<(i32, i32, i32)> {
+        let pos = self.pending.pop_front()?;
+        self.queued.remove(&pos);
+        Some(pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.queued.clear();
+    }
+}
+
+impl World {
+    /// Returns the fill level of the block at `(x, y, z)`: `0` if it is not
+    /// water (or not loaded), otherwise `1..=WATER_MAX_LEVEL`.
+    pub fn water_level(&self, x: i32, y: i32, z: i32) -> u8 {
+        let cx = x.div_euclid(CHUNK_SIZE);
+        let cz = z.div_euclid(CHUNK_SIZE);
+        match self.chunks.get(&(cx, cz)) {
+            Some(chunk) => {
+                chunk.get_water_level(x.rem_euclid(CHUNK_SIZE), y, z.rem_euclid(CHUNK_SIZE))
+            }
+            None => 0,
+        }
+    }
+
+    /// Makes `(x, y, z)` water with the given fill level, or air when `level`
+    /// is `0`, as a player-visible change: the chunk is saved and the
+    /// affected meshes are rebuilt.
+    pub fn set_water_level(&mut self, x: i32, y: i32, z: i32, level: u8) {
+        if level == 0 {
+            self.set_block_player(x, y, z, BlockType::Air);
+        } else {
+            if self.get_block(x, y, z) != BlockType::Water {
+                self.set_block_player(x, y, z, BlockType::Water);
+            }
+            let cx = x.div_euclid(CHUNK_SIZE);
+            let cz = z.div_euclid(CHUNK_SIZE);
+            if let Some(chunk) = self.chunks.get_mut(&(cx, cz)) {
+                chunk.set_water_level(x.rem_euclid(CHUNK_SIZE), y, z.rem_euclid(CHUNK_SIZE), level);
+                chunk.player_modified = true;
+            }
+        }
+        self.mark_block_dirty(x, y, z);
+    }
+
+    /// Height of the water surface within the block at `(x, y, z)`, from
+    /// `0.0` (no water) to `1.0`.
+    ///
+    /// Water with more water on top always fills its block, so a falling
+    /// column renders and swims as one body.
+    pub fn water_surface_height(&self, x: i32, y: i32, z: i32) -> f32 {
+        let level = self.water_level(x, y, z);
+        if level > 0 && self.get_block(x, y + 1, z) == BlockType::Water {
+            1.0
+        } else {
+            level as f32 / WATER_MAX_LEVEL as f32
+        }
+    }
+
+    /// Returns `true` if the world-space point `pos` is below the water
+    /// surface of the block containing it.
+    pub fn is_point_in_water(&self, pos: Vec3) -> bool {
+        let (x, y, z) = (
+            pos.x.floor() as i32,
+            pos.y.floor() as i32,
+            pos.z.floor() as i32,
+        );
+        pos.y - (y as f32) < self.water_surface_height(x, y, z)
+    }
+
+    /// Queues `(x, y, z)` and its six neighbours for a water update after the
+    /// block there changed.
+    ///
+    /// Water only moves when something around it changes, so every edit
+    /// that could open or close a path for water must call this.
+    pub fn schedule_water_update(&mut self, x: i32, y: i32, z: i32) {
+        self.water_updates.push(x, y, z);
+        for (dx, dy, dz) in [
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ] {
+            if self.get_block(x + dx, y + dy, z + dz) == BlockType::Water {
+                self.water_updates.push(x + dx, y + dy, z + dz);
+            }
+        }
+    }
+
+    /// Runs one water tick: moves the water of up to
+    /// [`WATER_UPDATES_PER_TICK`] queued blocks.
+    ///
+    /// Blocks scheduled during the tick wait for the next one, so water
+    /// advances one block per tick and a large flood is spread over many
+    /// frames instead of stalling one.
+    ///
+    /// # Returns
+    /// The number of blocks processed.
+    pub fn tick_water(&mut self) -> usize {
+        let count = self.water_updates.len().min(WATER_UPDATES_PER_TICK);
+        for _ in 0..count {
+            if let Some((x, y, z)) = self.water_updates.pop() {
+                self.flow_water(x, y, z);
+            }
+        }
+        count
+    }
+
+    /// How much more water `(x, y, z)` can take, or `None` if water cannot
+    /// enter it at all (a solid or non-air block, or an unloaded chunk).
+    fn water_room(&self, x: i32, y: i32, z: i32) -> Option<u8> {
+        if !self.is_block_loaded(x, y, z) {
+            return None;
+        }
+        match self.get_block(x, y, z) {
+            BlockType::Air => Some(WATER_MAX_LEVEL),
+            BlockType::Water => Some(WATER_MAX_LEVEL - self.water_level(x, y, z)),
+            _ => None,
+        }
+    }
+
+    /// Moves the water at `(x, y, z)`, conserving its volume.
+    ///
+    /// Water first falls into the block below as far as it has room.
+    /// Whatever cannot fall spreads sideways one level at a time to
+    /// neighbours at least two levels lower, so a level-1 puddle on flat
+    /// ground stays put.  Every block that changes is rescheduled together
+    /// with its neighbours.
+    fn flow_water(&mut self, x: i32, y: i32, z: i32) {
+        let mut level = self.water_level(x, y, z);
+        if level == 0 {
+            return;
+        }
+
+        let below_room = self.water_room(x, y - 1, z);
+        if let Some(room) = below_room.filter(|&room| room > 0) {
+            let moved = room.min(level);
+            let below = self.water_level(x, y - 1, z);
+            self.set_water_level(x, y - 1, z, below + moved);
+            self.set_water_level(x, y, z, level - moved);
+            self.schedule_water_update(x, y - 1, z);
+            self.schedule_water_update(x, y, z);
+            return;
+        }
+
+        let start = level;
+        for (dx, dz) in HORIZONTAL {
+            if level <= 1 {
+                break;
+            }
+            let (nx, nz) = (x + dx, z + dz);
+            if self.water_room(nx, y, nz).is_none() {
+                continue;
+            }
+            let neighbour = self.water_level(nx, y, nz);
+            if neighbour + 1 < level {
+                self.set_water_level(nx, y, nz, neighbour + 1);
+                self.schedule_water_update(nx, y, nz);
+                level -= 1;
+            }
+        }
+        if level != start {
+            self.set_water_level(x, y, z, level);
+            self.schedule_water_update(x, y, z);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::chunk::Chunk;
+
+    /// A 3×3-chunk world of air with a stone floor at y = 9.
+    fn floored_world() -> World {
+        let mut world = World::new_empty_with_seed(1);
+        for cx in -1..=1 {
+            for cz in -1..=1 {
+                world.chunks.insert((cx, cz), Chunk::new(cx, cz));
+            }
+        }
+        for x in -CHUNK_SIZE..2 * CHUNK_SIZE {
+            for z in -CHUNK_SIZE..2 * CHUNK_SIZE {
+                world.set_block(x, 9, z, BlockType::Stone);
+            }
+        }
+        world
+    }
+
+    fn total_water(world: &World) -> u32 {
+        let mut total = 0;
+        for x in -CHUNK_SIZE..2 * CHUNK_SIZE {
+            for y in 0..20 {
+                for z in -CHUNK_SIZE..2 * CHUNK_SIZE {
+                    total += world.water_level(x, y, z) as u32;
+                }
+            }
+        }
+        total
+    }
+
+    #[test]
+    fn water_falls_and_spreads_without_losing_volume() {
+        let mut world = floored_world();
+        world.set_water_level(4, 15, 4, WATER_MAX_LEVEL);
+        world.schedule_water_update(4, 15, 4);
+
+        for _ in 0..200 {
+            world.tick_water();
+        }
+
+        assert!(world.water_updates.is_empty());
+        assert_eq!(world.get_block(4, 15, 4), BlockType::Air);
+        assert!(world.water_level(4, 10, 4) > 0);
+        assert!(world.water_level(5, 10, 4) > 0);
+        assert_eq!(total_water(&world), WATER_MAX_LEVEL as u32);
+        // Settled: no two neighbours differ by more than one level.
+        for (dx, dz) in HORIZONTAL {
+            let diff =
+                world.water_level(4, 10, 4) as i32 - world.water_level(4 + dx, 10, 4 + dz) as i32;
+            assert!(diff.abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn partial_water_only_submerges_below_its_surface() {
+        let mut world = floored_world();
+        world.set_water_level(0, 10, 0, WATER_MAX_LEVEL / 2);
+
+        assert!(world.is_point_in_water(Vec3::new(0.5, 10.25, 0.5)));
+        assert!(!world.is_point_in_water(Vec3::new(0.5, 10.75, 0.5)));
+    }
+}